@@ -0,0 +1,252 @@
+//! Audit log CLI command: query and export the shared-memory audit trail.
+//!
+//! `bnity audit` reads the memory audit log (share, revoke, merge, ... events)
+//! with optional filters and pagination. Without `--format` it prints a table
+//! (or the usual JSON envelope with `--json`); `--format json|csv` writes a
+//! plain export suitable for redirecting to a file.
+
+use anyhow::Result;
+use clap::ValueEnum;
+use comfy_table::{Cell, Color};
+use console::style;
+
+use boternity_core::repository::workflow::WorkflowRunFilter;
+use boternity_infra::sqlite::audit::AuditLogFilter;
+use boternity_types::memory::{AuditAction, MemoryAuditEntry};
+
+use crate::cli::color::new_table;
+use crate::cli::output::print_json;
+use crate::state::AppState;
+
+/// Export format for `bnity audit --format`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum AuditFormat {
+    /// A JSON array of audit entries.
+    Json,
+    /// CSV with a header row.
+    Csv,
+}
+
+/// Columns of the CSV export, in order.
+const CSV_HEADER: &str = "id,created_at,bot_id,memory_id,action,actor,details";
+
+/// Query the audit log and print or export the matching entries.
+#[allow(clippy::too_many_arguments)]
+pub async fn handle_audit(
+    state: &AppState,
+    since: Option<&str>,
+    actor: Option<String>,
+    action: Option<&str>,
+    bot: Option<&str>,
+    limit: u32,
+    offset: u32,
+    format: Option<AuditFormat>,
+    json: bool,
+) -> Result<()> {
+    let bot_id = match bot {
+        Some(slug) => Some(state.bot_service.get_bot_by_slug(slug).await?.id.0),
+        None => None,
+    };
+    let filter = AuditLogFilter {
+        since: since
+            .map(|s| WorkflowRunFilter::parse_since(s, chrono::Utc::now()))
+            .transpose()
+            .map_err(|e| anyhow::anyhow!(e))?,
+        actor,
+        action: action
+            .map(str::parse::<AuditAction>)
+            .transpose()
+            .map_err(|e| anyhow::anyhow!(e))?,
+        bot_id,
+        limit: Some(i64::from(limit)),
+        offset: Some(i64::from(offset)),
+    };
+
+    let entries = state.audit_log.query(&filter).await?;
+
+    match format {
+        Some(AuditFormat::Json) => println!("{}", entries_to_json(&entries)?),
+        Some(AuditFormat::Csv) => print!("{}", entries_to_csv(&entries)),
+        None if json => print_json(&entries)?,
+        None => print_table(&entries, offset),
+    }
+
+    Ok(())
+}
+
+/// Render entries as a pretty-printed JSON array.
+fn entries_to_json(entries: &[MemoryAuditEntry]) -> Result<String> {
+    Ok(serde_json::to_string_pretty(entries)?)
+}
+
+/// Render entries as CSV (RFC 4180 quoting), header row first.
+fn entries_to_csv(entries: &[MemoryAuditEntry]) -> String {
+    let mut out = String::from(CSV_HEADER);
+    out.push('\n');
+    for entry in entries {
+        let fields = [
+            entry.id.to_string(),
+            entry.created_at.to_rfc3339(),
+            entry.bot_id.to_string(),
+            entry.memory_id.to_string(),
+            entry.action.to_string(),
+            entry.actor.clone(),
+            entry.details.clone().unwrap_or_default(),
+        ];
+        let row: Vec<String> = fields.iter().map(String::as_str).map(csv_field).collect();
+        out.push_str(&row.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+/// Quote a CSV field if it contains a delimiter, quote, or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_owned()
+    }
+}
+
+fn print_table(entries: &[MemoryAuditEntry], offset: u32) {
+    if entries.is_empty() {
+        println!();
+        println!("  {} No audit entries match.", style("i").blue().bold());
+        println!();
+        return;
+    }
+
+    let mut table = new_table();
+    table.set_header(vec![
+        Cell::new("Time").fg(Color::White),
+        Cell::new("Action").fg(Color::White),
+        Cell::new("Actor").fg(Color::White),
+        Cell::new("Memory").fg(Color::White),
+        Cell::new("Details").fg(Color::White),
+    ]);
+
+    for entry in entries {
+        let memory_id = entry.memory_id.to_string();
+        table.add_row(vec![
+            Cell::new(entry.created_at.format("%Y-%m-%d %H:%M:%S").to_string()).fg(Color::DarkGrey),
+            Cell::new(entry.action.to_string()).fg(Color::Cyan),
+            Cell::new(&entry.actor),
+            Cell::new(&memory_id[..8]),
+            Cell::new(entry.details.as_deref().unwrap_or("-")),
+        ]);
+    }
+
+    println!();
+    println!("{table}");
+    println!();
+    println!(
+        "  {} entr{} (from #{})",
+        style(entries.len()).bold(),
+        if entries.len() == 1 { "y" } else { "ies" },
+        offset + 1
+    );
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use boternity_infra::sqlite::audit::SqliteAuditLog;
+    use boternity_infra::sqlite::pool::DatabasePool;
+    use chrono::{Duration, Utc};
+    use uuid::Uuid;
+
+    async fn audit_log_with_rows(dir: &tempfile::TempDir) -> SqliteAuditLog {
+        let url = format!("sqlite://{}?mode=rwc", dir.path().join("test.db").display());
+        let pool = DatabasePool::new(&url).await.unwrap();
+        let bot_id = Uuid::now_v7();
+        sqlx::query(
+            "INSERT INTO bots (id, slug, name, description, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(bot_id.to_string())
+        .bind("audit-bot")
+        .bind("Audit Bot")
+        .bind("")
+        .bind(Utc::now().to_rfc3339())
+        .bind(Utc::now().to_rfc3339())
+        .execute(&pool.writer)
+        .await
+        .unwrap();
+
+        let audit_log = SqliteAuditLog::new(pool);
+        let start = Utc::now() - Duration::days(2);
+        for (hours, action, actor, details) in [
+            (0, AuditAction::Share, "user", None),
+            (
+                24,
+                AuditAction::Merge,
+                "system",
+                Some(r#"{"from":"a","into":"b"}"#),
+            ),
+            (30, AuditAction::Revoke, "user", Some("reason: \"stale\"")),
+        ] {
+            audit_log
+                .log(&MemoryAuditEntry {
+                    id: Uuid::now_v7(),
+                    bot_id,
+                    memory_id: Uuid::now_v7(),
+                    action,
+                    actor: actor.to_string(),
+                    details: details.map(str::to_string),
+                    created_at: start + Duration::hours(hours),
+                })
+                .await
+                .unwrap();
+        }
+        audit_log
+    }
+
+    #[tokio::test]
+    async fn test_filtered_csv_export() {
+        let dir = tempfile::tempdir().unwrap();
+        let audit_log = audit_log_with_rows(&dir).await;
+
+        let filter = AuditLogFilter {
+            actor: Some("user".to_string()),
+            ..Default::default()
+        };
+        let csv = entries_to_csv(&audit_log.query(&filter).await.unwrap());
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], CSV_HEADER);
+        assert!(lines[1].contains(",revoke,user,\"reason: \"\"stale\"\"\""));
+        assert!(lines[2].ends_with(",share,user,"));
+        assert!(!csv.contains("merge"));
+    }
+
+    #[tokio::test]
+    async fn test_filtered_json_export() {
+        let dir = tempfile::tempdir().unwrap();
+        let audit_log = audit_log_with_rows(&dir).await;
+
+        let filter = AuditLogFilter {
+            since: Some(WorkflowRunFilter::parse_since("36h", Utc::now()).unwrap()),
+            action: Some(AuditAction::Merge),
+            ..Default::default()
+        };
+        let json = entries_to_json(&audit_log.query(&filter).await.unwrap()).unwrap();
+        let exported: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        let rows = exported.as_array().unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0]["action"], "merge");
+        assert_eq!(rows[0]["actor"], "system");
+        assert_eq!(rows[0]["details"], r#"{"from":"a","into":"b"}"#);
+
+        let filter = AuditLogFilter {
+            limit: Some(1),
+            offset: Some(1),
+            ..Default::default()
+        };
+        let page = audit_log.query(&filter).await.unwrap();
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].action, AuditAction::Merge);
+    }
+}
@@ -1,14 +1,65 @@
-//! Bot lifecycle CLI commands: create, list, show, delete, clone.
+//! Bot lifecycle CLI commands: create, list, show, delete, clone, status.
 
-use anyhow::Result;
-use comfy_table::{presets, Cell, Color, ContentArrangement, Table};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use clap::Subcommand;
+use comfy_table::{Cell, Color};
 use console::style;
 use dialoguer::{Confirm, Input};
-use indicatif::{ProgressBar, ProgressStyle};
 
+use boternity_core::builder::assembler::{AssemblyResult, BotAssembler};
+use boternity_core::builder::spec::{BotSpec, SecretRef, ValidatedBotSpec};
+use boternity_core::service::secret::SecretService;
+use boternity_infra::filesystem::identity::{validate_identity, IssueSeverity};
 use boternity_types::bot::{BotCategory, BotStatus, CreateBotRequest};
+use boternity_types::secret::SecretScope;
+
+use crate::cli::color::new_table;
+use crate::cli::output::print_json;
+use crate::cli::progress::{ProgressReporter, SpinnerProgress};
+use crate::state::{AppState, ConcreteBotService};
+
+/// Bot lifecycle subcommands.
+#[derive(Subcommand)]
+pub enum BotCommand {
+    /// Show or change a bot's lifecycle status and its history.
+    Status {
+        /// Bot slug.
+        slug: String,
+
+        /// New status: active, disabled, or archived.
+        #[arg(long, value_name = "STATUS")]
+        set: Option<BotStatus>,
+
+        /// Why the status is changing (recorded in the history).
+        #[arg(long, requires = "set")]
+        reason: Option<String>,
+
+        /// Allow reactivating an archived bot.
+        #[arg(long, requires = "set")]
+        restore: bool,
+    },
+
+    /// Restore a deleted bot (undo `bnity delete bot`).
+    Restore {
+        /// Bot slug.
+        slug: String,
+    },
+}
 
-use crate::state::AppState;
+/// Handle a bot lifecycle subcommand.
+pub async fn handle_bot_command(cmd: BotCommand, state: &AppState, json: bool) -> Result<()> {
+    match cmd {
+        BotCommand::Status {
+            slug,
+            set,
+            reason,
+            restore,
+        } => bot_status(state, &slug, set, reason, restore, json).await,
+        BotCommand::Restore { slug } => restore_bot(state, &slug, json).await,
+    }
+}
 
 /// Create a new bot via interactive wizard or one-shot flags.
 ///
@@ -53,14 +104,8 @@ pub async fn create_bot(
         None => None,
     };
 
-    let spinner = ProgressBar::new_spinner();
-    spinner.set_style(
-        ProgressStyle::default_spinner()
-            .template("{spinner:.cyan} {msg}")
-            .unwrap(),
-    );
-    spinner.set_message("Creating bot...");
-    spinner.enable_steady_tick(std::time::Duration::from_millis(80));
+    let progress = SpinnerProgress::new();
+    progress.start("Creating bot...");
 
     let request = CreateBotRequest {
         name: name.clone(),
@@ -71,10 +116,10 @@ pub async fn create_bot(
 
     let bot = state.bot_service.create_bot(request).await?;
 
-    spinner.finish_and_clear();
+    progress.finish();
 
     if json {
-        println!("{}", serde_json::to_string_pretty(&bot)?);
+        print_json(&bot)?;
         return Ok(());
     }
 
@@ -135,30 +180,126 @@ pub async fn create_bot(
     Ok(())
 }
 
+/// Create a bot from a declarative TOML spec, without the Forge conversation.
+///
+/// The whole spec is checked first -- required fields, IDENTITY.md values,
+/// and every secret reference -- and nothing is written unless all of it
+/// is valid.
+///
+/// ```bash
+/// bnity create bot --spec luna.toml
+/// ```
+pub async fn create_bot_from_spec(state: &AppState, path: &Path, json: bool) -> Result<()> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read spec {}", path.display()))?;
+    let spec = BotSpec::parse(&content)?.validate()?;
+
+    let result = create_from_spec(
+        &state.bot_service,
+        &state.secret_service,
+        &spec,
+        |var| std::env::var(var).ok(),
+    )
+    .await?;
+    let bot = &result.bot;
+
+    if json {
+        print_json(bot)?;
+        return Ok(());
+    }
+
+    println!();
+    println!(
+        "  {} Bot created from {}",
+        style("✓").green().bold(),
+        style(path.display()).dim()
+    );
+    println!();
+    println!("  {}  {}", style("Name:").bold(), style(&bot.name).cyan());
+    println!("  {}  {}", style("Slug:").bold(), &bot.slug);
+    println!("  {}  {}", style("ID:").bold(), style(bot.id.to_string()).dim());
+    if !spec.secrets.is_empty() {
+        let keys: Vec<&str> = spec.secrets.iter().map(|(key, _)| key.as_str()).collect();
+        println!("  {}  {}", style("Secrets:").bold(), keys.join(", "));
+    }
+    println!();
+
+    Ok(())
+}
+
+/// Check a validated spec against the environment, then create the bot.
+///
+/// Identity errors and unresolvable secret references are all reported
+/// together before anything is written. Secrets are stored bot-scoped.
+async fn create_from_spec(
+    bot_service: &ConcreteBotService,
+    secret_service: &SecretService,
+    spec: &ValidatedBotSpec,
+    env: impl Fn(&str) -> Option<String>,
+) -> Result<AssemblyResult> {
+    let mut problems: Vec<String> = validate_identity(&spec.identity_content)
+        .into_iter()
+        .filter(|issue| issue.severity == IssueSeverity::Error)
+        .map(|issue| format!("identity.{}: {}", issue.field, issue.message))
+        .collect();
+
+    let mut secrets = Vec::with_capacity(spec.secrets.len());
+    for (key, reference) in &spec.secrets {
+        let value = match reference {
+            SecretRef::Env(var) => env(var),
+            SecretRef::Secret(name) => secret_service.get_secret(name, &SecretScope::Global).await?,
+        };
+        match value {
+            Some(value) => secrets.push((key, value)),
+            None => problems.push(format!("secrets.{key}: {reference} is not set")),
+        }
+    }
+
+    if !problems.is_empty() {
+        anyhow::bail!(
+            "invalid spec:\n{}",
+            problems
+                .iter()
+                .map(|p| format!("  - {p}"))
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+    }
+
+    let result = BotAssembler::assemble_spec(bot_service, spec).await?;
+    let scope = SecretScope::Bot(result.bot.id.clone());
+    for (key, value) in secrets {
+        secret_service.set_secret(key, &value, &scope).await?;
+    }
+    Ok(result)
+}
+
 /// List all bots in a rich colored table.
 pub async fn list_bots(
     state: &AppState,
     status: Option<String>,
     sort: &str,
+    include_deleted: bool,
     json: bool,
 ) -> Result<()> {
     use boternity_core::repository::bot::BotFilter;
 
     let status_filter = match status {
-        Some(s) => Some(s.parse::<BotStatus>().map_err(|e| anyhow::anyhow!(e))?),
+        Some(s) => Some(BotStatus::parse_lenient(s).map_err(|e| anyhow::anyhow!(e))?),
         None => None,
     };
 
     let filter = Some(BotFilter {
         status: status_filter,
         sort_by: Some(sort.to_string()),
+        include_deleted,
         ..Default::default()
     });
 
     let bots = state.bot_service.list_bots(filter).await?;
 
     if json {
-        println!("{}", serde_json::to_string_pretty(&bots)?);
+        print_json(&bots)?;
         return Ok(());
     }
 
@@ -173,9 +314,7 @@ pub async fn list_bots(
         return Ok(());
     }
 
-    let mut table = Table::new();
-    table.load_preset(presets::UTF8_FULL_CONDENSED);
-    table.set_content_arrangement(ContentArrangement::Dynamic);
+    let mut table = new_table();
 
     table.set_header(vec![
         Cell::new("Name").fg(Color::White),
@@ -190,6 +329,7 @@ pub async fn list_bots(
         let name_display = format!("{} {}", emoji, bot.name);
 
         let status_cell = match &bot.status {
+            _ if bot.deleted_at.is_some() => Cell::new("✗ deleted").fg(Color::Red),
             BotStatus::Active => Cell::new("● active").fg(Color::Green),
             BotStatus::Disabled => Cell::new("○ disabled").fg(Color::Yellow),
             BotStatus::Archived => Cell::new("◌ archived").fg(Color::DarkGrey),
@@ -233,7 +373,7 @@ pub async fn show_bot(state: &AppState, slug: &str, json: bool) -> Result<()> {
     let bot = state.bot_service.get_bot_by_slug(slug).await?;
 
     if json {
-        println!("{}", serde_json::to_string_pretty(&bot)?);
+        print_json(&bot)?;
         return Ok(());
     }
 
@@ -351,15 +491,39 @@ pub async fn show_bot(state: &AppState, slug: &str, json: bool) -> Result<()> {
     Ok(())
 }
 
-/// Delete a bot permanently with confirmation.
+/// Delete a bot. By default this is a soft delete (restorable with
+/// `bnity bot restore`); `--purge` removes the bot and all its data.
+///
+/// # Examples
+///
+/// ```bash
+/// bnity delete bot luna
+/// bnity delete bot luna --purge --force
+/// ```
 pub async fn delete_bot(
     state: &AppState,
     slug: &str,
+    purge: bool,
     force: bool,
     json: bool,
 ) -> Result<()> {
     let bot = state.bot_service.get_bot_by_slug(slug).await?;
 
+    if !purge {
+        state.bot_service.soft_delete_bot(&bot.id).await?;
+        if json {
+            print_json(&serde_json::json!({"deleted": true, "purged": false, "slug": slug}))?;
+        } else {
+            println!(
+                "  {} Bot '{}' deleted. Restore it with: {}",
+                style("✓").red().bold(),
+                bot.name,
+                style(format!("bnity bot restore {slug}")).yellow()
+            );
+        }
+        return Ok(());
+    }
+
     if !force && !json {
         let confirmed = Confirm::new()
             .with_prompt(format!(
@@ -375,28 +539,37 @@ pub async fn delete_bot(
         }
     }
 
-    let spinner = ProgressBar::new_spinner();
-    spinner.set_style(
-        ProgressStyle::default_spinner()
-            .template("{spinner:.red} {msg}")
-            .unwrap(),
-    );
-    spinner.set_message(format!("Deleting {}...", bot.name));
-    spinner.enable_steady_tick(std::time::Duration::from_millis(80));
+    let progress = SpinnerProgress::with_color("red");
+    progress.start(&format!("Deleting {}...", bot.name));
 
     state.bot_service.delete_bot(&bot.id).await?;
 
-    spinner.finish_and_clear();
+    progress.finish();
 
     if json {
+        print_json(&serde_json::json!({"deleted": true, "purged": true, "slug": slug}))?;
+    } else {
         println!(
-            "{}",
-            serde_json::json!({"deleted": true, "slug": slug})
+            "  {} Bot '{}' permanently deleted.",
+            style("✓").red().bold(),
+            bot.name
         );
+    }
+
+    Ok(())
+}
+
+/// Restore a soft-deleted bot and reactivate it.
+async fn restore_bot(state: &AppState, slug: &str, json: bool) -> Result<()> {
+    let bot = state.bot_service.get_bot_by_slug(slug).await?;
+    let bot = state.bot_service.restore_bot(&bot.id).await?;
+
+    if json {
+        print_json(&bot)?;
     } else {
         println!(
-            "  {} Bot '{}' deleted.",
-            style("✓").red().bold(),
+            "  {} Bot '{}' restored.",
+            style("✓").green().bold(),
             bot.name
         );
     }
@@ -408,21 +581,15 @@ pub async fn delete_bot(
 pub async fn clone_bot(state: &AppState, slug: &str, json: bool) -> Result<()> {
     let source = state.bot_service.get_bot_by_slug(slug).await?;
 
-    let spinner = ProgressBar::new_spinner();
-    spinner.set_style(
-        ProgressStyle::default_spinner()
-            .template("{spinner:.cyan} {msg}")
-            .unwrap(),
-    );
-    spinner.set_message(format!("Cloning {}...", source.name));
-    spinner.enable_steady_tick(std::time::Duration::from_millis(80));
+    let progress = SpinnerProgress::new();
+    progress.start(&format!("Cloning {}...", source.name));
 
     let cloned = state.bot_service.clone_bot(&source.id).await?;
 
-    spinner.finish_and_clear();
+    progress.finish();
 
     if json {
-        println!("{}", serde_json::to_string_pretty(&cloned)?);
+        print_json(&cloned)?;
     } else {
         println!(
             "  {} Cloned '{}' as '{}'",
@@ -439,6 +606,89 @@ pub async fn clone_bot(state: &AppState, slug: &str, json: bool) -> Result<()> {
     Ok(())
 }
 
+/// Show a bot's status history, or change its status when `set` is given.
+///
+/// # Examples
+///
+/// ```bash
+/// bnity bot status luna
+/// bnity bot status luna --set disabled --reason "rate limit investigation"
+/// bnity bot status luna --set active --restore
+/// ```
+async fn bot_status(
+    state: &AppState,
+    slug: &str,
+    set: Option<BotStatus>,
+    reason: Option<String>,
+    restore: bool,
+    json: bool,
+) -> Result<()> {
+    let mut bot = state.bot_service.get_bot_by_slug(slug).await?;
+
+    if let Some(status) = set {
+        let previous = bot.status.clone();
+        bot = state
+            .bot_service
+            .set_bot_status(&bot.id, status, reason, restore)
+            .await?;
+
+        if !json {
+            println!(
+                "  {} {} {} -> {}",
+                style("✓").green().bold(),
+                style(&bot.name).cyan(),
+                format_status(&previous),
+                format_status(&bot.status)
+            );
+            return Ok(());
+        }
+    }
+
+    let history = state.bot_service.status_history(&bot.id).await?;
+
+    if json {
+        let result = serde_json::json!({
+            "slug": bot.slug,
+            "status": bot.status,
+            "history": history,
+        });
+        print_json(&result)?;
+        return Ok(());
+    }
+
+    println!();
+    println!("  {} {}", style(&bot.name).bold(), format_status(&bot.status));
+    println!();
+
+    if history.is_empty() {
+        println!("  {}", style("No status changes recorded.").dim());
+        println!();
+        return Ok(());
+    }
+
+    let mut table = new_table();
+    table.set_header(vec![
+        Cell::new("When").fg(Color::White),
+        Cell::new("From").fg(Color::White),
+        Cell::new("To").fg(Color::White),
+        Cell::new("Reason").fg(Color::White),
+    ]);
+
+    for transition in &history {
+        table.add_row(vec![
+            Cell::new(format_relative_time(&transition.created_at)).fg(Color::DarkGrey),
+            Cell::new(transition.from_status.to_string()),
+            Cell::new(transition.to_status.to_string()),
+            Cell::new(transition.reason.as_deref().unwrap_or("-")).fg(Color::DarkGrey),
+        ]);
+    }
+
+    println!("{table}");
+    println!();
+
+    Ok(())
+}
+
 // --- Formatting helpers ---
 
 fn format_status(status: &BotStatus) -> String {
@@ -484,3 +734,112 @@ fn format_number(n: i64) -> String {
         n.to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    use boternity_core::repository::secret::SecretProvider;
+    use boternity_core::service::bot::BotService;
+    use boternity_core::service::soul::SoulService;
+    use boternity_infra::crypto::hash::IntegrityHasher;
+    use boternity_infra::crypto::vault::VaultCrypto;
+    use boternity_infra::filesystem::identity::parse_identity_frontmatter;
+    use boternity_infra::filesystem::LocalFileSystem;
+    use boternity_infra::secret::VaultSecretProvider;
+    use boternity_infra::sqlite::bot::SqliteBotRepository;
+    use boternity_infra::sqlite::pool::DatabasePool;
+    use boternity_infra::sqlite::secret::SqliteSecretRepository;
+    use boternity_infra::sqlite::soul::SqliteSoulRepository;
+
+    const SPEC: &str = r#"
+name = "Luna"
+description = "A curious research assistant"
+category = "research"
+
+[soul]
+tone = "curious"
+traits = ["thorough"]
+purpose = "Help the user research scientific questions."
+
+[identity]
+temperature = 0.3
+
+[secrets]
+GITHUB_TOKEN = "env:LUNA_GITHUB_TOKEN"
+SEARCH_API_KEY = "secret:SEARCH_API_KEY"
+"#;
+
+    async fn services(dir: &tempfile::TempDir) -> (ConcreteBotService, SecretService) {
+        let url = format!("sqlite://{}?mode=rwc", dir.path().join("test.db").display());
+        let pool = DatabasePool::new(&url).await.unwrap();
+        let bot_service = BotService::new(
+            SqliteBotRepository::new(pool.clone()),
+            SoulService::new(
+                SqliteSoulRepository::new(pool.clone()),
+                LocalFileSystem::new(),
+                IntegrityHasher::default(),
+            ),
+            dir.path().to_path_buf(),
+        );
+        let vault = VaultSecretProvider::new(
+            SqliteSecretRepository::new(pool),
+            VaultCrypto::new(&[5u8; 32]),
+        );
+        vault
+            .set("SEARCH_API_KEY", "search-key", &SecretScope::Global)
+            .await
+            .unwrap();
+        (bot_service, SecretService::new(vec![Arc::new(vault)]))
+    }
+
+    fn env(var: &str) -> Option<String> {
+        (var == "LUNA_GITHUB_TOKEN").then(|| "gh-token".to_string())
+    }
+
+    #[tokio::test]
+    async fn test_create_from_complete_spec() {
+        let dir = tempfile::tempdir().unwrap();
+        let (bot_service, secret_service) = services(&dir).await;
+        let spec = BotSpec::parse(SPEC).unwrap().validate().unwrap();
+
+        let result = create_from_spec(&bot_service, &secret_service, &spec, env)
+            .await
+            .unwrap();
+
+        let bot = bot_service.get_bot_by_slug("luna").await.unwrap();
+        assert_eq!(bot.id, result.bot.id);
+        assert_eq!(bot.category, BotCategory::Research);
+
+        let soul = std::fs::read_to_string(&result.file_paths.soul_path).unwrap();
+        assert!(soul.contains("Help the user research scientific questions."));
+        let identity = std::fs::read_to_string(&result.file_paths.identity_path).unwrap();
+        let frontmatter = parse_identity_frontmatter(&identity).unwrap();
+        assert_eq!(frontmatter.display_name, "Luna");
+        assert!((frontmatter.temperature - 0.3).abs() < f64::EPSILON);
+
+        // The env-sourced secret has no global fallback, so this proves the
+        // bot-scoped copy was written.
+        let token = secret_service
+            .get_secret("GITHUB_TOKEN", &SecretScope::Bot(bot.id.clone()))
+            .await
+            .unwrap();
+        assert_eq!(token.as_deref(), Some("gh-token"));
+    }
+
+    #[tokio::test]
+    async fn test_unresolved_secret_writes_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let (bot_service, secret_service) = services(&dir).await;
+        let spec = BotSpec::parse(SPEC).unwrap().validate().unwrap();
+
+        let err = create_from_spec(&bot_service, &secret_service, &spec, |_| None)
+            .await
+            .unwrap_err();
+
+        assert!(err.to_string().contains("secrets.GITHUB_TOKEN"));
+        assert!(bot_service.list_bots(None).await.unwrap().is_empty());
+        assert!(!dir.path().join("bots").join("luna").exists());
+    }
+}
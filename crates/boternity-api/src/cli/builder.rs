@@ -3,23 +3,31 @@
 //! Drives the multi-turn conversation between the user and Forge (the builder
 //! agent) using dialoguer for arrow-key selection, back navigation, and live
 //! preview. Supports three modes: new, resume (from draft), and reconfigure
-//! (existing bot).
+//! (existing bot). `bnity build --list` shows saved drafts and
+//! `bnity build --resume <id>` picks one by session id (or unique prefix).
+//! Reconfiguring shows a diff of the proposed changes and only writes them
+//! to the bot once confirmed.
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+use comfy_table::{Cell, Color};
 use console::style;
 use dialoguer::{Confirm, Input, Select};
 use uuid::Uuid;
 
 use boternity_core::builder::agent::BuilderAgent;
-use boternity_core::builder::assembler::BotAssembler;
-use boternity_core::builder::draft_store::{BuilderDraft, BuilderDraftStore};
+use boternity_core::builder::assembler::{BotAssembler, ReconfigurePreview};
+use boternity_core::builder::defaults::classify_purpose;
+use boternity_core::builder::draft_store::{BuilderDraft, BuilderDraftStore, BuilderDraftSummary};
 use boternity_core::builder::memory::{BuilderMemoryEntry, BuilderMemoryStore};
 use boternity_core::builder::state::{new_builder_state, BuilderStateExt};
 use boternity_infra::builder::llm_builder::LlmBuilderAgent;
 use boternity_infra::builder::sqlite_memory_store::SqliteBuilderMemoryStore;
-use boternity_types::builder::{BuilderAnswer, BuilderState, BuilderTurn};
+use boternity_types::bot::Bot;
+use boternity_types::builder::{BuilderAnswer, BuilderConfig, BuilderState, BuilderTurn};
 
-use crate::state::AppState;
+use crate::cli::color::new_table;
+use crate::cli::output::print_json;
+use crate::state::{AppState, ConcreteBotService};
 
 /// Run the interactive builder wizard to create a new bot.
 ///
@@ -45,6 +53,7 @@ pub async fn run_builder_wizard(state: &AppState) -> Result<()> {
     );
 
     let mut builder_state = new_builder_state(session_id, description.clone());
+    builder_state.purpose_category = Some(classify_purpose(&description));
 
     println!();
     println!(
@@ -57,20 +66,35 @@ pub async fn run_builder_wizard(state: &AppState) -> Result<()> {
         .await
         .context("Builder failed to start")?;
 
-    run_conversation_loop(state, &builder, &mut builder_state, turn).await
+    run_conversation_loop(state, &builder, &mut builder_state, turn, None).await
 }
 
-/// Resume a builder session from a saved draft.
-///
-/// Lists available drafts, lets the user select one, and resumes the
-/// conversation from where they left off.
-pub async fn run_builder_resume(state: &AppState) -> Result<()> {
+/// List saved builder drafts (`bnity build --list`).
+pub async fn list_drafts(state: &AppState, json: bool) -> Result<()> {
     let drafts = state
         .builder_draft_store
         .list_drafts()
         .await
         .context("Failed to list builder drafts")?;
 
+    if json {
+        let items: Vec<serde_json::Value> = drafts
+            .iter()
+            .map(|d| {
+                serde_json::json!({
+                    "session_id": d.session_id,
+                    "initial_description": d.initial_description,
+                    "phase": d.phase,
+                    "question_count": d.question_count,
+                    "created_at": d.created_at,
+                    "updated_at": d.updated_at,
+                })
+            })
+            .collect();
+        print_json(&items)?;
+        return Ok(());
+    }
+
     if drafts.is_empty() {
         println!();
         println!("  No saved drafts found. Start a new session with: bnity build");
@@ -78,36 +102,94 @@ pub async fn run_builder_resume(state: &AppState) -> Result<()> {
         return Ok(());
     }
 
-    let items: Vec<String> = drafts
-        .iter()
-        .map(|d| {
-            format!(
-                "{} -- {} phase, updated {}",
-                d.initial_description,
-                d.phase,
-                d.updated_at.format("%Y-%m-%d %H:%M")
-            )
-        })
-        .collect();
+    let mut table = new_table();
+
+    table.set_header(vec![
+        Cell::new("ID").fg(Color::White),
+        Cell::new("Description").fg(Color::White),
+        Cell::new("Phase").fg(Color::White),
+        Cell::new("Questions").fg(Color::White),
+        Cell::new("Updated").fg(Color::White),
+    ]);
+
+    for draft in &drafts {
+        table.add_row(vec![
+            Cell::new(&draft.session_id.to_string()[..8]).fg(Color::Cyan),
+            Cell::new(&draft.initial_description),
+            Cell::new(&draft.phase),
+            Cell::new(draft.question_count),
+            Cell::new(draft.updated_at.format("%Y-%m-%d %H:%M")).fg(Color::DarkGrey),
+        ]);
+    }
 
     println!();
-    println!("  {} Saved builder sessions:", style("*").cyan().bold());
+    println!("{table}");
     println!();
+    println!(
+        "  Resume one with: {}",
+        style("bnity build --resume <id>").yellow()
+    );
+    println!();
+
+    Ok(())
+}
 
-    let selection = Select::new()
-        .items(&items)
-        .default(0)
-        .interact()?;
+/// Resume a builder session from a saved draft.
+///
+/// With `id`, resumes the draft whose session id equals or starts with it.
+/// Otherwise lists available drafts and lets the user select one. The
+/// conversation continues from where they left off.
+pub async fn run_builder_resume(state: &AppState, id: Option<&str>) -> Result<()> {
+    let drafts = state
+        .builder_draft_store
+        .list_drafts()
+        .await
+        .context("Failed to list builder drafts")?;
+
+    if drafts.is_empty() {
+        println!();
+        println!("  No saved drafts found. Start a new session with: bnity build");
+        println!();
+        return Ok(());
+    }
+
+    let session_id = match id {
+        Some(id) => resolve_draft(&drafts, id)?.session_id,
+        None => {
+            let items: Vec<String> = drafts
+                .iter()
+                .map(|d| {
+                    format!(
+                        "{} -- {} phase, updated {}",
+                        d.initial_description,
+                        d.phase,
+                        d.updated_at.format("%Y-%m-%d %H:%M")
+                    )
+                })
+                .collect();
+
+            println!();
+            println!("  {} Saved builder sessions:", style("*").cyan().bold());
+            println!();
+
+            let selection = Select::new()
+                .items(&items)
+                .default(0)
+                .interact()?;
+            drafts[selection].session_id
+        }
+    };
 
     let draft = state
         .builder_draft_store
-        .load_draft(&drafts[selection].session_id)
+        .load_draft(&session_id)
         .await
         .context("Failed to load draft")?
         .context("Draft not found")?;
 
-    let builder_state: BuilderState =
-        serde_json::from_str(&draft.state_json).context("Failed to deserialize builder state")?;
+    let mut builder_state = draft
+        .state()
+        .context("Failed to deserialize builder state")?;
 
     let provider = state
         .create_single_provider("claude-sonnet-4-20250514")
@@ -127,21 +209,26 @@ pub async fn run_builder_resume(state: &AppState) -> Result<()> {
         style(&builder_state.initial_description).yellow()
     );
 
-    let turn = builder
-        .resume(&builder_state)
-        .await
-        .context("Builder failed to resume")?;
+    // Re-present the question the user left on; drafts saved before the
+    // pending turn was recorded fall back to asking the builder.
+    let turn = match builder_state.pending_turn.take() {
+        Some(turn) => turn,
+        None => builder
+            .resume(&builder_state)
+            .await
+            .context("Builder failed to resume")?,
+    };
 
-    let mut builder_state = builder_state;
-    run_conversation_loop(state, &builder, &mut builder_state, turn).await
+    run_conversation_loop(state, &builder, &mut builder_state, turn, None).await
 }
 
 /// Reconfigure an existing bot through the builder wizard.
 ///
 /// Loads the bot's current configuration and enters the builder conversation
-/// with the existing config pre-populated.
-pub async fn run_builder_reconfigure(state: &AppState, slug: &str) -> Result<()> {
-    use boternity_types::builder::{BuilderConfig, ModelConfig, PersonalityConfig};
+/// with the existing config pre-populated. The result is applied to the same
+/// bot after a diff preview (emitted as JSON with `--json`) is confirmed.
+pub async fn run_builder_reconfigure(state: &AppState, slug: &str, json: bool) -> Result<()> {
+    use boternity_types::builder::{ModelConfig, PersonalityConfig};
 
     let bot = state
         .bot_service
@@ -219,7 +306,7 @@ pub async fn run_builder_reconfigure(state: &AppState, slug: &str) -> Result<()>
         .await
         .context("Builder failed to start reconfiguration")?;
 
-    run_conversation_loop(state, &builder, &mut builder_state, turn).await
+    run_conversation_loop(state, &builder, &mut builder_state, turn, Some((&bot, json))).await
 }
 
 // ---------------------------------------------------------------------------
@@ -232,15 +319,25 @@ pub async fn run_builder_reconfigure(state: &AppState, slug: &str) -> Result<()>
 /// ShowPreview (live config display), ReadyToAssemble (confirmation + assembly),
 /// and Clarify (free-text input).
 ///
-/// Auto-saves drafts after each turn. Records builder memory after successful
-/// assembly.
+/// Auto-saves a draft (including the turn being shown) before each turn.
+/// Records builder memory after successful assembly.
+///
+/// With `reconfigure` set (the target bot and the `--json` flag), the final
+/// config is previewed and applied to that bot instead of creating a new one.
 async fn run_conversation_loop(
     state: &AppState,
     builder: &LlmBuilderAgent<SqliteBuilderMemoryStore>,
     builder_state: &mut BuilderState,
     mut turn: BuilderTurn,
+    reconfigure: Option<(&Bot, bool)>,
 ) -> Result<()> {
     loop {
+        // Auto-save draft before presenting the turn
+        builder_state.pending_turn = Some(turn.clone());
+        if let Ok(draft) = BuilderDraft::from_state(builder_state) {
+            let _ = state.builder_draft_store.save_draft(draft).await;
+        }
+
         match turn {
             BuilderTurn::AskQuestion {
                 phase: _,
@@ -320,6 +417,44 @@ async fn run_conversation_loop(
                     .context("Builder failed after preview")?;
             }
 
+            BuilderTurn::ReadyToAssemble { config } if reconfigure.is_some() => {
+                let Some((bot, json)) = reconfigure else {
+                    unreachable!("guarded by reconfigure.is_some()")
+                };
+                let outcome = preview_and_apply(&state.bot_service, bot, &config, json, || {
+                    Ok(Confirm::new()
+                        .with_prompt(format!("Apply these changes to {}?", bot.name))
+                        .default(false)
+                        .interact()?)
+                })
+                .await?;
+
+                match outcome {
+                    ReconfigureOutcome::Declined => {
+                        turn = builder
+                            .next_turn(builder_state, BuilderAnswer::Back)
+                            .await
+                            .context("Builder failed to go back from assembly")?;
+                        continue;
+                    }
+                    ReconfigureOutcome::Unchanged => {
+                        println!("\n  Nothing to change; {} is already up to date.", bot.name);
+                    }
+                    ReconfigureOutcome::Applied(updated) => {
+                        println!(
+                            "\n  {} Updated {}",
+                            style("✓").green().bold(),
+                            style(&updated.name).cyan()
+                        );
+                    }
+                }
+                let _ = state
+                    .builder_draft_store
+                    .delete_draft(&builder_state.session_id)
+                    .await;
+                break;
+            }
+
             BuilderTurn::ReadyToAssemble { config } => {
                 println!(
                     "\n{}",
@@ -422,25 +557,114 @@ async fn run_conversation_loop(
                     .context("Builder failed to process clarification")?;
             }
         }
-
-        // Auto-save draft after each turn
-        let draft = BuilderDraft {
-            session_id: builder_state.session_id,
-            state_json: serde_json::to_string(builder_state).unwrap_or_default(),
-            schema_version: 1,
-            created_at: chrono::Utc::now(),
-            updated_at: chrono::Utc::now(),
-        };
-        let _ = state.builder_draft_store.save_draft(draft).await;
     }
 
     Ok(())
 }
 
+// ---------------------------------------------------------------------------
+// Reconfigure preview
+// ---------------------------------------------------------------------------
+
+/// Result of offering a reconfiguration to the user.
+#[derive(Debug)]
+enum ReconfigureOutcome {
+    /// The changes were written; holds the updated bot.
+    Applied(Bot),
+    /// The user declined; nothing was written.
+    Declined,
+    /// The config matches the bot already; nothing to confirm.
+    Unchanged,
+}
+
+/// Show what applying `config` to `bot` would change and apply it only if
+/// `confirm` returns true.
+async fn preview_and_apply(
+    bot_service: &ConcreteBotService,
+    bot: &Bot,
+    config: &BuilderConfig,
+    json: bool,
+    confirm: impl FnOnce() -> Result<bool>,
+) -> Result<ReconfigureOutcome> {
+    let preview = BotAssembler::preview_reconfigure(bot_service, bot, config)
+        .await
+        .context("Failed to compare with the current bot")?;
+
+    if json {
+        print_json(&preview)?;
+    } else {
+        print_reconfigure_preview(&preview);
+    }
+    if !preview.has_changes() {
+        return Ok(ReconfigureOutcome::Unchanged);
+    }
+    if !confirm()? {
+        return Ok(ReconfigureOutcome::Declined);
+    }
+
+    let updated = BotAssembler::apply_reconfigure(bot_service, &preview)
+        .await
+        .context("Failed to apply changes")?;
+    Ok(ReconfigureOutcome::Applied(updated))
+}
+
+fn print_reconfigure_preview(preview: &ReconfigurePreview) {
+    println!(
+        "\n{}",
+        style(format!("=== Changes to {} ===", preview.slug)).yellow().bold()
+    );
+    if !preview.has_changes() {
+        println!("  (no changes)");
+        return;
+    }
+    for change in &preview.fields {
+        println!(
+            "  {}: {} -> {}",
+            style(&change.field).bold(),
+            style(&change.current).red(),
+            style(&change.proposed).green()
+        );
+    }
+    for (file, diff) in [
+        ("SOUL.md", &preview.soul_diff),
+        ("IDENTITY.md", &preview.identity_diff),
+    ] {
+        let Some(diff) = diff else { continue };
+        println!("\n  {}", style(file).bold());
+        for line in diff.lines() {
+            if line.starts_with('+') {
+                println!("  {}", style(line).green());
+            } else if line.starts_with('-') {
+                println!("  {}", style(line).red());
+            } else {
+                println!("  {}", style(line).dim());
+            }
+        }
+    }
+    println!();
+}
+
 // ---------------------------------------------------------------------------
 // Helpers
 // ---------------------------------------------------------------------------
 
+/// Find the draft whose session id equals or uniquely starts with `id`.
+fn resolve_draft<'a>(
+    drafts: &'a [BuilderDraftSummary],
+    id: &str,
+) -> Result<&'a BuilderDraftSummary> {
+    let id = id.trim().to_lowercase();
+    let matches: Vec<&BuilderDraftSummary> = drafts
+        .iter()
+        .filter(|d| d.session_id.to_string().starts_with(&id))
+        .collect();
+    match matches.as_slice() {
+        [draft] => Ok(draft),
+        [] => bail!("No saved draft matches '{id}'. See: bnity build --list"),
+        _ => bail!("'{id}' matches {} drafts; use a longer id", matches.len()),
+    }
+}
+
 /// Extract a field from YAML frontmatter in a Markdown file.
 fn extract_frontmatter_field(content: &str, field: &str) -> Option<String> {
     let lines: Vec<&str> = content.lines().collect();
@@ -460,3 +684,165 @@ fn extract_frontmatter_field(content: &str, field: &str) -> Option<String> {
     }
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use boternity_core::service::bot::BotService;
+    use boternity_core::service::soul::SoulService;
+    use boternity_infra::crypto::hash::IntegrityHasher;
+    use boternity_infra::filesystem::LocalFileSystem;
+    use boternity_infra::sqlite::bot::SqliteBotRepository;
+    use boternity_infra::sqlite::pool::DatabasePool;
+    use boternity_infra::sqlite::soul::SqliteSoulRepository;
+    use boternity_types::bot::{BotCategory, CreateBotRequest};
+    use boternity_types::builder::{ModelConfig, PersonalityConfig};
+
+    async fn bot_service_with_bot(dir: &tempfile::TempDir) -> (ConcreteBotService, Bot) {
+        let url = format!("sqlite://{}?mode=rwc", dir.path().join("test.db").display());
+        let pool = DatabasePool::new(&url).await.unwrap();
+        let bot_service = BotService::new(
+            SqliteBotRepository::new(pool.clone()),
+            SoulService::new(
+                SqliteSoulRepository::new(pool),
+                LocalFileSystem::new(),
+                IntegrityHasher::default(),
+            ),
+            dir.path().to_path_buf(),
+        );
+        let bot = bot_service
+            .create_bot(CreateBotRequest {
+                name: "Luna".to_string(),
+                description: Some("A helpful bot".to_string()),
+                category: Some(BotCategory::Assistant),
+                tags: None,
+            })
+            .await
+            .unwrap();
+        (bot_service, bot)
+    }
+
+    fn research_config() -> BuilderConfig {
+        BuilderConfig {
+            name: "Luna".to_string(),
+            description: "A curious research assistant".to_string(),
+            category: "research".to_string(),
+            tags: vec![],
+            personality: PersonalityConfig {
+                tone: "curious".to_string(),
+                traits: vec!["thorough".to_string()],
+                purpose: "Help with research.".to_string(),
+                boundaries: None,
+            },
+            model_config: ModelConfig {
+                model: "claude-opus-4-20250514".to_string(),
+                temperature: 0.3,
+                max_tokens: 8192,
+            },
+            skills: vec![],
+        }
+    }
+
+    fn read(bot_service: &ConcreteBotService, bot: &Bot, file: &str) -> String {
+        std::fs::read_to_string(bot_service.bot_dir(&bot.slug).join(file)).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_reconfigure_preview_lists_soul_and_identity_deltas() {
+        let dir = tempfile::tempdir().unwrap();
+        let (bot_service, bot) = bot_service_with_bot(&dir).await;
+
+        let preview = BotAssembler::preview_reconfigure(&bot_service, &bot, &research_config())
+            .await
+            .unwrap();
+
+        let fields: Vec<&str> = preview.fields.iter().map(|c| c.field.as_str()).collect();
+        assert_eq!(fields, vec!["description", "category"]);
+        let soul_diff = preview.soul_diff.as_deref().unwrap();
+        assert!(soul_diff.contains("+tone: curious"));
+        let identity_diff = preview.identity_diff.as_deref().unwrap();
+        assert!(identity_diff.contains("+model: claude-opus-4-20250514"));
+        assert!(identity_diff.contains("+max_tokens: 8192"));
+        // Keys the builder does not manage are kept
+        assert!(identity_diff.contains(" display_name: Luna"));
+
+        let json = serde_json::to_value(&preview).unwrap();
+        assert_eq!(json["fields"][1]["proposed"], "research");
+        assert!(json["soul_diff"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_declining_reconfigure_leaves_bot_unchanged() {
+        let dir = tempfile::tempdir().unwrap();
+        let (bot_service, bot) = bot_service_with_bot(&dir).await;
+        let soul_before = read(&bot_service, &bot, "SOUL.md");
+        let identity_before = read(&bot_service, &bot, "IDENTITY.md");
+
+        let outcome = preview_and_apply(&bot_service, &bot, &research_config(), true, || Ok(false))
+            .await
+            .unwrap();
+
+        assert!(matches!(outcome, ReconfigureOutcome::Declined));
+        assert_eq!(read(&bot_service, &bot, "SOUL.md"), soul_before);
+        assert_eq!(read(&bot_service, &bot, "IDENTITY.md"), identity_before);
+        let unchanged = bot_service.get_bot(&bot.id).await.unwrap();
+        assert_eq!(unchanged.description, "A helpful bot");
+        assert_eq!(unchanged.category, BotCategory::Assistant);
+        let versions = bot_service.soul_service().get_soul_versions(&bot.id).await.unwrap();
+        assert_eq!(versions.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_confirmed_reconfigure_updates_existing_bot() {
+        let dir = tempfile::tempdir().unwrap();
+        let (bot_service, bot) = bot_service_with_bot(&dir).await;
+
+        let outcome = preview_and_apply(&bot_service, &bot, &research_config(), true, || Ok(true))
+            .await
+            .unwrap();
+
+        let ReconfigureOutcome::Applied(updated) = outcome else {
+            panic!("expected the changes to be applied");
+        };
+        assert_eq!(updated.id, bot.id);
+        assert_eq!(updated.category, BotCategory::Research);
+        assert!(read(&bot_service, &bot, "SOUL.md").contains("tone: curious"));
+        assert!(read(&bot_service, &bot, "IDENTITY.md").contains("max_tokens: 8192"));
+        assert_eq!(bot_service.list_bots(None).await.unwrap().len(), 1);
+
+        // Re-previewing the same config finds nothing left to change
+        let again = preview_and_apply(&bot_service, &updated, &research_config(), true, || {
+            panic!("no confirmation needed without changes")
+        })
+        .await
+        .unwrap();
+        assert!(matches!(again, ReconfigureOutcome::Unchanged));
+    }
+
+    fn summary(session_id: &str) -> BuilderDraftSummary {
+        BuilderDraftSummary {
+            session_id: Uuid::parse_str(session_id).unwrap(),
+            initial_description: "A bot".to_string(),
+            phase: "basics".to_string(),
+            question_count: 0,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_resolve_draft_by_full_id_or_prefix() {
+        let drafts = vec![
+            summary("0191a000-0000-7000-8000-000000000001"),
+            summary("0191b000-0000-7000-8000-000000000002"),
+        ];
+
+        let found = resolve_draft(&drafts, "0191b000-0000-7000-8000-000000000002").unwrap();
+        assert_eq!(found.session_id, drafts[1].session_id);
+        let found = resolve_draft(&drafts, "0191A").unwrap();
+        assert_eq!(found.session_id, drafts[0].session_id);
+
+        assert!(resolve_draft(&drafts, "0191").is_err());
+        assert!(resolve_draft(&drafts, "ffff").is_err());
+    }
+}
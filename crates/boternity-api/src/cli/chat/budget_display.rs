@@ -2,11 +2,30 @@
 //!
 //! Renders live budget counters, warning prompts, exhaustion messages,
 //! and completion stats with cost estimates. Colors change at the 80%
-//! threshold to provide visual feedback on budget consumption.
+//! threshold to provide visual feedback on budget consumption. After a
+//! spawn completes, a per-agent breakdown shows which agent used what.
 
+use boternity_types::agent::AgentNode;
 use console::style;
+use serde::Serialize;
+use uuid::Uuid;
 
-use super::tree_renderer::format_tokens_human;
+use super::tree_renderer::{format_tokens_human, render_breakdown_line};
+
+/// One agent's share of the tokens used by a request.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AgentTokenShare {
+    pub agent_id: Uuid,
+    /// Display label: `root`, `agent-1`, `agent-1.2`, ...
+    pub label: String,
+    pub task: String,
+    /// Depth relative to the root agent (root = 0).
+    pub depth: u8,
+    /// Tokens spent by this agent itself, excluding its children.
+    pub tokens_used: u32,
+    /// Percentage of the request total, 0-100.
+    pub share_pct: f64,
+}
 
 /// Render a budget counter line showing tokens used vs total.
 ///
@@ -89,9 +108,132 @@ pub fn render_completion_stats(
     )
 }
 
+/// Flatten an agent tree into a per-agent token breakdown (depth-first).
+///
+/// A node's `tokens_used` includes its children (the root node carries the
+/// request total), so each entry reports the node's own tokens: its total
+/// minus its children's totals. Shares are relative to the sum of the
+/// top-level nodes.
+pub fn agent_token_breakdown(tree: &[AgentNode]) -> Vec<AgentTokenShare> {
+    fn visit(
+        node: &AgentNode,
+        label: String,
+        depth: u8,
+        total: u32,
+        out: &mut Vec<AgentTokenShare>,
+    ) {
+        let children_tokens: u32 = node.children.iter().map(|c| c.tokens_used).sum();
+        let own = node.tokens_used.saturating_sub(children_tokens);
+        out.push(AgentTokenShare {
+            agent_id: node.agent_id,
+            label: label.clone(),
+            task: if depth == 0 { String::new() } else { node.task.clone() },
+            depth,
+            tokens_used: own,
+            share_pct: if total == 0 {
+                0.0
+            } else {
+                own as f64 * 100.0 / total as f64
+            },
+        });
+
+        for (i, child) in node.children.iter().enumerate() {
+            let child_label = if depth == 0 {
+                format!("agent-{}", i + 1)
+            } else {
+                format!("{label}.{}", i + 1)
+            };
+            visit(child, child_label, depth + 1, total, out);
+        }
+    }
+
+    let total: u32 = tree.iter().map(|n| n.tokens_used).sum();
+    let mut out = Vec::new();
+    for node in tree {
+        visit(node, "root".to_string(), 0, total, &mut out);
+    }
+    out
+}
+
+/// Render the per-agent token breakdown as display lines.
+///
+/// Example:
+/// ```text
+///   Token breakdown:
+///   root 1,200 tokens (17.8%)
+///   +-- agent-1: Research quantum computing 2,450 tokens (36.4%)
+///   L-- agent-2: Summarize findings 3,090 tokens (45.8%)
+/// ```
+pub fn render_agent_breakdown(breakdown: &[AgentTokenShare]) -> Vec<String> {
+    let mut lines = vec![format!("  {}", style("Token breakdown:").dim())];
+
+    for (i, entry) in breakdown.iter().enumerate() {
+        if entry.depth == 0 {
+            lines.push(format!(
+                "  {} {} {}",
+                style(&entry.label).cyan(),
+                style(format!("{} tokens", format_tokens_human(entry.tokens_used))).dim(),
+                style(format!("({:.1}%)", entry.share_pct)).dim(),
+            ));
+            continue;
+        }
+
+        // Position among siblings: siblings are the entries at the same depth
+        // up to the next shallower entry.
+        let siblings_before = breakdown[..i]
+            .iter()
+            .rev()
+            .take_while(|e| e.depth >= entry.depth)
+            .filter(|e| e.depth == entry.depth)
+            .count();
+        let siblings_after = breakdown[i + 1..]
+            .iter()
+            .take_while(|e| e.depth >= entry.depth)
+            .filter(|e| e.depth == entry.depth)
+            .count();
+
+        lines.push(render_breakdown_line(
+            entry.depth,
+            siblings_before,
+            siblings_before + siblings_after + 1,
+            &entry.label,
+            &entry.task,
+            entry.tokens_used,
+            entry.share_pct,
+        ));
+    }
+
+    lines
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use boternity_types::agent::AgentStatus;
+
+    fn node(task: &str, tokens_used: u32, children: Vec<AgentNode>) -> AgentNode {
+        AgentNode {
+            agent_id: Uuid::now_v7(),
+            parent_id: None,
+            task: task.to_string(),
+            depth: 0,
+            status: AgentStatus::Completed,
+            tokens_used,
+            duration_ms: 0,
+            children,
+        }
+    }
+
+    fn known_tree() -> Vec<AgentNode> {
+        vec![node(
+            "root",
+            10_000,
+            vec![
+                node("Research quantum computing", 4_000, vec![node("Find papers", 1_000, vec![])]),
+                node("Summarize findings", 5_000, vec![]),
+            ],
+        )]
+    }
 
     #[test]
     fn render_budget_counter_low_usage() {
@@ -156,4 +298,59 @@ mod tests {
         assert!(stats.contains("$0.00"));
         assert!(stats.contains("0.0s"));
     }
+
+    #[test]
+    fn agent_token_breakdown_splits_own_tokens() {
+        let breakdown = agent_token_breakdown(&known_tree());
+        let rows: Vec<(&str, u8, u32)> = breakdown
+            .iter()
+            .map(|e| (e.label.as_str(), e.depth, e.tokens_used))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                ("root", 0, 1_000),
+                ("agent-1", 1, 3_000),
+                ("agent-1.1", 2, 1_000),
+                ("agent-2", 1, 5_000),
+            ]
+        );
+        let total_share: f64 = breakdown.iter().map(|e| e.share_pct).sum();
+        assert!((total_share - 100.0).abs() < 0.001);
+        assert!((breakdown[3].share_pct - 50.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn render_agent_breakdown_formats_known_tree() {
+        let lines: Vec<String> = render_agent_breakdown(&agent_token_breakdown(&known_tree()))
+            .iter()
+            .map(|l| console::strip_ansi_codes(l).into_owned())
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                "  Token breakdown:".to_string(),
+                "  root 1,000 tokens (10.0%)".to_string(),
+                "  \u{251C}\u{2500}\u{2500} agent-1: Research quantum computing 3,000 tokens (30.0%)".to_string(),
+                "    \u{2514}\u{2500}\u{2500} agent-1.1: Find papers 1,000 tokens (10.0%)".to_string(),
+                "  \u{2514}\u{2500}\u{2500} agent-2: Summarize findings 5,000 tokens (50.0%)".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn agent_token_breakdown_serializes_for_json() {
+        let breakdown = agent_token_breakdown(&known_tree());
+        let json = serde_json::to_value(&breakdown).unwrap();
+        assert_eq!(json[1]["label"], "agent-1");
+        assert_eq!(json[1]["tokens_used"], 3_000);
+        assert!((json[1]["share_pct"].as_f64().unwrap() - 30.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn agent_token_breakdown_empty_tree() {
+        assert!(agent_token_breakdown(&[]).is_empty());
+        let breakdown = agent_token_breakdown(&[node("root", 0, vec![])]);
+        assert_eq!(breakdown[0].share_pct, 0.0);
+    }
 }
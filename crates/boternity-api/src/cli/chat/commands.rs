@@ -1,7 +1,7 @@
 //! Slash command parsing and execution for the chat loop.
 //!
 //! Commands start with `/` and provide in-chat controls for session
-//! management, help, and memory injection.
+//! management, help, memory injection and extraction, and running skills.
 
 use console::style;
 
@@ -20,6 +20,10 @@ pub enum ChatCommand {
     History,
     /// Manually inject a memory.
     Remember(String),
+    /// Extract memories from the conversation now (`/memory extract`).
+    ExtractMemory,
+    /// Run an attached skill (`/skill <name> [args]`).
+    Skill { name: String, args: String },
     /// Unknown command.
     Unknown(String),
 }
@@ -54,6 +58,21 @@ pub fn parse(input: &str) -> Option<ChatCommand> {
                 Some(ChatCommand::Unknown("/remember requires a fact".to_string()))
             }
         }
+        "/memory" => match arg.as_deref() {
+            Some("extract") => Some(ChatCommand::ExtractMemory),
+            _ => Some(ChatCommand::Unknown("/memory requires 'extract'".to_string())),
+        },
+        "/skill" => {
+            let arg = arg.unwrap_or_default();
+            let mut parts = arg.splitn(2, char::is_whitespace);
+            match parts.next().filter(|name| !name.is_empty()) {
+                Some(name) => Some(ChatCommand::Skill {
+                    name: name.to_string(),
+                    args: parts.next().unwrap_or("").trim().to_string(),
+                }),
+                None => Some(ChatCommand::Unknown("/skill requires a skill name".to_string())),
+            }
+        }
         other => Some(ChatCommand::Unknown(other.to_string())),
     }
 }
@@ -93,6 +112,16 @@ pub fn print_help() {
         style("/remember").cyan(),
         "Save a fact to memory"
     );
+    println!(
+        "  {} {}",
+        style("/memory extract").cyan(),
+        "Extract memories from this conversation now"
+    );
+    println!(
+        "  {} {}",
+        style("/skill <name> [args]").cyan(),
+        "Run an attached skill and add its output to the chat"
+    );
     println!();
     println!(
         "  {}",
@@ -133,6 +162,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_memory_extract() {
+        assert_eq!(parse("/memory extract"), Some(ChatCommand::ExtractMemory));
+        assert_eq!(parse("/MEMORY  extract "), Some(ChatCommand::ExtractMemory));
+        assert_eq!(
+            parse("/memory"),
+            Some(ChatCommand::Unknown("/memory requires 'extract'".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_skill() {
+        assert_eq!(
+            parse("/skill summarize the quarterly report"),
+            Some(ChatCommand::Skill {
+                name: "summarize".to_string(),
+                args: "the quarterly report".to_string(),
+            })
+        );
+        assert_eq!(
+            parse("/skill  word-count "),
+            Some(ChatCommand::Skill {
+                name: "word-count".to_string(),
+                args: String::new(),
+            })
+        );
+        assert_eq!(
+            parse("/skill"),
+            Some(ChatCommand::Unknown("/skill requires a skill name".to_string()))
+        );
+    }
+
     #[test]
     fn test_parse_not_command() {
         assert_eq!(parse("hello world"), None);
@@ -18,7 +18,7 @@ use std::io::Write;
 use std::time::Instant;
 
 use console::style;
-use futures_util::StreamExt;
+use futures_util::{Stream, StreamExt};
 use tracing::{debug, info, warn};
 use uuid::Uuid;
 
@@ -26,6 +26,7 @@ use boternity_core::agent::budget::RequestBudget;
 use boternity_core::agent::context::AgentContext;
 use boternity_core::agent::orchestrator::AgentOrchestrator;
 use boternity_core::agent::request_context::RequestContext;
+use boternity_core::agent::summarizer::ContextSummarizer;
 use boternity_core::agent::title::generate_title;
 use boternity_core::chat::session::SessionManager;
 use boternity_core::llm::health::ProviderHealth;
@@ -34,49 +35,25 @@ use boternity_core::memory::box_vector::BoxVectorMemoryStore;
 use boternity_core::memory::extractor::SessionMemoryExtractor;
 use boternity_core::memory::store::MemoryRepository;
 use boternity_infra::filesystem::identity::parse_identity_frontmatter;
+use boternity_infra::filesystem::user::parse_user_content;
 use boternity_infra::filesystem::LocalFileSystem;
 use boternity_infra::llm::pricing::estimate_cost;
+use boternity_infra::skill::local_executor::{run_attached_skill, LocalSkillExecutor};
 use boternity_types::event::AgentEvent;
-use boternity_types::llm::{CompletionRequest, LlmError, StreamEvent};
+use boternity_types::llm::{LlmError, StreamEvent};
 use boternity_types::memory::RankedMemory;
 
+use crate::cli::output::print_json_with_warnings;
+use crate::cli::progress::{ProgressReporter, SpinnerProgress};
 use crate::state::AppState;
 
 use super::banner::print_welcome_banner;
 use super::budget_display;
 use super::commands::{self, ChatCommand};
 use super::input::{ChatInput, InputEvent};
-use super::renderer::ChatRenderer;
+use super::renderer::{ChatRenderer, MarkdownStream};
 use super::tree_renderer;
 
-/// Build a [`CompletionRequest`] from agent context and a user message.
-///
-/// Replicates the request building logic from `AgentEngine::build_request()`,
-/// which constructs the system prompt + conversation history + user message.
-fn build_completion_request(
-    context: &AgentContext,
-    user_message: &str,
-) -> CompletionRequest {
-    let mut messages = context.build_messages();
-
-    // Add the current user message to the request
-    messages.push(boternity_types::llm::Message {
-        role: boternity_types::llm::MessageRole::User,
-        content: user_message.to_string(),
-    });
-
-    CompletionRequest {
-        model: context.agent_config.model.clone(),
-        messages,
-        system: Some(context.system_prompt.clone()),
-        max_tokens: context.agent_config.max_tokens,
-        temperature: Some(context.agent_config.temperature),
-        stream: true,
-        stop_sequences: None,
-        output_config: None,
-    }
-}
-
 /// Print a failover warning to stderr with visual formatting.
 fn print_failover_warning(warning: &str) {
     eprintln!(
@@ -125,6 +102,95 @@ fn print_verbose_provider_info(provider_name: &str, provider_count: usize) {
     );
 }
 
+/// How a response stream ended.
+#[derive(Debug)]
+enum StreamEnd {
+    /// The stream finished normally.
+    Completed,
+    /// The provider rejected the request as too long before any output.
+    ContextOverflow { max: u32, requested: u32 },
+    /// The stream failed.
+    Failed(LlmError),
+}
+
+/// A streamed response as received.
+#[derive(Debug)]
+struct StreamOutcome {
+    response: String,
+    input_tokens: u32,
+    output_tokens: u32,
+    stop_reason: String,
+    end: StreamEnd,
+}
+
+/// Render a response stream to `out` as it arrives.
+///
+/// `progress` is cleared when the first token arrives (or the stream ends).
+/// When `allow_overflow_retry` is set, a context-length error before any
+/// output ends the stream with [`StreamEnd::ContextOverflow`] so the caller
+/// can compact history and retry; otherwise it is a failure.
+async fn consume_stream(
+    mut stream: impl Stream<Item = Result<StreamEvent, LlmError>> + Unpin,
+    renderer: &ChatRenderer,
+    bot_name: &str,
+    progress: &dyn ProgressReporter,
+    allow_overflow_retry: bool,
+    out: &mut impl Write,
+) -> StreamOutcome {
+    let mut outcome = StreamOutcome {
+        response: String::new(),
+        input_tokens: 0,
+        output_tokens: 0,
+        stop_reason: "end_turn".to_string(),
+        end: StreamEnd::Completed,
+    };
+    let mut markdown_stream = MarkdownStream::default();
+    let mut first_token_received = false;
+
+    while let Some(event_result) = stream.next().await {
+        match event_result {
+            Ok(StreamEvent::TextDelta { text: delta, .. }) => {
+                if !first_token_received {
+                    progress.finish();
+                    first_token_received = true;
+                    let _ = write!(out, "\n  {} ", style(bot_name).cyan().bold());
+                }
+                let _ = write!(out, "{}", renderer.render_streaming_markdown(&mut markdown_stream, &delta));
+                let _ = out.flush();
+                outcome.response.push_str(&delta);
+            }
+            Ok(StreamEvent::Usage(usage)) => {
+                outcome.input_tokens = usage.input_tokens;
+                outcome.output_tokens = usage.output_tokens;
+            }
+            Ok(StreamEvent::MessageDelta { stop_reason }) => {
+                outcome.stop_reason = stop_reason.to_string();
+            }
+            Ok(StreamEvent::Done) => break,
+            Ok(_) => {}
+            Err(LlmError::ContextLengthExceeded { max, requested })
+                if !first_token_received && allow_overflow_retry =>
+            {
+                outcome.end = StreamEnd::ContextOverflow { max, requested };
+                break;
+            }
+            Err(e) => {
+                outcome.end = StreamEnd::Failed(e);
+                break;
+            }
+        }
+    }
+    progress.finish();
+
+    // Flush the trailing partial line or unclosed code block
+    if first_token_received {
+        let _ = write!(out, "{}", renderer.finish_streaming_markdown(&mut markdown_stream));
+        let _ = out.flush();
+    }
+
+    outcome
+}
+
 /// Run the interactive chat loop for a bot.
 ///
 /// Builds a [`FallbackChain`] from the configured providers and uses it
@@ -135,12 +201,20 @@ fn print_verbose_provider_info(provider_name: &str, provider_count: usize) {
 ///
 /// When `quiet` is true, suppresses sub-agent detail output, showing only
 /// the final synthesized response.
+///
+/// When `max_cost` is set, messages are refused once the session's
+/// estimated spend reaches that many USD.
+///
+/// When `json` is true, the per-agent token breakdown after a spawn is
+/// emitted as a JSON line instead of the rendered tree.
 pub async fn run_chat_loop(
     state: &AppState,
     bot_slug: &str,
     _resume_session_id: Option<String>,
     verbose: bool,
     quiet: bool,
+    max_cost: Option<f64>,
+    json: bool,
 ) -> anyhow::Result<()> {
     let bot = state.bot_service.get_bot_by_slug(bot_slug).await?;
 
@@ -161,7 +235,8 @@ pub async fn run_chat_loop(
     let bot_emoji = None::<String>;
 
     // Build fallback chain with all configured providers
-    let mut fallback_chain = state.build_fallback_chain(&model).await?;
+    let provider_preference = identity_fm.as_ref().and_then(|fm| fm.provider_preference.clone());
+    let mut fallback_chain = state.build_fallback_chain(&model, provider_preference.as_ref()).await?;
     let provider_count = fallback_chain.providers.len();
 
     // Get capabilities from the primary provider for token budget
@@ -188,25 +263,42 @@ pub async fn run_chat_loop(
         model: model.clone(),
         temperature,
         max_tokens,
+        guard_untrusted_content: identity_fm.as_ref().is_some_and(|fm| fm.guard_untrusted_content),
     };
     let token_budget = TokenBudget::from_capabilities(&primary_caps);
-    let mut agent_context = AgentContext::new(agent_config, soul_content, identity_content.clone(), user_content, memories, token_budget);
+    let (user_profile, user_body) = parse_user_content(&user_content);
+    let mut agent_context = AgentContext::new(agent_config, soul_content, identity_content.clone(), user_body.to_string(), memories, token_budget)
+        .with_user_profile(user_profile);
 
     // Create orchestrator for sub-agent execution
     let orchestrator = AgentOrchestrator::new(3);
 
-    // Resolve per-request token budget
+    // Resolve per-request token budget (IDENTITY.md may cap it per bot)
+    let budget_override = identity_fm.as_ref().and_then(|fm| fm.max_request_tokens).map(|requested| {
+        boternity_infra::config::validate_request_budget_override(requested, primary_caps.max_context_tokens)
+            .unwrap_or_else(|e| {
+                eprintln!("  {} {e}; using {} instead.", style("!").yellow().bold(), e.max_context_tokens);
+                e.max_context_tokens
+            })
+    });
     let request_budget_total = boternity_infra::config::resolve_request_budget(
         &state.global_config,
-        None, // IdentityFrontmatter does not have max_request_tokens field yet
+        budget_override,
     );
 
     // Create session
     let session = state.chat_service.create_session(bot.id.0, model.clone()).await?;
-    let mut session_manager = SessionManager::new(session);
+    let mut session_manager = SessionManager::new(session)
+        .with_max_cost(max_cost)
+        .with_extraction_interval(identity_fm.as_ref().and_then(|fm| fm.memory_extraction_interval));
     let session_id = session_manager.session().id;
     let session_id_str = session_id.to_string();
 
+    // Mark the bot as used (also refreshed when the session ends)
+    if let Err(e) = state.bot_service.touch_activity(&bot.id).await {
+        warn!(error = %e, "Failed to update bot last_active_at");
+    }
+
     // Print welcome banner
     print_welcome_banner(&bot.name, bot_emoji.as_deref(), &bot.description, &model, &session_id_str);
 
@@ -219,12 +311,10 @@ pub async fn run_chat_loop(
 
     // Generate and display greeting using fallback chain
     let renderer = ChatRenderer::new(None);
-    let greeting_spinner = indicatif::ProgressBar::new_spinner();
-    greeting_spinner.set_style(indicatif::ProgressStyle::default_spinner().template("{spinner:.cyan} {msg}").unwrap());
-    greeting_spinner.set_message("thinking...");
-    greeting_spinner.enable_steady_tick(std::time::Duration::from_millis(80));
+    let progress = SpinnerProgress::new();
+    progress.start("thinking...");
 
-    let greeting_request = build_completion_request(&agent_context, "Generate a short, warm greeting message that introduces yourself and invites the user to chat. Stay fully in character. Keep it under 2 sentences.");
+    let greeting_request = agent_context.completion_request("Generate a short, warm greeting message that introduces yourself and invites the user to chat. Stay fully in character. Keep it under 2 sentences.")?;
     let greeting = match fallback_chain.complete(&greeting_request).await {
         Ok(result) => {
             if let Some(ref warning) = result.failover_warning {
@@ -233,12 +323,12 @@ pub async fn run_chat_loop(
             result.response.content
         }
         Err(e) => {
-            greeting_spinner.finish_and_clear();
+            progress.finish();
             eprintln!("\n  {} Could not generate greeting: {e}", style("!").yellow().bold());
             "Hello! I'm ready to chat.".to_string()
         }
     };
-    greeting_spinner.finish_and_clear();
+    progress.finish();
 
     let rendered_greeting = renderer.render_final(&greeting);
     println!("  {}", rendered_greeting.trim());
@@ -250,6 +340,8 @@ pub async fn run_chat_loop(
 
     let mut first_user_message: Option<String> = None;
     let mut first_assistant_response: Option<String> = None;
+    // Stored messages condensed into the session's rolling summary
+    let mut summarized_messages: u32 = 0;
 
     // Prepare vector memory search components.
     // Create a fresh LanceDB connection for the chat loop's vector memory search.
@@ -258,9 +350,7 @@ pub async fn run_chat_loop(
     let vector_store_for_chat = match boternity_infra::vector::lance::LanceVectorStore::new(
         state.data_dir.join("vector_store"),
     ).await {
-        Ok(vs) => Some(BoxVectorMemoryStore::new(
-            boternity_infra::vector::memory::LanceVectorMemoryStore::new(vs),
-        )),
+        Ok(vs) => Some(BoxVectorMemoryStore::new(state.open_vector_memory(vs))),
         Err(e) => {
             warn!(error = %e, "Failed to open vector store for memory recall; proceeding without vector search");
             None
@@ -271,7 +361,7 @@ pub async fn run_chat_loop(
     let prompt = format!("  {} ", style("You >").green().bold());
     let (mut chat_input, _writer) = ChatInput::new(prompt.clone()).map_err(|e| anyhow::anyhow!("Failed to initialize input: {e}"))?;
 
-    loop {
+    'chat: loop {
         let event = chat_input.read_line().await;
         match event {
             InputEvent::Eof => {
@@ -323,6 +413,39 @@ pub async fn run_chat_loop(
                             }
                             continue;
                         }
+                        ChatCommand::ExtractMemory => {
+                            session_manager.request_memory_extraction();
+                            if session_manager.should_extract_memory() {
+                                match extract_session_memories(state, &model, &agent_context, bot.id.0, session_id).await {
+                                    Ok(count) => println!("\n  {} Extracted {count} memor{} from this conversation.\n", style("*").cyan().bold(), if count == 1 { "y" } else { "ies" }),
+                                    Err(e) => println!("\n  {} Memory extraction failed: {e}\n", style("!").red().bold()),
+                                }
+                                session_manager.mark_memory_extracted();
+                            }
+                            continue;
+                        }
+                        ChatCommand::Skill { name, args } => {
+                            let bot_dir = state.data_dir.join("bots").join(&bot.slug);
+                            let progress = SpinnerProgress::new();
+                            progress.start(&format!("running skill '{name}'..."));
+                            let result = run_attached_skill(&state.skill_store, &bot_dir, &name, &args, &LocalSkillExecutor::new()).await;
+                            progress.finish();
+                            match result {
+                                Ok(result) => {
+                                    let output = result.output.trim().to_string();
+                                    println!("\n  {} {}", style(format!("[skill {name}]")).cyan().bold(), style(format!("{}ms", result.duration.as_millis())).dim());
+                                    println!("  {}\n", renderer.render_final(&output).trim());
+                                    // Add the exchange to the conversation so the bot can refer to it
+                                    let skill_message = format!("Output of skill `{name}`:\n\n{output}");
+                                    agent_context.add_user_message(text.clone());
+                                    agent_context.add_assistant_message(skill_message.clone());
+                                    let _ = state.chat_service.save_user_message(session_id, text.clone()).await;
+                                    let _ = state.chat_service.save_assistant_message(session_id, skill_message, model.clone(), 0, 0, "end_turn".to_string(), 0).await;
+                                }
+                                Err(e) => println!("\n  {} Skill failed: {e:#}\n", style("!").red().bold()),
+                            }
+                            continue;
+                        }
                         ChatCommand::Unknown(cmd_name) => {
                             println!("\n  {} Unknown command: {}. Type /help for available commands.\n", style("?").yellow().bold(), style(cmd_name).dim());
                             continue;
@@ -330,8 +453,22 @@ pub async fn run_chat_loop(
                     }
                 }
 
+                // Session cost guard: refuse new requests once the cap is reached
+                if let Err(exceeded) = session_manager.check_cost_budget() {
+                    eprintln!("\n  {} {exceeded}.", style("!").red().bold());
+                    eprintln!("  {}", style("Start a new session or raise --max-cost to continue. /exit to quit.").dim());
+                    continue;
+                }
+
+                // Bot usage quota: refuse new requests until the window resets
+                if let Err(e) = state.chat_service.check_quota(&bot.id.0, &bot.slug).await {
+                    eprintln!("\n  {} {e}.", style("!").red().bold());
+                    eprintln!("  {}", style("Raise the bot's limits under [quota] in config.toml to continue. /exit to quit.").dim());
+                    continue;
+                }
+
                 // Send to LLM via FallbackChain
-                // Note: do NOT add to agent_context here -- build_completion_request()
+                // Note: do NOT add to agent_context here -- completion_request()
                 // appends the user message to the request automatically.
                 // We add it to history after the response completes, alongside
                 // the assistant message.
@@ -359,107 +496,121 @@ pub async fn run_chat_loop(
                     print_verbose_memories(&recalled);
                 }
 
-                // Thinking spinner
-                let spinner = indicatif::ProgressBar::new_spinner();
-                spinner.set_style(indicatif::ProgressStyle::default_spinner().template("{spinner:.cyan} {msg}").unwrap());
-                spinner.set_message("thinking...");
-                spinner.enable_steady_tick(std::time::Duration::from_millis(80));
-
-                // Build request and select provider via fallback chain
-                let request = build_completion_request(&agent_context, &text);
-                let stream_selection = match fallback_chain.select_stream(request) {
-                    Ok(selection) => selection,
-                    Err(e) => {
-                        spinner.finish_and_clear();
-                        // Handle "all providers down" clearly
-                        if matches!(&e, LlmError::Provider { message } if message.contains("bnity provider status")) {
-                            eprintln!("\n  {} All providers in the fallback chain are currently unavailable.", style("!").red().bold());
-                            eprintln!("  {} Run {} to check provider health.", style("Tip:").dim(), style("bnity provider status").cyan());
-                        } else {
-                            eprintln!("\n  {} LLM error: {e}", style("!").red().bold());
+                // Build request and select provider via fallback chain.
+                // If the provider rejects the request as too long before any
+                // output arrives, trim (and summarize) the oldest history once
+                // and retry.
+                let mut overflow_retried = false;
+                let (stream_provider_name, failover_warning, start_time, mut full_response, input_tokens, output_tokens, stop_reason) = loop {
+                    progress.start("thinking...");
+
+                    let request = agent_context.completion_request(&text)?;
+                    let stream_selection = match fallback_chain.select_stream(request) {
+                        Ok(selection) => selection,
+                        Err(e) => {
+                            progress.finish();
+                            // Handle "all providers down" clearly
+                            if matches!(&e, LlmError::Provider { message } if message.contains("bnity provider status")) {
+                                eprintln!("\n  {} All providers in the fallback chain are currently unavailable.", style("!").red().bold());
+                                eprintln!("  {} Run {} to check provider health.", style("Tip:").dim(), style("bnity provider status").cyan());
+                            } else {
+                                eprintln!("\n  {} LLM error: {e}", style("!").red().bold());
+                            }
+                            eprintln!("  {}", style("Type a message to retry, /exit to quit.").dim());
+                            continue 'chat;
                         }
-                        eprintln!("  {}", style("Type a message to retry, /exit to quit.").dim());
-                        continue;
-                    }
-                };
+                    };
 
-                let stream_provider_name = stream_selection.provider_name.clone();
+                    let stream_provider_name = stream_selection.provider_name.clone();
 
-                // Verbose: show provider selection on stderr
-                if verbose {
-                    print_verbose_provider_info(&stream_provider_name, provider_count);
-                }
+                    // Verbose: show provider selection on stderr
+                    if verbose {
+                        print_verbose_provider_info(&stream_provider_name, provider_count);
+                    }
 
-                // Print failover warning to stderr if we're on a non-primary provider
-                if let Some(ref warning) = stream_selection.failover_warning {
-                    print_failover_warning(warning);
-                }
+                    // Print failover warning to stderr if we're on a non-primary provider
+                    if let Some(ref warning) = stream_selection.failover_warning {
+                        print_failover_warning(warning);
+                    }
 
-                let start_time = Instant::now();
-                let mut stream = stream_selection.stream;
-                let mut full_response = String::new();
-                let mut input_tokens: u32 = 0;
-                let mut output_tokens: u32 = 0;
-                let mut stop_reason = "end_turn".to_string();
-                let mut first_token_received = false;
-                let mut had_error = false;
-                let mut stream_error: Option<LlmError> = None;
-
-                while let Some(event_result) = stream.next().await {
-                    match event_result {
-                        Ok(stream_event) => match stream_event {
-                            StreamEvent::TextDelta { text: delta, .. } => {
-                                if !first_token_received {
-                                    spinner.finish_and_clear();
-                                    first_token_received = true;
-                                    print!("\n  {} ", style(&bot.name).cyan().bold());
-                                    let _ = std::io::stdout().flush();
-                                }
-                                renderer.print_streaming_token(&delta);
-                                full_response.push_str(&delta);
-                            }
-                            StreamEvent::Usage(usage) => {
-                                input_tokens = usage.input_tokens;
-                                output_tokens = usage.output_tokens;
-                            }
-                            StreamEvent::MessageDelta { stop_reason: sr } => { stop_reason = sr.to_string(); }
-                            StreamEvent::Done => { break; }
-                            _ => {}
-                        },
-                        Err(e) => {
-                            spinner.finish_and_clear();
+                    let start_time = Instant::now();
+                    let outcome = consume_stream(
+                        stream_selection.stream,
+                        &renderer,
+                        &bot.name,
+                        &progress,
+                        !overflow_retried,
+                        &mut std::io::stdout(),
+                    )
+                    .await;
+
+                    let (max, requested) = match outcome.end {
+                        StreamEnd::Completed => {
+                            fallback_chain.record_stream_success(&stream_provider_name);
+                            break (
+                                stream_provider_name,
+                                stream_selection.failover_warning,
+                                start_time,
+                                outcome.response,
+                                outcome.input_tokens,
+                                outcome.output_tokens,
+                                outcome.stop_reason,
+                            );
+                        }
+                        StreamEnd::Failed(e) => {
                             eprintln!("\n  {} LLM error: {e}", style("!").red().bold());
                             eprintln!("  {}", style("Type a message to retry, /exit to quit.").dim());
-                            had_error = true;
-                            // Save error for health tracking
+                            // Report failover errors for health tracking
                             if ProviderHealth::is_failover_error(&e) {
-                                stream_error = Some(e);
+                                fallback_chain.record_stream_failure(&stream_provider_name, &e);
                             }
-                            break;
+                            continue 'chat;
                         }
-                    }
-                }
+                        StreamEnd::ContextOverflow { max, requested } => (max, requested),
+                    };
 
-                // Report stream outcome to fallback chain for health tracking
-                if had_error {
-                    if let Some(ref err) = stream_error {
-                        fallback_chain.record_stream_failure(&stream_provider_name, err);
+                    // Context overflow: compact history and retry once
+                    overflow_retried = true;
+                    let summarizer = state.create_single_provider(&model).await.ok();
+                    match ContextSummarizer::recover_from_overflow(summarizer.as_ref(), &mut agent_context, max, requested).await {
+                        Some(recovery) => {
+                            summarized_messages += recovery.removed_messages as u32;
+                            persist_rolling_summary(state, session_id, &agent_context, summarized_messages).await;
+                            eprintln!(
+                                "\n  {} Conversation too long for {}; {} {} older message{} and retrying.",
+                                style("!").yellow().bold(),
+                                style(&stream_provider_name).cyan(),
+                                if recovery.summarized { "summarized" } else { "dropped" },
+                                recovery.removed_messages,
+                                if recovery.removed_messages == 1 { "" } else { "s" },
+                            );
+                        }
+                        None => {
+                            eprintln!("\n  {} Message is too long for {} even without history.", style("!").red().bold(), style(&stream_provider_name).cyan());
+                            eprintln!("  {}", style("Type a shorter message to retry, /exit to quit.").dim());
+                            continue 'chat;
+                        }
                     }
-                } else {
-                    fallback_chain.record_stream_success(&stream_provider_name);
-                }
-
-                if !first_token_received && !had_error { spinner.finish_and_clear(); }
-                if had_error { continue; }
+                };
 
                 // Check if the response contains spawn instructions.
                 // If it does, hand off to the orchestrator for sub-agent execution.
                 let spawn_instruction = boternity_core::agent::spawner::parse_spawn_instructions(&full_response);
 
+                // Estimated spend for this exchange (initial response plus any sub-agents)
+                let mut turn_cost = estimate_cost(
+                    input_tokens,
+                    output_tokens,
+                    &model,
+                    &stream_provider_name,
+                    &state.global_config.provider_pricing,
+                );
+
                 if let Some(_spawn_instr) = spawn_instruction {
                     // Sub-agent execution via orchestrator.
-                    // Subscribe to the event bus for real-time rendering.
-                    let mut event_rx = state.event_bus.subscribe();
+                    // Subscribe to this request's events for real-time rendering.
+                    let request_id = Uuid::now_v7();
+                    let mut event_rx = state.event_bus.subscribe_request(request_id);
 
                     // Show pre-spawn text from the initial response
                     let pre_spawn_text = boternity_core::agent::spawner::extract_text_before_spawn(&full_response);
@@ -471,7 +622,7 @@ pub async fn run_chat_loop(
 
                     // Create a per-request budget and context
                     let request_budget = RequestBudget::new(request_budget_total);
-                    let request_ctx = RequestContext::new(Uuid::now_v7(), request_budget);
+                    let request_ctx = RequestContext::new(request_id, request_budget);
 
                     // Register the root cancellation token so Ctrl+C can cancel the tree.
                     // The orchestrator's RequestContext.cancellation is the root token.
@@ -618,12 +769,26 @@ pub async fn run_chat_loop(
                                 &stream_provider_name,
                                 &state.global_config.provider_pricing,
                             );
+                            turn_cost += cost;
                             println!("{}", budget_display::render_completion_stats(
                                 result.total_tokens_used,
                                 request_budget_total,
                                 cost,
                                 response_ms as f64 / 1000.0,
                             ));
+
+                            // Per-agent token breakdown (only when sub-agents ran)
+                            if result.agent_tree.iter().any(|n| !n.children.is_empty()) {
+                                let breakdown = budget_display::agent_token_breakdown(&result.agent_tree);
+                                if json {
+                                    let payload = serde_json::json!({ "agent_breakdown": breakdown });
+                                    print_json_with_warnings(&payload, failover_warning.iter().cloned().collect())?;
+                                } else {
+                                    for line in budget_display::render_agent_breakdown(&breakdown) {
+                                        println!("{line}");
+                                    }
+                                }
+                            }
                             println!();
 
                             // Persist messages
@@ -643,18 +808,22 @@ pub async fn run_chat_loop(
                                         boternity_types::llm::Message {
                                             role: boternity_types::llm::MessageRole::User,
                                             content: mem_ctx.task_description.clone(),
+                                            tool: None,
+                                            images: Vec::new(),
                                         },
                                         boternity_types::llm::Message {
                                             role: boternity_types::llm::MessageRole::Assistant,
                                             content: mem_ctx.response_text.clone(),
+                                            tool: None,
+                                            images: Vec::new(),
                                         },
                                     ];
                                     match SessionMemoryExtractor::extract(&extract_provider, &mem_messages, bot.id.0, session_id).await {
-                                        Ok(entries) => {
-                                            for mut entry in entries {
+                                        Ok(mut entries) => {
+                                            for entry in &mut entries {
                                                 entry.source_agent_id = Some(mem_ctx.agent_id);
-                                                let _ = state.chat_service.memory_repo().save_memory(&entry).await;
                                             }
+                                            let _ = state.chat_service.save_extracted_memories(&bot.id.0, &entries).await;
                                         }
                                         Err(e) => {
                                             debug!(error = %e, agent_id = %mem_ctx.agent_id, "Sub-agent memory extraction failed");
@@ -687,7 +856,7 @@ pub async fn run_chat_loop(
                     println!();
 
                     // Include provider name in stats footer when using a non-primary provider
-                    if stream_selection.failover_warning.is_some() {
+                    if failover_warning.is_some() {
                         renderer.print_stats_footer(output_tokens, response_ms, &format!("{} via {}", model, stream_provider_name));
                     } else {
                         renderer.print_stats_footer(output_tokens, response_ms, &model);
@@ -701,7 +870,11 @@ pub async fn run_chat_loop(
                 }
 
                 session_manager.add_token_usage(input_tokens, output_tokens);
-                let _ = state.chat_service.update_session_tokens(&session_id, input_tokens, output_tokens).await;
+                session_manager.add_cost(turn_cost);
+                let _ = state.chat_service.update_session_usage(&session_id, input_tokens, output_tokens, turn_cost).await;
+                if let Err(exceeded) = session_manager.check_cost_budget() {
+                    eprintln!("  {} {exceeded}. Further messages in this session are blocked.", style("!").yellow().bold());
+                }
                 session_manager.increment_turn();
 
                 // Title generation after first exchange
@@ -723,42 +896,221 @@ pub async fn run_chat_loop(
                 // Periodic memory extraction
                 if session_manager.should_extract_memory() {
                     info!(turn = session_manager.turn_count(), "Running periodic memory extraction");
-                    if let Ok(extract_provider) = state.create_single_provider(&model).await {
-                        let messages = agent_context.build_messages();
-                        match SessionMemoryExtractor::extract(&extract_provider, &messages, bot.id.0, session_id).await {
-                            Ok(entries) => {
-                                for entry in entries { let _ = state.chat_service.memory_repo().save_memory(&entry).await; }
-                            }
-                            Err(e) => { warn!(error = %e, "Periodic memory extraction failed"); }
-                        }
+                    if let Err(e) = extract_session_memories(state, &model, &agent_context, bot.id.0, session_id).await {
+                        warn!(error = %e, "Periodic memory extraction failed");
                     }
+                    session_manager.mark_memory_extracted();
                 }
 
                 // Context summarization check
                 if agent_context.should_summarize() {
-                    info!("Context window approaching limit, summarization needed");
+                    info!("Context window approaching limit, summarizing older messages");
+                    let summarizer = state.create_single_provider(&model).await.ok();
+                    // No overflow sizes: keeps roughly the newer half
+                    let keep = agent_context.overflow_keep_count(0, 0);
+                    if let Some(compaction) = ContextSummarizer::compact(summarizer.as_ref(), &mut agent_context, keep).await {
+                        summarized_messages += compaction.removed_messages as u32;
+                        persist_rolling_summary(state, session_id, &agent_context, summarized_messages).await;
+                    }
                 }
             }
         }
     }
 
-    // Final memory extraction
+    // Final memory extraction (left pending for retry at next startup if it fails)
     info!("Running final memory extraction");
-    let messages = agent_context.build_messages();
-    if !messages.is_empty() {
-        if let Ok(extract_provider) = state.create_single_provider(&model).await {
-            match SessionMemoryExtractor::extract(&extract_provider, &messages, bot.id.0, session_id).await {
-                Ok(entries) => {
-                    let count = entries.len();
-                    for entry in entries { let _ = state.chat_service.memory_repo().save_memory(&entry).await; }
-                    if count > 0 { info!(count, "Memories extracted at session end"); }
-                }
-                Err(e) => { warn!(error = %e, "Final memory extraction failed"); }
+    if !agent_context.build_messages().is_empty() {
+        match extract_session_memories(state, &model, &agent_context, bot.id.0, session_id).await {
+            Ok(count) => {
+                if count > 0 { info!(count, "Memories extracted at session end"); }
             }
+            Err(e) => { warn!(error = %e, "Final memory extraction failed; will retry on next startup"); }
         }
     }
 
     let _ = state.chat_service.end_session(&session_id).await;
+    if let Err(e) = state.bot_service.touch_activity(&bot.id).await {
+        warn!(error = %e, "Failed to update bot last_active_at");
+    }
     session_manager.mark_completed();
     Ok(())
 }
+
+/// Extract memories from the conversation so far and save them.
+///
+/// The session is marked pending first and cleared on success, so a failure
+/// or crash leaves it to [`AppState::retry_pending_extractions`].
+///
+/// Returns the number of memories saved.
+async fn extract_session_memories(
+    state: &AppState,
+    model: &str,
+    agent_context: &AgentContext,
+    bot_id: uuid::Uuid,
+    session_id: uuid::Uuid,
+) -> anyhow::Result<usize> {
+    let pending = state.chat_service.begin_extraction(bot_id, session_id).await?;
+    let result = async {
+        let extract_provider = state.create_single_provider(model).await?;
+        let messages = agent_context.build_messages();
+        let entries = SessionMemoryExtractor::extract(&extract_provider, &messages, bot_id, session_id).await?;
+        anyhow::Ok(state.chat_service.save_extracted_memories(&bot_id, &entries).await?)
+    }
+    .await;
+
+    match &result {
+        Ok(_) => state.chat_service.finish_extraction(&pending).await?,
+        Err(e) => state.chat_service.fail_extraction(&pending, &format!("{e:#}")).await?,
+    }
+    result
+}
+
+/// Save the context's rolling summary to the session so a resumed session
+/// starts from it instead of replaying the condensed messages.
+async fn persist_rolling_summary(
+    state: &AppState,
+    session_id: uuid::Uuid,
+    agent_context: &AgentContext,
+    summarized_messages: u32,
+) {
+    if let Err(e) = state
+        .chat_service
+        .update_rolling_summary(&session_id, agent_context.history_summary.clone(), summarized_messages)
+        .await
+    {
+        warn!(error = %e, "Failed to persist rolling summary");
+    }
+}
+
+/// Parse a `--max-cost` value in USD (e.g., `0.50` or `$2`).
+pub fn parse_max_cost(s: &str) -> Result<f64, String> {
+    let trimmed = s.trim().trim_start_matches('$');
+    let value: f64 = trimmed
+        .parse()
+        .map_err(|_| format!("invalid cost '{s}' (expected a USD amount like 0.50)"))?;
+    if !value.is_finite() || value <= 0.0 {
+        return Err("max cost must be a positive USD amount".to_string());
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use boternity_types::llm::{StopReason, Usage};
+
+    use crate::cli::progress::NoopProgress;
+
+    /// Counts how often the indicator was cleared.
+    #[derive(Default)]
+    struct CountingProgress {
+        finished: AtomicUsize,
+    }
+
+    impl ProgressReporter for CountingProgress {
+        fn start(&self, _message: &str) {}
+
+        fn finish(&self) {
+            self.finished.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    fn text(delta: &str) -> Result<StreamEvent, LlmError> {
+        Ok(StreamEvent::TextDelta {
+            index: 0,
+            text: delta.to_string(),
+        })
+    }
+
+    async fn consume(
+        events: Vec<Result<StreamEvent, LlmError>>,
+        progress: &dyn ProgressReporter,
+        allow_overflow_retry: bool,
+    ) -> (StreamOutcome, String) {
+        let renderer = ChatRenderer::with_colors(None, false);
+        let mut out = Vec::new();
+        let outcome = consume_stream(
+            futures_util::stream::iter(events),
+            &renderer,
+            "Luna",
+            progress,
+            allow_overflow_retry,
+            &mut out,
+        )
+        .await;
+        (outcome, String::from_utf8(out).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_stream_renders_response_and_usage() {
+        let events = vec![
+            Ok(StreamEvent::Connected),
+            text("Hello "),
+            text("there.\nHow can I help?"),
+            Ok(StreamEvent::Usage(Usage {
+                input_tokens: 12,
+                output_tokens: 7,
+                ..Default::default()
+            })),
+            Ok(StreamEvent::MessageDelta {
+                stop_reason: StopReason::MaxTokens,
+            }),
+            Ok(StreamEvent::Done),
+            text("ignored after done"),
+        ];
+
+        let (outcome, printed) = consume(events, &NoopProgress, true).await;
+
+        assert!(matches!(outcome.end, StreamEnd::Completed));
+        assert_eq!(outcome.response, "Hello there.\nHow can I help?");
+        assert_eq!((outcome.input_tokens, outcome.output_tokens), (12, 7));
+        assert_eq!(outcome.stop_reason, "max_tokens");
+        assert_eq!(printed, "\n  Luna Hello there.\nHow can I help?\n");
+    }
+
+    #[tokio::test]
+    async fn test_stream_failure_keeps_partial_response() {
+        let events = vec![
+            text("Partial"),
+            Err(LlmError::Provider {
+                message: "connection reset".to_string(),
+            }),
+        ];
+
+        let (outcome, printed) = consume(events, &NoopProgress, true).await;
+
+        assert!(matches!(outcome.end, StreamEnd::Failed(LlmError::Provider { .. })));
+        assert_eq!(outcome.response, "Partial");
+        assert!(printed.ends_with("Partial\n"));
+    }
+
+    #[tokio::test]
+    async fn test_context_overflow_only_retried_once() {
+        let overflow = || {
+            vec![Err(LlmError::ContextLengthExceeded {
+                max: 100,
+                requested: 150,
+            })]
+        };
+
+        let progress = CountingProgress::default();
+        let (outcome, printed) = consume(overflow(), &progress, true).await;
+        assert!(matches!(
+            outcome.end,
+            StreamEnd::ContextOverflow {
+                max: 100,
+                requested: 150
+            }
+        ));
+        assert!(printed.is_empty());
+        assert!(progress.finished.load(Ordering::SeqCst) > 0);
+
+        let (outcome, _) = consume(overflow(), &NoopProgress, false).await;
+        assert!(matches!(
+            outcome.end,
+            StreamEnd::Failed(LlmError::ContextLengthExceeded { .. })
+        ));
+    }
+}
@@ -1,10 +1,14 @@
 //! Terminal markdown rendering with syntax-highlighted code blocks.
 //!
 //! `ChatRenderer` combines `termimad` for prose and `syntect` for code block
-//! syntax highlighting. During streaming, tokens are printed raw; once the
-//! full response is collected, it is rendered as formatted markdown.
-
-use std::io::Write;
+//! syntax highlighting. During streaming, a [`MarkdownStream`] buffers tokens
+//! and hands back each block once it is complete -- a prose line (heading,
+//! list item, paragraph line) when its newline arrives, a code block when its
+//! closing fence arrives -- so every block is rendered exactly once and
+//! nothing is reprinted.
+//!
+//! When color is off (see [`crate::cli::color`]) the renderer prints the
+//! markdown source unstyled.
 
 use crossterm::style::Color;
 use syntect::easy::HighlightLines;
@@ -18,11 +22,20 @@ pub struct ChatRenderer {
     skin: MadSkin,
     syntax_set: SyntaxSet,
     theme_set: ThemeSet,
+    /// Emit plain text with no escape codes.
+    plain: bool,
 }
 
 impl ChatRenderer {
     /// Create a new renderer with an optional accent color for the bot.
+    ///
+    /// Follows the global color setting.
     pub fn new(accent_color: Option<Color>) -> Self {
+        Self::with_colors(accent_color, console::colors_enabled())
+    }
+
+    /// Create a renderer with color explicitly on or off.
+    pub fn with_colors(accent_color: Option<Color>, colors: bool) -> Self {
         let mut skin = MadSkin::default_dark();
 
         // Apply accent color to headers and bold text if provided
@@ -41,6 +54,7 @@ impl ChatRenderer {
             skin,
             syntax_set: SyntaxSet::load_defaults_newlines(),
             theme_set: ThemeSet::load_defaults(),
+            plain: !colors,
         }
     }
 
@@ -49,46 +63,48 @@ impl ChatRenderer {
     /// Code fences with a language tag are highlighted via syntect; everything
     /// else is rendered through termimad.
     pub fn render_final(&self, markdown: &str) -> String {
-        let mut output = String::new();
-        let mut in_code_block = false;
-        let mut code_lang = String::new();
-        let mut code_buf = String::new();
-
-        for line in markdown.lines() {
-            if line.starts_with("```") && !in_code_block {
-                // Opening code fence
-                in_code_block = true;
-                code_lang = line.trim_start_matches('`').trim().to_string();
-                code_buf.clear();
-            } else if line.starts_with("```") && in_code_block {
-                // Closing code fence -- render the accumulated code
-                in_code_block = false;
-                let highlighted = self.highlight_code(&code_buf, &code_lang);
-                output.push_str(&highlighted);
-                output.push('\n');
-            } else if in_code_block {
-                code_buf.push_str(line);
-                code_buf.push('\n');
-            } else {
-                // Prose line -- render through termimad
-                let rendered = self.skin.term_text(line);
-                output.push_str(&format!("{rendered}"));
-            }
-        }
+        let mut stream = MarkdownStream::default();
+        let mut output = self.render_blocks(&stream.push(markdown));
+        output.push_str(&self.render_blocks(&stream.finish()));
+        output
+    }
 
-        // Handle unclosed code block
-        if in_code_block && !code_buf.is_empty() {
-            let highlighted = self.highlight_code(&code_buf, &code_lang);
-            output.push_str(&highlighted);
-        }
+    /// Render the blocks completed by a streaming token.
+    ///
+    /// Incomplete trailing text stays buffered in `stream` until a later
+    /// token completes it or [`finish_streaming_markdown`](Self::finish_streaming_markdown)
+    /// flushes it. Concatenating every chunk equals [`render_final`](Self::render_final)
+    /// on the full response.
+    pub fn render_streaming_markdown(&self, stream: &mut MarkdownStream, token: &str) -> String {
+        self.render_blocks(&stream.push(token))
+    }
 
-        output
+    /// Render whatever is still buffered once the stream has ended.
+    pub fn finish_streaming_markdown(&self, stream: &mut MarkdownStream) -> String {
+        self.render_blocks(&stream.finish())
     }
 
-    /// Print a single streaming token (raw, no formatting).
-    pub fn print_streaming_token(&self, token: &str) {
-        print!("{token}");
-        let _ = std::io::stdout().flush();
+    /// Render completed markdown blocks.
+    fn render_blocks(&self, blocks: &[MarkdownBlock]) -> String {
+        let mut output = String::new();
+        for block in blocks {
+            match block {
+                MarkdownBlock::Prose(line) if self.plain => {
+                    output.push_str(line);
+                    output.push('\n');
+                }
+                MarkdownBlock::Prose(line) => {
+                    output.push_str(&format!("{}", self.skin.term_text(line)));
+                }
+                MarkdownBlock::Code { lang, code, closed } => {
+                    output.push_str(&self.highlight_code(code, lang));
+                    if *closed {
+                        output.push('\n');
+                    }
+                }
+            }
+        }
+        output
     }
 
     /// Print the stats footer after a bot response.
@@ -110,6 +126,14 @@ impl ChatRenderer {
 
     /// Highlight a code block using syntect.
     fn highlight_code(&self, code: &str, lang: &str) -> String {
+        if self.plain {
+            let mut output = format!("  --- {lang} ---\n");
+            for line in code.lines() {
+                output.push_str(&format!("  {line}\n"));
+            }
+            return output;
+        }
+
         let syntax = if lang.is_empty() {
             self.syntax_set.find_syntax_plain_text()
         } else {
@@ -149,3 +173,173 @@ impl ChatRenderer {
         }
     }
 }
+
+/// A complete markdown block ready to render.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MarkdownBlock {
+    /// One line outside a code fence (rendered through termimad).
+    Prose(String),
+    /// A fenced code block. `closed` is false when the response ended
+    /// before the closing fence.
+    Code {
+        lang: String,
+        code: String,
+        closed: bool,
+    },
+}
+
+/// Splits streamed markdown into complete blocks.
+///
+/// Text after the last newline is held back because a partial line may
+/// still turn into a heading, list item, or code fence.
+#[derive(Debug, Default)]
+pub struct MarkdownStream {
+    /// Text received after the last newline.
+    partial: String,
+    /// `(lang, code)` of the code block being accumulated, if inside a fence.
+    code: Option<(String, String)>,
+}
+
+impl MarkdownStream {
+    /// Feed a token and return the blocks it completed.
+    pub fn push(&mut self, token: &str) -> Vec<MarkdownBlock> {
+        self.partial.push_str(token);
+        let mut blocks = Vec::new();
+        while let Some(pos) = self.partial.find('\n') {
+            let mut line: String = self.partial.drain(..=pos).collect();
+            line.pop();
+            if line.ends_with('\r') {
+                line.pop();
+            }
+            self.feed_line(&line, &mut blocks);
+        }
+        blocks
+    }
+
+    /// Flush the trailing partial line and any unclosed code block.
+    ///
+    /// An unclosed code block is only emitted if it has content.
+    pub fn finish(&mut self) -> Vec<MarkdownBlock> {
+        let mut blocks = Vec::new();
+        if !self.partial.is_empty() {
+            let line = std::mem::take(&mut self.partial);
+            self.feed_line(&line, &mut blocks);
+        }
+        if let Some((lang, code)) = self.code.take() {
+            if !code.is_empty() {
+                blocks.push(MarkdownBlock::Code {
+                    lang,
+                    code,
+                    closed: false,
+                });
+            }
+        }
+        blocks
+    }
+
+    fn feed_line(&mut self, line: &str, blocks: &mut Vec<MarkdownBlock>) {
+        let is_fence = line.starts_with("```");
+        match self.code.take() {
+            // Opening code fence
+            None if is_fence => {
+                let lang = line.trim_start_matches('`').trim().to_string();
+                self.code = Some((lang, String::new()));
+            }
+            None => blocks.push(MarkdownBlock::Prose(line.to_string())),
+            // Closing code fence -- the block is complete
+            Some((lang, code)) if is_fence => blocks.push(MarkdownBlock::Code {
+                lang,
+                code,
+                closed: true,
+            }),
+            Some((lang, mut code)) => {
+                code.push_str(line);
+                code.push('\n');
+                self.code = Some((lang, code));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RESPONSE: &str = "# Plan\n\nSteps:\n- first\n- second\n\n```rust\nfn main() {}\n```\nDone **now**.";
+
+    fn prose(line: &str) -> MarkdownBlock {
+        MarkdownBlock::Prose(line.to_string())
+    }
+
+    #[test]
+    fn test_blocks_complete_mid_stream() {
+        let mut stream = MarkdownStream::default();
+        assert!(stream.push("# Pl").is_empty());
+        assert_eq!(stream.push("an\n\nSte"), vec![prose("# Plan"), prose("")]);
+        assert_eq!(stream.push("ps:\n- fir"), vec![prose("Steps:")]);
+        assert_eq!(stream.push("st\n"), vec![prose("- first")]);
+
+        // Code block is held until the closing fence
+        assert!(stream.push("```rust\nfn main()").is_empty());
+        assert!(stream.push(" {}\n``").is_empty());
+        assert_eq!(
+            stream.push("`\nDone"),
+            vec![MarkdownBlock::Code {
+                lang: "rust".to_string(),
+                code: "fn main() {}\n".to_string(),
+                closed: true,
+            }]
+        );
+        assert_eq!(stream.finish(), vec![prose("Done")]);
+        assert!(stream.finish().is_empty());
+    }
+
+    #[test]
+    fn test_unclosed_code_block_flushed_on_finish() {
+        let mut stream = MarkdownStream::default();
+        assert!(stream.push("```py\nprint(1)\r\nprint(2)").is_empty());
+        assert_eq!(
+            stream.finish(),
+            vec![MarkdownBlock::Code {
+                lang: "py".to_string(),
+                code: "print(1)\nprint(2)\n".to_string(),
+                closed: false,
+            }]
+        );
+
+        // An empty unclosed fence renders nothing
+        let mut stream = MarkdownStream::default();
+        stream.push("```\n");
+        assert!(stream.finish().is_empty());
+    }
+
+    #[test]
+    fn test_streamed_output_matches_final_render_for_any_split() {
+        let renderer = ChatRenderer::new(None);
+        let expected = renderer.render_final(RESPONSE);
+        assert!(!expected.is_empty());
+
+        for chunk_size in [1, 2, 3, 7, 16, RESPONSE.len()] {
+            let mut stream = MarkdownStream::default();
+            let mut output = String::new();
+            let chars: Vec<char> = RESPONSE.chars().collect();
+            for chunk in chars.chunks(chunk_size) {
+                let token: String = chunk.iter().collect();
+                output.push_str(&renderer.render_streaming_markdown(&mut stream, &token));
+            }
+            output.push_str(&renderer.finish_streaming_markdown(&mut stream));
+            assert_eq!(output, expected, "chunk size {chunk_size}");
+        }
+    }
+
+    #[test]
+    fn test_plain_render_has_no_ansi_escapes() {
+        let renderer = ChatRenderer::with_colors(Some(Color::Cyan), false);
+        let output = renderer.render_final(RESPONSE);
+
+        assert!(!output.contains('\x1b'));
+        assert!(output.contains("# Plan\n"));
+        assert!(output.contains("  fn main() {}\n"));
+        assert!(output.contains("Done **now**."));
+    }
+}
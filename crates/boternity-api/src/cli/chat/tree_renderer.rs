@@ -79,6 +79,40 @@ pub fn render_agent_completion(
     )
 }
 
+/// Render one line of the per-agent token breakdown.
+///
+/// Example: `  +-- agent-1: Research quantum... 2,450 tokens (36.4%)`
+///
+/// Uses the same branch layout as [`render_agent_header`].
+pub fn render_breakdown_line(
+    depth: u8,
+    index: usize,
+    total: usize,
+    label: &str,
+    task: &str,
+    tokens: u32,
+    share_pct: f64,
+) -> String {
+    let indent = "  ".repeat(depth as usize);
+    let branch = if index == total.saturating_sub(1) {
+        LAST
+    } else {
+        BRANCH
+    };
+    let task = if task.is_empty() {
+        String::new()
+    } else {
+        format!(": {}", truncate_task(task, 40))
+    };
+
+    format!(
+        "{indent}{branch} {}{task} {} {}",
+        style(label).cyan(),
+        style(format!("{} tokens", format_tokens_human(tokens))).dim(),
+        style(format!("({share_pct:.1}%)")).dim(),
+    )
+}
+
 /// Render a depth limit warning.
 ///
 /// Example: `  ! Depth limit reached (attempted depth 4, max 3)`
@@ -208,6 +242,13 @@ mod tests {
         assert!(line.contains("3.2s"));
     }
 
+    #[test]
+    fn render_breakdown_line_contains_tokens_and_share() {
+        let line = render_breakdown_line(1, 0, 2, "agent-1", "Research", 2450, 36.42);
+        let plain = console::strip_ansi_codes(&line);
+        assert_eq!(plain, format!("  {BRANCH} agent-1: Research 2,450 tokens (36.4%)"));
+    }
+
     #[test]
     fn render_depth_limit_warning_contains_values() {
         let warning = render_depth_limit_warning(4, 3);
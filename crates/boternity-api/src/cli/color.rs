@@ -0,0 +1,74 @@
+//! Whether styled output uses color.
+//!
+//! Color is on only when the stream is a terminal, `NO_COLOR` is unset (or
+//! empty, per <https://no-color.org>), and `--no-color` was not passed.
+//! [`init`] applies the decision to `console`, which every `style(...)` call
+//! site consults; tables built with [`new_table`] and the chat renderer
+//! follow it too.
+
+use std::ffi::OsStr;
+use std::io::IsTerminal;
+
+use comfy_table::{presets, ContentArrangement, Table};
+
+/// Decide whether output may be colored.
+pub fn should_color(no_color_flag: bool, no_color_env: Option<&OsStr>, is_terminal: bool) -> bool {
+    let env_disabled = no_color_env.is_some_and(|value| !value.is_empty());
+    is_terminal && !no_color_flag && !env_disabled
+}
+
+/// Apply the color policy for stdout and stderr. Call once at startup.
+pub fn init(no_color_flag: bool) {
+    let env = std::env::var_os("NO_COLOR");
+    console::set_colors_enabled(should_color(
+        no_color_flag,
+        env.as_deref(),
+        std::io::stdout().is_terminal(),
+    ));
+    console::set_colors_enabled_stderr(should_color(
+        no_color_flag,
+        env.as_deref(),
+        std::io::stderr().is_terminal(),
+    ));
+}
+
+/// A table in the CLI's standard style, uncolored when color is off.
+pub fn new_table() -> Table {
+    let mut table = Table::new();
+    table.load_preset(presets::UTF8_FULL_CONDENSED);
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+    if !console::colors_enabled() {
+        table.force_no_tty();
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use comfy_table::{Cell, Color};
+
+    #[test]
+    fn test_should_color() {
+        assert!(should_color(false, None, true));
+        assert!(should_color(false, Some(OsStr::new("")), true));
+        assert!(!should_color(false, Some(OsStr::new("1")), true));
+        assert!(!should_color(true, None, true));
+        assert!(!should_color(false, None, false));
+    }
+
+    #[test]
+    fn test_no_color_output_has_no_ansi_escapes() {
+        console::set_colors_enabled(false);
+
+        let styled = format!("{}", console::style("ok").green().bold());
+        assert_eq!(styled, "ok");
+
+        let mut table = new_table();
+        table.set_header(vec![Cell::new("Name").fg(Color::White)]);
+        table.add_row(vec![Cell::new("luna").fg(Color::Cyan)]);
+        let rendered = table.to_string();
+        assert!(rendered.contains("luna"));
+        assert!(!rendered.contains('\x1b'));
+    }
+}
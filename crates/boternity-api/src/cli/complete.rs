@@ -0,0 +1,339 @@
+//! Dynamic shell completion values.
+//!
+//! The scripts from `bnity completions <shell>` complete subcommands and flags
+//! statically, then call the hidden `bnity __complete` command for values
+//! that live in the database: bot slugs, secret keys, and session ids.
+//!
+//! ```bash
+//! bnity __complete bots             # one slug per line
+//! bnity __complete -- chat luna --resume   # infer the context from the words typed
+//! ```
+
+use anyhow::Result;
+use clap::ValueEnum;
+use clap_complete::{Shell, generate};
+
+use boternity_core::service::secret::SecretService;
+use boternity_types::secret::SecretScope;
+
+use crate::state::{AppState, ConcreteBotService, ConcreteChatService};
+
+/// Sessions offered per bot, most recent first.
+const SESSIONS_PER_BOT: i64 = 20;
+
+/// The kind of value being completed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CompleteContext {
+    /// Bot slugs.
+    Bots,
+    /// Global secret key names.
+    Secrets,
+    /// Chat session ids.
+    Sessions,
+}
+
+/// Command paths whose next positional argument takes a dynamic value.
+const POSITIONAL_SLOTS: &[(&str, CompleteContext)] = &[
+    ("show", CompleteContext::Bots),
+    ("chat", CompleteContext::Bots),
+    ("check", CompleteContext::Bots),
+    ("sessions", CompleteContext::Bots),
+    ("remember", CompleteContext::Bots),
+    ("forget", CompleteContext::Bots),
+    ("memories reindex", CompleteContext::Bots),
+    ("memories compact", CompleteContext::Bots),
+    ("delete bot", CompleteContext::Bots),
+    ("clone bot", CompleteContext::Bots),
+    ("bot status", CompleteContext::Bots),
+    ("bot restore", CompleteContext::Bots),
+    ("soul edit", CompleteContext::Bots),
+    ("soul history", CompleteContext::Bots),
+    ("soul diff", CompleteContext::Bots),
+    ("soul rollback", CompleteContext::Bots),
+    ("soul verify", CompleteContext::Bots),
+    ("secret show", CompleteContext::Secrets),
+    ("set secret", CompleteContext::Secrets),
+    ("export session", CompleteContext::Sessions),
+    ("delete session", CompleteContext::Sessions),
+];
+
+/// Flags whose value is dynamic.
+const FLAG_SLOTS: &[(&str, CompleteContext)] = &[
+    ("--resume", CompleteContext::Sessions),
+    ("--reconfigure", CompleteContext::Bots),
+];
+
+/// Print the completion script for `shell`, with dynamic value lookups for
+/// the shells that support them.
+pub fn print_completions(shell: Shell) {
+    let mut cmd = <super::Cli as clap::CommandFactory>::command();
+    generate(shell, &mut cmd, "bnity", &mut std::io::stdout());
+    if let Some(glue) = dynamic_glue(shell) {
+        print!("{glue}");
+    }
+}
+
+/// Shell code that consults `bnity __complete` before the static completions.
+fn dynamic_glue(shell: Shell) -> Option<&'static str> {
+    match shell {
+        Shell::Bash => Some(
+            r#"
+_bnity_dynamic() {
+    local cur="${COMP_WORDS[COMP_CWORD]}" values
+    values="$(bnity __complete -- "${COMP_WORDS[@]:1:COMP_CWORD-1}" 2>/dev/null)"
+    if [[ -n "$values" ]]; then
+        COMPREPLY=($(compgen -W "$values" -- "$cur"))
+    else
+        _bnity "$@"
+    fi
+}
+complete -F _bnity_dynamic -o bashdefault -o default bnity
+"#,
+        ),
+        Shell::Zsh => Some(
+            r#"
+_bnity_dynamic() {
+    local -a values
+    values=(${(f)"$(bnity __complete -- ${words[2,CURRENT-1]} 2>/dev/null)"})
+    if (( ${#values} )); then
+        compadd -a values
+    else
+        _bnity "$@"
+    fi
+}
+compdef _bnity_dynamic bnity
+"#,
+        ),
+        Shell::Fish => Some(
+            r#"
+function __bnity_dynamic
+    bnity __complete -- (commandline -opc)[2..-1] 2>/dev/null
+end
+complete -c bnity -f -a '(__bnity_dynamic)'
+"#,
+        ),
+        _ => None,
+    }
+}
+
+/// Handle `bnity __complete`.
+///
+/// Prints one candidate per line. When only the typed words are given and
+/// they don't end at a dynamic slot, prints nothing without opening the
+/// database, so static completions stay fast.
+pub async fn run_complete(context: Option<CompleteContext>, words: &[String]) -> Result<()> {
+    let (context, bot) = match context {
+        Some(context) => (context, None),
+        None => match context_for(words) {
+            Some(slot) => slot,
+            None => return Ok(()),
+        },
+    };
+
+    let state = AppState::init().await?;
+    let values = match context {
+        CompleteContext::Bots => bot_slugs(&state.bot_service).await?,
+        CompleteContext::Secrets => secret_keys(&state.secret_service).await?,
+        CompleteContext::Sessions => {
+            session_ids(&state.bot_service, &state.chat_service, bot.as_deref()).await?
+        }
+    };
+    for value in values {
+        println!("{value}");
+    }
+    Ok(())
+}
+
+/// Work out what the next word completes from the words typed after `bnity`.
+///
+/// Returns the context and, for `chat <slug> --resume`, the bot whose
+/// sessions to offer.
+fn context_for(words: &[String]) -> Option<(CompleteContext, Option<String>)> {
+    let positionals: Vec<&str> = words
+        .iter()
+        .map(String::as_str)
+        .filter(|word| !word.starts_with('-'))
+        .collect();
+
+    if let Some(last) = words.last()
+        && let Some((_, context)) = FLAG_SLOTS.iter().find(|(flag, _)| flag == last)
+    {
+        let bot = match positionals.as_slice() {
+            ["chat", slug, ..] => Some(slug.to_string()),
+            _ => None,
+        };
+        return Some((*context, bot));
+    }
+
+    let path = positionals.join(" ");
+    POSITIONAL_SLOTS
+        .iter()
+        .find(|(slot, _)| *slot == path)
+        .map(|(_, context)| (*context, None))
+}
+
+async fn bot_slugs(bot_service: &ConcreteBotService) -> Result<Vec<String>> {
+    Ok(bot_service
+        .list_bots(None)
+        .await?
+        .into_iter()
+        .map(|bot| bot.slug)
+        .collect())
+}
+
+async fn secret_keys(secret_service: &SecretService) -> Result<Vec<String>> {
+    Ok(secret_service
+        .list_secrets(&SecretScope::Global)
+        .await?
+        .into_iter()
+        .map(|entry| entry.key.0)
+        .collect())
+}
+
+async fn session_ids(
+    bot_service: &ConcreteBotService,
+    chat_service: &ConcreteChatService,
+    bot: Option<&str>,
+) -> Result<Vec<String>> {
+    let bots = match bot {
+        Some(slug) => vec![bot_service.get_bot_by_slug(slug).await?],
+        None => bot_service.list_bots(None).await?,
+    };
+    let mut ids = Vec::new();
+    for bot in bots {
+        let sessions = chat_service
+            .list_sessions(&bot.id.0, Some(SESSIONS_PER_BOT), None)
+            .await?;
+        ids.extend(sessions.into_iter().map(|session| session.id.to_string()));
+    }
+    Ok(ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    use boternity_core::chat::service::ChatService;
+    use boternity_core::repository::secret::SecretProvider;
+    use boternity_core::service::bot::BotService;
+    use boternity_core::service::soul::SoulService;
+    use boternity_infra::crypto::hash::IntegrityHasher;
+    use boternity_infra::crypto::vault::VaultCrypto;
+    use boternity_infra::filesystem::LocalFileSystem;
+    use boternity_infra::secret::VaultSecretProvider;
+    use boternity_infra::sqlite::bot::SqliteBotRepository;
+    use boternity_infra::sqlite::chat::SqliteChatRepository;
+    use boternity_infra::sqlite::memory::SqliteMemoryRepository;
+    use boternity_infra::sqlite::pool::DatabasePool;
+    use boternity_infra::sqlite::secret::SqliteSecretRepository;
+    use boternity_infra::sqlite::soul::SqliteSoulRepository;
+    use boternity_types::bot::CreateBotRequest;
+
+    fn words(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    async fn pool(dir: &tempfile::TempDir) -> DatabasePool {
+        let url = format!("sqlite://{}?mode=rwc", dir.path().join("test.db").display());
+        DatabasePool::new(&url).await.unwrap()
+    }
+
+    fn bot_service(pool: &DatabasePool, dir: &tempfile::TempDir) -> ConcreteBotService {
+        BotService::new(
+            SqliteBotRepository::new(pool.clone()),
+            SoulService::new(
+                SqliteSoulRepository::new(pool.clone()),
+                LocalFileSystem::new(),
+                IntegrityHasher::default(),
+            ),
+            dir.path().to_path_buf(),
+        )
+    }
+
+    fn create_request(name: &str) -> CreateBotRequest {
+        CreateBotRequest {
+            name: name.to_string(),
+            description: None,
+            category: None,
+            tags: None,
+        }
+    }
+
+    #[test]
+    fn test_context_for_positional_and_flag_slots() {
+        assert_eq!(
+            context_for(&words("show")),
+            Some((CompleteContext::Bots, None))
+        );
+        assert_eq!(
+            context_for(&words("--json delete bot")),
+            Some((CompleteContext::Bots, None))
+        );
+        assert_eq!(
+            context_for(&words("secret show")),
+            Some((CompleteContext::Secrets, None))
+        );
+        assert_eq!(
+            context_for(&words("chat luna --resume")),
+            Some((CompleteContext::Sessions, Some("luna".to_string())))
+        );
+        // The slot is already filled, or takes no dynamic value.
+        assert_eq!(context_for(&words("show luna")), None);
+        assert_eq!(context_for(&words("list")), None);
+        assert_eq!(context_for(&[]), None);
+    }
+
+    #[tokio::test]
+    async fn test_complete_returns_current_slugs_and_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let pool = pool(&dir).await;
+        let bots = bot_service(&pool, &dir);
+        bots.create_bot(create_request("Luna")).await.unwrap();
+        bots.create_bot(create_request("Research Assistant"))
+            .await
+            .unwrap();
+
+        let mut slugs = bot_slugs(&bots).await.unwrap();
+        slugs.sort();
+        assert_eq!(slugs, vec!["luna", "research-assistant"]);
+
+        let vault = VaultSecretProvider::new(
+            SqliteSecretRepository::new(pool.clone()),
+            VaultCrypto::new(&[6u8; 32]),
+        );
+        vault
+            .set("ANTHROPIC_API_KEY", "sk-test", &SecretScope::Global)
+            .await
+            .unwrap();
+        let secrets = SecretService::new(vec![Arc::new(vault)]);
+        assert_eq!(
+            secret_keys(&secrets).await.unwrap(),
+            vec!["ANTHROPIC_API_KEY"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_complete_sessions_filtered_by_bot() {
+        let dir = tempfile::tempdir().unwrap();
+        let pool = pool(&dir).await;
+        let bots = bot_service(&pool, &dir);
+        let luna = bots.create_bot(create_request("Luna")).await.unwrap();
+        let other = bots.create_bot(create_request("Other")).await.unwrap();
+        let chat = ChatService::new(
+            SqliteChatRepository::new(pool.clone()),
+            SqliteMemoryRepository::new(pool),
+        );
+        let session = chat
+            .create_session(luna.id.0, "claude-test".to_string())
+            .await
+            .unwrap();
+        chat.create_session(other.id.0, "claude-test".to_string())
+            .await
+            .unwrap();
+
+        let ids = session_ids(&bots, &chat, Some("luna")).await.unwrap();
+        assert_eq!(ids, vec![session.id.to_string()]);
+        assert_eq!(session_ids(&bots, &chat, None).await.unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,140 @@
+//! Database maintenance CLI subcommands.
+//!
+//! `bnity db migrate` applies pending schema migrations explicitly (they also
+//! run automatically at startup); `--dry-run` lists them without applying.
+//! `backup`, `vacuum`, and `stats` are safe to run while the server is up.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use clap::Subcommand;
+use comfy_table::{Cell, Color};
+use console::style;
+
+use boternity_infra::filesystem::resolve_data_dir;
+use boternity_infra::sqlite::migrations::{pending_migrations, run_migrations, MigrationStatus};
+use boternity_infra::sqlite::pool::DatabasePool;
+
+use super::color::new_table;
+use super::output::print_json;
+use super::storage::format_size;
+use crate::state::database_url;
+
+/// Database subcommands.
+#[derive(Subcommand)]
+pub enum DbCommand {
+    /// Apply pending schema migrations.
+    Migrate {
+        /// List pending migrations without applying them.
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Write a consistent copy of the database to a new file.
+    Backup {
+        /// Destination file (must not exist).
+        path: PathBuf,
+    },
+
+    /// Rebuild the database file to reclaim unused space.
+    Vacuum,
+
+    /// Show row counts and sizes per table.
+    Stats,
+}
+
+/// Handle a database subcommand.
+///
+/// Runs before `AppState::init`, which would otherwise apply migrations itself.
+pub async fn handle_db_command(action: DbCommand, json: bool) -> Result<()> {
+    match action {
+        DbCommand::Migrate { dry_run } => migrate(dry_run, json).await,
+        DbCommand::Backup { path } => backup(&path, json).await,
+        DbCommand::Vacuum => vacuum(json).await,
+        DbCommand::Stats => stats(json).await,
+    }
+}
+
+/// Open the database (applying pending migrations, as at startup).
+async fn open_pool() -> Result<DatabasePool> {
+    let data_dir = resolve_data_dir();
+    tokio::fs::create_dir_all(&data_dir).await?;
+    DatabasePool::new(&database_url(&data_dir))
+        .await
+        .with_context(|| "Failed to open database")
+}
+
+async fn backup(path: &Path, json: bool) -> Result<()> {
+    if path.exists() {
+        anyhow::bail!("Backup destination '{}' already exists", path.display());
+    }
+    let pool = open_pool().await?;
+    pool.backup_to(path)
+        .await
+        .with_context(|| format!("Failed to back up database to '{}'", path.display()))?;
+    let bytes = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+
+    if json {
+        print_json(&serde_json::json!({"path": path.display().to_string(), "bytes": bytes}))?;
+    } else {
+        println!(
+            "  {} Backed up database to {} ({})",
+            style("*").green().bold(),
+            style(path.display()).cyan(),
+            format_size(bytes)
+        );
+    }
+    Ok(())
+}
+
+async fn vacuum(json: bool) -> Result<()> {
+    let pool = open_pool().await?;
+    let report = pool.vacuum().await.with_context(|| "VACUUM failed")?;
+
+    if json {
+        print_json(&serde_json::json!({
+                "bytes_before": report.bytes_before,
+                "bytes_after": report.bytes_after,
+                "reclaimed": report.reclaimed(),
+            }))?;
+    } else {
+        println!(
+            "  {} Vacuumed database: {} -> {} ({} reclaimed)",
+            style("*").green().bold(),
+            format_size(report.bytes_before),
+            format_size(report.bytes_after),
+            style(format_size(report.reclaimed())).bold()
+        );
+    }
+    Ok(())
+}
+
+async fn stats(json: bool) -> Result<()> {
+    let pool = open_pool().await?;
+    let tables = pool.table_stats().await?;
+
+    if json {
+        print_json(&tables)?;
+        return Ok(());
+    }
+
+    let mut table = new_table();
+    table.set_header(vec![
+        Cell::new("Table").fg(Color::White),
+        Cell::new("Rows").fg(Color::White),
+        Cell::new("Size").fg(Color::White),
+    ]);
+    for t in &tables {
+        table.add_row(vec![
+            Cell::new(&t.name).fg(Color::Cyan),
+            Cell::new(t.rows).fg(Color::White),
+            Cell::new(t.bytes.map(format_size).unwrap_or_else(|| "-".to_string()))
+                .fg(Color::DarkGrey),
+        ]);
+    }
+
+    println!();
+    println!("{table}");
+    println!();
+    Ok(())
+}
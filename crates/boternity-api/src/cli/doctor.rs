@@ -0,0 +1,580 @@
+//! `bnity doctor`: diagnose a broken or half-configured installation.
+//!
+//! Runs before `AppState::init` (which fails outright on many of the problems
+//! this is meant to explain) and checks, in order:
+//!
+//! - the data directory is writable
+//! - the database opens and its migrations are applied
+//! - the vault key file is readable and decrypts every stored secret
+//! - each provider in the chain has its API key and answers a test request
+//! - the vector store opens
+//! - the OS sandbox for untrusted skills really restricts a probe process
+//!
+//! Every failing check carries a remediation hint. Nothing is modified beyond
+//! what a normal command would create (the data directory, the vector store).
+
+use std::future::Future;
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::Result;
+use console::style;
+use serde::Serialize;
+
+use boternity_core::repository::secret::{DynSecretProvider, SecretProvider};
+use boternity_core::service::secret::SecretService;
+use boternity_infra::crypto::vault::VaultCrypto;
+use boternity_infra::filesystem::resolve_data_dir;
+use boternity_infra::llm::{create_provider, test_provider_connection};
+use boternity_infra::secret::chain::build_secret_chain;
+use boternity_infra::secret::env::EnvSecretProvider;
+use boternity_infra::secret::VaultSecretProvider;
+use boternity_infra::skill::sandbox::{self, SandboxStatus};
+use boternity_infra::sqlite::migrations::pending_migrations;
+use boternity_infra::sqlite::pool::DatabasePool;
+use boternity_infra::sqlite::secret::SqliteSecretRepository;
+use boternity_infra::vector::lance::LanceVectorStore;
+use boternity_types::identity::Identity;
+use boternity_types::llm::{ProviderConfig, ProviderType};
+use boternity_types::secret::SecretScope;
+
+use super::output::print_json;
+use super::provider::{infer_capabilities, load_provider_configs};
+use crate::state::database_url;
+
+/// Secret holding the primary provider key (see `AppState::build_fallback_chain`).
+const PRIMARY_KEY_SECRET: &str = "ANTHROPIC_API_KEY";
+
+/// Outcome of a single check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Pass,
+    /// Works, but something is likely to bite later.
+    Warn,
+    Fail,
+}
+
+/// One line of the doctor report.
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckResult {
+    /// Stable check identifier (e.g. `database`, `provider:openai`).
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+    /// What to do about a warning or failure.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+}
+
+impl CheckResult {
+    fn pass(name: &str, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status: CheckStatus::Pass,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+
+    fn warn(name: &str, detail: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status: CheckStatus::Warn,
+            detail: detail.into(),
+            hint: Some(hint.into()),
+        }
+    }
+
+    fn fail(name: &str, detail: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status: CheckStatus::Fail,
+            detail: detail.into(),
+            hint: Some(hint.into()),
+        }
+    }
+}
+
+/// The full doctor report.
+#[derive(Debug, Clone, Serialize)]
+pub struct DoctorReport {
+    pub data_dir: String,
+    /// False when any check failed (warnings do not count).
+    pub healthy: bool,
+    pub checks: Vec<CheckResult>,
+}
+
+impl DoctorReport {
+    fn new(data_dir: &Path, checks: Vec<CheckResult>) -> Self {
+        Self {
+            data_dir: data_dir.display().to_string(),
+            healthy: checks.iter().all(|c| c.status != CheckStatus::Fail),
+            checks,
+        }
+    }
+
+    /// Look up a check by name.
+    pub fn check(&self, name: &str) -> Option<&CheckResult> {
+        self.checks.iter().find(|c| c.name == name)
+    }
+}
+
+/// Sends a test request to a provider.
+///
+/// Real runs hit the network; tests substitute a stub.
+pub trait ProviderProbe {
+    fn probe(
+        &self,
+        config: &ProviderConfig,
+        api_key: Option<&str>,
+    ) -> impl Future<Output = Result<(), String>>;
+}
+
+/// Probe that runs the same connection test as `bnity provider test`.
+struct LiveProbe;
+
+impl ProviderProbe for LiveProbe {
+    async fn probe(&self, config: &ProviderConfig, api_key: Option<&str>) -> Result<(), String> {
+        let provider = create_provider(config, api_key).map_err(|e| e.to_string())?;
+        test_provider_connection(&provider)
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Run every check and print the report.
+///
+/// Exits with status 1 when any check fails, so scripts can gate on it.
+pub async fn run_doctor(json: bool) -> Result<()> {
+    let data_dir = resolve_data_dir();
+    let mut report = diagnose(&data_dir, &LiveProbe, true).await;
+    // Outside `diagnose`: this depends on the host kernel, not the installation.
+    report.checks.push(check_sandbox(&sandbox::self_test().await));
+
+    if json {
+        print_json(&report)?;
+    } else {
+        print_report(&report);
+    }
+    if !report.healthy {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Check the installation at `data_dir`.
+///
+/// `include_env` adds environment variables to the secret lookup, as the
+/// real secret chain does.
+pub async fn diagnose(
+    data_dir: &Path,
+    probe: &impl ProviderProbe,
+    include_env: bool,
+) -> DoctorReport {
+    let mut checks = Vec::new();
+
+    let dir_ok = check_data_dir(data_dir).await;
+    let dir_writable = dir_ok.status == CheckStatus::Pass;
+    checks.push(dir_ok);
+    if !dir_writable {
+        return DoctorReport::new(data_dir, checks);
+    }
+
+    let pool = check_database(data_dir, &mut checks).await;
+
+    // Secrets: vault when the database is usable, otherwise env only.
+    let mut secret_chain: Vec<DynSecretProvider> = Vec::new();
+    match check_vault(data_dir, pool, &mut checks).await {
+        Some(vault) => secret_chain = build_secret_chain(vault, None, include_env),
+        None if include_env => secret_chain.push(Arc::new(EnvSecretProvider::new())),
+        None => {}
+    }
+    let secrets = SecretService::new(secret_chain);
+
+    check_providers(data_dir, &secrets, probe, &mut checks).await;
+    checks.push(check_vector_store(data_dir).await);
+
+    DoctorReport::new(data_dir, checks)
+}
+
+async fn check_data_dir(data_dir: &Path) -> CheckResult {
+    const NAME: &str = "data_dir";
+    let hint = "Check permissions, or point BOTERNITY_DATA_DIR at a writable directory";
+
+    if let Err(e) = tokio::fs::create_dir_all(data_dir).await {
+        return CheckResult::fail(NAME, format!("cannot create {}: {e}", data_dir.display()), hint);
+    }
+    let probe_file = data_dir.join(".doctor-write-test");
+    match tokio::fs::write(&probe_file, b"ok").await {
+        Ok(()) => {
+            let _ = tokio::fs::remove_file(&probe_file).await;
+            CheckResult::pass(NAME, format!("{} is writable", data_dir.display()))
+        }
+        Err(e) => CheckResult::fail(NAME, format!("{} is not writable: {e}", data_dir.display()), hint),
+    }
+}
+
+/// Check connectivity and migrations; returns a pool only when the schema
+/// is current (opening a full pool would apply pending migrations).
+async fn check_database(data_dir: &Path, checks: &mut Vec<CheckResult>) -> Option<DatabasePool> {
+    let url = database_url(data_dir);
+    let writer = match DatabasePool::connect_writer(&url).await {
+        Ok(writer) => writer,
+        Err(e) => {
+            checks.push(CheckResult::fail(
+                "database",
+                format!("cannot open database: {e}"),
+                "Restore boternity.db from a backup (bnity db backup) or move it aside to start fresh",
+            ));
+            return None;
+        }
+    };
+    checks.push(CheckResult::pass("database", "connected"));
+
+    let pending = match pending_migrations(&writer).await {
+        Ok(pending) => pending,
+        Err(e) => {
+            checks.push(CheckResult::fail(
+                "migrations",
+                format!("cannot read migration state: {e}"),
+                "The database may be corrupt; restore it from a backup",
+            ));
+            return None;
+        }
+    };
+    writer.close().await;
+
+    if !pending.is_empty() {
+        let versions: Vec<String> = pending.iter().map(|m| m.version.to_string()).collect();
+        checks.push(CheckResult::warn(
+            "migrations",
+            format!("{} pending: {}", pending.len(), versions.join(", ")),
+            "Run: bnity db migrate (they also apply on the next command)",
+        ));
+        return None;
+    }
+    checks.push(CheckResult::pass("migrations", "schema is up to date"));
+    DatabasePool::new(&url).await.ok()
+}
+
+/// Check the vault key and that every global secret decrypts with it.
+async fn check_vault(
+    data_dir: &Path,
+    pool: Option<DatabasePool>,
+    checks: &mut Vec<CheckResult>,
+) -> Option<VaultSecretProvider> {
+    let key_path = data_dir.join("vault.key");
+    if !key_path.exists() {
+        // A missing key is created on first use; that only hurts if secrets exist.
+        checks.push(CheckResult::warn(
+            "vault_key",
+            "vault.key not found",
+            "It is created on the next command; secrets stored under a lost key cannot be read",
+        ));
+        return None;
+    }
+    let crypto = match VaultCrypto::from_key_file(&key_path) {
+        Ok(crypto) => crypto,
+        Err(e) => {
+            checks.push(CheckResult::fail(
+                "vault_key",
+                e.to_string(),
+                "Restore vault.key from a backup; without it stored secrets cannot be decrypted",
+            ));
+            return None;
+        }
+    };
+    checks.push(CheckResult::pass("vault_key", "vault.key is readable"));
+
+    let pool = pool?;
+    let vault = VaultSecretProvider::new(SqliteSecretRepository::new(pool), crypto);
+    let entries = match vault.list(&SecretScope::Global).await {
+        Ok(entries) => entries,
+        Err(e) => {
+            checks.push(CheckResult::fail(
+                "vault_secrets",
+                format!("cannot list secrets: {e}"),
+                "The secrets table may be corrupt; restore the database from a backup",
+            ));
+            return None;
+        }
+    };
+    let mut unreadable = Vec::new();
+    for entry in &entries {
+        if vault.get(&entry.key.0, &SecretScope::Global).await.is_err() {
+            unreadable.push(entry.key.0.clone());
+        }
+    }
+    if unreadable.is_empty() {
+        checks.push(CheckResult::pass(
+            "vault_secrets",
+            format!("{} secret(s) decrypt", entries.len()),
+        ));
+    } else {
+        checks.push(CheckResult::fail(
+            "vault_secrets",
+            format!("cannot decrypt: {}", unreadable.join(", ")),
+            "vault.key does not match these secrets; restore the original key or set them again with: bnity set secret <KEY>",
+        ));
+    }
+    Some(vault)
+}
+
+/// Check the primary provider and every enabled provider in providers.json.
+async fn check_providers(
+    data_dir: &Path,
+    secrets: &SecretService,
+    probe: &impl ProviderProbe,
+    checks: &mut Vec<CheckResult>,
+) {
+    let primary_key = secrets
+        .get_secret(PRIMARY_KEY_SECRET, &SecretScope::Global)
+        .await
+        .ok()
+        .flatten();
+    let primary_type = match &primary_key {
+        Some(key) if key.starts_with("bedrock-api-key-") => ProviderType::Bedrock,
+        _ => ProviderType::Anthropic,
+    };
+    let primary_name = if primary_type == ProviderType::Bedrock { "bedrock" } else { "anthropic" };
+    let mut configs = vec![ProviderConfig {
+        name: primary_name.to_string(),
+        capabilities: infer_capabilities(primary_name, Identity::DEFAULT_MODEL, &primary_type),
+        provider_type: primary_type,
+        api_key_secret_name: Some(PRIMARY_KEY_SECRET.to_string()),
+        base_url: None,
+        model: Identity::DEFAULT_MODEL.to_string(),
+        priority: 0,
+        enabled: true,
+        extra_headers: Default::default(),
+        extra_body: Default::default(),
+    }];
+
+    match load_provider_configs(data_dir).await {
+        Ok(mut extra) => {
+            extra.sort_by_key(|c| c.priority);
+            configs.extend(
+                extra
+                    .into_iter()
+                    .filter(|c| c.enabled && c.name != primary_name),
+            );
+        }
+        Err(e) => checks.push(CheckResult::fail(
+            "providers_config",
+            e.to_string(),
+            "Fix or remove providers.json, then re-add providers with: bnity provider add",
+        )),
+    }
+
+    for config in &configs {
+        let name = format!("provider:{}", config.name);
+        let api_key = match config.api_key_secret_name.as_deref() {
+            Some(secret) => match secrets.get_secret(secret, &SecretScope::Global).await {
+                Ok(Some(key)) => Some(key),
+                Ok(None) => {
+                    checks.push(CheckResult::fail(
+                        &name,
+                        format!("API key secret '{secret}' is not set"),
+                        format!("Run: bnity set secret {secret}"),
+                    ));
+                    continue;
+                }
+                Err(e) => {
+                    checks.push(CheckResult::fail(
+                        &name,
+                        format!("cannot read secret '{secret}': {e}"),
+                        "See the vault checks above",
+                    ));
+                    continue;
+                }
+            },
+            None => None,
+        };
+
+        checks.push(match probe.probe(config, api_key.as_deref()).await {
+            Ok(()) => CheckResult::pass(&name, format!("{} reachable", config.model)),
+            Err(e) => CheckResult::fail(
+                &name,
+                format!("connection test failed: {e}"),
+                format!(
+                    "Check the key, network, and base URL; retry with: bnity provider test {}",
+                    config.name
+                ),
+            ),
+        });
+    }
+}
+
+async fn check_vector_store(data_dir: &Path) -> CheckResult {
+    const NAME: &str = "vector_store";
+    match LanceVectorStore::new(data_dir.join("vector_store")).await {
+        Ok(_) => CheckResult::pass(NAME, "opened"),
+        Err(e) => CheckResult::fail(
+            NAME,
+            format!("cannot open vector store: {e}"),
+            "Move vector_store/ aside; it is recreated empty (memories in SQLite are kept)",
+        ),
+    }
+}
+
+/// Report the sandbox self-test. Never a failure: skills still get WASM
+/// isolation, but a no-op sandbox must not go unnoticed.
+fn check_sandbox(status: &SandboxStatus) -> CheckResult {
+    const NAME: &str = "sandbox";
+    let hint = "Untrusted skills run without OS isolation; install only skills you trust, \
+                or enable Landlock (Linux 6.2+) / sandbox-exec (macOS)";
+    match status {
+        SandboxStatus::Enforced => CheckResult::pass(NAME, "OS sandbox blocks a probe process"),
+        SandboxStatus::Degraded { reason } => {
+            CheckResult::warn(NAME, format!("OS sandbox is degraded: {reason}"), hint)
+        }
+        SandboxStatus::Unavailable { reason } => {
+            CheckResult::warn(NAME, format!("OS sandbox is unavailable: {reason}"), hint)
+        }
+    }
+}
+
+fn print_report(report: &DoctorReport) {
+    println!();
+    println!(
+        "  {} {}",
+        style("Boternity doctor").bold(),
+        style(&report.data_dir).dim()
+    );
+    println!();
+    for check in &report.checks {
+        let label = match check.status {
+            CheckStatus::Pass => style("pass").green(),
+            CheckStatus::Warn => style("warn").yellow(),
+            CheckStatus::Fail => style("FAIL").red().bold(),
+        };
+        println!("  {label} {:<20} {}", check.name, check.detail);
+        if let Some(hint) = &check.hint {
+            println!("       {}", style(hint).dim());
+        }
+    }
+    println!();
+    if report.healthy {
+        println!("  {} All checks passed.", style("✓").green().bold());
+    } else {
+        let failed = report
+            .checks
+            .iter()
+            .filter(|c| c.status == CheckStatus::Fail)
+            .count();
+        println!(
+            "  {} {failed} check{} failed.",
+            style("✗").red().bold(),
+            if failed == 1 { "" } else { "s" }
+        );
+    }
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Stub probe: every provider answers.
+    struct Reachable;
+
+    impl ProviderProbe for Reachable {
+        async fn probe(&self, _config: &ProviderConfig, _api_key: Option<&str>) -> Result<(), String> {
+            Ok(())
+        }
+    }
+
+    /// Migrated database plus vault key, as after a first successful run.
+    async fn installed(dir: &tempfile::TempDir, primary_key: Option<&str>) {
+        let pool = DatabasePool::new(&database_url(dir.path())).await.unwrap();
+        let crypto = VaultCrypto::from_key_file(&dir.path().join("vault.key")).unwrap();
+        let vault = VaultSecretProvider::new(SqliteSecretRepository::new(pool), crypto);
+        if let Some(key) = primary_key {
+            vault
+                .set(PRIMARY_KEY_SECRET, key, &SecretScope::Global)
+                .await
+                .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_healthy_setup_passes_every_check() {
+        let dir = tempfile::tempdir().unwrap();
+        installed(&dir, Some("sk-ant-test")).await;
+
+        let report = diagnose(dir.path(), &Reachable, false).await;
+
+        let names: Vec<&str> = report.checks.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "data_dir",
+                "database",
+                "migrations",
+                "vault_key",
+                "vault_secrets",
+                "provider:anthropic",
+                "vector_store"
+            ]
+        );
+        assert!(
+            report.checks.iter().all(|c| c.status == CheckStatus::Pass),
+            "{report:#?}"
+        );
+        assert!(report.healthy);
+    }
+
+    #[tokio::test]
+    async fn test_missing_provider_key_fails_with_hint() {
+        let dir = tempfile::tempdir().unwrap();
+        installed(&dir, None).await;
+
+        let report = diagnose(dir.path(), &Reachable, false).await;
+
+        assert!(!report.healthy);
+        let check = report.check("provider:anthropic").unwrap();
+        assert_eq!(check.status, CheckStatus::Fail);
+        assert_eq!(
+            check.hint.as_deref(),
+            Some("Run: bnity set secret ANTHROPIC_API_KEY")
+        );
+        // The other checks are unaffected
+        assert_eq!(report.check("vault_key").unwrap().status, CheckStatus::Pass);
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["healthy"], false);
+        assert_eq!(json["checks"][5]["status"], "fail");
+    }
+
+    #[test]
+    fn test_sandbox_degradation_warns_without_failing() {
+        assert_eq!(check_sandbox(&SandboxStatus::Enforced).status, CheckStatus::Pass);
+
+        let check = check_sandbox(&SandboxStatus::Unavailable {
+            reason: "kernel lacks Landlock support".to_string(),
+        });
+        assert_eq!(check.status, CheckStatus::Warn);
+        assert!(check.detail.contains("kernel lacks Landlock support"));
+        assert!(check.hint.is_some());
+
+        let report = DoctorReport::new(Path::new("/tmp"), vec![check]);
+        assert!(report.healthy);
+    }
+
+    #[tokio::test]
+    async fn test_corrupt_vault_key_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        installed(&dir, Some("sk-ant-test")).await;
+        std::fs::write(dir.path().join("vault.key"), "not-hex").unwrap();
+
+        let report = diagnose(dir.path(), &Reachable, false).await;
+
+        assert_eq!(report.check("vault_key").unwrap().status, CheckStatus::Fail);
+        assert_eq!(
+            report.check("provider:anthropic").unwrap().status,
+            CheckStatus::Fail
+        );
+    }
+}
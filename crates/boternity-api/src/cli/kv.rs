@@ -1,15 +1,20 @@
 //! Key-value store CLI subcommands for per-bot structured data.
 //!
-//! Provides set, get, delete, and list operations on a per-bot key-value store.
+//! Provides set, get, incr, delete, and list operations on a per-bot key-value store.
 //! Values support arbitrary JSON (objects, arrays, strings, numbers, etc.).
+//! Keys can be given a TTL on set (`--ttl 30s`, `5m`, `2h`, `1d`).
+
+use std::time::Duration;
 
 use anyhow::{Context, Result};
 use clap::Subcommand;
-use comfy_table::{presets, Cell, Color, ContentArrangement, Table};
+use comfy_table::{Cell, Color};
 use console::style;
 
 use boternity_core::storage::kv_store::KvStore;
 
+use crate::cli::color::new_table;
+use crate::cli::output::print_json;
 use crate::state::AppState;
 
 /// Key-value store subcommands.
@@ -25,6 +30,23 @@ pub enum KvCommand {
 
         /// JSON value (string, number, object, array, boolean, null).
         value: String,
+
+        /// Expire the key after this long (e.g. 30s, 5m, 2h, 1d; bare number = seconds).
+        #[arg(long, value_parser = parse_ttl)]
+        ttl: Option<Duration>,
+    },
+
+    /// Atomically add to an integer value (missing keys start at 0).
+    Incr {
+        /// Bot slug.
+        slug: String,
+
+        /// Key name.
+        key: String,
+
+        /// Amount to add (may be negative).
+        #[arg(default_value = "1", allow_hyphen_values = true)]
+        delta: i64,
     },
 
     /// Get a value by key.
@@ -55,7 +77,13 @@ pub enum KvCommand {
 /// Handle a KV subcommand.
 pub async fn handle_kv_command(cmd: KvCommand, state: &AppState, json: bool) -> Result<()> {
     match cmd {
-        KvCommand::Set { slug, key, value } => kv_set(state, &slug, &key, &value, json).await,
+        KvCommand::Set {
+            slug,
+            key,
+            value,
+            ttl,
+        } => kv_set(state, &slug, &key, &value, ttl, json).await,
+        KvCommand::Incr { slug, key, delta } => kv_incr(state, &slug, &key, delta, json).await,
         KvCommand::Get { slug, key } => kv_get(state, &slug, &key, json).await,
         KvCommand::Delete { slug, key } => kv_delete(state, &slug, &key, json).await,
         KvCommand::List { slug } => kv_list(state, &slug, json).await,
@@ -68,7 +96,14 @@ pub async fn handle_kv_command(cmd: KvCommand, state: &AppState, json: bool) ->
 /// This provides a good UX: `bnity kv set bot1 name "Alice"` stores
 /// the string `"Alice"`, while `bnity kv set bot1 config '{"theme":"dark"}'`
 /// stores the parsed JSON object.
-async fn kv_set(state: &AppState, slug: &str, key: &str, value_str: &str, json: bool) -> Result<()> {
+async fn kv_set(
+    state: &AppState,
+    slug: &str,
+    key: &str,
+    value_str: &str,
+    ttl: Option<Duration>,
+    json: bool,
+) -> Result<()> {
     let bot = state
         .bot_service
         .get_bot_by_slug(slug)
@@ -80,15 +115,19 @@ async fn kv_set(state: &AppState, slug: &str, key: &str, value_str: &str, json:
         serde_json::Value::String(value_str.to_string())
     });
 
-    state.kv_store.set(&bot.id.0, key, &value).await?;
+    state
+        .kv_store
+        .set_with_ttl(&bot.id.0, key, &value, ttl)
+        .await?;
 
     if json {
         let result = serde_json::json!({
             "key": key,
             "value": value,
             "bot": slug,
+            "ttl_secs": ttl.map(|t| t.as_secs()),
         });
-        println!("{}", serde_json::to_string_pretty(&result)?);
+        print_json(&result)?;
     } else {
         println!();
         println!(
@@ -97,12 +136,66 @@ async fn kv_set(state: &AppState, slug: &str, key: &str, value_str: &str, json:
             style(key).cyan(),
             style(&bot.name).cyan(),
         );
+        if let Some(ttl) = ttl {
+            println!("     Expires in {}s", ttl.as_secs());
+        }
         println!();
     }
 
     Ok(())
 }
 
+/// Atomically increment an integer value.
+async fn kv_incr(state: &AppState, slug: &str, key: &str, delta: i64, json: bool) -> Result<()> {
+    let bot = state
+        .bot_service
+        .get_bot_by_slug(slug)
+        .await
+        .with_context(|| format!("Bot '{slug}' not found"))?;
+
+    let value = state.kv_store.increment(&bot.id.0, key, delta).await?;
+
+    if json {
+        let result = serde_json::json!({
+            "key": key,
+            "value": value,
+            "bot": slug,
+        });
+        print_json(&result)?;
+    } else {
+        println!();
+        println!(
+            "  {} = {}",
+            style(key).cyan().bold(),
+            style(value).white(),
+        );
+        println!();
+    }
+
+    Ok(())
+}
+
+/// Parse a TTL like `30s`, `5m`, `2h`, `1d`, or a bare number of seconds.
+fn parse_ttl(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+    let (digits, unit_secs) = match s.char_indices().last() {
+        Some((i, 's')) => (&s[..i], 1),
+        Some((i, 'm')) => (&s[..i], 60),
+        Some((i, 'h')) => (&s[..i], 3600),
+        Some((i, 'd')) => (&s[..i], 86_400),
+        _ => (s, 1),
+    };
+    let n: u64 = digits
+        .parse()
+        .map_err(|_| format!("invalid TTL '{s}' (expected e.g. 30s, 5m, 2h, 1d)"))?;
+    if n == 0 {
+        return Err("TTL must be greater than zero".to_string());
+    }
+    n.checked_mul(unit_secs)
+        .map(Duration::from_secs)
+        .ok_or_else(|| format!("TTL '{s}' is too large"))
+}
+
 /// Get a value by key and pretty-print it.
 async fn kv_get(state: &AppState, slug: &str, key: &str, json: bool) -> Result<()> {
     let bot = state
@@ -121,7 +214,7 @@ async fn kv_get(state: &AppState, slug: &str, key: &str, json: bool) -> Result<(
                     "value": val,
                     "bot": slug,
                 });
-                println!("{}", serde_json::to_string_pretty(&result)?);
+                print_json(&result)?;
             } else {
                 println!();
                 println!(
@@ -139,7 +232,7 @@ async fn kv_get(state: &AppState, slug: &str, key: &str, json: bool) -> Result<(
                     "value": null,
                     "bot": slug,
                 });
-                println!("{}", serde_json::to_string_pretty(&result)?);
+                print_json(&result)?;
             } else {
                 println!();
                 println!(
@@ -171,7 +264,7 @@ async fn kv_delete(state: &AppState, slug: &str, key: &str, json: bool) -> Resul
             "deleted": key,
             "bot": slug,
         });
-        println!("{}", serde_json::to_string_pretty(&result)?);
+        print_json(&result)?;
     } else {
         println!();
         println!(
@@ -202,7 +295,7 @@ async fn kv_list(state: &AppState, slug: &str, json: bool) -> Result<()> {
             "count": keys.len(),
             "bot": slug,
         });
-        println!("{}", serde_json::to_string_pretty(&result)?);
+        print_json(&result)?;
         return Ok(());
     }
 
@@ -230,9 +323,7 @@ async fn kv_list(state: &AppState, slug: &str, json: bool) -> Result<()> {
     println!();
 
     // Show each key with its value preview
-    let mut table = Table::new();
-    table.load_preset(presets::UTF8_FULL_CONDENSED);
-    table.set_content_arrangement(ContentArrangement::Dynamic);
+    let mut table = new_table();
 
     table.set_header(vec![
         Cell::new("Key").fg(Color::White),
@@ -263,3 +354,25 @@ async fn kv_list(state: &AppState, slug: &str, json: bool) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ttl_units() {
+        assert_eq!(parse_ttl("45").unwrap(), Duration::from_secs(45));
+        assert_eq!(parse_ttl("30s").unwrap(), Duration::from_secs(30));
+        assert_eq!(parse_ttl("5m").unwrap(), Duration::from_secs(300));
+        assert_eq!(parse_ttl("2h").unwrap(), Duration::from_secs(7200));
+        assert_eq!(parse_ttl("1d").unwrap(), Duration::from_secs(86_400));
+    }
+
+    #[test]
+    fn test_parse_ttl_rejects_invalid() {
+        assert!(parse_ttl("").is_err());
+        assert!(parse_ttl("0s").is_err());
+        assert!(parse_ttl("abc").is_err());
+        assert!(parse_ttl("5w").is_err());
+    }
+}
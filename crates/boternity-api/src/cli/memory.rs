@@ -1,12 +1,14 @@
-//! Memory management CLI commands: list, search, remember, forget, delete, export, audit.
+//! Memory management CLI commands: list, search, remember, edit, reindex, compact, forget,
+//! delete, export, audit.
 //!
 //! Provides memory browsing with provenance, semantic search with similarity scores,
-//! manual injection (to both SQLite and LanceDB), individual deletion with audit,
-//! JSON export, and audit log viewing.
+//! manual injection (to both SQLite and LanceDB), in-place editing with re-embedding,
+//! individual deletion with audit, JSON export, and audit log viewing.
 
 use anyhow::{Context, Result};
 use chrono::Utc;
-use comfy_table::{presets, Cell, Color, ContentArrangement, Table};
+use clap::Subcommand;
+use comfy_table::{Cell, Color};
 use console::style;
 use dialoguer::Confirm;
 use uuid::Uuid;
@@ -15,12 +17,50 @@ use boternity_core::memory::box_embedder::BoxEmbedder;
 use boternity_core::memory::box_vector::BoxVectorMemoryStore;
 use boternity_core::memory::store::MemoryRepository;
 use boternity_infra::sqlite::audit::SqliteAuditLog;
+use boternity_infra::vector::lance::{LanceVectorStore, MIN_INDEXABLE_ROWS};
 use boternity_types::memory::{
     AuditAction, MemoryAuditEntry, MemoryCategory, MemoryEntry, VectorMemoryEntry,
 };
 
+use crate::cli::color::new_table;
+use crate::cli::output::{print_json, print_json_with_warnings};
 use crate::state::AppState;
 
+/// Subcommands of `bnity memories`.
+#[derive(Subcommand)]
+pub enum MemoriesCommand {
+    /// Fix a stored memory's fact, importance, or category.
+    Edit {
+        /// Memory ID.
+        id: String,
+
+        /// Replacement fact text (re-embeds the memory).
+        #[arg(long)]
+        fact: Option<String>,
+
+        /// Importance from 1 (trivial) to 5 (critical).
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=5))]
+        importance: Option<u8>,
+
+        /// Category: preference, fact, decision, context, or correction.
+        #[arg(long, value_parser = MemoryCategory::parse_lenient)]
+        category: Option<MemoryCategory>,
+    },
+
+    /// Rebuild a bot's vector search index.
+    Reindex {
+        /// Bot slug.
+        slug: String,
+    },
+
+    /// Compact vector tables fragmented by many writes and deletes.
+    Compact {
+        /// Bot slug (compacts every vector table, including shared memories,
+        /// when omitted).
+        slug: Option<String>,
+    },
+}
+
 /// List all memories for a bot with provenance, category, and importance.
 ///
 /// # Examples
@@ -43,7 +83,7 @@ pub async fn list_memories(state: &AppState, slug: &str, json: bool) -> Result<(
         .await?;
 
     if json {
-        println!("{}", serde_json::to_string_pretty(&memories)?);
+        print_json(&memories)?;
         return Ok(());
     }
 
@@ -58,9 +98,7 @@ pub async fn list_memories(state: &AppState, slug: &str, json: bool) -> Result<(
         return Ok(());
     }
 
-    let mut table = Table::new();
-    table.load_preset(presets::UTF8_FULL_CONDENSED);
-    table.set_content_arrangement(ContentArrangement::Dynamic);
+    let mut table = new_table();
 
     table.set_header(vec![
         Cell::new("Fact").fg(Color::White),
@@ -177,7 +215,7 @@ pub async fn search_memories(
                 })
             })
             .collect();
-        println!("{}", serde_json::to_string_pretty(&json_results)?);
+        print_json(&json_results)?;
         return Ok(());
     }
 
@@ -193,9 +231,7 @@ pub async fn search_memories(
         return Ok(());
     }
 
-    let mut table = Table::new();
-    table.load_preset(presets::UTF8_FULL_CONDENSED);
-    table.set_content_arrangement(ContentArrangement::Dynamic);
+    let mut table = new_table();
 
     table.set_header(vec![
         Cell::new("Similarity").fg(Color::White),
@@ -304,6 +340,8 @@ pub async fn remember(
         .save_memory(&entry)
         .await?;
 
+    let mut warnings = Vec::new();
+
     // Save to LanceDB if vector store and embedder are available
     if let (Some(vs), Some(emb)) = (vector_store, embedder) {
         let embeddings = emb
@@ -327,6 +365,9 @@ pub async fn remember(
             };
             if let Err(e) = vs.add(&vector_entry, &embedding).await {
                 tracing::warn!("Failed to store memory in vector DB (SQLite saved): {e}");
+                warnings.push(format!(
+                    "Memory saved but not indexed for semantic recall: {e}"
+                ));
             }
         }
     }
@@ -348,7 +389,7 @@ pub async fn remember(
     }
 
     if json {
-        println!("{}", serde_json::to_string_pretty(&entry)?);
+        print_json_with_warnings(&entry, warnings)?;
     } else {
         println!(
             "  {} Memory saved for '{}'",
@@ -364,6 +405,200 @@ pub async fn remember(
     Ok(())
 }
 
+/// Edit a stored memory in place (SQLite, plus a fresh embedding in LanceDB
+/// when the fact changes).
+///
+/// # Examples
+///
+/// ```bash
+/// bnity memories edit <memory-id> --fact "Lives in Berlin"
+/// bnity memories edit <memory-id> --importance 5 --category preference
+/// ```
+pub async fn edit_memory(
+    state: &AppState,
+    memory_id: Uuid,
+    fact: Option<&str>,
+    importance: Option<u8>,
+    category: Option<MemoryCategory>,
+    vector_store: Option<&BoxVectorMemoryStore>,
+    json: bool,
+) -> Result<()> {
+    let mut entry = state
+        .chat_service
+        .memory_repo()
+        .get_memory(&memory_id)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Memory '{memory_id}' not found"))?;
+
+    let fact_changed = apply_memory_edit(&mut entry, fact, importance, category)?;
+
+    state
+        .chat_service
+        .memory_repo()
+        .update_memory(&entry)
+        .await?;
+
+    if fact_changed {
+        match vector_store {
+            Some(vs) => state
+                .chat_service
+                .refresh_memory_embedding(&entry, &state.embedder, vs)
+                .await
+                .with_context(|| "Memory updated, but re-embedding failed")?,
+            None => tracing::warn!(
+                "Vector store unavailable; memory {} updated in SQLite only",
+                memory_id
+            ),
+        }
+    }
+
+    if json {
+        print_json(&entry)?;
+    } else {
+        println!(
+            "  {} Memory {} updated.",
+            style("*").green().bold(),
+            &memory_id.to_string()[..8]
+        );
+        println!(
+            "  {} [{}, {}]",
+            style(&entry.fact).dim(),
+            entry.category,
+            format_importance(entry.importance)
+        );
+    }
+
+    Ok(())
+}
+
+/// Rebuild the ANN index over a bot's vector memories.
+///
+/// Indexes are built automatically once a table reaches
+/// `[vector_index] min_rows`; reindexing folds in rows added since and picks
+/// up changed tuning parameters.
+///
+/// # Examples
+///
+/// ```bash
+/// bnity memories reindex my-bot
+/// ```
+pub async fn reindex_memories(state: &AppState, slug: &str, json: bool) -> Result<()> {
+    let bot = state
+        .bot_service
+        .get_bot_by_slug(slug)
+        .await
+        .with_context(|| format!("Bot '{slug}' not found"))?;
+
+    let (rows, indexed) = state.vector_memory.reindex(&bot.id.0).await?;
+
+    if json {
+        print_json(&serde_json::json!({"bot": slug, "rows": rows, "indexed": indexed}))?;
+    } else if indexed {
+        println!(
+            "  {} Rebuilt the vector index for '{}' ({} memories).",
+            style("*").green().bold(),
+            style(&bot.name).cyan(),
+            rows
+        );
+    } else {
+        println!(
+            "  {} '{}' has {} memories, below the index threshold of {}; search is exhaustive.",
+            style("i").blue().bold(),
+            style(&bot.name).cyan(),
+            rows,
+            state
+                .global_config
+                .vector_index
+                .min_rows
+                .max(MIN_INDEXABLE_ROWS)
+        );
+    }
+
+    Ok(())
+}
+
+/// Compact one bot's memory table, or every vector table.
+///
+/// # Examples
+///
+/// ```bash
+/// bnity memories compact my-bot
+/// bnity memories compact
+/// ```
+pub async fn compact_memories(state: &AppState, slug: Option<&str>, json: bool) -> Result<()> {
+    let reports = match slug {
+        Some(slug) => {
+            let bot = state
+                .bot_service
+                .get_bot_by_slug(slug)
+                .await
+                .with_context(|| format!("Bot '{slug}' not found"))?;
+            let table = LanceVectorStore::bot_table_name(&bot.id.0);
+            state
+                .vector_store
+                .compact_table(&table)
+                .await?
+                .into_iter()
+                .collect::<Vec<_>>()
+        }
+        None => state.vector_store.compact_all().await?,
+    };
+
+    if json {
+        print_json(&reports)?;
+        return Ok(());
+    }
+    if reports.is_empty() {
+        println!("  {} No vector tables to compact.", style("i").blue().bold());
+        return Ok(());
+    }
+    for report in &reports {
+        println!(
+            "  {} {} ({} fragments merged into {})",
+            style("*").green().bold(),
+            report.table,
+            report.fragments_removed,
+            report.fragments_added
+        );
+    }
+    Ok(())
+}
+
+/// Apply the requested edits to `entry`, returning whether the fact changed.
+///
+/// Rejects an edit with no fields, an empty fact, or importance outside 1-5.
+fn apply_memory_edit(
+    entry: &mut MemoryEntry,
+    fact: Option<&str>,
+    importance: Option<u8>,
+    category: Option<MemoryCategory>,
+) -> Result<bool> {
+    if fact.is_none() && importance.is_none() && category.is_none() {
+        anyhow::bail!("Nothing to edit: pass --fact, --importance, or --category");
+    }
+
+    let mut fact_changed = false;
+    if let Some(fact) = fact {
+        let fact = fact.trim();
+        if fact.is_empty() {
+            anyhow::bail!("--fact cannot be empty");
+        }
+        fact_changed = fact != entry.fact;
+        entry.fact = fact.to_string();
+    }
+    if let Some(importance) = importance {
+        if !(1..=5).contains(&importance) {
+            anyhow::bail!("--importance must be between 1 and 5, got {importance}");
+        }
+        entry.importance = importance;
+    }
+    if let Some(category) = category {
+        entry.category = category;
+    }
+
+    Ok(fact_changed)
+}
+
 /// Delete all memories for a bot with confirmation.
 ///
 /// # Examples
@@ -387,10 +622,7 @@ pub async fn forget(state: &AppState, slug: &str, force: bool, json: bool) -> Re
 
     if memories.is_empty() {
         if json {
-            println!(
-                "{}",
-                serde_json::json!({"deleted": 0, "bot": slug})
-            );
+            print_json(&serde_json::json!({"deleted": 0, "bot": slug}))?;
         } else {
             println!(
                 "  {} No memories to delete for '{}'.",
@@ -424,10 +656,7 @@ pub async fn forget(state: &AppState, slug: &str, force: bool, json: bool) -> Re
         .await?;
 
     if json {
-        println!(
-            "{}",
-            serde_json::json!({"deleted": count, "bot": slug})
-        );
+        print_json(&serde_json::json!({"deleted": count, "bot": slug}))?;
     } else {
         println!(
             "  {} Wiped {} memor{} for '{}'.",
@@ -512,10 +741,7 @@ pub async fn delete_memory(
     }
 
     if json {
-        println!(
-            "{}",
-            serde_json::json!({"deleted": true, "memory_id": memory_id.to_string()})
-        );
+        print_json(&serde_json::json!({"deleted": true, "memory_id": memory_id.to_string()}))?;
     } else {
         println!(
             "  {} Memory {} deleted.",
@@ -591,7 +817,7 @@ pub async fn memory_audit(
         .with_context(|| "Failed to read audit log")?;
 
     if json {
-        println!("{}", serde_json::to_string_pretty(&entries)?);
+        print_json(&entries)?;
         return Ok(());
     }
 
@@ -606,9 +832,7 @@ pub async fn memory_audit(
         return Ok(());
     }
 
-    let mut table = Table::new();
-    table.load_preset(presets::UTF8_FULL_CONDENSED);
-    table.set_content_arrangement(ContentArrangement::Dynamic);
+    let mut table = new_table();
 
     table.set_header(vec![
         Cell::new("Action").fg(Color::White),
@@ -687,3 +911,55 @@ fn category_to_cell(category: &MemoryCategory) -> Cell {
         MemoryCategory::Correction => Cell::new("correction").fg(Color::Red),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_entry() -> MemoryEntry {
+        MemoryEntry {
+            id: Uuid::now_v7(),
+            bot_id: Uuid::now_v7(),
+            session_id: Uuid::now_v7(),
+            fact: "User lives in NYC".to_string(),
+            category: MemoryCategory::Fact,
+            importance: 3,
+            source_message_id: None,
+            superseded_by: None,
+            created_at: Utc::now(),
+            is_manual: false,
+            source_agent_id: None,
+        }
+    }
+
+    #[test]
+    fn test_apply_memory_edit_updates_fields() {
+        let mut entry = make_entry();
+        let changed = apply_memory_edit(
+            &mut entry,
+            Some(" User lives in Berlin "),
+            Some(5),
+            Some(MemoryCategory::Correction),
+        )
+        .unwrap();
+        assert!(changed);
+        assert_eq!(entry.fact, "User lives in Berlin");
+        assert_eq!(entry.importance, 5);
+        assert_eq!(entry.category, MemoryCategory::Correction);
+
+        // Same fact text does not count as a change (no re-embed)
+        assert!(!apply_memory_edit(&mut entry, Some("User lives in Berlin"), None, None).unwrap());
+    }
+
+    #[test]
+    fn test_apply_memory_edit_validates_input() {
+        let mut entry = make_entry();
+        assert!(apply_memory_edit(&mut entry, None, None, None).is_err());
+        assert!(apply_memory_edit(&mut entry, Some("   "), None, None).is_err());
+        assert!(apply_memory_edit(&mut entry, None, Some(0), None).is_err());
+        assert!(apply_memory_edit(&mut entry, None, Some(6), None).is_err());
+        assert_eq!(entry.fact, "User lives in NYC");
+        assert_eq!(entry.importance, 3);
+        assert!("urgent".parse::<MemoryCategory>().is_err());
+    }
+}
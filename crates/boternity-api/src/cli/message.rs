@@ -5,13 +5,15 @@
 
 use anyhow::{bail, Context, Result};
 use clap::Subcommand;
-use comfy_table::{presets, Cell, Color, ContentArrangement, Table};
+use comfy_table::{Cell, Color};
 use console::style;
 
 use boternity_core::message::envelope;
 use boternity_core::repository::message::MessageRepository;
 use boternity_types::message::BotSubscription;
 
+use crate::cli::color::new_table;
+use crate::cli::output::print_json;
 use crate::state::AppState;
 
 /// Bot-to-bot messaging subcommands.
@@ -211,7 +213,7 @@ async fn handle_send(
             "type": message_type,
             "status": "sent",
         });
-        println!("{}", serde_json::to_string_pretty(&out)?);
+        print_json(&out)?;
     } else {
         let dest = if let Some(ch) = channel_name {
             format!("channel '{}'", style(ch).cyan())
@@ -287,7 +289,7 @@ async fn handle_history(
                 })
             })
             .collect();
-        println!("{}", serde_json::to_string_pretty(&out)?);
+        print_json(&out)?;
         return Ok(());
     }
 
@@ -301,10 +303,8 @@ async fn handle_history(
         return Ok(());
     }
 
-    let mut table = Table::new();
+    let mut table = new_table();
     table
-        .load_preset(presets::UTF8_FULL_CONDENSED)
-        .set_content_arrangement(ContentArrangement::Dynamic)
         .set_header(vec![
             Cell::new("Time").fg(Color::Cyan),
             Cell::new("Sender"),
@@ -356,7 +356,7 @@ async fn handle_channels(repo: &impl MessageRepository, json: bool) -> Result<()
                 })
             })
             .collect();
-        println!("{}", serde_json::to_string_pretty(&out)?);
+        print_json(&out)?;
         return Ok(());
     }
 
@@ -371,10 +371,8 @@ async fn handle_channels(repo: &impl MessageRepository, json: bool) -> Result<()
         return Ok(());
     }
 
-    let mut table = Table::new();
+    let mut table = new_table();
     table
-        .load_preset(presets::UTF8_FULL_CONDENSED)
-        .set_content_arrangement(ContentArrangement::Dynamic)
         .set_header(vec![
             Cell::new("Channel").fg(Color::Cyan),
             Cell::new("Created At"),
@@ -441,14 +439,11 @@ async fn handle_subscribe(
         .map_err(|e| anyhow::anyhow!("Failed to subscribe: {e}"))?;
 
     if json {
-        println!(
-            "{}",
-            serde_json::json!({
+        print_json(&serde_json::json!({
                 "subscribed": true,
                 "bot": bot_slug,
                 "channel": channel_name,
-            })
-        );
+            }))?;
     } else {
         println!();
         println!(
@@ -487,13 +482,10 @@ async fn handle_unsubscribe(
 
     if !removed {
         if json {
-            println!(
-                "{}",
-                serde_json::json!({
+            print_json(&serde_json::json!({
                     "unsubscribed": false,
                     "reason": "not subscribed",
-                })
-            );
+                }))?;
         } else {
             println!();
             println!(
@@ -506,14 +498,11 @@ async fn handle_unsubscribe(
     }
 
     if json {
-        println!(
-            "{}",
-            serde_json::json!({
+        print_json(&serde_json::json!({
                 "unsubscribed": true,
                 "bot": bot_slug,
                 "channel": channel_name,
-            })
-        );
+            }))?;
     } else {
         println!();
         println!(
@@ -556,7 +545,7 @@ async fn handle_channel_history(
                 })
             })
             .collect();
-        println!("{}", serde_json::to_string_pretty(&out)?);
+        print_json(&out)?;
         return Ok(());
     }
 
@@ -570,10 +559,8 @@ async fn handle_channel_history(
         return Ok(());
     }
 
-    let mut table = Table::new();
+    let mut table = new_table();
     table
-        .load_preset(presets::UTF8_FULL_CONDENSED)
-        .set_content_arrangement(ContentArrangement::Dynamic)
         .set_header(vec![
             Cell::new("Time").fg(Color::Cyan),
             Cell::new("Sender"),
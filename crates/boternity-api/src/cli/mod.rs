@@ -3,13 +3,22 @@
 //! Uses clap derive macros for argument parsing. The CLI follows a verb-noun
 //! pattern (e.g., `bnity create bot`, `bnity list bots`).
 
+pub mod audit;
 pub mod bot;
 pub mod builder;
 pub mod chat;
+pub mod color;
+pub mod complete;
+pub mod db;
+pub mod doctor;
 pub mod kv;
 pub mod memory;
 pub mod message;
+pub mod output;
+pub mod picker;
+pub mod progress;
 pub mod provider;
+pub mod search;
 pub mod secret;
 pub mod session;
 pub mod shared_memory;
@@ -30,13 +39,20 @@ use clap_complete::Shell;
 #[command(propagate_version = true)]
 pub struct Cli {
     /// Output machine-readable JSON instead of styled text.
-    #[arg(long, global = true)]
+    ///
+    /// Every command prints a `{status, data, warnings}` envelope, pretty on
+    /// a terminal and compact when piped.
+    #[arg(long, global = true, env = "BNITY_JSON")]
     pub json: bool,
 
     /// Suppress all output except errors.
     #[arg(long, global = true)]
     pub quiet: bool,
 
+    /// Disable colored output (also off when piped or `NO_COLOR` is set).
+    #[arg(long, global = true)]
+    pub no_color: bool,
+
     /// Detailed output (-v for verbose, -vv for debug/trace).
     #[arg(short, long, action = clap::ArgAction::Count, global = true)]
     pub verbose: u8,
@@ -62,8 +78,14 @@ pub enum Commands {
 
     /// Show details of a bot.
     Show {
-        /// Bot slug to display.
-        slug: String,
+        /// Bot slug to display (pick interactively if omitted).
+        slug: Option<String>,
+    },
+
+    /// Bot lifecycle management (status).
+    Bot {
+        #[command(subcommand)]
+        action: bot::BotCommand,
     },
 
     /// Delete a resource.
@@ -85,6 +107,12 @@ pub enum Commands {
         resource: SetResource,
     },
 
+    /// Inspect stored secrets (show).
+    Secret {
+        #[command(subcommand)]
+        action: secret::SecretCommand,
+    },
+
     /// Soul management (edit, history, diff, rollback, verify).
     Soul {
         #[command(subcommand)]
@@ -124,13 +152,36 @@ pub enum Commands {
 
     /// System health check for a bot.
     Check {
-        /// Bot slug to check.
-        slug: String,
+        /// Bot slug to check (pick interactively if omitted).
+        slug: Option<String>,
+
+        /// Also validate IDENTITY.md frontmatter and list problems.
+        #[arg(long)]
+        identity: bool,
     },
 
     /// System status dashboard.
     Status,
 
+    /// Diagnose the installation: data dir, database, vault, providers, vector store.
+    Doctor,
+
+    /// Database maintenance (migrate, backup, vacuum, stats).
+    Db {
+        #[command(subcommand)]
+        action: db::DbCommand,
+    },
+
+    /// Search bots, sessions, messages, and memories.
+    Search {
+        /// Text to search for.
+        query: String,
+
+        /// Maximum results per source.
+        #[arg(long, default_value = "10")]
+        limit: usize,
+    },
+
     /// Start the REST API server.
     Serve {
         /// Port to listen on.
@@ -140,6 +191,14 @@ pub enum Commands {
         /// Host to bind to.
         #[arg(long, default_value = "127.0.0.1")]
         host: String,
+
+        /// PEM certificate chain; serves HTTPS when given with --tls-key.
+        #[arg(long, requires = "tls_key")]
+        tls_cert: Option<std::path::PathBuf>,
+
+        /// PEM private key for --tls-cert.
+        #[arg(long, requires = "tls_cert")]
+        tls_key: Option<std::path::PathBuf>,
     },
 
     /// Generate shell completions.
@@ -148,22 +207,76 @@ pub enum Commands {
         shell: Shell,
     },
 
+    /// Print dynamic completion values (used by the completion scripts).
+    #[command(name = "__complete", hide = true)]
+    Complete {
+        /// Kind of value to list; inferred from `words` when omitted.
+        context: Option<complete::CompleteContext>,
+
+        /// Words typed so far after `bnity`.
+        #[arg(last = true)]
+        words: Vec<String>,
+    },
+
     /// Export a resource (session).
     Export {
         #[command(subcommand)]
         resource: ExportResource,
     },
 
+    /// Import a resource (session) from an export bundle.
+    Import {
+        #[command(subcommand)]
+        resource: ImportResource,
+    },
+
+    /// Query or export the shared-memory audit log.
+    Audit {
+        /// Only entries within this window (e.g. 30m, 12h, 1d, 2w) or after
+        /// an RFC 3339 timestamp.
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Filter by actor (`system`, `user`, or a bot slug).
+        #[arg(long)]
+        actor: Option<String>,
+
+        /// Filter by action (add, delete, share, revoke, merge).
+        #[arg(long)]
+        action: Option<String>,
+
+        /// Only entries for this bot (slug).
+        #[arg(long)]
+        bot: Option<String>,
+
+        /// Maximum number of entries.
+        #[arg(long, default_value = "50")]
+        limit: u32,
+
+        /// Number of entries to skip (for paging through older entries).
+        #[arg(long, default_value = "0")]
+        offset: u32,
+
+        /// Write a plain export (json or csv) instead of a table.
+        #[arg(long, value_enum)]
+        format: Option<audit::AuditFormat>,
+    },
+
     /// Browse past sessions for a bot.
     Sessions {
         /// Bot slug.
         slug: String,
     },
 
-    /// Browse memories for a bot.
+    /// Browse memories for a bot, or edit one (`memories edit <id>`).
+    #[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
     Memories {
         /// Bot slug.
-        slug: String,
+        #[arg(required = true)]
+        slug: Option<String>,
+
+        #[command(subcommand)]
+        action: Option<memory::MemoriesCommand>,
     },
 
     /// Manually inject a memory for a bot.
@@ -187,8 +300,8 @@ pub enum Commands {
 
     /// Start an interactive chat session with a bot.
     Chat {
-        /// Bot slug to chat with.
-        slug: String,
+        /// Bot slug to chat with (pick interactively if omitted).
+        slug: Option<String>,
 
         /// Resume a previous session by ID.
         #[arg(long)]
@@ -201,6 +314,11 @@ pub enum Commands {
         /// Suppress sub-agent detail, showing only the final synthesized response.
         #[arg(long, short = 'q')]
         quiet: bool,
+
+        /// Hard cap on estimated session spend in USD (e.g., 0.50). Further
+        /// messages are blocked once the cap is reached.
+        #[arg(long, value_name = "USD", value_parser = chat::loop_runner::parse_max_cost)]
+        max_cost: Option<f64>,
     },
 
     /// Manage workflows (create, trigger, list, status, logs, delete, approve, cancel).
@@ -218,9 +336,13 @@ pub enum Commands {
     /// Interactive bot builder wizard powered by Forge.
     #[command(alias = "create-wizard")]
     Build {
-        /// Resume a previously saved builder session.
-        #[arg(long)]
-        resume: bool,
+        /// List saved builder drafts.
+        #[arg(long, conflicts_with_all = ["resume", "reconfigure"])]
+        list: bool,
+
+        /// Resume a saved builder session, optionally by id (or id prefix).
+        #[arg(long, value_name = "ID")]
+        resume: Option<Option<String>>,
 
         /// Reconfigure an existing bot by slug.
         #[arg(long)]
@@ -256,6 +378,10 @@ pub enum CreateResource {
         /// Category (assistant, creative, research, utility).
         #[arg(long)]
         category: Option<String>,
+
+        /// Create the bot from a TOML spec file (name, soul, identity, secrets).
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["name", "description", "category"])]
+        spec: Option<std::path::PathBuf>,
     },
 }
 
@@ -270,19 +396,32 @@ pub enum ListResource {
         /// Sort by field (name, created_at, last_active_at).
         #[arg(long, default_value = "created_at")]
         sort: String,
+
+        /// Include soft-deleted bots.
+        #[arg(long)]
+        deleted: bool,
     },
 
     /// List stored secrets (masked).
-    Secrets,
+    Secrets {
+        /// Show which provider currently supplies each value (e.g. an env
+        /// var overriding the vault).
+        #[arg(long)]
+        sources: bool,
+    },
 }
 
 #[derive(Subcommand)]
 pub enum DeleteResource {
-    /// Delete a bot permanently.
+    /// Delete a bot (recoverable with `bnity bot restore` unless --purge).
     Bot {
         /// Bot slug to delete.
         slug: String,
 
+        /// Permanently remove the bot and all its data.
+        #[arg(long)]
+        purge: bool,
+
         /// Skip confirmation prompt.
         #[arg(long)]
         force: bool,
@@ -329,6 +468,17 @@ pub enum SetResource {
         #[arg(long)]
         value: Option<String>,
     },
+
+    /// Import secrets in bulk from a dotenv-format file.
+    Secrets {
+        /// Path to the dotenv file (KEY=value per line).
+        #[arg(long, value_name = "FILE")]
+        from_env: std::path::PathBuf,
+
+        /// Secret scope: `global` (default) or `bot:<slug>`.
+        #[arg(long)]
+        scope: Option<String>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -337,6 +487,24 @@ pub enum ExportResource {
     Session {
         /// Session ID to export.
         id: String,
+
+        /// Sign the JSON bundle with the vault's export key so tampering is
+        /// detected on import (requires --json).
+        #[arg(long)]
+        sign: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ImportResource {
+    /// Import a chat session from a JSON export bundle.
+    Session {
+        /// Path to the bundle written by `bnity --json export session`.
+        file: std::path::PathBuf,
+
+        /// Accept a bundle that carries no signature.
+        #[arg(long)]
+        allow_unsigned: bool,
     },
 }
 
@@ -0,0 +1,136 @@
+//! Structured output for `--json` mode.
+//!
+//! Every command prints the same envelope so scripts can rely on one shape:
+//!
+//! ```json
+//! { "status": "ok", "data": { ... }, "warnings": [] }
+//! ```
+//!
+//! Failures print `"status": "error"` with the message in `error` and a null
+//! `data` (see [`print_error`]). Output is pretty-printed on a terminal and
+//! compact (one line per response) when piped.
+
+use std::io::IsTerminal;
+
+use anyhow::Result;
+use serde::Serialize;
+
+/// Whether the command succeeded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ResponseStatus {
+    Ok,
+    Error,
+}
+
+/// The JSON envelope printed by every command in `--json` mode.
+#[derive(Debug, Serialize)]
+pub struct CliResponse<T> {
+    pub status: ResponseStatus,
+    pub data: T,
+    /// Non-fatal problems (provider failover, skipped steps) that styled
+    /// output would print as yellow notes.
+    pub warnings: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl<T: Serialize> CliResponse<T> {
+    /// A successful response without warnings.
+    pub fn ok(data: T) -> Self {
+        Self {
+            status: ResponseStatus::Ok,
+            data,
+            warnings: Vec::new(),
+            error: None,
+        }
+    }
+
+    /// Attach warnings to the response.
+    pub fn with_warnings(mut self, warnings: Vec<String>) -> Self {
+        self.warnings = warnings;
+        self
+    }
+
+    /// Serialize the envelope, pretty-printed or compact.
+    pub fn render(&self, pretty: bool) -> Result<String> {
+        Ok(if pretty {
+            serde_json::to_string_pretty(self)?
+        } else {
+            serde_json::to_string(self)?
+        })
+    }
+
+    /// Print the envelope to stdout.
+    pub fn print(&self) -> Result<()> {
+        println!("{}", self.render(std::io::stdout().is_terminal())?);
+        Ok(())
+    }
+}
+
+impl CliResponse<()> {
+    /// A failed response carrying the error chain as its message.
+    pub fn error(err: &anyhow::Error) -> Self {
+        Self {
+            status: ResponseStatus::Error,
+            data: (),
+            warnings: Vec::new(),
+            error: Some(format!("{err:#}")),
+        }
+    }
+}
+
+/// Print `data` as a successful response.
+pub fn print_json<T: Serialize>(data: &T) -> Result<()> {
+    CliResponse::ok(data).print()
+}
+
+/// Print `data` as a successful response with warnings.
+pub fn print_json_with_warnings<T: Serialize>(data: &T, warnings: Vec<String>) -> Result<()> {
+    CliResponse::ok(data).with_warnings(warnings).print()
+}
+
+/// Print a command failure as an error response.
+pub fn print_error(err: &anyhow::Error) -> Result<()> {
+    CliResponse::error(err).print()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ok_envelope_shape() {
+        let response = CliResponse::ok(serde_json::json!({"slug": "luna"}));
+        let value: serde_json::Value =
+            serde_json::from_str(&response.render(false).unwrap()).unwrap();
+
+        assert_eq!(
+            value,
+            serde_json::json!({"status": "ok", "data": {"slug": "luna"}, "warnings": []})
+        );
+    }
+
+    #[test]
+    fn test_warnings_are_included() {
+        let response = CliResponse::ok(vec![1, 2])
+            .with_warnings(vec!["Primary provider unavailable".to_string()]);
+        let value: serde_json::Value =
+            serde_json::from_str(&response.render(true).unwrap()).unwrap();
+
+        assert_eq!(value["warnings"][0], "Primary provider unavailable");
+        assert_eq!(value["data"], serde_json::json!([1, 2]));
+    }
+
+    #[test]
+    fn test_error_envelope_has_null_data() {
+        let err = anyhow::anyhow!("bot not found").context("Failed to load bot");
+        let rendered = CliResponse::error(&err).render(false).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&rendered).unwrap();
+
+        assert_eq!(value["status"], "error");
+        assert!(value["data"].is_null());
+        assert_eq!(value["error"], "Failed to load bot: bot not found");
+        assert!(!rendered.contains('\n'));
+    }
+}
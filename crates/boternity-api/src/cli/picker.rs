@@ -0,0 +1,225 @@
+//! Interactive bot picker for commands that take a bot slug.
+//!
+//! When the slug is omitted or matches no bot, a terminal session gets a
+//! selection list ranked by similarity to what was typed. In `--json` mode or
+//! without a terminal the command fails instead, suggesting close matches.
+
+use std::io::IsTerminal;
+
+use anyhow::{Result, bail};
+use dialoguer::Select;
+
+use boternity_types::bot::Bot;
+
+use crate::state::AppState;
+
+/// Maximum number of "did you mean" suggestions in an error.
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Resolve a possibly missing or mistyped slug to an existing bot's slug.
+pub async fn resolve_bot_slug(state: &AppState, slug: Option<&str>, json: bool) -> Result<String> {
+    if let Some(slug) = slug
+        && let Ok(bot) = state.bot_service.get_bot_by_slug(slug).await
+    {
+        return Ok(bot.slug);
+    }
+
+    let bots = state.bot_service.list_bots(None).await?;
+    let interactive = !json && std::io::stdin().is_terminal() && std::io::stdout().is_terminal();
+    let bot = select_bot(slug, &bots, interactive, |prompt, items| {
+        Ok(Select::new()
+            .with_prompt(prompt)
+            .items(items)
+            .default(0)
+            .interact_opt()?)
+    })?;
+    Ok(bot.slug.clone())
+}
+
+/// Pick a bot for `query` (the slug as typed, if any).
+///
+/// Non-interactive callers get an error listing close matches. Otherwise
+/// `pick` is shown the prompt and display lines (best match first) and
+/// returns the chosen index, or `None` if the user cancelled.
+fn select_bot<'a>(
+    query: Option<&str>,
+    bots: &'a [Bot],
+    interactive: bool,
+    pick: impl FnOnce(&str, &[String]) -> Result<Option<usize>>,
+) -> Result<&'a Bot> {
+    if bots.is_empty() {
+        bail!("No bots yet. Create one with: bnity create bot");
+    }
+    if !interactive {
+        bail!(not_found_message(query, bots));
+    }
+
+    let ranked = rank_bots(query.unwrap_or_default(), bots);
+    let items: Vec<String> = ranked
+        .iter()
+        .map(|bot| format!("{} ({}) -- {}", bot.name, bot.slug, bot.description))
+        .collect();
+    let prompt = match query {
+        Some(query) => format!("No bot '{query}'. Pick one"),
+        None => "Pick a bot".to_string(),
+    };
+    match pick(&prompt, &items)? {
+        Some(index) if index < ranked.len() => Ok(ranked[index]),
+        _ => bail!("No bot selected"),
+    }
+}
+
+/// The error shown when a slug cannot be resolved without prompting.
+fn not_found_message(query: Option<&str>, bots: &[Bot]) -> String {
+    let Some(query) = query else {
+        return "A bot slug is required (see: bnity list bots)".to_string();
+    };
+    let suggestions: Vec<String> = rank_bots(query, bots)
+        .into_iter()
+        .filter(|bot| similarity(query, bot).is_some())
+        .take(MAX_SUGGESTIONS)
+        .map(|bot| format!("'{}'", bot.slug))
+        .collect();
+    if suggestions.is_empty() {
+        format!("Bot '{query}' not found (see: bnity list bots)")
+    } else {
+        format!(
+            "Bot '{query}' not found. Did you mean {}?",
+            suggestions.join(", ")
+        )
+    }
+}
+
+/// Bots ordered by similarity to `query`; unrelated bots keep list order
+/// at the end. An empty query keeps list order.
+fn rank_bots<'a>(query: &str, bots: &'a [Bot]) -> Vec<&'a Bot> {
+    let mut ranked: Vec<(usize, &Bot)> = bots
+        .iter()
+        .map(|bot| (similarity(query, bot).unwrap_or(usize::MAX), bot))
+        .collect();
+    ranked.sort_by_key(|(score, _)| *score);
+    ranked.into_iter().map(|(_, bot)| bot).collect()
+}
+
+/// Match cost of `query` against a bot's slug and name (lower is closer),
+/// or `None` when they are unrelated.
+///
+/// Substring matches rank first; otherwise the edit distance must be within
+/// a third of the query length (at least 2).
+fn similarity(query: &str, bot: &Bot) -> Option<usize> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return None;
+    }
+    let max_distance = (query.chars().count() / 3).max(2);
+    [bot.slug.to_lowercase(), bot.name.to_lowercase()]
+        .iter()
+        .filter_map(|candidate| {
+            if candidate.contains(&query) {
+                Some(0)
+            } else {
+                let distance = edit_distance(&query, candidate);
+                (distance <= max_distance).then_some(distance + 1)
+            }
+        })
+        .min()
+}
+
+/// Levenshtein distance over characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = prev[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(prev[j + 1] + 1).min(current[j] + 1);
+        }
+        prev = current;
+    }
+    prev[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use boternity_types::bot::{BotCategory, BotId, BotStatus};
+
+    fn bot(name: &str) -> Bot {
+        let now = chrono::Utc::now();
+        Bot {
+            id: BotId::new(),
+            slug: boternity_types::bot::slugify(name),
+            name: name.to_string(),
+            description: format!("{name} bot"),
+            status: BotStatus::Active,
+            category: BotCategory::Assistant,
+            tags: vec![],
+            user_id: None,
+            conversation_count: 0,
+            total_tokens_used: 0,
+            version_count: 0,
+            created_at: now,
+            updated_at: now,
+            last_active_at: None,
+            deleted_at: None,
+        }
+    }
+
+    fn no_prompt(_: &str, _: &[String]) -> Result<Option<usize>> {
+        panic!("non-interactive mode must not prompt")
+    }
+
+    #[test]
+    fn test_non_interactive_not_found_suggests_close_slugs() {
+        let bots = vec![bot("Luna"), bot("Research Assistant"), bot("Lunar Lander")];
+
+        let err = select_bot(Some("lnua"), &bots, false, no_prompt).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Bot 'lnua' not found. Did you mean 'luna'?"
+        );
+
+        let err = select_bot(Some("lun"), &bots, false, no_prompt).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Bot 'lun' not found. Did you mean 'luna', 'lunar-lander'?"
+        );
+
+        let err = select_bot(Some("zzzzzz"), &bots, false, no_prompt).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Bot 'zzzzzz' not found (see: bnity list bots)"
+        );
+    }
+
+    #[test]
+    fn test_non_interactive_missing_slug_errors() {
+        let bots = vec![bot("Luna")];
+        let err = select_bot(None, &bots, false, no_prompt).unwrap_err();
+        assert!(err.to_string().contains("slug is required"));
+    }
+
+    #[test]
+    fn test_interactive_picker_lists_best_match_first() {
+        let bots = vec![bot("Research Assistant"), bot("Luna")];
+
+        let picked = select_bot(Some("lnua"), &bots, true, |prompt, items| {
+            assert_eq!(prompt, "No bot 'lnua'. Pick one");
+            assert!(items[0].starts_with("Luna (luna)"));
+            Ok(Some(0))
+        })
+        .unwrap();
+        assert_eq!(picked.slug, "luna");
+
+        let cancelled = select_bot(None, &bots, true, |_, _| Ok(None));
+        assert!(cancelled.is_err());
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("luna", "luna"), 0);
+        assert_eq!(edit_distance("lnua", "luna"), 2);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+}
@@ -0,0 +1,86 @@
+//! Activity indicators for long-running steps.
+//!
+//! Flows that wait on the network or disk report progress through
+//! [`ProgressReporter`] rather than creating `indicatif` spinners inline, so
+//! they can run in tests (with [`NoopProgress`]) without drawing to the
+//! terminal.
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use indicatif::{ProgressBar, ProgressStyle};
+
+/// Shows that work is in progress.
+pub trait ProgressReporter: Send + Sync {
+    /// Show `message` with an activity indicator until [`finish`](Self::finish).
+    fn start(&self, message: &str);
+
+    /// Clear the indicator. Does nothing if none is showing.
+    fn finish(&self);
+}
+
+/// Reports nothing.
+pub struct NoopProgress;
+
+impl ProgressReporter for NoopProgress {
+    fn start(&self, _message: &str) {}
+
+    fn finish(&self) {}
+}
+
+/// An `indicatif` spinner for the CLI.
+pub struct SpinnerProgress {
+    /// Spinner color in indicatif template syntax (e.g. `cyan`).
+    color: &'static str,
+    bar: Mutex<Option<ProgressBar>>,
+}
+
+impl SpinnerProgress {
+    /// A cyan spinner.
+    pub fn new() -> Self {
+        Self::with_color("cyan")
+    }
+
+    /// A spinner in the given indicatif color.
+    pub fn with_color(color: &'static str) -> Self {
+        Self {
+            color,
+            bar: Mutex::new(None),
+        }
+    }
+}
+
+impl Default for SpinnerProgress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProgressReporter for SpinnerProgress {
+    fn start(&self, message: &str) {
+        let spinner = ProgressBar::new_spinner();
+        spinner.set_style(
+            ProgressStyle::default_spinner()
+                .template(&format!("{{spinner:.{}}} {{msg}}", self.color))
+                .unwrap(),
+        );
+        spinner.set_message(message.to_string());
+        spinner.enable_steady_tick(Duration::from_millis(80));
+        if let Some(previous) = self.bar.lock().unwrap().replace(spinner) {
+            previous.finish_and_clear();
+        }
+    }
+
+    fn finish(&self) {
+        if let Some(spinner) = self.bar.lock().unwrap().take() {
+            spinner.finish_and_clear();
+        }
+    }
+}
+
+impl Drop for SpinnerProgress {
+    /// Clear a spinner left running by an early return.
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
@@ -1,4 +1,4 @@
-//! Provider management CLI commands: status, add, remove, list.
+//! Provider management CLI commands: status, add, remove, list, test, pricing.
 //!
 //! Provides `bnity provider` subcommand for configuring, monitoring,
 //! and managing LLM providers in the multi-provider fallback chain.
@@ -8,17 +8,22 @@
 
 use std::path::{Path, PathBuf};
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use clap::Subcommand;
-use comfy_table::{presets, Cell, Color, ContentArrangement, Table};
+use comfy_table::{Cell, Color};
 use console::style;
 
+use boternity_infra::llm::openai_compat::config::openrouter_capabilities;
+use boternity_infra::llm::pricing;
 use boternity_infra::llm::{create_provider, test_provider_connection};
+use boternity_types::config::ProviderPricing;
 use boternity_types::llm::{
     ProviderCapabilities, ProviderConfig, ProviderStatusInfo, ProviderType,
 };
 use boternity_types::secret::SecretScope;
 
+use crate::cli::color::new_table;
+use crate::cli::output::{print_json, print_json_with_warnings};
 use crate::state::AppState;
 
 /// Provider management subcommands.
@@ -29,11 +34,11 @@ pub enum ProviderCommand {
 
     /// Add a new LLM provider to the fallback chain.
     Add {
-        /// Provider name (e.g., "openai", "gemini", "mistral", "glm", "bedrock", "claude_subscription").
+        /// Provider name (e.g., "openai", "gemini", "mistral", "glm", "openrouter", "bedrock", "claude_subscription").
         #[arg(long)]
         name: String,
 
-        /// Provider type: anthropic, openai_compatible, bedrock, claude_subscription.
+        /// Provider type: anthropic, openai_compatible, bedrock, cohere, claude_subscription.
         #[arg(long, value_name = "TYPE")]
         provider_type: String,
 
@@ -70,6 +75,64 @@ pub enum ProviderCommand {
 
     /// List all providers in the fallback chain with priority order.
     List,
+
+    /// Test a configured provider: latency, streaming, and capabilities.
+    Test {
+        /// Provider name to test.
+        name: String,
+    },
+
+    /// Set a provider's priority, shifting colliding providers down the chain.
+    Reorder {
+        /// Provider name to move.
+        name: String,
+
+        /// New priority (lower = higher priority).
+        #[arg(long)]
+        priority: u32,
+    },
+
+    /// Move a provider one position earlier in the fallback chain.
+    Promote {
+        /// Provider name to promote.
+        name: String,
+    },
+
+    /// Move a provider one position later in the fallback chain.
+    Demote {
+        /// Provider name to demote.
+        name: String,
+    },
+
+    /// Manage the pricing table used for cost estimates.
+    Pricing {
+        #[command(subcommand)]
+        command: PricingCommand,
+    },
+}
+
+/// Pricing table subcommands.
+#[derive(Subcommand)]
+pub enum PricingCommand {
+    /// Load and validate a pricing JSON file or URL, replacing the saved table.
+    Update {
+        /// Local file path or http(s) URL of the pricing JSON.
+        source: String,
+    },
+
+    /// Show the pricing entries used for cost estimates, in lookup order.
+    Show,
+}
+
+/// A priority change requested from the CLI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PriorityChange {
+    /// Set an explicit priority.
+    Set(u32),
+    /// Swap with the provider immediately ahead.
+    Promote,
+    /// Swap with the provider immediately behind.
+    Demote,
 }
 
 /// Handle a provider management subcommand.
@@ -106,6 +169,20 @@ pub async fn handle_provider_command(
         }
         ProviderCommand::Remove { name } => provider_remove(state, &name, json).await,
         ProviderCommand::List => provider_list(state, json).await,
+        ProviderCommand::Test { name } => provider_test(state, &name, json).await,
+        ProviderCommand::Reorder { name, priority } => {
+            provider_reorder(state, &name, PriorityChange::Set(priority), json).await
+        }
+        ProviderCommand::Promote { name } => {
+            provider_reorder(state, &name, PriorityChange::Promote, json).await
+        }
+        ProviderCommand::Demote { name } => {
+            provider_reorder(state, &name, PriorityChange::Demote, json).await
+        }
+        ProviderCommand::Pricing { command } => match command {
+            PricingCommand::Update { source } => pricing_update(state, &source, json).await,
+            PricingCommand::Show => pricing_show(state, json).await,
+        },
     }
 }
 
@@ -118,7 +195,7 @@ async fn provider_status(state: &AppState, json: bool) -> Result<()> {
 
     if configs.is_empty() {
         if json {
-            println!("[]");
+            print_json(&serde_json::json!([]))?;
         } else {
             println!();
             println!(
@@ -131,26 +208,36 @@ async fn provider_status(state: &AppState, json: bool) -> Result<()> {
         return Ok(());
     }
 
-    // Build a temporary fallback chain to get live health status.
-    // Note: In a running chat session the health is tracked in-memory.
-    // For the CLI status command we show the configured providers with
-    // their default (healthy) state, since circuit breaker state is
-    // only meaningful during an active session.
+    // Circuit state is tracked in-memory during a chat session and persisted
+    // when it changes; providers with no persisted row are shown healthy.
+    let persisted = state.provider_health_store.load_all().await.unwrap_or_default();
     let statuses: Vec<ProviderStatusInfo> = configs
         .iter()
-        .map(|c| ProviderStatusInfo {
-            name: c.name.clone(),
-            circuit_state: "closed".to_string(),
-            last_error: None,
-            last_success_ago: None,
-            total_calls: 0,
-            total_failures: 0,
-            uptime_since: Some(chrono::Utc::now().to_rfc3339()),
+        .map(|c| match persisted.iter().find(|row| row.name == c.name) {
+            Some(row) => ProviderStatusInfo {
+                name: c.name.clone(),
+                circuit_state: row.circuit_state.clone(),
+                last_error: row.last_error.clone(),
+                last_success_ago: None,
+                total_calls: row.total_calls,
+                total_failures: row.total_failures,
+                uptime_since: row.uptime_since.map(|t| t.to_rfc3339()),
+            },
+            None => ProviderStatusInfo {
+                name: c.name.clone(),
+                circuit_state: "closed".to_string(),
+                last_error: None,
+                last_success_ago: None,
+                total_calls: 0,
+                total_failures: 0,
+                uptime_since: Some(chrono::Utc::now().to_rfc3339()),
+            },
         })
         .collect();
+    let warnings = failover_warnings(&configs, &statuses);
 
     if json {
-        println!("{}", serde_json::to_string_pretty(&statuses)?);
+        print_json_with_warnings(&statuses, warnings)?;
         return Ok(());
     }
 
@@ -158,9 +245,7 @@ async fn provider_status(state: &AppState, json: bool) -> Result<()> {
     println!("  {}", style("Provider Health Status").bold());
     println!();
 
-    let mut table = Table::new();
-    table.load_preset(presets::UTF8_FULL_CONDENSED);
-    table.set_content_arrangement(ContentArrangement::Dynamic);
+    let mut table = new_table();
 
     table.set_header(vec![
         Cell::new("Priority").fg(Color::White),
@@ -219,6 +304,9 @@ async fn provider_status(state: &AppState, json: bool) -> Result<()> {
         style(configs.len()).bold(),
         if configs.len() == 1 { "" } else { "s" }
     );
+    for warning in &warnings {
+        println!("  {} {}", style("!").yellow().bold(), warning);
+    }
     println!(
         "  {}",
         style("Circuit breaker state resets each chat session.").dim()
@@ -292,7 +380,7 @@ async fn provider_add(
     };
 
     // Infer default capabilities from provider type/name
-    let capabilities = infer_capabilities(name, &provider_type);
+    let capabilities = infer_capabilities(name, model, &provider_type);
 
     let config = ProviderConfig {
         name: name.to_string(),
@@ -303,6 +391,8 @@ async fn provider_add(
         priority,
         enabled: true,
         capabilities,
+        extra_headers: Default::default(),
+        extra_body: Default::default(),
     };
 
     // Test connection unless skipped
@@ -314,20 +404,14 @@ async fn provider_add(
 
         let provider = create_provider(&config, api_key.as_deref())?;
         match test_provider_connection(&provider).await {
-            Ok(()) => {
+            Ok(_) => {
                 if !json {
                     println!("{}", style("connected").green().bold());
                 }
             }
             Err(e) => {
                 if json {
-                    let err = serde_json::json!({
-                        "error": "connection_test_failed",
-                        "message": e.to_string(),
-                        "provider": name,
-                    });
-                    println!("{}", serde_json::to_string_pretty(&err)?);
-                    return Ok(());
+                    bail!("Connection test failed for provider '{name}': {e}");
                 }
 
                 println!("{}", style("FAILED").red().bold());
@@ -351,7 +435,7 @@ async fn provider_add(
     save_provider_configs(&state.data_dir, &configs).await?;
 
     if json {
-        println!("{}", serde_json::to_string_pretty(&config)?);
+        print_json(&config)?;
     } else {
         println!(
             "  {} Provider '{}' added (priority {}, model: {}).",
@@ -374,10 +458,7 @@ async fn provider_remove(state: &AppState, name: &str, json: bool) -> Result<()>
 
     if configs.len() == before_len {
         if json {
-            println!(
-                "{}",
-                serde_json::json!({"error": "not_found", "provider": name})
-            );
+            bail!("Provider '{name}' not found");
         } else {
             println!(
                 "  {} Provider '{}' not found.",
@@ -391,10 +472,7 @@ async fn provider_remove(state: &AppState, name: &str, json: bool) -> Result<()>
     save_provider_configs(&state.data_dir, &configs).await?;
 
     if json {
-        println!(
-            "{}",
-            serde_json::json!({"removed": true, "provider": name})
-        );
+        print_json(&serde_json::json!({"removed": true, "provider": name}))?;
     } else {
         println!(
             "  {} Provider '{}' removed.",
@@ -406,13 +484,259 @@ async fn provider_remove(state: &AppState, name: &str, json: bool) -> Result<()>
     Ok(())
 }
 
+/// Test a configured provider and report latency and capabilities.
+///
+/// Streams a minimal request to measure first-token and total latency,
+/// falling back to a non-streaming request if streaming fails.
+async fn provider_test(state: &AppState, name: &str, json: bool) -> Result<()> {
+    let configs = load_provider_configs(&state.data_dir).await?;
+    let Some(config) = configs.iter().find(|c| c.name == name) else {
+        if json {
+            bail!("Provider '{name}' not found");
+        } else {
+            println!(
+                "  {} Provider '{}' not found.",
+                style("?").yellow().bold(),
+                style(name).cyan()
+            );
+        }
+        return Ok(());
+    };
+
+    let api_key = match config.api_key_secret_name.as_deref() {
+        Some(secret) => Some(
+            state
+                .secret_service
+                .get_secret(secret, &SecretScope::Global)
+                .await?
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Secret '{}' not found in vault. Set it with: bnity set secret {}",
+                        secret,
+                        secret
+                    )
+                })?,
+        ),
+        None => None,
+    };
+
+    if !json {
+        print!("  Testing {} ({})... ", style(name).cyan(), config.model);
+        let _ = std::io::Write::flush(&mut std::io::stdout());
+    }
+
+    let provider = create_provider(config, api_key.as_deref())?;
+    let report = match test_provider_connection(&provider).await {
+        Ok(report) => report,
+        Err(e) => {
+            if json {
+                bail!("Connection test failed for provider '{name}': {e}");
+            } else {
+                println!("{}", style("FAILED").red().bold());
+                eprintln!(
+                    "  {} Connection test failed: {}",
+                    style("!").red().bold(),
+                    e
+                );
+            }
+            return Ok(());
+        }
+    };
+
+    if json {
+        let mut value = serde_json::to_value(&report)?;
+        value["model"] = serde_json::json!(config.model);
+        print_json(&value)?;
+        return Ok(());
+    }
+
+    println!("{}", style("connected").green().bold());
+    println!();
+
+    let first_token = report
+        .first_token_ms
+        .map(|ms| format!("{ms} ms"))
+        .unwrap_or_else(|| "-".to_string());
+    let streaming = if report.streaming_ok {
+        style("works".to_string()).green()
+    } else if let Some(ref err) = report.streaming_error {
+        style(format!("failed ({err})")).red()
+    } else {
+        style("not supported".to_string()).dim()
+    };
+    let flag = |on: bool| if on { style("yes").green() } else { style("no").dim() };
+    let caps = &report.capabilities;
+
+    println!("  {:<20} {}", style("First token").dim(), first_token);
+    println!("  {:<20} {} ms", style("Total latency").dim(), report.total_ms);
+    println!("  {:<20} {}", style("Streaming").dim(), streaming);
+    println!("  {:<20} {}", style("Tool calling").dim(), flag(caps.tool_calling));
+    println!("  {:<20} {}", style("Vision").dim(), flag(caps.vision));
+    println!("  {:<20} {}", style("Extended thinking").dim(), flag(caps.extended_thinking));
+    println!("  {:<20} {}", style("Context window").dim(), caps.max_context_tokens);
+    println!("  {:<20} {}", style("Max output").dim(), caps.max_output_tokens);
+    println!();
+
+    Ok(())
+}
+
+/// Change a provider's position in the fallback chain and persist it.
+async fn provider_reorder(
+    state: &AppState,
+    name: &str,
+    change: PriorityChange,
+    json: bool,
+) -> Result<()> {
+    let mut configs = load_provider_configs(&state.data_dir).await?;
+    apply_priority_change(&mut configs, name, change)?;
+    save_provider_configs(&state.data_dir, &configs).await?;
+
+    configs.sort_by_key(|c| c.priority);
+
+    if json {
+        print_json(&configs)?;
+        return Ok(());
+    }
+
+    let priority = configs
+        .iter()
+        .find(|c| c.name == name)
+        .map(|c| c.priority)
+        .unwrap_or_default();
+    println!(
+        "  {} Provider '{}' now at priority {}.",
+        style("~").cyan().bold(),
+        style(name).cyan(),
+        priority
+    );
+    println!(
+        "  {} {}",
+        style("Fallback order:").dim(),
+        fallback_order(&configs).join(" -> ")
+    );
+
+    Ok(())
+}
+
+/// Apply a [`PriorityChange`] to the named provider.
+///
+/// `Set` moves the provider to the given priority and shifts any provider it
+/// collides with (and any further collisions) down by one. `Promote`/`Demote`
+/// swap priorities with the neighbouring provider in fallback order.
+/// Fails if the provider is unknown, cannot move further, or the result
+/// contains duplicate priorities.
+fn apply_priority_change(
+    configs: &mut [ProviderConfig],
+    name: &str,
+    change: PriorityChange,
+) -> Result<()> {
+    let idx = configs
+        .iter()
+        .position(|c| c.name == name)
+        .ok_or_else(|| anyhow::anyhow!("Provider '{}' not found.", name))?;
+
+    let mut order: Vec<usize> = (0..configs.len()).collect();
+    order.sort_by_key(|&i| configs[i].priority);
+    let pos = order.iter().position(|&i| i == idx).unwrap_or_default();
+
+    match change {
+        PriorityChange::Set(priority) => {
+            configs[idx].priority = priority;
+            let mut next = priority;
+            for &i in order.iter().filter(|&&i| i != idx) {
+                let current = configs[i].priority;
+                if current < next {
+                    continue;
+                }
+                if current > next {
+                    break;
+                }
+                next += 1;
+                configs[i].priority = next;
+            }
+        }
+        PriorityChange::Promote => {
+            if pos == 0 {
+                anyhow::bail!("Provider '{}' is already first in the fallback chain.", name);
+            }
+            let other = order[pos - 1];
+            let (a, b) = (configs[idx].priority, configs[other].priority);
+            configs[idx].priority = b;
+            configs[other].priority = a;
+        }
+        PriorityChange::Demote => {
+            if pos + 1 >= order.len() {
+                anyhow::bail!("Provider '{}' is already last in the fallback chain.", name);
+            }
+            let other = order[pos + 1];
+            let (a, b) = (configs[idx].priority, configs[other].priority);
+            configs[idx].priority = b;
+            configs[other].priority = a;
+        }
+    }
+
+    validate_unique_priorities(configs)
+}
+
+/// Ensure no two providers share a priority.
+fn validate_unique_priorities(configs: &[ProviderConfig]) -> Result<()> {
+    let mut seen = std::collections::HashMap::new();
+    for config in configs {
+        if let Some(other) = seen.insert(config.priority, &config.name) {
+            anyhow::bail!(
+                "Providers '{}' and '{}' share priority {}. Use `bnity provider reorder` to fix.",
+                other,
+                config.name,
+                config.priority
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Provider names in fallback order (ascending priority).
+fn fallback_order(configs: &[ProviderConfig]) -> Vec<&str> {
+    let mut sorted: Vec<&ProviderConfig> = configs.iter().collect();
+    sorted.sort_by_key(|c| c.priority);
+    sorted.into_iter().map(|c| c.name.as_str()).collect()
+}
+
+/// Describe where requests currently fail over to.
+///
+/// When the highest-priority enabled provider's circuit is open, requests go
+/// to the first enabled provider whose circuit is not.
+fn failover_warnings(configs: &[ProviderConfig], statuses: &[ProviderStatusInfo]) -> Vec<String> {
+    let is_open = |name: &str| {
+        statuses
+            .iter()
+            .any(|s| s.name == name && s.circuit_state == "open")
+    };
+    let enabled: Vec<&str> = fallback_order(configs)
+        .into_iter()
+        .filter(|name| configs.iter().any(|c| c.name == *name && c.enabled))
+        .collect();
+
+    let Some(primary) = enabled.first() else {
+        return Vec::new();
+    };
+    if !is_open(primary) {
+        return Vec::new();
+    }
+    match enabled.iter().find(|name| !is_open(name)) {
+        Some(fallback) => vec![format!(
+            "Primary provider '{primary}' is unavailable (circuit open); requests fail over to '{fallback}'"
+        )],
+        None => vec!["Every enabled provider has an open circuit; requests will fail".to_string()],
+    }
+}
+
 /// List all providers in fallback chain order.
 async fn provider_list(state: &AppState, json: bool) -> Result<()> {
     let mut configs = load_provider_configs(&state.data_dir).await?;
     configs.sort_by_key(|c| c.priority);
 
     if json {
-        println!("{}", serde_json::to_string_pretty(&configs)?);
+        print_json(&configs)?;
         return Ok(());
     }
 
@@ -431,9 +755,7 @@ async fn provider_list(state: &AppState, json: bool) -> Result<()> {
     println!("  {}", style("Fallback Chain Order").bold());
     println!();
 
-    let mut table = Table::new();
-    table.load_preset(presets::UTF8_FULL_CONDENSED);
-    table.set_content_arrangement(ContentArrangement::Dynamic);
+    let mut table = new_table();
 
     table.set_header(vec![
         Cell::new("Priority").fg(Color::White),
@@ -471,6 +793,123 @@ async fn provider_list(state: &AppState, json: bool) -> Result<()> {
     Ok(())
 }
 
+/// Load, validate, and save a pricing table from a file or URL.
+///
+/// Nothing is written unless every entry validates. Entries for models the
+/// built-in table does not know are kept but reported.
+async fn pricing_update(state: &AppState, source: &str, json: bool) -> Result<()> {
+    let content = pricing::fetch_pricing_source(source).await?;
+    let entries = pricing::parse_pricing_table(&content)?;
+    let path = pricing::save_pricing_file(&state.data_dir, &entries)
+        .await
+        .context("Failed to save pricing table")?;
+    let unknown = pricing::unknown_models(&entries);
+
+    if json {
+        let unknown: Vec<String> = unknown
+            .iter()
+            .map(|e| format!("{}/{}", e.provider_name, e.model_pattern))
+            .collect();
+        let result = serde_json::json!({
+            "source": source,
+            "path": path.display().to_string(),
+            "entries": entries.len(),
+            "unknown_models": unknown,
+        });
+        print_json(&result)?;
+        return Ok(());
+    }
+
+    println!(
+        "  {} Loaded {} pricing entr{} from {} into {}.",
+        style("+").green().bold(),
+        style(entries.len()).bold(),
+        if entries.len() == 1 { "y" } else { "ies" },
+        style(source).cyan(),
+        path.display()
+    );
+    for entry in &unknown {
+        println!(
+            "  {} {}/{} does not match any known model; check the provider and pattern.",
+            style("!").yellow().bold(),
+            entry.provider_name,
+            entry.model_pattern
+        );
+    }
+
+    Ok(())
+}
+
+/// Display the effective pricing entries in lookup order.
+///
+/// Configured entries (`config.toml` overrides, then `pricing.json`) match
+/// before the built-in table.
+async fn pricing_show(state: &AppState, json: bool) -> Result<()> {
+    let rows: Vec<(&str, ProviderPricing)> = state
+        .global_config
+        .provider_pricing
+        .iter()
+        .cloned()
+        .map(|p| ("configured", p))
+        .chain(pricing::default_pricing().into_iter().map(|p| ("built-in", p)))
+        .collect();
+
+    if json {
+        let rows: Vec<serde_json::Value> = rows
+            .iter()
+            .map(|(source, p)| {
+                serde_json::json!({
+                    "provider_name": p.provider_name,
+                    "model_pattern": p.model_pattern,
+                    "input_cost_per_million": p.input_cost_per_million,
+                    "output_cost_per_million": p.output_cost_per_million,
+                    "source": source,
+                })
+            })
+            .collect();
+        print_json(&rows)?;
+        return Ok(());
+    }
+
+    println!();
+    println!("  {}", style("Pricing Table (USD per million tokens)").bold());
+    println!();
+
+    let mut table = new_table();
+
+    table.set_header(vec![
+        Cell::new("Provider").fg(Color::White),
+        Cell::new("Model Pattern").fg(Color::White),
+        Cell::new("Input").fg(Color::White),
+        Cell::new("Output").fg(Color::White),
+        Cell::new("Source").fg(Color::White),
+    ]);
+
+    for (source, p) in &rows {
+        let source_cell = match *source {
+            "configured" => Cell::new(source).fg(Color::Cyan),
+            _ => Cell::new(source).fg(Color::DarkGrey),
+        };
+        table.add_row(vec![
+            Cell::new(&p.provider_name).fg(Color::White),
+            Cell::new(&p.model_pattern).fg(Color::White),
+            Cell::new(format!("${:.2}", p.input_cost_per_million)).fg(Color::Green),
+            Cell::new(format!("${:.2}", p.output_cost_per_million)).fg(Color::Green),
+            source_cell,
+        ]);
+    }
+
+    println!("{table}");
+    println!();
+    println!(
+        "  {}",
+        style("First matching entry wins. Update with: bnity provider pricing update <url|file>").dim()
+    );
+    println!();
+
+    Ok(())
+}
+
 // --- Persistence helpers ---
 
 /// Path to the providers.json file.
@@ -505,7 +944,14 @@ async fn save_provider_configs(data_dir: &Path, configs: &[ProviderConfig]) -> R
 }
 
 /// Infer default capabilities from provider name and type.
-fn infer_capabilities(name: &str, provider_type: &ProviderType) -> ProviderCapabilities {
+///
+/// OpenRouter proxies many model families, so its capabilities are inferred
+/// from the model identifier instead.
+pub(crate) fn infer_capabilities(
+    name: &str,
+    model: &str,
+    provider_type: &ProviderType,
+) -> ProviderCapabilities {
     match provider_type {
         ProviderType::Anthropic | ProviderType::Bedrock => ProviderCapabilities {
             streaming: true,
@@ -523,8 +969,16 @@ fn infer_capabilities(name: &str, provider_type: &ProviderType) -> ProviderCapab
             max_context_tokens: 200_000,
             max_output_tokens: 128_000,
         },
+        ProviderType::Cohere => ProviderCapabilities {
+            streaming: true,
+            tool_calling: true,
+            vision: false,
+            extended_thinking: false,
+            max_context_tokens: 128_000,
+            max_output_tokens: 4_000,
+        },
         ProviderType::OpenAiCompatible => match name {
-            "openai" => ProviderCapabilities {
+            "openai" | "azure" => ProviderCapabilities {
                 streaming: true,
                 tool_calling: true,
                 vision: true,
@@ -556,6 +1010,7 @@ fn infer_capabilities(name: &str, provider_type: &ProviderType) -> ProviderCapab
                 max_context_tokens: 200_000,
                 max_output_tokens: 128_000,
             },
+            "openrouter" => openrouter_capabilities(model),
             _ => ProviderCapabilities {
                 streaming: true,
                 tool_calling: true,
@@ -571,10 +1026,115 @@ fn infer_capabilities(name: &str, provider_type: &ProviderType) -> ProviderCapab
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cli::output::CliResponse;
+
+    fn chain(entries: &[(&str, u32)]) -> Vec<ProviderConfig> {
+        entries
+            .iter()
+            .map(|(name, priority)| ProviderConfig {
+                name: name.to_string(),
+                provider_type: ProviderType::OpenAiCompatible,
+                api_key_secret_name: None,
+                base_url: None,
+                model: "test-model".to_string(),
+                priority: *priority,
+                enabled: true,
+                capabilities: infer_capabilities(name, "test-model", &ProviderType::OpenAiCompatible),
+                extra_headers: Default::default(),
+                extra_body: Default::default(),
+            })
+            .collect()
+    }
+
+    fn status(name: &str, circuit_state: &str) -> ProviderStatusInfo {
+        ProviderStatusInfo {
+            name: name.to_string(),
+            circuit_state: circuit_state.to_string(),
+            last_error: None,
+            last_success_ago: None,
+            total_calls: 0,
+            total_failures: 0,
+            uptime_since: None,
+        }
+    }
+
+    #[test]
+    fn test_failover_warning_in_status_envelope() {
+        let configs = chain(&[("anthropic", 0), ("openai", 1), ("gemini", 2)]);
+        let statuses = vec![
+            status("anthropic", "open"),
+            status("openai", "open"),
+            status("gemini", "closed"),
+        ];
+
+        let warnings = failover_warnings(&configs, &statuses);
+        let rendered = CliResponse::ok(&statuses)
+            .with_warnings(warnings)
+            .render(false)
+            .unwrap();
+        let value: serde_json::Value = serde_json::from_str(&rendered).unwrap();
+
+        assert_eq!(value["status"], "ok");
+        assert_eq!(value["data"][0]["circuit_state"], "open");
+        let warnings = value["warnings"].as_array().unwrap();
+        assert_eq!(warnings.len(), 1);
+        let warning = warnings[0].as_str().unwrap();
+        assert!(warning.contains("'anthropic'"));
+        assert!(warning.contains("fail over to 'gemini'"));
+    }
+
+    #[test]
+    fn test_no_failover_warning_when_primary_healthy() {
+        let configs = chain(&[("anthropic", 0), ("openai", 1)]);
+        let statuses = vec![status("anthropic", "closed"), status("openai", "open")];
+        assert!(failover_warnings(&configs, &statuses).is_empty());
+    }
+
+    #[test]
+    fn test_reorder_moves_provider_and_shifts_collisions() {
+        let mut configs = chain(&[("openai", 0), ("gemini", 1), ("mistral", 2)]);
+        apply_priority_change(&mut configs, "mistral", PriorityChange::Set(0)).unwrap();
+        assert_eq!(fallback_order(&configs), vec!["mistral", "openai", "gemini"]);
+        validate_unique_priorities(&configs).unwrap();
+    }
+
+    #[test]
+    fn test_reorder_into_gap_leaves_others_alone() {
+        let mut configs = chain(&[("openai", 0), ("gemini", 10), ("mistral", 20)]);
+        apply_priority_change(&mut configs, "openai", PriorityChange::Set(15)).unwrap();
+        assert_eq!(fallback_order(&configs), vec!["gemini", "openai", "mistral"]);
+        assert_eq!(configs[1].priority, 10);
+        assert_eq!(configs[2].priority, 20);
+    }
+
+    #[test]
+    fn test_promote_and_demote_swap_neighbours() {
+        let mut configs = chain(&[("openai", 0), ("gemini", 1), ("mistral", 2)]);
+
+        apply_priority_change(&mut configs, "mistral", PriorityChange::Promote).unwrap();
+        assert_eq!(fallback_order(&configs), vec!["openai", "mistral", "gemini"]);
+
+        apply_priority_change(&mut configs, "openai", PriorityChange::Demote).unwrap();
+        assert_eq!(fallback_order(&configs), vec!["mistral", "openai", "gemini"]);
+    }
+
+    #[test]
+    fn test_promote_first_and_demote_last_fail() {
+        let mut configs = chain(&[("openai", 0), ("gemini", 1), ("mistral", 2)]);
+        assert!(apply_priority_change(&mut configs, "openai", PriorityChange::Promote).is_err());
+        assert!(apply_priority_change(&mut configs, "mistral", PriorityChange::Demote).is_err());
+        assert!(apply_priority_change(&mut configs, "missing", PriorityChange::Promote).is_err());
+    }
+
+    #[test]
+    fn test_validate_unique_priorities_rejects_duplicates() {
+        let configs = chain(&[("openai", 0), ("gemini", 0)]);
+        assert!(validate_unique_priorities(&configs).is_err());
+    }
 
     #[test]
     fn test_infer_capabilities_anthropic() {
-        let caps = infer_capabilities("anthropic", &ProviderType::Anthropic);
+        let caps = infer_capabilities("anthropic", "test-model", &ProviderType::Anthropic);
         assert!(caps.streaming);
         assert!(caps.tool_calling);
         assert!(caps.vision);
@@ -584,7 +1144,7 @@ mod tests {
 
     #[test]
     fn test_infer_capabilities_openai() {
-        let caps = infer_capabilities("openai", &ProviderType::OpenAiCompatible);
+        let caps = infer_capabilities("openai", "test-model", &ProviderType::OpenAiCompatible);
         assert_eq!(caps.max_context_tokens, 128_000);
         assert_eq!(caps.max_output_tokens, 16_384);
         assert!(caps.vision);
@@ -592,21 +1152,32 @@ mod tests {
 
     #[test]
     fn test_infer_capabilities_gemini() {
-        let caps = infer_capabilities("gemini", &ProviderType::OpenAiCompatible);
+        let caps = infer_capabilities("gemini", "test-model", &ProviderType::OpenAiCompatible);
         assert_eq!(caps.max_context_tokens, 1_000_000);
         assert_eq!(caps.max_output_tokens, 65_536);
     }
 
+    #[test]
+    fn test_infer_capabilities_openrouter_uses_model_family() {
+        let caps = infer_capabilities(
+            "openrouter",
+            "google/gemini-2.5-pro",
+            &ProviderType::OpenAiCompatible,
+        );
+        assert_eq!(caps.max_context_tokens, 1_000_000);
+        assert!(caps.vision);
+    }
+
     #[test]
     fn test_infer_capabilities_claude_subscription() {
-        let caps = infer_capabilities("claude_subscription", &ProviderType::ClaudeSubscription);
+        let caps = infer_capabilities("claude_subscription", "test-model", &ProviderType::ClaudeSubscription);
         assert!(caps.extended_thinking);
         assert_eq!(caps.max_output_tokens, 128_000);
     }
 
     #[test]
     fn test_infer_capabilities_unknown_openai_compat() {
-        let caps = infer_capabilities("custom-llm", &ProviderType::OpenAiCompatible);
+        let caps = infer_capabilities("custom-llm", "test-model", &ProviderType::OpenAiCompatible);
         assert_eq!(caps.max_context_tokens, 128_000);
         assert_eq!(caps.max_output_tokens, 8_192);
         assert!(!caps.vision);
@@ -0,0 +1,103 @@
+//! Global search CLI command: `bnity search "<query>"`.
+//!
+//! Searches bot descriptions, session titles, chat messages, and memories
+//! in one pass and prints the results grouped by source.
+
+use anyhow::{Context, Result};
+use console::style;
+
+use boternity_infra::search::{global_search, group_by_source};
+use boternity_types::search::SearchSource;
+
+use crate::cli::output::print_json;
+use crate::state::AppState;
+
+/// Search every bot's sessions, messages, and memories for `query`.
+///
+/// # Examples
+///
+/// ```bash
+/// bnity search "kubernetes upgrade"
+/// bnity search "dark mode" --limit 5 --json
+/// ```
+pub async fn search(state: &AppState, query: &str, limit: usize, json: bool) -> Result<()> {
+    let bots = state.bot_service.list_bots(None).await?;
+
+    let query_embedding = state
+        .embedder
+        .embed(&[query.to_string()])
+        .await
+        .context("Failed to embed search query")?
+        .into_iter()
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("Embedder returned no vectors"))?;
+
+    let hits = global_search(
+        &bots,
+        state.chat_service.chat_repo(),
+        state.vector_memory.as_ref(),
+        query,
+        &query_embedding,
+        limit,
+    )
+    .await
+    .context("Search failed")?;
+
+    if json {
+        print_json(&hits)?;
+        return Ok(());
+    }
+
+    println!();
+    if hits.is_empty() {
+        println!(
+            "  {} Nothing matched '{}'.",
+            style("i").blue().bold(),
+            style(query).dim()
+        );
+        println!();
+        return Ok(());
+    }
+
+    for (source, group) in group_by_source(&hits) {
+        println!(
+            "  {} {}",
+            style(source_heading(source)).bold(),
+            style(format!("({})", group.len())).dim()
+        );
+        for hit in group {
+            let location = match hit.session_id {
+                Some(id) if source != SearchSource::Memory => {
+                    format!("{} · session {}", hit.bot_slug, &id.to_string()[..8])
+                }
+                _ => hit.bot_slug.clone(),
+            };
+            println!(
+                "    {} {}  {}",
+                style(format!("{:.2}", hit.score)).dim(),
+                style(location).cyan(),
+                truncate(&hit.text.replace('\n', " "), 100)
+            );
+        }
+        println!();
+    }
+
+    Ok(())
+}
+
+fn source_heading(source: SearchSource) -> &'static str {
+    match source {
+        SearchSource::Bot => "Bots",
+        SearchSource::Session => "Sessions",
+        SearchSource::Message => "Messages",
+        SearchSource::Memory => "Memories",
+    }
+}
+
+fn truncate(s: &str, max_chars: usize) -> String {
+    if s.chars().count() <= max_chars {
+        s.to_string()
+    } else {
+        format!("{}...", s.chars().take(max_chars).collect::<String>())
+    }
+}
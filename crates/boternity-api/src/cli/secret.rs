@@ -1,15 +1,67 @@
-//! Secret management CLI commands: set, list.
+//! Secret management CLI commands: set, import, list, show.
 
-use anyhow::Result;
-use comfy_table::{presets, Cell, Color, ContentArrangement, Table};
+use std::collections::HashSet;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use clap::Subcommand;
+use comfy_table::{Cell, Color};
 use console::style;
-use dialoguer::Password;
+use dialoguer::{Confirm, Password};
 
 use boternity_core::service::secret::SecretService;
-use boternity_types::secret::SecretScope;
+use boternity_infra::secret::dotenv::parse_dotenv;
+use boternity_types::secret::{ScopeResolution, Secret, SecretScope};
 
+use crate::cli::color::new_table;
+use crate::cli::output::print_json;
 use crate::state::AppState;
 
+/// Secret inspection subcommands.
+#[derive(Subcommand)]
+pub enum SecretCommand {
+    /// Reveal a secret's plaintext value (asks for confirmation first).
+    Show {
+        /// Secret key name (e.g., ANTHROPIC_API_KEY).
+        key: String,
+
+        /// Secret scope: `global` (default) or `bot:<slug>`.
+        #[arg(long)]
+        scope: Option<String>,
+
+        /// Skip the confirmation prompt (required with --json).
+        #[arg(long, short = 'y')]
+        yes: bool,
+
+        /// Do not fall back to the global value when a bot scope has none.
+        #[arg(long)]
+        strict: bool,
+    },
+}
+
+/// Handle a secret subcommand.
+pub async fn handle_secret_command(
+    cmd: SecretCommand,
+    state: &AppState,
+    json: bool,
+) -> Result<()> {
+    match cmd {
+        SecretCommand::Show {
+            key,
+            scope,
+            yes,
+            strict,
+        } => {
+            let resolution = if strict {
+                ScopeResolution::Strict
+            } else {
+                ScopeResolution::Inherit
+            };
+            show_secret(state, &key, scope.as_deref(), resolution, yes, json).await
+        }
+    }
+}
+
 /// Set a secret value with hidden input prompt.
 ///
 /// # Examples
@@ -42,10 +94,7 @@ pub async fn set_secret(
         .await?;
 
     if json {
-        println!(
-            "{}",
-            serde_json::json!({"set": true, "key": key, "masked": SecretService::mask_secret(&secret_value)})
-        );
+        print_json(&serde_json::json!({"set": true, "key": key, "masked": SecretService::mask_secret(&secret_value)}))?;
     } else {
         println!(
             "  {} Secret '{}' set ({})",
@@ -58,15 +107,204 @@ pub async fn set_secret(
     Ok(())
 }
 
+/// Resolve a `--scope` argument into a [`SecretScope`].
+///
+/// Accepts `global` (the default when omitted) or `bot:<slug>`.
+pub async fn resolve_scope(state: &AppState, scope: Option<&str>) -> Result<SecretScope> {
+    match scope {
+        None | Some("global") => Ok(SecretScope::Global),
+        Some(s) => {
+            let slug = s.strip_prefix("bot:").ok_or_else(|| {
+                anyhow::anyhow!("Invalid scope '{s}'. Use 'global' or 'bot:<slug>'")
+            })?;
+            let bot = state.bot_service.get_bot_by_slug(slug).await?;
+            Ok(SecretScope::Bot(bot.id))
+        }
+    }
+}
+
+/// Import secrets in bulk from a dotenv-format file.
+///
+/// Blank lines, `#` comments, and `export ` prefixes are handled by the
+/// parser. Each entry is written through the secret service, so values
+/// land in the encrypted vault.
+///
+/// # Examples
+///
+/// ```bash
+/// bnity set secrets --from-env .env
+/// bnity set secrets --from-env .env --scope bot:my-bot
+/// ```
+pub async fn import_secrets(
+    state: &AppState,
+    path: &Path,
+    scope: Option<&str>,
+    json: bool,
+) -> Result<()> {
+    let scope = resolve_scope(state, scope).await?;
+
+    let content = tokio::fs::read_to_string(path)
+        .await
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let entries = parse_dotenv(&content)
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+
+    let mut existing: HashSet<String> = state
+        .secret_service
+        .list_secrets(&scope)
+        .await?
+        .into_iter()
+        .filter(|e| e.scope == scope)
+        .map(|e| e.key.0)
+        .collect();
+
+    let mut added = 0usize;
+    let mut updated = 0usize;
+
+    for (key, value) in &entries {
+        state.secret_service.set_secret(key, value, &scope).await?;
+        if existing.insert(key.clone()) {
+            added += 1;
+        } else {
+            updated += 1;
+        }
+    }
+
+    if json {
+        print_json(&serde_json::json!({
+                "imported": true,
+                "file": path.display().to_string(),
+                "scope": scope.to_string(),
+                "added": added,
+                "updated": updated,
+            }))?;
+    } else {
+        println!(
+            "  {} Imported {} secret{} from {} ({} added, {} updated)",
+            style("✓").green().bold(),
+            style(entries.len()).bold(),
+            if entries.len() == 1 { "" } else { "s" },
+            style(path.display()).cyan(),
+            added,
+            updated
+        );
+    }
+
+    Ok(())
+}
+
+/// Reveal a secret value after an explicit confirmation.
+///
+/// The value is printed to stdout only; it is never passed to tracing.
+/// In `--json` mode there is no interactive prompt, so `--yes` is required.
+async fn show_secret(
+    state: &AppState,
+    key: &str,
+    scope: Option<&str>,
+    resolution: ScopeResolution,
+    yes: bool,
+    json: bool,
+) -> Result<()> {
+    if json && !yes {
+        anyhow::bail!("Revealing a secret with --json requires --yes");
+    }
+
+    let scope = resolve_scope(state, scope).await?;
+
+    let service = &state.secret_service;
+    let revealed = reveal_with_confirmation(service, key, &scope, resolution, yes, || {
+        eprintln!();
+        eprintln!(
+            "  {} This will print the plaintext value of '{}' to your terminal.",
+            style("!").yellow().bold(),
+            style(key).bold()
+        );
+        Ok(Confirm::new()
+            .with_prompt("Reveal secret?")
+            .default(false)
+            .interact()?)
+    })
+    .await?;
+
+    let Some(value) = revealed else {
+        println!("  Cancelled.");
+        return Ok(());
+    };
+
+    if json {
+        print_json(&serde_json::json!({"key": key, "scope": scope.to_string(), "value": value.expose()}))?;
+    } else {
+        println!("{}", value.expose());
+    }
+
+    Ok(())
+}
+
+/// Resolve a secret only once the caller has confirmed.
+///
+/// `confirm` is not invoked when `yes` is set. If confirmation is declined
+/// the secret is never read and `Ok(None)` is returned.
+async fn reveal_with_confirmation<F>(
+    service: &SecretService,
+    key: &str,
+    scope: &SecretScope,
+    resolution: ScopeResolution,
+    yes: bool,
+    confirm: F,
+) -> Result<Option<Secret<String>>>
+where
+    F: FnOnce() -> Result<bool>,
+{
+    if !yes && !confirm()? {
+        return Ok(None);
+    }
+
+    let value = service
+        .reveal_secret(key, scope, resolution)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Secret '{key}' not found in scope {scope}"))?;
+
+    Ok(Some(value))
+}
+
 /// List all secrets with masked values.
-pub async fn list_secrets(state: &AppState, json: bool) -> Result<()> {
+///
+/// With `sources`, each secret is resolved through the provider chain and
+/// the provider that supplies its value is shown next to where it is stored.
+pub async fn list_secrets(state: &AppState, sources: bool, json: bool) -> Result<()> {
     let entries = state
         .secret_service
         .list_secrets(&SecretScope::Global)
         .await?;
 
+    if json && !sources {
+        print_json(&entries)?;
+        return Ok(());
+    }
+
+    let mut resolved = Vec::with_capacity(entries.len());
+    for entry in &entries {
+        let found = state
+            .secret_service
+            .get_with_source(&entry.key.0, &entry.scope)
+            .await
+            .ok()
+            .flatten();
+        resolved.push(found);
+    }
+
     if json {
-        println!("{}", serde_json::to_string_pretty(&entries)?);
+        let rows: Vec<serde_json::Value> = entries
+            .iter()
+            .zip(&resolved)
+            .map(|(entry, found)| {
+                let mut row = serde_json::to_value(entry).unwrap_or_default();
+                row["source"] = serde_json::to_value(found.as_ref().map(|(_, source)| source))
+                    .unwrap_or_default();
+                row
+            })
+            .collect();
+        print_json(&rows)?;
         return Ok(());
     }
 
@@ -81,34 +319,41 @@ pub async fn list_secrets(state: &AppState, json: bool) -> Result<()> {
         return Ok(());
     }
 
-    let mut table = Table::new();
-    table.load_preset(presets::UTF8_FULL_CONDENSED);
-    table.set_content_arrangement(ContentArrangement::Dynamic);
+    let mut table = new_table();
 
-    table.set_header(vec![
+    let mut header = vec![
         Cell::new("Key").fg(Color::White),
         Cell::new("Provider").fg(Color::White),
-        Cell::new("Scope").fg(Color::White),
-        Cell::new("Updated").fg(Color::White),
-    ]);
+    ];
+    if sources {
+        header.push(Cell::new("Source").fg(Color::White));
+    }
+    header.push(Cell::new("Scope").fg(Color::White));
+    header.push(Cell::new("Updated").fg(Color::White));
+    table.set_header(header);
 
-    for entry in &entries {
-        // Try to get the actual value for masking
-        let masked = match state
-            .secret_service
-            .get_secret(&entry.key.0, &SecretScope::Global)
-            .await
-        {
-            Ok(Some(val)) => SecretService::mask_secret(&val),
-            _ => "****".to_string(),
+    for (entry, found) in entries.iter().zip(&resolved) {
+        let masked = match found {
+            Some((val, _)) => SecretService::mask_secret(val),
+            None => "****".to_string(),
         };
 
-        table.add_row(vec![
+        let mut row = vec![
             Cell::new(format!("{}: {}", entry.key, masked)).fg(Color::Cyan),
             Cell::new(entry.provider.to_string()),
-            Cell::new(entry.scope.to_string()),
-            Cell::new(entry.updated_at.format("%Y-%m-%d").to_string()).fg(Color::DarkGrey),
-        ]);
+        ];
+        if sources {
+            row.push(match found {
+                Some((_, source)) if source.provider != entry.provider => {
+                    Cell::new(format!("{} (overrides)", source.provider)).fg(Color::Yellow)
+                }
+                Some((_, source)) => Cell::new(source.provider.to_string()),
+                None => Cell::new("-").fg(Color::DarkGrey),
+            });
+        }
+        row.push(Cell::new(entry.scope.to_string()));
+        row.push(Cell::new(entry.updated_at.format("%Y-%m-%d").to_string()).fg(Color::DarkGrey));
+        table.add_row(row);
     }
 
     println!();
@@ -123,3 +368,86 @@ pub async fn list_secrets(state: &AppState, json: bool) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    use boternity_core::repository::secret::SecretProvider;
+    use boternity_infra::crypto::vault::VaultCrypto;
+    use boternity_infra::secret::VaultSecretProvider;
+    use boternity_infra::sqlite::pool::DatabasePool;
+    use boternity_infra::sqlite::secret::SqliteSecretRepository;
+
+    async fn vault_service(dir: &tempfile::TempDir) -> SecretService {
+        let url = format!("sqlite://{}?mode=rwc", dir.path().join("test.db").display());
+        let pool = DatabasePool::new(&url).await.unwrap();
+        let vault = VaultSecretProvider::new(
+            SqliteSecretRepository::new(pool),
+            VaultCrypto::new(&[3u8; 32]),
+        );
+        vault
+            .set("REVEAL_ME", "sk-hidden-value", &SecretScope::Global)
+            .await
+            .unwrap();
+        SecretService::new(vec![Arc::new(vault)])
+    }
+
+    #[tokio::test]
+    async fn test_reveal_declined_returns_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let service = vault_service(&dir).await;
+
+        let revealed = reveal_with_confirmation(
+            &service,
+            "REVEAL_ME",
+            &SecretScope::Global,
+            ScopeResolution::Inherit,
+            false,
+            || Ok(false),
+        )
+        .await
+        .unwrap();
+
+        assert!(revealed.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_reveal_with_yes_skips_prompt_and_decrypts() {
+        let dir = tempfile::tempdir().unwrap();
+        let service = vault_service(&dir).await;
+
+        let revealed = reveal_with_confirmation(
+            &service,
+            "REVEAL_ME",
+            &SecretScope::Global,
+            ScopeResolution::Inherit,
+            true,
+            || panic!("confirmation must not be requested with --yes"),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(revealed.expose(), "sk-hidden-value");
+    }
+
+    #[tokio::test]
+    async fn test_reveal_missing_key_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let service = vault_service(&dir).await;
+
+        let result = reveal_with_confirmation(
+            &service,
+            "MISSING",
+            &SecretScope::Global,
+            ScopeResolution::Inherit,
+            true,
+            || Ok(true),
+        )
+        .await;
+
+        assert!(result.is_err());
+    }
+}
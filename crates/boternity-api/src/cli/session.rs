@@ -1,16 +1,27 @@
-//! Session management CLI commands: list, export, delete.
+//! Session management CLI commands: list, export, import, delete.
 //!
-//! Provides session browsing with rich tables, Markdown/JSON export,
-//! and deletion with confirmation prompt.
+//! Provides session browsing with rich tables, Markdown/JSON export
+//! (optionally HMAC-signed), import of JSON bundles with signature
+//! verification, and deletion with confirmation prompt.
 
-use anyhow::{Context, Result};
-use comfy_table::{presets, Cell, Color, ContentArrangement, Table};
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use comfy_table::{Cell, Color};
 use console::style;
 use dialoguer::Confirm;
 use uuid::Uuid;
 
 use boternity_core::chat::repository::ChatRepository;
-
+use boternity_infra::crypto::signing::{
+    EXPORT_SIGNING_KEY_SECRET, generate_signing_key, is_signed, sign_bundle, verify_bundle,
+};
+use boternity_types::bot::BotId;
+use boternity_types::chat::{ChatMessage, ChatSession};
+use boternity_types::secret::SecretScope;
+
+use crate::cli::color::new_table;
+use crate::cli::output::print_json;
 use crate::state::AppState;
 
 /// List past sessions for a bot with date, duration, title, and message preview.
@@ -34,7 +45,7 @@ pub async fn list_sessions(state: &AppState, slug: &str, json: bool) -> Result<(
         .await?;
 
     if json {
-        println!("{}", serde_json::to_string_pretty(&sessions)?);
+        print_json(&sessions)?;
         return Ok(());
     }
 
@@ -50,9 +61,7 @@ pub async fn list_sessions(state: &AppState, slug: &str, json: bool) -> Result<(
         return Ok(());
     }
 
-    let mut table = Table::new();
-    table.load_preset(presets::UTF8_FULL_CONDENSED);
-    table.set_content_arrangement(ContentArrangement::Dynamic);
+    let mut table = new_table();
 
     table.set_header(vec![
         Cell::new("Title").fg(Color::White),
@@ -128,8 +137,13 @@ pub async fn list_sessions(state: &AppState, slug: &str, json: bool) -> Result<(
 /// ```bash
 /// bnity export session <session-id>
 /// bnity export session <session-id> --json
+/// bnity export session <session-id> --json --sign > session.json
 /// ```
-pub async fn export_session(state: &AppState, session_id: Uuid, json: bool) -> Result<()> {
+pub async fn export_session(state: &AppState, session_id: Uuid, sign: bool, json: bool) -> Result<()> {
+    if sign && !json {
+        bail!("--sign requires --json (Markdown exports cannot be re-imported)");
+    }
+
     let session = state
         .chat_service
         .get_session(&session_id)
@@ -142,11 +156,15 @@ pub async fn export_session(state: &AppState, session_id: Uuid, json: bool) -> R
         .await?;
 
     if json {
-        let export = serde_json::json!({
+        let mut export = serde_json::json!({
             "session": session,
             "messages": messages,
         });
-        println!("{}", serde_json::to_string_pretty(&export)?);
+        if sign {
+            let key = export_signing_key(state).await?;
+            sign_bundle(&mut export, key.as_bytes())?;
+        }
+        print_json(&export)?;
         return Ok(());
     }
 
@@ -184,6 +202,7 @@ pub async fn export_session(state: &AppState, session_id: Uuid, json: bool) -> R
             boternity_types::llm::MessageRole::User => "**You**",
             boternity_types::llm::MessageRole::Assistant => "**Assistant**",
             boternity_types::llm::MessageRole::System => "**System**",
+            boternity_types::llm::MessageRole::Tool => "**Tool**",
         };
 
         let timestamp = msg.created_at.format("%H:%M");
@@ -196,6 +215,112 @@ pub async fn export_session(state: &AppState, session_id: Uuid, json: bool) -> R
     Ok(())
 }
 
+/// A session and its messages, as read from an export bundle.
+#[derive(Debug, serde::Deserialize)]
+struct SessionBundle {
+    session: ChatSession,
+    messages: Vec<ChatMessage>,
+}
+
+/// Import a session from a JSON export bundle.
+///
+/// Signed bundles are verified against the vault's export key; unsigned
+/// bundles are rejected unless `allow_unsigned` is set. The session and its
+/// messages get fresh IDs, so importing the same bundle twice is safe.
+///
+/// # Examples
+///
+/// ```bash
+/// bnity import session session.json
+/// bnity import session session.json --allow-unsigned
+/// ```
+pub async fn import_session(state: &AppState, file: &Path, allow_unsigned: bool, json: bool) -> Result<()> {
+    let content = std::fs::read_to_string(file)
+        .with_context(|| format!("Failed to read {}", file.display()))?;
+    let key = state
+        .secret_service
+        .get_secret(EXPORT_SIGNING_KEY_SECRET, &SecretScope::Global)
+        .await?;
+    let bundle = open_session_bundle(&content, key.as_deref().map(str::as_bytes), allow_unsigned)?;
+
+    let bot = state
+        .bot_service
+        .get_bot(&BotId(bundle.session.bot_id))
+        .await
+        .with_context(|| format!("Bot '{}' for this session not found", bundle.session.bot_id))?;
+
+    let session = ChatSession {
+        id: Uuid::now_v7(),
+        message_count: 0,
+        ..bundle.session
+    };
+    let repo = state.chat_service.chat_repo();
+    repo.create_session(&session).await?;
+    for message in bundle.messages {
+        let message = ChatMessage {
+            id: Uuid::now_v7(),
+            session_id: session.id,
+            ..message
+        };
+        repo.save_message(&message).await?;
+    }
+
+    if json {
+        print_json(&serde_json::json!({"imported": true, "session_id": session.id.to_string()}))?;
+    } else {
+        println!(
+            "  {} Imported session '{}' into '{}' ({}).",
+            style("+").green().bold(),
+            session.title.as_deref().unwrap_or("(untitled)"),
+            style(&bot.name).cyan(),
+            session.id
+        );
+    }
+
+    Ok(())
+}
+
+/// Parse and check an export bundle.
+///
+/// Accepts either the bare bundle or the full `--json` output envelope.
+fn open_session_bundle(content: &str, key: Option<&[u8]>, allow_unsigned: bool) -> Result<SessionBundle> {
+    let mut value: serde_json::Value =
+        serde_json::from_str(content).context("Export bundle is not valid JSON")?;
+    if value.get("status").is_some()
+        && let Some(data) = value.get_mut("data")
+    {
+        value = data.take();
+    }
+
+    if is_signed(&value) {
+        let key = key.context(
+            "Export bundle is signed but no export signing key is stored in the vault",
+        )?;
+        verify_bundle(&value, key)?;
+    } else if !allow_unsigned {
+        bail!("Export bundle is not signed (pass --allow-unsigned to import it anyway)");
+    }
+
+    serde_json::from_value(value).context("Export bundle does not contain a session")
+}
+
+/// The vault's export signing key, generated and stored on first use.
+async fn export_signing_key(state: &AppState) -> Result<String> {
+    if let Some(key) = state
+        .secret_service
+        .get_secret(EXPORT_SIGNING_KEY_SECRET, &SecretScope::Global)
+        .await?
+    {
+        return Ok(key);
+    }
+    let key = generate_signing_key();
+    state
+        .secret_service
+        .set_secret(EXPORT_SIGNING_KEY_SECRET, &key, &SecretScope::Global)
+        .await?;
+    Ok(key)
+}
+
 /// Delete a session with confirmation.
 ///
 /// # Examples
@@ -240,10 +365,7 @@ pub async fn delete_session(state: &AppState, session_id: Uuid, force: bool, jso
         .await?;
 
     if json {
-        println!(
-            "{}",
-            serde_json::json!({"deleted": true, "session_id": session_id.to_string()})
-        );
+        print_json(&serde_json::json!({"deleted": true, "session_id": session_id.to_string()}))?;
     } else {
         println!(
             "  {} Session '{}' deleted.",
@@ -270,3 +392,79 @@ fn format_duration(duration: chrono::TimeDelta) -> String {
         format!("{}s", total_secs)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use boternity_types::chat::SessionStatus;
+    use boternity_types::llm::MessageRole;
+    use chrono::Utc;
+
+    const KEY: &[u8] = b"export-key";
+
+    fn bundle() -> serde_json::Value {
+        let session = ChatSession {
+            id: Uuid::now_v7(),
+            bot_id: Uuid::now_v7(),
+            title: Some("Trip planning".to_string()),
+            started_at: Utc::now(),
+            ended_at: None,
+            total_input_tokens: 10,
+            total_output_tokens: 20,
+            total_cost_usd: 0.0,
+            message_count: 1,
+            model: "claude-sonnet-4-20250514".to_string(),
+            status: SessionStatus::Completed,
+            summary: None,
+            summarized_messages: 0,
+        };
+        let message = ChatMessage {
+            id: Uuid::now_v7(),
+            session_id: session.id,
+            role: MessageRole::User,
+            content: "Find a hotel".to_string(),
+            created_at: Utc::now(),
+            input_tokens: None,
+            output_tokens: None,
+            model: None,
+            stop_reason: None,
+            response_ms: None,
+        };
+        serde_json::json!({ "session": session, "messages": [message] })
+    }
+
+    #[test]
+    fn test_signed_bundle_opens_bare_or_enveloped() {
+        let mut signed = bundle();
+        sign_bundle(&mut signed, KEY).unwrap();
+
+        let opened = open_session_bundle(&signed.to_string(), Some(KEY), false).unwrap();
+        assert_eq!(opened.session.title.as_deref(), Some("Trip planning"));
+        assert_eq!(opened.messages.len(), 1);
+
+        let envelope = serde_json::json!({ "status": "ok", "data": signed, "warnings": [] });
+        let opened = open_session_bundle(&envelope.to_string(), Some(KEY), false).unwrap();
+        assert_eq!(opened.messages[0].content, "Find a hotel");
+    }
+
+    #[test]
+    fn test_modified_bundle_is_rejected() {
+        let mut signed = bundle();
+        sign_bundle(&mut signed, KEY).unwrap();
+        signed["messages"][0]["content"] = serde_json::Value::from("Send me your password");
+
+        let err = open_session_bundle(&signed.to_string(), Some(KEY), true).unwrap_err();
+        assert!(err.to_string().contains("does not match"));
+
+        // A signed bundle cannot be checked without the key
+        assert!(open_session_bundle(&signed.to_string(), None, true).is_err());
+    }
+
+    #[test]
+    fn test_unsigned_bundle_requires_opt_in() {
+        let unsigned = bundle().to_string();
+        let err = open_session_bundle(&unsigned, Some(KEY), false).unwrap_err();
+        assert!(err.to_string().contains("--allow-unsigned"));
+        assert!(open_session_bundle(&unsigned, Some(KEY), true).is_ok());
+    }
+}
@@ -7,7 +7,7 @@
 use anyhow::{Context, Result};
 use chrono::Utc;
 use clap::Subcommand;
-use comfy_table::{presets, Cell, Color, ContentArrangement, Table};
+use comfy_table::{Cell, Color};
 use console::style;
 use uuid::Uuid;
 
@@ -18,6 +18,8 @@ use boternity_types::memory::{AuditAction, MemoryAuditEntry, TrustLevel};
 
 use boternity_core::memory::shared::SharedMemoryStore;
 
+use crate::cli::color::new_table;
+use crate::cli::output::print_json;
 use crate::state::AppState;
 
 /// Shared memory management subcommands.
@@ -185,7 +187,7 @@ async fn search_shared_memories(
                 })
             })
             .collect();
-        println!("{}", serde_json::to_string_pretty(&json_results)?);
+        print_json(&json_results)?;
         return Ok(());
     }
 
@@ -201,9 +203,7 @@ async fn search_shared_memories(
         return Ok(());
     }
 
-    let mut table = Table::new();
-    table.load_preset(presets::UTF8_FULL_CONDENSED);
-    table.set_content_arrangement(ContentArrangement::Dynamic);
+    let mut table = new_table();
 
     table.set_header(vec![
         Cell::new("Similarity").fg(Color::White),
@@ -317,7 +317,7 @@ async fn list_shared_memories(
                 })
             })
             .collect();
-        println!("{}", serde_json::to_string_pretty(&json_results)?);
+        print_json(&json_results)?;
         return Ok(());
     }
 
@@ -332,9 +332,7 @@ async fn list_shared_memories(
         return Ok(());
     }
 
-    let mut table = Table::new();
-    table.load_preset(presets::UTF8_FULL_CONDENSED);
-    table.set_content_arrangement(ContentArrangement::Dynamic);
+    let mut table = new_table();
 
     table.set_header(vec![
         Cell::new("ID").fg(Color::White),
@@ -399,9 +397,7 @@ async fn share_memory(
     let memory_id = Uuid::parse_str(id)
         .map_err(|_| anyhow::anyhow!("Invalid memory ID: {id}"))?;
 
-    let trust_level: TrustLevel = level
-        .parse()
-        .map_err(|e: String| anyhow::anyhow!("{e}"))?;
+    let trust_level = TrustLevel::parse_lenient(level).map_err(|e| anyhow::anyhow!("{e}"))?;
 
     if trust_level == TrustLevel::Private {
         return Err(anyhow::anyhow!(
@@ -431,14 +427,11 @@ async fn share_memory(
     }
 
     if json {
-        println!(
-            "{}",
-            serde_json::json!({
+        print_json(&serde_json::json!({
                 "shared": true,
                 "memory_id": memory_id.to_string(),
                 "trust_level": trust_level.to_string()
-            })
-        );
+            }))?;
     } else {
         println!(
             "  {} Memory {} shared as '{}'.",
@@ -491,14 +484,11 @@ async fn revoke_memory(
     }
 
     if json {
-        println!(
-            "{}",
-            serde_json::json!({
+        print_json(&serde_json::json!({
                 "revoked": true,
                 "memory_id": memory_id.to_string(),
                 "bot": slug
-            })
-        );
+            }))?;
     } else {
         println!(
             "  {} Memory {} revoked (now private).",
@@ -533,13 +523,10 @@ async fn memory_details(
     // SharedMemoryStore trait. For now, we report integrity status.
 
     if json {
-        println!(
-            "{}",
-            serde_json::json!({
+        print_json(&serde_json::json!({
                 "memory_id": memory_id.to_string(),
                 "integrity_valid": is_valid,
-            })
-        );
+            }))?;
     } else {
         println!();
         println!(
@@ -1,23 +1,26 @@
 //! CLI skill management subcommands.
 //!
-//! Provides create, install, remove, list, inspect, attach, detach, enable,
-//! disable, publish, browse, and update operations for skills.
+//! Provides create, install, remove, list, search, inspect, attach, detach,
+//! enable, disable, publish, browse, and update operations for skills.
 
 use std::collections::HashMap;
 
 use anyhow::{bail, Context, Result};
 use clap::Subcommand;
-use comfy_table::{presets, Cell, Color, ContentArrangement, Table};
+use comfy_table::{Cell, Color};
 use console::style;
 use dialoguer::Confirm;
 
 use boternity_core::skill::inheritance::inspect_resolved_capabilities;
 use boternity_core::skill::registry::SkillRegistry;
+use boternity_infra::skill::suggest::{index_skill, suggest_skills};
 use boternity_types::skill::{
     BotSkillConfig, BotSkillsFile, SkillManifest, SkillMeta, SkillSource, SkillType,
     TrustTier,
 };
 
+use crate::cli::color::new_table;
+use crate::cli::output::print_json;
 use crate::state::AppState;
 
 /// Skill management subcommands.
@@ -64,6 +67,20 @@ pub enum SkillCommand {
         bot: Option<String>,
     },
 
+    /// Search installed skills by name and description.
+    Search {
+        /// Text to search for.
+        query: String,
+
+        /// Rank skills by meaning (embeddings) instead of matching text.
+        #[arg(long)]
+        semantic: bool,
+
+        /// Maximum number of results.
+        #[arg(long, default_value = "5")]
+        limit: usize,
+    },
+
     /// Inspect a skill, showing manifest and resolved capabilities.
     Inspect {
         /// Skill name to inspect.
@@ -152,6 +169,13 @@ pub async fn handle_skill_command(
         SkillCommand::List { bot } => {
             handle_list(bot.as_deref(), state, json)?;
         }
+        SkillCommand::Search {
+            query,
+            semantic,
+            limit,
+        } => {
+            handle_search(&query, semantic, limit, state, json).await?;
+        }
         SkillCommand::Inspect { name } => {
             handle_inspect(&name, state, json)?;
         }
@@ -236,7 +260,7 @@ fn handle_create(name: &str, skill_type: &str, state: &AppState, json: bool) ->
             "type": skill_type,
             "path": install_path.display().to_string(),
         });
-        println!("{}", serde_json::to_string_pretty(&out)?);
+        print_json(&out)?;
     } else {
         println!();
         println!(
@@ -320,7 +344,7 @@ async fn handle_install(
 
     if all_results.is_empty() {
         if json {
-            println!("{{\"error\": \"No skills found matching query\"}}");
+            print_json(&serde_json::json!([]))?;
         } else {
             println!("  No skills found matching '{query}'.");
         }
@@ -345,7 +369,7 @@ async fn handle_install(
                     })
                 })
                 .collect();
-            println!("{}", serde_json::to_string_pretty(&out)?);
+            print_json(&out)?;
             return Ok(());
         }
 
@@ -480,6 +504,19 @@ async fn handle_install(
         }
     }
 
+    // Index the description for `skill search --semantic`; a failure here
+    // only means it is embedded on the next search instead.
+    match state.skill_store.get_skill(&selected.name) {
+        Ok(skill) => {
+            if let Err(e) = index_skill(&state.skill_store, state.embedder.as_ref(), &skill).await {
+                tracing::warn!(skill = %selected.name, error = %e, "Failed to index skill for search");
+            }
+        }
+        Err(e) => {
+            tracing::warn!(skill = %selected.name, error = %e, "Failed to reload installed skill")
+        }
+    }
+
     if json {
         let out = serde_json::json!({
             "name": selected.name,
@@ -487,7 +524,7 @@ async fn handle_install(
             "path": install_path.display().to_string(),
             "capabilities": capabilities,
         });
-        println!("{}", serde_json::to_string_pretty(&out)?);
+        print_json(&out)?;
     } else {
         println!(
             "  {} Installed skill '{}' to {}",
@@ -505,6 +542,81 @@ async fn handle_install(
     Ok(())
 }
 
+// ---------------------------------------------------------------------------
+// Search
+// ---------------------------------------------------------------------------
+
+async fn handle_search(
+    query: &str,
+    semantic: bool,
+    limit: usize,
+    state: &AppState,
+    json: bool,
+) -> Result<()> {
+    // (name, description, similarity score for semantic search)
+    let results: Vec<(String, String, Option<f32>)> = if semantic {
+        suggest_skills(&state.skill_store, state.embedder.as_ref(), query, limit)
+            .await?
+            .into_iter()
+            .map(|s| (s.name, s.description, Some(s.score)))
+            .collect()
+    } else {
+        let needle = query.to_lowercase();
+        state
+            .skill_store
+            .list_skills()?
+            .into_iter()
+            .filter(|s| {
+                s.manifest.name.contains(&needle)
+                    || s.manifest.description.to_lowercase().contains(&needle)
+            })
+            .take(limit)
+            .map(|s| (s.manifest.name, s.manifest.description, None))
+            .collect()
+    };
+
+    if json {
+        let out: Vec<_> = results
+            .iter()
+            .map(|(name, description, score)| {
+                serde_json::json!({
+                    "name": name,
+                    "description": description,
+                    "score": score,
+                })
+            })
+            .collect();
+        print_json(&out)?;
+        return Ok(());
+    }
+
+    if results.is_empty() {
+        println!();
+        println!("  No installed skills match '{query}'.");
+        println!();
+        return Ok(());
+    }
+
+    let mut table = new_table();
+    let mut header = vec![Cell::new("Name").fg(Color::Cyan), Cell::new("Description")];
+    if semantic {
+        header.push(Cell::new("Score"));
+    }
+    table.set_header(header);
+    for (name, description, score) in &results {
+        let mut row = vec![Cell::new(name), Cell::new(description)];
+        if let Some(score) = score {
+            row.push(Cell::new(format!("{score:.2}")));
+        }
+        table.add_row(row);
+    }
+    println!();
+    println!("{table}");
+    println!();
+
+    Ok(())
+}
+
 // ---------------------------------------------------------------------------
 // Remove
 // ---------------------------------------------------------------------------
@@ -523,10 +635,7 @@ fn handle_remove(name: &str, bot_slug: Option<&str>, state: &AppState, json: boo
     state.skill_store.remove_skill(name)?;
 
     if json {
-        println!(
-            "{}",
-            serde_json::json!({"removed": name})
-        );
+        print_json(&serde_json::json!({"removed": name}))?;
     } else {
         println!();
         println!(
@@ -608,7 +717,7 @@ fn handle_list(bot_slug: Option<&str>, state: &AppState, json: bool) -> Result<(
                 })
             })
             .collect();
-        println!("{}", serde_json::to_string_pretty(&out)?);
+        print_json(&out)?;
         return Ok(());
     }
 
@@ -623,10 +732,8 @@ fn handle_list(bot_slug: Option<&str>, state: &AppState, json: bool) -> Result<(
         return Ok(());
     }
 
-    let mut table = Table::new();
+    let mut table = new_table();
     table
-        .load_preset(presets::UTF8_FULL_CONDENSED)
-        .set_content_arrangement(ContentArrangement::Dynamic)
         .set_header(vec![
             Cell::new("Name").fg(Color::Cyan),
             Cell::new("Type"),
@@ -723,7 +830,7 @@ fn handle_inspect(name: &str, state: &AppState, json: bool) -> Result<()> {
             "depth": inspected.depth,
             "source": format!("{:?}", skill.source),
         });
-        println!("{}", serde_json::to_string_pretty(&out)?);
+        print_json(&out)?;
         return Ok(());
     }
 
@@ -883,10 +990,7 @@ fn handle_attach(name: &str, bot_slug: &str, state: &AppState, json: bool) -> Re
     state.skill_store.save_bot_skills_config(&bot_dir, &config)?;
 
     if json {
-        println!(
-            "{}",
-            serde_json::json!({"attached": name, "bot": bot_slug})
-        );
+        print_json(&serde_json::json!({"attached": name, "bot": bot_slug}))?;
     } else {
         println!();
         println!(
@@ -918,10 +1022,7 @@ fn handle_detach(name: &str, bot_slug: &str, state: &AppState, json: bool) -> Re
     state.skill_store.save_bot_skills_config(&bot_dir, &config)?;
 
     if json {
-        println!(
-            "{}",
-            serde_json::json!({"detached": name, "bot": bot_slug})
-        );
+        print_json(&serde_json::json!({"detached": name, "bot": bot_slug}))?;
     } else {
         println!();
         println!(
@@ -964,10 +1065,7 @@ fn handle_enable_disable(
     let action = if enable { "Enabled" } else { "Disabled" };
 
     if json {
-        println!(
-            "{}",
-            serde_json::json!({"skill": name, "bot": bot_slug, "enabled": enable})
-        );
+        print_json(&serde_json::json!({"skill": name, "bot": bot_slug, "enabled": enable}))?;
     } else {
         println!();
         println!(
@@ -1017,7 +1115,7 @@ fn handle_publish(name: &str, state: &AppState, json: bool) -> Result<()> {
             "valid": issues.is_empty(),
             "issues": issues,
         });
-        println!("{}", serde_json::to_string_pretty(&out)?);
+        print_json(&out)?;
         return Ok(());
     }
 
@@ -1126,7 +1224,7 @@ fn handle_update(all: bool, name: Option<&str>, state: &AppState, json: bool) ->
 
     if to_check.is_empty() {
         if json {
-            println!("[]");
+            print_json(&serde_json::json!([]))?;
         } else {
             println!();
             println!("  No registry-installed skills to update.");
@@ -1155,7 +1253,7 @@ fn handle_update(all: bool, name: Option<&str>, state: &AppState, json: bool) ->
                 })
             })
             .collect();
-        println!("{}", serde_json::to_string_pretty(&out)?);
+        print_json(&out)?;
     } else {
         println!();
         for s in &to_check {
@@ -5,10 +5,12 @@
 //! enforcing the immutability invariant.
 
 use anyhow::Result;
-use comfy_table::{presets, Cell, Color, ContentArrangement, Table};
+use comfy_table::{Cell, Color};
 use console::style;
 use dialoguer::Confirm;
 
+use crate::cli::color::new_table;
+use crate::cli::output::print_json;
 use crate::state::AppState;
 
 /// Open a bot's SOUL.md in $EDITOR for editing.
@@ -58,7 +60,7 @@ pub async fn edit_soul(state: &AppState, slug: &str, json: bool) -> Result<()> {
 
     if new_content == current_content {
         if json {
-            println!("{}", serde_json::json!({"changed": false}));
+            print_json(&serde_json::json!({"changed": false}))?;
         } else {
             println!("  No changes made.");
         }
@@ -72,9 +74,9 @@ pub async fn edit_soul(state: &AppState, slug: &str, json: bool) -> Result<()> {
         .await?;
 
     if json {
-        println!("{}", serde_json::to_string_pretty(&soul)?);
+        print_json(&soul)?;
     } else {
-        let short_hash = &soul.hash[..8.min(soul.hash.len())];
+        let short_hash = short_hash(&soul.hash);
         println!(
             "  {} Soul updated: version {} (hash: {})",
             style("ok").green().bold(),
@@ -102,7 +104,7 @@ pub async fn soul_history(state: &AppState, slug: &str, json: bool) -> Result<()
     let versions = state.soul_service.get_soul_versions(&bot.id).await?;
 
     if json {
-        println!("{}", serde_json::to_string_pretty(&versions)?);
+        print_json(&versions)?;
         return Ok(());
     }
 
@@ -117,9 +119,7 @@ pub async fn soul_history(state: &AppState, slug: &str, json: bool) -> Result<()
         return Ok(());
     }
 
-    let mut table = Table::new();
-    table.load_preset(presets::UTF8_FULL_CONDENSED);
-    table.set_content_arrangement(ContentArrangement::Dynamic);
+    let mut table = new_table();
 
     table.set_header(vec![
         Cell::new("Version").fg(Color::White),
@@ -130,7 +130,7 @@ pub async fn soul_history(state: &AppState, slug: &str, json: bool) -> Result<()
 
     // Show most recent first
     for v in versions.iter().rev() {
-        let short_hash = &v.hash[..8.min(v.hash.len())];
+        let short_hash = short_hash(&v.hash);
         let relative = format_relative_time(&v.created_at);
         let message = v.message.as_deref().unwrap_or("-");
 
@@ -191,10 +191,7 @@ pub async fn soul_diff(
 
     if from_version == to_version {
         if json {
-            println!(
-                "{}",
-                serde_json::json!({"from": from_version, "to": to_version, "diff": ""})
-            );
+            print_json(&serde_json::json!({"from": from_version, "to": to_version, "diff": ""}))?;
         } else {
             println!("  No difference (same version).");
         }
@@ -207,14 +204,11 @@ pub async fn soul_diff(
         .await?;
 
     if json {
-        println!(
-            "{}",
-            serde_json::json!({
+        print_json(&serde_json::json!({
                 "from": from_version,
                 "to": to_version,
                 "diff": diff,
-            })
-        );
+            }))?;
         return Ok(());
     }
 
@@ -285,7 +279,7 @@ pub async fn soul_rollback(
         .await?;
 
     if json {
-        println!("{}", serde_json::to_string_pretty(&new_soul)?);
+        print_json(&new_soul)?;
     } else {
         println!(
             "  {} Soul rolled back: now at version {} (content from version {})",
@@ -301,7 +295,8 @@ pub async fn soul_rollback(
 /// Verify the integrity of a bot's SOUL.md file.
 ///
 /// Computes the SHA-256 hash of the file on disk and compares it against
-/// the stored hash. Reports valid or INTEGRITY VIOLATION.
+/// the stored hash, then checks that the version history chain is intact.
+/// Reports valid or INTEGRITY VIOLATION.
 ///
 /// # Examples
 ///
@@ -318,21 +313,36 @@ pub async fn soul_verify(state: &AppState, slug: &str, json: bool) -> Result<()>
         .await?;
 
     if json {
-        println!("{}", serde_json::to_string_pretty(&result)?);
+        print_json(&result)?;
         return Ok(());
     }
 
     if result.valid {
-        let short_hash = &result.expected_hash[..8.min(result.expected_hash.len())];
+        let short_hash = short_hash(&result.expected_hash);
         println!(
-            "  {} Soul integrity verified (version {}, hash: {})",
+            "  {} Soul integrity verified (version {}, hash: {}, history intact)",
             style("ok").green().bold(),
             style(result.version).cyan(),
             style(short_hash).dim()
         );
+    } else if result.expected_hash == result.actual_hash
+        && let Some(history_break) = &result.history_break
+    {
+        println!();
+        println!(
+            "  {} SOUL HISTORY VIOLATION!",
+            style("ERROR").red().bold()
+        );
+        println!();
+        println!("  The stored version history has been altered: {history_break}.");
+        println!(
+            "  Use `{}` to inspect versions",
+            style(format!("bnity soul history {slug}")).yellow()
+        );
+        println!();
     } else {
-        let short_expected = &result.expected_hash[..8.min(result.expected_hash.len())];
-        let short_actual = &result.actual_hash[..8.min(result.actual_hash.len())];
+        let short_expected = short_hash(&result.expected_hash);
+        let short_actual = short_hash(&result.actual_hash);
         println!();
         println!(
             "  {} SOUL INTEGRITY VIOLATION!",
@@ -351,6 +361,9 @@ pub async fn soul_verify(state: &AppState, slug: &str, json: bool) -> Result<()>
         println!(
             "  The SOUL.md file has been modified outside of Boternity."
         );
+        if let Some(history_break) = &result.history_break {
+            println!("  The version history has also been altered: {history_break}.");
+        }
         println!(
             "  Use `{}` to view versions",
             style(format!("bnity soul history {slug}")).yellow()
@@ -367,6 +380,12 @@ pub async fn soul_verify(state: &AppState, slug: &str, json: bool) -> Result<()>
 
 // --- Helper ---
 
+/// First 8 hex digits of a recorded hash, without any algorithm prefix.
+pub(crate) fn short_hash(hash: &str) -> &str {
+    let hex = hash.split_once(':').map_or(hash, |(_, hex)| hex);
+    &hex[..8.min(hex.len())]
+}
+
 fn format_relative_time(dt: &chrono::DateTime<chrono::Utc>) -> String {
     let now = chrono::Utc::now();
     let diff = now - *dt;
@@ -6,6 +6,7 @@ use console::style;
 use boternity_types::bot::BotStatus;
 use boternity_types::secret::SecretScope;
 
+use crate::cli::output::print_json;
 use crate::state::AppState;
 
 /// Display system status dashboard.
@@ -50,7 +51,7 @@ pub async fn status(state: &AppState, json: bool) -> Result<()> {
             "total_tokens": total_tokens,
             "total_conversations": total_conversations,
         });
-        println!("{}", serde_json::to_string_pretty(&status)?);
+        print_json(&status)?;
         return Ok(());
     }
 
@@ -1,16 +1,18 @@
 //! Storage CLI subcommands for managing bot file storage.
 //!
-//! Provides upload, download, list, info, and delete operations for bot files.
+//! Provides upload, download, list, info, search, and delete operations for bot files.
 //! Text files are automatically indexed for semantic search after upload.
 
 use anyhow::{Context, Result};
 use clap::Subcommand;
-use comfy_table::{presets, Cell, Color, ContentArrangement, Table};
+use comfy_table::{Cell, Color};
 use console::style;
 use dialoguer::Confirm;
 
 use boternity_core::storage::file_store::FileStore;
 
+use crate::cli::color::new_table;
+use crate::cli::output::print_json;
 use crate::state::AppState;
 
 /// File storage subcommands.
@@ -57,6 +59,19 @@ pub enum StorageCommand {
         filename: String,
     },
 
+    /// Semantic search across a bot's indexed text files.
+    Search {
+        /// Bot slug.
+        slug: String,
+
+        /// Natural-language query.
+        query: String,
+
+        /// Maximum number of matching chunks to return.
+        #[arg(long, default_value = "5")]
+        limit: usize,
+    },
+
     /// Delete a file from a bot's storage.
     Delete {
         /// Bot slug.
@@ -88,6 +103,9 @@ pub async fn handle_storage_command(
         } => download_file(state, &slug, &filename, output.as_deref(), json).await,
         StorageCommand::List { slug } => list_files(state, &slug, json).await,
         StorageCommand::Info { slug, filename } => file_info(state, &slug, &filename, json).await,
+        StorageCommand::Search { slug, query, limit } => {
+            search_files(state, &slug, &query, limit, json).await
+        }
         StorageCommand::Delete {
             slug,
             filename,
@@ -130,9 +148,9 @@ async fn upload_file(
     // Save file
     let file = state.file_store.save_file(&bot.id.0, filename, &data).await?;
 
-    // Auto-index text files
+    // Auto-index text files (and documents, with `document-extract`)
     let mime = boternity_infra::storage::detect_mime(filename);
-    let indexed = if boternity_infra::storage::is_text_mime(&mime) {
+    let indexed = if boternity_infra::storage::is_indexable_mime(&mime) {
         let chunks = state
             .file_indexer
             .index_file(&bot.id.0, &file.id, filename, &data)
@@ -150,7 +168,7 @@ async fn upload_file(
             "version": file.version,
             "indexed": indexed,
         });
-        println!("{}", serde_json::to_string_pretty(&result)?);
+        print_json(&result)?;
     } else {
         println!();
         println!(
@@ -202,7 +220,7 @@ async fn download_file(
             "output_path": output_path,
             "size_bytes": data.len(),
         });
-        println!("{}", serde_json::to_string_pretty(&result)?);
+        print_json(&result)?;
     } else {
         println!();
         println!(
@@ -243,7 +261,7 @@ async fn list_files(state: &AppState, slug: &str, json: bool) -> Result<()> {
                 })
             })
             .collect();
-        println!("{}", serde_json::to_string_pretty(&items)?);
+        print_json(&items)?;
         return Ok(());
     }
 
@@ -270,9 +288,7 @@ async fn list_files(state: &AppState, slug: &str, json: bool) -> Result<()> {
     );
     println!();
 
-    let mut table = Table::new();
-    table.load_preset(presets::UTF8_FULL_CONDENSED);
-    table.set_content_arrangement(ContentArrangement::Dynamic);
+    let mut table = new_table();
 
     table.set_header(vec![
         Cell::new("Filename").fg(Color::White),
@@ -343,7 +359,7 @@ async fn file_info(state: &AppState, slug: &str, filename: &str, json: bool) ->
             "updated_at": file.updated_at.to_rfc3339(),
             "versions": version_items,
         });
-        println!("{}", serde_json::to_string_pretty(&result)?);
+        print_json(&result)?;
         return Ok(());
     }
 
@@ -367,9 +383,7 @@ async fn file_info(state: &AppState, slug: &str, filename: &str, json: bool) ->
         println!();
         println!("  Version History:");
 
-        let mut table = Table::new();
-        table.load_preset(presets::UTF8_FULL_CONDENSED);
-        table.set_content_arrangement(ContentArrangement::Dynamic);
+        let mut table = new_table();
 
         table.set_header(vec![
             Cell::new("Version").fg(Color::White),
@@ -404,6 +418,99 @@ async fn file_info(state: &AppState, slug: &str, filename: &str, json: bool) ->
     Ok(())
 }
 
+/// Search a bot's indexed file chunks by semantic similarity.
+///
+/// Only indexable files (text, plus documents with `document-extract`) are
+/// indexed on upload, so other binary files never appear.
+async fn search_files(
+    state: &AppState,
+    slug: &str,
+    query: &str,
+    limit: usize,
+    json: bool,
+) -> Result<()> {
+    let bot = state
+        .bot_service
+        .get_bot_by_slug(slug)
+        .await
+        .with_context(|| format!("Bot '{slug}' not found"))?;
+
+    let matches = state
+        .file_indexer
+        .search_file_chunks_scored(&bot.id.0, query, limit)
+        .await?;
+
+    if json {
+        let items: Vec<serde_json::Value> = matches
+            .iter()
+            .map(|m| {
+                serde_json::json!({
+                    "filename": m.chunk.filename,
+                    "chunk_index": m.chunk.chunk_index,
+                    "snippet": m.chunk.chunk_text,
+                    "score": m.score,
+                })
+            })
+            .collect();
+        print_json(&items)?;
+        return Ok(());
+    }
+
+    if matches.is_empty() {
+        println!();
+        println!(
+            "  {} No matching file content for '{}'.",
+            style("i").blue().bold(),
+            style(&bot.name).cyan(),
+        );
+        println!();
+        return Ok(());
+    }
+
+    println!();
+    println!(
+        "  Results for \"{}\" in '{}' ({} matches)",
+        query,
+        style(&bot.name).cyan(),
+        matches.len(),
+    );
+    println!();
+
+    let mut table = new_table();
+
+    table.set_header(vec![
+        Cell::new("Score").fg(Color::White),
+        Cell::new("File").fg(Color::White),
+        Cell::new("Chunk").fg(Color::White),
+        Cell::new("Snippet").fg(Color::White),
+    ]);
+
+    for m in &matches {
+        table.add_row(vec![
+            Cell::new(format!("{:.3}", m.score)),
+            Cell::new(&m.chunk.filename).fg(Color::Cyan),
+            Cell::new(m.chunk.chunk_index),
+            Cell::new(snippet(&m.chunk.chunk_text, 120)).fg(Color::DarkGrey),
+        ]);
+    }
+
+    println!("{table}");
+    println!();
+
+    Ok(())
+}
+
+/// Collapse whitespace and truncate text to `max_chars` for table display.
+fn snippet(text: &str, max_chars: usize) -> String {
+    let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if collapsed.chars().count() <= max_chars {
+        collapsed
+    } else {
+        let truncated: String = collapsed.chars().take(max_chars).collect();
+        format!("{truncated}...")
+    }
+}
+
 /// Delete a file from a bot's storage (removes file, versions, and index).
 async fn delete_file(
     state: &AppState,
@@ -453,7 +560,7 @@ async fn delete_file(
             "deleted": filename,
             "bot": slug,
         });
-        println!("{}", serde_json::to_string_pretty(&result)?);
+        print_json(&result)?;
     } else {
         println!();
         println!(
@@ -475,7 +582,7 @@ async fn delete_file(
 }
 
 /// Format bytes into a human-readable size string.
-fn format_size(bytes: u64) -> String {
+pub(crate) fn format_size(bytes: u64) -> String {
     if bytes < 1024 {
         format!("{} B", bytes)
     } else if bytes < 1024 * 1024 {
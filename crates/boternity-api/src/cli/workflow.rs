@@ -1,19 +1,22 @@
 //! CLI workflow management subcommands.
 //!
-//! Provides create, trigger, list, status, logs, delete, approve, and cancel
-//! operations for workflow definitions and runs.
+//! Provides create, trigger, list, status, runs, logs, tail, delete, approve,
+//! and cancel operations for workflow definitions and runs.
 
 use std::path::PathBuf;
 
 use anyhow::{bail, Context, Result};
 use clap::Subcommand;
-use comfy_table::{presets, Cell, Color, ContentArrangement, Table};
+use comfy_table::{Cell, Color};
 use console::style;
 
-use boternity_core::repository::workflow::WorkflowRepository;
+use boternity_core::repository::workflow::{WorkflowRepository, WorkflowRunFilter};
 use boternity_core::workflow::definition::{load_workflow_file, WorkflowError};
+use boternity_core::workflow::tail::{RunTail, TailEvent};
 use boternity_types::workflow::{WorkflowOwner, WorkflowRunStatus};
 
+use crate::cli::color::new_table;
+use crate::cli::output::{print_json, CliResponse};
 use crate::state::AppState;
 
 /// Workflow management subcommands.
@@ -60,12 +63,46 @@ pub enum WorkflowCommand {
         limit: u32,
     },
 
+    /// List runs across all workflows, newest first.
+    Runs {
+        /// Filter by run status (e.g. running, failed, completed).
+        #[arg(long)]
+        status: Option<String>,
+
+        /// Filter by workflow name.
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Only runs started within this window (e.g. 30m, 12h, 1d, 2w) or
+        /// after an RFC 3339 timestamp.
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Maximum number of runs to display.
+        #[arg(long, default_value = "20")]
+        limit: u32,
+
+        /// Number of runs to skip (for paging through older runs).
+        #[arg(long, default_value = "0")]
+        offset: u32,
+    },
+
     /// Show step logs for a specific workflow run.
     Logs {
         /// Workflow run UUID.
         run_id: String,
     },
 
+    /// Follow a workflow run, printing step events until it finishes.
+    Tail {
+        /// Workflow run UUID.
+        run_id: String,
+
+        /// Polling interval in milliseconds.
+        #[arg(long, default_value = "500")]
+        interval_ms: u64,
+    },
+
     /// Delete a registered workflow.
     Delete {
         /// Workflow name.
@@ -111,7 +148,31 @@ pub async fn handle_workflow_command(
         WorkflowCommand::Status { target, limit } => {
             handle_status(&target, limit, &repo, json).await
         }
+        WorkflowCommand::Runs {
+            status,
+            name,
+            since,
+            limit,
+            offset,
+        } => {
+            let filter = WorkflowRunFilter {
+                status: status.as_deref().map(parse_run_status).transpose()?,
+                workflow_name: name,
+                since: since
+                    .as_deref()
+                    .map(|s| WorkflowRunFilter::parse_since(s, chrono::Utc::now()))
+                    .transpose()
+                    .map_err(|e| anyhow::anyhow!(e))?,
+                limit: Some(i64::from(limit)),
+                offset: Some(i64::from(offset)),
+            };
+            handle_runs(&filter, &repo, json).await
+        }
         WorkflowCommand::Logs { run_id } => handle_logs(&run_id, &repo, json).await,
+        WorkflowCommand::Tail {
+            run_id,
+            interval_ms,
+        } => handle_tail(&run_id, interval_ms, &repo, json).await,
         WorkflowCommand::Delete { name, bot } => {
             handle_delete(&name, bot.as_deref(), state, &repo, json).await
         }
@@ -166,7 +227,7 @@ async fn handle_create(
             "triggers": def.triggers.len(),
             "owner": format!("{:?}", def.owner),
         });
-        println!("{}", serde_json::to_string_pretty(&out)?);
+        print_json(&out)?;
     } else {
         println!();
         println!(
@@ -233,6 +294,7 @@ async fn handle_trigger(
         completed_at: None,
         error: None,
         concurrency_key: Some(def.name.clone()),
+        idempotency_key: None,
     };
 
     repo.create_run(&run)
@@ -245,7 +307,7 @@ async fn handle_trigger(
             "workflow_name": def.name,
             "status": "pending",
         });
-        println!("{}", serde_json::to_string_pretty(&out)?);
+        print_json(&out)?;
     } else {
         println!();
         println!(
@@ -301,7 +363,7 @@ async fn handle_list(
                 })
             })
             .collect();
-        println!("{}", serde_json::to_string_pretty(&out)?);
+        print_json(&out)?;
         return Ok(());
     }
 
@@ -316,10 +378,8 @@ async fn handle_list(
         return Ok(());
     }
 
-    let mut table = Table::new();
+    let mut table = new_table();
     table
-        .load_preset(presets::UTF8_FULL_CONDENSED)
-        .set_content_arrangement(ContentArrangement::Dynamic)
         .set_header(vec![
             Cell::new("Name").fg(Color::Cyan),
             Cell::new("Version"),
@@ -403,7 +463,7 @@ async fn handle_status(
                 })
             })
             .collect();
-        println!("{}", serde_json::to_string_pretty(&out)?);
+        print_json(&out)?;
         return Ok(());
     }
 
@@ -414,10 +474,8 @@ async fn handle_status(
         return Ok(());
     }
 
-    let mut table = Table::new();
+    let mut table = new_table();
     table
-        .load_preset(presets::UTF8_FULL_CONDENSED)
-        .set_content_arrangement(ContentArrangement::Dynamic)
         .set_header(vec![
             Cell::new("Run ID").fg(Color::Cyan),
             Cell::new("Status"),
@@ -469,7 +527,7 @@ fn display_single_run(
             "completed_at": run.completed_at.map(|t| t.to_rfc3339()),
             "error": run.error,
         });
-        println!("{}", serde_json::to_string_pretty(&out)?);
+        print_json(&out)?;
         return Ok(());
     }
 
@@ -494,6 +552,90 @@ fn display_single_run(
     Ok(())
 }
 
+// ---------------------------------------------------------------------------
+// Runs
+// ---------------------------------------------------------------------------
+
+async fn handle_runs(
+    filter: &WorkflowRunFilter,
+    repo: &impl WorkflowRepository,
+    json: bool,
+) -> Result<()> {
+    let runs = repo
+        .list_runs_filtered(filter)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to list runs: {e}"))?;
+
+    if json {
+        let out: Vec<_> = runs
+            .iter()
+            .map(|r| {
+                serde_json::json!({
+                    "run_id": r.id.to_string(),
+                    "workflow_id": r.workflow_id.to_string(),
+                    "workflow_name": r.workflow_name,
+                    "status": format!("{:?}", r.status),
+                    "trigger": r.trigger_type,
+                    "started_at": r.started_at.to_rfc3339(),
+                    "completed_at": r.completed_at.map(|t| t.to_rfc3339()),
+                    "error": r.error,
+                })
+            })
+            .collect();
+        print_json(&out)?;
+        return Ok(());
+    }
+
+    if runs.is_empty() {
+        println!();
+        println!("  No matching workflow runs.");
+        println!();
+        return Ok(());
+    }
+
+    let mut table = new_table();
+    table.set_header(vec![
+        Cell::new("Run ID").fg(Color::Cyan),
+        Cell::new("Workflow"),
+        Cell::new("Status"),
+        Cell::new("Trigger"),
+        Cell::new("Started"),
+        Cell::new("Completed"),
+    ]);
+
+    for r in &runs {
+        let completed = r
+            .completed_at
+            .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_else(|| "-".to_string());
+
+        table.add_row(vec![
+            Cell::new(r.id.to_string().chars().take(8).collect::<String>()),
+            Cell::new(&r.workflow_name),
+            format_status(r.status),
+            Cell::new(&r.trigger_type),
+            Cell::new(r.started_at.format("%Y-%m-%d %H:%M").to_string()),
+            Cell::new(completed),
+        ]);
+    }
+
+    println!();
+    println!("  {} workflow run(s)", style(runs.len()).cyan());
+    println!();
+    println!("{table}");
+    if filter.limit.is_some_and(|limit| runs.len() as i64 == limit) {
+        let next = filter.offset.unwrap_or(0) + runs.len() as i64;
+        println!();
+        println!(
+            "  {}",
+            style(format!("More runs may exist; use --offset {next} to see older runs.")).dim()
+        );
+    }
+    println!();
+
+    Ok(())
+}
+
 // ---------------------------------------------------------------------------
 // Logs
 // ---------------------------------------------------------------------------
@@ -527,7 +669,7 @@ async fn handle_logs(
                 })
             })
             .collect();
-        println!("{}", serde_json::to_string_pretty(&out)?);
+        print_json(&out)?;
         return Ok(());
     }
 
@@ -538,10 +680,8 @@ async fn handle_logs(
         return Ok(());
     }
 
-    let mut table = Table::new();
+    let mut table = new_table();
     table
-        .load_preset(presets::UTF8_FULL_CONDENSED)
-        .set_content_arrangement(ContentArrangement::Dynamic)
         .set_header(vec![
             Cell::new("Step").fg(Color::Cyan),
             Cell::new("Name"),
@@ -584,6 +724,143 @@ async fn handle_logs(
     Ok(())
 }
 
+// ---------------------------------------------------------------------------
+// Tail
+// ---------------------------------------------------------------------------
+
+async fn handle_tail(
+    run_id_str: &str,
+    interval_ms: u64,
+    repo: &impl WorkflowRepository,
+    json: bool,
+) -> Result<()> {
+    let run_id: uuid::Uuid = run_id_str
+        .parse()
+        .with_context(|| format!("Invalid run ID: '{run_id_str}'"))?;
+
+    let mut tail = RunTail::new(repo, run_id);
+    let interval = std::time::Duration::from_millis(interval_ms.max(50));
+
+    if !json {
+        println!();
+        println!(
+            "  Following run '{}' (Ctrl-C to stop)",
+            style(&run_id_str[..8.min(run_id_str.len())]).cyan()
+        );
+        println!();
+    }
+
+    loop {
+        let events = tail.poll().await.map_err(|e| match e {
+            boternity_types::error::RepositoryError::NotFound => {
+                anyhow::anyhow!("Run '{run_id_str}' not found")
+            }
+            other => anyhow::anyhow!("Failed to read run: {other}"),
+        })?;
+
+        for event in &events {
+            if json {
+                // One compact envelope per line so the stream can be piped
+                println!("{}", CliResponse::ok(event).render(false)?);
+            } else {
+                print_tail_event(event);
+            }
+        }
+
+        if tail.is_finished() {
+            break;
+        }
+        tokio::time::sleep(interval).await;
+    }
+
+    if !json {
+        println!();
+    }
+    Ok(())
+}
+
+fn print_tail_event(event: &TailEvent) {
+    let now = chrono::Utc::now().format("%H:%M:%S");
+    match event {
+        TailEvent::StepStarted {
+            step_id, attempt, ..
+        } => {
+            let retry = if *attempt > 1 {
+                format!(" (attempt {attempt})")
+            } else {
+                String::new()
+            };
+            println!(
+                "  {} {} {}{}",
+                style(now).dim(),
+                style(">").blue().bold(),
+                step_id,
+                style(retry).dim()
+            );
+        }
+        TailEvent::StepCompleted {
+            step_id,
+            duration_ms,
+            ..
+        } => {
+            let took = duration_ms
+                .map(|ms| format!(" ({ms}ms)"))
+                .unwrap_or_default();
+            println!(
+                "  {} {} {}{}",
+                style(now).dim(),
+                style("*").green().bold(),
+                step_id,
+                style(took).dim()
+            );
+        }
+        TailEvent::StepFailed { step_id, error, .. } => {
+            println!(
+                "  {} {} {} {}",
+                style(now).dim(),
+                style("x").red().bold(),
+                step_id,
+                style(error.as_deref().unwrap_or("failed")).red()
+            );
+        }
+        TailEvent::StepSkipped { step_id, .. } => {
+            println!(
+                "  {} {} {} {}",
+                style(now).dim(),
+                style("-").dim(),
+                step_id,
+                style("skipped").dim()
+            );
+        }
+        TailEvent::StepWaitingApproval { step_id, .. } => {
+            println!(
+                "  {} {} {} {}",
+                style(now).dim(),
+                style("?").magenta().bold(),
+                step_id,
+                style("waiting for approval").magenta()
+            );
+        }
+        TailEvent::RunStatus { status, error } => {
+            let label = format!("{status:?}").to_lowercase();
+            let label = match status {
+                WorkflowRunStatus::Completed => style(label).green(),
+                WorkflowRunStatus::Failed | WorkflowRunStatus::Crashed => style(label).red(),
+                _ => style(label).yellow(),
+            };
+            println!(
+                "  {} Run {}{}",
+                style(now).dim(),
+                label.bold(),
+                error
+                    .as_deref()
+                    .map(|e| format!(": {e}"))
+                    .unwrap_or_default()
+            );
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Delete
 // ---------------------------------------------------------------------------
@@ -613,10 +890,7 @@ async fn handle_delete(
     }
 
     if json {
-        println!(
-            "{}",
-            serde_json::json!({"deleted": name, "id": def.id.to_string()})
-        );
+        print_json(&serde_json::json!({"deleted": name, "id": def.id.to_string()}))?;
     } else {
         println!();
         println!(
@@ -662,10 +936,7 @@ async fn handle_approve(
         .map_err(|e| anyhow::anyhow!("Failed to approve run: {e}"))?;
 
     if json {
-        println!(
-            "{}",
-            serde_json::json!({"approved": run_id_str, "status": "running"})
-        );
+        print_json(&serde_json::json!({"approved": run_id_str, "status": "running"}))?;
     } else {
         println!();
         println!(
@@ -718,10 +989,7 @@ async fn handle_cancel(
     .map_err(|e| anyhow::anyhow!("Failed to cancel run: {e}"))?;
 
     if json {
-        println!(
-            "{}",
-            serde_json::json!({"cancelled": run_id_str, "status": "cancelled"})
-        );
+        print_json(&serde_json::json!({"cancelled": run_id_str, "status": "cancelled"}))?;
     } else {
         println!();
         println!(
@@ -755,6 +1023,15 @@ async fn resolve_owner(bot_slug: Option<&str>, state: &AppState) -> Result<Workf
     }
 }
 
+/// Parse a run status name as accepted by `--status`.
+fn parse_run_status(s: &str) -> Result<WorkflowRunStatus> {
+    serde_json::from_value(serde_json::Value::String(s.trim().to_lowercase())).map_err(|_| {
+        anyhow::anyhow!(
+            "Unknown run status '{s}'. Expected one of: pending, running, paused, completed, failed, crashed, cancelled"
+        )
+    })
+}
+
 fn format_status(status: WorkflowRunStatus) -> Cell {
     match status {
         WorkflowRunStatus::Pending => Cell::new("pending").fg(Color::Yellow),
@@ -766,3 +1043,25 @@ fn format_status(status: WorkflowRunStatus) -> Cell {
         WorkflowRunStatus::Cancelled => Cell::new("cancelled").fg(Color::DarkYellow),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_run_status() {
+        assert_eq!(
+            parse_run_status("running").unwrap(),
+            WorkflowRunStatus::Running
+        );
+        assert_eq!(
+            parse_run_status("Failed").unwrap(),
+            WorkflowRunStatus::Failed
+        );
+        assert_eq!(
+            parse_run_status(" cancelled ").unwrap(),
+            WorkflowRunStatus::Cancelled
+        );
+        assert!(parse_run_status("done").is_err());
+    }
+}
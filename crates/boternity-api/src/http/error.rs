@@ -4,7 +4,7 @@ use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
 use serde_json::json;
 
-use boternity_types::error::{BotError, SecretError, SoulError};
+use boternity_types::error::{BotError, QuotaError, SecretError, SoulError};
 
 /// Application-level error that maps to HTTP responses.
 #[derive(Debug)]
@@ -15,8 +15,12 @@ pub enum AppError {
     Soul(SoulError),
     /// Secret-related errors.
     Secret(SecretError),
+    /// Bot usage quota errors.
+    Quota(QuotaError),
     /// Authentication failure.
     Unauthorized(String),
+    /// Requested resource does not exist.
+    NotFound(String),
     /// Validation error.
     Validation(String),
     /// Generic internal error.
@@ -41,6 +45,12 @@ impl From<SecretError> for AppError {
     }
 }
 
+impl From<QuotaError> for AppError {
+    fn from(e: QuotaError) -> Self {
+        AppError::Quota(e)
+    }
+}
+
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
         let (status, code, message) = match &self {
@@ -56,6 +66,9 @@ impl IntoResponse for AppError {
             AppError::Bot(BotError::InvalidStatus(msg)) => {
                 (StatusCode::BAD_REQUEST, "VALIDATION_ERROR", msg.clone())
             }
+            AppError::Bot(e @ BotError::InvalidStatusTransition { .. }) => {
+                (StatusCode::CONFLICT, "INVALID_STATUS_TRANSITION", e.to_string())
+            }
             AppError::Bot(BotError::SoulIntegrityViolation { expected, actual }) => {
                 (StatusCode::CONFLICT, "SOUL_INTEGRITY_VIOLATION", format!("Soul integrity violation: expected hash {expected}, got {actual}"))
             }
@@ -77,9 +90,18 @@ impl IntoResponse for AppError {
             AppError::Secret(e) => {
                 (StatusCode::INTERNAL_SERVER_ERROR, "SECRET_ERROR", e.to_string())
             }
+            AppError::Quota(e @ QuotaError::Exceeded { .. }) => {
+                (StatusCode::TOO_MANY_REQUESTS, "QUOTA_EXCEEDED", e.to_string())
+            }
+            AppError::Quota(e) => {
+                (StatusCode::INTERNAL_SERVER_ERROR, "QUOTA_ERROR", e.to_string())
+            }
             AppError::Unauthorized(msg) => {
                 (StatusCode::UNAUTHORIZED, "UNAUTHORIZED", msg.clone())
             }
+            AppError::NotFound(msg) => {
+                (StatusCode::NOT_FOUND, "NOT_FOUND", msg.clone())
+            }
             AppError::Validation(msg) => {
                 (StatusCode::BAD_REQUEST, "VALIDATION_ERROR", msg.clone())
             }
@@ -45,7 +45,7 @@ pub async fn list_bots(
     let request_id = uuid::Uuid::now_v7().to_string();
 
     let status_filter = match &query.status {
-        Some(s) => Some(s.parse::<BotStatus>().map_err(|e| AppError::Validation(e))?),
+        Some(s) => Some(BotStatus::parse_lenient(s).map_err(AppError::Validation)?),
         None => None,
     };
 
@@ -66,6 +66,7 @@ pub async fn list_bots(
         sort_order,
         limit: query.limit,
         offset: query.offset,
+        include_deleted: false,
     });
 
     let bots = state.bot_service.list_bots(filter).await?;
@@ -240,18 +240,9 @@ async fn create_builder_agent(
 // ---------------------------------------------------------------------------
 
 async fn save_draft_from_state(state: &AppState, builder_state: &BuilderState) -> Result<(), AppError> {
-    let state_json = serde_json::to_string(builder_state)
+    let draft = BuilderDraft::from_state(builder_state)
         .map_err(|e| AppError::Internal(format!("Failed to serialize builder state: {e}")))?;
 
-    let now = Utc::now();
-    let draft = BuilderDraft {
-        session_id: builder_state.session_id,
-        state_json,
-        schema_version: 1,
-        created_at: now,
-        updated_at: now,
-    };
-
     state
         .builder_draft_store
         .save_draft(draft)
@@ -340,7 +331,8 @@ pub async fn submit_answer(
         .map_err(|e| AppError::Internal(format!("Failed to load draft: {e}")))?
         .ok_or_else(|| AppError::Validation(format!("No draft found for session {session_id}")))?;
 
-    let mut builder_state: BuilderState = serde_json::from_str(&draft.state_json)
+    let mut builder_state = draft
+        .state()
         .map_err(|e| AppError::Internal(format!("Failed to deserialize builder state: {e}")))?;
 
     // Advance conversation
@@ -547,7 +539,8 @@ pub async fn get_session(
         .map_err(|e| AppError::Internal(format!("Failed to load draft: {e}")))?
         .ok_or_else(|| AppError::Validation(format!("No draft found for session {session_id}")))?;
 
-    let builder_state: BuilderState = serde_json::from_str(&draft.state_json)
+    let builder_state = draft
+        .state()
         .map_err(|e| AppError::Internal(format!("Failed to deserialize builder state: {e}")))?;
 
     let elapsed = start.elapsed().as_millis() as u64;
@@ -187,7 +187,7 @@ async fn handle_builder_ws(socket: WebSocket, session_id_str: String, state: App
 
     // Check if a draft exists and pre-load state
     if let Ok(Some(draft)) = state.builder_draft_store.load_draft(&session_id).await {
-        if let Ok(loaded_state) = serde_json::from_str::<BuilderState>(&draft.state_json) {
+        if let Ok(loaded_state) = draft.state() {
             builder_state = Some(loaded_state);
             // Default to bot mode for existing drafts (mode will be set on
             // StartBot/StartSkill if the client sends one instead of Resume)
@@ -515,7 +515,7 @@ async fn handle_resume(
     if builder_state.is_none() {
         match state.builder_draft_store.load_draft(&session_id).await {
             Ok(Some(draft)) => {
-                match serde_json::from_str::<BuilderState>(&draft.state_json) {
+                match draft.state() {
                     Ok(loaded) => {
                         *builder_state = Some(loaded);
                         *session_mode = Some(SessionMode::Bot); // Default; client can override
@@ -580,18 +580,9 @@ async fn create_builder_agent(
 
 /// Save builder state as a draft.
 async fn save_draft(state: &AppState, builder_state: &BuilderState) -> Result<(), String> {
-    let state_json = serde_json::to_string(builder_state)
+    let draft = BuilderDraft::from_state(builder_state)
         .map_err(|e| format!("Failed to serialize builder state: {e}"))?;
 
-    let now = Utc::now();
-    let draft = BuilderDraft {
-        session_id: builder_state.session_id,
-        state_json,
-        schema_version: 1,
-        created_at: now,
-        updated_at: now,
-    };
-
     state
         .builder_draft_store
         .save_draft(draft)
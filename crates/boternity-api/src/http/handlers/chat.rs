@@ -50,9 +50,10 @@ use boternity_core::llm::health::ProviderHealth;
 use boternity_core::llm::token_budget::TokenBudget;
 use boternity_core::memory::box_vector::BoxVectorMemoryStore;
 use boternity_infra::filesystem::identity::parse_identity_frontmatter;
+use boternity_infra::filesystem::user::parse_user_content;
 use boternity_infra::filesystem::LocalFileSystem;
 use boternity_types::event::AgentEvent;
-use boternity_types::llm::{CompletionRequest, StreamEvent};
+use boternity_types::llm::StreamEvent;
 
 use crate::http::error::AppError;
 use crate::http::extractors::auth::Authenticated;
@@ -67,29 +68,6 @@ pub struct StreamChatRequest {
     pub message: String,
 }
 
-/// Build a [`CompletionRequest`] from agent context and a user message.
-///
-/// Replicates the request building logic from `AgentEngine::build_request()`.
-fn build_completion_request(context: &AgentContext, user_message: &str) -> CompletionRequest {
-    let mut messages = context.build_messages();
-
-    messages.push(boternity_types::llm::Message {
-        role: boternity_types::llm::MessageRole::User,
-        content: user_message.to_string(),
-    });
-
-    CompletionRequest {
-        model: context.agent_config.model.clone(),
-        messages,
-        system: Some(context.system_prompt.clone()),
-        max_tokens: context.agent_config.max_tokens,
-        temperature: Some(context.agent_config.temperature),
-        stream: true,
-        stop_sequences: None,
-        output_config: None,
-    }
-}
-
 /// Convert an [`AgentEvent`] into an SSE [`Event`].
 ///
 /// Maps each event variant to a named SSE event type with a JSON payload.
@@ -246,6 +224,9 @@ pub async fn stream_chat(
         )));
     }
 
+    // Reject chat once the bot has used up its quota for the current window
+    state.chat_service.check_quota(&bot.id.0, &bot.slug).await?;
+
     // Read personality files
     let soul_content = tokio::fs::read_to_string(LocalFileSystem::soul_path(
         &state.data_dir,
@@ -280,9 +261,12 @@ pub async fn stream_chat(
         .map(|fm| fm.max_tokens as u32)
         .unwrap_or(4096);
 
-    // Build fallback chain
+    // Build fallback chain (honoring any per-bot provider preference)
+    let provider_preference = identity_fm
+        .as_ref()
+        .and_then(|fm| fm.provider_preference.clone());
     let mut fallback_chain = state
-        .build_fallback_chain(&model)
+        .build_fallback_chain(&model, provider_preference.as_ref())
         .await
         .map_err(|e| AppError::Internal(e.to_string()))?;
 
@@ -327,33 +311,27 @@ pub async fn stream_chat(
         model: model.clone(),
         temperature,
         max_tokens,
+        guard_untrusted_content: identity_fm.as_ref().is_some_and(|fm| fm.guard_untrusted_content),
     };
     let token_budget = TokenBudget::from_capabilities(&primary_caps);
+    let (user_profile, user_body) = parse_user_content(&user_content);
     let mut agent_context = AgentContext::new(
         agent_config,
         soul_content,
         identity_content,
-        user_content,
+        user_body.to_string(),
         memories,
         token_budget,
-    );
+    )
+    .with_user_profile(user_profile);
 
-    // Load conversation history into agent context for session continuation
-    let history = state
+    // Load the rolling summary and later messages for session continuation
+    if let Err(e) = state
         .chat_service
-        .get_messages(&session_id, None, None)
+        .rehydrate_context(&session_id, &mut agent_context)
         .await
-        .unwrap_or_default();
-    for msg in &history {
-        match msg.role {
-            boternity_types::chat::MessageRole::User => {
-                agent_context.add_user_message(msg.content.clone());
-            }
-            boternity_types::chat::MessageRole::Assistant => {
-                agent_context.add_assistant_message(msg.content.clone());
-            }
-            _ => {}
-        }
+    {
+        tracing::warn!(error = %e, "Failed to load session history");
     }
 
     // Vector memory recall
@@ -363,9 +341,7 @@ pub async fn stream_chat(
         )
         .await
         {
-            Ok(vs) => Some(BoxVectorMemoryStore::new(
-                boternity_infra::vector::memory::LanceVectorMemoryStore::new(vs),
-            )),
+            Ok(vs) => Some(BoxVectorMemoryStore::new(state.open_vector_memory(vs))),
             Err(_) => None,
         };
 
@@ -383,7 +359,9 @@ pub async fn stream_chat(
     }
 
     // Build the completion request
-    let request = build_completion_request(&agent_context, &body.message);
+    let request = agent_context
+        .completion_request(&body.message)
+        .map_err(|e| AppError::Internal(e.to_string()))?;
 
     // Select provider and get stream
     let stream_selection = fallback_chain
@@ -402,11 +380,21 @@ pub async fn stream_chat(
     let bot_id = bot.id.clone();
     let event_bus = state.event_bus.clone();
     let global_config = state.global_config.clone();
+    let budget_override = identity_fm.as_ref().and_then(|fm| fm.max_request_tokens).map(|requested| {
+        boternity_infra::config::validate_request_budget_override(requested, primary_caps.max_context_tokens)
+            .unwrap_or_else(|e| {
+                tracing::warn!(bot = %bot.slug, error = %e, "Clamping max_request_tokens to the context window");
+                e.max_context_tokens
+            })
+    });
     let agent_cancellations = state.agent_cancellations.clone();
     let state_for_orch = state.clone();
 
-    // Subscribe to EventBus for sub-agent events BEFORE starting orchestrator
-    let mut event_rx = state.event_bus.subscribe();
+    // Subscribe to EventBus for sub-agent events BEFORE starting orchestrator.
+    // Only this request's events are forwarded, so concurrent chats sharing
+    // the bus never see each other's agent trees.
+    let orch_request_id = Uuid::now_v7();
+    let mut event_rx = state.event_bus.subscribe_request(orch_request_id);
 
     // Build the SSE stream
     let sse_stream = async_stream::stream! {
@@ -466,13 +454,12 @@ pub async fn stream_chat(
             // Resolve per-request budget.
             let request_budget_total = boternity_infra::config::resolve_request_budget(
                 &global_config,
-                None,
+                budget_override,
             );
             let request_budget = RequestBudget::new(request_budget_total);
-            let request_ctx = RequestContext::new(Uuid::now_v7(), request_budget);
+            let request_ctx = RequestContext::new(orch_request_id, request_budget);
 
             // Register cancellation token
-            let orch_request_id = request_ctx.request_id;
             agent_cancellations.insert(orch_request_id, request_ctx.cancellation.clone());
 
             // Create orchestrator and provider
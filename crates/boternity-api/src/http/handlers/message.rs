@@ -3,15 +3,16 @@
 //! Endpoints for sending messages between bots, viewing message history,
 //! managing pub/sub channels, and subscribing/unsubscribing bots.
 
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use axum::extract::{Path, Query, State};
 use axum::Json;
 use serde::Deserialize;
 use uuid::Uuid;
 
+use boternity_core::message::envelope;
 use boternity_core::repository::message::MessageRepository;
-use boternity_types::message::{BotMessage, BotSubscription, MessageRecipient};
+use boternity_types::message::{BotMessage, BotSubscription, MessagePriority, MessageRecipient};
 
 use crate::http::error::AppError;
 use crate::http::extractors::auth::Authenticated;
@@ -38,6 +39,12 @@ pub struct SendMessageRequest {
     /// Optional reply-to message ID.
     #[serde(default)]
     pub reply_to: Option<Uuid>,
+    /// Delivery priority (default `normal`).
+    #[serde(default)]
+    pub priority: MessagePriority,
+    /// Seconds until the message expires (default: never).
+    #[serde(default)]
+    pub ttl_secs: Option<u64>,
 }
 
 /// Query parameters for message history with pagination.
@@ -81,6 +88,12 @@ pub async fn send_message(
         body: body.body,
         timestamp: chrono::Utc::now(),
         reply_to: body.reply_to,
+        priority: body.priority,
+        expires_at: None,
+    };
+    let msg = match body.ttl_secs {
+        Some(secs) => envelope::with_ttl(msg, Duration::from_secs(secs)),
+        None => msg,
     };
 
     state
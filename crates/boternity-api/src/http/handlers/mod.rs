@@ -11,6 +11,7 @@ pub mod session;
 pub mod skill;
 pub mod soul;
 pub mod stats;
+pub mod storage;
 pub mod webhook;
 pub mod workflow;
 pub mod ws;
@@ -600,6 +600,19 @@ pub async fn install_skill(
         }
     }
 
+    // Index the description for semantic search; on failure it is embedded
+    // on the next search instead.
+    if let Ok(skill) = state.skill_store.get_skill(skill_name)
+        && let Err(e) = boternity_infra::skill::suggest::index_skill(
+            &state.skill_store,
+            state.embedder.as_ref(),
+            &skill,
+        )
+        .await
+    {
+        tracing::warn!(skill = %skill_name, error = %e, "Failed to index skill for search");
+    }
+
     let elapsed = start.elapsed().as_millis() as u64;
     let resp = ApiResponse::success(
         serde_json::json!({
@@ -8,8 +8,6 @@ use axum::extract::State;
 use axum::Json;
 use sqlx::Row;
 
-use boternity_core::chat::repository::ChatRepository;
-
 use crate::http::error::AppError;
 use crate::http::extractors::auth::Authenticated;
 use crate::http::response::ApiResponse;
@@ -17,9 +15,11 @@ use crate::state::AppState;
 
 /// GET /api/v1/stats - Aggregate dashboard statistics.
 ///
-/// Returns bot counts by status, total sessions, active sessions, and
-/// total messages. Uses efficient COUNT(*) SQL queries directly on the
-/// database pool for performance.
+/// Returns bot counts by status, total sessions, active sessions, total
+/// messages, and reader/writer connection pool usage. Uses efficient COUNT(*)
+/// SQL queries directly on the database pool for performance, all inside one
+/// read snapshot so the counts agree with each other without blocking
+/// concurrent writes.
 pub async fn get_stats(
     State(state): State<AppState>,
     _auth: Authenticated,
@@ -27,6 +27,12 @@ pub async fn get_stats(
     let start = Instant::now();
     let request_id = uuid::Uuid::now_v7().to_string();
 
+    let mut snapshot = state
+        .db_pool
+        .read_snapshot()
+        .await
+        .map_err(|e| AppError::Internal(format!("Failed to open read snapshot: {e}")))?;
+
     // Bot counts by status (efficient single query with conditional counts)
     let bot_row = sqlx::query(
         r#"SELECT
@@ -36,7 +42,7 @@ pub async fn get_stats(
             SUM(CASE WHEN status = 'archived' THEN 1 ELSE 0 END) as archived_bots
         FROM bots"#,
     )
-    .fetch_one(&state.db_pool.reader)
+    .fetch_one(&mut *snapshot)
     .await
     .map_err(|e| AppError::Internal(format!("Failed to query bot stats: {e}")))?;
 
@@ -45,29 +51,28 @@ pub async fn get_stats(
     let disabled_bots: i64 = bot_row.try_get("disabled_bots").unwrap_or(0);
     let archived_bots: i64 = bot_row.try_get("archived_bots").unwrap_or(0);
 
-    // Session counts
-    let total_sessions = state
-        .chat_service
-        .chat_repo()
-        .count_sessions()
-        .await
-        .unwrap_or(0);
-
-    // Active sessions (status = 'active')
-    let active_session_row =
-        sqlx::query("SELECT COUNT(*) as cnt FROM chat_sessions WHERE status = 'active'")
-            .fetch_one(&state.db_pool.reader)
-            .await
-            .map_err(|e| AppError::Internal(format!("Failed to query active sessions: {e}")))?;
-    let active_sessions: i64 = active_session_row.try_get("cnt").unwrap_or(0);
+    // Session counts (total and status = 'active')
+    let session_row = sqlx::query(
+        r#"SELECT
+            COUNT(*) as total_sessions,
+            SUM(CASE WHEN status = 'active' THEN 1 ELSE 0 END) as active_sessions
+        FROM chat_sessions"#,
+    )
+    .fetch_one(&mut *snapshot)
+    .await
+    .map_err(|e| AppError::Internal(format!("Failed to query session stats: {e}")))?;
+    let total_sessions: i64 = session_row.try_get("total_sessions").unwrap_or(0);
+    let active_sessions: i64 = session_row.try_get("active_sessions").unwrap_or(0);
 
     // Total messages
-    let total_messages = state
-        .chat_service
-        .chat_repo()
-        .count_messages()
+    let message_row = sqlx::query("SELECT COUNT(*) as cnt FROM chat_messages")
+        .fetch_one(&mut *snapshot)
         .await
-        .unwrap_or(0);
+        .map_err(|e| AppError::Internal(format!("Failed to query message count: {e}")))?;
+    let total_messages: i64 = message_row.try_get("cnt").unwrap_or(0);
+    drop(snapshot);
+
+    let pool_metrics = state.db_pool.metrics().await;
 
     let elapsed = start.elapsed().as_millis() as u64;
 
@@ -79,6 +84,7 @@ pub async fn get_stats(
         "total_sessions": total_sessions,
         "active_sessions": active_sessions,
         "total_messages": total_messages,
+        "database_pool": pool_metrics,
     });
 
     let resp = ApiResponse::success(data, request_id, elapsed)
@@ -0,0 +1,159 @@
+//! File storage HTTP handlers with streaming upload and download.
+//!
+//! Endpoints:
+//! - GET /api/v1/bots/{id}/files            - List files for a bot
+//! - PUT /api/v1/bots/{id}/files/{filename} - Upload (raw request body, streamed to disk)
+//! - GET /api/v1/bots/{id}/files/{filename} - Download (streamed from disk)
+//!
+//! Uploads and downloads never hold the full file in memory: the request
+//! body is piped through `LocalFileStore::save_file_stream`, and downloads
+//! are served in `STREAM_CHUNK_SIZE` chunks from an open file handle.
+
+use std::time::Instant;
+
+use axum::body::Body;
+use axum::extract::{Path, State};
+use axum::http::{header, HeaderValue};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use futures_util::TryStreamExt;
+use tokio_util::io::{ReaderStream, StreamReader};
+use uuid::Uuid;
+
+use boternity_core::storage::file_store::FileStore;
+use boternity_infra::storage::filesystem::STREAM_CHUNK_SIZE;
+use boternity_types::error::RepositoryError;
+
+use crate::http::error::AppError;
+use crate::http::extractors::auth::Authenticated;
+use crate::http::response::ApiResponse;
+use crate::state::AppState;
+
+/// Resolve a bot by ID or slug.
+async fn resolve_bot(
+    state: &AppState,
+    id_or_slug: &str,
+) -> Result<boternity_types::bot::Bot, AppError> {
+    match state.bot_service.get_bot_by_slug(id_or_slug).await {
+        Ok(bot) => Ok(bot),
+        Err(_) => {
+            let id = id_or_slug
+                .parse()
+                .map_err(|_| AppError::Bot(boternity_types::error::BotError::NotFound))?;
+            Ok(state.bot_service.get_bot(&id).await?)
+        }
+    }
+}
+
+/// Map a storage error for a named file to an API error.
+fn storage_error(filename: &str, e: RepositoryError) -> AppError {
+    match e {
+        RepositoryError::NotFound => AppError::NotFound(format!("File '{filename}' not found")),
+        RepositoryError::Conflict(msg) => AppError::Validation(msg),
+        other => AppError::Internal(other.to_string()),
+    }
+}
+
+/// GET /api/v1/bots/{id}/files - List files stored for a bot.
+pub async fn list_files(
+    State(state): State<AppState>,
+    _auth: Authenticated,
+    Path(id_or_slug): Path<String>,
+) -> Result<Json<ApiResponse<Vec<serde_json::Value>>>, AppError> {
+    let start = Instant::now();
+    let request_id = Uuid::now_v7().to_string();
+
+    let bot = resolve_bot(&state, &id_or_slug).await?;
+
+    let files = state
+        .file_store
+        .list_files(&bot.id.0)
+        .await
+        .map_err(|e| AppError::Internal(e.to_string()))?;
+
+    let elapsed = start.elapsed().as_millis() as u64;
+
+    let files_json: Vec<serde_json::Value> = files
+        .iter()
+        .map(|f| serde_json::to_value(f).unwrap())
+        .collect();
+
+    let resp = ApiResponse::success(files_json, request_id, elapsed)
+        .with_link("self", &format!("/api/v1/bots/{}/files", bot.id))
+        .with_link("bot", &format!("/api/v1/bots/{}", bot.id));
+
+    Ok(Json(resp))
+}
+
+/// PUT /api/v1/bots/{id}/files/{filename} - Upload a file from the raw request body.
+///
+/// The body is streamed straight to disk; it is not subject to axum's
+/// default body limit, only to the store's own maximum file size.
+pub async fn upload_file(
+    State(state): State<AppState>,
+    _auth: Authenticated,
+    Path((id_or_slug, filename)): Path<(String, String)>,
+    body: Body,
+) -> Result<Json<ApiResponse<serde_json::Value>>, AppError> {
+    let start = Instant::now();
+    let request_id = Uuid::now_v7().to_string();
+
+    let bot = resolve_bot(&state, &id_or_slug).await?;
+
+    let reader = StreamReader::new(body.into_data_stream().map_err(std::io::Error::other));
+
+    let file = state
+        .file_store
+        .save_file_stream(&bot.id.0, &filename, reader)
+        .await
+        .map_err(|e| storage_error(&filename, e))?;
+
+    let elapsed = start.elapsed().as_millis() as u64;
+
+    let resp = ApiResponse::success(serde_json::to_value(&file).unwrap(), request_id, elapsed)
+        .with_link(
+            "self",
+            &format!("/api/v1/bots/{}/files/{}", bot.id, file.filename),
+        )
+        .with_link("files", &format!("/api/v1/bots/{}/files", bot.id));
+
+    Ok(Json(resp))
+}
+
+/// GET /api/v1/bots/{id}/files/{filename} - Download the current version of a file.
+///
+/// Responds with the raw bytes, `Content-Type` from the detected MIME type,
+/// and `Content-Length` from the stored size.
+pub async fn download_file(
+    State(state): State<AppState>,
+    _auth: Authenticated,
+    Path((id_or_slug, filename)): Path<(String, String)>,
+) -> Result<Response, AppError> {
+    let bot = resolve_bot(&state, &id_or_slug).await?;
+
+    let (file, handle) = state
+        .file_store
+        .open_file(&bot.id.0, &filename)
+        .await
+        .map_err(|e| storage_error(&filename, e))?;
+
+    let content_type = HeaderValue::from_str(&file.mime_type)
+        .unwrap_or_else(|_| HeaderValue::from_static("application/octet-stream"));
+    let disposition = HeaderValue::from_str(&format!(
+        "attachment; filename=\"{}\"",
+        file.filename.replace('"', "")
+    ))
+    .unwrap_or_else(|_| HeaderValue::from_static("attachment"));
+
+    let body = Body::from_stream(ReaderStream::with_capacity(handle, STREAM_CHUNK_SIZE));
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, content_type),
+            (header::CONTENT_LENGTH, HeaderValue::from(file.size_bytes)),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        body,
+    )
+        .into_response())
+}
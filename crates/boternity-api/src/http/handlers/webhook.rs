@@ -2,7 +2,8 @@
 //!
 //! Receives incoming webhook requests, verifies authentication
 //! (HMAC-SHA256 or bearer token) via the `WebhookRegistry`, and
-//! spawns a background workflow execution via `DagExecutor`.
+//! spawns a background workflow execution via `DagExecutor`. Triggers with
+//! a dedup window answer repeat deliveries with the existing run.
 
 use std::sync::Arc;
 use std::time::Instant;
@@ -14,7 +15,9 @@ use axum::Json;
 use uuid::Uuid;
 
 use boternity_core::repository::workflow::WorkflowRepository;
-use boternity_core::workflow::executor::WorkflowExecutor;
+use boternity_infra::workflow::webhook_handler::{
+    dedup_window, delivery_idempotency_key, DeliveryClaim, IDEMPOTENCY_KEY_HEADER,
+};
 
 use crate::http::error::AppError;
 use crate::http::response::ApiResponse;
@@ -30,6 +33,11 @@ use crate::state::AppState;
 /// - **HMAC-SHA256**: Reads `X-Hub-Signature-256` header
 /// - **Bearer token**: Reads `Authorization` header
 /// - **None**: No authentication required
+///
+/// If the webhook trigger sets `dedup_window_secs`, a delivery whose
+/// idempotency key (`Idempotency-Key` header, or a hash of the body) was
+/// seen within the window returns the earlier run ID with status
+/// `duplicate` instead of starting a new run.
 pub async fn receive_webhook(
     State(state): State<AppState>,
     Path(path): Path<String>,
@@ -82,22 +90,66 @@ pub async fn receive_webhook(
             ))
         })?;
 
+    // Deduplicate repeat deliveries if the trigger asks for it
+    let (run_id, idempotency_key) = match dedup_window(&def, &webhook_path) {
+        Some(window) => {
+            let key = delivery_idempotency_key(
+                headers
+                    .get(IDEMPOTENCY_KEY_HEADER)
+                    .and_then(|v| v.to_str().ok()),
+                &body,
+            );
+            let claim = state
+                .webhook_dedup
+                .claim(state.workflow_repo.as_ref(), def.id, &key, window)
+                .await
+                .map_err(|e| AppError::Internal(e.to_string()))?;
+            match claim {
+                DeliveryClaim::New(run_id) => (run_id, Some(key)),
+                DeliveryClaim::Duplicate(run_id) => {
+                    tracing::info!(
+                        webhook_path = %webhook_path,
+                        run_id = %run_id,
+                        "Duplicate webhook delivery, not starting a new run"
+                    );
+                    let elapsed = start.elapsed().as_millis() as u64;
+                    let resp = ApiResponse::success(
+                        serde_json::json!({
+                            "workflow_id": def.id.to_string(),
+                            "workflow_name": def.name,
+                            "run_id": run_id.to_string(),
+                            "status": "duplicate",
+                            "trigger": "webhook",
+                        }),
+                        request_id,
+                        elapsed,
+                    )
+                    .with_link("run", &format!("/api/v1/runs/{run_id}"))
+                    .with_link("workflow", &format!("/api/v1/workflows/{}", def.id));
+                    return Ok(Json(resp));
+                }
+            }
+        }
+        None => (Uuid::now_v7(), None),
+    };
+
     // Prepare trigger payload
     let trigger_payload = if payload.is_null() { None } else { Some(payload) };
 
     tracing::info!(
         webhook_path = %webhook_path,
         workflow_id = %def.id,
+        run_id = %run_id,
         "Webhook triggering workflow execution"
     );
 
-    // Spawn background execution task -- the executor creates its own run record
+    // Spawn background execution task -- the executor creates the run record
     let executor = Arc::clone(&state.workflow_executor);
     let def_clone = def.clone();
     let trigger_payload_clone = trigger_payload.clone();
     tokio::spawn(async move {
         match executor
-            .execute(&def_clone, "webhook", trigger_payload_clone)
+            .execute_run(&def_clone, run_id, "webhook", trigger_payload_clone, idempotency_key)
             .await
         {
             Ok(result) => {
@@ -123,12 +175,14 @@ pub async fn receive_webhook(
         serde_json::json!({
             "workflow_id": def.id.to_string(),
             "workflow_name": def.name,
+            "run_id": run_id.to_string(),
             "status": "submitted",
             "trigger": "webhook",
         }),
         request_id,
         elapsed,
     )
+    .with_link("run", &format!("/api/v1/runs/{run_id}"))
     .with_link("runs", &format!("/api/v1/workflows/{}/runs", def.id))
     .with_link("workflow", &format!("/api/v1/workflows/{}", def.id));
 
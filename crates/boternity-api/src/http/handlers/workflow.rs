@@ -13,7 +13,7 @@ use uuid::Uuid;
 
 use std::sync::Arc;
 
-use boternity_core::repository::workflow::WorkflowRepository;
+use boternity_core::repository::workflow::{WorkflowRepository, WorkflowRunFilter};
 use boternity_core::workflow::executor::WorkflowExecutor;
 use boternity_types::workflow::{WorkflowDefinition, WorkflowRunStatus};
 
@@ -38,6 +38,24 @@ fn default_run_limit() -> u32 {
     20
 }
 
+/// Query parameters for listing runs across all workflows.
+#[derive(Debug, Deserialize)]
+pub struct ListAllRunsQuery {
+    /// Only runs in this status (e.g. `running`, `failed`).
+    pub status: Option<WorkflowRunStatus>,
+    /// Only runs of the workflow with this name.
+    pub name: Option<String>,
+    /// Only runs started within this window (`30m`, `12h`, `1d`, `2w`) or
+    /// after an RFC 3339 timestamp.
+    pub since: Option<String>,
+    /// Maximum number of runs to return (default 20).
+    #[serde(default = "default_run_limit")]
+    pub limit: u32,
+    /// Number of runs to skip (default 0).
+    #[serde(default)]
+    pub offset: u32,
+}
+
 // ---------------------------------------------------------------------------
 // Router
 // ---------------------------------------------------------------------------
@@ -58,6 +76,7 @@ pub fn workflow_routes() -> Router<AppState> {
         .route("/workflows/{id}/trigger", post(trigger_workflow))
         .route("/workflows/{id}/runs", get(list_runs))
         // Run management
+        .route("/runs", get(list_all_runs))
         .route("/runs/{run_id}", get(get_run))
         .route("/runs/{run_id}/approve", post(approve_run))
         .route("/runs/{run_id}/cancel", post(cancel_run))
@@ -300,6 +319,51 @@ pub async fn list_runs(
     Ok(Json(resp))
 }
 
+/// GET /api/v1/runs - List runs across all workflows, newest first.
+///
+/// Supports `status`, `name`, and `since` filters with `limit`/`offset`
+/// pagination.
+pub async fn list_all_runs(
+    State(state): State<AppState>,
+    _auth: Authenticated,
+    Query(query): Query<ListAllRunsQuery>,
+) -> Result<Json<ApiResponse<Vec<serde_json::Value>>>, AppError> {
+    let start = Instant::now();
+    let request_id = Uuid::now_v7().to_string();
+
+    let since = query
+        .since
+        .as_deref()
+        .map(|s| WorkflowRunFilter::parse_since(s, chrono::Utc::now()))
+        .transpose()
+        .map_err(AppError::Validation)?;
+
+    let filter = WorkflowRunFilter {
+        status: query.status,
+        workflow_name: query.name,
+        since,
+        limit: Some(i64::from(query.limit)),
+        offset: Some(i64::from(query.offset)),
+    };
+
+    let runs = state
+        .workflow_repo
+        .list_runs_filtered(&filter)
+        .await
+        .map_err(|e| AppError::Internal(e.to_string()))?;
+
+    let elapsed = start.elapsed().as_millis() as u64;
+    let runs_json: Vec<serde_json::Value> = runs
+        .iter()
+        .map(|r| serde_json::to_value(r).unwrap())
+        .collect();
+
+    let resp = ApiResponse::success(runs_json, request_id, elapsed)
+        .with_link("self", "/api/v1/runs");
+
+    Ok(Json(resp))
+}
+
 /// GET /api/v1/runs/:run_id - Get run detail with step logs.
 pub async fn get_run(
     State(state): State<AppState>,
@@ -8,6 +8,10 @@
 //! - **Receives commands:** Parses incoming text frames as [`WsCommand`] and
 //!   processes cancellation, budget decisions, and pings.
 //!
+//! Connecting with `?request_id=<uuid>` narrows the stream to that request:
+//! events already published for it are replayed first, then only its live
+//! events are forwarded.
+//!
 //! Lagged receivers (when the client is too slow to keep up) are handled
 //! gracefully: the handler logs a warning and continues receiving.
 //!
@@ -18,7 +22,7 @@
 use std::collections::HashSet;
 
 use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
-use axum::extract::State;
+use axum::extract::{Query, State};
 use axum::response::IntoResponse;
 use boternity_types::event::AgentEvent;
 use futures_util::{SinkExt, StreamExt};
@@ -49,14 +53,22 @@ enum WsCommand {
     UnsubscribeWorkflow { run_id: String },
 }
 
+/// Query parameters for `/ws/events`.
+#[derive(Debug, Default, serde::Deserialize)]
+pub struct WsParams {
+    /// Only forward events of this request, replaying its backlog on connect.
+    pub request_id: Option<Uuid>,
+}
+
 /// Upgrade an HTTP request to a WebSocket connection for agent events.
 ///
 /// This is mounted at `/ws/events` in the router.
 pub async fn ws_handler(
     ws: WebSocketUpgrade,
     State(state): State<AppState>,
+    Query(params): Query<WsParams>,
 ) -> impl IntoResponse {
-    ws.on_upgrade(move |socket| handle_ws_connection(socket, state))
+    ws.on_upgrade(move |socket| handle_ws_connection(socket, state, params.request_id))
 }
 
 /// Core WebSocket connection handler.
@@ -65,11 +77,25 @@ pub async fn ws_handler(
 /// [`EventBus`] and incoming WebSocket messages from the client. This
 /// approach keeps both sender and receiver in a single task, enabling
 /// bidirectional communication (e.g., responding to `Ping` with a pong).
-async fn handle_ws_connection(socket: WebSocket, state: AppState) {
+async fn handle_ws_connection(socket: WebSocket, state: AppState, request_id: Option<Uuid>) {
     let (mut ws_sender, mut ws_receiver) = socket.split();
 
-    // Subscribe to the event bus for agent lifecycle events.
-    let mut event_rx = state.event_bus.subscribe();
+    // Subscribe to the event bus for agent lifecycle events, narrowed to one
+    // request (with its backlog) when the client asked for it.
+    let (backlog, mut event_rx) = match request_id {
+        Some(id) => state.event_bus.subscribe_request_with_replay(id),
+        None => (Vec::new(), state.event_bus.subscribe_filtered(|_| true)),
+    };
+    for event in backlog {
+        match serde_json::to_string(&event) {
+            Ok(json) => {
+                if ws_sender.send(Message::Text(json.into())).await.is_err() {
+                    return;
+                }
+            }
+            Err(err) => tracing::warn!("Failed to serialize AgentEvent: {err}"),
+        }
+    }
 
     let budget_responses = state.budget_responses.clone();
     let agent_cancellations = state.agent_cancellations.clone();
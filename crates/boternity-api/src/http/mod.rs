@@ -8,3 +8,4 @@ pub mod extractors;
 pub mod handlers;
 pub mod response;
 pub mod router;
+pub mod server;
@@ -90,6 +90,12 @@ pub fn build_router(state: AppState) -> Router {
             "/bots/{id}/user",
             get(handlers::identity::get_user_context).put(handlers::identity::update_user_context),
         )
+        // File storage (streaming upload/download)
+        .route("/bots/{id}/files", get(handlers::storage::list_files))
+        .route(
+            "/bots/{id}/files/{filename}",
+            get(handlers::storage::download_file).put(handlers::storage::upload_file),
+        )
         // Dashboard stats
         .route("/stats", get(handlers::stats::get_stats))
         // Secrets
@@ -0,0 +1,166 @@
+//! Serving the router over plain HTTP or HTTPS.
+//!
+//! `bnity serve --tls-cert cert.pem --tls-key key.pem` terminates TLS
+//! in-process with rustls (via `axum-server`); without them the server
+//! speaks plain HTTP as before.
+
+use std::future::Future;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use axum::Router;
+use axum_server::Handle;
+use axum_server::tls_rustls::RustlsConfig;
+use rustls::ServerConfig;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use tokio::net::TcpListener;
+
+/// How long in-flight HTTPS requests may run after shutdown is requested.
+const TLS_SHUTDOWN_GRACE: Duration = Duration::from_secs(10);
+
+/// Load a PEM certificate chain and private key for HTTPS.
+///
+/// Fails with a message naming the offending file when either is missing,
+/// holds no usable PEM items, or the key does not match the certificate.
+pub fn load_tls_config(cert_path: &Path, key_path: &Path) -> Result<RustlsConfig> {
+    for (label, path) in [("certificate", cert_path), ("key", key_path)] {
+        if !path.is_file() {
+            bail!("TLS {label} file '{}' not found", path.display());
+        }
+    }
+
+    let certs = CertificateDer::pem_file_iter(cert_path)
+        .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+        .with_context(|| format!("Failed to read TLS certificate '{}'", cert_path.display()))?;
+    if certs.is_empty() {
+        bail!("No certificates found in '{}'", cert_path.display());
+    }
+    let key = PrivateKeyDer::from_pem_file(key_path)
+        .with_context(|| format!("Failed to read TLS private key '{}'", key_path.display()))?;
+
+    let mut config =
+        ServerConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+            .with_safe_default_protocol_versions()?
+            .with_no_client_auth()
+            .with_single_cert(certs, key)
+            .with_context(|| {
+                format!(
+                    "TLS key '{}' does not match certificate '{}'",
+                    key_path.display(),
+                    cert_path.display()
+                )
+            })?;
+    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+
+    Ok(RustlsConfig::from_config(Arc::new(config)))
+}
+
+/// Serve `router` on `listener` until `shutdown` resolves.
+///
+/// With `tls` set, connections are served over HTTPS.
+pub async fn serve(
+    listener: TcpListener,
+    router: Router,
+    tls: Option<RustlsConfig>,
+    shutdown: impl Future<Output = ()> + Send + 'static,
+) -> Result<()> {
+    let Some(tls) = tls else {
+        axum::serve(listener, router)
+            .with_graceful_shutdown(shutdown)
+            .await?;
+        return Ok(());
+    };
+
+    let handle = Handle::new();
+    let shutdown_handle = handle.clone();
+    tokio::spawn(async move {
+        shutdown.await;
+        shutdown_handle.graceful_shutdown(Some(TLS_SHUTDOWN_GRACE));
+    });
+
+    axum_server::from_tcp_rustls(listener.into_std()?, tls)
+        .handle(handle)
+        .serve(router.into_make_service())
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::routing::get;
+
+    /// Write a self-signed certificate for `localhost` into `dir`.
+    fn write_self_signed(dir: &Path) -> (std::path::PathBuf, std::path::PathBuf) {
+        let cert = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+        let cert_path = dir.join("cert.pem");
+        let key_path = dir.join("key.pem");
+        std::fs::write(&cert_path, cert.cert.pem()).unwrap();
+        std::fs::write(&key_path, cert.key_pair.serialize_pem()).unwrap();
+        (cert_path, key_path)
+    }
+
+    #[tokio::test]
+    async fn test_serves_https_with_self_signed_cert() {
+        let dir = tempfile::tempdir().unwrap();
+        let (cert_path, key_path) = write_self_signed(dir.path());
+        let tls = load_tls_config(&cert_path, &key_path).unwrap();
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let router = Router::new().route("/ping", get(|| async { "pong" }));
+        let (stop_tx, stop_rx) = tokio::sync::oneshot::channel::<()>();
+        let server = tokio::spawn(serve(listener, router, Some(tls), async {
+            let _ = stop_rx.await;
+        }));
+
+        let client = reqwest::Client::builder()
+            .danger_accept_invalid_certs(true)
+            .build()
+            .unwrap();
+        let response = client
+            .get(format!("https://localhost:{port}/ping"))
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        assert_eq!(response.text().await.unwrap(), "pong");
+
+        // Plain HTTP is not accepted on the TLS port
+        assert!(
+            client
+                .get(format!("http://localhost:{port}/ping"))
+                .send()
+                .await
+                .is_err()
+        );
+
+        stop_tx.send(()).unwrap();
+        server.await.unwrap().unwrap();
+    }
+
+    #[test]
+    fn test_missing_or_invalid_files_error_cleanly() {
+        let dir = tempfile::tempdir().unwrap();
+        let (cert_path, key_path) = write_self_signed(dir.path());
+
+        let missing = dir.path().join("missing-key.pem");
+        let err = load_tls_config(&cert_path, &missing).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            format!("TLS key file '{}' not found", missing.display())
+        );
+
+        let not_a_key = dir.path().join("not-a-key.pem");
+        std::fs::write(&not_a_key, "hello").unwrap();
+        let err = load_tls_config(&cert_path, &not_a_key).err().unwrap();
+        assert!(err.to_string().contains("Failed to read TLS private key"));
+
+        // A key is not a certificate
+        let err = load_tls_config(&key_path, &key_path).err().unwrap();
+        assert!(err.to_string().contains("No certificates found"));
+    }
+}
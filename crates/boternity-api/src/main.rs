@@ -9,16 +9,17 @@ mod cli;
 mod http;
 mod state;
 
+use boternity_infra::filesystem::identity::{IssueSeverity, validate_identity};
 use clap::Parser;
-use clap_complete::generate;
 use tracing_subscriber::EnvFilter;
 
-use cli::{Cli, CloneResource, Commands, CreateResource, DeleteResource, ExportResource, ListResource, SetResource, SoulCommand};
+use cli::{Cli, CloneResource, Commands, CreateResource, DeleteResource, ExportResource, ImportResource, ListResource, SetResource, SoulCommand};
 use state::AppState;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
+    cli::color::init(cli.no_color);
 
     // Set up tracing based on verbosity
     let filter = match cli.verbose {
@@ -31,15 +32,44 @@ async fn main() -> anyhow::Result<()> {
     tracing_subscriber::fmt()
         .with_env_filter(EnvFilter::new(filter))
         .with_target(false)
+        .with_ansi(console::colors_enabled_stderr())
         .init();
 
+    // In JSON mode failures are reported in the response envelope too, so
+    // scripts never have to parse styled error text.
+    let json = cli.json;
+    match run(cli).await {
+        Err(e) if json => {
+            cli::output::print_error(&e)?;
+            std::process::exit(1);
+        }
+        result => result,
+    }
+}
+
+/// Dispatch the parsed command.
+async fn run(cli: Cli) -> anyhow::Result<()> {
     // Shell completions don't need app state
     if let Commands::Completions { shell } = &cli.command {
-        let mut cmd = <Cli as clap::CommandFactory>::command();
-        generate(*shell, &mut cmd, "bnity", &mut std::io::stdout());
+        cli::complete::print_completions(*shell);
         return Ok(());
     }
 
+    // Completion lookups open the database only when a value is needed
+    if let Commands::Complete { context, words } = &cli.command {
+        return cli::complete::run_complete(*context, words).await;
+    }
+
+    // Migrations must be inspected before AppState::init applies them
+    if let Commands::Db { action } = cli.command {
+        return cli::db::handle_db_command(action, cli.json).await;
+    }
+
+    // Doctor diagnoses the problems that make AppState::init fail
+    if let Commands::Doctor = cli.command {
+        return cli::doctor::run_doctor(cli.json).await;
+    }
+
     // Initialize application state (DB, services)
     let state = AppState::init().await?;
 
@@ -49,27 +79,32 @@ async fn main() -> anyhow::Result<()> {
                 name,
                 description,
                 category,
-            } => {
-                cli::bot::create_bot(&state, name, description, category, cli.json).await?;
-            }
+                spec,
+            } => match spec {
+                Some(path) => cli::bot::create_bot_from_spec(&state, &path, cli.json).await?,
+                None => {
+                    cli::bot::create_bot(&state, name, description, category, cli.json).await?;
+                }
+            },
         },
 
         Commands::List { resource } => match resource {
-            ListResource::Bots { status, sort } => {
-                cli::bot::list_bots(&state, status, &sort, cli.json).await?;
+            ListResource::Bots { status, sort, deleted } => {
+                cli::bot::list_bots(&state, status, &sort, deleted, cli.json).await?;
             }
-            ListResource::Secrets => {
-                cli::secret::list_secrets(&state, cli.json).await?;
+            ListResource::Secrets { sources } => {
+                cli::secret::list_secrets(&state, sources, cli.json).await?;
             }
         },
 
         Commands::Show { slug } => {
+            let slug = cli::picker::resolve_bot_slug(&state, slug.as_deref(), cli.json).await?;
             cli::bot::show_bot(&state, &slug, cli.json).await?;
         }
 
         Commands::Delete { resource } => match resource {
-            DeleteResource::Bot { slug, force } => {
-                cli::bot::delete_bot(&state, &slug, force, cli.json).await?;
+            DeleteResource::Bot { slug, purge, force } => {
+                cli::bot::delete_bot(&state, &slug, purge, force, cli.json).await?;
             }
             DeleteResource::Session { id, force } => {
                 let session_id = id.parse::<uuid::Uuid>().map_err(|_| anyhow::anyhow!("Invalid session ID: {id}"))?;
@@ -91,8 +126,15 @@ async fn main() -> anyhow::Result<()> {
             SetResource::Secret { key, value } => {
                 cli::secret::set_secret(&state, &key, value.as_deref(), cli.json).await?;
             }
+            SetResource::Secrets { from_env, scope } => {
+                cli::secret::import_secrets(&state, &from_env, scope.as_deref(), cli.json).await?;
+            }
         },
 
+        Commands::Secret { action } => {
+            cli::secret::handle_secret_command(action, &state, cli.json).await?;
+        }
+
         Commands::Soul { action } => match action {
             SoulCommand::Edit { slug } => {
                 cli::soul::edit_soul(&state, &slug, cli.json).await?;
@@ -115,8 +157,9 @@ async fn main() -> anyhow::Result<()> {
             }
         },
 
-        Commands::Check { slug } => {
+        Commands::Check { slug, identity } => {
             // Health check for a bot including soul integrity verification
+            let slug = cli::picker::resolve_bot_slug(&state, slug.as_deref(), cli.json).await?;
             let bot = state.bot_service.get_bot_by_slug(&slug).await?;
             let soul_path = state.data_dir.join("bots").join(&bot.slug).join("SOUL.md");
             let has_soul = tokio::fs::try_exists(&soul_path).await.unwrap_or(false);
@@ -135,6 +178,17 @@ async fn main() -> anyhow::Result<()> {
 
             let integrity_ok = soul_integrity.as_ref().is_some_and(|r| r.valid);
 
+            // Lint IDENTITY.md frontmatter when requested
+            let identity_issues = if identity && has_identity {
+                let content = tokio::fs::read_to_string(&identity_path).await?;
+                Some(validate_identity(&content))
+            } else {
+                None
+            };
+            let identity_ok = identity_issues
+                .as_ref()
+                .is_none_or(|issues| issues.iter().all(|i| i.severity != IssueSeverity::Error));
+
             if cli.json {
                 let check = serde_json::json!({
                     "slug": slug,
@@ -142,9 +196,10 @@ async fn main() -> anyhow::Result<()> {
                     "soul_exists": has_soul,
                     "identity_exists": has_identity,
                     "soul_integrity": soul_integrity.as_ref().map(|r| r.valid),
-                    "healthy": has_soul && has_identity && integrity_ok,
+                    "identity_issues": identity_issues,
+                    "healthy": has_soul && has_identity && integrity_ok && identity_ok,
                 });
-                println!("{}", serde_json::to_string_pretty(&check)?);
+                cli::output::print_json(&check)?;
             } else {
                 println!();
                 println!(
@@ -161,6 +216,16 @@ async fn main() -> anyhow::Result<()> {
                 };
                 println!("  {} SOUL.md exists", check_mark(has_soul));
                 println!("  {} IDENTITY.md exists", check_mark(has_identity));
+                if let Some(issues) = &identity_issues {
+                    println!("  {} IDENTITY.md frontmatter", check_mark(identity_ok));
+                    for issue in issues {
+                        let label = match issue.severity {
+                            IssueSeverity::Error => console::style("error").red(),
+                            IssueSeverity::Warning => console::style("warning").yellow(),
+                        };
+                        println!("     {label} {}: {}", issue.field, issue.message);
+                    }
+                }
                 println!(
                     "  {} Bot status: {}",
                     check_mark(bot.status == boternity_types::bot::BotStatus::Active),
@@ -174,11 +239,11 @@ async fn main() -> anyhow::Result<()> {
                     if !integrity.valid {
                         println!(
                             "     Expected: {}",
-                            &integrity.expected_hash[..8.min(integrity.expected_hash.len())]
+                            cli::soul::short_hash(&integrity.expected_hash)
                         );
                         println!(
                             "     Actual:   {}",
-                            &integrity.actual_hash[..8.min(integrity.actual_hash.len())]
+                            cli::soul::short_hash(&integrity.actual_hash)
                         );
                     }
                 }
@@ -190,6 +255,14 @@ async fn main() -> anyhow::Result<()> {
             cli::status::status(&state, cli.json).await?;
         }
 
+        Commands::Search { query, limit } => {
+            cli::search::search(&state, &query, limit, cli.json).await?;
+        }
+
+        Commands::Bot { action } => {
+            cli::bot::handle_bot_command(action, &state, cli.json).await?;
+        }
+
         Commands::Provider { action } => {
             cli::provider::handle_provider_command(action, &state, cli.json).await?;
         }
@@ -226,7 +299,13 @@ async fn main() -> anyhow::Result<()> {
             cli::message::handle_message_command(action, &state, cli.json).await?;
         }
 
-        Commands::Serve { port, host } => {
+        Commands::Serve { port, host, tls_cert, tls_key } => {
+            // Load TLS material before printing anything so bad paths fail fast
+            let tls = match (&tls_cert, &tls_key) {
+                (Some(cert), Some(key)) => Some(http::server::load_tls_config(cert, key)?),
+                _ => None,
+            };
+
             // Ensure an API key exists, print it if new
             let api_key = http::extractors::auth::ensure_api_key(&state).await?;
             if api_key.starts_with("bnity_") {
@@ -243,39 +322,112 @@ async fn main() -> anyhow::Result<()> {
             let addr = format!("{host}:{port}");
             let listener = tokio::net::TcpListener::bind(&addr).await?;
 
+            let scheme = if tls.is_some() { "https" } else { "http" };
             println!(
                 "  {} Boternity API listening on {}",
                 console::style("⚡").bold(),
-                console::style(format!("http://{addr}")).cyan()
+                console::style(format!("{scheme}://{addr}")).cyan()
             );
             println!(
                 "  {}",
                 console::style("Press Ctrl+C to stop").dim()
             );
 
+            // Resume memory extractions interrupted by a previous crash
+            let retry_state = state.clone();
+            tokio::spawn(async move { retry_state.retry_pending_extractions().await });
+
+            if let Some(hours) = state.global_config.vector_index.compact_interval_hours {
+                let compact_state = state.clone();
+                let period = std::time::Duration::from_secs(hours.max(1) * 3600);
+                tokio::spawn(async move { compact_state.compact_vector_tables_every(period).await });
+            }
+
             let router = http::router::build_router(state);
 
-            axum::serve(listener, router)
-                .with_graceful_shutdown(shutdown_signal())
-                .await?;
+            http::server::serve(listener, router, tls, shutdown_signal()).await?;
 
             println!("\n  Server stopped.");
         }
 
         Commands::Export { resource } => match resource {
-            ExportResource::Session { id } => {
+            ExportResource::Session { id, sign } => {
                 let session_id = id.parse::<uuid::Uuid>().map_err(|_| anyhow::anyhow!("Invalid session ID: {id}"))?;
-                cli::session::export_session(&state, session_id, cli.json).await?;
+                cli::session::export_session(&state, session_id, sign, cli.json).await?;
             }
         },
 
+        Commands::Import { resource } => match resource {
+            ImportResource::Session { file, allow_unsigned } => {
+                cli::session::import_session(&state, &file, allow_unsigned, cli.json).await?;
+            }
+        },
+
+        Commands::Audit {
+            since,
+            actor,
+            action,
+            bot,
+            limit,
+            offset,
+            format,
+        } => {
+            cli::audit::handle_audit(
+                &state,
+                since.as_deref(),
+                actor,
+                action.as_deref(),
+                bot.as_deref(),
+                limit,
+                offset,
+                format,
+                cli.json,
+            )
+            .await?;
+        }
+
         Commands::Sessions { slug } => {
             cli::session::list_sessions(&state, &slug, cli.json).await?;
         }
 
-        Commands::Memories { slug } => {
-            cli::memory::list_memories(&state, &slug, cli.json).await?;
-        }
+        Commands::Memories { slug, action } => match (action, slug) {
+            (Some(cli::memory::MemoriesCommand::Edit { id, fact, importance, category }), _) => {
+                let memory_id = id.parse::<uuid::Uuid>().map_err(|_| anyhow::anyhow!("Invalid memory ID: {id}"))?;
+                let vector_store = match boternity_infra::vector::lance::LanceVectorStore::new(
+                    state.data_dir.join("vector_store"),
+                )
+                .await
+                {
+                    Ok(vs) => Some(boternity_core::memory::box_vector::BoxVectorMemoryStore::new(
+                        state.open_vector_memory(vs),
+                    )),
+                    Err(e) => {
+                        tracing::warn!(error = %e, "Failed to open vector store; skipping re-embed");
+                        None
+                    }
+                };
+                cli::memory::edit_memory(
+                    &state,
+                    memory_id,
+                    fact.as_deref(),
+                    importance,
+                    category,
+                    vector_store.as_ref(),
+                    cli.json,
+                )
+                .await?;
+            }
+            (Some(cli::memory::MemoriesCommand::Reindex { slug }), _) => {
+                cli::memory::reindex_memories(&state, &slug, cli.json).await?;
+            }
+            (Some(cli::memory::MemoriesCommand::Compact { slug }), _) => {
+                cli::memory::compact_memories(&state, slug.as_deref(), cli.json).await?;
+            }
+            (None, Some(slug)) => {
+                cli::memory::list_memories(&state, &slug, cli.json).await?;
+            }
+            (None, None) => unreachable!("clap requires a slug when no subcommand is given"),
+        },
 
         Commands::Remember { slug, fact } => {
             cli::memory::remember(&state, &slug, &fact, None, None, None, cli.json).await?;
@@ -285,17 +437,31 @@ async fn main() -> anyhow::Result<()> {
             cli::memory::forget(&state, &slug, force, cli.json).await?;
         }
 
-        Commands::Chat { slug, resume, verbose, quiet } => {
-            cli::chat::loop_runner::run_chat_loop(&state, &slug, resume, verbose, quiet).await?;
+        Commands::Chat { slug, resume, verbose, quiet, max_cost } => {
+            let slug = cli::picker::resolve_bot_slug(&state, slug.as_deref(), cli.json).await?;
+            let retry_state = state.clone();
+            tokio::spawn(async move { retry_state.retry_pending_extractions().await });
+            cli::chat::loop_runner::run_chat_loop(&state, &slug, resume, verbose, quiet, max_cost, cli.json).await?;
         }
 
-        Commands::Completions { .. } => unreachable!("handled above"),
+        Commands::Completions { .. }
+        | Commands::Complete { .. }
+        | Commands::Db { .. }
+        | Commands::Doctor => {
+            unreachable!("handled above")
+        }
 
-        Commands::Build { resume, reconfigure } => {
-            if resume {
-                cli::builder::run_builder_resume(&state).await?;
+        Commands::Build {
+            list,
+            resume,
+            reconfigure,
+        } => {
+            if list {
+                cli::builder::list_drafts(&state, cli.json).await?;
+            } else if let Some(id) = resume {
+                cli::builder::run_builder_resume(&state, id.as_deref()).await?;
             } else if let Some(slug) = reconfigure {
-                cli::builder::run_builder_reconfigure(&state, &slug).await?;
+                cli::builder::run_builder_reconfigure(&state, &slug, cli.json).await?;
             } else {
                 cli::builder::run_builder_wizard(&state).await?;
             }
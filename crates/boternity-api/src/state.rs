@@ -13,24 +13,24 @@ use std::sync::Arc;
 
 use boternity_core::chat::service::ChatService;
 use boternity_core::event::EventBus;
-use boternity_core::llm::fallback::FallbackChain;
+use boternity_core::llm::fallback::{apply_provider_preference, FallbackChain};
 use boternity_core::llm::provider::LlmProvider;
 use boternity_core::memory::box_embedder::BoxEmbedder;
-use boternity_core::memory::embedder::Embedder;
+use boternity_core::memory::similarity::SimilarityPolicy;
 use boternity_core::message::{LoopGuard, MessageBus};
 use boternity_core::service::bot::BotService;
 use boternity_core::service::secret::SecretService;
 use boternity_core::service::soul::SoulService;
-use boternity_types::config::GlobalConfig;
+use boternity_types::config::{EmbedderKind, GlobalConfig};
 use dashmap::DashMap;
 use tokio::sync::oneshot;
 use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
-use boternity_infra::crypto::hash::Sha256ContentHasher;
+use boternity_infra::crypto::hash::IntegrityHasher;
 use boternity_infra::crypto::vault::VaultCrypto;
 use boternity_infra::filesystem::{resolve_data_dir, LocalFileSystem};
 use boternity_infra::llm::openai_compat::config::default_cost_table;
-use boternity_infra::secret::chain::build_secret_chain;
+use boternity_infra::secret::chain::build_ordered_secret_chain;
 use boternity_infra::secret::VaultSecretProvider;
 use boternity_infra::skill::skill_store::SkillStore;
 use boternity_infra::skill::wasm_runtime::WasmRuntime;
@@ -51,18 +51,22 @@ use boternity_infra::sqlite::soul::SqliteSoulRepository;
 use boternity_infra::sqlite::workflow::SqliteWorkflowRepository;
 use boternity_infra::storage::filesystem::LocalFileStore;
 use boternity_infra::storage::indexer::FileIndexer;
-use boternity_infra::vector::embedder::FastEmbedEmbedder;
+use boternity_infra::vector::embedder::{DeterministicEmbedder, FastEmbedEmbedder};
 use boternity_infra::vector::lance::LanceVectorStore;
 use boternity_infra::vector::memory::LanceVectorMemoryStore;
 use boternity_infra::vector::shared::LanceSharedMemoryStore;
+use boternity_infra::workflow::event_trigger::{EventTriggerBridge, EventTriggerCallback};
 use boternity_infra::workflow::execution_context::LiveExecutionContext;
-use boternity_infra::workflow::webhook_handler::WebhookRegistry;
+use boternity_infra::workflow::hot_reload::{
+    watch_definitions, DefinitionWatcher, ReloadCallback, WorkflowReloader,
+};
+use boternity_infra::workflow::webhook_handler::{DeliveryDeduplicator, WebhookRegistry};
 use boternity_core::repository::workflow::WorkflowRepository;
 use boternity_core::workflow::executor::{DagExecutor, WorkflowExecutor};
 use boternity_core::workflow::scheduler::{CronCallback, CronScheduler};
 use boternity_core::workflow::trigger::TriggerManager;
-use boternity_types::llm::{FallbackChainConfig, ProviderConfig, ProviderType};
-use boternity_types::workflow::WorkflowRunStatus;
+use boternity_types::llm::{FallbackChainConfig, ProviderConfig, ProviderPreference, ProviderType};
+use boternity_types::workflow::{WorkflowDefinition, WorkflowRunStatus};
 use boternity_types::secret::SecretScope;
 
 use boternity_core::llm::box_provider::BoxLlmProvider;
@@ -75,16 +79,16 @@ pub type ConcreteBotService = BotService<
     SqliteBotRepository,
     SqliteSoulRepository,
     LocalFileSystem,
-    Sha256ContentHasher,
+    IntegrityHasher,
 >;
 
 pub type ConcreteSoulService =
-    SoulService<SqliteSoulRepository, LocalFileSystem, Sha256ContentHasher>;
+    SoulService<SqliteSoulRepository, LocalFileSystem, IntegrityHasher>;
 
 pub type ConcreteChatService = ChatService<SqliteChatRepository, SqliteMemoryRepository>;
 
-/// Concrete type alias for the file indexer pinned to FastEmbedEmbedder.
-pub type ConcreteFileIndexer = FileIndexer<FastEmbedEmbedder>;
+/// Concrete type alias for the file indexer, using the configured embedder.
+pub type ConcreteFileIndexer = FileIndexer<BoxEmbedder>;
 
 /// Shared application state holding all services.
 ///
@@ -110,10 +114,14 @@ pub struct AppState {
     // --- Phase 3 services ---
     /// LanceDB vector store for bot memories, shared memories, and file chunks.
     pub vector_store: Arc<LanceVectorStore>,
-    /// Type-erased embedding generator (FastEmbedEmbedder in production).
+    /// Type-erased embedding generator, selected by `embedder` in config.toml
+    /// (FastEmbedEmbedder by default).
     pub embedder: Arc<BoxEmbedder>,
     /// Per-bot vector memory store backed by LanceDB.
     pub vector_memory: Arc<LanceVectorMemoryStore>,
+    /// Vault key for memory facts at rest; `None` unless `encrypt_memories`
+    /// is set in config.toml.
+    pub memory_crypto: Option<Arc<VaultCrypto>>,
     /// Cross-bot shared memory store backed by LanceDB.
     pub shared_memory: Arc<LanceSharedMemoryStore>,
     /// Local filesystem file store with version history.
@@ -129,6 +137,9 @@ pub struct AppState {
 
     // --- Phase 5 services ---
     /// Event bus for agent lifecycle events (broadcast to WebSocket + CLI).
+    ///
+    /// Shared by all requests; the orchestrator tags each event with its
+    /// request id, so per-request consumers use `subscribe_request`.
     pub event_bus: EventBus,
     /// Global configuration from `~/.boternity/config.toml`.
     pub global_config: GlobalConfig,
@@ -162,12 +173,18 @@ pub struct AppState {
     pub message_bus: Arc<MessageBus>,
     /// DashMap-backed webhook path-to-config registry for incoming webhooks.
     pub webhook_registry: Arc<WebhookRegistry>,
+    /// Suppresses repeat webhook deliveries within a trigger's dedup window.
+    pub webhook_dedup: Arc<DeliveryDeduplicator>,
     /// DAG executor for running workflows with real service wiring.
     pub workflow_executor: Arc<DagExecutor<SqliteWorkflowRepository>>,
     /// Cron scheduler for time-based workflow triggers.
     pub cron_scheduler: Arc<CronScheduler>,
     /// Central trigger registry for cron/webhook/event/file_watch triggers.
     pub trigger_manager: Arc<TriggerManager>,
+    /// Listener launching event-triggered workflows; stops when dropped.
+    pub event_trigger_bridge: Arc<EventTriggerBridge>,
+    /// Watcher reloading edited workflow YAML files; `None` if it failed to start.
+    pub workflow_watcher: Option<Arc<DefinitionWatcher>>,
 }
 
 impl AppState {
@@ -178,12 +195,12 @@ impl AppState {
         // Ensure data directory exists
         tokio::fs::create_dir_all(&data_dir).await?;
 
+        // Global config is needed first: it sizes the database pools
+        let global_config = boternity_infra::config::load_global_config(&data_dir).await;
+
         // Initialize database
-        let db_url = format!(
-            "sqlite://{}?mode=rwc",
-            data_dir.join("boternity.db").display()
-        );
-        let db_pool = DatabasePool::new(&db_url).await?;
+        let db_url = database_url(&data_dir);
+        let db_pool = DatabasePool::with_config(&db_url, &global_config.database).await?;
 
         // Create repository instances
         let bot_repo = SqliteBotRepository::new(db_pool.clone());
@@ -193,7 +210,7 @@ impl AppState {
         let soul_service = SoulService::new(
             SqliteSoulRepository::new(db_pool.clone()),
             LocalFileSystem::new(),
-            Sha256ContentHasher::new(),
+            IntegrityHasher::new(global_config.integrity_hash),
         );
 
         // Wire bot service
@@ -210,31 +227,39 @@ impl AppState {
         // entry triggers a separate macOS authorization prompt, causing multiple
         // password dialogs per command. The keychain is used only for the vault
         // master key (VaultCrypto::from_keychain above), not for individual secrets.
-        let secret_chain = build_secret_chain(vault_provider, None, true);
+        let secret_chain =
+            build_ordered_secret_chain(vault_provider, None, &global_config.secret_providers);
         let secret_service = SecretService::new(secret_chain);
 
         // Create a separate soul service for the API (bot_service owns one internally)
         let api_soul_service = SoulService::new(
             SqliteSoulRepository::new(db_pool.clone()),
             LocalFileSystem::new(),
-            Sha256ContentHasher::new(),
+            IntegrityHasher::new(global_config.integrity_hash),
         );
 
         // Wire chat service with its repositories
         let chat_repo = SqliteChatRepository::new(db_pool.clone());
         let memory_repo = SqliteMemoryRepository::new(db_pool.clone());
-        let chat_service = ChatService::new(chat_repo, memory_repo);
+        let mut chat_service =
+            ChatService::new(chat_repo, memory_repo).with_quota(global_config.quota.clone());
+        if global_config.adaptive_recall {
+            chat_service = chat_service.with_similarity_policy(SimilarityPolicy::Adaptive);
+        }
 
         // --- Phase 3 services ---
 
-        // Initialize embedding model (downloads on first run, cached after)
-        let embedder = FastEmbedEmbedder::new()?;
+        // Initialize embedding model (fastembed downloads on first run, cached after)
+        let embedder = match global_config.embedder {
+            EmbedderKind::Fastembed => BoxEmbedder::new(FastEmbedEmbedder::new()?),
+            EmbedderKind::Deterministic => BoxEmbedder::new(DeterministicEmbedder::new()),
+        };
         tracing::info!(
             model = embedder.model_name(),
             dimension = embedder.dimension(),
             "Embedding model loaded"
         );
-        let embedder_arc = Arc::new(embedder);
+        let box_embedder = Arc::new(embedder);
 
         // Initialize LanceDB vector store at {data_dir}/vector_store
         let vector_store_path = data_dir.join("vector_store");
@@ -249,14 +274,28 @@ impl AppState {
         let vector_memory_lance = LanceVectorStore::new(vector_memory_store_path)
             .await
             .map_err(|e| anyhow::anyhow!("Failed to initialize vector memory store: {e}"))?;
-        let vector_memory = Arc::new(LanceVectorMemoryStore::new(vector_memory_lance));
+        let memory_crypto = if global_config.encrypt_memories {
+            Some(Arc::new(VaultCrypto::from_key_file(&vault_key_path)?))
+        } else {
+            None
+        };
+        let mut vector_memory = LanceVectorMemoryStore::new(vector_memory_lance)
+            .with_index_config(global_config.vector_index.clone());
+        if let Some(crypto) = &memory_crypto {
+            vector_memory = vector_memory.with_encryption(Arc::clone(crypto));
+        }
+        let vector_memory = Arc::new(vector_memory);
 
         // Cross-bot shared memory store
         let shared_memory_store_path = data_dir.join("vector_store");
         let shared_memory_lance = LanceVectorStore::new(shared_memory_store_path)
             .await
             .map_err(|e| anyhow::anyhow!("Failed to initialize shared memory store: {e}"))?;
-        let shared_memory = Arc::new(LanceSharedMemoryStore::new(shared_memory_lance));
+        let shared_memory = Arc::new(
+            LanceSharedMemoryStore::new(shared_memory_lance)
+                .with_index_config(global_config.vector_index.clone())
+                .with_hash_algorithm(global_config.integrity_hash),
+        );
 
         // File metadata store (SQLite)
         let file_metadata_store = SqliteFileMetadataStore::new(db_pool.clone());
@@ -267,12 +306,9 @@ impl AppState {
         // File indexer for chunking and embedding text files
         let file_indexer = Arc::new(FileIndexer::new(
             Arc::clone(&vector_store),
-            embedder_arc.clone(),
+            Arc::clone(&box_embedder),
         ));
 
-        // Type-erase the embedder for dynamic dispatch
-        let box_embedder = Arc::new(BoxEmbedder::new(FastEmbedEmbedder::new()?));
-
         // KV store (SQLite)
         let kv_store = Arc::new(SqliteKvStore::new(db_pool.clone()));
 
@@ -283,8 +319,7 @@ impl AppState {
         let provider_health_store = Arc::new(SqliteProviderHealthStore::new(db_pool.clone()));
 
         // --- Phase 5 services ---
-        let global_config = boternity_infra::config::load_global_config(&data_dir).await;
-        let event_bus = EventBus::new(1024);
+        let event_bus = EventBus::with_replay(1024, 512);
         let agent_cancellations = Arc::new(DashMap::new());
         let budget_responses = Arc::new(DashMap::new());
 
@@ -325,6 +360,7 @@ impl AppState {
 
         // Webhook registry for incoming webhook path resolution
         let webhook_registry = Arc::new(WebhookRegistry::new());
+        let webhook_dedup = Arc::new(DeliveryDeduplicator::new());
 
         // Crash recovery: mark any runs left in Running status as Crashed.
         // This handles workflows that were interrupted by a process restart.
@@ -369,7 +405,9 @@ impl AppState {
             event_bus.clone(),
             data_dir.clone(),
             live_exec_ctx,
-        ));
+        )
+        .with_message_bus(Arc::clone(&message_bus))
+        .with_secrets(Arc::clone(&secret_service)));
 
         // Cron scheduler for time-based workflow triggers
         let cron_scheduler = Arc::new(CronScheduler::new());
@@ -389,64 +427,7 @@ impl AppState {
                 .await;
 
             // Register cron triggers with the scheduler
-            for trigger in &def.triggers {
-                if let boternity_types::workflow::TriggerConfig::Cron { schedule, .. } = trigger {
-                    let executor = Arc::clone(&workflow_executor);
-                    let wf_repo_for_cron = Arc::clone(&workflow_repo);
-                    let wf_id = def.id;
-                    let sched = Arc::clone(&cron_scheduler);
-                    let cb: CronCallback = Arc::new(move |workflow_id, _fired_at| {
-                        let exec = Arc::clone(&executor);
-                        let repo = Arc::clone(&wf_repo_for_cron);
-                        let sched_inner = Arc::clone(&sched);
-                        Box::pin(async move {
-                            sched_inner.record_fire(workflow_id).await;
-                            match repo.get_definition(&workflow_id).await {
-                                Ok(Some(def)) => {
-                                    match exec.execute(&def, "cron", None).await {
-                                        Ok(result) => {
-                                            tracing::info!(
-                                                %workflow_id,
-                                                run_id = %result.run_id,
-                                                status = ?result.status,
-                                                "cron-triggered workflow completed"
-                                            );
-                                        }
-                                        Err(e) => {
-                                            tracing::error!(
-                                                %workflow_id,
-                                                error = %e,
-                                                "cron-triggered workflow failed"
-                                            );
-                                        }
-                                    }
-                                }
-                                Ok(None) => {
-                                    tracing::warn!(
-                                        %workflow_id,
-                                        "cron trigger: workflow definition not found"
-                                    );
-                                }
-                                Err(e) => {
-                                    tracing::error!(
-                                        %workflow_id,
-                                        error = %e,
-                                        "cron trigger: failed to load definition"
-                                    );
-                                }
-                            }
-                        })
-                    });
-                    if let Err(e) = cron_scheduler.schedule_workflow(wf_id, schedule, cb).await {
-                        tracing::warn!(
-                            workflow_id = %def.id,
-                            schedule = %schedule,
-                            error = %e,
-                            "failed to schedule cron trigger"
-                        );
-                    }
-                }
-            }
+            schedule_cron_triggers(def, &workflow_executor, &workflow_repo, &cron_scheduler).await;
         }
 
         if !all_defs.is_empty() {
@@ -458,110 +439,86 @@ impl AppState {
             );
         }
 
-        // EventBus listener for event-driven workflow triggers
-        let event_triggers = trigger_manager.get_event_triggers().await;
-        if !event_triggers.is_empty() {
-            let mut rx = event_bus.subscribe();
-            let executor_for_events = Arc::clone(&workflow_executor);
-            let repo_for_events = Arc::clone(&workflow_repo);
-            let tm_for_events = Arc::clone(&trigger_manager);
-
-            tokio::spawn(async move {
-                loop {
-                    match rx.recv().await {
-                        Ok(event) => {
-                            let event_type = serde_json::to_value(&event)
-                                .ok()
-                                .and_then(|v| v["type"].as_str().map(|s| s.to_string()))
-                                .unwrap_or_default();
-                            let event_json = serde_json::to_value(&event).ok();
-
-                            let triggers = tm_for_events.get_event_triggers().await;
-                            for (workflow_id, _source, trigger_event_type, when_clause) in &triggers
-                            {
-                                if trigger_event_type != &event_type {
-                                    continue;
-                                }
+        // Workflow YAML files in `{data_dir}/workflows` are loaded at startup
+        // and reloaded when edited.
+        let workflows_dir = data_dir.join("workflows");
+        tokio::fs::create_dir_all(&workflows_dir).await?;
+        let on_reload: ReloadCallback = {
+            let exec = Arc::clone(&workflow_executor);
+            let repo = Arc::clone(&workflow_repo);
+            let sched = Arc::clone(&cron_scheduler);
+            Arc::new(move |def| {
+                let exec = Arc::clone(&exec);
+                let repo = Arc::clone(&repo);
+                let sched = Arc::clone(&sched);
+                Box::pin(async move {
+                    let _ = sched.unschedule_workflow(def.id).await;
+                    schedule_cron_triggers(&def, &exec, &repo, &sched).await;
+                })
+            })
+        };
+        let workflow_reloader = Arc::new(
+            WorkflowReloader::new(Arc::clone(&workflow_repo), Arc::clone(&trigger_manager))
+                .with_on_reload(on_reload),
+        );
+        match workflow_reloader.reload_dir(&workflows_dir).await {
+            Ok(outcomes) if !outcomes.is_empty() => {
+                tracing::info!(files = outcomes.len(), "loaded workflow files");
+            }
+            Ok(_) => {}
+            Err(e) => tracing::warn!(error = %e, "failed to load workflow files"),
+        }
+        let workflow_watcher =
+            match watch_definitions(&workflows_dir, Arc::clone(&workflow_reloader), None) {
+                Ok(watcher) => Some(Arc::new(watcher)),
+                Err(e) => {
+                    tracing::warn!(error = %e, "workflow hot-reload disabled");
+                    None
+                }
+            };
 
-                                let trigger_ctx =
-                                    boternity_core::workflow::trigger::TriggerContext::new(
-                                        "event",
-                                        &event_type,
-                                        *workflow_id,
-                                        event_json.clone(),
-                                    );
-                                match tm_for_events
-                                    .evaluate_when_clause(when_clause.as_deref(), &trigger_ctx)
-                                {
-                                    Ok(true) => {
-                                        let exec = Arc::clone(&executor_for_events);
-                                        let repo = Arc::clone(&repo_for_events);
-                                        let wf_id = *workflow_id;
-                                        let payload = event_json.clone();
-                                        tokio::spawn(async move {
-                                            match repo.get_definition(&wf_id).await {
-                                                Ok(Some(def)) => {
-                                                    match exec
-                                                        .execute(&def, "event", payload)
-                                                        .await
-                                                    {
-                                                        Ok(result) => {
-                                                            tracing::info!(
-                                                                %wf_id,
-                                                                run_id = %result.run_id,
-                                                                "event-triggered workflow completed"
-                                                            );
-                                                        }
-                                                        Err(e) => {
-                                                            tracing::error!(
-                                                                %wf_id,
-                                                                error = %e,
-                                                                "event-triggered workflow failed"
-                                                            );
-                                                        }
-                                                    }
-                                                }
-                                                _ => {
-                                                    tracing::warn!(
-                                                        %wf_id,
-                                                        "event trigger: workflow not found"
-                                                    );
-                                                }
-                                            }
-                                        });
-                                    }
-                                    Ok(false) => {}
-                                    Err(e) => {
-                                        tracing::warn!(
-                                            %workflow_id,
-                                            error = %e,
-                                            "event trigger when-clause evaluation failed"
-                                        );
-                                    }
-                                }
+        // EventBus listener for event-driven workflow triggers. Always started
+        // so workflows registered after startup are also picked up.
+        let event_trigger_bridge = {
+            let exec = Arc::clone(&workflow_executor);
+            let repo = Arc::clone(&workflow_repo);
+            let launch: EventTriggerCallback = Arc::new(move |workflow_id, payload| {
+                let exec = Arc::clone(&exec);
+                let repo = Arc::clone(&repo);
+                Box::pin(async move {
+                    match repo.get_definition(&workflow_id).await {
+                        Ok(Some(def)) => match exec.execute(&def, "event", Some(payload)).await {
+                            Ok(result) => {
+                                tracing::info!(
+                                    %workflow_id,
+                                    run_id = %result.run_id,
+                                    "event-triggered workflow completed"
+                                );
                             }
-                        }
-                        Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
-                            tracing::warn!(
-                                skipped = n,
-                                "workflow event listener lagged"
-                            );
-                        }
-                        Err(tokio::sync::broadcast::error::RecvError::Closed) => {
-                            tracing::info!(
-                                "event bus closed, workflow event listener shutting down"
-                            );
-                            break;
+                            Err(e) => {
+                                tracing::error!(
+                                    %workflow_id,
+                                    error = %e,
+                                    "event-triggered workflow failed"
+                                );
+                            }
+                        },
+                        _ => {
+                            tracing::warn!(%workflow_id, "event trigger: workflow not found");
                         }
                     }
-                }
+                })
             });
-
-            tracing::info!(
-                event_triggers = event_triggers.len(),
-                "started workflow event trigger listener"
-            );
-        }
+            Arc::new(EventTriggerBridge::start(
+                &event_bus,
+                Arc::clone(&trigger_manager),
+                launch,
+            ))
+        };
+        tracing::info!(
+            event_triggers = trigger_manager.get_event_triggers().await.len(),
+            "started workflow event trigger listener"
+        );
 
         Ok(Self {
             bot_service: Arc::new(bot_service),
@@ -573,6 +530,7 @@ impl AppState {
             vector_store,
             embedder: box_embedder,
             vector_memory,
+            memory_crypto,
             shared_memory,
             file_store,
             file_indexer,
@@ -592,9 +550,12 @@ impl AppState {
             message_repo,
             message_bus,
             webhook_registry,
+            webhook_dedup,
             workflow_executor,
             cron_scheduler,
             trigger_manager,
+            event_trigger_bridge,
+            workflow_watcher,
         })
     }
 
@@ -606,14 +567,23 @@ impl AppState {
     ///
     /// The chain uses the default cost table for failover cost warnings.
     ///
+    /// When the bot has a [`ProviderPreference`] (from IDENTITY.md), its
+    /// preferred providers lead the chain (or form the whole chain when
+    /// exclusive). Without one, the global chain is used as-is.
+    ///
     /// # Arguments
     ///
     /// * `model` - The model to use for the primary Anthropic provider
+    /// * `preference` - Optional per-bot provider override
     ///
     /// # Errors
     ///
     /// Returns an error if no ANTHROPIC_API_KEY is found in the secret store.
-    pub async fn build_fallback_chain(&self, model: &str) -> anyhow::Result<FallbackChain> {
+    pub async fn build_fallback_chain(
+        &self,
+        model: &str,
+        preference: Option<&ProviderPreference>,
+    ) -> anyhow::Result<FallbackChain> {
         let api_key_value = self
             .secret_service
             .get_secret("ANTHROPIC_API_KEY", &SecretScope::Global)
@@ -642,6 +612,8 @@ impl AppState {
                     priority: 0,
                     enabled: true,
                     capabilities: caps,
+                    extra_headers: Default::default(),
+                    extra_body: Default::default(),
                 },
             )
         } else {
@@ -659,6 +631,8 @@ impl AppState {
                     priority: 0,
                     enabled: true,
                     capabilities: caps,
+                    extra_headers: Default::default(),
+                    extra_body: Default::default(),
                 },
             )
         };
@@ -705,12 +679,17 @@ impl AppState {
             }
         }
 
-        let chain_config = FallbackChainConfig {
+        let mut chain_config = FallbackChainConfig {
             providers: all_configs,
             rate_limit_queue_timeout_ms: 5000,
             cost_warning_multiplier: 3.0,
         };
 
+        // Per-bot override: reorder or restrict the global chain
+        if let Some(preference) = preference {
+            apply_provider_preference(&mut chain_config, &mut all_providers, preference);
+        }
+
         let cost_table = default_cost_table();
 
         // Build the chain keyed by provider name for cost lookups
@@ -761,8 +740,164 @@ impl AppState {
         }
     }
 
+    /// Retry memory extractions left pending by a crash or failure.
+    ///
+    /// Each job uses the model its session ran with. Failures are logged and
+    /// rescheduled. Returns the number of memories recovered.
+    pub async fn retry_pending_extractions(&self) -> usize {
+        let due = match self.chat_service.due_extractions().await {
+            Ok(due) => due,
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to load pending memory extractions");
+                return 0;
+            }
+        };
+
+        let mut recovered = 0;
+        for pending in due {
+            let model = match self.chat_service.get_session(&pending.session_id).await {
+                Ok(Some(session)) => session.model,
+                Ok(None) => {
+                    // The session is gone; nothing left to extract from.
+                    let _ = self.chat_service.finish_extraction(&pending).await;
+                    continue;
+                }
+                Err(e) => {
+                    tracing::warn!(session_id = %pending.session_id, error = %e, "Failed to load session for extraction retry");
+                    continue;
+                }
+            };
+            let provider = match self.create_single_provider(&model).await {
+                Ok(provider) => provider,
+                Err(e) => {
+                    let _ = self.chat_service.fail_extraction(&pending, &e.to_string()).await;
+                    continue;
+                }
+            };
+            match self.chat_service.retry_extraction(&pending, &provider).await {
+                Ok(Some(count)) => recovered += count,
+                Ok(None) => {}
+                Err(e) => {
+                    tracing::warn!(session_id = %pending.session_id, error = %e, "Memory extraction retry failed");
+                }
+            }
+        }
+        recovered
+    }
+
+    /// Compact all vector tables every `period`, forever.
+    ///
+    /// Spawned by `bnity serve` when `[vector_index] compact_interval_hours`
+    /// is set. Failures are logged and retried next period.
+    pub async fn compact_vector_tables_every(&self, period: std::time::Duration) {
+        let mut ticker = tokio::time::interval(period);
+        // The first tick completes immediately; skip it so startup stays quick.
+        ticker.tick().await;
+        loop {
+            ticker.tick().await;
+            match self.vector_store.compact_all().await {
+                Ok(reports) => {
+                    let removed: usize = reports.iter().map(|r| r.fragments_removed).sum();
+                    tracing::info!(
+                        tables = reports.len(),
+                        fragments_removed = removed,
+                        "Compacted vector tables"
+                    );
+                }
+                Err(e) => tracing::warn!(error = %e, "Vector table compaction failed"),
+            }
+        }
+    }
+
+    /// Wrap a fresh LanceDB connection in a vector memory store configured
+    /// like `vector_memory` (index settings and fact encryption).
+    pub fn open_vector_memory(&self, store: LanceVectorStore) -> LanceVectorMemoryStore {
+        let memory = LanceVectorMemoryStore::new(store)
+            .with_index_config(self.global_config.vector_index.clone());
+        match &self.memory_crypto {
+            Some(crypto) => memory.with_encryption(Arc::clone(crypto)),
+            None => memory,
+        }
+    }
+
     /// Return the path to the skills directory (`{data_dir}/skills`).
     pub fn skills_dir(&self) -> PathBuf {
         self.data_dir.join("skills")
     }
 }
+
+/// SQLite connection URL for the database inside `data_dir`.
+pub fn database_url(data_dir: &std::path::Path) -> String {
+    format!("sqlite://{}?mode=rwc", data_dir.join("boternity.db").display())
+}
+
+/// Schedule the cron triggers of `def`, running it on `executor` when they fire.
+///
+/// The definition is re-read from `repo` on every fire, so a reloaded
+/// definition takes effect on the next run.
+async fn schedule_cron_triggers(
+    def: &WorkflowDefinition,
+    executor: &Arc<DagExecutor<SqliteWorkflowRepository>>,
+    repo: &Arc<SqliteWorkflowRepository>,
+    cron_scheduler: &Arc<CronScheduler>,
+) {
+    for trigger in &def.triggers {
+        if let boternity_types::workflow::TriggerConfig::Cron { schedule, .. } = trigger {
+            let executor = Arc::clone(executor);
+            let wf_repo_for_cron = Arc::clone(repo);
+            let wf_id = def.id;
+            let sched = Arc::clone(cron_scheduler);
+            let cb: CronCallback = Arc::new(move |workflow_id, _fired_at| {
+                let exec = Arc::clone(&executor);
+                let repo = Arc::clone(&wf_repo_for_cron);
+                let sched_inner = Arc::clone(&sched);
+                Box::pin(async move {
+                    sched_inner.record_fire(workflow_id).await;
+                    match repo.get_definition(&workflow_id).await {
+                        Ok(Some(def)) => {
+                            match exec.execute(&def, "cron", None).await {
+                                Ok(result) => {
+                                    tracing::info!(
+                                        %workflow_id,
+                                        run_id = %result.run_id,
+                                        status = ?result.status,
+                                        "cron-triggered workflow completed"
+                                    );
+                                }
+                                Err(e) => {
+                                    tracing::error!(
+                                        %workflow_id,
+                                        error = %e,
+                                        "cron-triggered workflow failed"
+                                    );
+                                }
+                            }
+                        }
+                        Ok(None) => {
+                            tracing::warn!(
+                                %workflow_id,
+                                "cron trigger: workflow definition not found"
+                            );
+                        }
+                        Err(e) => {
+                            tracing::error!(
+                                %workflow_id,
+                                error = %e,
+                                "cron trigger: failed to load definition"
+                            );
+                        }
+                    }
+                })
+            });
+            if let Err(e) = cron_scheduler.schedule_workflow(wf_id, schedule, cb).await {
+                tracing::warn!(
+                    workflow_id = %def.id,
+                    schedule = %schedule,
+                    error = %e,
+                    "failed to schedule cron trigger"
+                );
+            }
+        }
+    }
+
+}
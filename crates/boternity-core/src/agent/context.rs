@@ -6,12 +6,13 @@
 //! prompt via a `<long_term_memory>` section when available.
 
 use boternity_types::agent::AgentConfig;
-use boternity_types::llm::{Message, MessageRole};
+use boternity_types::llm::{CompletionRequest, LlmError, Message, MessageRole};
 use boternity_types::memory::{MemoryEntry, RankedMemory};
+use boternity_types::user::UserProfile;
 
 use crate::llm::token_budget::TokenBudget;
 
-use super::prompt::SystemPromptBuilder;
+use super::prompt::{SystemPromptBuilder, escape_xml};
 
 /// Holds all state needed for an agent conversation.
 ///
@@ -29,6 +30,8 @@ pub struct AgentContext {
     pub identity_content: String,
     /// Content from USER.md -- standing user instructions.
     pub user_content: String,
+    /// Structured fields from the USER.md frontmatter, if any.
+    pub user_profile: Option<UserProfile>,
     /// Extracted memories from previous sessions (session-scoped).
     pub memories: Vec<MemoryEntry>,
     /// Semantically recalled long-term memories from vector search.
@@ -40,6 +43,10 @@ pub struct AgentContext {
     pub conversation_history: Vec<Message>,
     /// Token budget for context window management.
     pub token_budget: TokenBudget,
+    /// Summary of older messages that were compacted out of the history.
+    ///
+    /// Appended to the system prompt as a `<conversation_summary>` section.
+    pub history_summary: Option<String>,
     /// Pre-built system prompt assembled from personality + memories.
     pub system_prompt: String,
     /// Whether verbose mode is enabled (shows memory injection details).
@@ -59,17 +66,19 @@ impl AgentContext {
         token_budget: TokenBudget,
     ) -> Self {
         let system_prompt =
-            SystemPromptBuilder::build(&config, &soul, &identity, &user, &memories, &[]);
+            SystemPromptBuilder::build(&config, &soul, &identity, None, &user, &memories, &[]);
 
         Self {
             agent_config: config,
             soul_content: soul,
             identity_content: identity,
             user_content: user,
+            user_profile: None,
             memories,
             recalled_memories: Vec::new(),
             conversation_history: Vec::new(),
             token_budget,
+            history_summary: None,
             system_prompt,
             verbose: false,
         }
@@ -81,6 +90,16 @@ impl AgentContext {
         self
     }
 
+    /// Set the structured USER.md profile and rebuild the system prompt.
+    ///
+    /// `user_content` should then hold only the free-text body below the
+    /// frontmatter.
+    pub fn with_user_profile(mut self, profile: Option<UserProfile>) -> Self {
+        self.user_profile = profile;
+        self.rebuild_system_prompt();
+        self
+    }
+
     /// Update the recalled long-term memories and rebuild the system prompt.
     ///
     /// Called before each LLM request with fresh vector search results.
@@ -90,6 +109,14 @@ impl AgentContext {
         self.rebuild_system_prompt();
     }
 
+    /// Restore a persisted rolling summary, e.g. when resuming a session.
+    ///
+    /// Replaces any current summary and rebuilds the system prompt.
+    pub fn set_history_summary(&mut self, summary: Option<String>) {
+        self.history_summary = summary.filter(|s| !s.trim().is_empty());
+        self.rebuild_system_prompt();
+    }
+
     /// Rebuild the system prompt from current state.
     ///
     /// Called after recalled_memories changes to keep the system prompt
@@ -99,10 +126,17 @@ impl AgentContext {
             &self.agent_config,
             &self.soul_content,
             &self.identity_content,
+            self.user_profile.as_ref(),
             &self.user_content,
             &self.memories,
             &self.recalled_memories,
         );
+        if let Some(ref summary) = self.history_summary {
+            self.system_prompt.push_str(&format!(
+                "\n\n<conversation_summary>\n{}\n</conversation_summary>",
+                escape_xml(summary)
+            ));
+        }
     }
 
     /// Add a user message to the conversation history.
@@ -110,6 +144,8 @@ impl AgentContext {
         self.conversation_history.push(Message {
             role: MessageRole::User,
             content,
+            tool: None,
+            images: Vec::new(),
         });
     }
 
@@ -118,6 +154,8 @@ impl AgentContext {
         self.conversation_history.push(Message {
             role: MessageRole::Assistant,
             content,
+            tool: None,
+            images: Vec::new(),
         });
     }
 
@@ -130,6 +168,23 @@ impl AgentContext {
         self.conversation_history.clone()
     }
 
+    /// Build a streaming request for `user_message` from this context: the
+    /// system prompt, the conversation history, then the message itself,
+    /// with the agent's model, token limit, and temperature.
+    ///
+    /// Fails when the agent config does not make a valid request (e.g. a
+    /// `max_tokens` of zero).
+    pub fn completion_request(&self, user_message: &str) -> Result<CompletionRequest, LlmError> {
+        CompletionRequest::builder(self.agent_config.model.clone())
+            .system(self.system_prompt.clone())
+            .messages(self.build_messages())
+            .message(Message::from(user_message.to_string()))
+            .max_tokens(self.agent_config.max_tokens)
+            .temperature(self.agent_config.temperature)
+            .stream(true)
+            .build()
+    }
+
     /// Whether the conversation should be summarized to free token budget.
     ///
     /// Estimates conversation tokens from the character count of all messages
@@ -141,6 +196,63 @@ impl AgentContext {
         self.token_budget.should_summarize(estimated_tokens)
     }
 
+    /// How many of the most recent messages to keep after the provider
+    /// rejected a request as too long.
+    ///
+    /// `max`/`requested` come from [`LlmError::ContextLengthExceeded`]. When
+    /// both are known, enough history is dropped to shed the overflow plus a
+    /// 20% safety margin; otherwise half of the history is dropped. The kept
+    /// window always starts with a user message and always drops at least
+    /// one message when there is history to drop.
+    ///
+    /// [`LlmError::ContextLengthExceeded`]: boternity_types::llm::LlmError::ContextLengthExceeded
+    pub fn overflow_keep_count(&self, max: u32, requested: u32) -> usize {
+        let current = self.estimate_conversation_tokens();
+        let target = if max > 0 && requested > max {
+            let excess = requested - max;
+            current.saturating_sub(excess + excess / 5)
+        } else {
+            current / 2
+        };
+
+        let mut kept_tokens: u32 = 0;
+        let mut keep = 0;
+        for msg in self.conversation_history.iter().rev() {
+            let tokens = (msg.content.len() / 4) as u32;
+            if kept_tokens + tokens > target {
+                break;
+            }
+            kept_tokens += tokens;
+            keep += 1;
+        }
+
+        let len = self.conversation_history.len();
+        keep = keep.min(len.saturating_sub(1));
+        while keep > 0 && self.conversation_history[len - keep].role != MessageRole::User {
+            keep -= 1;
+        }
+        keep
+    }
+
+    /// Drop all but the `keep_recent` most recent messages.
+    ///
+    /// When a `summary` of the dropped messages is provided it is merged into
+    /// [`Self::history_summary`] and the system prompt is rebuilt. Returns the
+    /// number of messages removed.
+    pub fn compact_history(&mut self, keep_recent: usize, summary: Option<String>) -> usize {
+        let removed = self.conversation_history.len().saturating_sub(keep_recent);
+        self.conversation_history.drain(..removed);
+
+        if let Some(summary) = summary.filter(|s| !s.trim().is_empty()) {
+            self.history_summary = Some(match self.history_summary.take() {
+                Some(previous) => format!("{previous}\n\n{summary}"),
+                None => summary,
+            });
+            self.rebuild_system_prompt();
+        }
+        removed
+    }
+
     /// Create a child context for a sub-agent task.
     ///
     /// Inherits the bot's personality (SOUL.md) and model config but gets:
@@ -165,10 +277,12 @@ impl AgentContext {
             soul_content: self.soul_content.clone(),
             identity_content: self.identity_content.clone(),
             user_content: String::new(),
+            user_profile: None,
             memories: Vec::new(),
             recalled_memories: Vec::new(),
             conversation_history: Vec::new(),
             token_budget: self.token_budget.clone(),
+            history_summary: None,
             system_prompt,
             verbose: self.verbose,
         }
@@ -203,6 +317,7 @@ mod tests {
             model: "claude-sonnet-4-20250514".to_string(),
             temperature: 0.7,
             max_tokens: 4096,
+            guard_untrusted_content: false,
         }
     }
 
@@ -262,6 +377,37 @@ mod tests {
         assert_eq!(messages[0].role, MessageRole::User);
     }
 
+    #[test]
+    fn test_completion_request_from_context() {
+        let mut ctx = AgentContext::new(
+            test_config(),
+            "Soul content.".to_string(),
+            "Identity content.".to_string(),
+            String::new(),
+            vec![],
+            TokenBudget::new(200_000),
+        );
+        ctx.add_user_message("Previous message".to_string());
+        ctx.add_assistant_message("Previous response".to_string());
+
+        let request = ctx.completion_request("New question").unwrap();
+        assert_eq!(request.model, "claude-sonnet-4-20250514");
+        assert_eq!(request.max_tokens, 4096);
+        assert_eq!(request.temperature, Some(0.7));
+        assert!(request.stream);
+        assert_eq!(request.system.as_deref(), Some(ctx.system_prompt.as_str()));
+        // 2 history messages + 1 new user message
+        assert_eq!(request.messages.len(), 3);
+        assert_eq!(request.messages[2].content, "New question");
+        assert_eq!(request.messages[2].role, MessageRole::User);
+
+        ctx.agent_config.max_tokens = 0;
+        assert!(matches!(
+            ctx.completion_request("New question"),
+            Err(LlmError::InvalidRequest(_))
+        ));
+    }
+
     #[test]
     fn test_should_summarize_false_when_small() {
         let mut ctx = AgentContext::new(
@@ -296,6 +442,74 @@ mod tests {
         assert!(ctx.should_summarize());
     }
 
+    fn context_with_history(turns: usize, chars: usize) -> AgentContext {
+        let mut ctx = AgentContext::new(
+            test_config(),
+            String::new(),
+            String::new(),
+            String::new(),
+            vec![],
+            TokenBudget::new(200_000),
+        );
+        for i in 0..turns {
+            ctx.add_user_message(format!("{i}{}", "u".repeat(chars)));
+            ctx.add_assistant_message(format!("{i}{}", "a".repeat(chars)));
+        }
+        ctx
+    }
+
+    #[test]
+    fn test_overflow_keep_count_sheds_reported_excess() {
+        // 10 turns x 2 messages x ~100 tokens = ~2000 tokens
+        let ctx = context_with_history(10, 400);
+        // Over by 500 tokens -> shed 600 with margin -> keep ~1400 tokens
+        let keep = ctx.overflow_keep_count(10_000, 10_500);
+        assert!(keep < 20);
+        assert!(keep >= 12);
+        assert_eq!(ctx.conversation_history[20 - keep].role, MessageRole::User);
+    }
+
+    #[test]
+    fn test_overflow_keep_count_halves_when_sizes_unknown() {
+        let ctx = context_with_history(10, 400);
+        let keep = ctx.overflow_keep_count(0, 0);
+        assert!(keep <= 10);
+        assert!(keep > 0);
+        assert_eq!(ctx.conversation_history[20 - keep].role, MessageRole::User);
+    }
+
+    #[test]
+    fn test_overflow_keep_count_always_drops_something() {
+        let ctx = context_with_history(1, 4);
+        // Tiny history that "fits" the target still loses at least one message
+        assert_eq!(ctx.overflow_keep_count(100, 101), 0);
+    }
+
+    #[test]
+    fn test_compact_history_with_summary() {
+        let mut ctx = context_with_history(3, 10);
+        let removed = ctx.compact_history(2, Some("The user introduced themselves.".to_string()));
+
+        assert_eq!(removed, 4);
+        assert_eq!(ctx.conversation_history.len(), 2);
+        assert!(ctx.conversation_history[0].content.starts_with('2'));
+        assert!(ctx.system_prompt.contains("<conversation_summary>"));
+        assert!(ctx.system_prompt.contains("The user introduced themselves."));
+
+        // Summary survives a prompt rebuild from new recalled memories
+        ctx.set_recalled_memories(vec![]);
+        assert!(ctx.system_prompt.contains("The user introduced themselves."));
+    }
+
+    #[test]
+    fn test_compact_history_without_summary() {
+        let mut ctx = context_with_history(3, 10);
+        let removed = ctx.compact_history(4, None);
+        assert_eq!(removed, 2);
+        assert!(ctx.history_summary.is_none());
+        assert!(!ctx.system_prompt.contains("<conversation_summary>"));
+    }
+
     #[test]
     fn test_child_for_task_has_empty_conversation_history() {
         let mut ctx = AgentContext::new(
@@ -317,6 +531,26 @@ mod tests {
         assert!(child.user_content.is_empty());
     }
 
+    #[test]
+    fn test_with_user_profile_rebuilds_prompt() {
+        let ctx = AgentContext::new(
+            test_config(),
+            "I am creative.".to_string(),
+            "Name: Luna".to_string(),
+            "Be concise.".to_string(),
+            vec![],
+            TokenBudget::new(200_000),
+        )
+        .with_user_profile(Some(UserProfile {
+            pronouns: Some("she/her".to_string()),
+            ..Default::default()
+        }));
+
+        assert!(ctx.system_prompt.contains("<user_profile>\nPronouns: she/her\n</user_profile>"));
+        assert!(ctx.system_prompt.contains("Be concise."));
+        assert!(ctx.child_for_task("Summarize", 1).user_profile.is_none());
+    }
+
     #[test]
     fn test_child_for_task_inherits_soul_and_config() {
         let ctx = AgentContext::new(
@@ -9,9 +9,7 @@ use std::pin::Pin;
 use futures_util::Stream;
 use tracing::{Instrument, debug, info, info_span};
 
-use boternity_types::llm::{
-    CompletionRequest, CompletionResponse, LlmError, StreamEvent,
-};
+use boternity_types::llm::{CompletionResponse, LlmError, StreamEvent};
 
 use crate::llm::box_provider::BoxLlmProvider;
 
@@ -46,7 +44,10 @@ impl AgentEngine {
         user_message: &str,
     ) -> Pin<Box<dyn Stream<Item = Result<StreamEvent, LlmError>> + Send + 'static>> {
         Self::log_recalled_memories(context);
-        let request = self.build_request(context, user_message);
+        let request = match context.completion_request(user_message) {
+            Ok(request) => request,
+            Err(e) => return Box::pin(futures_util::stream::once(async move { Err(e) })),
+        };
 
         let span = info_span!(
             "gen_ai.execute",
@@ -73,7 +74,7 @@ impl AgentEngine {
         user_message: &str,
     ) -> Result<CompletionResponse, LlmError> {
         Self::log_recalled_memories(context);
-        let request = self.build_request(context, user_message);
+        let request = context.completion_request(user_message)?;
 
         let span = info_span!(
             "gen_ai.complete",
@@ -98,7 +99,7 @@ impl AgentEngine {
         let greeting_prompt = "Generate a short, warm greeting message that introduces yourself \
             and invites the user to chat. Stay fully in character. Keep it under 2 sentences.";
 
-        let request = self.build_request(context, greeting_prompt);
+        let request = context.completion_request(greeting_prompt)?;
 
         let span = info_span!(
             "gen_ai.greeting",
@@ -143,28 +144,6 @@ impl AgentEngine {
             );
         }
     }
-
-    /// Build a CompletionRequest from the agent context and a user message.
-    fn build_request(&self, context: &AgentContext, user_message: &str) -> CompletionRequest {
-        let mut messages = context.build_messages();
-
-        // Add the current user message to the request
-        messages.push(boternity_types::llm::Message {
-            role: boternity_types::llm::MessageRole::User,
-            content: user_message.to_string(),
-        });
-
-        CompletionRequest {
-            model: context.agent_config.model.clone(),
-            messages,
-            system: Some(context.system_prompt.clone()),
-            max_tokens: context.agent_config.max_tokens,
-            temperature: Some(context.agent_config.temperature),
-            stream: true, // Default to streaming; overridden by complete()
-            stop_sequences: None,
-            output_config: None,
-        }
-    }
 }
 
 /// A stream wrapper that keeps an OTel span alive for the duration of streaming.
@@ -211,6 +190,7 @@ mod tests {
             model: "claude-sonnet-4-20250514".to_string(),
             temperature: 0.7,
             max_tokens: 4096,
+            guard_untrusted_content: false,
         };
 
         AgentContext::new(
@@ -226,7 +206,7 @@ mod tests {
     #[test]
     fn test_build_request() {
         // We can't construct AgentEngine without a real provider,
-        // but we can test build_request indirectly via the context.
+        // but the request is built from the context's messages.
         let ctx = test_context();
         let messages = ctx.build_messages();
         assert!(messages.is_empty()); // No conversation history yet
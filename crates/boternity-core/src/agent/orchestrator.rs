@@ -16,7 +16,7 @@ use uuid::Uuid;
 
 use boternity_types::agent::{AgentNode, AgentStatus, SpawnMode, SubAgentResult};
 use boternity_types::event::AgentEvent;
-use boternity_types::llm::{CompletionRequest, LlmError, Message, MessageRole, StreamEvent};
+use boternity_types::llm::{CompletionRequest, LlmError, StreamEvent};
 
 use crate::agent::budget::BudgetStatus;
 use crate::agent::context::AgentContext;
@@ -69,18 +69,25 @@ impl AgentOrchestrator {
         let root_agent_id = Uuid::now_v7();
         let start = Instant::now();
 
-        // Step a: Rebuild system prompt with agent capabilities
-        context.system_prompt = SystemPromptBuilder::build_with_capabilities(
+        // Tag every event of this tree with the request id so subscribers can
+        // demultiplex concurrent requests sharing one bus.
+        let event_bus = &event_bus.scoped(request_ctx.request_id);
+
+        // Step a: Rebuild system prompt with agent capabilities, keeping
+        // recalled memories within the memory budget
+        context.system_prompt = SystemPromptBuilder::build_with_capabilities_budgeted(
             &context.agent_config,
             &context.soul_content,
             &context.identity_content,
+            context.user_profile.as_ref(),
             &context.user_content,
             &context.memories,
             &context.recalled_memories,
+            context.token_budget.memory_budget,
         );
 
         // Step b: Build CompletionRequest
-        let request = build_completion_request(context, user_message);
+        let request = context.completion_request(user_message)?;
 
         // Step c: Stream the response, tracking budget
         let full_response = self
@@ -197,7 +204,7 @@ impl AgentOrchestrator {
             });
 
             let synthesis_prompt = build_synthesis_prompt(&sub_results);
-            let synthesis_request = build_completion_request(context, &synthesis_prompt);
+            let synthesis_request = context.completion_request(&synthesis_prompt)?;
             let synthesis_response = self
                 .stream_and_collect(
                     provider,
@@ -348,11 +355,14 @@ impl AgentOrchestrator {
             let bus = event_bus.clone();
             let req_ctx = child_request_ctx.clone();
 
-            // Build completion request for the child
-            let child_request = build_completion_request(&child_ctx, &task_desc);
-
-            // Create the stream outside the JoinSet (uses provider reference)
-            let stream = provider.stream(child_request);
+            // Create the stream outside the JoinSet (uses provider reference).
+            // An invalid child request fails through the normal stream error path.
+            let stream = match child_ctx.completion_request(&task_desc) {
+                Ok(child_request) => {
+                    provider.stream_with_cancel(child_request, req_ctx.cancellation.clone())
+                }
+                Err(e) => Box::pin(futures_util::stream::once(async move { Err(e) })),
+            };
 
             // Spawn the collection task (the stream is 'static)
             set.spawn(async move {
@@ -617,8 +627,8 @@ impl AgentOrchestrator {
         agent_id: Uuid,
         task: &str,
     ) -> Result<(String, u32), OrchestratorError> {
-        let request = build_completion_request(context, task);
-        let stream = provider.stream(request);
+        let request = context.completion_request(task)?;
+        let stream = provider.stream_with_cancel(request, request_ctx.cancellation.clone());
 
         let (response, tokens) =
             collect_stream_with_events(stream, request_ctx, event_bus, agent_id, self.max_depth)
@@ -639,7 +649,7 @@ impl AgentOrchestrator {
         event_bus: &EventBus,
         agent_id: Uuid,
     ) -> Result<String, OrchestratorError> {
-        let stream = provider.stream(request);
+        let stream = provider.stream_with_cancel(request, request_ctx.cancellation.clone());
 
         let (response, _tokens) =
             collect_stream_with_events(stream, request_ctx, event_bus, agent_id, self.max_depth)
@@ -740,30 +750,17 @@ async fn collect_stream_with_events(
         }
     }
 
-    Ok((full_response, total_tokens))
-}
-
-/// Build a `CompletionRequest` from an `AgentContext` and a user message.
-///
-/// Replicates the pattern from `AgentEngine::build_request` for use by the
-/// orchestrator (which doesn't own an `AgentEngine`).
-fn build_completion_request(context: &AgentContext, user_message: &str) -> CompletionRequest {
-    let mut messages = context.build_messages();
-    messages.push(Message {
-        role: MessageRole::User,
-        content: user_message.to_string(),
-    });
-
-    CompletionRequest {
-        model: context.agent_config.model.clone(),
-        messages,
-        system: Some(context.system_prompt.clone()),
-        max_tokens: context.agent_config.max_tokens,
-        temperature: Some(context.agent_config.temperature),
-        stream: true,
-        stop_sequences: None,
-        output_config: None,
+    // A cancelled stream ends without `Done`; report it as a cancellation
+    // rather than a (truncated) successful response.
+    if request_ctx.is_cancelled() {
+        event_bus.publish(AgentEvent::AgentCancelled {
+            agent_id,
+            reason: "Request cancelled during streaming".to_string(),
+        });
+        return Err(OrchestratorError::Cancelled);
     }
+
+    Ok((full_response, total_tokens))
 }
 
 /// Build the synthesis prompt from sub-agent results.
@@ -1153,44 +1150,6 @@ mod tests {
         assert!(result.ends_with("..."));
     }
 
-    #[test]
-    fn test_build_completion_request_from_context() {
-        use boternity_types::agent::AgentConfig;
-        use crate::llm::token_budget::TokenBudget;
-
-        let config = AgentConfig {
-            bot_id: Uuid::now_v7(),
-            bot_name: "TestBot".to_string(),
-            bot_slug: "testbot".to_string(),
-            bot_emoji: None,
-            model: "claude-sonnet-4-20250514".to_string(),
-            temperature: 0.7,
-            max_tokens: 4096,
-        };
-
-        let mut context = AgentContext::new(
-            config,
-            "Soul content.".to_string(),
-            "Identity content.".to_string(),
-            String::new(),
-            vec![],
-            TokenBudget::new(200_000),
-        );
-
-        context.add_user_message("Previous message".to_string());
-        context.add_assistant_message("Previous response".to_string());
-
-        let request = build_completion_request(&context, "New question");
-
-        assert_eq!(request.model, "claude-sonnet-4-20250514");
-        assert_eq!(request.max_tokens, 4096);
-        assert!(request.system.is_some());
-        // 2 history messages + 1 new user message
-        assert_eq!(request.messages.len(), 3);
-        assert_eq!(request.messages[2].content, "New question");
-        assert_eq!(request.messages[2].role, MessageRole::User);
-    }
-
     #[test]
     fn test_orchestrator_error_display() {
         let err = OrchestratorError::Cancelled;
@@ -1222,4 +1181,126 @@ mod tests {
 
         assert!(prompt.contains("(no output)"));
     }
+
+    /// Streams the last user message back word by word.
+    struct EchoProvider;
+
+    impl crate::llm::provider::LlmProvider for EchoProvider {
+        fn name(&self) -> &str {
+            "echo"
+        }
+
+        fn capabilities(&self) -> &boternity_types::llm::ProviderCapabilities {
+            static CAPS: boternity_types::llm::ProviderCapabilities =
+                boternity_types::llm::ProviderCapabilities {
+                    streaming: true,
+                    tool_calling: false,
+                    vision: false,
+                    extended_thinking: false,
+                    max_context_tokens: 200_000,
+                    max_output_tokens: 4096,
+                };
+            &CAPS
+        }
+
+        async fn complete(
+            &self,
+            _request: &CompletionRequest,
+        ) -> Result<boternity_types::llm::CompletionResponse, LlmError> {
+            Err(LlmError::Provider {
+                message: "echo only streams".to_string(),
+            })
+        }
+
+        fn stream(
+            &self,
+            request: CompletionRequest,
+        ) -> std::pin::Pin<
+            Box<dyn futures_util::Stream<Item = Result<StreamEvent, LlmError>> + Send + 'static>,
+        > {
+            let text = request.messages.last().map(|m| m.content.clone()).unwrap_or_default();
+            let mut events: Vec<Result<StreamEvent, LlmError>> = text
+                .split_whitespace()
+                .map(|word| {
+                    Ok(StreamEvent::TextDelta {
+                        index: 0,
+                        text: format!("{word} "),
+                    })
+                })
+                .collect();
+            events.push(Ok(StreamEvent::Done));
+            Box::pin(futures_util::stream::iter(events).then(|e| async move {
+                tokio::task::yield_now().await;
+                e
+            }))
+        }
+
+        async fn count_tokens(
+            &self,
+            _request: &CompletionRequest,
+        ) -> Result<boternity_types::llm::TokenCount, LlmError> {
+            Ok(boternity_types::llm::TokenCount { input_tokens: 0 })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_requests_do_not_cross_talk() {
+        use crate::agent::budget::RequestBudget;
+        use crate::llm::token_budget::TokenBudget;
+        use boternity_types::agent::AgentConfig;
+
+        fn context() -> AgentContext {
+            AgentContext::new(
+                AgentConfig {
+                    bot_id: Uuid::now_v7(),
+                    bot_name: "TestBot".to_string(),
+                    bot_slug: "testbot".to_string(),
+                    bot_emoji: None,
+                    model: "echo".to_string(),
+                    temperature: 0.7,
+                    max_tokens: 4096,
+                    guard_untrusted_content: false,
+                },
+                String::new(),
+                String::new(),
+                String::new(),
+                vec![],
+                TokenBudget::new(200_000),
+            )
+        }
+
+        let bus = EventBus::new(1024);
+        let ctx_a = RequestContext::new(Uuid::now_v7(), RequestBudget::new(500_000));
+        let ctx_b = RequestContext::new(Uuid::now_v7(), RequestBudget::new(500_000));
+        let mut rx_a = bus.subscribe_request(ctx_a.request_id);
+        let mut rx_b = bus.subscribe_request(ctx_b.request_id);
+
+        let provider = BoxLlmProvider::new(EchoProvider);
+        let orchestrator = AgentOrchestrator::default();
+        let (mut context_a, mut context_b) = (context(), context());
+        let (result_a, result_b) = tokio::join!(
+            orchestrator.execute(&provider, &mut context_a, "alpha alpha alpha", &ctx_a, &bus),
+            orchestrator.execute(&provider, &mut context_b, "bravo bravo bravo bravo", &ctx_b, &bus),
+        );
+        result_a.unwrap();
+        result_b.unwrap();
+
+        for (rx, ctx, word, count) in [
+            (&mut rx_a, &ctx_a, "alpha", 3),
+            (&mut rx_b, &ctx_b, "bravo", 4),
+        ] {
+            let mut deltas = Vec::new();
+            while let Ok(event) = rx.try_recv() {
+                match event {
+                    AgentEvent::AgentTextDelta { text, .. } => deltas.push(text),
+                    AgentEvent::BudgetUpdate { request_id, .. } => {
+                        assert_eq!(request_id, ctx.request_id)
+                    }
+                    other => panic!("unexpected event: {other:?}"),
+                }
+            }
+            assert_eq!(deltas.len(), count);
+            assert!(deltas.iter().all(|d| d.trim() == word), "{deltas:?}");
+        }
+    }
 }
@@ -5,10 +5,12 @@
 //! key context while freeing token budget for new messages, preventing
 //! personality drift in long conversations.
 
-use boternity_types::llm::{CompletionRequest, LlmError, Message, MessageRole};
+use boternity_types::llm::{CompletionRequest, LlmError, Message};
 
 use crate::llm::box_provider::BoxLlmProvider;
 
+use super::context::AgentContext;
+
 /// System prompt for the context summarization LLM call.
 const SUMMARY_SYSTEM_PROMPT: &str = r#"Summarize the following conversation segment concisely. Preserve:
 1. Key decisions and conclusions
@@ -18,6 +20,16 @@ const SUMMARY_SYSTEM_PROMPT: &str = r#"Summarize the following conversation segm
 
 Keep the summary under 500 words. Write in third person (e.g., "The user asked about..." "The assistant recommended...")."#;
 
+/// Outcome of compacting history, e.g. after a context-length-exceeded error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OverflowRecovery {
+    /// Number of history messages dropped from the context.
+    pub removed_messages: usize,
+    /// Whether the dropped messages were condensed into a summary
+    /// (false when no summarizer was available or summarization failed).
+    pub summarized: bool,
+}
+
 /// Stateless utility for summarizing conversation context.
 ///
 /// Used by the sliding window manager to condense older messages into
@@ -54,26 +66,79 @@ impl ContextSummarizer {
             .collect::<Vec<_>>()
             .join("\n\n");
 
-        let request = CompletionRequest {
-            model: model.to_string(),
-            messages: vec![Message {
-                role: MessageRole::User,
-                content: format!(
-                    "Please summarize this conversation:\n\n<conversation>\n{conversation_text}\n</conversation>"
-                ),
-            }],
-            system: Some(SUMMARY_SYSTEM_PROMPT.to_string()),
-            max_tokens: 1024,
-            temperature: Some(0.0),
-            stream: false,
-            stop_sequences: None,
-            output_config: None,
-        };
+        let request = CompletionRequest::builder(model)
+            .system(SUMMARY_SYSTEM_PROMPT)
+            .message(Message::from(format!(
+                "Please summarize this conversation:\n\n<conversation>\n{conversation_text}\n</conversation>"
+            )))
+            .max_tokens(1024)
+            .temperature(0.0)
+            .build()?;
 
         let response = provider.complete(&request).await?;
         Ok(response.content.trim().to_string())
     }
 
+    /// Shrink the conversation history after a provider rejected a request
+    /// with [`LlmError::ContextLengthExceeded`].
+    ///
+    /// Picks how much history to drop from the reported `max`/`requested`
+    /// sizes (see [`AgentContext::overflow_keep_count`]), summarizes the
+    /// dropped messages with `provider` when one is given, and compacts the
+    /// context. Summarization failures fall back to plain truncation.
+    ///
+    /// Returns `None` when there is no history left to drop, in which case
+    /// retrying would fail the same way.
+    pub async fn recover_from_overflow(
+        provider: Option<&BoxLlmProvider>,
+        context: &mut AgentContext,
+        max: u32,
+        requested: u32,
+    ) -> Option<OverflowRecovery> {
+        let keep = context.overflow_keep_count(max, requested);
+        Self::compact(provider, context, keep).await
+    }
+
+    /// Summarize all but the `keep` most recent messages into the context's
+    /// rolling summary and drop them from its history.
+    ///
+    /// Without a provider, or when summarization fails, the older messages
+    /// are dropped without a summary. Returns `None` when there is nothing
+    /// to drop.
+    pub async fn compact(
+        provider: Option<&BoxLlmProvider>,
+        context: &mut AgentContext,
+        keep: usize,
+    ) -> Option<OverflowRecovery> {
+        let messages = context.build_messages();
+        let (to_summarize, _) = Self::select_messages_to_summarize(&messages, keep);
+        if to_summarize.is_empty() {
+            return None;
+        }
+
+        let summary = match provider {
+            Some(provider) => {
+                let model = context.agent_config.model.clone();
+                match Self::summarize(provider, to_summarize, &model).await {
+                    Ok(summary) if !summary.is_empty() => Some(summary),
+                    Ok(_) => None,
+                    Err(e) => {
+                        tracing::warn!(error = %e, "Context summarization failed; truncating history");
+                        None
+                    }
+                }
+            }
+            None => None,
+        };
+
+        let summarized = summary.is_some();
+        let removed_messages = context.compact_history(keep, summary);
+        Some(OverflowRecovery {
+            removed_messages,
+            summarized,
+        })
+    }
+
     /// Split messages into two slices: those to summarize, and those to keep.
     ///
     /// Returns `(to_summarize, to_keep)` where `to_keep` contains the most
@@ -99,16 +164,195 @@ impl ContextSummarizer {
 mod tests {
     use super::*;
 
+    use std::pin::Pin;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use futures_util::Stream;
+
+    use boternity_types::agent::AgentConfig;
+    use boternity_types::llm::{
+        CompletionResponse, MessageRole, ProviderCapabilities, StopReason, StreamEvent,
+        TokenCount, Usage,
+    };
+
+    use crate::llm::provider::LlmProvider;
+    use crate::llm::token_budget::TokenBudget;
+
+    /// Rejects any request with more than `limit` messages as too long and
+    /// records the message count of every request it sees.
+    struct OverflowProvider {
+        limit: usize,
+        seen: Arc<std::sync::Mutex<Vec<usize>>>,
+        summaries: Arc<AtomicUsize>,
+    }
+
+    impl LlmProvider for OverflowProvider {
+        fn name(&self) -> &str {
+            "overflow"
+        }
+
+        fn capabilities(&self) -> &ProviderCapabilities {
+            static CAPS: ProviderCapabilities = ProviderCapabilities {
+                streaming: false,
+                tool_calling: false,
+                vision: false,
+                extended_thinking: false,
+                max_context_tokens: 1_000,
+                max_output_tokens: 100,
+            };
+            &CAPS
+        }
+
+        async fn complete(&self, request: &CompletionRequest) -> Result<CompletionResponse, LlmError> {
+            if request.system.as_deref() == Some(SUMMARY_SYSTEM_PROMPT) {
+                self.summaries.fetch_add(1, Ordering::SeqCst);
+                return Ok(response("Earlier, the user shared some facts."));
+            }
+
+            self.seen.lock().unwrap().push(request.messages.len());
+            if request.messages.len() > self.limit {
+                Err(LlmError::ContextLengthExceeded {
+                    max: 1_000,
+                    requested: 1_200,
+                })
+            } else {
+                Ok(response("ok"))
+            }
+        }
+
+        fn stream(
+            &self,
+            _request: CompletionRequest,
+        ) -> Pin<Box<dyn Stream<Item = Result<StreamEvent, LlmError>> + Send + 'static>> {
+            Box::pin(futures_util::stream::empty())
+        }
+
+        async fn count_tokens(&self, _request: &CompletionRequest) -> Result<TokenCount, LlmError> {
+            Ok(TokenCount { input_tokens: 0 })
+        }
+    }
+
+    fn response(content: &str) -> CompletionResponse {
+        CompletionResponse {
+            id: "resp".to_string(),
+            content: content.to_string(),
+            model: "mock".to_string(),
+            stop_reason: StopReason::EndTurn,
+            usage: Usage::default(),
+        }
+    }
+
+    fn long_context() -> AgentContext {
+        let mut ctx = AgentContext::new(
+            AgentConfig {
+                bot_id: uuid::Uuid::now_v7(),
+                bot_name: "Luna".to_string(),
+                bot_slug: "luna".to_string(),
+                bot_emoji: None,
+                model: "mock".to_string(),
+                temperature: 0.7,
+                max_tokens: 100,
+                guard_untrusted_content: false,
+            },
+            String::new(),
+            String::new(),
+            String::new(),
+            vec![],
+            TokenBudget::new(1_000),
+        );
+        for i in 0..6 {
+            ctx.add_user_message(format!("question {i} {}", "x".repeat(400)));
+            ctx.add_assistant_message(format!("answer {i} {}", "y".repeat(400)));
+        }
+        ctx
+    }
+
+    fn request_for(ctx: &AgentContext) -> CompletionRequest {
+        CompletionRequest {
+            model: "mock".to_string(),
+            messages: ctx.build_messages(),
+            system: Some(ctx.system_prompt.clone()),
+            max_tokens: 100,
+            temperature: None,
+            top_p: None,
+            frequency_penalty: None,
+            presence_penalty: None,
+            stream: false,
+            stop_sequences: None,
+            output_config: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_overflow_recovery_retries_with_trimmed_history() {
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let summaries = Arc::new(AtomicUsize::new(0));
+        let provider = BoxLlmProvider::new(OverflowProvider {
+            limit: 8,
+            seen: seen.clone(),
+            summaries: summaries.clone(),
+        });
+        let mut ctx = long_context();
+
+        let (max, requested) = match provider.complete(&request_for(&ctx)).await {
+            Err(LlmError::ContextLengthExceeded { max, requested }) => (max, requested),
+            other => panic!("expected context overflow, got {other:?}"),
+        };
+
+        let recovery = ContextSummarizer::recover_from_overflow(Some(&provider), &mut ctx, max, requested)
+            .await
+            .expect("history should be trimmed");
+        assert!(recovery.removed_messages > 0);
+        assert!(recovery.summarized);
+        assert_eq!(summaries.load(Ordering::SeqCst), 1);
+        assert!(ctx.system_prompt.contains("Earlier, the user shared some facts."));
+
+        let retried = provider.complete(&request_for(&ctx)).await;
+        assert!(retried.is_ok());
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.len(), 2);
+        assert_eq!(seen[0], 12);
+        assert!(seen[1] < seen[0]);
+        assert_eq!(ctx.conversation_history[0].role, MessageRole::User);
+    }
+
+    #[tokio::test]
+    async fn test_overflow_recovery_without_summarizer_truncates() {
+        let mut ctx = long_context();
+        let recovery = ContextSummarizer::recover_from_overflow(None, &mut ctx, 0, 0)
+            .await
+            .unwrap();
+        assert_eq!(recovery.removed_messages, 6);
+        assert!(!recovery.summarized);
+        assert!(ctx.history_summary.is_none());
+        assert_eq!(ctx.conversation_history.len(), 6);
+    }
+
+    #[tokio::test]
+    async fn test_overflow_recovery_with_empty_history_gives_up() {
+        let mut ctx = long_context();
+        ctx.conversation_history.clear();
+        assert!(ContextSummarizer::recover_from_overflow(None, &mut ctx, 1_000, 1_200)
+            .await
+            .is_none());
+    }
+
     #[test]
     fn test_select_messages_fewer_than_keep() {
         let messages = vec![
             Message {
                 role: MessageRole::User,
                 content: "Hello".to_string(),
+                tool: None,
+                images: Vec::new(),
             },
             Message {
                 role: MessageRole::Assistant,
                 content: "Hi!".to_string(),
+                tool: None,
+                images: Vec::new(),
             },
         ];
 
@@ -124,10 +368,14 @@ mod tests {
             Message {
                 role: MessageRole::User,
                 content: "One".to_string(),
+                tool: None,
+                images: Vec::new(),
             },
             Message {
                 role: MessageRole::Assistant,
                 content: "Two".to_string(),
+                tool: None,
+                images: Vec::new(),
             },
         ];
 
@@ -143,26 +391,38 @@ mod tests {
             Message {
                 role: MessageRole::User,
                 content: "Oldest".to_string(),
+                tool: None,
+                images: Vec::new(),
             },
             Message {
                 role: MessageRole::Assistant,
                 content: "Old reply".to_string(),
+                tool: None,
+                images: Vec::new(),
             },
             Message {
                 role: MessageRole::User,
                 content: "Middle".to_string(),
+                tool: None,
+                images: Vec::new(),
             },
             Message {
                 role: MessageRole::Assistant,
                 content: "Middle reply".to_string(),
+                tool: None,
+                images: Vec::new(),
             },
             Message {
                 role: MessageRole::User,
                 content: "Recent".to_string(),
+                tool: None,
+                images: Vec::new(),
             },
             Message {
                 role: MessageRole::Assistant,
                 content: "Recent reply".to_string(),
+                tool: None,
+                images: Vec::new(),
             },
         ];
 
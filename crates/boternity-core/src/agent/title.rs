@@ -39,29 +39,21 @@ pub async fn generate_title(
     first_assistant_message: &str,
     model: &str,
 ) -> Result<String, LlmError> {
-    let request = CompletionRequest {
-        model: model.to_string(),
-        messages: vec![
-            Message {
-                role: MessageRole::User,
-                content: first_user_message.to_string(),
-            },
-            Message {
-                role: MessageRole::Assistant,
-                content: first_assistant_message.to_string(),
-            },
-            Message {
-                role: MessageRole::User,
-                content: "Based on our exchange above, generate a title.".to_string(),
-            },
-        ],
-        system: Some(TITLE_SYSTEM_PROMPT.to_string()),
-        max_tokens: 50,
-        temperature: Some(0.3),
-        stream: false,
-        stop_sequences: None,
-        output_config: None,
-    };
+    let request = CompletionRequest::builder(model)
+        .system(TITLE_SYSTEM_PROMPT)
+        .message(Message::from(first_user_message.to_string()))
+        .message(Message {
+            role: MessageRole::Assistant,
+            content: first_assistant_message.to_string(),
+            tool: None,
+            images: Vec::new(),
+        })
+        .message(Message::from(
+            "Based on our exchange above, generate a title.".to_string(),
+        ))
+        .max_tokens(50)
+        .temperature(0.3)
+        .build()?;
 
     let response = provider.complete(&request).await?;
 
@@ -11,7 +11,9 @@
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
-use boternity_types::bot::CreateBotRequest;
+use serde::Serialize;
+
+use boternity_types::bot::{Bot, BotId, CreateBotRequest, UpdateBotRequest};
 use boternity_types::builder::{BuilderConfig, ModelConfig, PersonalityConfig};
 use boternity_types::skill::{BotSkillConfig, BotSkillsFile, TrustTier};
 
@@ -20,10 +22,12 @@ use crate::repository::soul::SoulRepository;
 use crate::service::bot::BotService;
 use crate::service::fs::FileSystem;
 use crate::service::hash::ContentHasher;
+use crate::service::soul::compute_line_diff;
 use crate::skill::manifest::serialize_bot_skills_config;
 
 use super::agent::BuilderError;
 use super::skill_builder::SkillBuildResult;
+use super::spec::ValidatedBotSpec;
 
 // ---------------------------------------------------------------------------
 // Assembly result types
@@ -49,6 +53,43 @@ pub struct AssemblyResult {
     pub file_paths: AssemblyPaths,
 }
 
+/// A bot record field that a reconfiguration would change.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub current: String,
+    pub proposed: String,
+}
+
+/// What applying a builder config to an existing bot would change.
+///
+/// Built by [`BotAssembler::preview_reconfigure`] without writing anything;
+/// [`BotAssembler::apply_reconfigure`] then writes exactly these changes.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReconfigurePreview {
+    pub bot_id: BotId,
+    pub slug: String,
+    /// Changed bot record fields (name, description, category, tags).
+    pub fields: Vec<FieldChange>,
+    /// Line diff of SOUL.md (`+`/`-`/` ` prefixed), `None` if unchanged.
+    pub soul_diff: Option<String>,
+    /// Line diff of IDENTITY.md, `None` if unchanged.
+    pub identity_diff: Option<String>,
+    #[serde(skip)]
+    soul_content: String,
+    #[serde(skip)]
+    identity_content: String,
+    #[serde(skip)]
+    update: UpdateBotRequest,
+}
+
+impl ReconfigurePreview {
+    /// Whether applying the preview would change anything.
+    pub fn has_changes(&self) -> bool {
+        !self.fields.is_empty() || self.soul_diff.is_some() || self.identity_diff.is_some()
+    }
+}
+
 // ---------------------------------------------------------------------------
 // BotAssembler
 // ---------------------------------------------------------------------------
@@ -82,9 +123,6 @@ impl BotAssembler {
         F: FileSystem,
         H: ContentHasher,
     {
-        // Step 1: Create bot via BotService::create_bot.
-        // This creates the DB record AND writes default SOUL.md, IDENTITY.md,
-        // USER.md to disk.
         let create_req = CreateBotRequest {
             name: config.name.clone(),
             description: Some(config.description.clone()),
@@ -96,6 +134,214 @@ impl BotAssembler {
             ),
             tags: Some(config.tags.clone()),
         };
+        let soul_content = generate_soul_content(&config.personality, &config.name);
+        let identity_content = generate_identity_content(&config.model_config);
+        let user_content = generate_user_content(&config.name, &config.description);
+        let (bot, file_paths) = Self::create_with_files(
+            bot_service,
+            create_req,
+            &soul_content,
+            &identity_content,
+            &user_content,
+        )
+        .await?;
+        let bot_dir = file_paths.bot_dir.clone();
+
+        // Step 5: Attach skills if any were requested.
+        let skills_attached = if !config.skills.is_empty() {
+            // Build SkillBuildResults from SkillRequests (lightweight -- no LLM call here,
+            // these are already-generated manifests from the builder flow).
+            let skill_results: Vec<SkillBuildResult> = config
+                .skills
+                .iter()
+                .map(|sr| skill_request_to_build_result(sr))
+                .collect();
+            Self::attach_skills(&bot.slug, &skill_results, &bot_dir)?
+        } else {
+            Vec::new()
+        };
+
+        Ok(AssemblyResult {
+            bot,
+            soul_content,
+            identity_content,
+            user_content,
+            skills_attached,
+            file_paths,
+        })
+    }
+
+    /// Assemble a bot from a validated declarative spec (no LLM involved).
+    ///
+    /// The spec's files are already rendered by [`BotSpec::validate`], so
+    /// this only runs the create-and-overwrite sequence. Secrets are not
+    /// touched here; resolving them is up to the caller.
+    ///
+    /// [`BotSpec::validate`]: super::spec::BotSpec::validate
+    pub async fn assemble_spec<B, S, F, H>(
+        bot_service: &BotService<B, S, F, H>,
+        spec: &ValidatedBotSpec,
+    ) -> Result<AssemblyResult, BuilderError>
+    where
+        B: BotRepository,
+        S: SoulRepository,
+        F: FileSystem,
+        H: ContentHasher,
+    {
+        let create_req = CreateBotRequest {
+            name: spec.name.clone(),
+            description: Some(spec.description.clone()),
+            category: Some(spec.category.clone()),
+            tags: Some(spec.tags.clone()),
+        };
+        let (bot, file_paths) = Self::create_with_files(
+            bot_service,
+            create_req,
+            &spec.soul_content,
+            &spec.identity_content,
+            &spec.user_content,
+        )
+        .await?;
+
+        Ok(AssemblyResult {
+            bot,
+            soul_content: spec.soul_content.clone(),
+            identity_content: spec.identity_content.clone(),
+            user_content: spec.user_content.clone(),
+            skills_attached: Vec::new(),
+            file_paths,
+        })
+    }
+
+    /// Compare a builder config against an existing bot without writing.
+    ///
+    /// The proposed IDENTITY.md keeps every key of the current file and only
+    /// replaces the model settings the builder controls. USER.md is never
+    /// touched by a reconfiguration.
+    pub async fn preview_reconfigure<B, S, F, H>(
+        bot_service: &BotService<B, S, F, H>,
+        bot: &Bot,
+        config: &BuilderConfig,
+    ) -> Result<ReconfigurePreview, BuilderError>
+    where
+        B: BotRepository,
+        S: SoulRepository,
+        F: FileSystem,
+        H: ContentHasher,
+    {
+        let bot_dir = bot_service.bot_dir(&bot.slug);
+        let fs = bot_service.soul_service().fs();
+        let current_soul = fs.read_file(&bot_dir.join("SOUL.md")).await.unwrap_or_default();
+        let current_identity = fs
+            .read_file(&bot_dir.join("IDENTITY.md"))
+            .await
+            .unwrap_or_default();
+
+        let soul_content = generate_soul_content(&config.personality, &config.name);
+        let identity_content = merge_identity_content(&current_identity, &config.model_config);
+        let diff = |current: &str, proposed: &str| {
+            (current != proposed).then(|| compute_line_diff(current, proposed))
+        };
+
+        let mut fields = Vec::new();
+        let mut update = UpdateBotRequest::default();
+        let mut change = |field: &str, current: String, proposed: String| {
+            let changed = current != proposed;
+            if changed {
+                fields.push(FieldChange {
+                    field: field.to_string(),
+                    current,
+                    proposed,
+                });
+            }
+            changed
+        };
+        if change("name", bot.name.clone(), config.name.clone()) {
+            update.name = Some(config.name.clone());
+        }
+        if change("description", bot.description.clone(), config.description.clone()) {
+            update.description = Some(config.description.clone());
+        }
+        // An unrecognized category from the LLM keeps the current one.
+        let category = config.category.parse().unwrap_or_else(|_| bot.category.clone());
+        if change("category", bot.category.to_string(), category.to_string()) {
+            update.category = Some(category);
+        }
+        if change("tags", bot.tags.join(", "), config.tags.join(", ")) {
+            update.tags = Some(config.tags.clone());
+        }
+
+        Ok(ReconfigurePreview {
+            bot_id: bot.id.clone(),
+            slug: bot.slug.clone(),
+            fields,
+            soul_diff: diff(&current_soul, &soul_content),
+            identity_diff: diff(&current_identity, &identity_content),
+            soul_content,
+            identity_content,
+            update,
+        })
+    }
+
+    /// Write the changes listed in a preview to the existing bot.
+    ///
+    /// A changed soul is saved as a new version, so it can be rolled back
+    /// with `bnity soul rollback`.
+    pub async fn apply_reconfigure<B, S, F, H>(
+        bot_service: &BotService<B, S, F, H>,
+        preview: &ReconfigurePreview,
+    ) -> Result<Bot, BuilderError>
+    where
+        B: BotRepository,
+        S: SoulRepository,
+        F: FileSystem,
+        H: ContentHasher,
+    {
+        let bot_dir = bot_service.bot_dir(&preview.slug);
+
+        if preview.soul_diff.is_some() {
+            bot_service
+                .soul_service()
+                .update_soul(
+                    &preview.bot_id,
+                    preview.soul_content.clone(),
+                    Some("Reconfigured via builder".to_string()),
+                    &bot_dir.join("SOUL.md"),
+                )
+                .await
+                .map_err(|e| BuilderError::AssemblyError(e.to_string()))?;
+        }
+        if preview.identity_diff.is_some() {
+            bot_service
+                .soul_service()
+                .write_identity(&preview.identity_content, &bot_dir.join("IDENTITY.md"))
+                .await
+                .map_err(|e| BuilderError::AssemblyError(e.to_string()))?;
+        }
+
+        bot_service
+            .update_bot(&preview.bot_id, preview.update.clone())
+            .await
+            .map_err(|e| BuilderError::AssemblyError(e.to_string()))
+    }
+
+    /// Steps 1-4: create the bot, then overwrite its default files.
+    async fn create_with_files<B, S, F, H>(
+        bot_service: &BotService<B, S, F, H>,
+        create_req: CreateBotRequest,
+        soul_content: &str,
+        identity_content: &str,
+        user_content: &str,
+    ) -> Result<(Bot, AssemblyPaths), BuilderError>
+    where
+        B: BotRepository,
+        S: SoulRepository,
+        F: FileSystem,
+        H: ContentHasher,
+    {
+        // Step 1: Create bot via BotService::create_bot.
+        // This creates the DB record AND writes default SOUL.md, IDENTITY.md,
+        // USER.md to disk.
         let bot = bot_service
             .create_bot(create_req)
             .await
@@ -103,60 +349,39 @@ impl BotAssembler {
 
         let bot_dir = bot_service.bot_dir(&bot.slug);
 
-        // Step 2: Generate builder SOUL.md content and overwrite default.
-        let soul_content = generate_soul_content(&config.personality, &config.name);
+        // Step 2: Overwrite default SOUL.md (versioned via the soul repository).
         let soul_path = bot_dir.join("SOUL.md");
         bot_service
             .soul_service()
-            .write_and_save_soul(&bot.id, &soul_content, &soul_path)
+            .write_and_save_soul(&bot.id, soul_content, &soul_path)
             .await
             .map_err(|e| BuilderError::AssemblyError(e.to_string()))?;
 
-        // Step 3: Generate builder IDENTITY.md and overwrite default.
-        let identity_content = generate_identity_content(&config.model_config);
+        // Step 3: Overwrite default IDENTITY.md.
         let identity_path = bot_dir.join("IDENTITY.md");
         bot_service
             .soul_service()
-            .write_identity(&identity_content, &identity_path)
+            .write_identity(identity_content, &identity_path)
             .await
             .map_err(|e| BuilderError::AssemblyError(e.to_string()))?;
 
-        // Step 4: Generate builder USER.md and overwrite default.
-        let user_content = generate_user_content(&config.name, &config.description);
+        // Step 4: Overwrite default USER.md.
         let user_path = bot_dir.join("USER.md");
         bot_service
             .soul_service()
-            .write_user(&user_content, &user_path)
+            .write_user(user_content, &user_path)
             .await
             .map_err(|e| BuilderError::AssemblyError(e.to_string()))?;
 
-        // Step 5: Attach skills if any were requested.
-        let skills_attached = if !config.skills.is_empty() {
-            // Build SkillBuildResults from SkillRequests (lightweight -- no LLM call here,
-            // these are already-generated manifests from the builder flow).
-            let skill_results: Vec<SkillBuildResult> = config
-                .skills
-                .iter()
-                .map(|sr| skill_request_to_build_result(sr))
-                .collect();
-            Self::attach_skills(&bot.slug, &skill_results, &bot_dir)?
-        } else {
-            Vec::new()
-        };
-
-        Ok(AssemblyResult {
+        Ok((
             bot,
-            soul_content,
-            identity_content,
-            user_content,
-            skills_attached,
-            file_paths: AssemblyPaths {
+            AssemblyPaths {
                 bot_dir,
                 soul_path,
                 identity_path,
                 user_path,
             },
-        })
+        ))
     }
 
     /// Attach skills to a bot by writing SKILL.md files and updating skills.toml.
@@ -375,6 +600,8 @@ fn skill_request_to_build_result(
             parents: None,
             secrets: None,
             categories: None,
+            cacheable: None,
+            manifest_version: None,
         }),
         allowed_tools: None,
     };
@@ -453,6 +680,47 @@ max_tokens: {max_tokens}
     )
 }
 
+/// Apply the builder model configuration to existing IDENTITY.md content.
+///
+/// Replaces `model`, `temperature`, and `max_tokens` in place and leaves
+/// every other key and the body untouched. Content without frontmatter is
+/// replaced by [`generate_identity_content`].
+pub fn merge_identity_content(current: &str, model_config: &ModelConfig) -> String {
+    let lines: Vec<&str> = current.lines().collect();
+    let end = (lines.first().map(|l| l.trim()) == Some("---"))
+        .then(|| lines.iter().skip(1).position(|l| l.trim() == "---"))
+        .flatten()
+        .map(|p| p + 1);
+    let Some(end) = end else {
+        return generate_identity_content(model_config);
+    };
+
+    let mut updates = vec![
+        ("model", model_config.model.clone()),
+        ("temperature", model_config.temperature.to_string()),
+        ("max_tokens", model_config.max_tokens.to_string()),
+    ];
+    let mut merged: Vec<String> = vec![lines[0].to_string()];
+    for line in &lines[1..end] {
+        let key = line.split_once(':').map(|(k, _)| k.trim());
+        match updates.iter().position(|(k, _)| Some(*k) == key) {
+            Some(i) => {
+                let (key, value) = updates.remove(i);
+                merged.push(format!("{key}: {value}"));
+            }
+            None => merged.push(line.to_string()),
+        }
+    }
+    merged.extend(updates.into_iter().map(|(key, value)| format!("{key}: {value}")));
+    merged.extend(lines[end..].iter().map(|l| l.to_string()));
+
+    let mut content = merged.join("\n");
+    if current.ends_with('\n') {
+        content.push('\n');
+    }
+    content
+}
+
 /// Generate USER.md seeded from builder context.
 pub fn generate_user_content(name: &str, description: &str) -> String {
     format!(
@@ -569,6 +837,26 @@ mod tests {
         assert!(content.contains("\n---\n"));
     }
 
+    #[test]
+    fn test_merge_identity_content_keeps_other_keys() {
+        let current = "---\ndisplay_name: CodeBot\nmodel: old-model\ntemperature: 0.9\nguard_untrusted_content: true\n---\n# CodeBot\n";
+        let merged = merge_identity_content(current, &test_model_config());
+
+        assert!(merged.contains("display_name: CodeBot"));
+        assert!(merged.contains("guard_untrusted_content: true"));
+        assert!(merged.contains("model: claude-sonnet-4-20250514"));
+        assert!(!merged.contains("old-model"));
+        assert!(merged.contains("temperature: 0.2"));
+        // Missing key appended inside the frontmatter
+        assert!(merged.contains("max_tokens: 4096\n---\n# CodeBot\n"));
+    }
+
+    #[test]
+    fn test_merge_identity_content_without_frontmatter() {
+        let merged = merge_identity_content("no frontmatter", &test_model_config());
+        assert_eq!(merged, generate_identity_content(&test_model_config()));
+    }
+
     // --- generate_user_content tests ---
 
     #[test]
@@ -611,6 +899,7 @@ mod tests {
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
             last_active_at: None,
+            deleted_at: None,
         };
 
         AssemblyResult {
@@ -688,6 +977,8 @@ mod tests {
                     parents: None,
                     secrets: None,
                     categories: None,
+                    cacheable: None,
+                    manifest_version: None,
                 }),
                 allowed_tools: None,
             },
@@ -735,6 +1026,8 @@ mod tests {
                     parents: None,
                     secrets: None,
                     categories: None,
+                    cacheable: None,
+                    manifest_version: None,
                 }),
                 allowed_tools: None,
             },
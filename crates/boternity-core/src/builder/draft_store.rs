@@ -12,7 +12,12 @@ use std::future::Future;
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
+use boternity_types::builder::BuilderState;
 use boternity_types::error::RepositoryError;
+use boternity_types::schema::{BUILDER_STATE_SCHEMA_VERSION, SchemaError, from_str_at_version};
+
+/// Schema version written with new drafts.
+pub const DRAFT_SCHEMA_VERSION: u32 = BUILDER_STATE_SCHEMA_VERSION;
 
 // ---------------------------------------------------------------------------
 // Domain types
@@ -20,10 +25,9 @@ use boternity_types::error::RepositoryError;
 
 /// A saved builder draft containing the full serialized state.
 ///
-/// `state_json` holds the serialized `BuilderState`. The `schema_version`
-/// field enables forward-compatible deserialization: if the `BuilderState`
-/// shape changes in a future release, migration logic can inspect the
-/// version and transform the JSON before deserializing.
+/// `state_json` holds the serialized `BuilderState`. [`BuilderDraft::state`]
+/// migrates it forward from `schema_version` before deserializing, so drafts
+/// saved by older releases still resume.
 #[derive(Debug, Clone)]
 pub struct BuilderDraft {
     /// Session ID (matches `BuilderState.session_id`).
@@ -38,6 +42,31 @@ pub struct BuilderDraft {
     pub updated_at: DateTime<Utc>,
 }
 
+impl BuilderDraft {
+    /// Snapshot a builder state as a draft updated now.
+    ///
+    /// The store keeps the original `created_at` when the session already
+    /// has a draft.
+    pub fn from_state(state: &BuilderState) -> Result<Self, serde_json::Error> {
+        let now = Utc::now();
+        Ok(Self {
+            session_id: state.session_id,
+            state_json: serde_json::to_string(state)?,
+            schema_version: DRAFT_SCHEMA_VERSION,
+            created_at: now,
+            updated_at: now,
+        })
+    }
+
+    /// Deserialize the saved builder state, migrating older schema versions.
+    ///
+    /// Fails with [`SchemaError::UnsupportedVersion`] for drafts saved by a
+    /// newer release.
+    pub fn state(&self) -> Result<BuilderState, SchemaError> {
+        from_str_at_version(&self.state_json, self.schema_version)
+    }
+}
+
 /// Lightweight summary of a builder draft for listing.
 ///
 /// Avoids deserializing the full `state_json` when only metadata is needed
@@ -50,6 +79,10 @@ pub struct BuilderDraftSummary {
     pub initial_description: String,
     /// Current phase name (e.g., "personality", "model").
     pub phase: String,
+    /// Number of questions answered so far.
+    pub question_count: usize,
+    /// When the draft was first created.
+    pub created_at: DateTime<Utc>,
     /// When the draft was last updated.
     pub updated_at: DateTime<Utc>,
 }
@@ -64,6 +97,8 @@ pub struct BuilderDraftSummary {
 /// all async traits in this project.
 pub trait BuilderDraftStore: Send + Sync {
     /// Save or update a builder draft (upsert on session_id).
+    ///
+    /// Updating an existing draft keeps its original `created_at`.
     fn save_draft(
         &self,
         draft: BuilderDraft,
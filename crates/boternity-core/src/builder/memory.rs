@@ -79,3 +79,28 @@ pub trait BuilderMemoryStore: Send + Sync {
         limit: usize,
     ) -> impl Future<Output = Result<Vec<BuilderMemoryEntry>, RepositoryError>> + Send;
 }
+
+/// Recall past sessions to inform a new builder session.
+///
+/// Sessions with the same purpose category come first; the remaining slots
+/// are filled with the most recent sessions of any category so preferences
+/// like the usual model or naming style carry over between bot kinds.
+pub async fn recall_relevant<S: BuilderMemoryStore>(
+    store: &S,
+    category: &PurposeCategory,
+    limit: usize,
+) -> Result<Vec<BuilderMemoryEntry>, RepositoryError> {
+    let mut entries = store.recall_by_category(category, limit).await?;
+    if entries.len() < limit {
+        for entry in store.recall_recent(limit).await? {
+            if entries.len() == limit {
+                break;
+            }
+            if !entries.iter().any(|e| e.id == entry.id) {
+                entries.push(entry);
+            }
+        }
+    }
+    Ok(entries)
+}
+
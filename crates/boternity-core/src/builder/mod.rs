@@ -12,4 +12,5 @@ pub mod draft_store;
 pub mod memory;
 pub mod prompt;
 pub mod skill_builder;
+pub mod spec;
 pub mod state;
@@ -101,6 +101,8 @@ pub fn format_phase_label(phase: &BuilderPhase) -> &'static str {
 /// - `<accumulated_context>`: State from prior exchanges in this session
 /// - `<current_config>`: Non-None fields from the partial config
 /// - `<past_sessions>`: Recalled builder memories (omitted when empty)
+/// - `<user_preferences>`: Models, tones, and bot names the user keeps
+///   choosing across those memories (omitted when empty)
 ///
 /// The `recalled_memories` parameter enables cross-session continuity:
 /// Forge can reference past choices to suggest similar options for the
@@ -110,7 +112,7 @@ pub fn build_forge_system_prompt(
     mode: &BuilderMode,
     recalled_memories: &[RecalledBuilderMemory],
 ) -> String {
-    let mut sections = Vec::with_capacity(6);
+    let mut sections = Vec::with_capacity(7);
 
     // Forge identity section
     sections.push(format!(
@@ -147,6 +149,14 @@ pub fn build_forge_system_prompt(
         ));
     }
 
+    let preferences = build_preferences(recalled_memories);
+    if !preferences.is_empty() {
+        sections.push(format!(
+            "<user_preferences>\n{}\n</user_preferences>",
+            preferences
+        ));
+    }
+
     sections.join("\n\n")
 }
 
@@ -311,6 +321,56 @@ fn build_past_sessions(memories: &[RecalledBuilderMemory]) -> String {
     lines.join("\n")
 }
 
+/// Summarize recurring choices across past sessions.
+///
+/// Memories are ordered most recent first, so ties go to the latest choice.
+fn build_preferences(memories: &[RecalledBuilderMemory]) -> String {
+    let mut lines = Vec::new();
+    let total = memories.len();
+
+    if let Some((model, count)) =
+        most_common(memories.iter().filter_map(|m| m.chosen_model.as_deref()))
+    {
+        lines.push(format!(
+            "Preferred model: {model} (chosen in {count} of {total} past builds). Suggest it as the default model option."
+        ));
+    }
+    if let Some((tone, count)) =
+        most_common(memories.iter().filter_map(|m| m.chosen_tone.as_deref()))
+    {
+        lines.push(format!(
+            "Preferred tone: {tone} (chosen in {count} of {total} past builds)."
+        ));
+    }
+
+    let names: Vec<&str> = memories.iter().filter_map(|m| m.bot_slug.as_deref()).collect();
+    if !names.is_empty() {
+        lines.push(format!(
+            "Existing bot names: {}. Suggest names in a consistent style and avoid reusing these.",
+            names.join(", ")
+        ));
+    }
+
+    lines.join("\n")
+}
+
+/// The most frequent value and its count; the first seen wins ties.
+fn most_common<'a>(values: impl Iterator<Item = &'a str>) -> Option<(&'a str, usize)> {
+    let mut counts: Vec<(&str, usize)> = Vec::new();
+    for value in values {
+        match counts.iter_mut().find(|(v, _)| *v == value) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((value, 1)),
+        }
+    }
+    counts
+        .into_iter()
+        .fold(None, |best: Option<(&str, usize)>, (value, count)| match best {
+            Some((_, best_count)) if best_count >= count => best,
+            _ => Some((value, count)),
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -466,4 +526,35 @@ mod tests {
         assert!(prompt.contains("new skill creation"));
         assert!(prompt.contains("skill name, description, type"));
     }
+
+    #[test]
+    fn test_recalled_memories_surface_preferences() {
+        let state = test_state();
+        let memory = |slug: &str, model: &str, tone: &str| RecalledBuilderMemory {
+            initial_description: "A bot".to_string(),
+            chosen_tone: Some(tone.to_string()),
+            chosen_model: Some(model.to_string()),
+            chosen_skills: vec![],
+            bot_slug: Some(slug.to_string()),
+        };
+        let memories = vec![
+            memory("nova-writer", "claude-opus-4", "casual"),
+            memory("nova-coder", "claude-haiku-3", "formal"),
+            memory("nova-helper", "claude-haiku-3", "formal"),
+        ];
+
+        let prompt = build_forge_system_prompt(&state, &BuilderMode::NewBot, &memories);
+
+        assert!(prompt.contains("<user_preferences>"));
+        assert!(prompt.contains("Preferred model: claude-haiku-3 (chosen in 2 of 3 past builds)"));
+        assert!(prompt.contains("Preferred tone: formal"));
+        assert!(prompt.contains("Existing bot names: nova-writer, nova-coder, nova-helper"));
+    }
+
+    #[test]
+    fn test_most_common_prefers_first_on_tie() {
+        assert_eq!(most_common(["a", "b", "b", "a"].into_iter()), Some(("a", 2)));
+        assert_eq!(most_common(["a", "b", "b"].into_iter()), Some(("b", 2)));
+        assert_eq!(most_common(std::iter::empty()), None);
+    }
 }
@@ -8,7 +8,7 @@
 use serde::{Deserialize, Serialize};
 
 use boternity_types::llm::{
-    CompletionRequest, Message, MessageRole, OutputConfig, OutputFormat, OutputJsonSchema,
+    CompletionRequest, Message, OutputConfig, OutputFormat, OutputJsonSchema,
 };
 
 use crate::llm::box_provider::BoxLlmProvider;
@@ -188,18 +188,13 @@ Output as a JSON object with exactly these fields:
             "additionalProperties": false
         });
 
-        let completion_request = CompletionRequest {
-            model: String::new(), // provider fills model
-            messages: vec![Message {
-                role: MessageRole::User,
-                content: user_message,
-            }],
-            system: Some(system_prompt),
-            max_tokens: 4096,
-            temperature: Some(0.4),
-            stream: false,
-            stop_sequences: None,
-            output_config: Some(OutputConfig {
+        // The provider fills in its own model
+        let completion_request = CompletionRequest::builder(String::new())
+            .system(system_prompt)
+            .message(Message::from(user_message))
+            .max_tokens(4096)
+            .temperature(0.4)
+            .output_config(OutputConfig {
                 format: OutputFormat {
                     type_field: "json_schema".to_owned(),
                     json_schema: OutputJsonSchema {
@@ -208,8 +203,9 @@ Output as a JSON object with exactly these fields:
                         strict: Some(true),
                     },
                 },
-            }),
-        };
+            })
+            .build()
+            .map_err(|e| BuilderError::LlmError(e.to_string()))?;
 
         let response = provider
             .complete(&completion_request)
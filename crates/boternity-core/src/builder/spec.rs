@@ -0,0 +1,470 @@
+//! Declarative bot specs for non-interactive creation.
+//!
+//! `bnity create bot --spec luna.toml` builds a bot from a TOML file instead
+//! of the Forge conversation, for CI and automation:
+//!
+//! ```toml
+//! name = "Luna"
+//! description = "A curious research assistant"
+//! category = "research"
+//! tags = ["science"]
+//!
+//! [soul]
+//! tone = "curious"
+//! traits = ["thorough", "candid"]
+//! purpose = "Help the user research scientific questions."
+//! boundaries = "No medical advice."   # optional
+//! # content = "..."                    # full SOUL.md instead of tone/purpose
+//!
+//! [identity]                           # optional, defaults as in IDENTITY.md
+//! model = "claude-sonnet-4-20250514"
+//! temperature = 0.3
+//! guard_untrusted_content = true
+//!
+//! [secrets]                            # bot-scoped secrets and their source
+//! GITHUB_TOKEN = "env:GITHUB_TOKEN"
+//! SEARCH_API_KEY = "secret:SEARCH_API_KEY"
+//! ```
+//!
+//! [`BotSpec::validate`] checks the whole spec and reports every problem at
+//! once; only a [`ValidatedBotSpec`] can be assembled, so nothing is written
+//! for an invalid spec.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::Deserialize;
+
+use boternity_types::bot::{BotCategory, slugify};
+use boternity_types::builder::PersonalityConfig;
+use boternity_types::identity::Identity;
+
+use super::assembler::{generate_soul_content, generate_user_content};
+
+/// A bot spec as read from disk. Required fields are optional here so that
+/// validation can report all missing fields together.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BotSpec {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub category: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub soul: Option<SoulSpec>,
+    #[serde(default)]
+    pub identity: IdentitySpec,
+    /// Bot-scoped secret key -> reference (`env:VAR` or `secret:KEY`).
+    #[serde(default)]
+    pub secrets: BTreeMap<String, String>,
+}
+
+/// The `[soul]` table: either full `content` or the builder personality fields.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SoulSpec {
+    pub content: Option<String>,
+    pub tone: Option<String>,
+    #[serde(default)]
+    pub traits: Vec<String>,
+    pub purpose: Option<String>,
+    pub boundaries: Option<String>,
+}
+
+/// The `[identity]` table; mirrors the IDENTITY.md frontmatter keys.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct IdentitySpec {
+    pub model: Option<String>,
+    pub provider: Option<String>,
+    pub temperature: Option<f64>,
+    pub max_tokens: Option<i32>,
+    #[serde(default)]
+    pub preferred_providers: Vec<String>,
+    #[serde(default)]
+    pub provider_exclusive: bool,
+    pub max_request_tokens: Option<u32>,
+    #[serde(default)]
+    pub guard_untrusted_content: bool,
+    pub memory_extraction_interval: Option<u32>,
+}
+
+/// Where the value of a spec secret comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SecretRef {
+    /// An environment variable of the creating process.
+    Env(String),
+    /// An existing global secret.
+    Secret(String),
+}
+
+impl SecretRef {
+    /// Parse `env:NAME` or `secret:NAME`.
+    pub fn parse(reference: &str) -> Option<Self> {
+        let (kind, name) = reference.split_once(':')?;
+        let name = name.trim();
+        if name.is_empty() {
+            return None;
+        }
+        match kind.trim() {
+            "env" => Some(Self::Env(name.to_string())),
+            "secret" => Some(Self::Secret(name.to_string())),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for SecretRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Env(name) => write!(f, "env:{name}"),
+            Self::Secret(name) => write!(f, "secret:{name}"),
+        }
+    }
+}
+
+/// A problem found while validating a spec.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpecIssue {
+    /// Dotted path of the offending field (e.g. `soul.purpose`).
+    pub field: String,
+    pub message: String,
+}
+
+impl fmt::Display for SpecIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+/// Why a spec could not be used.
+#[derive(Debug, thiserror::Error)]
+pub enum SpecError {
+    #[error("invalid spec file: {0}")]
+    Parse(String),
+
+    #[error("invalid spec:\n{}", format_issues(.0))]
+    Invalid(Vec<SpecIssue>),
+}
+
+fn format_issues(issues: &[SpecIssue]) -> String {
+    issues
+        .iter()
+        .map(|i| format!("  - {i}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// A fully validated spec with the bot files rendered.
+#[derive(Debug, Clone)]
+pub struct ValidatedBotSpec {
+    pub name: String,
+    pub description: String,
+    pub category: BotCategory,
+    pub tags: Vec<String>,
+    pub soul_content: String,
+    pub identity_content: String,
+    pub user_content: String,
+    pub secrets: Vec<(String, SecretRef)>,
+}
+
+impl BotSpec {
+    /// Parse a TOML spec.
+    pub fn parse(content: &str) -> Result<Self, SpecError> {
+        toml::from_str(content).map_err(|e| SpecError::Parse(e.to_string()))
+    }
+
+    /// Check every field and render the bot files.
+    ///
+    /// Returns all issues found, not just the first.
+    pub fn validate(&self) -> Result<ValidatedBotSpec, SpecError> {
+        let mut issues = Vec::new();
+        let mut issue = |field: &str, message: &str| {
+            issues.push(SpecIssue {
+                field: field.to_string(),
+                message: message.to_string(),
+            })
+        };
+
+        let name = required(&self.name);
+        match name {
+            None => issue("name", "required field is missing"),
+            Some(name) if slugify(name).is_empty() => {
+                issue("name", "must contain at least one alphanumeric character")
+            }
+            Some(_) => {}
+        }
+        let description = required(&self.description);
+        if description.is_none() {
+            issue("description", "required field is missing");
+        }
+        let category = match required(&self.category) {
+            None => {
+                issue("category", "required field is missing");
+                None
+            }
+            Some(raw) => match raw.parse::<BotCategory>() {
+                Ok(category) => Some(category),
+                Err(e) => {
+                    issue("category", &e);
+                    None
+                }
+            },
+        };
+
+        let soul_body = match &self.soul {
+            None => {
+                issue("soul", "required table is missing");
+                None
+            }
+            Some(soul) => match required(&soul.content) {
+                Some(content) => Some(SoulBody::Content(content.to_string())),
+                None => {
+                    let tone = required(&soul.tone);
+                    let purpose = required(&soul.purpose);
+                    if tone.is_none() {
+                        issue("soul.tone", "required unless soul.content is set");
+                    }
+                    if purpose.is_none() {
+                        issue("soul.purpose", "required unless soul.content is set");
+                    }
+                    tone.zip(purpose).map(|(tone, purpose)| {
+                        SoulBody::Personality(PersonalityConfig {
+                            tone: tone.to_string(),
+                            traits: soul.traits.clone(),
+                            purpose: purpose.to_string(),
+                            boundaries: required(&soul.boundaries).map(str::to_string),
+                        })
+                    })
+                }
+            },
+        };
+
+        let identity = &self.identity;
+        if let Some(t) = identity.temperature
+            && !(0.0..=2.0).contains(&t)
+        {
+            issue("identity.temperature", "must be between 0.0 and 2.0");
+        }
+        if identity.max_tokens.is_some_and(|m| m <= 0) {
+            issue("identity.max_tokens", "must be positive");
+        }
+        if identity.max_request_tokens == Some(0) {
+            issue("identity.max_request_tokens", "must be positive");
+        }
+        if identity.provider_exclusive && identity.preferred_providers.is_empty() {
+            issue(
+                "identity.provider_exclusive",
+                "requires identity.preferred_providers",
+            );
+        }
+
+        let mut secrets = Vec::new();
+        for (key, reference) in &self.secrets {
+            let field = format!("secrets.{key}");
+            if key.trim().is_empty() {
+                issue(&field, "secret key cannot be empty");
+                continue;
+            }
+            match SecretRef::parse(reference) {
+                Some(secret_ref) => secrets.push((key.clone(), secret_ref)),
+                None => issue(
+                    &field,
+                    &format!("'{reference}' is not 'env:NAME' or 'secret:NAME'"),
+                ),
+            }
+        }
+
+        let (Some(name), Some(description), Some(category), Some(soul_body)) =
+            (name, description, category, soul_body)
+        else {
+            return Err(SpecError::Invalid(issues));
+        };
+        if !issues.is_empty() {
+            return Err(SpecError::Invalid(issues));
+        }
+
+        let soul_content = match soul_body {
+            SoulBody::Content(content) => content,
+            SoulBody::Personality(personality) => generate_soul_content(&personality, name),
+        };
+
+        let identity_content = render_identity(name, &category, identity);
+        Ok(ValidatedBotSpec {
+            name: name.to_string(),
+            description: description.to_string(),
+            category,
+            tags: self.tags.clone(),
+            soul_content,
+            identity_content,
+            user_content: generate_user_content(name, description),
+            secrets,
+        })
+    }
+}
+
+enum SoulBody {
+    Content(String),
+    Personality(PersonalityConfig),
+}
+
+/// A trimmed, non-empty value.
+fn required(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|v| !v.is_empty())
+}
+
+/// Render IDENTITY.md with full frontmatter (unset keys use the defaults).
+fn render_identity(name: &str, category: &BotCategory, identity: &IdentitySpec) -> String {
+    let mut lines = vec![
+        "---".to_string(),
+        format!("display_name: {name}"),
+        format!("category: {category}"),
+        format!(
+            "model: {}",
+            identity.model.as_deref().unwrap_or(Identity::DEFAULT_MODEL)
+        ),
+        format!(
+            "provider: {}",
+            identity.provider.as_deref().unwrap_or(Identity::DEFAULT_PROVIDER)
+        ),
+        format!(
+            "temperature: {}",
+            identity.temperature.unwrap_or(Identity::DEFAULT_TEMPERATURE)
+        ),
+        format!(
+            "max_tokens: {}",
+            identity.max_tokens.unwrap_or(Identity::DEFAULT_MAX_TOKENS)
+        ),
+    ];
+    if !identity.preferred_providers.is_empty() {
+        lines.push(format!(
+            "preferred_providers: {}",
+            identity.preferred_providers.join(", ")
+        ));
+        lines.push(format!("provider_exclusive: {}", identity.provider_exclusive));
+    }
+    if let Some(max) = identity.max_request_tokens {
+        lines.push(format!("max_request_tokens: {max}"));
+    }
+    if identity.guard_untrusted_content {
+        lines.push("guard_untrusted_content: true".to_string());
+    }
+    if let Some(interval) = identity.memory_extraction_interval {
+        lines.push(format!("memory_extraction_interval: {interval}"));
+    }
+    lines.push("---".to_string());
+    lines.push(format!("# {name} - Identity Configuration"));
+    lines.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COMPLETE_SPEC: &str = r#"
+name = "Luna"
+description = "A curious research assistant"
+category = "research"
+tags = ["science"]
+
+[soul]
+tone = "curious"
+traits = ["thorough", "candid"]
+purpose = "Help the user research scientific questions."
+
+[identity]
+model = "claude-sonnet-4-20250514"
+temperature = 0.3
+preferred_providers = ["openrouter"]
+guard_untrusted_content = true
+
+[secrets]
+GITHUB_TOKEN = "env:GITHUB_TOKEN"
+SEARCH_API_KEY = "secret:SEARCH_API_KEY"
+"#;
+
+    #[test]
+    fn test_complete_spec_validates_and_renders_files() {
+        let spec = BotSpec::parse(COMPLETE_SPEC).unwrap().validate().unwrap();
+
+        assert_eq!(spec.name, "Luna");
+        assert_eq!(spec.category, BotCategory::Research);
+        assert!(spec.soul_content.contains("curious personality"));
+        assert!(spec.soul_content.contains("Help the user research scientific questions."));
+        assert!(spec.identity_content.contains("display_name: Luna"));
+        assert!(spec.identity_content.contains("temperature: 0.3"));
+        assert!(spec.identity_content.contains("preferred_providers: openrouter"));
+        assert!(spec.identity_content.contains("guard_untrusted_content: true"));
+        assert_eq!(
+            spec.secrets,
+            vec![
+                ("GITHUB_TOKEN".to_string(), SecretRef::Env("GITHUB_TOKEN".to_string())),
+                (
+                    "SEARCH_API_KEY".to_string(),
+                    SecretRef::Secret("SEARCH_API_KEY".to_string())
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_spec_missing_required_fields_reports_all() {
+        let spec = BotSpec::parse(
+            r#"
+name = "Luna"
+
+[soul]
+traits = ["kind"]
+
+[secrets]
+TOKEN = "vault:TOKEN"
+"#,
+        )
+        .unwrap();
+
+        let Err(SpecError::Invalid(issues)) = spec.validate() else {
+            panic!("expected validation failure");
+        };
+        let fields: Vec<&str> = issues.iter().map(|i| i.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            vec!["description", "category", "soul.tone", "soul.purpose", "secrets.TOKEN"]
+        );
+    }
+
+    #[test]
+    fn test_spec_soul_content_and_bad_values() {
+        let spec = BotSpec::parse(
+            r#"
+name = "Luna"
+description = "d"
+category = "wizard"
+
+[soul]
+content = "# Luna"
+
+[identity]
+temperature = 3.5
+provider_exclusive = true
+"#,
+        )
+        .unwrap();
+
+        let Err(SpecError::Invalid(issues)) = spec.validate() else {
+            panic!("expected validation failure");
+        };
+        let fields: Vec<&str> = issues.iter().map(|i| i.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            vec!["category", "identity.temperature", "identity.provider_exclusive"]
+        );
+    }
+
+    #[test]
+    fn test_spec_rejects_unknown_keys() {
+        assert!(matches!(
+            BotSpec::parse("name = \"Luna\"\nnmae = \"typo\""),
+            Err(SpecError::Parse(_))
+        ));
+    }
+}
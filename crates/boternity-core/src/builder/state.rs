@@ -9,6 +9,7 @@
 use boternity_types::builder::{
     BuilderExchange, BuilderPhase, BuilderState, PartialBuilderConfig,
 };
+use boternity_types::schema::BUILDER_STATE_SCHEMA_VERSION;
 use uuid::Uuid;
 
 /// Create a new `BuilderState` for a fresh builder session.
@@ -23,6 +24,8 @@ pub fn new_builder_state(session_id: Uuid, initial_description: String) -> Build
         conversation: Vec::new(),
         config: PartialBuilderConfig::default(),
         phase_history: Vec::new(),
+        pending_turn: None,
+        schema_version: BUILDER_STATE_SCHEMA_VERSION,
     }
 }
 
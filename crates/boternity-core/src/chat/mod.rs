@@ -3,8 +3,10 @@
 //! This module defines the `ChatRepository` trait that the infrastructure
 //! layer implements for session, message, and context summary CRUD,
 //! plus the `ChatService` for session lifecycle orchestration and
-//! `SessionManager` for turn tracking.
+//! `SessionManager` for turn tracking. `quota` enforces per-bot LLM usage
+//! limits.
 
+pub mod quota;
 pub mod repository;
 pub mod service;
 pub mod session;
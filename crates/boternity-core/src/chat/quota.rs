@@ -0,0 +1,123 @@
+//! Per-bot LLM usage quotas.
+//!
+//! Usage (requests and tokens) is accumulated per bot in fixed windows of
+//! `QuotaConfig::window_hours`, aligned to the Unix epoch so every process
+//! agrees on the current window without coordination. `ChatService` records
+//! usage after each turn and checks it with [`check_usage`] before a
+//! completion is dispatched.
+
+use boternity_types::config::{QuotaConfig, UsageLimits};
+use boternity_types::error::QuotaError;
+use boternity_types::quota::{BotUsage, QuotaKind};
+use chrono::{DateTime, Duration, Utc};
+
+/// Start and end of the quota window containing `now`.
+pub fn window_bounds(config: &QuotaConfig, now: DateTime<Utc>) -> (DateTime<Utc>, DateTime<Utc>) {
+    let window_secs = i64::from(config.window_hours.max(1)) * 3600;
+    let start_secs = now.timestamp().div_euclid(window_secs) * window_secs;
+    let start = DateTime::from_timestamp(start_secs, 0).unwrap_or(now);
+    (start, start + Duration::seconds(window_secs))
+}
+
+/// Check a bot's usage in the current window against its limits.
+///
+/// A limit is reached once usage is at or above it: the next request would
+/// exceed it. `usage` is `None` when nothing was recorded this window.
+/// Requests are checked before tokens.
+pub fn check_usage(
+    usage: Option<&BotUsage>,
+    limits: &UsageLimits,
+    resets_at: DateTime<Utc>,
+) -> Result<(), QuotaError> {
+    let (requests, tokens) = usage.map_or((0, 0), |u| (u.requests, u.tokens));
+    for (kind, used, limit) in [
+        (QuotaKind::Requests, requests, limits.max_requests),
+        (QuotaKind::Tokens, tokens, limits.max_tokens),
+    ] {
+        if let Some(limit) = limit
+            && used >= limit
+        {
+            return Err(QuotaError::Exceeded {
+                kind,
+                used,
+                limit,
+                resets_at,
+            });
+        }
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn at(rfc3339: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(rfc3339)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_window_bounds_align_to_epoch() {
+        let config = QuotaConfig::default();
+        let (start, end) = window_bounds(&config, at("2026-03-01T17:45:12Z"));
+        assert_eq!(start, at("2026-03-01T00:00:00Z"));
+        assert_eq!(end, at("2026-03-02T00:00:00Z"));
+
+        let hourly = QuotaConfig {
+            window_hours: 6,
+            ..Default::default()
+        };
+        let (start, end) = window_bounds(&hourly, at("2026-03-01T17:45:12Z"));
+        assert_eq!(start, at("2026-03-01T12:00:00Z"));
+        assert_eq!(end, at("2026-03-01T18:00:00Z"));
+    }
+
+    #[test]
+    fn test_check_usage_blocks_at_limit() {
+        let resets_at = at("2026-03-02T00:00:00Z");
+        let limits = UsageLimits {
+            max_requests: Some(3),
+            max_tokens: Some(1000),
+        };
+        let mut usage = BotUsage {
+            bot_id: Uuid::now_v7(),
+            window_start: at("2026-03-01T00:00:00Z"),
+            requests: 2,
+            tokens: 400,
+        };
+
+        assert!(check_usage(None, &limits, resets_at).is_ok());
+        assert!(check_usage(Some(&usage), &limits, resets_at).is_ok());
+
+        usage.tokens = 1000;
+        let err = check_usage(Some(&usage), &limits, resets_at).unwrap_err();
+        assert!(matches!(
+            err,
+            QuotaError::Exceeded {
+                kind: QuotaKind::Tokens,
+                used: 1000,
+                limit: 1000,
+                ..
+            }
+        ));
+
+        usage.requests = 3;
+        let err = check_usage(Some(&usage), &limits, resets_at).unwrap_err();
+        assert!(matches!(
+            err,
+            QuotaError::Exceeded {
+                kind: QuotaKind::Requests,
+                ..
+            }
+        ));
+
+        assert!(check_usage(Some(&usage), &UsageLimits::default(), resets_at).is_ok());
+    }
+}
@@ -3,8 +3,10 @@
 //! Provides CRUD operations for chat sessions, messages, and context summaries.
 //! Follows the same RPITIT pattern as BotRepository.
 
-use boternity_types::chat::{ChatMessage, ChatSession, ContextSummary};
+use boternity_types::chat::{ChatMessage, ChatSearchHit, ChatSession, ContextSummary};
 use boternity_types::error::RepositoryError;
+use boternity_types::quota::BotUsage;
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
 /// Repository trait for chat session and message persistence.
@@ -102,4 +104,29 @@ pub trait ChatRepository: Send + Sync {
     fn count_messages(
         &self,
     ) -> impl std::future::Future<Output = Result<u64, RepositoryError>> + Send;
+
+    /// Full-text search over session titles and message content, across all bots.
+    ///
+    /// Returns up to `limit` hits ordered by relevance (best first).
+    fn search(
+        &self,
+        query: &str,
+        limit: i64,
+    ) -> impl std::future::Future<Output = Result<Vec<ChatSearchHit>, RepositoryError>> + Send;
+
+    /// Get a bot's LLM usage for the quota window starting at `window_start`.
+    fn get_bot_usage(
+        &self,
+        bot_id: &Uuid,
+        window_start: DateTime<Utc>,
+    ) -> impl std::future::Future<Output = Result<Option<BotUsage>, RepositoryError>> + Send;
+
+    /// Atomically add requests and tokens to a bot's usage for a window.
+    fn add_bot_usage(
+        &self,
+        bot_id: &Uuid,
+        window_start: DateTime<Utc>,
+        requests: u64,
+        tokens: u64,
+    ) -> impl std::future::Future<Output = Result<(), RepositoryError>> + Send;
 }
@@ -9,15 +9,23 @@
 //! since the vector backend is optional and not always available.
 
 use boternity_types::chat::{ChatMessage, ChatSession, MessageRole, SessionStatus};
-use boternity_types::error::RepositoryError;
-use boternity_types::memory::{MemoryEntry, RankedMemory, VectorMemoryEntry};
-use chrono::Utc;
+use boternity_types::config::QuotaConfig;
+use boternity_types::error::{QuotaError, RepositoryError};
+use boternity_types::memory::{MemoryEntry, PendingExtraction, RankedMemory, VectorMemoryEntry};
+use chrono::{DateTime, Utc};
 use tracing::{debug, info, warn};
 use uuid::Uuid;
 
+use crate::agent::context::AgentContext;
+use crate::chat::quota::{check_usage, window_bounds};
 use crate::chat::repository::ChatRepository;
 use crate::memory::box_embedder::BoxEmbedder;
 use crate::memory::box_vector::BoxVectorMemoryStore;
+use crate::llm::box_provider::BoxLlmProvider;
+use crate::memory::extractor::{
+    SessionMemoryExtractor, find_superseded, new_pending_extraction, record_extraction_failure,
+};
+use crate::memory::similarity::SimilarityPolicy;
 use crate::memory::store::MemoryRepository;
 
 /// Default number of memories to retrieve per vector search.
@@ -39,6 +47,8 @@ const DEFAULT_DEDUP_THRESHOLD: f32 = 0.15;
 pub struct ChatService<C: ChatRepository, M: MemoryRepository> {
     chat_repo: C,
     memory_repo: M,
+    similarity_policy: SimilarityPolicy,
+    quota: QuotaConfig,
 }
 
 impl<C: ChatRepository, M: MemoryRepository> ChatService<C, M> {
@@ -47,9 +57,23 @@ impl<C: ChatRepository, M: MemoryRepository> ChatService<C, M> {
         Self {
             chat_repo,
             memory_repo,
+            similarity_policy: SimilarityPolicy::Fixed(DEFAULT_MIN_SIMILARITY),
+            quota: QuotaConfig::default(),
         }
     }
 
+    /// Filter recalled memories with `policy` instead of the fixed default.
+    pub fn with_similarity_policy(mut self, policy: SimilarityPolicy) -> Self {
+        self.similarity_policy = policy;
+        self
+    }
+
+    /// Enforce per-bot usage limits from `quota` (unlimited by default).
+    pub fn with_quota(mut self, quota: QuotaConfig) -> Self {
+        self.quota = quota;
+        self
+    }
+
     /// Access the chat repository.
     pub fn chat_repo(&self) -> &C {
         &self.chat_repo
@@ -78,9 +102,12 @@ impl<C: ChatRepository, M: MemoryRepository> ChatService<C, M> {
             ended_at: None,
             total_input_tokens: 0,
             total_output_tokens: 0,
+            total_cost_usd: 0.0,
             message_count: 0,
             model,
             status: SessionStatus::Active,
+            summary: None,
+            summarized_messages: 0,
         };
 
         self.chat_repo.create_session(&session).await
@@ -211,6 +238,59 @@ impl<C: ChatRepository, M: MemoryRepository> ChatService<C, M> {
             .await
     }
 
+    // --- Rolling summary ---
+
+    /// Persist the session's rolling summary.
+    ///
+    /// `summarized_messages` is how many leading stored messages the summary
+    /// stands in for; [`rehydrate_context`](Self::rehydrate_context) skips
+    /// them and starts from the summary instead.
+    pub async fn update_rolling_summary(
+        &self,
+        session_id: &Uuid,
+        summary: Option<String>,
+        summarized_messages: u32,
+    ) -> Result<(), RepositoryError> {
+        let session = self.chat_repo.get_session(session_id).await?;
+        if let Some(mut session) = session {
+            session.summary = summary;
+            session.summarized_messages = summarized_messages;
+            self.chat_repo.update_session(&session).await?;
+            debug!(session_id = %session_id, summarized_messages, "Rolling summary updated");
+        } else {
+            warn!(session_id = %session_id, "Attempted to update summary for non-existent session");
+        }
+        Ok(())
+    }
+
+    /// Rebuild a resumed session's conversation into `context`.
+    ///
+    /// Restores the rolling summary into the system prompt and replays only
+    /// the user and assistant messages after the summarized prefix, so long
+    /// sessions resume compact. Returns the number of messages replayed.
+    pub async fn rehydrate_context(
+        &self,
+        session_id: &Uuid,
+        context: &mut AgentContext,
+    ) -> Result<usize, RepositoryError> {
+        let Some(session) = self.chat_repo.get_session(session_id).await? else {
+            return Ok(0);
+        };
+        context.set_history_summary(session.summary);
+
+        let messages = self.chat_repo.get_messages(session_id, None, None).await?;
+        let mut replayed = 0;
+        for msg in messages.into_iter().skip(session.summarized_messages as usize) {
+            match msg.role {
+                MessageRole::User => context.add_user_message(msg.content),
+                MessageRole::Assistant => context.add_assistant_message(msg.content),
+                _ => continue,
+            }
+            replayed += 1;
+        }
+        Ok(replayed)
+    }
+
     // --- Memory operations ---
 
     /// Load all memories for a bot (for injection into system prompt).
@@ -223,22 +303,200 @@ impl<C: ChatRepository, M: MemoryRepository> ChatService<C, M> {
         self.memory_repo.get_memories(bot_id, None).await
     }
 
+    /// Save freshly extracted memories, superseding the facts they update.
+    ///
+    /// Each entry is compared against the bot's current memories (and the
+    /// entries saved before it) with [`find_superseded`]; a match is marked
+    /// `superseded_by` the new entry so recall only returns the current fact.
+    /// Returns the number of memories saved.
+    pub async fn save_extracted_memories(
+        &self,
+        bot_id: &Uuid,
+        entries: &[MemoryEntry],
+    ) -> Result<usize, RepositoryError> {
+        let mut current = self.memory_repo.get_memories(bot_id, None).await?;
+        for entry in entries {
+            self.memory_repo.save_memory(entry).await?;
+            if let Some(old_id) = find_superseded(&current, entry) {
+                self.memory_repo.mark_superseded(&old_id, &entry.id).await?;
+                debug!(old_id = %old_id, new_id = %entry.id, fact = %entry.fact, "Memory superseded");
+                current.retain(|m| m.id != old_id);
+            }
+            current.push(entry.clone());
+        }
+        Ok(entries.len())
+    }
+
+    // --- Pending extraction ---
+
+    /// Mark a session as needing memory extraction before attempting it.
+    ///
+    /// Reuses the session's existing marker if one is still pending, so
+    /// periodic and final extraction share a single retry job.
+    pub async fn begin_extraction(
+        &self,
+        bot_id: Uuid,
+        session_id: Uuid,
+    ) -> Result<PendingExtraction, RepositoryError> {
+        let existing = self.memory_repo.get_pending_extractions(&bot_id).await?;
+        if let Some(pending) = existing.into_iter().find(|p| p.session_id == session_id) {
+            return Ok(pending);
+        }
+        let pending = new_pending_extraction(bot_id, session_id);
+        self.memory_repo.save_pending_extraction(&pending).await?;
+        Ok(pending)
+    }
+
+    /// Clear the marker after a successful extraction.
+    pub async fn finish_extraction(
+        &self,
+        pending: &PendingExtraction,
+    ) -> Result<(), RepositoryError> {
+        match self.memory_repo.delete_pending_extraction(&pending.id).await {
+            Ok(()) | Err(RepositoryError::NotFound) => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Record a failed extraction so it is retried later with backoff.
+    pub async fn fail_extraction(
+        &self,
+        pending: &PendingExtraction,
+        error: &str,
+    ) -> Result<(), RepositoryError> {
+        let mut pending = pending.clone();
+        record_extraction_failure(&mut pending, error, Utc::now());
+        self.memory_repo.update_pending_extraction(&pending).await
+    }
+
+    /// Pending extractions due for a retry now, oldest first.
+    pub async fn due_extractions(&self) -> Result<Vec<PendingExtraction>, RepositoryError> {
+        self.memory_repo.get_due_pending_extractions(Utc::now()).await
+    }
+
+    /// Retry an interrupted or failed extraction from the session's saved
+    /// messages.
+    ///
+    /// Facts the session already produced are skipped. Returns the number of
+    /// memories saved, or `None` when the LLM call failed and the job was
+    /// rescheduled.
+    pub async fn retry_extraction(
+        &self,
+        pending: &PendingExtraction,
+        provider: &BoxLlmProvider,
+    ) -> Result<Option<usize>, RepositoryError> {
+        let messages = self.chat_repo.get_messages(&pending.session_id, None, None).await?;
+        let entries = match SessionMemoryExtractor::extract_from_messages(
+            provider,
+            &messages,
+            pending.bot_id,
+            pending.session_id,
+        )
+        .await
+        {
+            Ok(entries) => entries,
+            Err(e) => {
+                warn!(session_id = %pending.session_id, error = %e, "Memory extraction retry failed");
+                self.fail_extraction(pending, &e.to_string()).await?;
+                return Ok(None);
+            }
+        };
+
+        let known: Vec<String> = self
+            .memory_repo
+            .get_memories_by_session(&pending.session_id)
+            .await?
+            .into_iter()
+            .map(|m| m.fact.to_lowercase())
+            .collect();
+        let fresh: Vec<MemoryEntry> = entries
+            .into_iter()
+            .filter(|e| !known.contains(&e.fact.to_lowercase()))
+            .collect();
+
+        let count = self.save_extracted_memories(&pending.bot_id, &fresh).await?;
+        self.finish_extraction(pending).await?;
+        info!(session_id = %pending.session_id, count, "Recovered memories from interrupted extraction");
+        Ok(Some(count))
+    }
+
     /// Update the session's token usage counters.
     pub async fn update_session_tokens(
         &self,
         session_id: &Uuid,
         input_tokens: u32,
         output_tokens: u32,
+    ) -> Result<(), RepositoryError> {
+        self.update_session_usage(session_id, input_tokens, output_tokens, 0.0)
+            .await
+    }
+
+    /// Update the session's token counters and accumulated cost (USD).
+    ///
+    /// Also records the turn (one request plus its tokens) against the bot's
+    /// usage for the current quota window.
+    pub async fn update_session_usage(
+        &self,
+        session_id: &Uuid,
+        input_tokens: u32,
+        output_tokens: u32,
+        cost_usd: f64,
     ) -> Result<(), RepositoryError> {
         let session = self.chat_repo.get_session(session_id).await?;
         if let Some(mut session) = session {
             session.total_input_tokens += input_tokens;
             session.total_output_tokens += output_tokens;
+            session.total_cost_usd += cost_usd;
             self.chat_repo.update_session(&session).await?;
+
+            let tokens = u64::from(input_tokens) + u64::from(output_tokens);
+            self.record_usage_at(&session.bot_id, 1, tokens, Utc::now())
+                .await?;
         }
         Ok(())
     }
 
+    // --- Usage quotas ---
+
+    /// Check that the bot may dispatch another completion request.
+    ///
+    /// Call before sending a request to the LLM. Returns
+    /// `QuotaError::Exceeded` once the bot's request or token usage in the
+    /// current window has reached its limit.
+    pub async fn check_quota(&self, bot_id: &Uuid, bot_slug: &str) -> Result<(), QuotaError> {
+        self.check_quota_at(bot_id, bot_slug, Utc::now()).await
+    }
+
+    /// [`check_quota`](Self::check_quota) as of `now`.
+    pub async fn check_quota_at(
+        &self,
+        bot_id: &Uuid,
+        bot_slug: &str,
+        now: DateTime<Utc>,
+    ) -> Result<(), QuotaError> {
+        let limits = self.quota.limits_for(bot_slug);
+        if limits.max_requests.is_none() && limits.max_tokens.is_none() {
+            return Ok(());
+        }
+        let (window_start, resets_at) = window_bounds(&self.quota, now);
+        let usage = self.chat_repo.get_bot_usage(bot_id, window_start).await?;
+        check_usage(usage.as_ref(), limits, resets_at)
+    }
+
+    /// Add requests and tokens to the bot's usage in the window containing `now`.
+    pub async fn record_usage_at(
+        &self,
+        bot_id: &Uuid,
+        requests: u64,
+        tokens: u64,
+        now: DateTime<Utc>,
+    ) -> Result<(), RepositoryError> {
+        let (window_start, _) = window_bounds(&self.quota, now);
+        self.chat_repo
+            .add_bot_usage(bot_id, window_start, requests, tokens)
+            .await
+    }
+
     // --- Vector memory operations ---
 
     /// Search long-term vector memory for facts relevant to a user message.
@@ -279,10 +537,16 @@ impl<C: ChatRepository, M: MemoryRepository> ChatService<C, M> {
 
         // Search vector store
         match vector_store
-            .search(bot_id, &embedding, DEFAULT_MEMORY_SEARCH_LIMIT, DEFAULT_MIN_SIMILARITY)
+            .search(
+                bot_id,
+                &embedding,
+                DEFAULT_MEMORY_SEARCH_LIMIT,
+                self.similarity_policy.search_floor(),
+            )
             .await
         {
             Ok(results) => {
+                let results = self.similarity_policy.apply(results);
                 debug!(
                     bot_id = %bot_id,
                     count = results.len(),
@@ -418,6 +682,41 @@ impl<C: ChatRepository, M: MemoryRepository> ChatService<C, M> {
         Ok(stored_count)
     }
 
+    /// Replace a memory's vector entry after its fact was edited.
+    ///
+    /// Embeds the current fact and swaps out the old vector (same ID) so
+    /// semantic recall matches the new text rather than the stale embedding.
+    pub async fn refresh_memory_embedding(
+        &self,
+        entry: &MemoryEntry,
+        embedder: &BoxEmbedder,
+        vector_store: &BoxVectorMemoryStore,
+    ) -> Result<(), RepositoryError> {
+        let embedding = embedder
+            .embed(std::slice::from_ref(&entry.fact))
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| RepositoryError::Query("Embedder returned no vectors".to_string()))?;
+
+        vector_store.delete(&entry.bot_id, &entry.id).await?;
+        let vector_entry = VectorMemoryEntry {
+            id: entry.id,
+            bot_id: entry.bot_id,
+            fact: entry.fact.clone(),
+            category: entry.category.clone(),
+            importance: entry.importance,
+            // Manual memories carry a nil session ID
+            session_id: (!entry.is_manual).then_some(entry.session_id),
+            source_memory_id: Some(entry.id),
+            embedding_model: embedder.model_name().to_string(),
+            created_at: entry.created_at,
+            last_accessed_at: None,
+            access_count: 0,
+        };
+        vector_store.add(&vector_entry, &embedding).await
+    }
+
     /// Check for embedding model mismatch and re-embed stale memories.
     ///
     /// Compares the current embedder model name against stored entries.
@@ -1,13 +1,24 @@
 //! Session manager for chat sessions.
 //!
 //! Wraps a `ChatSession` with turn tracking and lifecycle management.
-//! Tracks when memory extraction should run (every N turns).
+//! Tracks when memory extraction should run (every N turns, or on demand)
+//! and enforces an optional per-session cost cap.
 
 use boternity_types::chat::{ChatSession, SessionStatus};
 use chrono::Utc;
 
 /// Default number of turns between memory extraction attempts.
-const MEMORY_EXTRACTION_INTERVAL: u32 = 10;
+pub const MEMORY_EXTRACTION_INTERVAL: u32 = 10;
+
+/// Returned when a session's accumulated cost has reached its cap.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[error("session cost budget reached: spent ~${spent_usd:.2} of ${limit_usd:.2}")]
+pub struct CostBudgetExceeded {
+    /// Estimated spend so far (USD).
+    pub spent_usd: f64,
+    /// Configured cap (USD).
+    pub limit_usd: f64,
+}
 
 /// Manages the lifecycle and state of a single chat session.
 ///
@@ -17,17 +28,45 @@ pub struct SessionManager {
     session: ChatSession,
     /// Turn counter (incremented on each user+assistant exchange).
     turn_count: u32,
+    /// Optional hard cap on the session's estimated spend (USD).
+    max_cost_usd: Option<f64>,
+    /// Turns between periodic memory extractions (0 disables them).
+    extraction_interval: u32,
+    /// Set by `/memory extract`; cleared once extraction has run.
+    extraction_requested: bool,
 }
 
 impl SessionManager {
     /// Create a new session manager wrapping an existing session.
+    ///
+    /// Spend already recorded on the session (e.g., when resuming) counts
+    /// toward any cost cap.
     pub fn new(session: ChatSession) -> Self {
         Self {
             session,
             turn_count: 0,
+            max_cost_usd: None,
+            extraction_interval: MEMORY_EXTRACTION_INTERVAL,
+            extraction_requested: false,
         }
     }
 
+    /// Set the number of turns between periodic memory extractions.
+    ///
+    /// `None` keeps the default ([`MEMORY_EXTRACTION_INTERVAL`]); `Some(0)`
+    /// disables periodic extraction (end-of-session and on-demand extraction
+    /// still run).
+    pub fn with_extraction_interval(mut self, interval: Option<u32>) -> Self {
+        self.extraction_interval = interval.unwrap_or(MEMORY_EXTRACTION_INTERVAL);
+        self
+    }
+
+    /// Cap the session's estimated spend in USD. `None` disables the guard.
+    pub fn with_max_cost(mut self, max_cost_usd: Option<f64>) -> Self {
+        self.max_cost_usd = max_cost_usd;
+        self
+    }
+
     /// Access the underlying chat session.
     pub fn session(&self) -> &ChatSession {
         &self.session
@@ -51,12 +90,26 @@ impl SessionManager {
         self.turn_count += 1;
     }
 
-    /// Whether memory extraction should run based on the turn counter.
+    /// Whether memory extraction should run.
     ///
-    /// Returns true every `MEMORY_EXTRACTION_INTERVAL` turns (default: 10),
-    /// ensuring periodic memory capture during long conversations.
+    /// Returns true every `extraction_interval` turns (default: 10),
+    /// ensuring periodic memory capture during long conversations, or
+    /// whenever an extraction was requested and has not yet run.
     pub fn should_extract_memory(&self) -> bool {
-        self.turn_count > 0 && self.turn_count % MEMORY_EXTRACTION_INTERVAL == 0
+        self.extraction_requested
+            || (self.extraction_interval > 0
+                && self.turn_count > 0
+                && self.turn_count % self.extraction_interval == 0)
+    }
+
+    /// Request a memory extraction regardless of the turn count.
+    pub fn request_memory_extraction(&mut self) {
+        self.extraction_requested = true;
+    }
+
+    /// Record that memory extraction ran, clearing any pending request.
+    pub fn mark_memory_extracted(&mut self) {
+        self.extraction_requested = false;
     }
 
     /// Mark the session as completed.
@@ -80,6 +133,30 @@ impl SessionManager {
         self.session.total_input_tokens += input_tokens;
         self.session.total_output_tokens += output_tokens;
     }
+
+    /// Add the estimated cost (USD) of a completed exchange.
+    pub fn add_cost(&mut self, cost_usd: f64) {
+        self.session.total_cost_usd += cost_usd;
+    }
+
+    /// Estimated spend so far (USD).
+    pub fn total_cost(&self) -> f64 {
+        self.session.total_cost_usd
+    }
+
+    /// Check whether another request may be sent under the cost cap.
+    ///
+    /// Trips once cumulative spend reaches the cap; the request that
+    /// crosses it is allowed to complete, later ones are blocked.
+    pub fn check_cost_budget(&self) -> Result<(), CostBudgetExceeded> {
+        match self.max_cost_usd {
+            Some(limit) if self.session.total_cost_usd >= limit => Err(CostBudgetExceeded {
+                spent_usd: self.session.total_cost_usd,
+                limit_usd: limit,
+            }),
+            _ => Ok(()),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -96,9 +173,12 @@ mod tests {
             ended_at: None,
             total_input_tokens: 0,
             total_output_tokens: 0,
+            total_cost_usd: 0.0,
             message_count: 0,
             model: "claude-sonnet-4-20250514".to_string(),
             status: SessionStatus::Active,
+            summary: None,
+            summarized_messages: 0,
         }
     }
 
@@ -148,6 +228,37 @@ mod tests {
         assert!(mgr.should_extract_memory());
     }
 
+    #[test]
+    fn test_should_extract_memory_custom_interval() {
+        let mut mgr = SessionManager::new(test_session()).with_extraction_interval(Some(3));
+        let triggered: Vec<u32> = (1..=9)
+            .filter(|_| {
+                mgr.increment_turn();
+                mgr.should_extract_memory()
+            })
+            .collect();
+        assert_eq!(triggered, vec![3, 6, 9]);
+
+        let mut disabled = SessionManager::new(test_session()).with_extraction_interval(Some(0));
+        for _ in 0..20 {
+            disabled.increment_turn();
+            assert!(!disabled.should_extract_memory());
+        }
+    }
+
+    #[test]
+    fn test_requested_extraction_ignores_turn_count() {
+        let mut mgr = SessionManager::new(test_session()).with_extraction_interval(Some(0));
+        assert!(!mgr.should_extract_memory());
+
+        mgr.request_memory_extraction();
+        assert!(mgr.should_extract_memory());
+        assert_eq!(mgr.turn_count(), 0);
+
+        mgr.mark_memory_extracted();
+        assert!(!mgr.should_extract_memory());
+    }
+
     #[test]
     fn test_mark_completed() {
         let mut mgr = SessionManager::new(test_session());
@@ -177,4 +288,36 @@ mod tests {
         assert_eq!(mgr.session().total_input_tokens, 150);
         assert_eq!(mgr.session().total_output_tokens, 275);
     }
+
+    #[test]
+    fn test_cost_budget_trips_once_threshold_crossed() {
+        let mut mgr = SessionManager::new(test_session()).with_max_cost(Some(0.10));
+        assert!(mgr.check_cost_budget().is_ok());
+
+        mgr.add_cost(0.04);
+        mgr.add_cost(0.05);
+        assert!(mgr.check_cost_budget().is_ok());
+
+        mgr.add_cost(0.02);
+        let err = mgr.check_cost_budget().unwrap_err();
+        assert!((err.spent_usd - 0.11).abs() < 1e-9);
+        assert_eq!(err.limit_usd, 0.10);
+        assert_eq!(err.to_string(), "session cost budget reached: spent ~$0.11 of $0.10");
+    }
+
+    #[test]
+    fn test_cost_budget_counts_resumed_spend() {
+        let mut session = test_session();
+        session.total_cost_usd = 1.50;
+        let mgr = SessionManager::new(session).with_max_cost(Some(1.00));
+        assert!(mgr.check_cost_budget().is_err());
+        assert_eq!(mgr.total_cost(), 1.50);
+    }
+
+    #[test]
+    fn test_no_cost_budget_never_trips() {
+        let mut mgr = SessionManager::new(test_session());
+        mgr.add_cost(1_000.0);
+        assert!(mgr.check_cost_budget().is_ok());
+    }
 }
@@ -2,16 +2,105 @@
 //!
 //! Built on `tokio::sync::broadcast`, the `EventBus` supports multiple
 //! concurrent subscribers. Publishing with no active subscribers is a no-op.
+//!
+//! A bus created with [`EventBus::with_replay`] also keeps a bounded ring
+//! buffer of recent events keyed by request id, so a subscriber that joins
+//! mid-request (a reconnecting WebSocket, a late CLI renderer) can catch up
+//! via [`EventBus::subscribe_with_replay`] before receiving live events.
+//!
+//! [`EventBus::subscribe_filtered`] and [`EventBus::subscribe_request`] return
+//! a [`FilteredReceiver`] that only yields matching events, so a subscriber
+//! following one request is not handed every event of concurrent requests.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
 
 use boternity_types::event::AgentEvent;
 use tokio::sync::broadcast;
+use uuid::Uuid;
+
+/// Bounded history of recent request-scoped events, oldest first.
+#[derive(Debug)]
+struct ReplayBuffer {
+    capacity: usize,
+    events: VecDeque<(Uuid, AgentEvent)>,
+}
+
+impl ReplayBuffer {
+    /// Buffered events of `request_id`, oldest first.
+    fn backlog(&self, request_id: Uuid) -> Vec<AgentEvent> {
+        self.events
+            .iter()
+            .filter(|(id, _)| *id == request_id)
+            .map(|(_, event)| event.clone())
+            .collect()
+    }
+}
+
+/// An event together with the request id it was published under.
+#[derive(Debug, Clone)]
+struct TaggedEvent {
+    request_id: Option<Uuid>,
+    event: AgentEvent,
+}
+
+/// Predicate deciding which events a [`FilteredReceiver`] yields.
+type EventFilter = Box<dyn Fn(Option<Uuid>, &AgentEvent) -> bool + Send + Sync>;
+
+/// A subscription that only yields events accepted by its filter.
+///
+/// Non-matching events are skipped inside [`recv`](Self::recv), so callers
+/// never see them.
+pub struct FilteredReceiver {
+    receiver: broadcast::Receiver<TaggedEvent>,
+    filter: EventFilter,
+}
+
+impl FilteredReceiver {
+    /// Receive the next matching event.
+    ///
+    /// Returns `Lagged` if the subscriber fell behind (counting skipped events
+    /// of any kind) and `Closed` once every sender is dropped.
+    pub async fn recv(&mut self) -> Result<AgentEvent, broadcast::error::RecvError> {
+        loop {
+            let tagged = self.receiver.recv().await?;
+            if (self.filter)(tagged.request_id, &tagged.event) {
+                return Ok(tagged.event);
+            }
+        }
+    }
+
+    /// Receive the next matching event without waiting.
+    pub fn try_recv(&mut self) -> Result<AgentEvent, broadcast::error::TryRecvError> {
+        loop {
+            let tagged = self.receiver.try_recv()?;
+            if (self.filter)(tagged.request_id, &tagged.event) {
+                return Ok(tagged.event);
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for FilteredReceiver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FilteredReceiver")
+            .field("pending", &self.receiver.len())
+            .finish()
+    }
+}
 
 /// Multi-consumer event bus for agent hierarchy events.
 ///
 /// Wraps a `tokio::sync::broadcast` channel. Cloning the bus clones the
-/// sender, allowing multiple producers and consumers.
+/// sender, allowing multiple producers and consumers. Clones share the
+/// replay buffer, if any.
 pub struct EventBus {
     sender: broadcast::Sender<AgentEvent>,
+    /// Same events with their request id, for filtered subscribers.
+    tagged: broadcast::Sender<TaggedEvent>,
+    replay: Option<Arc<Mutex<ReplayBuffer>>>,
+    /// Request id attached to events published through this handle.
+    request_id: Option<Uuid>,
 }
 
 impl EventBus {
@@ -20,7 +109,42 @@ impl EventBus {
     /// A capacity of 1024 is recommended for typical agent hierarchies.
     pub fn new(capacity: usize) -> Self {
         let (sender, _) = broadcast::channel(capacity);
-        Self { sender }
+        let (tagged, _) = broadcast::channel(capacity);
+        Self {
+            sender,
+            tagged,
+            replay: None,
+            request_id: None,
+        }
+    }
+
+    /// Create an event bus that also retains the last `replay_len` events
+    /// for replay to late subscribers.
+    ///
+    /// Only events with a request id are buffered: those published through a
+    /// [`scoped`](Self::scoped) handle, or variants that carry their own
+    /// `request_id`.
+    pub fn with_replay(capacity: usize, replay_len: usize) -> Self {
+        let mut bus = Self::new(capacity);
+        bus.replay = Some(Arc::new(Mutex::new(ReplayBuffer {
+            capacity: replay_len,
+            events: VecDeque::with_capacity(replay_len),
+        })));
+        bus
+    }
+
+    /// Return a handle that tags everything it publishes with `request_id`.
+    ///
+    /// The handle shares the channel and replay buffer with this bus. Pass it
+    /// to the orchestrator so agent events (which only carry an agent id) can
+    /// be replayed by request.
+    pub fn scoped(&self, request_id: Uuid) -> Self {
+        Self {
+            sender: self.sender.clone(),
+            tagged: self.tagged.clone(),
+            replay: self.replay.clone(),
+            request_id: Some(request_id),
+        }
     }
 
     /// Create a new subscriber that will receive all future events.
@@ -28,10 +152,89 @@ impl EventBus {
         self.sender.subscribe()
     }
 
+    /// Create a subscriber that only receives events matching `filter`.
+    pub fn subscribe_filtered<F>(&self, filter: F) -> FilteredReceiver
+    where
+        F: Fn(&AgentEvent) -> bool + Send + Sync + 'static,
+    {
+        FilteredReceiver {
+            receiver: self.tagged.subscribe(),
+            filter: Box::new(move |_, event| filter(event)),
+        }
+    }
+
+    /// Create a subscriber that only receives events of `request_id`.
+    ///
+    /// Matches events published through a [`scoped`](Self::scoped) handle for
+    /// the request, and variants carrying that `request_id` themselves.
+    pub fn subscribe_request(&self, request_id: Uuid) -> FilteredReceiver {
+        FilteredReceiver {
+            receiver: self.tagged.subscribe(),
+            filter: Box::new(move |tag, event| {
+                tag.or_else(|| event.request_id()) == Some(request_id)
+            }),
+        }
+    }
+
+    /// Like [`subscribe_request`](Self::subscribe_request), but also return
+    /// the buffered backlog for the request (see
+    /// [`subscribe_with_replay`](Self::subscribe_with_replay)).
+    pub fn subscribe_request_with_replay(
+        &self,
+        request_id: Uuid,
+    ) -> (Vec<AgentEvent>, FilteredReceiver) {
+        let Some(replay) = &self.replay else {
+            return (Vec::new(), self.subscribe_request(request_id));
+        };
+        let buffer = replay.lock().unwrap_or_else(|e| e.into_inner());
+        (buffer.backlog(request_id), self.subscribe_request(request_id))
+    }
+
+    /// Subscribe and return the buffered events for `request_id`, oldest first.
+    ///
+    /// The backlog and the receiver are taken under the same lock publishers
+    /// hold while sending, so every event appears exactly once: either in the
+    /// backlog or on the receiver. Without a replay buffer the backlog is empty.
+    pub fn subscribe_with_replay(
+        &self,
+        request_id: Uuid,
+    ) -> (Vec<AgentEvent>, broadcast::Receiver<AgentEvent>) {
+        let Some(replay) = &self.replay else {
+            return (Vec::new(), self.sender.subscribe());
+        };
+        let buffer = replay.lock().unwrap_or_else(|e| e.into_inner());
+        (buffer.backlog(request_id), self.sender.subscribe())
+    }
+
     /// Publish an event to all current subscribers.
     ///
-    /// If there are no subscribers, the event is silently dropped.
+    /// If there are no subscribers, the event is silently dropped (but still
+    /// recorded in the replay buffer when it has a request id).
     pub fn publish(&self, event: AgentEvent) {
+        let key = self.request_id.or_else(|| event.request_id());
+        match (&self.replay, key) {
+            (Some(replay), Some(request_id)) => {
+                let mut buffer = replay.lock().unwrap_or_else(|e| e.into_inner());
+                if buffer.capacity > 0 {
+                    if buffer.events.len() == buffer.capacity {
+                        buffer.events.pop_front();
+                    }
+                    buffer.events.push_back((request_id, event.clone()));
+                }
+                self.send(key, event);
+            }
+            _ => self.send(key, event),
+        }
+    }
+
+    /// Send to plain subscribers, and to filtered ones if there are any.
+    fn send(&self, request_id: Option<Uuid>, event: AgentEvent) {
+        if self.tagged.receiver_count() > 0 {
+            let _ = self.tagged.send(TaggedEvent {
+                request_id,
+                event: event.clone(),
+            });
+        }
         let _ = self.sender.send(event);
     }
 
@@ -45,6 +248,9 @@ impl Clone for EventBus {
     fn clone(&self) -> Self {
         Self {
             sender: self.sender.clone(),
+            tagged: self.tagged.clone(),
+            replay: self.replay.clone(),
+            request_id: self.request_id,
         }
     }
 }
@@ -53,6 +259,9 @@ impl std::fmt::Debug for EventBus {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("EventBus")
             .field("receiver_count", &self.sender.receiver_count())
+            .field("filtered_count", &self.tagged.receiver_count())
+            .field("replay", &self.replay.is_some())
+            .field("request_id", &self.request_id)
             .finish()
     }
 }
@@ -60,7 +269,6 @@ impl std::fmt::Debug for EventBus {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use uuid::Uuid;
 
     fn sample_event() -> AgentEvent {
         AgentEvent::AgentSpawned {
@@ -150,4 +358,131 @@ mod tests {
         assert!(debug.contains("EventBus"));
         assert!(debug.contains("receiver_count"));
     }
+
+    #[tokio::test]
+    async fn late_subscriber_replays_backlog_before_live_events() {
+        let bus = EventBus::with_replay(16, 8);
+        let request_id = Uuid::now_v7();
+        let scoped = bus.scoped(request_id);
+
+        for i in 0..3 {
+            scoped.publish(AgentEvent::AgentTextDelta {
+                agent_id: Uuid::now_v7(),
+                text: format!("early {i}"),
+            });
+        }
+        // Another request's events are not replayed
+        bus.scoped(Uuid::now_v7()).publish(sample_event());
+
+        let (backlog, mut rx) = bus.subscribe_with_replay(request_id);
+        scoped.publish(AgentEvent::SynthesisStarted { request_id });
+
+        let texts: Vec<String> = backlog
+            .iter()
+            .map(|e| match e {
+                AgentEvent::AgentTextDelta { text, .. } => text.clone(),
+                other => panic!("unexpected backlog event: {other:?}"),
+            })
+            .collect();
+        assert_eq!(texts, vec!["early 0", "early 1", "early 2"]);
+
+        // Only the live event arrives on the receiver -- no duplicates
+        let live = rx.recv().await.unwrap();
+        assert!(matches!(live, AgentEvent::SynthesisStarted { .. }));
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn replay_buffer_is_bounded() {
+        let bus = EventBus::with_replay(16, 2);
+        let request_id = Uuid::now_v7();
+        for i in 0..5 {
+            bus.publish(AgentEvent::BudgetUpdate {
+                request_id,
+                tokens_used: i,
+                budget_total: 100,
+                percentage: i as f32,
+            });
+        }
+
+        // Unscoped events carrying their own request id are buffered too
+        let (backlog, _rx) = bus.subscribe_with_replay(request_id);
+        let used: Vec<u32> = backlog
+            .iter()
+            .map(|e| match e {
+                AgentEvent::BudgetUpdate { tokens_used, .. } => *tokens_used,
+                other => panic!("unexpected backlog event: {other:?}"),
+            })
+            .collect();
+        assert_eq!(used, vec![3, 4]);
+    }
+
+    #[test]
+    fn subscribe_with_replay_without_buffer_is_empty() {
+        let bus = EventBus::new(16);
+        let request_id = Uuid::now_v7();
+        bus.scoped(request_id).publish(sample_event());
+
+        let (backlog, _rx) = bus.subscribe_with_replay(request_id);
+        assert!(backlog.is_empty());
+    }
+
+    #[tokio::test]
+    async fn request_filtered_subscriber_only_sees_its_request() {
+        let bus = EventBus::new(64);
+        let (req_a, req_b) = (Uuid::now_v7(), Uuid::now_v7());
+        let mut rx_a = bus.subscribe_request(req_a);
+        let mut rx_b = bus.subscribe_request(req_b);
+
+        let publish = |request_id: Uuid, n: usize| {
+            let scoped = bus.scoped(request_id);
+            tokio::spawn(async move {
+                for i in 0..n {
+                    scoped.publish(AgentEvent::AgentTextDelta {
+                        agent_id: request_id,
+                        text: format!("{i}"),
+                    });
+                    tokio::task::yield_now().await;
+                }
+                scoped.publish(AgentEvent::SynthesisStarted { request_id });
+            })
+        };
+        let (a, b) = (publish(req_a, 5), publish(req_b, 7));
+        a.await.unwrap();
+        b.await.unwrap();
+
+        for (rx, request_id, deltas) in [(&mut rx_a, req_a, 5), (&mut rx_b, req_b, 7)] {
+            let mut received = Vec::new();
+            while let Ok(event) = rx.try_recv() {
+                received.push(event);
+            }
+            assert_eq!(received.len(), deltas + 1);
+            for event in &received {
+                match event {
+                    AgentEvent::AgentTextDelta { agent_id, .. } => assert_eq!(*agent_id, request_id),
+                    AgentEvent::SynthesisStarted { request_id: id } => assert_eq!(*id, request_id),
+                    other => panic!("unexpected event: {other:?}"),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn predicate_filtered_subscriber_skips_other_events() {
+        let bus = EventBus::new(16);
+        let mut rx = bus.subscribe_filtered(|e| matches!(e, AgentEvent::AgentSpawned { .. }));
+        let mut all = bus.subscribe();
+
+        bus.publish(AgentEvent::AgentTextDelta {
+            agent_id: Uuid::now_v7(),
+            text: "ignored".to_string(),
+        });
+        bus.publish(sample_event());
+
+        assert!(matches!(rx.try_recv(), Ok(AgentEvent::AgentSpawned { .. })));
+        assert!(rx.try_recv().is_err());
+        // Plain subscribers still see everything
+        assert!(matches!(all.try_recv(), Ok(AgentEvent::AgentTextDelta { .. })));
+        assert!(matches!(all.try_recv(), Ok(AgentEvent::AgentSpawned { .. })));
+    }
 }
@@ -5,4 +5,4 @@
 
 pub mod bus;
 
-pub use bus::EventBus;
+pub use bus::{EventBus, FilteredReceiver};
@@ -4,17 +4,26 @@
 //! 1. Define an object-safe `LlmProviderDyn` trait with boxed futures
 //! 2. Blanket-impl `LlmProviderDyn` for all `T: LlmProvider`
 //! 3. `BoxLlmProvider` wraps `Box<dyn LlmProviderDyn>` and delegates
+//!
+//! `BoxLlmProvider` also checks each request against the provider's
+//! capabilities before delegating, so a request for a feature the provider
+//! lacks fails with `LlmError::Unsupported` instead of an opaque provider error.
+//! Streams for structured-output requests are validated against the schema as
+//! they arrive (see [`super::structured`]).
 
 use std::future::Future;
 use std::pin::Pin;
 
 use futures_util::Stream;
+use tokio_util::sync::CancellationToken;
 
 use boternity_types::llm::{
-    CompletionRequest, CompletionResponse, LlmError, ProviderCapabilities, StreamEvent, TokenCount,
+    CompletionRequest, CompletionResponse, LlmError, LlmFeature, ProviderCapabilities, StreamEvent,
+    TokenCount,
 };
 
 use super::provider::LlmProvider;
+use super::structured::validate_json_stream;
 
 /// Object-safe version of [`LlmProvider`] with boxed futures.
 ///
@@ -35,6 +44,12 @@ pub trait LlmProviderDyn: Send + Sync {
         request: CompletionRequest,
     ) -> Pin<Box<dyn Stream<Item = Result<StreamEvent, LlmError>> + Send + 'static>>;
 
+    fn stream_with_cancel_boxed(
+        &self,
+        request: CompletionRequest,
+        cancel: CancellationToken,
+    ) -> Pin<Box<dyn Stream<Item = Result<StreamEvent, LlmError>> + Send + 'static>>;
+
     fn count_tokens_boxed<'a>(
         &'a self,
         request: &'a CompletionRequest,
@@ -65,6 +80,14 @@ impl<T: LlmProvider> LlmProviderDyn for T {
         self.stream(request)
     }
 
+    fn stream_with_cancel_boxed(
+        &self,
+        request: CompletionRequest,
+        cancel: CancellationToken,
+    ) -> Pin<Box<dyn Stream<Item = Result<StreamEvent, LlmError>> + Send + 'static>> {
+        self.stream_with_cancel(request, cancel)
+    }
+
     fn count_tokens_boxed<'a>(
         &'a self,
         request: &'a CompletionRequest,
@@ -103,11 +126,21 @@ impl BoxLlmProvider {
         self.inner.capabilities()
     }
 
+    /// Check that this provider can serve `request`, streamed or not.
+    pub fn check_request(&self, request: &CompletionRequest, stream: bool) -> Result<(), LlmError> {
+        let capabilities = self.capabilities();
+        if stream {
+            capabilities.require(LlmFeature::Streaming)?;
+        }
+        request.check_capabilities(capabilities)
+    }
+
     /// Send a completion request and receive the full response.
     pub async fn complete(
         &self,
         request: &CompletionRequest,
     ) -> Result<CompletionResponse, LlmError> {
+        self.check_request(request, false)?;
         self.inner.complete_boxed(request).await
     }
 
@@ -116,7 +149,24 @@ impl BoxLlmProvider {
         &self,
         request: CompletionRequest,
     ) -> Pin<Box<dyn Stream<Item = Result<StreamEvent, LlmError>> + Send + 'static>> {
-        self.inner.stream_boxed(request)
+        if let Err(e) = self.check_request(&request, true) {
+            return Box::pin(futures_util::stream::once(async move { Err(e) }));
+        }
+        let schema = output_schema(&request);
+        with_output_validation(self.inner.stream_boxed(request), schema)
+    }
+
+    /// Send a streaming completion request that ends when `cancel` fires.
+    pub fn stream_with_cancel(
+        &self,
+        request: CompletionRequest,
+        cancel: CancellationToken,
+    ) -> Pin<Box<dyn Stream<Item = Result<StreamEvent, LlmError>> + Send + 'static>> {
+        if let Err(e) = self.check_request(&request, true) {
+            return Box::pin(futures_util::stream::once(async move { Err(e) }));
+        }
+        let schema = output_schema(&request);
+        with_output_validation(self.inner.stream_with_cancel_boxed(request, cancel), schema)
     }
 
     /// Count the tokens in a request without sending it to the LLM.
@@ -127,3 +177,152 @@ impl BoxLlmProvider {
         self.inner.count_tokens_boxed(request).await
     }
 }
+
+/// The JSON schema a structured-output request must match, if any.
+fn output_schema(request: &CompletionRequest) -> Option<serde_json::Value> {
+    request
+        .output_config
+        .as_ref()
+        .map(|config| config.format.json_schema.schema.clone())
+}
+
+fn with_output_validation(
+    stream: Pin<Box<dyn Stream<Item = Result<StreamEvent, LlmError>> + Send + 'static>>,
+    schema: Option<serde_json::Value>,
+) -> Pin<Box<dyn Stream<Item = Result<StreamEvent, LlmError>> + Send + 'static>> {
+    match schema {
+        Some(schema) => validate_json_stream(stream, schema),
+        None => stream,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use futures_util::StreamExt;
+
+    use boternity_types::llm::{ImageSource, Message, StopReason, Usage};
+
+    /// Counts calls that reach the provider; every call stands in for a
+    /// network request.
+    struct CountingProvider {
+        capabilities: ProviderCapabilities,
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl LlmProvider for CountingProvider {
+        fn name(&self) -> &str {
+            "counting"
+        }
+
+        fn capabilities(&self) -> &ProviderCapabilities {
+            &self.capabilities
+        }
+
+        async fn complete(
+            &self,
+            request: &CompletionRequest,
+        ) -> Result<CompletionResponse, LlmError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(CompletionResponse {
+                id: "resp".to_string(),
+                content: "ok".to_string(),
+                model: request.model.clone(),
+                stop_reason: StopReason::EndTurn,
+                usage: Usage::default(),
+            })
+        }
+
+        fn stream(
+            &self,
+            _request: CompletionRequest,
+        ) -> Pin<Box<dyn Stream<Item = Result<StreamEvent, LlmError>> + Send + 'static>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Box::pin(futures_util::stream::iter([Ok(StreamEvent::Done)]))
+        }
+
+        async fn count_tokens(&self, _request: &CompletionRequest) -> Result<TokenCount, LlmError> {
+            Ok(TokenCount { input_tokens: 0 })
+        }
+    }
+
+    fn counting_provider(vision: bool, streaming: bool) -> (BoxLlmProvider, Arc<AtomicUsize>) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let provider = BoxLlmProvider::new(CountingProvider {
+            capabilities: ProviderCapabilities {
+                streaming,
+                tool_calling: false,
+                vision,
+                extended_thinking: false,
+                max_context_tokens: 8_192,
+                max_output_tokens: 1_024,
+            },
+            calls: calls.clone(),
+        });
+        (provider, calls)
+    }
+
+    fn image_request() -> CompletionRequest {
+        CompletionRequest::builder("test-model")
+            .message(
+                Message::from("What is this?".to_string()).with_image(ImageSource::Url {
+                    url: "https://example.com/cat.jpg".to_string(),
+                }),
+            )
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_vision_request_rejected_before_provider_call() {
+        let (provider, calls) = counting_provider(false, true);
+
+        let err = provider.complete(&image_request()).await.unwrap_err();
+        assert!(matches!(
+            err,
+            LlmError::Unsupported {
+                feature: LlmFeature::Vision
+            }
+        ));
+
+        let events: Vec<_> = provider.stream(image_request()).collect().await;
+        assert!(matches!(
+            events.as_slice(),
+            [Err(LlmError::Unsupported {
+                feature: LlmFeature::Vision
+            })]
+        ));
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+        let (provider, calls) = counting_provider(true, true);
+        assert!(provider.complete(&image_request()).await.is_ok());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_stream_rejected_for_non_streaming_provider() {
+        let (provider, calls) = counting_provider(false, false);
+        let request = CompletionRequest::builder("test-model")
+            .message(Message::from("Hello".to_string()))
+            .build()
+            .unwrap();
+
+        let events: Vec<_> = provider
+            .stream_with_cancel(request.clone(), CancellationToken::new())
+            .collect()
+            .await;
+        assert!(matches!(
+            events.as_slice(),
+            [Err(LlmError::Unsupported {
+                feature: LlmFeature::Streaming
+            })]
+        ));
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+        // The same request is fine without streaming
+        assert!(provider.complete(&request).await.is_ok());
+    }
+}
@@ -0,0 +1,238 @@
+//! Shared LLM error taxonomy.
+//!
+//! Every provider maps transport failures to [`LlmError`] through these
+//! helpers so the same HTTP status or provider error type always yields the
+//! same variant, and the fallback chain and retry decorator see consistent
+//! errors regardless of which backend produced them.
+//!
+//! | Input                                   | Variant                 |
+//! |-----------------------------------------|-------------------------|
+//! | 401, 403, 498 / `authentication_error`  | `AuthenticationFailed`  |
+//! | 429 / `rate_limit_error`                | `RateLimited`           |
+//! | 503, 529 / `overloaded_error`           | `Overloaded`            |
+//! | 400, 413, 422 + context-length message  | `ContextLengthExceeded` |
+//! | 400, 413, 422 / `invalid_request_error` | `InvalidRequest`        |
+//! | anything else                           | `Provider`              |
+
+use boternity_types::llm::LlmError;
+
+/// Map a non-2xx HTTP status and its error message to an [`LlmError`].
+///
+/// `message` is the provider's error message (or raw body when it could not
+/// be parsed). 498 is Cohere's "invalid token" status; 529 is Anthropic's
+/// "overloaded" status.
+pub fn classify_http_status(status: u16, message: &str) -> LlmError {
+    match status {
+        401 | 403 | 498 => LlmError::AuthenticationFailed,
+        429 => LlmError::RateLimited {
+            retry_after_ms: None,
+        },
+        503 | 529 => LlmError::Overloaded(message.to_string()),
+        400 | 413 | 422 if is_context_length_message(message) => {
+            let (max, requested) = parse_context_length(message);
+            LlmError::ContextLengthExceeded { max, requested }
+        }
+        400 | 413 | 422 => LlmError::InvalidRequest(message.to_string()),
+        _ => LlmError::Provider {
+            message: format!("HTTP {status}: {message}"),
+        },
+    }
+}
+
+/// Map a provider error type/code string to an [`LlmError`].
+///
+/// Covers the Anthropic SSE/JSON `error.type` values (also used by Bedrock)
+/// and the OpenAI `code`/`type` fields. Returns `None` for unknown types so
+/// callers can fall back to status- or message-based classification.
+pub fn classify_error_type(error_type: &str, message: &str) -> Option<LlmError> {
+    let err = match error_type {
+        "authentication_error" | "invalid_api_key" => LlmError::AuthenticationFailed,
+        "rate_limit_error" | "rate_limit_exceeded" => LlmError::RateLimited {
+            retry_after_ms: None,
+        },
+        "overloaded_error" | "server_error" => LlmError::Overloaded(message.to_string()),
+        "context_length_exceeded" => {
+            let (max, requested) = parse_context_length(message);
+            LlmError::ContextLengthExceeded { max, requested }
+        }
+        "invalid_request_error" if is_context_length_message(message) => {
+            let (max, requested) = parse_context_length(message);
+            LlmError::ContextLengthExceeded { max, requested }
+        }
+        "invalid_request_error" => LlmError::InvalidRequest(message.to_string()),
+        _ => return None,
+    };
+    Some(err)
+}
+
+/// Whether an error is transient and worth retrying on the same provider.
+pub fn is_retryable(error: &LlmError) -> bool {
+    matches!(
+        error,
+        LlmError::RateLimited { .. } | LlmError::Overloaded(..)
+    )
+}
+
+/// Whether an error means the provider rejected our credentials.
+pub fn is_auth(error: &LlmError) -> bool {
+    matches!(error, LlmError::AuthenticationFailed)
+}
+
+/// Whether an error message describes a prompt exceeding the context window.
+pub fn is_context_length_message(message: &str) -> bool {
+    let message = message.to_lowercase();
+    message.contains("maximum context length")
+        || message.contains("context_length_exceeded")
+        || message.contains("prompt is too long")
+        || message.contains("input is too long")
+}
+
+/// Extract `(max, requested)` token counts from a context-length error message.
+///
+/// Recognizes the OpenAI form ("maximum context length is N tokens" followed
+/// by "you requested M tokens" or "resulted in M tokens") and the Anthropic
+/// form ("prompt is too long: M tokens > N maximum"). Unknown values are 0.
+pub fn parse_context_length(message: &str) -> (u32, u32) {
+    fn leading_number(text: &str) -> u32 {
+        let digits: String = text
+            .trim_start()
+            .chars()
+            .take_while(|c| c.is_ascii_digit())
+            .collect();
+        digits.parse().unwrap_or(0)
+    }
+
+    fn number_after(message: &str, marker: &str) -> u32 {
+        message
+            .find(marker)
+            .map(|idx| leading_number(&message[idx + marker.len()..]))
+            .unwrap_or(0)
+    }
+
+    // Anthropic: "prompt is too long: 201234 tokens > 200000 maximum"
+    if let Some((before, after)) = message
+        .find("prompt is too long:")
+        .map(|idx| &message[idx + "prompt is too long:".len()..])
+        .and_then(|rest| rest.split_once('>'))
+    {
+        return (leading_number(after), leading_number(before));
+    }
+
+    let max = number_after(message, "maximum context length is");
+    let requested = match number_after(message, "you requested") {
+        0 => number_after(message, "resulted in"),
+        n => n,
+    };
+    (max, requested)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_http_status() {
+        assert!(matches!(classify_http_status(401, ""), LlmError::AuthenticationFailed));
+        assert!(matches!(classify_http_status(403, ""), LlmError::AuthenticationFailed));
+        assert!(matches!(classify_http_status(498, ""), LlmError::AuthenticationFailed));
+        assert!(matches!(
+            classify_http_status(429, "slow down"),
+            LlmError::RateLimited { retry_after_ms: None }
+        ));
+        assert!(matches!(classify_http_status(503, "busy"), LlmError::Overloaded(_)));
+        assert!(matches!(classify_http_status(529, "busy"), LlmError::Overloaded(_)));
+        assert!(matches!(classify_http_status(400, "bad field"), LlmError::InvalidRequest(_)));
+        assert!(matches!(classify_http_status(422, "bad field"), LlmError::InvalidRequest(_)));
+
+        let err = classify_http_status(500, "boom");
+        assert!(matches!(err, LlmError::Provider { .. }));
+        assert_eq!(err.to_string(), "provider error: HTTP 500: boom");
+    }
+
+    #[test]
+    fn test_classify_http_status_context_length() {
+        let err = classify_http_status(400, "prompt is too long: 201234 tokens > 200000 maximum");
+        assert!(matches!(
+            err,
+            LlmError::ContextLengthExceeded {
+                max: 200_000,
+                requested: 201_234
+            }
+        ));
+    }
+
+    #[test]
+    fn test_classify_error_type() {
+        assert!(matches!(
+            classify_error_type("authentication_error", ""),
+            Some(LlmError::AuthenticationFailed)
+        ));
+        assert!(matches!(
+            classify_error_type("rate_limit_exceeded", ""),
+            Some(LlmError::RateLimited { .. })
+        ));
+        assert!(matches!(
+            classify_error_type("overloaded_error", "busy"),
+            Some(LlmError::Overloaded(_))
+        ));
+        assert!(matches!(
+            classify_error_type("invalid_request_error", "missing field"),
+            Some(LlmError::InvalidRequest(_))
+        ));
+        assert!(classify_error_type("api_error", "oops").is_none());
+    }
+
+    #[test]
+    fn test_status_and_type_agree() {
+        // The same failure reported by status or by error type must map to the same variant.
+        let pairs = [
+            (401, "authentication_error"),
+            (429, "rate_limit_error"),
+            (529, "overloaded_error"),
+            (400, "invalid_request_error"),
+        ];
+        for (status, error_type) in pairs {
+            let by_status = classify_http_status(status, "msg");
+            let by_type = classify_error_type(error_type, "msg").unwrap();
+            assert_eq!(
+                std::mem::discriminant(&by_status),
+                std::mem::discriminant(&by_type),
+                "{status} vs {error_type}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_is_retryable_and_is_auth() {
+        assert!(is_retryable(&LlmError::RateLimited { retry_after_ms: Some(10) }));
+        assert!(is_retryable(&LlmError::Overloaded("busy".to_string())));
+        assert!(!is_retryable(&LlmError::AuthenticationFailed));
+        assert!(!is_retryable(&LlmError::Provider { message: "x".to_string() }));
+
+        assert!(is_auth(&LlmError::AuthenticationFailed));
+        assert!(!is_auth(&LlmError::InvalidRequest("x".to_string())));
+    }
+
+    #[test]
+    fn test_parse_context_length_variants() {
+        assert_eq!(
+            parse_context_length(
+                "This model's maximum context length is 8192 tokens. However, you requested \
+                 8200 tokens (7000 in the messages, 1200 in the completion)."
+            ),
+            (8192, 8200)
+        );
+        assert_eq!(
+            parse_context_length(
+                "This model's maximum context length is 128000 tokens. However, your \
+                 messages resulted in 130512 tokens."
+            ),
+            (128_000, 130_512)
+        );
+        assert_eq!(
+            parse_context_length("prompt is too long: 201234 tokens > 200000 maximum"),
+            (200_000, 201_234)
+        );
+        assert_eq!(parse_context_length("context too long"), (0, 0));
+    }
+}
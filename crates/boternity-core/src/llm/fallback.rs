@@ -11,8 +11,8 @@ use std::time::Instant;
 use futures_util::Stream;
 
 use boternity_types::llm::{
-    CompletionRequest, CompletionResponse, FallbackChainConfig, LlmError, ProviderCostInfo,
-    ProviderStatusInfo, StreamEvent,
+    CompletionRequest, CompletionResponse, FallbackChainConfig, LlmError, ProviderConfig,
+    ProviderCostInfo, ProviderPreference, ProviderStatusInfo, StreamEvent,
 };
 
 use super::box_provider::BoxLlmProvider;
@@ -49,6 +49,52 @@ impl std::fmt::Debug for StreamSelection {
     }
 }
 
+/// Apply a per-bot [`ProviderPreference`] to a chain configuration.
+///
+/// Preferred providers are renumbered to lead the chain (in preference
+/// order); the remaining providers keep their relative order behind them.
+/// With `exclusive`, non-preferred providers are dropped along with their
+/// entries in `providers`. If none of the preferred providers are configured
+/// the chain is left unchanged so the bot still falls back to the global chain.
+pub fn apply_provider_preference(
+    config: &mut FallbackChainConfig,
+    providers: &mut Vec<BoxLlmProvider>,
+    preference: &ProviderPreference,
+) {
+    let rank = |name: &str| preference.preferred.iter().position(|p| p == name);
+
+    if !config.providers.iter().any(|c| rank(&c.name).is_some()) {
+        tracing::warn!(
+            preferred = ?preference.preferred,
+            "No preferred provider is configured; using the global chain"
+        );
+        return;
+    }
+
+    let lead = preference.preferred.len() as u32;
+    let mut pairs: Vec<(ProviderConfig, BoxLlmProvider)> = config
+        .providers
+        .drain(..)
+        .zip(providers.drain(..))
+        .collect();
+
+    if preference.exclusive {
+        pairs.retain(|(cfg, _)| rank(&cfg.name).is_some());
+    }
+
+    for (cfg, _) in &mut pairs {
+        cfg.priority = match rank(&cfg.name) {
+            Some(r) => r as u32,
+            None => lead.saturating_add(cfg.priority),
+        };
+    }
+
+    for (cfg, provider) in pairs {
+        config.providers.push(cfg);
+        providers.push(provider);
+    }
+}
+
 /// Routes LLM requests through multiple providers with automatic failover.
 ///
 /// Providers are ordered by priority. On failure, the chain tries the next
@@ -198,6 +244,7 @@ impl FallbackChain {
     /// Tries providers in priority order. On transient errors (provider down,
     /// rate limited, overloaded), fails over to the next available provider.
     /// Auth and config errors are returned immediately without failover.
+    /// Providers lacking a feature the request needs (e.g. vision) are skipped.
     ///
     /// Returns the response, the name of the provider that handled it, and
     /// an optional failover warning if a non-primary provider was used.
@@ -236,6 +283,19 @@ impl FallbackChain {
                 continue;
             }
 
+            // Skip providers that lack a feature the request needs
+            if let Err(err @ LlmError::Unsupported { .. }) =
+                self.providers[idx].1.check_request(request, false)
+            {
+                tracing::debug!(
+                    provider = %provider_name,
+                    error = %err,
+                    "Provider unsupported, skipping"
+                );
+                last_error = Some(err);
+                continue;
+            }
+
             let start = Instant::now();
             let (_health, provider) = &mut self.providers[idx];
 
@@ -298,7 +358,8 @@ impl FallbackChain {
 
     /// Select a provider for streaming and return its stream.
     ///
-    /// Selects the first available provider by priority and starts its stream.
+    /// Selects the first available provider by priority that supports
+    /// streaming and the request's features, and starts its stream.
     /// Mid-stream failover is not possible -- if the stream errors after starting,
     /// the error is propagated to the caller.
     ///
@@ -308,6 +369,7 @@ impl FallbackChain {
         request: CompletionRequest,
     ) -> Result<StreamSelection, LlmError> {
         let indices = self.sorted_indices();
+        let mut unsupported: Option<LlmError> = None;
 
         for idx in indices {
             if !self.providers[idx].0.is_available() {
@@ -318,6 +380,15 @@ impl FallbackChain {
 
             let provider_name = self.providers[idx].0.name.clone();
             let (_, provider) = &self.providers[idx];
+            if let Err(err @ LlmError::Unsupported { .. }) = provider.check_request(&request, true) {
+                tracing::debug!(
+                    provider = %provider_name,
+                    error = %err,
+                    "Provider unsupported for streaming, skipping"
+                );
+                unsupported = Some(err);
+                continue;
+            }
             let stream = provider.stream(request);
 
             let failover_warning = self.build_failover_warning(&provider_name);
@@ -332,9 +403,9 @@ impl FallbackChain {
             });
         }
 
-        Err(LlmError::Provider {
+        Err(unsupported.unwrap_or(LlmError::Provider {
             message: "All providers in fallback chain are unavailable. Run `bnity provider status` for details.".to_string(),
-        })
+        }))
     }
 
     /// Record a stream success for the named provider.
@@ -367,7 +438,7 @@ impl FallbackChain {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use boternity_types::llm::{ProviderCapabilities, StopReason, Usage};
+    use boternity_types::llm::{ImageSource, Message, ProviderCapabilities, StopReason, Usage};
     use crate::llm::provider::LlmProvider;
     use futures_util::StreamExt;
     use std::future::Future;
@@ -518,6 +589,8 @@ mod tests {
                     priority: *priority,
                     enabled: true,
                     capabilities: default_caps(),
+                    extra_headers: Default::default(),
+                    extra_body: Default::default(),
                 })
                 .collect(),
             rate_limit_queue_timeout_ms: 5000,
@@ -532,6 +605,9 @@ mod tests {
             system: None,
             max_tokens: 100,
             temperature: None,
+            top_p: None,
+            frequency_penalty: None,
+            presence_penalty: None,
             stream: false,
             stop_sequences: None,
             output_config: None,
@@ -540,6 +616,82 @@ mod tests {
 
     // --- Tests ---
 
+    fn three_provider_chain() -> (FallbackChainConfig, Vec<BoxLlmProvider>) {
+        let config = make_config(&[("anthropic", 0), ("openai", 1), ("gemini", 2)]);
+        let providers = vec![
+            BoxLlmProvider::new(MockProvider::ok("anthropic", default_caps())),
+            BoxLlmProvider::new(MockProvider::ok("openai", default_caps())),
+            BoxLlmProvider::new(MockProvider::ok("gemini", default_caps())),
+        ];
+        (config, providers)
+    }
+
+    #[tokio::test]
+    async fn test_provider_preference_leads_chain() {
+        let (mut config, mut providers) = three_provider_chain();
+        let preference = ProviderPreference {
+            preferred: vec!["gemini".to_string()],
+            exclusive: false,
+        };
+        apply_provider_preference(&mut config, &mut providers, &preference);
+
+        let mut chain = FallbackChain::new(config, providers, HashMap::new());
+        assert_eq!(chain.primary_provider_name, "gemini");
+        assert_eq!(chain.providers.len(), 3);
+
+        let result = chain.complete(&test_request()).await.unwrap();
+        assert_eq!(result.provider_name, "gemini");
+        assert!(result.failover_warning.is_none());
+    }
+
+    #[test]
+    fn test_provider_preference_keeps_relative_order_of_rest() {
+        let (mut config, mut providers) = three_provider_chain();
+        let preference = ProviderPreference {
+            preferred: vec!["gemini".to_string()],
+            exclusive: false,
+        };
+        apply_provider_preference(&mut config, &mut providers, &preference);
+
+        let mut order: Vec<(&str, u32)> = config
+            .providers
+            .iter()
+            .map(|c| (c.name.as_str(), c.priority))
+            .collect();
+        order.sort_by_key(|(_, p)| *p);
+        let names: Vec<&str> = order.iter().map(|(n, _)| *n).collect();
+        assert_eq!(names, vec!["gemini", "anthropic", "openai"]);
+    }
+
+    #[test]
+    fn test_provider_preference_exclusive_restricts_chain() {
+        let (mut config, mut providers) = three_provider_chain();
+        let preference = ProviderPreference {
+            preferred: vec!["openai".to_string(), "gemini".to_string()],
+            exclusive: true,
+        };
+        apply_provider_preference(&mut config, &mut providers, &preference);
+
+        assert_eq!(providers.len(), 2);
+        let chain = FallbackChain::new(config, providers, HashMap::new());
+        assert_eq!(chain.primary_provider_name, "openai");
+        assert!(chain.providers.iter().all(|(h, _)| h.name != "anthropic"));
+    }
+
+    #[test]
+    fn test_provider_preference_unknown_falls_back_to_global() {
+        let (mut config, mut providers) = three_provider_chain();
+        let preference = ProviderPreference {
+            preferred: vec!["missing".to_string()],
+            exclusive: true,
+        };
+        apply_provider_preference(&mut config, &mut providers, &preference);
+
+        assert_eq!(providers.len(), 3);
+        let chain = FallbackChain::new(config, providers, HashMap::new());
+        assert_eq!(chain.primary_provider_name, "anthropic");
+    }
+
     #[tokio::test]
     async fn test_happy_path_primary_succeeds() {
         let config = make_config(&[("primary", 0), ("secondary", 1)]);
@@ -579,6 +731,48 @@ mod tests {
             .contains("Switched to secondary"));
     }
 
+    #[tokio::test]
+    async fn test_skips_providers_lacking_required_feature() {
+        let config = make_config(&[("text-only", 0), ("vision", 1)]);
+        let vision_caps = ProviderCapabilities {
+            vision: true,
+            ..default_caps()
+        };
+        let providers = vec![
+            BoxLlmProvider::new(MockProvider::ok("text-only", default_caps())),
+            BoxLlmProvider::new(MockProvider::ok("vision", vision_caps)),
+        ];
+        let mut request = test_request();
+        request.messages.push(
+            Message::from("What is this?".to_string()).with_image(ImageSource::Url {
+                url: "https://example.com/cat.jpg".to_string(),
+            }),
+        );
+
+        let mut chain = FallbackChain::new(config, providers, HashMap::new());
+        let result = chain.complete(&request).await.unwrap();
+        assert_eq!(result.provider_name, "vision");
+        let selection = chain.select_stream(request.clone()).unwrap();
+        assert_eq!(selection.provider_name, "vision");
+        // Skipping is not a failure
+        assert!(chain.providers[0].0.is_available());
+
+        let config = make_config(&[("text-only", 0)]);
+        let providers = vec![BoxLlmProvider::new(MockProvider::ok(
+            "text-only",
+            default_caps(),
+        ))];
+        let mut chain = FallbackChain::new(config, providers, HashMap::new());
+        assert!(matches!(
+            chain.complete(&request).await,
+            Err(LlmError::Unsupported { .. })
+        ));
+        assert!(matches!(
+            chain.select_stream(request),
+            Err(LlmError::Unsupported { .. })
+        ));
+    }
+
     #[tokio::test]
     async fn test_no_failover_on_auth_error() {
         let config = make_config(&[("primary", 0), ("secondary", 1)]);
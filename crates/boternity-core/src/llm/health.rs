@@ -187,7 +187,10 @@ impl ProviderHealth {
     /// - Provider, Stream, RateLimited, Overloaded
     ///
     /// Non-failover errors (request/auth issues -- won't help to try another provider):
-    /// - AuthenticationFailed, InvalidRequest, ContextLengthExceeded
+    /// - AuthenticationFailed, InvalidRequest, ContextLengthExceeded, Unsupported
+    ///
+    /// `FallbackChain` checks for `Unsupported` before calling a provider and
+    /// skips it without counting a failure.
     pub fn is_failover_error(error: &LlmError) -> bool {
         matches!(
             error,
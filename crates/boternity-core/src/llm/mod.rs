@@ -3,12 +3,18 @@
 //! This module defines the core traits and utilities for LLM provider integration:
 //! - `LlmProvider`: RPITIT trait for concrete provider implementations
 //! - `BoxLlmProvider`: Object-safe wrapper for dynamic dispatch
+//! - `errors`: Shared HTTP status / error type classification for providers
+//! - `RetryingProvider`: Retry-with-backoff decorator for transient errors
+//! - `structured`: Streaming validation of structured-output JSON
 //! - `TokenBudget`: Context window allocation management
 
 pub mod box_provider;
+pub mod errors;
 pub mod fallback;
 pub mod health;
 pub mod provider;
 pub mod registry;
+pub mod retry;
+pub mod structured;
 pub mod token_budget;
 pub mod types;
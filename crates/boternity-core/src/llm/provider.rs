@@ -6,7 +6,8 @@
 
 use std::pin::Pin;
 
-use futures_util::Stream;
+use futures_util::{Stream, StreamExt};
+use tokio_util::sync::CancellationToken;
 
 use boternity_types::llm::{
     CompletionRequest, CompletionResponse, LlmError, ProviderCapabilities, StreamEvent, TokenCount,
@@ -41,9 +42,179 @@ pub trait LlmProvider: Send + Sync {
         request: CompletionRequest,
     ) -> Pin<Box<dyn Stream<Item = Result<StreamEvent, LlmError>> + Send + 'static>>;
 
+    /// Send a streaming completion request that stops when `cancel` fires.
+    ///
+    /// Once the token is cancelled the stream ends (without a `Done` event)
+    /// and the underlying request is dropped, closing the connection.
+    /// The default implementation wraps [`stream`](Self::stream) with
+    /// [`cancellable_stream`]; providers that can abort the request more
+    /// directly override it.
+    fn stream_with_cancel(
+        &self,
+        request: CompletionRequest,
+        cancel: CancellationToken,
+    ) -> Pin<Box<dyn Stream<Item = Result<StreamEvent, LlmError>> + Send + 'static>> {
+        cancellable_stream(self.stream(request), cancel)
+    }
+
     /// Count the tokens in a request without sending it to the LLM.
     fn count_tokens(
         &self,
         request: &CompletionRequest,
     ) -> impl std::future::Future<Output = Result<TokenCount, LlmError>> + Send;
 }
+
+/// Wrap a provider stream so it ends as soon as `cancel` fires.
+///
+/// Cancellation is checked before every item, so a cancelled token wins over
+/// an already-ready delta. The inner stream is dropped when cancelled rather
+/// than left un-polled, releasing the underlying HTTP connection.
+pub fn cancellable_stream(
+    inner: Pin<Box<dyn Stream<Item = Result<StreamEvent, LlmError>> + Send + 'static>>,
+    cancel: CancellationToken,
+) -> Pin<Box<dyn Stream<Item = Result<StreamEvent, LlmError>> + Send + 'static>> {
+    Box::pin(async_stream::stream! {
+        let mut inner = inner;
+        loop {
+            let next = tokio::select! {
+                biased;
+                _ = cancel.cancelled() => {
+                    tracing::debug!("LLM stream cancelled");
+                    break;
+                }
+                next = inner.next() => next,
+            };
+            match next {
+                Some(item) => yield item,
+                None => break,
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use boternity_types::llm::{CompletionResponse, StopReason, TokenCount, Usage};
+
+    use super::*;
+
+    /// Emits a text delta every 10ms, forever.
+    struct EndlessProvider {
+        capabilities: ProviderCapabilities,
+    }
+
+    impl EndlessProvider {
+        fn new() -> Self {
+            Self {
+                capabilities: ProviderCapabilities {
+                    streaming: true,
+                    tool_calling: false,
+                    vision: false,
+                    extended_thinking: false,
+                    max_context_tokens: 1000,
+                    max_output_tokens: 100,
+                },
+            }
+        }
+    }
+
+    impl LlmProvider for EndlessProvider {
+        fn name(&self) -> &str {
+            "endless"
+        }
+
+        fn capabilities(&self) -> &ProviderCapabilities {
+            &self.capabilities
+        }
+
+        async fn complete(
+            &self,
+            _request: &CompletionRequest,
+        ) -> Result<CompletionResponse, LlmError> {
+            Ok(CompletionResponse {
+                id: "endless".to_string(),
+                content: String::new(),
+                model: "endless".to_string(),
+                stop_reason: StopReason::EndTurn,
+                usage: Usage::default(),
+            })
+        }
+
+        fn stream(
+            &self,
+            _request: CompletionRequest,
+        ) -> Pin<Box<dyn Stream<Item = Result<StreamEvent, LlmError>> + Send + 'static>> {
+            Box::pin(async_stream::stream! {
+                let mut i = 0u32;
+                loop {
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                    yield Ok(StreamEvent::TextDelta { index: 0, text: format!("chunk{i}") });
+                    i += 1;
+                }
+            })
+        }
+
+        async fn count_tokens(&self, _request: &CompletionRequest) -> Result<TokenCount, LlmError> {
+            Ok(TokenCount { input_tokens: 0 })
+        }
+    }
+
+    fn test_request() -> CompletionRequest {
+        CompletionRequest {
+            model: "endless".to_string(),
+            messages: vec![],
+            system: None,
+            max_tokens: 100,
+            temperature: None,
+            top_p: None,
+            frequency_penalty: None,
+            presence_penalty: None,
+            stream: true,
+            stop_sequences: None,
+            output_config: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stream_with_cancel_stops_promptly() {
+        let provider = EndlessProvider::new();
+        let cancel = CancellationToken::new();
+        let mut stream = provider.stream_with_cancel(test_request(), cancel.clone());
+
+        for _ in 0..3 {
+            let event = stream.next().await.unwrap().unwrap();
+            assert!(matches!(event, StreamEvent::TextDelta { .. }));
+        }
+
+        cancel.cancel();
+        let next = tokio::time::timeout(Duration::from_millis(50), stream.next())
+            .await
+            .expect("stream should end promptly after cancellation");
+        assert!(next.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_stream_with_cancel_before_first_event() {
+        let provider = EndlessProvider::new();
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+
+        let mut stream = provider.stream_with_cancel(test_request(), cancel);
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_cancellable_stream_passes_through_when_not_cancelled() {
+        let inner: Pin<Box<dyn Stream<Item = Result<StreamEvent, LlmError>> + Send>> =
+            Box::pin(futures_util::stream::iter(vec![
+                Ok(StreamEvent::Connected),
+                Ok(StreamEvent::Done),
+            ]));
+        let events: Vec<_> = cancellable_stream(inner, CancellationToken::new())
+            .collect()
+            .await;
+        assert_eq!(events.len(), 2);
+    }
+}
@@ -0,0 +1,392 @@
+//! Retry-with-backoff decorator for LLM providers.
+//!
+//! `RetryingProvider` wraps any [`LlmProvider`] and retries transient
+//! failures (`RateLimited`, `Overloaded`) in place before they reach the
+//! fallback chain. Delays grow exponentially from `base_delay`, but a
+//! provider-supplied `retry_after_ms` always wins. All other errors
+//! propagate immediately.
+
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures_util::{Stream, StreamExt};
+
+use boternity_types::llm::{
+    CompletionRequest, CompletionResponse, LlmError, ProviderCapabilities, StreamEvent, TokenCount,
+};
+
+use super::errors;
+use super::provider::LlmProvider;
+
+/// Retry policy for [`RetryingProvider`].
+#[derive(Debug, Clone)]
+pub struct RetryConfig {
+    /// Total attempts including the first call (1 = no retries).
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubled for each subsequent retry.
+    pub base_delay: Duration,
+    /// Upper bound for any single delay, including `retry_after_ms`.
+    pub max_delay: Duration,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RetryConfig {
+    /// Delay before retry number `retry` (1-based) after `error`.
+    ///
+    /// Honors `RateLimited { retry_after_ms }` when present, otherwise
+    /// `base_delay * 2^(retry - 1)`. Always capped at `max_delay`.
+    pub fn delay_for(&self, retry: u32, error: &LlmError) -> Duration {
+        let delay = match error {
+            LlmError::RateLimited {
+                retry_after_ms: Some(ms),
+            } => Duration::from_millis(*ms),
+            _ => self
+                .base_delay
+                .saturating_mul(2u32.saturating_pow(retry.saturating_sub(1))),
+        };
+        delay.min(self.max_delay)
+    }
+}
+
+/// Decorator that retries transient errors with exponential backoff.
+///
+/// Streams are retried only while nothing but `Connected` has been
+/// received, so callers never see duplicated deltas.
+pub struct RetryingProvider<P> {
+    inner: Arc<P>,
+    config: RetryConfig,
+}
+
+impl<P: LlmProvider + 'static> RetryingProvider<P> {
+    /// Wrap `inner` with the given retry policy.
+    pub fn new(inner: P, config: RetryConfig) -> Self {
+        Self {
+            inner: Arc::new(inner),
+            config,
+        }
+    }
+
+    /// Whether an error is transient and worth retrying on the same provider.
+    pub fn is_retryable(error: &LlmError) -> bool {
+        errors::is_retryable(error)
+    }
+}
+
+impl<P: LlmProvider + 'static> LlmProvider for RetryingProvider<P> {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn capabilities(&self) -> &ProviderCapabilities {
+        self.inner.capabilities()
+    }
+
+    async fn complete(&self, request: &CompletionRequest) -> Result<CompletionResponse, LlmError> {
+        let mut attempt = 1;
+        loop {
+            match self.inner.complete(request).await {
+                Err(e) if Self::is_retryable(&e) && attempt < self.config.max_attempts => {
+                    let delay = self.config.delay_for(attempt, &e);
+                    tracing::warn!(
+                        provider = %self.inner.name(),
+                        attempt,
+                        delay_ms = delay.as_millis() as u64,
+                        error = %e,
+                        "Retrying LLM completion after transient error"
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    fn stream(
+        &self,
+        request: CompletionRequest,
+    ) -> Pin<Box<dyn Stream<Item = Result<StreamEvent, LlmError>> + Send + 'static>> {
+        let inner = Arc::clone(&self.inner);
+        let config = self.config.clone();
+
+        Box::pin(async_stream::stream! {
+            let mut attempt = 1;
+            let mut connected = false;
+            'attempts: loop {
+                let mut stream = inner.stream(request.clone());
+                let mut started = false;
+
+                while let Some(item) = stream.next().await {
+                    match item {
+                        Ok(StreamEvent::Connected) if !started => {
+                            // Forward only the first Connected across attempts.
+                            if !connected {
+                                connected = true;
+                                yield Ok(StreamEvent::Connected);
+                            }
+                        }
+                        Err(e)
+                            if !started
+                                && Self::is_retryable(&e)
+                                && attempt < config.max_attempts =>
+                        {
+                            let delay = config.delay_for(attempt, &e);
+                            tracing::warn!(
+                                provider = %inner.name(),
+                                attempt,
+                                delay_ms = delay.as_millis() as u64,
+                                error = %e,
+                                "Retrying LLM stream after transient error"
+                            );
+                            drop(stream);
+                            tokio::time::sleep(delay).await;
+                            attempt += 1;
+                            continue 'attempts;
+                        }
+                        other => {
+                            started = true;
+                            yield other;
+                        }
+                    }
+                }
+                break;
+            }
+        })
+    }
+
+    async fn count_tokens(&self, request: &CompletionRequest) -> Result<TokenCount, LlmError> {
+        self.inner.count_tokens(request).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::time::Instant;
+
+    use boternity_types::llm::{StopReason, Usage};
+
+    use super::*;
+
+    /// Fails the first `failures` calls with `error`, then succeeds.
+    struct FlakyProvider {
+        calls: Arc<AtomicU32>,
+        failures: u32,
+        error: fn() -> LlmError,
+        capabilities: ProviderCapabilities,
+    }
+
+    impl FlakyProvider {
+        fn new(failures: u32, error: fn() -> LlmError) -> (Self, Arc<AtomicU32>) {
+            let calls = Arc::new(AtomicU32::new(0));
+            let provider = Self {
+                calls: Arc::clone(&calls),
+                failures,
+                error,
+                capabilities: ProviderCapabilities {
+                    streaming: true,
+                    tool_calling: false,
+                    vision: false,
+                    extended_thinking: false,
+                    max_context_tokens: 1000,
+                    max_output_tokens: 100,
+                },
+            };
+            (provider, calls)
+        }
+
+        /// Record a call and return the error if this call should fail.
+        fn next_error(&self) -> Option<LlmError> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            (call < self.failures).then(self.error)
+        }
+    }
+
+    impl LlmProvider for FlakyProvider {
+        fn name(&self) -> &str {
+            "flaky"
+        }
+
+        fn capabilities(&self) -> &ProviderCapabilities {
+            &self.capabilities
+        }
+
+        async fn complete(
+            &self,
+            _request: &CompletionRequest,
+        ) -> Result<CompletionResponse, LlmError> {
+            if let Some(err) = self.next_error() {
+                return Err(err);
+            }
+            Ok(CompletionResponse {
+                id: "resp".to_string(),
+                content: "ok".to_string(),
+                model: "flaky-model".to_string(),
+                stop_reason: StopReason::EndTurn,
+                usage: Usage::default(),
+            })
+        }
+
+        fn stream(
+            &self,
+            _request: CompletionRequest,
+        ) -> Pin<Box<dyn Stream<Item = Result<StreamEvent, LlmError>> + Send + 'static>> {
+            let error = self.next_error();
+            Box::pin(async_stream::stream! {
+                yield Ok(StreamEvent::Connected);
+                match error {
+                    Some(err) => yield Err(err),
+                    None => {
+                        yield Ok(StreamEvent::TextDelta { index: 0, text: "ok".to_string() });
+                        yield Ok(StreamEvent::Done);
+                    }
+                }
+            })
+        }
+
+        async fn count_tokens(&self, _request: &CompletionRequest) -> Result<TokenCount, LlmError> {
+            Ok(TokenCount { input_tokens: 0 })
+        }
+    }
+
+    fn fast_config(max_attempts: u32) -> RetryConfig {
+        RetryConfig {
+            max_attempts,
+            base_delay: Duration::from_millis(20),
+            max_delay: Duration::from_secs(1),
+        }
+    }
+
+    fn test_request() -> CompletionRequest {
+        CompletionRequest {
+            model: "flaky-model".to_string(),
+            messages: vec![],
+            system: None,
+            max_tokens: 100,
+            temperature: None,
+            top_p: None,
+            frequency_penalty: None,
+            presence_penalty: None,
+            stream: false,
+            stop_sequences: None,
+            output_config: None,
+        }
+    }
+
+    #[test]
+    fn test_delay_for_exponential_backoff() {
+        let config = fast_config(5);
+        let err = LlmError::Overloaded("busy".to_string());
+        assert_eq!(config.delay_for(1, &err), Duration::from_millis(20));
+        assert_eq!(config.delay_for(2, &err), Duration::from_millis(40));
+        assert_eq!(config.delay_for(3, &err), Duration::from_millis(80));
+    }
+
+    #[test]
+    fn test_delay_for_honors_retry_after_and_cap() {
+        let config = fast_config(5);
+        let err = LlmError::RateLimited {
+            retry_after_ms: Some(250),
+        };
+        assert_eq!(config.delay_for(1, &err), Duration::from_millis(250));
+
+        let err = LlmError::RateLimited {
+            retry_after_ms: Some(60_000),
+        };
+        assert_eq!(config.delay_for(1, &err), Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_complete_retries_rate_limited_then_succeeds() {
+        let (provider, calls) = FlakyProvider::new(2, || LlmError::RateLimited {
+            retry_after_ms: Some(30),
+        });
+        let retrying = RetryingProvider::new(provider, fast_config(3));
+
+        let start = Instant::now();
+        let response = retrying.complete(&test_request()).await.unwrap();
+        let elapsed = start.elapsed();
+
+        assert_eq!(response.content, "ok");
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        // Two retries, each waiting the provider's retry_after (30ms).
+        assert!(elapsed >= Duration::from_millis(60), "elapsed {elapsed:?}");
+        assert!(elapsed < Duration::from_millis(500), "elapsed {elapsed:?}");
+    }
+
+    #[tokio::test]
+    async fn test_complete_backoff_without_retry_after() {
+        let (provider, calls) = FlakyProvider::new(2, || LlmError::Overloaded("busy".to_string()));
+        let retrying = RetryingProvider::new(provider, fast_config(3));
+
+        let start = Instant::now();
+        retrying.complete(&test_request()).await.unwrap();
+
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        // 20ms + 40ms of exponential backoff.
+        assert!(start.elapsed() >= Duration::from_millis(60));
+    }
+
+    #[tokio::test]
+    async fn test_complete_gives_up_after_max_attempts() {
+        let (provider, calls) = FlakyProvider::new(5, || LlmError::RateLimited {
+            retry_after_ms: Some(1),
+        });
+        let retrying = RetryingProvider::new(provider, fast_config(3));
+
+        let err = retrying.complete(&test_request()).await.unwrap_err();
+        assert!(matches!(err, LlmError::RateLimited { .. }));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_complete_non_retryable_propagates_immediately() {
+        let (provider, calls) = FlakyProvider::new(1, || LlmError::AuthenticationFailed);
+        let retrying = RetryingProvider::new(provider, fast_config(3));
+
+        let err = retrying.complete(&test_request()).await.unwrap_err();
+        assert!(matches!(err, LlmError::AuthenticationFailed));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_stream_retries_rate_limited_then_succeeds() {
+        let (provider, calls) = FlakyProvider::new(2, || LlmError::RateLimited {
+            retry_after_ms: Some(30),
+        });
+        let retrying = RetryingProvider::new(provider, fast_config(3));
+
+        let start = Instant::now();
+        let events: Vec<_> = retrying.stream(test_request()).collect().await;
+        let elapsed = start.elapsed();
+
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        assert!(elapsed >= Duration::from_millis(60), "elapsed {elapsed:?}");
+        assert!(events.iter().all(|e| e.is_ok()));
+        // Connected is forwarded once, followed by the successful attempt.
+        assert!(matches!(events[0], Ok(StreamEvent::Connected)));
+        assert!(matches!(events[1], Ok(StreamEvent::TextDelta { .. })));
+        assert!(matches!(events[2], Ok(StreamEvent::Done)));
+        assert_eq!(events.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_stream_non_retryable_propagates_immediately() {
+        let (provider, calls) = FlakyProvider::new(1, || LlmError::AuthenticationFailed);
+        let retrying = RetryingProvider::new(provider, fast_config(3));
+
+        let events: Vec<_> = retrying.stream(test_request()).collect().await;
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(matches!(events.last(), Some(Err(LlmError::AuthenticationFailed))));
+    }
+}
@@ -0,0 +1,878 @@
+//! Streaming validation of structured-output responses.
+//!
+//! When a request carries an [`OutputConfig`](boternity_types::llm::OutputConfig)
+//! the response text must be JSON matching its schema. Rather than parse the
+//! text once the stream ends, [`StreamingJsonValidator`] checks it as the
+//! deltas arrive: a syntax error, a value of the wrong type, or an unknown
+//! property is reported as soon as the offending character streams in, with
+//! the JSON path where it happened (e.g. `$.steps[2].name`).
+//!
+//! Supported schema keywords: `type`, `properties`, `required`,
+//! `additionalProperties`, `items`, `enum`, `const`, `anyOf`/`oneOf` (by
+//! value type), and local `$ref`s such as `#/$defs/Step`. Other keywords are
+//! ignored.
+
+use std::pin::Pin;
+
+use futures_util::{Stream, StreamExt};
+use serde_json::Value;
+
+use boternity_types::llm::{LlmError, StreamEvent};
+
+/// Upper bound on `$ref` hops when resolving a schema, to stop cycles.
+const MAX_REF_DEPTH: usize = 32;
+
+/// JSON value kinds, as named by JSON Schema's `type` keyword.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Object,
+    Array,
+    String,
+    Number,
+    Boolean,
+    Null,
+}
+
+impl Kind {
+    fn name(self) -> &'static str {
+        match self {
+            Kind::Object => "object",
+            Kind::Array => "array",
+            Kind::String => "string",
+            Kind::Number => "number",
+            Kind::Boolean => "boolean",
+            Kind::Null => "null",
+        }
+    }
+
+    /// Whether a value of this kind can satisfy schema type `ty`.
+    fn matches(self, ty: &str) -> bool {
+        match self {
+            Kind::Number => ty == "number" || ty == "integer",
+            kind => ty == kind.name(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum ObjectState {
+    /// After `{`: a key or `}`.
+    KeyOrEnd,
+    /// After `,`: a key.
+    Key,
+    /// After a key: `:`.
+    Colon,
+    /// After `:`: the value.
+    Value,
+    /// After a value: `,` or `}`.
+    CommaOrEnd,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum ArrayState {
+    /// After `[`: a value or `]`.
+    ValueOrEnd,
+    /// After `,`: a value.
+    Value,
+    /// After a value: `,` or `]`.
+    CommaOrEnd,
+}
+
+#[derive(Debug)]
+enum Frame {
+    Object {
+        schema: Option<Value>,
+        path: String,
+        seen: Vec<String>,
+        key: String,
+        state: ObjectState,
+    },
+    Array {
+        schema: Option<Value>,
+        path: String,
+        len: usize,
+        state: ArrayState,
+    },
+}
+
+/// A scalar token being read.
+#[derive(Debug)]
+enum Token {
+    String {
+        text: String,
+        escape: bool,
+        /// Hex digits of a pending `\uXXXX` escape.
+        unicode: Option<String>,
+        /// A key rather than a value.
+        key: bool,
+    },
+    Number(String),
+    Literal {
+        text: String,
+        expected: &'static str,
+    },
+}
+
+/// Where the next value goes and what it must match.
+struct Slot {
+    schema: Option<Value>,
+    path: String,
+}
+
+/// Validates JSON against a schema incrementally, one chunk at a time.
+///
+/// ```
+/// use boternity_core::llm::structured::StreamingJsonValidator;
+///
+/// let schema = serde_json::json!({
+///     "type": "object",
+///     "properties": { "name": { "type": "string" } },
+///     "additionalProperties": false
+/// });
+/// let mut validator = StreamingJsonValidator::new(schema);
+/// validator.push(r#"{"name": "Lu"#).unwrap();
+/// validator.push(r#"na"}"#).unwrap();
+/// validator.finish().unwrap();
+///
+/// let mut validator = StreamingJsonValidator::new(serde_json::json!({"type": "object"}));
+/// assert!(validator.push("[").is_err());
+/// ```
+pub struct StreamingJsonValidator {
+    root: Value,
+    stack: Vec<Frame>,
+    token: Option<(Token, Slot)>,
+    started: bool,
+    done: bool,
+}
+
+impl StreamingJsonValidator {
+    /// Start validating a response against `schema`.
+    pub fn new(schema: Value) -> Self {
+        Self {
+            root: schema,
+            stack: Vec::new(),
+            token: None,
+            started: false,
+            done: false,
+        }
+    }
+
+    /// Feed the next chunk of response text.
+    ///
+    /// Fails with [`LlmError::Deserialization`] as soon as the text so far
+    /// cannot be the start of a matching document.
+    pub fn push(&mut self, chunk: &str) -> Result<(), LlmError> {
+        for c in chunk.chars() {
+            self.push_char(c)?;
+        }
+        Ok(())
+    }
+
+    /// Check that the text fed so far is one complete, matching value.
+    pub fn finish(&mut self) -> Result<(), LlmError> {
+        // A top-level number has no closing delimiter
+        if matches!(self.token, Some((Token::Number(_), _))) {
+            self.end_token()?;
+        }
+        if self.done {
+            Ok(())
+        } else if self.started {
+            Err(invalid_json(
+                &self.current_path(),
+                "response ended before the JSON was complete",
+            ))
+        } else {
+            Err(invalid_json("$", "response contained no JSON"))
+        }
+    }
+
+    fn push_char(&mut self, c: char) -> Result<(), LlmError> {
+        if self.token.is_some() {
+            if !self.push_token_char(c)? {
+                return Ok(());
+            }
+            // The number ended on a structural character; handle it below
+        }
+
+        if c.is_whitespace() {
+            return Ok(());
+        }
+        if self.done {
+            return Err(invalid_json(
+                "$",
+                &format!("unexpected '{c}' after the JSON value"),
+            ));
+        }
+
+        match self.stack.last_mut() {
+            None => {
+                let slot = Slot {
+                    schema: Some(self.root.clone()),
+                    path: "$".to_string(),
+                };
+                self.started = true;
+                self.begin_value(c, slot)
+            }
+            Some(Frame::Object { state, path, .. }) => match (*state, c) {
+                (ObjectState::KeyOrEnd | ObjectState::Key, '"') => {
+                    let slot = Slot {
+                        schema: None,
+                        path: path.clone(),
+                    };
+                    self.token = Some((
+                        Token::String {
+                            text: String::new(),
+                            escape: false,
+                            unicode: None,
+                            key: true,
+                        },
+                        slot,
+                    ));
+                    Ok(())
+                }
+                (ObjectState::KeyOrEnd | ObjectState::CommaOrEnd, '}') => self.close_container(),
+                (ObjectState::Colon, ':') => {
+                    *state = ObjectState::Value;
+                    Ok(())
+                }
+                (ObjectState::CommaOrEnd, ',') => {
+                    *state = ObjectState::Key;
+                    Ok(())
+                }
+                (ObjectState::Value, _) => {
+                    let slot = self.child_slot();
+                    self.begin_value(c, slot)
+                }
+                (state, _) => {
+                    let expected = match state {
+                        ObjectState::KeyOrEnd => "a key or '}'",
+                        ObjectState::Key => "a key",
+                        ObjectState::Colon => "':'",
+                        ObjectState::CommaOrEnd => "',' or '}'",
+                        ObjectState::Value => unreachable!(),
+                    };
+                    Err(invalid_json(
+                        path,
+                        &format!("expected {expected}, found '{c}'"),
+                    ))
+                }
+            },
+            Some(Frame::Array { state, path, .. }) => match (*state, c) {
+                (ArrayState::ValueOrEnd | ArrayState::CommaOrEnd, ']') => self.close_container(),
+                (ArrayState::CommaOrEnd, ',') => {
+                    *state = ArrayState::Value;
+                    Ok(())
+                }
+                (ArrayState::CommaOrEnd, _) => Err(invalid_json(
+                    path,
+                    &format!("expected ',' or ']', found '{c}'"),
+                )),
+                (ArrayState::ValueOrEnd | ArrayState::Value, _) => {
+                    let slot = self.child_slot();
+                    self.begin_value(c, slot)
+                }
+            },
+        }
+    }
+
+    /// Feed `c` to the token being read. Returns `true` when `c` was not
+    /// consumed (it ended a number) and still needs handling.
+    fn push_token_char(&mut self, c: char) -> Result<bool, LlmError> {
+        let (token, slot) = self.token.as_mut().expect("token in progress");
+        match token {
+            Token::String {
+                text,
+                escape,
+                unicode,
+                ..
+            } => {
+                if let Some(hex) = unicode {
+                    if !c.is_ascii_hexdigit() {
+                        return Err(invalid_json(&slot.path, "invalid \\u escape in string"));
+                    }
+                    hex.push(c);
+                    if hex.len() == 4 {
+                        let code = u32::from_str_radix(hex, 16).unwrap_or(0xFFFD);
+                        text.push(char::from_u32(code).unwrap_or('\u{FFFD}'));
+                        *unicode = None;
+                    }
+                } else if *escape {
+                    *escape = false;
+                    match c {
+                        '"' | '\\' | '/' => text.push(c),
+                        'b' => text.push('\u{8}'),
+                        'f' => text.push('\u{c}'),
+                        'n' => text.push('\n'),
+                        'r' => text.push('\r'),
+                        't' => text.push('\t'),
+                        'u' => *unicode = Some(String::new()),
+                        _ => {
+                            return Err(invalid_json(
+                                &slot.path,
+                                &format!("invalid escape '\\{c}' in string"),
+                            ));
+                        }
+                    }
+                } else if c == '\\' {
+                    *escape = true;
+                } else if c == '"' {
+                    self.end_token()?;
+                } else if c.is_control() {
+                    return Err(invalid_json(
+                        &slot.path,
+                        "unescaped control character in string",
+                    ));
+                } else {
+                    text.push(c);
+                }
+                Ok(false)
+            }
+            Token::Number(text) => {
+                if c.is_ascii_digit() || matches!(c, '.' | 'e' | 'E' | '+' | '-') {
+                    text.push(c);
+                    Ok(false)
+                } else {
+                    self.end_token()?;
+                    Ok(true)
+                }
+            }
+            Token::Literal { text, expected } => {
+                text.push(c);
+                if !expected.starts_with(text.as_str()) {
+                    return Err(invalid_json(
+                        &slot.path,
+                        &format!("invalid literal '{text}'"),
+                    ));
+                }
+                if text.len() == expected.len() {
+                    self.end_token()?;
+                }
+                Ok(false)
+            }
+        }
+    }
+
+    /// Start a value whose first character is `c`, checking its type
+    /// against the slot's schema before any more of it streams in.
+    fn begin_value(&mut self, c: char, slot: Slot) -> Result<(), LlmError> {
+        let kind = match c {
+            '{' => Kind::Object,
+            '[' => Kind::Array,
+            '"' => Kind::String,
+            '-' | '0'..='9' => Kind::Number,
+            't' | 'f' => Kind::Boolean,
+            'n' => Kind::Null,
+            _ => return Err(invalid_json(&slot.path, &format!("unexpected '{c}'"))),
+        };
+        let schema = self.narrow(slot.schema.as_ref(), kind, &slot.path)?;
+        let slot = Slot {
+            schema,
+            path: slot.path,
+        };
+
+        match kind {
+            Kind::Object => self.stack.push(Frame::Object {
+                schema: slot.schema,
+                path: slot.path,
+                seen: Vec::new(),
+                key: String::new(),
+                state: ObjectState::KeyOrEnd,
+            }),
+            Kind::Array => self.stack.push(Frame::Array {
+                schema: slot.schema,
+                path: slot.path,
+                len: 0,
+                state: ArrayState::ValueOrEnd,
+            }),
+            Kind::String => {
+                let token = Token::String {
+                    text: String::new(),
+                    escape: false,
+                    unicode: None,
+                    key: false,
+                };
+                self.token = Some((token, slot));
+            }
+            Kind::Number => self.token = Some((Token::Number(c.to_string()), slot)),
+            Kind::Boolean | Kind::Null => {
+                let expected = match c {
+                    't' => "true",
+                    'f' => "false",
+                    _ => "null",
+                };
+                let token = Token::Literal {
+                    text: c.to_string(),
+                    expected,
+                };
+                self.token = Some((token, slot));
+            }
+        }
+        Ok(())
+    }
+
+    /// Finish the current scalar token.
+    fn end_token(&mut self) -> Result<(), LlmError> {
+        let (token, slot) = self.token.take().expect("token in progress");
+        let value = match token {
+            Token::String {
+                text, key: true, ..
+            } => return self.end_key(text),
+            Token::String { text, .. } => Value::String(text),
+            Token::Number(text) => match serde_json::from_str::<serde_json::Number>(&text) {
+                Ok(number) => Value::Number(number),
+                Err(_) => {
+                    return Err(invalid_json(
+                        &slot.path,
+                        &format!("invalid number '{text}'"),
+                    ));
+                }
+            },
+            Token::Literal { expected, .. } => match expected {
+                "true" => Value::Bool(true),
+                "false" => Value::Bool(false),
+                _ => Value::Null,
+            },
+        };
+
+        if let Some(schema) = &slot.schema {
+            if value.is_f64() && !allows(schema, "number") && allows(schema, "integer") {
+                return Err(mismatch(
+                    &slot.path,
+                    &format!("expected an integer, found {value}"),
+                ));
+            }
+            check_enum(schema, &value, &slot.path)?;
+        }
+        self.end_value();
+        Ok(())
+    }
+
+    /// Record an object key, rejecting properties the schema does not allow.
+    fn end_key(&mut self, key: String) -> Result<(), LlmError> {
+        let root = &self.root;
+        let Some(Frame::Object {
+            schema,
+            path,
+            seen,
+            key: current,
+            state,
+        }) = self.stack.last_mut()
+        else {
+            unreachable!("keys are only read inside objects");
+        };
+
+        if let Some(schema) = schema {
+            let known = schema
+                .get("properties")
+                .and_then(Value::as_object)
+                .is_some_and(|props| props.contains_key(&key));
+            let additional = schema.get("additionalProperties").map(|v| resolve(root, v));
+            if !known && matches!(additional, Some(Value::Bool(false))) {
+                return Err(mismatch(&child_path(path, &key), "property is not allowed"));
+            }
+        }
+        seen.push(key.clone());
+        *current = key;
+        *state = ObjectState::Colon;
+        Ok(())
+    }
+
+    /// Close the innermost object or array.
+    fn close_container(&mut self) -> Result<(), LlmError> {
+        let frame = self.stack.pop().expect("container open");
+        if let Frame::Object {
+            schema: Some(schema),
+            path,
+            seen,
+            ..
+        } = &frame
+        {
+            let required = schema.get("required").and_then(Value::as_array);
+            for name in required.into_iter().flatten().filter_map(Value::as_str) {
+                if !seen.iter().any(|key| key == name) {
+                    return Err(mismatch(
+                        path,
+                        &format!("missing required property '{name}'"),
+                    ));
+                }
+            }
+        }
+        self.end_value();
+        Ok(())
+    }
+
+    /// Move the parent past a finished value.
+    fn end_value(&mut self) {
+        match self.stack.last_mut() {
+            None => self.done = true,
+            Some(Frame::Object { state, .. }) => *state = ObjectState::CommaOrEnd,
+            Some(Frame::Array { state, len, .. }) => {
+                *len += 1;
+                *state = ArrayState::CommaOrEnd;
+            }
+        }
+    }
+
+    /// The schema and path for the next value in the innermost container.
+    fn child_slot(&self) -> Slot {
+        match self.stack.last() {
+            Some(Frame::Object {
+                schema, path, key, ..
+            }) => {
+                let schema = schema.as_ref().and_then(|schema| {
+                    schema
+                        .get("properties")
+                        .and_then(|props| props.get(key))
+                        .or_else(|| schema.get("additionalProperties").filter(|v| v.is_object()))
+                        .cloned()
+                });
+                Slot {
+                    schema,
+                    path: child_path(path, key),
+                }
+            }
+            Some(Frame::Array {
+                schema, path, len, ..
+            }) => Slot {
+                schema: schema
+                    .as_ref()
+                    .and_then(|schema| schema.get("items"))
+                    .filter(|items| items.is_object())
+                    .cloned(),
+                path: format!("{path}[{len}]"),
+            },
+            None => Slot {
+                schema: Some(self.root.clone()),
+                path: "$".to_string(),
+            },
+        }
+    }
+
+    /// Resolve `schema` for a value of `kind`, failing on a type mismatch.
+    ///
+    /// `anyOf`/`oneOf` branches are filtered by type; when exactly one
+    /// remains validation continues with it, otherwise the value is not
+    /// checked further.
+    fn narrow(
+        &self,
+        schema: Option<&Value>,
+        kind: Kind,
+        path: &str,
+    ) -> Result<Option<Value>, LlmError> {
+        let Some(schema) = schema else {
+            return Ok(None);
+        };
+        let schema = resolve(&self.root, schema);
+
+        if let Some(types) = schema_types(schema)
+            && !types.iter().any(|ty| kind.matches(ty))
+        {
+            return Err(mismatch(
+                path,
+                &format!("expected {}, found {}", types.join(" or "), kind.name()),
+            ));
+        }
+
+        let branches = schema
+            .get("anyOf")
+            .or_else(|| schema.get("oneOf"))
+            .and_then(Value::as_array);
+        if let Some(branches) = branches {
+            let matching: Vec<&Value> = branches
+                .iter()
+                .map(|branch| resolve(&self.root, branch))
+                .filter(|branch| {
+                    schema_types(branch).is_none_or(|types| types.iter().any(|ty| kind.matches(ty)))
+                })
+                .collect();
+            return match matching.as_slice() {
+                [] => Err(mismatch(
+                    path,
+                    &format!("no schema variant accepts {}", kind.name()),
+                )),
+                [branch] => Ok(Some((*branch).clone())),
+                _ => Ok(None),
+            };
+        }
+        Ok(Some(schema.clone()))
+    }
+
+    /// Path of the value being read, for errors at end of input.
+    fn current_path(&self) -> String {
+        if let Some((_, slot)) = &self.token {
+            return slot.path.clone();
+        }
+        match self.stack.last() {
+            Some(Frame::Object { path, .. } | Frame::Array { path, .. }) => path.clone(),
+            None => "$".to_string(),
+        }
+    }
+}
+
+/// Follow local `$ref`s (`#/...`) from `schema` to the schema they name.
+fn resolve<'a>(root: &'a Value, mut schema: &'a Value) -> &'a Value {
+    for _ in 0..MAX_REF_DEPTH {
+        let Some(pointer) = schema
+            .get("$ref")
+            .and_then(Value::as_str)
+            .and_then(|r| r.strip_prefix('#'))
+        else {
+            break;
+        };
+        match root.pointer(pointer) {
+            Some(target) => schema = target,
+            None => break,
+        }
+    }
+    schema
+}
+
+/// The types a schema's `type` keyword allows, if it has one.
+fn schema_types(schema: &Value) -> Option<Vec<&str>> {
+    match schema.get("type")? {
+        Value::String(ty) => Some(vec![ty.as_str()]),
+        Value::Array(types) => Some(types.iter().filter_map(Value::as_str).collect()),
+        _ => None,
+    }
+}
+
+/// Whether the schema's `type` keyword names `ty`.
+fn allows(schema: &Value, ty: &str) -> bool {
+    schema_types(schema).is_none_or(|types| types.contains(&ty))
+}
+
+fn check_enum(schema: &Value, value: &Value, path: &str) -> Result<(), LlmError> {
+    if let Some(allowed) = schema.get("enum").and_then(Value::as_array)
+        && !allowed.contains(value)
+    {
+        return Err(mismatch(
+            path,
+            &format!("{value} is not one of the allowed values"),
+        ));
+    }
+    if let Some(expected) = schema.get("const")
+        && expected != value
+    {
+        return Err(mismatch(
+            path,
+            &format!("expected {expected}, found {value}"),
+        ));
+    }
+    Ok(())
+}
+
+fn child_path(path: &str, key: &str) -> String {
+    format!("{path}.{key}")
+}
+
+fn invalid_json(path: &str, reason: &str) -> LlmError {
+    LlmError::Deserialization(format!(
+        "invalid JSON in structured output at {path}: {reason}"
+    ))
+}
+
+fn mismatch(path: &str, reason: &str) -> LlmError {
+    LlmError::Deserialization(format!(
+        "structured output does not match schema at {path}: {reason}"
+    ))
+}
+
+/// Validate the text of a structured-output stream against `schema`.
+///
+/// Events pass through unchanged until the text stops matching; then the
+/// stream yields one [`LlmError::Deserialization`] and ends, dropping the
+/// provider stream. On `Done` the text must be a complete value.
+pub fn validate_json_stream(
+    inner: Pin<Box<dyn Stream<Item = Result<StreamEvent, LlmError>> + Send + 'static>>,
+    schema: Value,
+) -> Pin<Box<dyn Stream<Item = Result<StreamEvent, LlmError>> + Send + 'static>> {
+    Box::pin(async_stream::stream! {
+        let mut inner = inner;
+        let mut validator = StreamingJsonValidator::new(schema);
+        while let Some(item) = inner.next().await {
+            let checked = match &item {
+                Ok(StreamEvent::TextDelta { text, .. }) => validator.push(text),
+                Ok(StreamEvent::Done) => validator.finish(),
+                _ => Ok(()),
+            };
+            if let Err(e) = checked {
+                tracing::warn!(error = %e, "Structured output failed validation mid-stream");
+                yield Err(e);
+                break;
+            }
+            yield item;
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string" },
+                "count": { "type": "integer" },
+                "mood": { "enum": ["calm", "curious"] },
+                "steps": { "type": "array", "items": { "$ref": "#/$defs/Step" } }
+            },
+            "required": ["name", "steps"],
+            "additionalProperties": false,
+            "$defs": {
+                "Step": {
+                    "type": "object",
+                    "properties": { "title": { "type": "string" }, "done": { "type": "boolean" } },
+                    "required": ["title"],
+                    "additionalProperties": false
+                }
+            }
+        })
+    }
+
+    /// Feed `text` one character at a time, returning the error and how
+    /// many characters had been accepted before it.
+    fn first_error(text: &str) -> Option<(usize, String)> {
+        let mut validator = StreamingJsonValidator::new(schema());
+        for (i, c) in text.chars().enumerate() {
+            if let Err(e) = validator.push(&c.to_string()) {
+                return Some((i, e.to_string()));
+            }
+        }
+        validator
+            .finish()
+            .err()
+            .map(|e| (text.len(), e.to_string()))
+    }
+
+    #[test]
+    fn test_valid_document_in_any_chunking() {
+        let text = r#"{"name": "Luna \"the\" bot", "count": 3, "mood": "calm",
+            "steps": [{"title": "plan", "done": true}, {"title": "bé"}]}"#;
+        assert_eq!(first_error(text), None);
+
+        let chars: Vec<char> = text.chars().collect();
+        let mut validator = StreamingJsonValidator::new(schema());
+        for chunk in chars.chunks(7) {
+            validator.push(&chunk.iter().collect::<String>()).unwrap();
+        }
+        validator.finish().unwrap();
+    }
+
+    #[test]
+    fn test_type_mismatch_detected_at_first_character() {
+        let text = r#"{"name": "Luna", "count": "three", "steps": []}"#;
+        let (at, err) = first_error(text).unwrap();
+        assert_eq!(at, text.find("\"three").unwrap());
+        assert!(
+            err.contains("at $.count: expected integer, found string"),
+            "{err}"
+        );
+
+        let (_, err) = first_error(r#"{"name": "a", "count": 2.5, "steps": []}"#).unwrap();
+        assert!(err.contains("expected an integer"), "{err}");
+    }
+
+    #[test]
+    fn test_schema_violations_report_path() {
+        let text = r#"{"name": "a", "steps": [{"title": "x"}, {"title": "y", "extra": 1}]}"#;
+        let (at, err) = first_error(text).unwrap();
+        // Rejected once the key is read, before its value
+        assert_eq!(at, text.find("\"extra\"").unwrap() + "\"extra\"".len() - 1);
+        assert!(
+            err.contains("at $.steps[1].extra: property is not allowed"),
+            "{err}"
+        );
+
+        let (_, err) = first_error(r#"{"name": "a", "steps": [{"done": false}]}"#).unwrap();
+        assert!(
+            err.contains("at $.steps[0]: missing required property 'title'"),
+            "{err}"
+        );
+
+        let (_, err) = first_error(r#"{"name": "a", "mood": "angry", "steps": []}"#).unwrap();
+        assert!(err.contains("at $.mood"), "{err}");
+    }
+
+    #[test]
+    fn test_invalid_and_incomplete_json() {
+        let (at, err) = first_error(r#"{"name": "a",, "steps": []}"#).unwrap();
+        assert_eq!(at, 13);
+        assert!(
+            err.starts_with("deserialization error: invalid JSON"),
+            "{err}"
+        );
+
+        let (_, err) = first_error(r#"{"name": "a", "steps": [tru"#).unwrap();
+        assert!(err.contains("ended before the JSON was complete"), "{err}");
+
+        assert!(first_error(r#"Sure! {"name": "a"}"#).is_some());
+        assert!(first_error(r#"{"name": "a", "steps": []} trailing"#).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_stream_stops_at_first_violation() {
+        let events: Vec<Result<StreamEvent, LlmError>> = vec![
+            Ok(StreamEvent::Connected),
+            Ok(StreamEvent::TextDelta {
+                index: 0,
+                text: r#"{"name": "#.to_string(),
+            }),
+            Ok(StreamEvent::TextDelta {
+                index: 0,
+                text: "42, ".to_string(),
+            }),
+            Ok(StreamEvent::TextDelta {
+                index: 0,
+                text: r#""steps": []}"#.to_string(),
+            }),
+            Ok(StreamEvent::Done),
+        ];
+        let stream = validate_json_stream(Box::pin(futures_util::stream::iter(events)), schema());
+        let seen: Vec<_> = stream.collect().await;
+
+        assert_eq!(seen.len(), 3);
+        assert!(matches!(seen[1], Ok(StreamEvent::TextDelta { .. })));
+        match &seen[2] {
+            Err(LlmError::Deserialization(msg)) => assert!(msg.contains("$.name"), "{msg}"),
+            other => panic!("expected a deserialization error, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stream_passes_valid_output_through() {
+        let events: Vec<Result<StreamEvent, LlmError>> = vec![
+            Ok(StreamEvent::TextDelta {
+                index: 0,
+                text: r#"{"name": "a", "#.to_string(),
+            }),
+            Ok(StreamEvent::TextDelta {
+                index: 0,
+                text: r#""steps": []}"#.to_string(),
+            }),
+            Ok(StreamEvent::Done),
+        ];
+        let stream = validate_json_stream(Box::pin(futures_util::stream::iter(events)), schema());
+        let seen: Vec<_> = stream.collect().await;
+        assert_eq!(seen.len(), 3);
+        assert!(seen.iter().all(Result::is_ok));
+
+        // A truncated response fails when the stream completes
+        let events: Vec<Result<StreamEvent, LlmError>> = vec![
+            Ok(StreamEvent::TextDelta {
+                index: 0,
+                text: r#"{"name": "a""#.to_string(),
+            }),
+            Ok(StreamEvent::Done),
+        ];
+        let stream = validate_json_stream(Box::pin(futures_util::stream::iter(events)), schema());
+        let seen: Vec<_> = stream.collect().await;
+        assert!(matches!(
+            seen.last(),
+            Some(Err(LlmError::Deserialization(_)))
+        ));
+    }
+}
@@ -58,6 +58,11 @@ impl TokenBudget {
     }
 }
 
+/// Rough token count for text (~4 characters per token, rounded up).
+pub fn estimate_tokens(text: &str) -> u32 {
+    text.len().div_ceil(4) as u32
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -88,6 +93,13 @@ mod tests {
         assert!(budget.should_summarize(70_000));
     }
 
+    #[test]
+    fn test_estimate_tokens() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abcd"), 1);
+        assert_eq!(estimate_tokens("abcde"), 2);
+    }
+
     #[test]
     fn test_from_capabilities() {
         let caps = ProviderCapabilities {
@@ -80,3 +80,22 @@ impl BoxEmbedder {
         self.inner.dimension_dyn()
     }
 }
+
+/// `BoxEmbedder` is itself an `Embedder`, so generic consumers (e.g. the file
+/// indexer) can use whichever embedder was selected at runtime.
+impl Embedder for BoxEmbedder {
+    fn embed(
+        &self,
+        texts: &[String],
+    ) -> impl Future<Output = Result<Vec<Vec<f32>>, RepositoryError>> + Send {
+        self.inner.embed_boxed(texts)
+    }
+
+    fn model_name(&self) -> &str {
+        self.inner.model_name_dyn()
+    }
+
+    fn dimension(&self) -> usize {
+        self.inner.dimension_dyn()
+    }
+}
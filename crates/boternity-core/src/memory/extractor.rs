@@ -6,15 +6,20 @@
 //!
 //! Failed JSON parsing logs a warning and returns an empty vector -- extraction
 //! failures should be queued for retry (via `pending_memory_extractions`), not
-//! silently dropped.
+//! silently dropped. [`new_pending_extraction`] and [`record_extraction_failure`]
+//! build and reschedule those markers.
+//!
+//! [`find_superseded`] detects when a newly extracted fact updates an existing
+//! one ("User lives in NYC" -> "User lives in Berlin") so the old memory can be
+//! marked `superseded_by` the new one and dropped from recall.
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use serde::Deserialize;
 use uuid::Uuid;
 
 use boternity_types::chat::ChatMessage;
 use boternity_types::llm::{CompletionRequest, LlmError, Message, MessageRole};
-use boternity_types::memory::{MemoryCategory, MemoryEntry};
+use boternity_types::memory::{MemoryCategory, MemoryEntry, PendingExtraction};
 
 use crate::llm::box_provider::BoxLlmProvider;
 
@@ -91,16 +96,13 @@ impl SessionMemoryExtractor {
             return Ok(Vec::new());
         }
 
-        let request = CompletionRequest {
-            model: String::new(), // Provider uses its default model
-            messages: messages.to_vec(),
-            system: Some(EXTRACTION_SYSTEM_PROMPT.to_string()),
-            max_tokens: 2048,
-            temperature: Some(0.0),
-            stream: false,
-            stop_sequences: None,
-            output_config: None,
-        };
+        // The provider uses its default model
+        let request = CompletionRequest::builder(String::new())
+            .system(EXTRACTION_SYSTEM_PROMPT)
+            .messages(messages.to_vec())
+            .max_tokens(2048)
+            .temperature(0.0)
+            .build()?;
 
         let response = provider.complete(&request).await?;
 
@@ -182,13 +184,14 @@ impl SessionMemoryExtractor {
     ) -> Result<Vec<MemoryEntry>, LlmError> {
         let llm_messages: Vec<Message> = messages
             .iter()
+            // Tool output is not conversation; it would also need the
+            // matching call to be valid in a request.
+            .filter(|m| m.role != MessageRole::Tool)
             .map(|m| Message {
-                role: match m.role {
-                    boternity_types::llm::MessageRole::System => MessageRole::System,
-                    boternity_types::llm::MessageRole::User => MessageRole::User,
-                    boternity_types::llm::MessageRole::Assistant => MessageRole::Assistant,
-                },
+                role: m.role.clone(),
                 content: m.content.clone(),
+                tool: None,
+                images: Vec::new(),
             })
             .collect();
 
@@ -196,10 +199,143 @@ impl SessionMemoryExtractor {
     }
 }
 
+/// Extraction attempts before a pending job is abandoned (the repository only
+/// returns jobs with fewer attempts).
+pub const MAX_EXTRACTION_ATTEMPTS: u32 = 3;
+
+/// Delay before retrying a failed extraction; doubles with each attempt.
+const RETRY_BASE_DELAY_SECS: i64 = 60;
+
+/// A marker recording that `session_id` still needs memory extraction.
+///
+/// Saved before an extraction starts and deleted once it succeeds, so a crash
+/// or failure in between leaves it behind to be retried. Due immediately.
+pub fn new_pending_extraction(bot_id: Uuid, session_id: Uuid) -> PendingExtraction {
+    let now = Utc::now();
+    PendingExtraction {
+        id: Uuid::now_v7(),
+        session_id,
+        bot_id,
+        attempt_count: 0,
+        last_attempt_at: None,
+        next_attempt_at: now,
+        error_message: None,
+        created_at: now,
+    }
+}
+
+/// Record a failed extraction attempt and schedule the next one with
+/// exponential backoff (1, 2, 4... minutes).
+pub fn record_extraction_failure(
+    pending: &mut PendingExtraction,
+    error: &str,
+    now: DateTime<Utc>,
+) {
+    let delay = RETRY_BASE_DELAY_SECS << pending.attempt_count.min(10);
+    pending.attempt_count += 1;
+    pending.last_attempt_at = Some(now);
+    pending.next_attempt_at = now + chrono::Duration::seconds(delay);
+    pending.error_message = Some(error.to_string());
+}
+
+/// Single-valued predicates: a subject can only hold one value at a time.
+///
+/// Each pattern maps to a canonical predicate so "moved to" updates "lives in".
+/// Multi-valued predicates ("likes", "uses", "is") are deliberately absent --
+/// "User likes tea" does not contradict "User likes coffee".
+const SINGLE_VALUED_PREDICATES: &[(&str, &str)] = &[
+    (" lives in ", "lives in"),
+    (" lives at ", "lives in"),
+    (" moved to ", "lives in"),
+    (" relocated to ", "lives in"),
+    (" is based in ", "lives in"),
+    (" is located in ", "lives in"),
+    (" works at ", "works at"),
+    (" works for ", "works at"),
+    (" works as ", "works as"),
+    (" is called ", "is called"),
+    (" is named ", "is called"),
+    ("'s name is ", "'s name is"),
+    (" name is ", "'s name is"),
+    ("'s timezone is ", "'s timezone is"),
+    ("'s favorite ", "'s favorite"),
+    ("'s favourite ", "'s favorite"),
+];
+
+/// Split a fact into a `(slot, value)` pair around a single-valued predicate.
+///
+/// The slot is the normalized subject plus canonical predicate (plus the
+/// attribute for "'s favorite X is"); the value is whatever follows. Returns
+/// `None` when the fact has no recognized predicate.
+fn fact_slot(fact: &str) -> Option<(String, String)> {
+    let normalized = fact
+        .trim()
+        .trim_end_matches(['.', '!', ';'])
+        .replace('\u{2019}', "'")
+        .to_lowercase();
+    let normalized = format!(" {}", normalized.split_whitespace().collect::<Vec<_>>().join(" "));
+
+    let (idx, pattern, canonical) = SINGLE_VALUED_PREDICATES
+        .iter()
+        .filter_map(|(pattern, canonical)| normalized.find(pattern).map(|i| (i, *pattern, *canonical)))
+        .min_by_key(|(i, _, _)| *i)?;
+
+    let subject = normalized[..idx].trim();
+    let subject = subject.strip_prefix("the ").unwrap_or(subject);
+    let mut rest = normalized[idx + pattern.len()..].trim();
+    let mut predicate = canonical.to_string();
+    if canonical == "'s favorite" {
+        // "'s favorite color is blue" -> slot includes "color"
+        let (attribute, value) = rest.split_once(" is ")?;
+        predicate = format!("{canonical} {attribute} is");
+        rest = value.trim();
+    }
+
+    if subject.is_empty() || rest.is_empty() {
+        return None;
+    }
+    let separator = if predicate.starts_with('\'') { "" } else { " " };
+    Some((format!("{subject}{separator}{predicate}"), rest.to_string()))
+}
+
+/// Find the existing memory that `new` supersedes, if any.
+///
+/// A memory is superseded when both facts assign a value to the same subject
+/// and single-valued predicate but the values differ. Memories that are
+/// already superseded, or belong to another bot, are ignored.
+pub fn find_superseded(existing: &[MemoryEntry], new: &MemoryEntry) -> Option<Uuid> {
+    let (slot, value) = fact_slot(&new.fact)?;
+    existing
+        .iter()
+        .filter(|e| e.id != new.id && e.bot_id == new.bot_id && e.superseded_by.is_none())
+        .find(|e| {
+            fact_slot(&e.fact).is_some_and(|(other_slot, other_value)| {
+                other_slot == slot && other_value != value
+            })
+        })
+        .map(|e| e.id)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_record_extraction_failure_backs_off() {
+        let mut pending = new_pending_extraction(Uuid::now_v7(), Uuid::now_v7());
+        let now = Utc::now();
+
+        record_extraction_failure(&mut pending, "rate limited", now);
+        assert_eq!(pending.attempt_count, 1);
+        assert_eq!(pending.last_attempt_at, Some(now));
+        assert_eq!(pending.next_attempt_at, now + chrono::Duration::seconds(60));
+        assert_eq!(pending.error_message.as_deref(), Some("rate limited"));
+
+        record_extraction_failure(&mut pending, "timeout", now);
+        assert_eq!(pending.attempt_count, 2);
+        assert_eq!(pending.next_attempt_at, now + chrono::Duration::seconds(120));
+    }
+
     #[test]
     fn test_raw_memory_entry_deserialize() {
         let json = r#"[
@@ -244,4 +380,67 @@ mod tests {
         assert!(EXTRACTION_SYSTEM_PROMPT.contains("\"importance\""));
         assert!(EXTRACTION_SYSTEM_PROMPT.contains("empty array: []"));
     }
+
+    fn memory(bot_id: Uuid, fact: &str) -> MemoryEntry {
+        MemoryEntry {
+            id: Uuid::now_v7(),
+            bot_id,
+            session_id: Uuid::now_v7(),
+            fact: fact.to_string(),
+            category: MemoryCategory::Fact,
+            importance: 3,
+            source_message_id: None,
+            superseded_by: None,
+            created_at: Utc::now(),
+            is_manual: false,
+            source_agent_id: None,
+        }
+    }
+
+    #[test]
+    fn test_fact_slot() {
+        assert_eq!(
+            fact_slot("User lives in NYC."),
+            Some(("user lives in".to_string(), "nyc".to_string()))
+        );
+        assert_eq!(
+            fact_slot("User moved to Berlin"),
+            Some(("user lives in".to_string(), "berlin".to_string()))
+        );
+        assert_eq!(
+            fact_slot("User's favorite color is blue"),
+            Some(("user's favorite color is".to_string(), "blue".to_string()))
+        );
+        assert_eq!(fact_slot("User likes coffee"), None);
+    }
+
+    #[test]
+    fn test_find_superseded_location_change() {
+        let bot_id = Uuid::now_v7();
+        let nyc = memory(bot_id, "User lives in NYC");
+        let prefs = memory(bot_id, "User prefers dark mode");
+        let berlin = memory(bot_id, "User lives in Berlin");
+
+        assert_eq!(find_superseded(&[prefs.clone(), nyc.clone()], &berlin), Some(nyc.id));
+        assert_eq!(
+            find_superseded(&[nyc.clone()], &memory(bot_id, "The user moved to Berlin.")),
+            Some(nyc.id)
+        );
+        // Restating the same value is not an update
+        assert_eq!(find_superseded(&[nyc], &memory(bot_id, "The user lives in nyc.")), None);
+    }
+
+    #[test]
+    fn test_find_superseded_ignores_unrelated_and_stale() {
+        let bot_id = Uuid::now_v7();
+        let coffee = memory(bot_id, "User likes coffee");
+        assert_eq!(find_superseded(&[coffee], &memory(bot_id, "User likes tea")), None);
+
+        let mut old = memory(bot_id, "User works at Acme");
+        old.superseded_by = Some(Uuid::now_v7());
+        assert_eq!(find_superseded(&[old], &memory(bot_id, "User works for Globex")), None);
+
+        let other_bot = memory(Uuid::now_v7(), "User works at Acme");
+        assert_eq!(find_superseded(&[other_bot], &memory(bot_id, "User works at Globex")), None);
+    }
 }
@@ -3,13 +3,16 @@
 //! This module defines the `MemoryRepository` trait that the infrastructure
 //! layer implements for long-term memory and pending extraction CRUD,
 //! the `SessionMemoryExtractor` that uses an LLM to identify key
-//! facts worth persisting across sessions, and the `BoxVectorMemoryStore`
-//! and `BoxEmbedder` for type-erased dynamic dispatch of RPITIT traits.
+//! facts worth persisting across sessions, the `SimilarityPolicy` that decides
+//! which recalled memories are close enough to use, and the
+//! `BoxVectorMemoryStore` and `BoxEmbedder` for type-erased dynamic dispatch
+//! of RPITIT traits.
 
 pub mod box_embedder;
 pub mod box_vector;
 pub mod embedder;
 pub mod extractor;
 pub mod shared;
+pub mod similarity;
 pub mod store;
 pub mod vector;
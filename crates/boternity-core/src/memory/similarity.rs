@@ -0,0 +1,141 @@
+//! Similarity cutoffs for memory recall.
+//!
+//! A fixed `min_similarity` behaves differently per embedding model: scores
+//! from one model cluster high, another's spread low. [`SimilarityPolicy::Adaptive`]
+//! instead looks at the similarities a search returned and cuts at the elbow,
+//! the largest drop that clearly separates a cluster of close matches from
+//! the rest.
+
+use boternity_types::memory::RankedMemory;
+
+/// Lowest similarity the adaptive policy ever recalls.
+const ADAPTIVE_FLOOR: f32 = 0.15;
+
+/// A drop smaller than this is never treated as an elbow.
+const MIN_ELBOW_GAP: f32 = 0.08;
+
+/// An elbow must be this many times the median drop between neighbours.
+const ELBOW_RATIO: f32 = 3.0;
+
+/// How recalled memories are filtered by similarity to the query.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SimilarityPolicy {
+    /// Keep results at or above a fixed similarity.
+    Fixed(f32),
+    /// Keep results above the elbow of the returned similarity distribution,
+    /// or everything above a low floor when there is no clear elbow.
+    Adaptive,
+}
+
+impl SimilarityPolicy {
+    /// The `min_similarity` to pass to the vector store.
+    pub fn search_floor(&self) -> f32 {
+        match self {
+            Self::Fixed(min_similarity) => *min_similarity,
+            Self::Adaptive => ADAPTIVE_FLOOR,
+        }
+    }
+
+    /// The similarity a result needs to be kept, given all candidates'.
+    pub fn cutoff(&self, similarities: &[f32]) -> f32 {
+        match self {
+            Self::Fixed(min_similarity) => *min_similarity,
+            Self::Adaptive => adaptive_cutoff(similarities),
+        }
+    }
+
+    /// Drop results below the cutoff, keeping the rest in order.
+    pub fn apply(&self, results: Vec<RankedMemory>) -> Vec<RankedMemory> {
+        let similarities: Vec<f32> = results.iter().map(|r| 1.0 - r.distance).collect();
+        let cutoff = self.cutoff(&similarities);
+        results
+            .into_iter()
+            .filter(|r| 1.0 - r.distance >= cutoff)
+            .collect()
+    }
+}
+
+/// Find the elbow in `similarities`: the largest drop between neighbours
+/// (sorted high to low) that is both absolutely and relatively large.
+fn adaptive_cutoff(similarities: &[f32]) -> f32 {
+    let mut sorted: Vec<f32> = similarities
+        .iter()
+        .copied()
+        .filter(|s| *s >= ADAPTIVE_FLOOR)
+        .collect();
+    sorted.sort_by(|a, b| b.total_cmp(a));
+    if sorted.len() < 3 {
+        return ADAPTIVE_FLOOR;
+    }
+
+    let gaps: Vec<f32> = sorted.windows(2).map(|pair| pair[0] - pair[1]).collect();
+    let mut elbow = 0;
+    for (i, gap) in gaps.iter().enumerate() {
+        if *gap > gaps[elbow] {
+            elbow = i;
+        }
+    }
+    let largest = gaps[elbow];
+
+    let mut ordered = gaps.clone();
+    ordered.sort_by(f32::total_cmp);
+    let median = ordered[ordered.len() / 2];
+
+    if largest >= MIN_ELBOW_GAP && largest >= ELBOW_RATIO * median {
+        sorted[elbow]
+    } else {
+        ADAPTIVE_FLOOR
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kept(policy: SimilarityPolicy, similarities: &[f32]) -> Vec<f32> {
+        let cutoff = policy.cutoff(similarities);
+        similarities
+            .iter()
+            .copied()
+            .filter(|s| *s >= cutoff)
+            .collect()
+    }
+
+    #[test]
+    fn test_adaptive_keeps_tight_cluster_and_drops_outliers() {
+        let similarities = [0.91, 0.42, 0.88, 0.87, 0.35, 0.86, 0.2];
+        assert_eq!(
+            kept(SimilarityPolicy::Adaptive, &similarities),
+            vec![0.91, 0.88, 0.87, 0.86]
+        );
+
+        // The same shape from a model whose scores run lower
+        let similarities = [0.52, 0.5, 0.49, 0.22, 0.18];
+        assert_eq!(
+            kept(SimilarityPolicy::Adaptive, &similarities),
+            vec![0.52, 0.5, 0.49]
+        );
+    }
+
+    #[test]
+    fn test_adaptive_keeps_evenly_spread_results() {
+        let similarities = [0.8, 0.7, 0.6, 0.5, 0.4, 0.3];
+        assert_eq!(
+            kept(SimilarityPolicy::Adaptive, &similarities),
+            similarities.to_vec()
+        );
+
+        // Too few results to find an elbow; only the floor applies
+        assert_eq!(
+            kept(SimilarityPolicy::Adaptive, &[0.9, 0.3, 0.1]),
+            vec![0.9, 0.3]
+        );
+    }
+
+    #[test]
+    fn test_fixed_policy_uses_its_threshold() {
+        let policy = SimilarityPolicy::Fixed(0.5);
+        assert_eq!(policy.search_floor(), 0.5);
+        assert_eq!(kept(policy, &[0.91, 0.88, 0.42]), vec![0.91, 0.88]);
+    }
+}
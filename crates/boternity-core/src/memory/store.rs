@@ -5,6 +5,7 @@
 
 use boternity_types::error::RepositoryError;
 use boternity_types::memory::{MemoryEntry, PendingExtraction};
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
 /// Repository trait for bot long-term memory persistence.
@@ -18,7 +19,21 @@ pub trait MemoryRepository: Send + Sync {
         entry: &MemoryEntry,
     ) -> impl std::future::Future<Output = Result<(), RepositoryError>> + Send;
 
-    /// Get memories for a bot, ordered by importance DESC, created_at DESC.
+    /// Get a single memory by ID. Returns `None` if it does not exist.
+    fn get_memory(
+        &self,
+        memory_id: &Uuid,
+    ) -> impl std::future::Future<Output = Result<Option<MemoryEntry>, RepositoryError>> + Send;
+
+    /// Update a memory's fact, category, and importance.
+    fn update_memory(
+        &self,
+        entry: &MemoryEntry,
+    ) -> impl std::future::Future<Output = Result<(), RepositoryError>> + Send;
+
+    /// Get current memories for a bot, ordered by importance DESC, created_at DESC.
+    ///
+    /// Superseded memories (those with `superseded_by` set) are excluded.
     fn get_memories(
         &self,
         bot_id: &Uuid,
@@ -31,6 +46,13 @@ pub trait MemoryRepository: Send + Sync {
         memory_id: &Uuid,
     ) -> impl std::future::Future<Output = Result<(), RepositoryError>> + Send;
 
+    /// Mark a memory as superseded by a newer one.
+    fn mark_superseded(
+        &self,
+        memory_id: &Uuid,
+        superseded_by: &Uuid,
+    ) -> impl std::future::Future<Output = Result<(), RepositoryError>> + Send;
+
     /// Delete all memories for a bot. Returns the count of deleted entries.
     fn delete_all_memories(
         &self,
@@ -55,6 +77,13 @@ pub trait MemoryRepository: Send + Sync {
         bot_id: &Uuid,
     ) -> impl std::future::Future<Output = Result<Vec<PendingExtraction>, RepositoryError>> + Send;
 
+    /// Get pending extraction jobs across all bots that are due for a retry
+    /// at `now`, oldest first.
+    fn get_due_pending_extractions(
+        &self,
+        now: DateTime<Utc>,
+    ) -> impl std::future::Future<Output = Result<Vec<PendingExtraction>, RepositoryError>> + Send;
+
     /// Delete a pending extraction job (after successful extraction).
     fn delete_pending_extraction(
         &self,
@@ -1,28 +1,38 @@
 //! Bot-to-bot message bus with direct mailboxes, pub/sub channels, and send-and-wait.
 //!
 //! The `MessageBus` is the runtime hub for inter-bot communication. Each registered
-//! bot gets a bounded `mpsc` mailbox for direct messages. Pub/sub channels use
+//! bot gets a bounded priority [`Mailbox`] for direct messages. Pub/sub channels use
 //! `broadcast` for one-to-many delivery. The `send_and_wait` method supports
 //! synchronous request/response patterns with a configurable timeout.
+//!
+//! Messages that can no longer be delivered, such as ones whose TTL ran out
+//! while queued, go to the bus's [`DeadLetterQueue`] instead.
 
-use std::sync::Arc;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, VecDeque};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
-use boternity_types::message::{BotMessage, MessageRecipient};
+use boternity_types::message::{BotMessage, MessagePriority, MessageRecipient};
+use chrono::{DateTime, Utc};
 use dashmap::DashMap;
 use thiserror::Error;
-use tokio::sync::{broadcast, mpsc, oneshot};
+use tokio::sync::{Notify, broadcast, oneshot};
+use tokio::time::Instant;
 use tracing::{debug, warn};
 use uuid::Uuid;
 
 use super::router::LoopGuard;
 
-/// Buffer size for per-bot direct message mailboxes (mpsc).
+/// Capacity of per-bot direct message mailboxes.
 const DIRECT_BUFFER: usize = 256;
 
 /// Buffer size for pub/sub broadcast channels.
 const BROADCAST_BUFFER: usize = 1024;
 
+/// Dead letters kept before the oldest are discarded.
+const DEAD_LETTER_CAPACITY: usize = 1024;
+
 /// Errors that can occur during message bus operations.
 #[derive(Debug, Error)]
 pub enum MessageError {
@@ -42,6 +52,14 @@ pub enum MessageError {
     #[error("loop detected: {0}")]
     LoopDetected(String),
 
+    /// The message expired before it could be sent; it was dead-lettered.
+    #[error("message {0} expired before delivery")]
+    Expired(Uuid),
+
+    /// The sender is over its per-sender message rate.
+    #[error("bot {sender} is sending too fast; retry in {retry_after:?}")]
+    RateLimited { sender: Uuid, retry_after: Duration },
+
     /// Internal send failure (channel closed, etc.).
     #[error("send failed: {0}")]
     SendFailed(String),
@@ -50,18 +68,20 @@ pub enum MessageError {
 /// Central message bus for bot-to-bot communication.
 ///
 /// Provides three delivery modes:
-/// - **Direct:** One-to-one via per-bot `mpsc` mailboxes.
+/// - **Direct:** One-to-one via per-bot priority [`Mailbox`]es.
 /// - **Pub/sub:** One-to-many via named `broadcast` channels.
 /// - **Send-and-wait:** Synchronous request/response with timeout via `oneshot`.
 pub struct MessageBus {
-    /// Per-bot direct message senders (bot_id -> mpsc sender).
-    direct_senders: DashMap<Uuid, mpsc::Sender<BotMessage>>,
+    /// Per-bot direct message senders (bot_id -> mailbox sender).
+    direct_senders: DashMap<Uuid, MailboxSender>,
     /// Per-channel broadcast senders (channel_name -> broadcast sender).
     channel_senders: DashMap<String, broadcast::Sender<BotMessage>>,
     /// Pending reply channels for send_and_wait (message_id -> oneshot sender).
     reply_channels: DashMap<Uuid, oneshot::Sender<BotMessage>>,
     /// Loop guard for depth and rate limiting.
     loop_guard: Arc<LoopGuard>,
+    /// Undeliverable messages, shared with every mailbox.
+    dead_letters: Arc<DeadLetterQueue>,
 }
 
 impl MessageBus {
@@ -72,15 +92,16 @@ impl MessageBus {
             channel_senders: DashMap::new(),
             reply_channels: DashMap::new(),
             loop_guard,
+            dead_letters: Arc::new(DeadLetterQueue::new(DEAD_LETTER_CAPACITY)),
         }
     }
 
     /// Register a bot and return its message receiver.
     ///
-    /// The returned `mpsc::Receiver` is the bot's mailbox for direct messages.
-    /// If the bot is already registered, the old mailbox is replaced.
-    pub fn register_bot(&self, bot_id: Uuid) -> mpsc::Receiver<BotMessage> {
-        let (tx, rx) = mpsc::channel(DIRECT_BUFFER);
+    /// The returned [`Mailbox`] receives the bot's direct messages. If the
+    /// bot is already registered, the old mailbox is replaced.
+    pub fn register_bot(&self, bot_id: Uuid) -> Mailbox {
+        let (tx, rx) = mailbox(DIRECT_BUFFER, Arc::clone(&self.dead_letters));
         self.direct_senders.insert(bot_id, tx);
         debug!(%bot_id, "registered bot with message bus");
         rx
@@ -105,8 +126,10 @@ impl MessageBus {
     /// Send a direct message (fire-and-forget).
     ///
     /// The message is delivered to the recipient's mailbox. Returns an error
-    /// if the recipient is not registered, the mailbox is full, or the loop
-    /// guard rejects the message.
+    /// if the message has already expired, the recipient is not registered,
+    /// the mailbox is full, the sender is over its rate limit, or the loop
+    /// guard rejects the message. Messages that expire while queued are
+    /// dead-lettered rather than delivered.
     pub async fn send(&self, msg: BotMessage) -> Result<(), MessageError> {
         // Extract recipient bot_id for direct messages
         let recipient_id = match &msg.recipient {
@@ -118,6 +141,9 @@ impl MessageBus {
             }
         };
 
+        let msg = self.reject_expired(msg)?;
+        self.check_sender_rate(msg.sender_bot_id)?;
+
         // Check loop guard
         self.loop_guard
             .check(msg.sender_bot_id, recipient_id)
@@ -129,14 +155,12 @@ impl MessageBus {
             .get(&recipient_id)
             .ok_or(MessageError::NotRegistered(recipient_id))?;
 
-        sender
-            .try_send(msg)
-            .map_err(|e| match e {
-                mpsc::error::TrySendError::Full(_) => MessageError::ChannelFull(recipient_id),
-                mpsc::error::TrySendError::Closed(_) => MessageError::SendFailed(format!(
-                    "mailbox closed for bot {recipient_id}"
-                )),
-            })?;
+        sender.try_send(msg).map_err(|e| match e {
+            MailboxSendError::Full => MessageError::ChannelFull(recipient_id),
+            MailboxSendError::Closed => {
+                MessageError::SendFailed(format!("mailbox closed for bot {recipient_id}"))
+            }
+        })?;
 
         Ok(())
     }
@@ -219,7 +243,8 @@ impl MessageBus {
     /// Publish a message to a named pub/sub channel.
     ///
     /// Delivers to all current subscribers. If the channel does not exist or
-    /// has no subscribers, the message is silently dropped.
+    /// has no subscribers, the message is silently dropped. An already-expired
+    /// message is dead-lettered and returns [`MessageError::Expired`].
     pub fn publish(&self, msg: BotMessage) -> Result<usize, MessageError> {
         let channel_name = match &msg.recipient {
             MessageRecipient::Channel { name } => name.clone(),
@@ -230,6 +255,9 @@ impl MessageBus {
             }
         };
 
+        let msg = self.reject_expired(msg)?;
+        self.check_sender_rate(msg.sender_bot_id)?;
+
         if let Some(sender) = self.channel_senders.get(&channel_name) {
             match sender.send(msg) {
                 Ok(count) => {
@@ -248,6 +276,26 @@ impl MessageBus {
         }
     }
 
+    /// Dead-letter `msg` if it has already expired.
+    fn reject_expired(&self, msg: BotMessage) -> Result<BotMessage, MessageError> {
+        if msg.is_expired_at(Utc::now()) {
+            let id = msg.id;
+            self.dead_letters.push(msg, DeadLetterReason::Expired);
+            return Err(MessageError::Expired(id));
+        }
+        Ok(msg)
+    }
+
+    /// Take one message from the sender's rate budget.
+    fn check_sender_rate(&self, sender: Uuid) -> Result<(), MessageError> {
+        self.loop_guard
+            .check_sender(sender)
+            .map_err(|retry_after| MessageError::RateLimited {
+                sender,
+                retry_after,
+            })
+    }
+
     /// Get the number of registered bots.
     pub fn registered_bot_count(&self) -> usize {
         self.direct_senders.len()
@@ -257,6 +305,11 @@ impl MessageBus {
     pub fn loop_guard(&self) -> &LoopGuard {
         &self.loop_guard
     }
+
+    /// Get access to the dead-letter queue.
+    pub fn dead_letters(&self) -> &DeadLetterQueue {
+        &self.dead_letters
+    }
 }
 
 impl std::fmt::Debug for MessageBus {
@@ -265,6 +318,324 @@ impl std::fmt::Debug for MessageBus {
             .field("registered_bots", &self.direct_senders.len())
             .field("channels", &self.channel_senders.len())
             .field("pending_replies", &self.reply_channels.len())
+            .field("dead_letters", &self.dead_letters.len())
+            .finish()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Dead letters
+// ---------------------------------------------------------------------------
+
+/// Why a message was dead-lettered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeadLetterReason {
+    /// The message's TTL ran out before delivery.
+    Expired,
+}
+
+impl std::fmt::Display for DeadLetterReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeadLetterReason::Expired => write!(f, "expired"),
+        }
+    }
+}
+
+/// A message the bus gave up delivering.
+#[derive(Debug, Clone)]
+pub struct DeadLetter {
+    /// The undelivered message.
+    pub message: BotMessage,
+    /// Why it was not delivered.
+    pub reason: DeadLetterReason,
+    /// When the bus gave up on it.
+    pub dead_lettered_at: DateTime<Utc>,
+}
+
+/// Bounded store of undeliverable messages, oldest first.
+///
+/// When full, the oldest dead letter is discarded to make room.
+#[derive(Debug)]
+pub struct DeadLetterQueue {
+    entries: Mutex<VecDeque<DeadLetter>>,
+    capacity: usize,
+}
+
+impl DeadLetterQueue {
+    /// Create a queue holding at most `capacity` dead letters.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: Mutex::new(VecDeque::new()),
+            capacity: capacity.max(1),
+        }
+    }
+
+    fn entries(&self) -> std::sync::MutexGuard<'_, VecDeque<DeadLetter>> {
+        self.entries.lock().expect("dead-letter lock poisoned")
+    }
+
+    /// Record an undeliverable message.
+    pub fn push(&self, message: BotMessage, reason: DeadLetterReason) {
+        debug!(message_id = %message.id, %reason, "dead-lettered message");
+        let mut entries = self.entries();
+        if entries.len() >= self.capacity {
+            entries.pop_front();
+        }
+        entries.push_back(DeadLetter {
+            message,
+            reason,
+            dead_lettered_at: Utc::now(),
+        });
+    }
+
+    /// Copy of the current dead letters, oldest first.
+    pub fn snapshot(&self) -> Vec<DeadLetter> {
+        self.entries().iter().cloned().collect()
+    }
+
+    /// Remove and return all dead letters, oldest first.
+    pub fn drain(&self) -> Vec<DeadLetter> {
+        self.entries().drain(..).collect()
+    }
+
+    /// Number of dead letters held.
+    pub fn len(&self) -> usize {
+        self.entries().len()
+    }
+
+    /// Whether the queue is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Mailbox
+// ---------------------------------------------------------------------------
+
+/// A bot's direct-message mailbox.
+///
+/// Delivers higher-[`MessagePriority`] messages first and keeps FIFO order
+/// among messages of the same priority, so control traffic is not stuck
+/// behind bulk traffic. Messages whose TTL runs out while queued are moved to
+/// the dead-letter queue instead of being delivered. `recv` returns `None`
+/// once the bus drops the bot's registration and the queue is drained.
+pub struct Mailbox {
+    shared: Arc<MailboxShared>,
+}
+
+/// Sending half of a mailbox, held by the bus.
+struct MailboxSender {
+    shared: Arc<MailboxShared>,
+}
+
+struct MailboxShared {
+    state: Mutex<MailboxState>,
+    /// Wakes the receiver when a message arrives or the sender closes.
+    notify: Notify,
+    capacity: usize,
+    dead_letters: Arc<DeadLetterQueue>,
+}
+
+struct MailboxState {
+    queue: BinaryHeap<Queued>,
+    /// Insertion counter; breaks ties within a priority.
+    next_seq: u64,
+    sender_closed: bool,
+    receiver_closed: bool,
+}
+
+/// A queued message, ordered by priority then insertion (earliest first).
+struct Queued {
+    priority: MessagePriority,
+    seq: u64,
+    /// Runtime instant the message expires at, if it has a TTL.
+    deadline: Option<Instant>,
+    msg: BotMessage,
+}
+
+impl Queued {
+    fn is_expired(&self, now: Instant) -> bool {
+        self.deadline.is_some_and(|deadline| now >= deadline)
+    }
+}
+
+impl PartialEq for Queued {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Queued {}
+
+impl PartialOrd for Queued {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Queued {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // BinaryHeap pops the greatest: highest priority, then lowest seq.
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum MailboxSendError {
+    Full,
+    Closed,
+}
+
+/// Create a mailbox holding at most `capacity` undelivered messages.
+fn mailbox(capacity: usize, dead_letters: Arc<DeadLetterQueue>) -> (MailboxSender, Mailbox) {
+    let shared = Arc::new(MailboxShared {
+        state: Mutex::new(MailboxState {
+            queue: BinaryHeap::new(),
+            next_seq: 0,
+            sender_closed: false,
+            receiver_closed: false,
+        }),
+        notify: Notify::new(),
+        capacity,
+        dead_letters,
+    });
+    (
+        MailboxSender {
+            shared: Arc::clone(&shared),
+        },
+        Mailbox { shared },
+    )
+}
+
+impl MailboxShared {
+    fn state(&self) -> std::sync::MutexGuard<'_, MailboxState> {
+        self.state.lock().expect("mailbox lock poisoned")
+    }
+
+    /// Pop the next unexpired message, dead-lettering expired ones on the way.
+    fn pop(&self, state: &mut MailboxState) -> Option<BotMessage> {
+        let now = Instant::now();
+        while let Some(queued) = state.queue.pop() {
+            if queued.is_expired(now) {
+                self.dead_letters
+                    .push(queued.msg, DeadLetterReason::Expired);
+                continue;
+            }
+            return Some(queued.msg);
+        }
+        None
+    }
+
+    /// Move every expired message to the dead-letter queue.
+    fn purge_expired(&self, state: &mut MailboxState) {
+        let now = Instant::now();
+        if !state.queue.iter().any(|queued| queued.is_expired(now)) {
+            return;
+        }
+        let (expired, live): (Vec<_>, Vec<_>) = std::mem::take(&mut state.queue)
+            .into_vec()
+            .into_iter()
+            .partition(|queued| queued.is_expired(now));
+        state.queue = live.into();
+        for queued in expired {
+            self.dead_letters
+                .push(queued.msg, DeadLetterReason::Expired);
+        }
+    }
+}
+
+impl MailboxSender {
+    fn try_send(&self, msg: BotMessage) -> Result<(), MailboxSendError> {
+        let mut state = self.shared.state();
+        if state.receiver_closed {
+            return Err(MailboxSendError::Closed);
+        }
+        if state.queue.len() >= self.shared.capacity {
+            self.shared.purge_expired(&mut state);
+            if state.queue.len() >= self.shared.capacity {
+                return Err(MailboxSendError::Full);
+            }
+        }
+        // Track expiry on the runtime clock so it follows tokio's time.
+        let deadline = msg.expires_at.map(|expires_at| {
+            let remaining = (expires_at - Utc::now()).to_std().unwrap_or(Duration::ZERO);
+            Instant::now() + remaining
+        });
+        let seq = state.next_seq;
+        state.next_seq += 1;
+        state.queue.push(Queued {
+            priority: msg.priority,
+            seq,
+            deadline,
+            msg,
+        });
+        drop(state);
+        self.shared.notify.notify_one();
+        Ok(())
+    }
+}
+
+impl Drop for MailboxSender {
+    fn drop(&mut self) {
+        self.shared.state().sender_closed = true;
+        self.shared.notify.notify_one();
+    }
+}
+
+impl Mailbox {
+    /// Wait for the next message.
+    ///
+    /// Returns `None` when the bot has been unregistered (or re-registered
+    /// with a new mailbox) and no messages remain.
+    pub async fn recv(&mut self) -> Option<BotMessage> {
+        loop {
+            {
+                let mut state = self.shared.state();
+                if let Some(msg) = self.shared.pop(&mut state) {
+                    return Some(msg);
+                }
+                if state.sender_closed {
+                    return None;
+                }
+            }
+            self.shared.notify.notified().await;
+        }
+    }
+
+    /// Take the next message if one is waiting.
+    pub fn try_recv(&mut self) -> Option<BotMessage> {
+        let mut state = self.shared.state();
+        self.shared.pop(&mut state)
+    }
+
+    /// Number of undelivered, unexpired messages.
+    pub fn len(&self) -> usize {
+        let mut state = self.shared.state();
+        self.shared.purge_expired(&mut state);
+        state.queue.len()
+    }
+
+    /// Whether no messages are waiting.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Drop for Mailbox {
+    fn drop(&mut self) {
+        self.shared.state().receiver_closed = true;
+    }
+}
+
+impl std::fmt::Debug for Mailbox {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Mailbox")
+            .field("pending", &self.len())
+            .field("capacity", &self.shared.capacity)
             .finish()
     }
 }
@@ -417,6 +788,207 @@ mod tests {
         assert!(matches!(result, Err(MessageError::SendFailed(_))));
     }
 
+    #[tokio::test]
+    async fn sender_over_rate_is_rate_limited() {
+        let bus = MessageBus::new(Arc::new(LoopGuard::default().with_sender_limit(0.1, 2)));
+        let bot_a = Uuid::now_v7();
+        let bot_b = Uuid::now_v7();
+        let _rx_b = bus.register_bot(bot_b);
+
+        for _ in 0..2 {
+            let msg = envelope::direct(bot_a, "bot-a", bot_b, "hello", json!({}));
+            bus.send(msg).await.unwrap();
+        }
+
+        // The budget is shared between direct sends and channel publishes
+        let msg = envelope::channel(bot_a, "bot-a", "ch", "t", json!({}));
+        let result = bus.publish(msg);
+        assert!(matches!(
+            result,
+            Err(MessageError::RateLimited { sender, .. }) if sender == bot_a
+        ));
+
+        // Other senders are unaffected
+        let msg = envelope::direct(bot_b, "bot-b", bot_b, "hello", json!({}));
+        assert!(bus.send(msg).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn mailbox_delivers_by_priority_then_fifo() {
+        let bus = make_bus();
+        let bot_a = Uuid::now_v7();
+        let bot_b = Uuid::now_v7();
+        let mut rx_b = bus.register_bot(bot_b);
+
+        let sends = [
+            ("bulk-1", MessagePriority::Low),
+            ("normal-1", MessagePriority::Normal),
+            ("bulk-2", MessagePriority::Low),
+            ("urgent-1", MessagePriority::Urgent),
+            ("normal-2", MessagePriority::Normal),
+            ("high-1", MessagePriority::High),
+            ("urgent-2", MessagePriority::Urgent),
+        ];
+        for (message_type, priority) in sends {
+            let msg = envelope::with_priority(
+                envelope::direct(bot_a, "bot-a", bot_b, message_type, json!({})),
+                priority,
+            );
+            bus.send(msg).await.unwrap();
+        }
+        assert_eq!(rx_b.len(), sends.len());
+
+        let mut order = Vec::new();
+        while let Some(msg) = rx_b.try_recv() {
+            order.push(msg.message_type);
+        }
+        assert_eq!(
+            order,
+            vec![
+                "urgent-1", "urgent-2", "high-1", "normal-1", "normal-2", "bulk-1", "bulk-2"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn mailbox_recv_wakes_and_closes() {
+        let (tx, mut rx) = mailbox(2, Arc::new(DeadLetterQueue::new(8)));
+        let bot = Uuid::now_v7();
+
+        let waiter = tokio::spawn(async move {
+            let first = rx.recv().await;
+            let second = rx.recv().await;
+            (first, second, rx)
+        });
+        tokio::task::yield_now().await;
+
+        tx.try_send(envelope::direct(bot, "a", bot, "ping", json!({})))
+            .unwrap();
+        drop(tx);
+
+        let (first, second, _rx) = waiter.await.unwrap();
+        assert_eq!(first.unwrap().message_type, "ping");
+        assert!(second.is_none());
+    }
+
+    #[tokio::test]
+    async fn mailbox_full_and_closed() {
+        let (tx, rx) = mailbox(1, Arc::new(DeadLetterQueue::new(8)));
+        let bot = Uuid::now_v7();
+        let msg = || envelope::direct(bot, "a", bot, "t", json!({}));
+
+        tx.try_send(msg()).unwrap();
+        assert_eq!(tx.try_send(msg()), Err(MailboxSendError::Full));
+
+        drop(rx);
+        assert_eq!(tx.try_send(msg()), Err(MailboxSendError::Closed));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn expired_message_is_dead_lettered_not_delivered() {
+        let bus = make_bus();
+        let bot_a = Uuid::now_v7();
+        let bot_b = Uuid::now_v7();
+        let mut rx_b = bus.register_bot(bot_b);
+
+        let reminder = envelope::with_ttl(
+            envelope::direct(bot_a, "bot-a", bot_b, "reminder", json!({"in": "5m"})),
+            Duration::from_secs(300),
+        );
+        let reminder_id = reminder.id;
+        bus.send(reminder).await.unwrap();
+        let status = envelope::direct(bot_a, "bot-a", bot_b, "status", json!({}));
+        bus.send(status).await.unwrap();
+
+        tokio::time::advance(Duration::from_secs(3600)).await;
+
+        let delivered = rx_b.recv().await.unwrap();
+        assert_eq!(delivered.message_type, "status");
+        assert!(rx_b.try_recv().is_none());
+
+        let dead = bus.dead_letters().snapshot();
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[0].message.id, reminder_id);
+        assert_eq!(dead[0].reason, DeadLetterReason::Expired);
+        assert_eq!(dead[0].reason.to_string(), "expired");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn message_within_ttl_is_delivered() {
+        let bus = make_bus();
+        let bot_a = Uuid::now_v7();
+        let bot_b = Uuid::now_v7();
+        let mut rx_b = bus.register_bot(bot_b);
+
+        let msg = envelope::with_ttl(
+            envelope::direct(bot_a, "bot-a", bot_b, "reminder", json!({})),
+            Duration::from_secs(300),
+        );
+        bus.send(msg).await.unwrap();
+        tokio::time::advance(Duration::from_secs(60)).await;
+
+        assert_eq!(rx_b.recv().await.unwrap().message_type, "reminder");
+        assert!(bus.dead_letters().is_empty());
+    }
+
+    #[tokio::test]
+    async fn already_expired_send_is_rejected() {
+        let bus = make_bus();
+        let bot_a = Uuid::now_v7();
+        let bot_b = Uuid::now_v7();
+        let _rx_b = bus.register_bot(bot_b);
+
+        let mut msg = envelope::direct(bot_a, "bot-a", bot_b, "reminder", json!({}));
+        msg.expires_at = Some(Utc::now() - chrono::Duration::seconds(1));
+        let result = bus.send(msg.clone()).await;
+        assert!(matches!(result, Err(MessageError::Expired(id)) if id == msg.id));
+
+        msg.recipient = MessageRecipient::Channel {
+            name: "alerts".to_string(),
+        };
+        assert!(matches!(bus.publish(msg), Err(MessageError::Expired(_))));
+
+        let dead = bus.dead_letters().drain();
+        assert_eq!(dead.len(), 2);
+        assert!(bus.dead_letters().is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn full_mailbox_makes_room_by_purging_expired() {
+        let dead_letters = Arc::new(DeadLetterQueue::new(8));
+        let (tx, mut rx) = mailbox(1, Arc::clone(&dead_letters));
+        let bot = Uuid::now_v7();
+
+        let stale = envelope::with_ttl(
+            envelope::direct(bot, "a", bot, "stale", json!({})),
+            Duration::from_secs(1),
+        );
+        tx.try_send(stale).unwrap();
+        tokio::time::advance(Duration::from_secs(2)).await;
+
+        tx.try_send(envelope::direct(bot, "a", bot, "fresh", json!({})))
+            .unwrap();
+        assert_eq!(rx.try_recv().unwrap().message_type, "fresh");
+        assert_eq!(dead_letters.len(), 1);
+    }
+
+    #[test]
+    fn dead_letter_queue_discards_oldest_when_full() {
+        let queue = DeadLetterQueue::new(2);
+        let bot = Uuid::now_v7();
+        for message_type in ["first", "second", "third"] {
+            let msg = envelope::direct(bot, "a", bot, message_type, json!({}));
+            queue.push(msg, DeadLetterReason::Expired);
+        }
+
+        let kept: Vec<_> = queue
+            .snapshot()
+            .into_iter()
+            .map(|dead| dead.message.message_type)
+            .collect();
+        assert_eq!(kept, vec!["second", "third"]);
+    }
+
     #[test]
     fn debug_impl() {
         let bus = make_bus();
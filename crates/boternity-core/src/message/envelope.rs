@@ -1,9 +1,12 @@
 //! Helper constructors for `BotMessage` envelopes.
 //!
 //! Reduces boilerplate when building messages for direct or channel delivery.
+//! Messages are built at normal priority with no expiry; use [`with_priority`]
+//! and [`with_ttl`] to change that.
 
-use boternity_types::message::{BotMessage, MessageRecipient};
+use boternity_types::message::{BotMessage, MessagePriority, MessageRecipient};
 use chrono::Utc;
+use std::time::Duration;
 use uuid::Uuid;
 
 /// Build a direct message from one bot to another.
@@ -25,6 +28,8 @@ pub fn direct(
         body,
         timestamp: Utc::now(),
         reply_to: None,
+        priority: MessagePriority::Normal,
+        expires_at: None,
     }
 }
 
@@ -47,10 +52,14 @@ pub fn channel(
         body,
         timestamp: Utc::now(),
         reply_to: None,
+        priority: MessagePriority::Normal,
+        expires_at: None,
     }
 }
 
 /// Build a reply message referencing the original.
+///
+/// The reply inherits the original's priority.
 pub fn reply(
     sender_bot_id: Uuid,
     sender_bot_name: impl Into<String>,
@@ -69,9 +78,26 @@ pub fn reply(
         body,
         timestamp: Utc::now(),
         reply_to: Some(original.id),
+        priority: original.priority,
+        expires_at: None,
     }
 }
 
+/// Set the delivery priority of a message.
+pub fn with_priority(mut msg: BotMessage, priority: MessagePriority) -> BotMessage {
+    msg.priority = priority;
+    msg
+}
+
+/// Expire a message `ttl` after its timestamp.
+///
+/// Once expired, the bus dead-letters the message instead of delivering it.
+pub fn with_ttl(mut msg: BotMessage, ttl: Duration) -> BotMessage {
+    let ttl = chrono::Duration::from_std(ttl).unwrap_or(chrono::Duration::MAX);
+    msg.expires_at = msg.timestamp.checked_add_signed(ttl);
+    msg
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -91,6 +117,8 @@ mod tests {
             MessageRecipient::Direct { bot_id } if bot_id == recipient
         ));
         assert!(msg.reply_to.is_none());
+        assert_eq!(msg.priority, MessagePriority::Normal);
+        assert!(msg.expires_at.is_none());
     }
 
     #[test]
@@ -117,4 +145,34 @@ mod tests {
             MessageRecipient::Direct { bot_id } if bot_id == sender_a
         ));
     }
+
+    #[test]
+    fn reply_inherits_priority() {
+        let sender_a = Uuid::now_v7();
+        let sender_b = Uuid::now_v7();
+        let original = with_priority(
+            direct(sender_a, "bot-a", sender_b, "stop", json!({})),
+            MessagePriority::Urgent,
+        );
+        let resp = reply(sender_b, "bot-b", &original, "stopped", json!({}));
+
+        assert_eq!(resp.priority, MessagePriority::Urgent);
+    }
+
+    #[test]
+    fn with_ttl_sets_expiry_from_timestamp() {
+        let bot = Uuid::now_v7();
+        let msg = with_ttl(
+            direct(bot, "bot-a", bot, "reminder", json!({})),
+            Duration::from_secs(300),
+        );
+        assert_eq!(
+            msg.expires_at,
+            Some(msg.timestamp + chrono::Duration::minutes(5))
+        );
+
+        // An absurd TTL never expires rather than overflowing
+        let msg = with_ttl(msg, Duration::MAX);
+        assert!(msg.expires_at.is_none());
+    }
 }
@@ -3,8 +3,28 @@
 //! Defines the `MessageProcessor` trait that bot message handlers implement.
 //! The default implementation is a placeholder; actual LLM/skill wiring happens
 //! in Plan 09 (inter-bot delegation).
+//!
+//! Cross-cutting concerns (logging, auth, transformation) are written as
+//! [`MessageMiddleware`] and stacked around a processor with
+//! [`MessageProcessorExt::with_middleware`]:
+//!
+//! ```
+//! use boternity_core::message::handler::DefaultMessageProcessor;
+//! use boternity_core::message::{FilterMiddleware, LoggingMiddleware, MessageProcessorExt};
+//! use boternity_types::message::BotMessage;
+//!
+//! let processor = DefaultMessageProcessor
+//!     .with_middleware(FilterMiddleware::new(|_, msg: &BotMessage| {
+//!         msg.message_type != "spam"
+//!     }))
+//!     .with_middleware(LoggingMiddleware);
+//! ```
+//!
+//! The last middleware added is the outermost: it sees the message first and
+//! the reply last.
 
 use boternity_types::message::BotMessage;
+use tracing::debug;
 use uuid::Uuid;
 
 /// Trait for processing incoming bot-to-bot messages.
@@ -39,6 +59,114 @@ impl MessageProcessor for DefaultMessageProcessor {
     }
 }
 
+/// Middleware wrapped around a [`MessageProcessor`].
+///
+/// A middleware either passes the message on by calling
+/// `next.process_message` (possibly with a modified message, and possibly
+/// modifying the reply), or short-circuits by returning without calling it.
+pub trait MessageMiddleware: Send + Sync {
+    /// Handle a message on its way to `next`.
+    fn handle<P: MessageProcessor>(
+        &self,
+        bot_id: Uuid,
+        message: &BotMessage,
+        next: &P,
+    ) -> impl std::future::Future<Output = Option<BotMessage>> + Send;
+}
+
+/// A processor with a middleware in front of it.
+///
+/// Built with [`MessageProcessorExt::with_middleware`]; layers nest, so a
+/// `Layered` can itself be wrapped again.
+pub struct Layered<M, P> {
+    middleware: M,
+    inner: P,
+}
+
+impl<M, P> Layered<M, P> {
+    /// Put `middleware` in front of `inner`.
+    pub fn new(middleware: M, inner: P) -> Self {
+        Self { middleware, inner }
+    }
+}
+
+impl<M: MessageMiddleware, P: MessageProcessor> MessageProcessor for Layered<M, P> {
+    async fn process_message(&self, bot_id: Uuid, message: &BotMessage) -> Option<BotMessage> {
+        self.middleware.handle(bot_id, message, &self.inner).await
+    }
+}
+
+/// Stacking helpers for any [`MessageProcessor`].
+pub trait MessageProcessorExt: MessageProcessor + Sized {
+    /// Wrap this processor in `middleware`, which runs before it.
+    fn with_middleware<M: MessageMiddleware>(self, middleware: M) -> Layered<M, Self> {
+        Layered::new(middleware, self)
+    }
+}
+
+impl<P: MessageProcessor> MessageProcessorExt for P {}
+
+/// Logs each message and whether it produced a reply.
+pub struct LoggingMiddleware;
+
+impl MessageMiddleware for LoggingMiddleware {
+    async fn handle<P: MessageProcessor>(
+        &self,
+        bot_id: Uuid,
+        message: &BotMessage,
+        next: &P,
+    ) -> Option<BotMessage> {
+        debug!(
+            %bot_id,
+            message_id = %message.id,
+            sender = %message.sender_bot_id,
+            message_type = %message.message_type,
+            "processing bot message"
+        );
+        let reply = next.process_message(bot_id, message).await;
+        debug!(
+            %bot_id,
+            message_id = %message.id,
+            replied = reply.is_some(),
+            "processed bot message"
+        );
+        reply
+    }
+}
+
+/// Drops messages the predicate rejects, without calling the inner processor.
+pub struct FilterMiddleware<F> {
+    accept: F,
+}
+
+impl<F> FilterMiddleware<F>
+where
+    F: Fn(Uuid, &BotMessage) -> bool + Send + Sync,
+{
+    /// Pass on only messages for which `accept(bot_id, message)` is true.
+    pub fn new(accept: F) -> Self {
+        Self { accept }
+    }
+}
+
+impl<F> MessageMiddleware for FilterMiddleware<F>
+where
+    F: Fn(Uuid, &BotMessage) -> bool + Send + Sync,
+{
+    async fn handle<P: MessageProcessor>(
+        &self,
+        bot_id: Uuid,
+        message: &BotMessage,
+        next: &P,
+    ) -> Option<BotMessage> {
+        if !(self.accept)(bot_id, message) {
+            debug!(%bot_id, message_id = %message.id, "message rejected by filter");
+            return None;
+        }
+        next.process_message(bot_id, message).await
+    }
+}
+
 /// Echo processor that replies with the same body (useful for testing).
 #[cfg(test)]
 pub(crate) struct EchoProcessor;
@@ -60,6 +188,8 @@ impl MessageProcessor for EchoProcessor {
             body: message.body.clone(),
             timestamp: Utc::now(),
             reply_to: Some(message.id),
+            priority: message.priority,
+            expires_at: None,
         })
     }
 }
@@ -74,6 +204,55 @@ mod tests {
     use boternity_types::message::MessageRecipient;
     use chrono::Utc;
     use serde_json::json;
+    use std::sync::{Arc, Mutex};
+
+    /// Records when it sees a message and a reply, under its name.
+    struct RecordingMiddleware {
+        name: &'static str,
+        log: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl MessageMiddleware for RecordingMiddleware {
+        async fn handle<P: MessageProcessor>(
+            &self,
+            bot_id: Uuid,
+            message: &BotMessage,
+            next: &P,
+        ) -> Option<BotMessage> {
+            self.log.lock().unwrap().push(format!("{}:in", self.name));
+            let reply = next.process_message(bot_id, message).await;
+            self.log.lock().unwrap().push(format!("{}:out", self.name));
+            reply
+        }
+    }
+
+    /// Counts how often the innermost processor runs.
+    struct CountingProcessor(Arc<Mutex<usize>>);
+
+    impl MessageProcessor for CountingProcessor {
+        async fn process_message(&self, bot_id: Uuid, message: &BotMessage) -> Option<BotMessage> {
+            *self.0.lock().unwrap() += 1;
+            EchoProcessor.process_message(bot_id, message).await
+        }
+    }
+
+    /// Uppercases the `text` field of the body before passing it on.
+    struct ShoutMiddleware;
+
+    impl MessageMiddleware for ShoutMiddleware {
+        async fn handle<P: MessageProcessor>(
+            &self,
+            bot_id: Uuid,
+            message: &BotMessage,
+            next: &P,
+        ) -> Option<BotMessage> {
+            let mut message = message.clone();
+            if let Some(text) = message.body["text"].as_str() {
+                message.body["text"] = json!(text.to_uppercase());
+            }
+            next.process_message(bot_id, &message).await
+        }
+    }
 
     fn sample_message(sender: Uuid, recipient: Uuid) -> BotMessage {
         BotMessage {
@@ -87,6 +266,8 @@ mod tests {
             body: json!({"text": "hello"}),
             timestamp: Utc::now(),
             reply_to: None,
+            priority: Default::default(),
+            expires_at: None,
         }
     }
 
@@ -134,4 +315,73 @@ mod tests {
         let echo = EchoProcessor;
         assert!(process_with(&echo, bot, &msg).await.is_some());
     }
+
+    #[tokio::test]
+    async fn middleware_runs_outermost_last_added_first() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let processor = EchoProcessor
+            .with_middleware(RecordingMiddleware {
+                name: "inner",
+                log: Arc::clone(&log),
+            })
+            .with_middleware(RecordingMiddleware {
+                name: "outer",
+                log: Arc::clone(&log),
+            });
+
+        let bot = Uuid::now_v7();
+        let msg = sample_message(Uuid::now_v7(), bot);
+        assert!(processor.process_message(bot, &msg).await.is_some());
+
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec!["outer:in", "inner:in", "inner:out", "outer:out"]
+        );
+    }
+
+    #[tokio::test]
+    async fn filter_short_circuits_inner_layers() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let calls = Arc::new(Mutex::new(0));
+        let processor = CountingProcessor(Arc::clone(&calls))
+            .with_middleware(RecordingMiddleware {
+                name: "inner",
+                log: Arc::clone(&log),
+            })
+            .with_middleware(FilterMiddleware::new(|_, msg: &BotMessage| {
+                msg.message_type != "spam"
+            }))
+            .with_middleware(RecordingMiddleware {
+                name: "outer",
+                log: Arc::clone(&log),
+            })
+            .with_middleware(LoggingMiddleware);
+
+        let bot = Uuid::now_v7();
+        let mut spam = sample_message(Uuid::now_v7(), bot);
+        spam.message_type = "spam".to_string();
+        assert!(processor.process_message(bot, &spam).await.is_none());
+        assert_eq!(*calls.lock().unwrap(), 0);
+        assert_eq!(*log.lock().unwrap(), vec!["outer:in", "outer:out"]);
+
+        log.lock().unwrap().clear();
+        let msg = sample_message(Uuid::now_v7(), bot);
+        assert!(processor.process_message(bot, &msg).await.is_some());
+        assert_eq!(*calls.lock().unwrap(), 1);
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec!["outer:in", "inner:in", "inner:out", "outer:out"]
+        );
+    }
+
+    #[tokio::test]
+    async fn middleware_can_transform_message() {
+        let processor = EchoProcessor.with_middleware(ShoutMiddleware);
+        let bot = Uuid::now_v7();
+        let msg = sample_message(Uuid::now_v7(), bot);
+
+        let reply = processor.process_message(bot, &msg).await.unwrap();
+        assert_eq!(reply.body, json!({"text": "HELLO"}));
+        assert_eq!(reply.reply_to, Some(msg.id));
+    }
 }
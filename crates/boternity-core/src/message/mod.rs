@@ -1,16 +1,22 @@
 //! Bot-to-bot message bus with direct messaging, pub/sub channels, and loop prevention.
 //!
 //! This module provides the runtime messaging infrastructure for inter-bot communication:
-//! - `bus` -- `MessageBus` with per-bot mailboxes, pub/sub channels, and send-and-wait
+//! - `bus` -- `MessageBus` with per-bot priority mailboxes, pub/sub channels, send-and-wait,
+//!   and a dead-letter queue for expired messages
 //! - `envelope` -- Helper constructors for `BotMessage`
-//! - `router` -- `LoopGuard` with depth, rate, and time-window protection
-//! - `handler` -- `MessageProcessor` trait for pluggable message handling pipelines
+//! - `router` -- `LoopGuard` with depth, rate, and time-window protection, plus a
+//!   per-sender token-bucket `SenderRateLimiter`
+//! - `handler` -- `MessageProcessor` trait and stackable `MessageMiddleware` for message
+//!   handling pipelines
 
 pub mod bus;
 pub mod envelope;
 pub mod handler;
 pub mod router;
 
-pub use bus::{MessageBus, MessageError};
-pub use handler::MessageProcessor;
-pub use router::LoopGuard;
+pub use bus::{DeadLetter, DeadLetterQueue, DeadLetterReason, Mailbox, MessageBus, MessageError};
+pub use handler::{
+    FilterMiddleware, Layered, LoggingMiddleware, MessageMiddleware, MessageProcessor,
+    MessageProcessorExt,
+};
+pub use router::{LoopGuard, SenderRateLimiter};
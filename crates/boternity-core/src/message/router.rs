@@ -4,6 +4,9 @@
 //! - Delegation depth per conversation chain (max hops)
 //! - Exchange rate per bot pair (max messages per window)
 //! - Time window that resets rate counters
+//!
+//! Separately, a token bucket per sender caps how fast one bot can send across
+//! all recipients and channels, so a chatty bot cannot flood the bus.
 
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{Duration, Instant};
@@ -20,6 +23,12 @@ const DEFAULT_MAX_RATE: u32 = 10;
 /// Default time window for rate limiting.
 const DEFAULT_WINDOW: Duration = Duration::from_secs(60);
 
+/// Default sustained send rate per sender (messages per second).
+const DEFAULT_SENDER_RATE: f64 = 0.5;
+
+/// Default burst size per sender (messages sendable at once from a full bucket).
+const DEFAULT_SENDER_BURST: u32 = 30;
+
 /// Three-layer loop prevention guard for bot-to-bot messaging.
 ///
 /// **Layer 1 -- Depth:** Limits how many hops a conversation chain can take.
@@ -33,6 +42,8 @@ pub struct LoopGuard {
     pair_counters: DashMap<(Uuid, Uuid), PairCounter>,
     /// Per-conversation depth tracker: conversation_root_id -> current_depth.
     depth_tracker: DashMap<Uuid, AtomicU64>,
+    /// Per-sender throttle across all recipients.
+    sender_limiter: SenderRateLimiter,
 }
 
 /// Rate counter for a bot pair.
@@ -50,9 +61,17 @@ impl LoopGuard {
             window,
             pair_counters: DashMap::new(),
             depth_tracker: DashMap::new(),
+            sender_limiter: SenderRateLimiter::default(),
         }
     }
 
+    /// Throttle each sender to `rate` messages per second, allowing bursts
+    /// of up to `burst` messages.
+    pub fn with_sender_limit(mut self, rate: f64, burst: u32) -> Self {
+        self.sender_limiter = SenderRateLimiter::new(rate, burst);
+        self
+    }
+
     /// Check whether a message from `sender` to `recipient` is allowed.
     ///
     /// Returns `Ok(())` if allowed, or `Err(reason)` if the loop guard rejects it.
@@ -60,6 +79,14 @@ impl LoopGuard {
         self.check_rate(sender, recipient)
     }
 
+    /// Take one message from `sender`'s send budget.
+    ///
+    /// Returns `Err(retry_after)` with the time until the next message is
+    /// allowed when the sender is over its rate.
+    pub fn check_sender(&self, sender: Uuid) -> Result<(), Duration> {
+        self.sender_limiter.try_acquire(sender)
+    }
+
     /// Check and increment the rate counter for a bot pair.
     fn check_rate(&self, sender: Uuid, recipient: Uuid) -> Result<(), String> {
         let key = (sender, recipient);
@@ -121,6 +148,7 @@ impl LoopGuard {
     pub fn reset_all(&self) {
         self.pair_counters.clear();
         self.depth_tracker.clear();
+        self.sender_limiter.buckets.clear();
     }
 
     /// Get the configured max depth.
@@ -148,6 +176,87 @@ impl std::fmt::Debug for LoopGuard {
             .field("window", &self.window)
             .field("active_pairs", &self.pair_counters.len())
             .field("active_conversations", &self.depth_tracker.len())
+            .field("sender_limiter", &self.sender_limiter)
+            .finish()
+    }
+}
+
+/// Token-bucket rate limiter keyed by sender bot.
+///
+/// Each sender's bucket holds up to `burst` tokens and refills at `rate`
+/// tokens per second; every message takes one token. Buckets start full.
+pub struct SenderRateLimiter {
+    rate: f64,
+    burst: u32,
+    buckets: DashMap<Uuid, TokenBucket>,
+}
+
+/// Token count for one sender, as of `updated`.
+struct TokenBucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl SenderRateLimiter {
+    /// Create a limiter allowing `rate` messages per second per sender, with
+    /// bursts of up to `burst`.
+    pub fn new(rate: f64, burst: u32) -> Self {
+        Self {
+            rate: rate.max(0.0),
+            burst: burst.max(1),
+            buckets: DashMap::new(),
+        }
+    }
+
+    /// Take a token for `sender`, or return how long until one is available.
+    pub fn try_acquire(&self, sender: Uuid) -> Result<(), Duration> {
+        self.try_acquire_at(sender, Instant::now())
+    }
+
+    fn try_acquire_at(&self, sender: Uuid, now: Instant) -> Result<(), Duration> {
+        let mut entry = self.buckets.entry(sender).or_insert_with(|| TokenBucket {
+            tokens: self.burst as f64,
+            updated: now,
+        });
+        let bucket = entry.value_mut();
+
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.rate).min(self.burst as f64);
+        bucket.updated = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            return Ok(());
+        }
+        if self.rate == 0.0 {
+            return Err(Duration::MAX);
+        }
+        Err(Duration::from_secs_f64((1.0 - bucket.tokens) / self.rate))
+    }
+
+    /// Sustained messages per second per sender.
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    /// Largest burst a sender can send at once.
+    pub fn burst(&self) -> u32 {
+        self.burst
+    }
+}
+
+impl Default for SenderRateLimiter {
+    fn default() -> Self {
+        Self::new(DEFAULT_SENDER_RATE, DEFAULT_SENDER_BURST)
+    }
+}
+
+impl std::fmt::Debug for SenderRateLimiter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SenderRateLimiter")
+            .field("rate", &self.rate)
+            .field("burst", &self.burst)
+            .field("active_senders", &self.buckets.len())
             .finish()
     }
 }
@@ -287,6 +396,63 @@ mod tests {
         assert_eq!(guard.track_depth(conv).unwrap(), 1);
     }
 
+    #[test]
+    fn sender_burst_within_budget_passes() {
+        let limiter = SenderRateLimiter::new(1.0, 5);
+        let sender = Uuid::now_v7();
+        let now = Instant::now();
+
+        for _ in 0..5 {
+            assert!(limiter.try_acquire_at(sender, now).is_ok());
+        }
+        let retry_after = limiter.try_acquire_at(sender, now).unwrap_err();
+        assert_eq!(retry_after, Duration::from_secs(1));
+
+        // Other senders have their own bucket
+        assert!(limiter.try_acquire_at(Uuid::now_v7(), now).is_ok());
+    }
+
+    #[test]
+    fn sender_over_rate_rejected_until_refill() {
+        let limiter = SenderRateLimiter::new(2.0, 2);
+        let sender = Uuid::now_v7();
+        let start = Instant::now();
+
+        assert!(limiter.try_acquire_at(sender, start).is_ok());
+        assert!(limiter.try_acquire_at(sender, start).is_ok());
+        assert!(limiter.try_acquire_at(sender, start).is_err());
+
+        // Half a token after 250ms: still rejected
+        let later = start + Duration::from_millis(250);
+        let retry_after = limiter.try_acquire_at(sender, later).unwrap_err();
+        assert_eq!(retry_after, Duration::from_millis(250));
+
+        // Sustained sends go through at the refill rate, no faster
+        let refilled = start + Duration::from_millis(500);
+        assert!(limiter.try_acquire_at(sender, refilled).is_ok());
+        assert!(limiter.try_acquire_at(sender, refilled).is_err());
+
+        // A long pause refills only up to the burst size
+        let idle = start + Duration::from_secs(60);
+        assert!(limiter.try_acquire_at(sender, idle).is_ok());
+        assert!(limiter.try_acquire_at(sender, idle).is_ok());
+        assert!(limiter.try_acquire_at(sender, idle).is_err());
+    }
+
+    #[test]
+    fn sender_limit_spans_recipients() {
+        let guard = LoopGuard::default().with_sender_limit(1.0, 3);
+        let sender = Uuid::now_v7();
+
+        for _ in 0..3 {
+            assert!(guard.check_sender(sender).is_ok());
+        }
+        assert!(guard.check_sender(sender).is_err());
+
+        guard.reset_all();
+        assert!(guard.check_sender(sender).is_ok());
+    }
+
     #[test]
     fn default_values() {
         let guard = LoopGuard::default();
@@ -1,6 +1,6 @@
 //! Bot repository trait definition.
 
-use boternity_types::bot::{Bot, BotCategory, BotId, BotStatus};
+use boternity_types::bot::{Bot, BotCategory, BotId, BotStatus, BotStatusTransition};
 use boternity_types::error::RepositoryError;
 
 use super::SortOrder;
@@ -20,6 +20,8 @@ pub struct BotFilter {
     pub limit: Option<i64>,
     /// Number of results to skip (offset pagination).
     pub offset: Option<i64>,
+    /// Include soft-deleted bots (hidden by default).
+    pub include_deleted: bool,
 }
 
 /// Repository trait for bot persistence.
@@ -46,6 +48,8 @@ pub trait BotRepository: Send + Sync {
     ) -> impl std::future::Future<Output = Result<Option<Bot>, RepositoryError>> + Send;
 
     /// List bots with optional filtering, sorting, and pagination.
+    ///
+    /// Soft-deleted bots are excluded unless `include_deleted` is set.
     fn list(
         &self,
         filter: Option<BotFilter>,
@@ -57,9 +61,28 @@ pub trait BotRepository: Send + Sync {
         bot: &Bot,
     ) -> impl std::future::Future<Output = Result<Bot, RepositoryError>> + Send;
 
+    /// Set only the bot's `last_active_at` timestamp.
+    fn touch_last_active(
+        &self,
+        id: &BotId,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> impl std::future::Future<Output = Result<(), RepositoryError>> + Send;
+
     /// Permanently delete a bot by ID.
     fn delete(
         &self,
         id: &BotId,
     ) -> impl std::future::Future<Output = Result<(), RepositoryError>> + Send;
+
+    /// Append a status change to the bot's status history.
+    fn record_status_transition(
+        &self,
+        transition: &BotStatusTransition,
+    ) -> impl std::future::Future<Output = Result<(), RepositoryError>> + Send;
+
+    /// List a bot's status history, oldest first.
+    fn list_status_transitions(
+        &self,
+        bot_id: &BotId,
+    ) -> impl std::future::Future<Output = Result<Vec<BotStatusTransition>, RepositoryError>> + Send;
 }
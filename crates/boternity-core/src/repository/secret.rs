@@ -12,6 +12,9 @@ use std::sync::Arc;
 use boternity_types::error::RepositoryError;
 use boternity_types::secret::{SecretEntry, SecretScope};
 
+/// Storage backend kind, as reported in [`SecretSource`](boternity_types::secret::SecretSource).
+pub type SecretProviderKind = boternity_types::secret::SecretProvider;
+
 /// Trait for secret storage backends (vault, keychain, environment).
 ///
 /// Each provider stores and retrieves secret values. The SecretService
@@ -20,6 +23,9 @@ use boternity_types::secret::{SecretEntry, SecretScope};
 /// This trait uses RPITIT for zero-cost async. For dynamic dispatch
 /// (trait objects), see [`BoxSecretProvider`].
 pub trait SecretProvider: Send + Sync {
+    /// Which backend this provider is.
+    fn kind(&self) -> SecretProviderKind;
+
     /// Retrieve a secret value by key and scope.
     /// Returns None if the secret does not exist in this provider.
     fn get(
@@ -57,6 +63,8 @@ pub trait SecretProvider: Send + Sync {
 ///
 /// A blanket implementation is provided for all types implementing `SecretProvider`.
 pub trait BoxSecretProvider: Send + Sync {
+    fn kind_boxed(&self) -> SecretProviderKind;
+
     fn get_boxed<'a>(
         &'a self,
         key: &'a str,
@@ -84,6 +92,10 @@ pub trait BoxSecretProvider: Send + Sync {
 
 /// Blanket implementation: any `SecretProvider` automatically implements `BoxSecretProvider`.
 impl<T: SecretProvider> BoxSecretProvider for T {
+    fn kind_boxed(&self) -> SecretProviderKind {
+        self.kind()
+    }
+
     fn get_boxed<'a>(
         &'a self,
         key: &'a str,
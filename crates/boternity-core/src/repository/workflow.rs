@@ -9,8 +9,57 @@ use boternity_types::workflow::{
     WorkflowDefinition, WorkflowOwner, WorkflowRun, WorkflowRunStatus, WorkflowStepLog,
     WorkflowStepStatus,
 };
+use chrono::{DateTime, Duration, Utc};
 use uuid::Uuid;
 
+/// Filter criteria for listing runs across all workflows.
+#[derive(Debug, Clone, Default)]
+pub struct WorkflowRunFilter {
+    /// Filter by run status.
+    pub status: Option<WorkflowRunStatus>,
+    /// Filter by workflow name (exact match).
+    pub workflow_name: Option<String>,
+    /// Only runs started at or after this time.
+    pub since: Option<DateTime<Utc>>,
+    /// Maximum number of results.
+    pub limit: Option<i64>,
+    /// Number of results to skip (offset pagination).
+    pub offset: Option<i64>,
+}
+
+impl WorkflowRunFilter {
+    /// Parse a `since` value relative to `now`.
+    ///
+    /// Accepts a relative age (`30s`, `15m`, `12h`, `1d`, `2w`) or an
+    /// RFC 3339 timestamp.
+    pub fn parse_since(spec: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>, String> {
+        let spec = spec.trim();
+        if let Ok(ts) = DateTime::parse_from_rfc3339(spec) {
+            return Ok(ts.with_timezone(&Utc));
+        }
+
+        let invalid = || {
+            format!(
+                "invalid since value '{spec}' (expected e.g. 30m, 12h, 1d, 2w or an RFC 3339 timestamp)"
+            )
+        };
+        let split = spec.len().checked_sub(1).ok_or_else(invalid)?;
+        let (amount, unit) = spec.split_at(split);
+        let amount: i64 = amount.parse().map_err(|_| invalid())?;
+        let age = match unit {
+            "s" => Duration::try_seconds(amount),
+            "m" => Duration::try_minutes(amount),
+            "h" => Duration::try_hours(amount),
+            "d" => Duration::try_days(amount),
+            "w" => Duration::try_weeks(amount),
+            _ => None,
+        }
+        .filter(|age| *age >= Duration::zero())
+        .ok_or_else(invalid)?;
+        now.checked_sub_signed(age).ok_or_else(invalid)
+    }
+}
+
 /// Repository trait for workflow persistence.
 ///
 /// Covers three entity families:
@@ -87,6 +136,21 @@ pub trait WorkflowRepository: Send + Sync {
         limit: u32,
     ) -> impl std::future::Future<Output = Result<Vec<WorkflowRun>, RepositoryError>> + Send;
 
+    /// List runs across all workflows matching `filter`, ordered by started_at DESC.
+    fn list_runs_filtered(
+        &self,
+        filter: &WorkflowRunFilter,
+    ) -> impl std::future::Future<Output = Result<Vec<WorkflowRun>, RepositoryError>> + Send;
+
+    /// Find the most recent run of a workflow with the given idempotency key
+    /// that started at or after `since`.
+    fn find_run_by_idempotency_key(
+        &self,
+        workflow_id: &Uuid,
+        idempotency_key: &str,
+        since: DateTime<Utc>,
+    ) -> impl std::future::Future<Output = Result<Option<WorkflowRun>, RepositoryError>> + Send;
+
     /// List runs that were left in `Running` status (crash recovery).
     fn list_crashed_runs(
         &self,
@@ -123,3 +187,40 @@ pub trait WorkflowRepository: Send + Sync {
         run_id: &Uuid,
     ) -> impl std::future::Future<Output = Result<Vec<String>, RepositoryError>> + Send;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_since_relative_and_absolute() {
+        let now = DateTime::parse_from_rfc3339("2026-03-10T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let cases = [
+            ("30s", now - Duration::seconds(30)),
+            ("15m", now - Duration::minutes(15)),
+            ("12h", now - Duration::hours(12)),
+            ("1d", now - Duration::days(1)),
+            ("2w", now - Duration::weeks(2)),
+        ];
+        for (spec, expected) in cases {
+            assert_eq!(WorkflowRunFilter::parse_since(spec, now).unwrap(), expected);
+        }
+
+        let absolute = WorkflowRunFilter::parse_since("2026-03-01T08:30:00+02:00", now).unwrap();
+        assert_eq!(absolute.to_rfc3339(), "2026-03-01T06:30:00+00:00");
+    }
+
+    #[test]
+    fn test_parse_since_rejects_invalid() {
+        let now = Utc::now();
+        for spec in ["", "d", "1y", "-1d", "one-day", "1.5h"] {
+            assert!(
+                WorkflowRunFilter::parse_since(spec, now).is_err(),
+                "{spec:?} should be rejected"
+            );
+        }
+    }
+}
@@ -7,7 +7,8 @@
 use std::path::{Path, PathBuf};
 
 use boternity_types::bot::{
-    Bot, BotCategory, BotId, BotStatus, CreateBotRequest, UpdateBotRequest, slugify,
+    Bot, BotCategory, BotId, BotStatus, BotStatusTransition, CreateBotRequest, UpdateBotRequest,
+    slugify,
 };
 use boternity_types::error::BotError;
 use boternity_types::soul::{Soul, SoulIntegrityResult};
@@ -108,6 +109,7 @@ impl<B: BotRepository, S: SoulRepository, F: FileSystem, H: ContentHasher>
             created_at: now,
             updated_at: now,
             last_active_at: None,
+            deleted_at: None,
         };
 
         // Save to database
@@ -241,9 +243,13 @@ impl<B: BotRepository, S: SoulRepository, F: FileSystem, H: ContentHasher>
         if let Some(description) = request.description {
             bot.description = description;
         }
-        if let Some(status) = request.status {
-            bot.status = status;
-        }
+        let status_change = match request.status {
+            Some(status) if status != bot.status => {
+                bot.status.check_transition(&status, false)?;
+                Some(std::mem::replace(&mut bot.status, status))
+            }
+            _ => None,
+        };
         if let Some(category) = request.category {
             bot.category = category;
         }
@@ -253,26 +259,147 @@ impl<B: BotRepository, S: SoulRepository, F: FileSystem, H: ContentHasher>
 
         bot.updated_at = chrono::Utc::now();
 
-        self.bot_repo
+        let bot = self
+            .bot_repo
+            .update(&bot)
+            .await
+            .map_err(|e| BotError::StorageError(e.to_string()))?;
+
+        if let Some(from) = status_change {
+            self.record_status_transition(&bot, from, None).await?;
+        }
+
+        Ok(bot)
+    }
+
+    /// Change a bot's lifecycle status, enforcing transition rules.
+    ///
+    /// Archived bots can only be reactivated with `restore`. Every successful
+    /// change is appended to the bot's status history with `reason`.
+    pub async fn set_bot_status(
+        &self,
+        id: &BotId,
+        status: BotStatus,
+        reason: Option<String>,
+        restore: bool,
+    ) -> Result<Bot, BotError> {
+        let mut bot = self.get_bot(id).await?;
+        bot.status.check_transition(&status, restore)?;
+
+        let from = std::mem::replace(&mut bot.status, status);
+        bot.updated_at = chrono::Utc::now();
+
+        let bot = self
+            .bot_repo
             .update(&bot)
             .await
+            .map_err(|e| BotError::StorageError(e.to_string()))?;
+        self.record_status_transition(&bot, from, reason).await?;
+
+        Ok(bot)
+    }
+
+    /// List a bot's status changes, oldest first.
+    pub async fn status_history(&self, id: &BotId) -> Result<Vec<BotStatusTransition>, BotError> {
+        self.bot_repo
+            .list_status_transitions(id)
+            .await
+            .map_err(|e| BotError::StorageError(e.to_string()))
+    }
+
+    async fn record_status_transition(
+        &self,
+        bot: &Bot,
+        from: BotStatus,
+        reason: Option<String>,
+    ) -> Result<(), BotError> {
+        let transition = BotStatusTransition {
+            id: uuid::Uuid::now_v7(),
+            bot_id: bot.id.clone(),
+            from_status: from,
+            to_status: bot.status.clone(),
+            reason: reason.filter(|r| !r.trim().is_empty()),
+            created_at: bot.updated_at,
+        };
+        self.bot_repo
+            .record_status_transition(&transition)
+            .await
             .map_err(|e| BotError::StorageError(e.to_string()))
     }
 
-    /// Touch a bot's `last_active_at` timestamp (e.g., after a chat).
+    /// Touch a bot's `last_active_at` timestamp (e.g., when a chat starts or ends).
+    ///
+    /// A single-column update: no read, and `updated_at` is left alone since
+    /// using a bot does not modify it.
     pub async fn touch_activity(&self, id: &BotId) -> Result<(), BotError> {
-        let mut bot = self.get_bot(id).await?;
-        let now = chrono::Utc::now();
-        bot.last_active_at = Some(now);
-        bot.updated_at = now;
         self.bot_repo
+            .touch_last_active(id, chrono::Utc::now())
+            .await
+            .map_err(|e| match e {
+                boternity_types::error::RepositoryError::NotFound => BotError::NotFound,
+                e => BotError::StorageError(e.to_string()),
+            })
+    }
+
+    /// Soft-delete a bot: archive it and hide it from default listings.
+    ///
+    /// All data (database rows and the bot directory) is kept so the bot can
+    /// be brought back with [`Self::restore_bot`]. Use [`Self::delete_bot`] to
+    /// purge it permanently.
+    pub async fn soft_delete_bot(&self, id: &BotId) -> Result<Bot, BotError> {
+        let mut bot = self.get_bot(id).await?;
+        if bot.deleted_at.is_some() {
+            return Ok(bot);
+        }
+
+        let from = std::mem::replace(&mut bot.status, BotStatus::Archived);
+        bot.updated_at = chrono::Utc::now();
+        bot.deleted_at = Some(bot.updated_at);
+
+        let bot = self
+            .bot_repo
             .update(&bot)
             .await
             .map_err(|e| BotError::StorageError(e.to_string()))?;
-        Ok(())
+        if from != BotStatus::Archived {
+            self.record_status_transition(&bot, from, Some("deleted".to_string()))
+                .await?;
+        }
+
+        Ok(bot)
+    }
+
+    /// Restore a soft-deleted bot and reactivate it.
+    ///
+    /// Fails with [`BotError::InvalidStatusTransition`] if the bot is not
+    /// soft-deleted.
+    pub async fn restore_bot(&self, id: &BotId) -> Result<Bot, BotError> {
+        let mut bot = self.get_bot(id).await?;
+        if bot.deleted_at.is_none() {
+            return Err(BotError::InvalidStatusTransition {
+                from: bot.status.to_string(),
+                to: BotStatus::Active.to_string(),
+                reason: "bot is not deleted".to_string(),
+            });
+        }
+
+        bot.status.check_transition(&BotStatus::Active, true)?;
+        let from = std::mem::replace(&mut bot.status, BotStatus::Active);
+        bot.updated_at = chrono::Utc::now();
+        bot.deleted_at = None;
+
+        let bot = self
+            .bot_repo
+            .update(&bot)
+            .await
+            .map_err(|e| BotError::StorageError(e.to_string()))?;
+        self.record_status_transition(&bot, from, Some("restored".to_string()))
+            .await?;
+
+        Ok(bot)
     }
 
-    /// Delete a bot and remove its directory from disk.
+    /// Permanently delete a bot and remove its directory from disk.
     pub async fn delete_bot(&self, id: &BotId) -> Result<(), BotError> {
         // Get bot to find slug for directory cleanup
         let bot = self.get_bot(id).await?;
@@ -1,14 +1,30 @@
 //! ContentHasher trait for computing integrity hashes.
 //!
 //! Defined in boternity-core so services can hash content without coupling to
-//! a specific hashing algorithm. The `Sha256ContentHasher` adapter lives in
+//! a specific hashing algorithm. The `IntegrityHasher` adapter lives in
 //! boternity-infra.
 
 /// Abstraction over content hashing for integrity verification.
 ///
-/// Used by SoulService to compute SHA-256 hashes of SOUL.md content and
-/// verify integrity at bot startup.
+/// Used by SoulService to compute hashes of SOUL.md content and verify
+/// integrity at bot startup. A hasher may record which algorithm produced a
+/// hash inside the hash string itself; `compute_hash_as` and `verify_hash`
+/// then re-hash with that algorithm, so hashes made before the configured
+/// algorithm changed keep verifying.
 pub trait ContentHasher: Send + Sync {
     /// Compute a hex-encoded hash of the given content.
     fn compute_hash(&self, content: &str) -> String;
+
+    /// Hash `content` with the algorithm that produced `recorded`.
+    ///
+    /// The default assumes a single algorithm.
+    fn compute_hash_as(&self, content: &str, recorded: &str) -> String {
+        let _ = recorded;
+        self.compute_hash(content)
+    }
+
+    /// Whether `content` matches a previously recorded hash.
+    fn verify_hash(&self, content: &str, recorded: &str) -> bool {
+        self.compute_hash_as(content, recorded) == recorded
+    }
 }
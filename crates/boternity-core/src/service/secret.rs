@@ -1,14 +1,16 @@
 //! Secret management service.
 //!
 //! SecretService resolves secrets through a chain of providers in priority order.
-//! Resolution precedence: env vars > per-bot keys > global vault.
+//! Resolution precedence: per-bot keys > global keys, and within a scope
+//! env vars > keychain > vault. Bot scopes inherit global keys unless the
+//! lookup is [`ScopeResolution::Strict`].
 //!
 //! This service lives in `boternity-core` and depends only on `boternity-types`
 //! and the `BoxSecretProvider` trait -- never on concrete infra implementations.
 
 use crate::repository::secret::DynSecretProvider;
 use boternity_types::error::RepositoryError;
-use boternity_types::secret::{SecretEntry, SecretScope};
+use boternity_types::secret::{ScopeResolution, Secret, SecretEntry, SecretScope, SecretSource};
 
 /// Service for managing secrets across multiple storage backends.
 ///
@@ -16,7 +18,7 @@ use boternity_types::secret::{SecretEntry, SecretScope};
 /// Default chain: `[EnvSecretProvider, KeychainProvider, VaultSecretProvider]`
 ///
 /// For bot-scoped secrets, the service first tries providers with the bot scope,
-/// then falls back to global scope (env vars > per-bot > global vault).
+/// then (unless strict) falls back to global scope.
 pub struct SecretService {
     providers: Vec<DynSecretProvider>,
 }
@@ -32,39 +34,98 @@ impl SecretService {
     /// Resolve a secret value by iterating through providers in priority order.
     ///
     /// For `SecretScope::Bot`: first tries providers with bot scope, then falls
-    /// back to global scope. This implements the precedence:
-    /// env vars > per-bot keys > global vault.
+    /// back to global scope (see [`ScopeResolution::Inherit`]).
     pub async fn get_secret(
         &self,
         key: &str,
         scope: &SecretScope,
     ) -> Result<Option<String>, RepositoryError> {
-        // For bot scope: first try bot-scoped, then fall back to global
-        if let SecretScope::Bot(_) = scope {
-            // Try bot-scoped first across all providers
-            for provider in &self.providers {
-                if let Some(value) = provider.get_boxed(key, scope).await? {
-                    return Ok(Some(value));
-                }
-            }
+        self.resolve_secret(key, scope, ScopeResolution::Inherit)
+            .await
+    }
 
-            // Fall back to global scope
-            for provider in &self.providers {
-                if let Some(value) = provider.get_boxed(key, &SecretScope::Global).await? {
-                    return Ok(Some(value));
-                }
-            }
+    /// Resolve a secret value under an explicit [`ScopeResolution`] policy.
+    ///
+    /// The requested scope is tried across all providers first, so a
+    /// bot-scoped value shadows the global one. With `Inherit`, a bot-scoped
+    /// miss then retries every provider in global scope; with `Strict` it
+    /// returns `None`.
+    pub async fn resolve_secret(
+        &self,
+        key: &str,
+        scope: &SecretScope,
+        resolution: ScopeResolution,
+    ) -> Result<Option<String>, RepositoryError> {
+        Ok(self
+            .resolve_with_source(key, scope, resolution)
+            .await?
+            .map(|(value, _)| value))
+    }
 
-            Ok(None)
-        } else {
-            // Global scope: iterate providers, first match wins
-            for provider in &self.providers {
-                if let Some(value) = provider.get_boxed(key, scope).await? {
-                    return Ok(Some(value));
-                }
+    /// Resolve a secret like [`get_secret`](Self::get_secret) and report
+    /// which provider (and scope) supplied the value.
+    pub async fn get_with_source(
+        &self,
+        key: &str,
+        scope: &SecretScope,
+    ) -> Result<Option<(String, SecretSource)>, RepositoryError> {
+        self.resolve_with_source(key, scope, ScopeResolution::Inherit)
+            .await
+    }
+
+    async fn resolve_with_source(
+        &self,
+        key: &str,
+        scope: &SecretScope,
+        resolution: ScopeResolution,
+    ) -> Result<Option<(String, SecretSource)>, RepositoryError> {
+        if let Some(found) = self.first_match(key, scope).await? {
+            return Ok(Some(found));
+        }
+
+        if let SecretScope::Bot(_) = scope
+            && resolution == ScopeResolution::Inherit
+        {
+            return self.first_match(key, &SecretScope::Global).await;
+        }
+
+        Ok(None)
+    }
+
+    /// The first provider (in chain order) holding `key` in exactly `scope`.
+    async fn first_match(
+        &self,
+        key: &str,
+        scope: &SecretScope,
+    ) -> Result<Option<(String, SecretSource)>, RepositoryError> {
+        for provider in &self.providers {
+            if let Some(value) = provider.get_boxed(key, scope).await? {
+                let source = SecretSource {
+                    provider: provider.kind_boxed(),
+                    scope: scope.clone(),
+                };
+                return Ok(Some((value, source)));
             }
-            Ok(None)
         }
+        Ok(None)
+    }
+
+    /// Resolve a secret for explicit display to the user.
+    ///
+    /// Same resolution as [`resolve_secret`](Self::resolve_secret), but the
+    /// value is wrapped in [`Secret`] so it stays redacted in any
+    /// `Debug`/`Display` output (including tracing fields) until the caller
+    /// calls `expose()`.
+    pub async fn reveal_secret(
+        &self,
+        key: &str,
+        scope: &SecretScope,
+        resolution: ScopeResolution,
+    ) -> Result<Option<Secret<String>>, RepositoryError> {
+        Ok(self
+            .resolve_secret(key, scope, resolution)
+            .await?
+            .map(Secret::new))
     }
 
     /// Store a secret value in the first writable provider.
@@ -165,6 +226,7 @@ mod tests {
     /// A mock provider that returns predefined values.
     struct MockProvider {
         name: &'static str,
+        kind: crate::repository::secret::SecretProviderKind,
         values: std::collections::HashMap<(String, String), String>,
         writable: bool,
     }
@@ -173,6 +235,11 @@ mod tests {
         fn new(name: &'static str, writable: bool) -> Self {
             Self {
                 name,
+                kind: match name {
+                    "env" => crate::repository::secret::SecretProviderKind::Environment,
+                    "keychain" => crate::repository::secret::SecretProviderKind::Keychain,
+                    _ => crate::repository::secret::SecretProviderKind::Vault,
+                },
                 values: std::collections::HashMap::new(),
                 writable,
             }
@@ -193,6 +260,10 @@ mod tests {
     }
 
     impl crate::repository::secret::SecretProvider for MockProvider {
+        fn kind(&self) -> crate::repository::secret::SecretProviderKind {
+            self.kind.clone()
+        }
+
         async fn get(
             &self,
             key: &str,
@@ -331,6 +402,115 @@ mod tests {
         assert_eq!(result, Some("bot-value".to_string()));
     }
 
+    #[tokio::test]
+    async fn test_strict_bot_scope_does_not_fall_back() {
+        let bot_id = boternity_types::bot::BotId::new();
+        let bot_scope = SecretScope::Bot(bot_id);
+
+        let env_provider = MockProvider::new("env", false)
+            .with_value("API_KEY", &SecretScope::Global, "env-value");
+        let vault_provider = MockProvider::new("vault", true)
+            .with_value("API_KEY", &SecretScope::Global, "global-value")
+            .with_value("BOT_KEY", &bot_scope, "bot-value");
+
+        let service = SecretService::new(vec![
+            Arc::new(env_provider),
+            Arc::new(vault_provider),
+        ]);
+
+        let missing = service
+            .resolve_secret("API_KEY", &bot_scope, ScopeResolution::Strict)
+            .await
+            .unwrap();
+        assert!(missing.is_none());
+
+        let own = service
+            .resolve_secret("BOT_KEY", &bot_scope, ScopeResolution::Strict)
+            .await
+            .unwrap();
+        assert_eq!(own, Some("bot-value".to_string()));
+
+        // Strict has no effect on global lookups
+        let global = service
+            .resolve_secret("API_KEY", &SecretScope::Global, ScopeResolution::Strict)
+            .await
+            .unwrap();
+        assert_eq!(global, Some("env-value".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_get_with_source_reports_provider_and_scope() {
+        let bot_id = boternity_types::bot::BotId::new();
+        let bot_scope = SecretScope::Bot(bot_id);
+
+        let env_provider = MockProvider::new("env", false)
+            .with_value("API_KEY", &SecretScope::Global, "env-value");
+        let vault_provider = MockProvider::new("vault", true)
+            .with_value("API_KEY", &SecretScope::Global, "vault-value")
+            .with_value("BOT_KEY", &bot_scope, "bot-value");
+
+        let service = SecretService::new(vec![
+            Arc::new(env_provider),
+            Arc::new(vault_provider),
+        ]);
+
+        let (value, source) = service
+            .get_with_source("API_KEY", &bot_scope)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(value, "env-value");
+        assert_eq!(
+            source,
+            SecretSource {
+                provider: boternity_types::secret::SecretProvider::Environment,
+                scope: SecretScope::Global,
+            }
+        );
+
+        let (_, source) = service
+            .get_with_source("BOT_KEY", &bot_scope)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            source.provider,
+            boternity_types::secret::SecretProvider::Vault
+        );
+        assert_eq!(source.scope, bot_scope);
+
+        assert!(
+            service
+                .get_with_source("MISSING", &bot_scope)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reveal_secret_is_redacted_until_exposed() {
+        let vault_provider = MockProvider::new("vault", true)
+            .with_value("API_KEY", &SecretScope::Global, "sk-plaintext");
+
+        let service = SecretService::new(vec![Arc::new(vault_provider)]);
+
+        let revealed = service
+            .reveal_secret("API_KEY", &SecretScope::Global, ScopeResolution::Inherit)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert!(!format!("{revealed:?}").contains("sk-plaintext"));
+        assert_eq!(revealed.expose(), "sk-plaintext");
+
+        let missing = service
+            .reveal_secret("MISSING", &SecretScope::Global, ScopeResolution::Inherit)
+            .await
+            .unwrap();
+        assert!(missing.is_none());
+    }
+
     #[tokio::test]
     async fn test_set_skips_readonly_provider() {
         let env_provider = MockProvider::new("env", false); // Read-only
@@ -15,13 +15,20 @@
 //! Both methods create a new version entry with SHA-256 hash. There is no
 //! method that silently overwrites SOUL.md without versioning. Any hash
 //! mismatch at bot startup is a hard block (CVE-2026-25253 mitigation).
+//!
+//! # History Chain
+//!
+//! Each new version records `prev_hash`, the chain hash of the version
+//! before it, where a version's chain hash is `hash(prev_hash ":" hash)`.
+//! Deleting or altering an intermediate version therefore breaks the link
+//! of every later version, which `verify_soul_history()` detects.
 
 use std::path::Path;
 
 use boternity_types::bot::{BotCategory, BotId};
 use boternity_types::error::SoulError;
 use boternity_types::identity::Identity;
-use boternity_types::soul::{Soul, SoulId, SoulIntegrityResult, SoulVersion};
+use boternity_types::soul::{Soul, SoulChainBreak, SoulId, SoulIntegrityResult, SoulVersion};
 
 use crate::repository::soul::SoulRepository;
 use crate::service::fs::FileSystem;
@@ -213,11 +220,12 @@ impl<S: SoulRepository, F: FileSystem, H: ContentHasher> SoulService<S, F, H> {
         // Compute hash
         let hash = self.hasher.compute_hash(content);
 
-        // Determine next version number
-        let next_version = match self.get_current_soul(bot_id).await? {
-            Some(current) => current.version + 1,
-            None => 1,
-        };
+        // Determine next version number and link to the current version
+        let current = self.get_current_soul(bot_id).await?;
+        let next_version = current.as_ref().map_or(1, |c| c.version + 1);
+        let prev_hash = current
+            .as_ref()
+            .map(|c| self.chain_hash(c.prev_hash.as_deref(), &c.hash));
 
         // Create soul version
         let soul = Soul {
@@ -228,6 +236,7 @@ impl<S: SoulRepository, F: FileSystem, H: ContentHasher> SoulService<S, F, H> {
             version: next_version,
             message: None,
             created_at: chrono::Utc::now(),
+            prev_hash,
         };
 
         // Save to repository
@@ -261,11 +270,12 @@ impl<S: SoulRepository, F: FileSystem, H: ContentHasher> SoulService<S, F, H> {
         // Compute hash
         let hash = self.hasher.compute_hash(&new_content);
 
-        // Determine next version number
-        let next_version = match self.get_current_soul(bot_id).await? {
-            Some(current) => current.version + 1,
-            None => 1,
-        };
+        // Determine next version number and link to the current version
+        let current = self.get_current_soul(bot_id).await?;
+        let next_version = current.as_ref().map_or(1, |c| c.version + 1);
+        let prev_hash = current
+            .as_ref()
+            .map(|c| self.chain_hash(c.prev_hash.as_deref(), &c.hash));
 
         // Create soul version
         let soul = Soul {
@@ -276,6 +286,7 @@ impl<S: SoulRepository, F: FileSystem, H: ContentHasher> SoulService<S, F, H> {
             version: next_version,
             message,
             created_at: chrono::Utc::now(),
+            prev_hash,
         };
 
         // Save to repository first (if this fails, disk is unchanged)
@@ -320,7 +331,8 @@ impl<S: SoulRepository, F: FileSystem, H: ContentHasher> SoulService<S, F, H> {
     }
 
     /// Verify soul integrity by comparing the SOUL.md file on disk with the
-    /// stored hash in the database.
+    /// stored hash in the database, and checking the stored version history
+    /// with `verify_soul_history()`.
     ///
     /// This is a pure read/verify operation -- it does NOT modify anything.
     /// Returns a `SoulIntegrityResult` with detailed hash comparison.
@@ -336,25 +348,75 @@ impl<S: SoulRepository, F: FileSystem, H: ContentHasher> SoulService<S, F, H> {
             .await
             .map_err(|e| SoulError::FileSystemError(e.to_string()))?;
 
-        // Compute hash of file content
-        let actual_hash = self.hasher.compute_hash(&file_content);
-
         // Get current soul from database (for stored hash and version)
         let current = self
             .get_current_soul(bot_id)
             .await?
             .ok_or(SoulError::NotFound)?;
 
-        let valid = actual_hash == current.hash;
+        // Hash the file with the algorithm the stored hash was made with
+        let actual_hash = self.hasher.compute_hash_as(&file_content, &current.hash);
+
+        let history_break = self.verify_soul_history(bot_id).await?;
+        let valid = actual_hash == current.hash && history_break.is_none();
 
         Ok(SoulIntegrityResult {
             valid,
             expected_hash: current.hash,
             actual_hash,
             version: current.version,
+            history_break,
         })
     }
 
+    /// Walk the full version history and return the first break in the chain.
+    ///
+    /// Checks that versions are numbered 1..=n without gaps, that every
+    /// version's content matches its hash, and that every version links to
+    /// its predecessor. Versions saved before chaining existed carry no link;
+    /// they are accepted only as an unbroken prefix of the history.
+    pub async fn verify_soul_history(
+        &self,
+        bot_id: &BotId,
+    ) -> Result<Option<SoulChainBreak>, SoulError> {
+        let mut versions = self.get_soul_versions(bot_id).await?;
+        versions.sort_by_key(|v| v.version);
+
+        let mut prev: Option<&SoulVersion> = None;
+        let mut legacy_prefix = true;
+        for (expected_version, v) in (1..).zip(&versions) {
+            if v.version != expected_version {
+                return Ok(Some(SoulChainBreak::MissingVersion {
+                    version: expected_version,
+                }));
+            }
+            if !self.hasher.verify_hash(&v.content, &v.hash) {
+                return Ok(Some(SoulChainBreak::ContentMismatch { version: v.version }));
+            }
+
+            legacy_prefix &= v.prev_hash.is_none();
+            let linked = match (prev, v.prev_hash.as_deref()) {
+                (None, None) => true,
+                (None, Some(_)) => false,
+                (Some(_), None) => legacy_prefix,
+                (Some(p), Some(link)) => self
+                    .hasher
+                    .verify_hash(&chain_input(p.prev_hash.as_deref(), &p.hash), link),
+            };
+            if !linked {
+                return Ok(Some(SoulChainBreak::BrokenLink { version: v.version }));
+            }
+            prev = Some(v);
+        }
+
+        Ok(None)
+    }
+
+    /// Chain hash of a version, linking its content hash to its predecessor.
+    fn chain_hash(&self, prev_hash: Option<&str>, hash: &str) -> String {
+        self.hasher.compute_hash(&chain_input(prev_hash, hash))
+    }
+
     /// Compute a simple line-by-line diff between two soul versions.
     ///
     /// Returns a string with lines prefixed by `+` (additions) and `-`
@@ -434,13 +496,18 @@ impl<S: SoulRepository, F: FileSystem, H: ContentHasher> SoulService<S, F, H> {
     }
 }
 
+/// Input hashed into a version's chain hash.
+fn chain_input(prev_hash: Option<&str>, hash: &str) -> String {
+    format!("{}:{hash}", prev_hash.unwrap_or_default())
+}
+
 /// Compute a simple line-by-line diff between two strings.
 ///
 /// Uses a basic longest-common-subsequence approach for line-level diffing.
 /// Lines present in `old` but not `new` are prefixed with `-`.
 /// Lines present in `new` but not `old` are prefixed with `+`.
 /// Unchanged lines are prefixed with ` ` (space).
-fn compute_line_diff(old: &str, new: &str) -> String {
+pub(crate) fn compute_line_diff(old: &str, new: &str) -> String {
     let old_lines: Vec<&str> = old.lines().collect();
     let new_lines: Vec<&str> = new.lines().collect();
 
@@ -548,6 +615,7 @@ mod tests {
                     content: s.content.clone(),
                     created_at: s.created_at,
                     message: s.message.clone(),
+                    prev_hash: s.prev_hash.clone(),
                 })
                 .collect())
         }
@@ -865,6 +933,129 @@ mod tests {
         assert!(result.is_err());
     }
 
+    // --- History chain tests ---
+
+    async fn build_chain(svc: &SoulService<MockSoulRepo, MockFs, MockHasher>, bot_id: &BotId) {
+        let path = PathBuf::from("/tmp/test/SOUL.md");
+        for i in 1..=4 {
+            svc.update_soul(bot_id, format!("Content v{i}"), None, &path)
+                .await
+                .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_history_chain_links_every_version() {
+        let svc = make_service();
+        let bot_id = test_bot_id();
+        build_chain(&svc, &bot_id).await;
+
+        let versions = svc.get_soul_versions(&bot_id).await.unwrap();
+        assert!(versions[0].prev_hash.is_none());
+        assert!(versions[1..].iter().all(|v| v.prev_hash.is_some()));
+
+        assert_eq!(svc.verify_soul_history(&bot_id).await.unwrap(), None);
+        let result = svc
+            .verify_soul_integrity(&bot_id, &PathBuf::from("/tmp/test/SOUL.md"))
+            .await
+            .unwrap();
+        assert!(result.valid);
+        assert!(result.history_break.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_history_detects_tampered_intermediate_version() {
+        let svc = make_service();
+        let bot_id = test_bot_id();
+        build_chain(&svc, &bot_id).await;
+
+        // Rewrite version 2 in storage, updating its hash so the content
+        // check alone would pass
+        {
+            let mut souls = svc.soul_repo.souls.lock().unwrap();
+            let v2 = souls.iter_mut().find(|s| s.version == 2).unwrap();
+            v2.content = "Rewritten history".to_string();
+            v2.hash = MockHasher.compute_hash(&v2.content);
+        }
+
+        assert_eq!(
+            svc.verify_soul_history(&bot_id).await.unwrap(),
+            Some(SoulChainBreak::BrokenLink { version: 3 })
+        );
+
+        // The file on disk still matches the current version, but the
+        // overall verification fails
+        let result = svc
+            .verify_soul_integrity(&bot_id, &PathBuf::from("/tmp/test/SOUL.md"))
+            .await
+            .unwrap();
+        assert_eq!(result.expected_hash, result.actual_hash);
+        assert!(!result.valid);
+    }
+
+    #[tokio::test]
+    async fn test_history_detects_deleted_or_altered_versions() {
+        let svc = make_service();
+        let bot_id = test_bot_id();
+        build_chain(&svc, &bot_id).await;
+
+        svc.soul_repo
+            .souls
+            .lock()
+            .unwrap()
+            .retain(|s| s.version != 2);
+        assert_eq!(
+            svc.verify_soul_history(&bot_id).await.unwrap(),
+            Some(SoulChainBreak::MissingVersion { version: 2 })
+        );
+
+        let svc = make_service();
+        build_chain(&svc, &bot_id).await;
+        svc.soul_repo.souls.lock().unwrap()[2].content = "Edited in place".to_string();
+        assert_eq!(
+            svc.verify_soul_history(&bot_id).await.unwrap(),
+            Some(SoulChainBreak::ContentMismatch { version: 3 })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_history_accepts_unlinked_legacy_prefix() {
+        let svc = make_service();
+        let bot_id = test_bot_id();
+        build_chain(&svc, &bot_id).await;
+
+        // Dropping a single link mid-chain is not mistaken for legacy data:
+        // v3 still links to v2's original chain hash
+        {
+            let mut souls = svc.soul_repo.souls.lock().unwrap();
+            souls[1].prev_hash = None;
+        }
+        assert_eq!(
+            svc.verify_soul_history(&bot_id).await.unwrap(),
+            Some(SoulChainBreak::BrokenLink { version: 3 })
+        );
+
+        // A history saved entirely before chaining verifies, and new versions
+        // chain onto it
+        let svc = make_service();
+        build_chain(&svc, &bot_id).await;
+        {
+            let mut souls = svc.soul_repo.souls.lock().unwrap();
+            for s in souls.iter_mut() {
+                s.prev_hash = None;
+            }
+        }
+        svc.update_soul(
+            &bot_id,
+            "Content v5".to_string(),
+            None,
+            &PathBuf::from("/tmp/test/SOUL.md"),
+        )
+        .await
+        .unwrap();
+        assert_eq!(svc.verify_soul_history(&bot_id).await.unwrap(), None);
+    }
+
     // --- Diff tests ---
 
     #[tokio::test]
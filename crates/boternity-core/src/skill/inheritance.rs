@@ -313,6 +313,8 @@ mod tests {
                 parents: parents.map(|p| p.into_iter().map(String::from).collect()),
                 secrets: None,
                 categories: None,
+                cacheable: None,
+                manifest_version: None,
             }),
         }
     }
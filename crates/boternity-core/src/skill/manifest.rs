@@ -2,9 +2,108 @@
 //!
 //! Handles the agentskills.io-compatible manifest format: YAML frontmatter
 //! delimited by `---` followed by a markdown body containing skill instructions.
+//!
+//! `metadata.manifest-version` declares the boternity schema version a skill
+//! was written against. A newer major version is rejected; a newer minor
+//! version parses with a warning, ignoring fields this build does not know.
 
 use anyhow::{bail, Context};
 use boternity_types::skill::{BotSkillsFile, SkillManifest, SkillType};
+use serde_yaml_ng::Value;
+
+/// Manifest schema version this build reads, as `(major, minor)`.
+pub const MANIFEST_VERSION: (u64, u64) = (1, 0);
+
+/// Top-level frontmatter fields (agentskills.io).
+const KNOWN_FIELDS: &[&str] = &[
+    "name",
+    "description",
+    "license",
+    "compatibility",
+    "metadata",
+    "allowed-tools",
+];
+
+/// Boternity fields under `metadata`.
+const KNOWN_METADATA_FIELDS: &[&str] = &[
+    "author",
+    "version",
+    "skill-type",
+    "capabilities",
+    "dependencies",
+    "conflicts-with",
+    "trust-tier",
+    "parents",
+    "secrets",
+    "categories",
+    "cacheable",
+    "manifest-version",
+];
+
+/// How a manifest's declared schema version relates to [`MANIFEST_VERSION`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ManifestCompat {
+    /// Same major version, minor not newer than ours (or no version declared).
+    Supported,
+    /// Same major version, newer minor: parses, but fields added since are ignored.
+    NewerMinor { version: String },
+}
+
+/// Check a declared `manifest-version` (`major` or `major.minor`) against
+/// [`MANIFEST_VERSION`].
+///
+/// Fails for a malformed version or a different major version.
+pub fn check_manifest_version(version: Option<&str>) -> anyhow::Result<ManifestCompat> {
+    let Some(version) = version else {
+        return Ok(ManifestCompat::Supported);
+    };
+
+    let mut parts = version.trim().splitn(2, '.');
+    let major = parts.next().and_then(|p| p.parse::<u64>().ok());
+    let minor = match parts.next() {
+        Some(p) => p.parse::<u64>().ok(),
+        None => Some(0),
+    };
+    let (Some(major), Some(minor)) = (major, minor) else {
+        bail!("Invalid manifest-version '{version}': expected 'major.minor'");
+    };
+
+    let (supported_major, supported_minor) = MANIFEST_VERSION;
+    if major != supported_major {
+        bail!(
+            "SKILL.md manifest-version {version} is not supported (this build reads {supported_major}.x); \
+             upgrade boternity to use this skill"
+        );
+    }
+    if minor > supported_minor {
+        return Ok(ManifestCompat::NewerMinor {
+            version: version.to_owned(),
+        });
+    }
+    Ok(ManifestCompat::Supported)
+}
+
+/// Frontmatter fields this build does not know, as dotted paths
+/// (e.g. `metadata.retries`).
+pub fn unknown_fields(frontmatter: &Value) -> Vec<String> {
+    let mut unknown = Vec::new();
+    let Some(map) = frontmatter.as_mapping() else {
+        return unknown;
+    };
+    for key in map.keys().filter_map(Value::as_str) {
+        if !KNOWN_FIELDS.contains(&key) {
+            unknown.push(key.to_owned());
+        }
+    }
+    if let Some(metadata) = map.get("metadata").and_then(Value::as_mapping) {
+        for key in metadata.keys().filter_map(Value::as_str) {
+            if !KNOWN_METADATA_FIELDS.contains(&key) {
+                unknown.push(format!("metadata.{key}"));
+            }
+        }
+    }
+    unknown
+}
 
 /// Extract YAML frontmatter and markdown body from a SKILL.md file.
 ///
@@ -40,14 +139,42 @@ pub fn extract_frontmatter(content: &str) -> anyhow::Result<(&str, &str)> {
 
 /// Parse a SKILL.md file into a `SkillManifest` and markdown body.
 ///
-/// Splits the content into YAML frontmatter and body, then deserializes
+/// Splits the content into YAML frontmatter and body, checks the declared
+/// `manifest-version` (see [`check_manifest_version`]), then deserializes
 /// the frontmatter into a `SkillManifest`.
 pub fn parse_skill_md(content: &str) -> anyhow::Result<(SkillManifest, String)> {
     let (yaml_str, body_str) = extract_frontmatter(content)?;
 
-    let manifest: SkillManifest =
+    let mut frontmatter: Value =
         serde_yaml_ng::from_str(yaml_str).context("Failed to parse SKILL.md YAML frontmatter")?;
 
+    // An unquoted `manifest-version: 1.1` parses as a number
+    let declared = frontmatter
+        .get_mut("metadata")
+        .and_then(|m| m.get_mut("manifest-version"));
+    if let Some(declared) = declared
+        && let Value::Number(n) = declared
+    {
+        *declared = Value::String(n.to_string());
+    }
+    let version = frontmatter
+        .get("metadata")
+        .and_then(|m| m.get("manifest-version"))
+        .and_then(Value::as_str);
+
+    if let ManifestCompat::NewerMinor { version } = check_manifest_version(version)? {
+        let (major, minor) = MANIFEST_VERSION;
+        tracing::warn!(
+            manifest_version = %version,
+            supported = %format!("{major}.{minor}"),
+            ignored_fields = ?unknown_fields(&frontmatter),
+            "SKILL.md uses a newer manifest minor version; unknown fields are ignored"
+        );
+    }
+
+    let manifest: SkillManifest = serde_yaml_ng::from_value(frontmatter)
+        .context("Failed to parse SKILL.md YAML frontmatter")?;
+
     Ok((manifest, body_str.to_owned()))
 }
 
@@ -261,6 +388,62 @@ Say hello to the user.
         validate_manifest(&manifest).unwrap();
     }
 
+    #[test]
+    fn parse_current_manifest_version() {
+        let content = "---\nname: hello-world\ndescription: Greets\nmetadata:\n  manifest-version: \"1.0\"\n---\n\nSay hello.\n";
+        let (manifest, _body) = parse_skill_md(content).unwrap();
+        let meta = manifest.metadata.unwrap();
+        assert_eq!(meta.manifest_version.as_deref(), Some("1.0"));
+
+        assert_eq!(
+            check_manifest_version(Some("1.0")).unwrap(),
+            ManifestCompat::Supported
+        );
+        assert_eq!(
+            check_manifest_version(Some("1")).unwrap(),
+            ManifestCompat::Supported
+        );
+        // No declared version is read as 1.0
+        assert_eq!(
+            check_manifest_version(None).unwrap(),
+            ManifestCompat::Supported
+        );
+    }
+
+    #[test]
+    fn parse_future_minor_manifest_ignores_new_fields() {
+        let content = "---\nname: hello-world\ndescription: Greets\nsandbox-profile: strict\nmetadata:\n  manifest-version: 1.3\n  retries: 3\n---\n\nSay hello.\n";
+        let (manifest, _body) = parse_skill_md(content).unwrap();
+        assert_eq!(manifest.name, "hello-world");
+        assert_eq!(
+            manifest.metadata.unwrap().manifest_version.as_deref(),
+            Some("1.3")
+        );
+
+        assert_eq!(
+            check_manifest_version(Some("1.3")).unwrap(),
+            ManifestCompat::NewerMinor {
+                version: "1.3".to_owned()
+            }
+        );
+        let (yaml, _) = extract_frontmatter(content).unwrap();
+        let frontmatter: Value = serde_yaml_ng::from_str(yaml).unwrap();
+        assert_eq!(
+            unknown_fields(&frontmatter),
+            vec!["sandbox-profile".to_owned(), "metadata.retries".to_owned()]
+        );
+    }
+
+    #[test]
+    fn reject_future_major_manifest() {
+        let content = "---\nname: hello-world\ndescription: Greets\nmetadata:\n  manifest-version: \"2.0\"\n---\n\nSay hello.\n";
+        let err = parse_skill_md(content).unwrap_err();
+        assert!(err.to_string().contains("not supported"), "{err}");
+
+        assert!(check_manifest_version(Some("one.two")).is_err());
+        assert!(check_manifest_version(Some("0.9")).is_err());
+    }
+
     #[test]
     fn reject_missing_frontmatter() {
         let content = "# No Frontmatter\n\nJust a markdown file.";
@@ -376,6 +559,8 @@ Say hello to the user.
                 parents: None,
                 secrets: None,
                 categories: None,
+                cacheable: None,
+                manifest_version: None,
             }),
             allowed_tools: None,
         };
@@ -409,6 +594,8 @@ Say hello to the user.
                 parents: None,
                 secrets: None,
                 categories: None,
+                cacheable: None,
+                manifest_version: None,
             }),
             allowed_tools: None,
         };
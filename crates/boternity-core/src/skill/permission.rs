@@ -26,6 +26,10 @@ pub enum PermissionError {
     /// The skill has no permission grants at all.
     #[error("no permission grants exist for skill '{skill_name}'")]
     NoGrants { skill_name: String },
+
+    /// The skill asked for a secret it did not declare in its manifest.
+    #[error("secret '{secret}' is not declared by skill '{skill_name}'")]
+    UndeclaredSecret { secret: String, skill_name: String },
 }
 
 // ---------------------------------------------------------------------------
@@ -135,6 +139,35 @@ pub fn create_grants_from_manifest(
         .collect()
 }
 
+/// Secret names a skill declares in its manifest `secrets` list.
+///
+/// Even with `GetSecret` granted, a skill may only read the secrets it
+/// declares here.
+pub fn declared_secrets(manifest: &SkillManifest) -> HashSet<String> {
+    manifest
+        .metadata
+        .as_ref()
+        .and_then(|m| m.secrets.as_ref())
+        .map(|secrets| secrets.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Check that `secret` is one of the skill's declared secrets.
+pub fn check_secret_declared(
+    skill_name: &str,
+    declared: &HashSet<String>,
+    secret: &str,
+) -> Result<(), PermissionError> {
+    if declared.contains(secret) {
+        Ok(())
+    } else {
+        Err(PermissionError::UndeclaredSecret {
+            secret: secret.to_string(),
+            skill_name: skill_name.to_string(),
+        })
+    }
+}
+
 /// Revoke a specific capability from the grants list.
 ///
 /// Sets `granted = false` for the matching capability. If the capability
@@ -232,6 +265,8 @@ mod tests {
                 parents: None,
                 secrets: None,
                 categories: None,
+                cacheable: None,
+                manifest_version: None,
             }),
         }
     }
@@ -373,6 +408,26 @@ mod tests {
         assert!(!grants[0].granted);
     }
 
+    #[test]
+    fn only_declared_secrets_pass_check() {
+        let mut manifest = make_manifest("my-skill", vec![Capability::GetSecret]);
+        manifest.metadata.as_mut().unwrap().secrets = Some(vec!["GITHUB_TOKEN".to_string()]);
+        let declared = declared_secrets(&manifest);
+
+        assert!(check_secret_declared("my-skill", &declared, "GITHUB_TOKEN").is_ok());
+        match check_secret_declared("my-skill", &declared, "ANTHROPIC_API_KEY").unwrap_err() {
+            PermissionError::UndeclaredSecret { secret, skill_name } => {
+                assert_eq!(secret, "ANTHROPIC_API_KEY");
+                assert_eq!(skill_name, "my-skill");
+            }
+            _ => panic!("expected UndeclaredSecret error"),
+        }
+
+        // No secrets section -> nothing is declared
+        let bare = make_manifest("bare-skill", vec![Capability::GetSecret]);
+        assert!(declared_secrets(&bare).is_empty());
+    }
+
     #[test]
     fn grant_capability_adds_new_entry() {
         let mut grants = vec![make_grant("test-skill", Capability::HttpGet, true)];
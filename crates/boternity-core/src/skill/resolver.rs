@@ -250,6 +250,8 @@ mod tests {
                 parents: None,
                 secrets: None,
                 categories: None,
+                cacheable: None,
+                manifest_version: None,
             }),
         }
     }
@@ -276,6 +278,8 @@ mod tests {
                 parents: None,
                 secrets: None,
                 categories: None,
+                cacheable: None,
+                manifest_version: None,
             }),
         }
     }
@@ -20,7 +20,7 @@ pub trait KvStore: Send + Sync {
         key: &str,
     ) -> impl std::future::Future<Output = Result<Option<serde_json::Value>, RepositoryError>> + Send;
 
-    /// Set a value for a key (upsert).
+    /// Set a value for a key (upsert). Clears any existing TTL.
     fn set(
         &self,
         bot_id: &Uuid,
@@ -28,6 +28,28 @@ pub trait KvStore: Send + Sync {
         value: &serde_json::Value,
     ) -> impl std::future::Future<Output = Result<(), RepositoryError>> + Send;
 
+    /// Set a value that expires after `ttl` (upsert).
+    ///
+    /// `None` behaves like `set`. Expired keys read as absent.
+    fn set_with_ttl(
+        &self,
+        bot_id: &Uuid,
+        key: &str,
+        value: &serde_json::Value,
+        ttl: Option<std::time::Duration>,
+    ) -> impl std::future::Future<Output = Result<(), RepositoryError>> + Send;
+
+    /// Atomically add `delta` to an integer value and return the new value.
+    ///
+    /// A missing or expired key starts at 0. An existing TTL is preserved.
+    /// Errors with `RepositoryError::Conflict` if the stored value is not an integer.
+    fn increment(
+        &self,
+        bot_id: &Uuid,
+        key: &str,
+        delta: i64,
+    ) -> impl std::future::Future<Output = Result<i64, RepositoryError>> + Send;
+
     /// Delete a key. No-op if key does not exist.
     fn delete(
         &self,
@@ -171,6 +171,36 @@ impl<R: WorkflowRepository> CheckpointManager<R> {
         Ok(())
     }
 
+    /// Mark every step still `Running` in a run as failed with `reason`.
+    ///
+    /// Used when the run is stopped underneath in-flight steps (e.g. a
+    /// workflow timeout). Returns the interrupted step logs.
+    pub async fn interrupt_running_steps(
+        &self,
+        run_id: Uuid,
+        reason: &str,
+    ) -> Result<Vec<WorkflowStepLog>, CheckpointError> {
+        let running: Vec<WorkflowStepLog> = self
+            .repo
+            .list_step_logs(&run_id)
+            .await
+            .map_err(|e| CheckpointError::Repository(e.to_string()))?
+            .into_iter()
+            .filter(|log| log.status == WorkflowStepStatus::Running)
+            .collect();
+
+        for log in &running {
+            self.checkpoint_step_failed(log.id, reason).await?;
+        }
+
+        tracing::debug!(
+            run_id = %run_id,
+            interrupted = running.len(),
+            "checkpointed interrupted steps"
+        );
+        Ok(running)
+    }
+
     // -----------------------------------------------------------------------
     // Run-level checkpoints
     // -----------------------------------------------------------------------
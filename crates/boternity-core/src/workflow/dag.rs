@@ -3,10 +3,14 @@
 //! Uses `petgraph` to model step dependencies as a directed graph. Topological
 //! sort detects cycles, and depth-based grouping produces parallel execution
 //! waves where all steps in a wave can run concurrently.
+//!
+//! Steps named in a conditional's `then_steps`/`else_steps` are ordered after
+//! the conditional, so the untaken branch can be skipped before it starts.
+//! Skips then propagate to steps whose dependencies were all skipped.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-use boternity_types::workflow::StepDefinition;
+use boternity_types::workflow::{StepConfig, StepDefinition};
 use petgraph::algo::toposort;
 use petgraph::graph::DiGraph;
 
@@ -21,7 +25,8 @@ use super::definition::WorkflowError;
 /// Each wave contains steps that can execute concurrently because all their
 /// dependencies are satisfied by prior waves. The algorithm:
 ///
-/// 1. Build a `DiGraph` with step IDs as nodes and `depends_on` edges.
+/// 1. Build a `DiGraph` with step IDs as nodes and `depends_on` edges, plus
+///    an edge from each conditional to the steps on its branches.
 /// 2. Run `petgraph::algo::toposort` to verify acyclicity.
 /// 3. Compute each node's depth (max dependency depth + 1).
 /// 4. Group steps by depth into waves.
@@ -35,13 +40,12 @@ pub fn build_execution_plan<'a>(
     }
 
     // Map step IDs to indices for petgraph
-    let id_to_step: HashMap<&str, &StepDefinition> =
-        steps.iter().map(|s| (s.id.as_str(), s)).collect();
     let id_to_idx: HashMap<&str, usize> = steps
         .iter()
         .enumerate()
         .map(|(i, s)| (s.id.as_str(), i))
         .collect();
+    let dependencies = ordering_dependencies(steps)?;
 
     // Build directed graph: edge from dependency -> dependent
     let mut graph = DiGraph::<&str, ()>::new();
@@ -49,14 +53,8 @@ pub fn build_execution_plan<'a>(
 
     for step in steps {
         let to_idx = id_to_idx[step.id.as_str()];
-        for dep in &step.depends_on {
-            let from_idx = id_to_idx.get(dep.as_str()).ok_or_else(|| {
-                WorkflowError::UnknownDependency(format!(
-                    "step '{}' depends on unknown step '{}'",
-                    step.id, dep
-                ))
-            })?;
-            graph.add_edge(node_indices[*from_idx], node_indices[to_idx], ());
+        for dep in &dependencies[step.id.as_str()] {
+            graph.add_edge(node_indices[id_to_idx[dep]], node_indices[to_idx], ());
         }
     }
 
@@ -70,13 +68,12 @@ pub fn build_execution_plan<'a>(
     let mut depths: HashMap<&str, usize> = HashMap::new();
     for &node_idx in &sorted {
         let step_id = graph[node_idx];
-        let step = id_to_step[step_id];
-        let depth = if step.depends_on.is_empty() {
+        let deps = &dependencies[step_id];
+        let depth = if deps.is_empty() {
             0
         } else {
-            step.depends_on
-                .iter()
-                .map(|dep| depths.get(dep.as_str()).copied().unwrap_or(0) + 1)
+            deps.iter()
+                .map(|dep| depths.get(dep).copied().unwrap_or(0) + 1)
                 .max()
                 .unwrap_or(0)
         };
@@ -106,19 +103,15 @@ pub fn validate_dag(steps: &[StepDefinition]) -> Result<(), WorkflowError> {
         .map(|(i, s)| (s.id.as_str(), i))
         .collect();
 
+    let dependencies = ordering_dependencies(steps)?;
+
     let mut graph = DiGraph::<&str, ()>::new();
     let node_indices: Vec<_> = steps.iter().map(|s| graph.add_node(s.id.as_str())).collect();
 
     for step in steps {
         let to_idx = id_to_idx[step.id.as_str()];
-        for dep in &step.depends_on {
-            let from_idx = id_to_idx.get(dep.as_str()).ok_or_else(|| {
-                WorkflowError::UnknownDependency(format!(
-                    "step '{}' depends on unknown step '{}'",
-                    step.id, dep
-                ))
-            })?;
-            graph.add_edge(node_indices[*from_idx], node_indices[to_idx], ());
+        for dep in &dependencies[step.id.as_str()] {
+            graph.add_edge(node_indices[id_to_idx[dep]], node_indices[to_idx], ());
         }
     }
 
@@ -130,6 +123,89 @@ pub fn validate_dag(steps: &[StepDefinition]) -> Result<(), WorkflowError> {
     Ok(())
 }
 
+/// Dependencies that order execution: each step's `depends_on`, plus the
+/// conditional step for every step named on one of its branches.
+///
+/// Branch references to unknown steps are left to definition validation.
+fn ordering_dependencies(
+    steps: &[StepDefinition],
+) -> Result<HashMap<&str, Vec<&str>>, WorkflowError> {
+    let ids: HashSet<&str> = steps.iter().map(|s| s.id.as_str()).collect();
+    let mut dependencies: HashMap<&str, Vec<&str>> =
+        steps.iter().map(|s| (s.id.as_str(), Vec::new())).collect();
+
+    for step in steps {
+        for dep in &step.depends_on {
+            if !ids.contains(dep.as_str()) {
+                return Err(WorkflowError::UnknownDependency(format!(
+                    "step '{}' depends on unknown step '{}'",
+                    step.id, dep
+                )));
+            }
+            dependencies
+                .get_mut(step.id.as_str())
+                .unwrap()
+                .push(dep.as_str());
+        }
+    }
+
+    for step in steps {
+        if let StepConfig::Conditional {
+            then_steps,
+            else_steps,
+            ..
+        } = &step.config
+        {
+            for branch_step in then_steps.iter().chain(else_steps) {
+                if let Some(deps) = dependencies.get_mut(branch_step.as_str())
+                    && !deps.contains(&step.id.as_str())
+                {
+                    deps.push(step.id.as_str());
+                }
+            }
+        }
+    }
+
+    Ok(dependencies)
+}
+
+// ---------------------------------------------------------------------------
+// Conditional branch skipping
+// ---------------------------------------------------------------------------
+
+/// Step IDs on the branch a conditional step did not take.
+///
+/// Empty for non-conditional steps. A step named on both branches is never
+/// returned, since the taken branch still needs it.
+pub fn untaken_branch(step: &StepDefinition, condition_met: bool) -> Vec<&str> {
+    let StepConfig::Conditional {
+        then_steps,
+        else_steps,
+        ..
+    } = &step.config
+    else {
+        return Vec::new();
+    };
+
+    let (taken, untaken) = if condition_met {
+        (then_steps, else_steps)
+    } else {
+        (else_steps, then_steps)
+    };
+    untaken
+        .iter()
+        .filter(|id| !taken.contains(id))
+        .map(String::as_str)
+        .collect()
+}
+
+/// Whether `step` depends only on skipped steps and should be skipped too.
+///
+/// Steps without `depends_on` entries are never skipped this way.
+pub fn all_dependencies_skipped(step: &StepDefinition, skipped: &HashSet<String>) -> bool {
+    !step.depends_on.is_empty() && step.depends_on.iter().all(|dep| skipped.contains(dep))
+}
+
 // ---------------------------------------------------------------------------
 // Transitive dependency closure
 // ---------------------------------------------------------------------------
@@ -321,6 +397,76 @@ mod tests {
         assert!(deps.is_empty());
     }
 
+    // -----------------------------------------------------------------------
+    // Conditional branches
+    // -----------------------------------------------------------------------
+
+    fn conditional_step(id: &str, then_steps: Vec<&str>, else_steps: Vec<&str>) -> StepDefinition {
+        StepDefinition {
+            step_type: StepType::Conditional,
+            config: StepConfig::Conditional {
+                condition: "trigger.fast == true".to_string(),
+                then_steps: then_steps.into_iter().map(String::from).collect(),
+                else_steps: else_steps.into_iter().map(String::from).collect(),
+            },
+            ..agent_step(id, vec![])
+        }
+    }
+
+    #[test]
+    fn test_branch_steps_run_after_conditional() {
+        // Branch steps declare no dependency, but must wait for the decision
+        let steps = vec![
+            conditional_step("check", vec!["fast"], vec!["slow"]),
+            agent_step("fast", vec![]),
+            agent_step("slow", vec![]),
+            agent_step("report", vec!["fast", "slow"]),
+        ];
+        let waves = build_execution_plan(&steps).unwrap();
+        let ids: Vec<Vec<&str>> = waves
+            .iter()
+            .map(|wave| wave.iter().map(|s| s.id.as_str()).collect())
+            .collect();
+        assert_eq!(
+            ids,
+            vec![vec!["check"], vec!["fast", "slow"], vec!["report"]]
+        );
+    }
+
+    #[test]
+    fn test_conditional_depending_on_its_branch_is_a_cycle() {
+        let mut check = conditional_step("check", vec!["fast"], vec![]);
+        check.depends_on = vec!["fast".to_string()];
+        let steps = vec![check, agent_step("fast", vec![])];
+        let err = validate_dag(&steps).unwrap_err();
+        assert!(err.to_string().contains("cycle detected"));
+    }
+
+    #[test]
+    fn test_untaken_branch() {
+        let step = conditional_step("check", vec!["fast", "shared"], vec!["slow", "shared"]);
+        assert_eq!(untaken_branch(&step, true), vec!["slow"]);
+        assert_eq!(untaken_branch(&step, false), vec!["fast"]);
+        assert!(untaken_branch(&agent_step("a", vec![]), true).is_empty());
+    }
+
+    #[test]
+    fn test_all_dependencies_skipped() {
+        let skipped: HashSet<String> = ["slow".to_string()].into();
+        assert!(all_dependencies_skipped(
+            &agent_step("x", vec!["slow"]),
+            &skipped
+        ));
+        assert!(!all_dependencies_skipped(
+            &agent_step("x", vec!["slow", "fast"]),
+            &skipped
+        ));
+        assert!(!all_dependencies_skipped(
+            &agent_step("x", vec![]),
+            &skipped
+        ));
+    }
+
     // -----------------------------------------------------------------------
     // Complex DAG: fork-join with multiple paths
     // -----------------------------------------------------------------------
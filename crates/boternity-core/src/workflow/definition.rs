@@ -262,6 +262,7 @@ fn discover_recursive(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use boternity_types::schema::WORKFLOW_DEFINITION_SCHEMA_VERSION;
     use boternity_types::workflow::{
         StepConfig, StepDefinition, StepType, TriggerConfig, WorkflowDefinition, WorkflowOwner,
     };
@@ -281,6 +282,7 @@ mod tests {
             triggers: vec![TriggerConfig::Manual {}],
             steps,
             metadata: HashMap::new(),
+            schema_version: WORKFLOW_DEFINITION_SCHEMA_VERSION,
         }
     }
 
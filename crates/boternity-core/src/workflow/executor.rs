@@ -13,6 +13,15 @@
 //! 4. Each step: checkpoint start -> evaluate condition -> run step -> checkpoint result.
 //! 5. Accumulate outputs in `WorkflowContext`.
 //! 6. On completion/failure/cancellation, update the run record.
+//!
+//! The whole run is bounded by `WorkflowDefinition::timeout_secs` (default
+//! [`DEFAULT_WORKFLOW_TIMEOUT_SECS`]). On timeout, in-flight steps are aborted
+//! and their logs marked failed; the run is `Crashed` if any step was
+//! interrupted and `Failed` otherwise.
+//!
+//! When a conditional step picks a branch, the steps on the other branch are
+//! checkpointed as skipped without running, as are steps whose dependencies
+//! were all skipped.
 
 use std::collections::HashSet;
 use std::sync::Arc;
@@ -20,7 +29,7 @@ use std::time::Duration;
 
 use boternity_types::event::AgentEvent;
 use boternity_types::workflow::{
-    WorkflowDefinition, WorkflowRun, WorkflowRunStatus, StepDefinition,
+    WorkflowDefinition, WorkflowRun, WorkflowRunStatus, StepConfig, StepDefinition,
 };
 use chrono::Utc;
 use dashmap::DashMap;
@@ -30,14 +39,16 @@ use tokio::task::JoinSet;
 use uuid::Uuid;
 
 use crate::event::bus::EventBus;
+use crate::message::MessageBus;
 use crate::repository::workflow::WorkflowRepository;
+use crate::service::secret::SecretService;
 
 use super::checkpoint::{CheckpointError, CheckpointManager};
 use super::context::WorkflowContext;
-use super::dag::build_execution_plan;
+use super::dag::{all_dependencies_skipped, build_execution_plan, untaken_branch};
 use super::definition::WorkflowError;
 use super::expression::WorkflowEvaluator;
-use super::step_runner::StepRunner;
+use super::step_runner::{StepOutput, StepRunner};
 
 // ---------------------------------------------------------------------------
 // Constants
@@ -49,6 +60,9 @@ pub const DEFAULT_WORKFLOW_TIMEOUT_SECS: u64 = 1800;
 /// Default step-level timeout (5 minutes).
 pub const DEFAULT_STEP_TIMEOUT_SECS: u64 = 300;
 
+/// Error recorded on steps aborted by the workflow-level timeout.
+pub const STEP_INTERRUPTED_BY_TIMEOUT: &str = "interrupted by workflow timeout";
+
 // ---------------------------------------------------------------------------
 // WorkflowExecutor trait
 // ---------------------------------------------------------------------------
@@ -154,6 +168,169 @@ impl<R: WorkflowRepository + 'static> DagExecutor<R> {
         }
     }
 
+    /// Send message steps on `bus`.
+    pub fn with_message_bus(mut self, bus: Arc<MessageBus>) -> Self {
+        self.step_runner = Arc::new(self.step_runner.as_ref().clone().with_message_bus(bus));
+        self
+    }
+
+    /// Resolve `{{ secrets.NAME }}` step references through `secrets`.
+    pub fn with_secrets(mut self, secrets: Arc<SecretService>) -> Self {
+        self.step_runner = Arc::new(self.step_runner.as_ref().clone().with_secrets(secrets));
+        self
+    }
+
+    /// Execute `definition` as run `run_id`, recording `idempotency_key` on
+    /// the run record.
+    ///
+    /// Lets callers hand out the run ID before execution starts, e.g. to
+    /// answer duplicate webhook deliveries with the same run.
+    pub async fn execute_run(
+        &self,
+        definition: &WorkflowDefinition,
+        run_id: Uuid,
+        trigger_type: &str,
+        trigger_payload: Option<Value>,
+        idempotency_key: Option<String>,
+    ) -> Result<ExecutionResult, ExecutorError> {
+        // Acquire concurrency permit (released on drop)
+        let _permit = self.acquire_concurrency_permit(definition).await?;
+
+        let cancel_token = tokio_util::sync::CancellationToken::new();
+        self.cancellation_tokens
+            .insert(run_id, cancel_token.clone());
+
+        let mut ctx = WorkflowContext::new(
+            definition.name.clone(),
+            run_id,
+            trigger_payload.clone(),
+        );
+
+        // Create the run record
+        let run = WorkflowRun {
+            id: run_id,
+            workflow_id: definition.id,
+            workflow_name: definition.name.clone(),
+            status: WorkflowRunStatus::Running,
+            trigger_type: trigger_type.to_string(),
+            trigger_payload,
+            context: ctx.to_json(),
+            started_at: Utc::now(),
+            completed_at: None,
+            error: None,
+            concurrency_key: Some(definition.name.clone()),
+            idempotency_key,
+        };
+
+        self.checkpoint
+            .repo()
+            .create_run(&run)
+            .await
+            .map_err(|e| {
+                ExecutorError::Workflow(WorkflowError::ExecutionError(e.to_string()))
+            })?;
+
+        // Publish run started event
+        self.event_bus.publish(AgentEvent::WorkflowRunStarted {
+            run_id,
+            workflow_name: definition.name.clone(),
+            trigger_type: trigger_type.to_string(),
+        });
+
+        tracing::info!(
+            run_id = %run_id,
+            workflow = definition.name.as_str(),
+            "starting workflow execution"
+        );
+
+        let run_start = std::time::Instant::now();
+        let completed_steps = HashSet::new();
+        let result = self
+            .execute_waves(definition, run_id, &mut ctx, &completed_steps, &cancel_token)
+            .await;
+
+        // Clean up cancellation token
+        self.cancellation_tokens.remove(&run_id);
+
+        match result {
+            Ok(status) => {
+                self.checkpoint
+                    .checkpoint_run_status(run_id, status, None, Some(&ctx.to_json()))
+                    .await
+                    .map_err(ExecutorError::Checkpoint)?;
+
+                let completed = self
+                    .checkpoint
+                    .get_completed_steps(run_id)
+                    .await
+                    .unwrap_or_default();
+
+                // Publish run completed event
+                self.event_bus.publish(AgentEvent::WorkflowRunCompleted {
+                    run_id,
+                    workflow_name: definition.name.clone(),
+                    duration_ms: run_start.elapsed().as_millis() as u64,
+                    steps_completed: completed.len() as u32,
+                });
+
+                Ok(ExecutionResult {
+                    run_id,
+                    status,
+                    context: ctx,
+                    completed_steps: completed,
+                    error: None,
+                })
+            }
+            Err(ExecutorError::ApprovalRequired { step_id, prompt }) => {
+                // Workflow is paused -- not a failure
+                let completed = self
+                    .checkpoint
+                    .get_completed_steps(run_id)
+                    .await
+                    .unwrap_or_default();
+
+                // Publish run paused event
+                self.event_bus.publish(AgentEvent::WorkflowRunPaused {
+                    run_id,
+                    step_id: step_id.clone(),
+                    reason: prompt.clone(),
+                });
+
+                Ok(ExecutionResult {
+                    run_id,
+                    status: WorkflowRunStatus::Paused,
+                    context: ctx,
+                    completed_steps: completed,
+                    error: Some(format!(
+                        "approval required at step '{}': {}",
+                        step_id, prompt
+                    )),
+                })
+            }
+            Err(e) => {
+                let err_msg = e.to_string();
+                let _ = self
+                    .checkpoint
+                    .checkpoint_run_status(
+                        run_id,
+                        e.run_status(),
+                        Some(&err_msg),
+                        Some(&ctx.to_json()),
+                    )
+                    .await;
+
+                // Publish run failed event
+                self.event_bus.publish(AgentEvent::WorkflowRunFailed {
+                    run_id,
+                    workflow_name: definition.name.clone(),
+                    error: err_msg,
+                });
+
+                Err(e)
+            }
+        }
+    }
+
     /// Acquire a concurrency permit for the workflow (if concurrency is limited).
     async fn acquire_concurrency_permit(
         &self,
@@ -194,9 +371,14 @@ impl<R: WorkflowRepository + 'static> DagExecutor<R> {
             .map(|wave| wave.into_iter().cloned().collect())
             .collect();
 
-        let workflow_timeout = Duration::from_secs(
-            definition.timeout_secs.unwrap_or(DEFAULT_WORKFLOW_TIMEOUT_SECS),
-        );
+        let timeout_secs = definition.timeout_secs.unwrap_or(DEFAULT_WORKFLOW_TIMEOUT_SECS);
+        let workflow_timeout = Duration::from_secs(timeout_secs);
+
+        // Untaken branches of conditionals that already ran (on resume)
+        let mut skipped = skipped_branches(definition, ctx);
+
+        // Shared across waves so in-flight steps can be stopped on timeout
+        let mut join_set = JoinSet::new();
 
         let execution = async {
             for (wave_idx, wave) in waves.iter().enumerate() {
@@ -211,9 +393,29 @@ impl<R: WorkflowRepository + 'static> DagExecutor<R> {
                     "processing wave"
                 );
 
-                let mut join_set = JoinSet::new();
-
                 for step_def in wave {
+                    // Skip untaken branches and steps that only depend on skipped steps
+                    if skipped.contains(&step_def.id)
+                        || all_dependencies_skipped(step_def, &skipped)
+                    {
+                        skipped.insert(step_def.id.clone());
+                        if !completed_steps.contains(&step_def.id) {
+                            tracing::debug!(
+                                step_id = step_def.id.as_str(),
+                                "skipping step on untaken branch"
+                            );
+                            self.checkpoint
+                                .checkpoint_step_skipped(
+                                    run_id,
+                                    &step_def.id,
+                                    &step_def.name,
+                                )
+                                .await
+                                .map_err(ExecutorError::Checkpoint)?;
+                        }
+                        continue;
+                    }
+
                     // Skip already-completed steps (crash recovery)
                     if completed_steps.contains(&step_def.id) {
                         tracing::debug!(
@@ -376,6 +578,17 @@ impl<R: WorkflowRepository + 'static> DagExecutor<R> {
 
                     match task_result {
                         Ok((step_id, output)) => {
+                            if let StepOutput::Branch { condition_met, .. } = &output
+                                && let Some(step) =
+                                    definition.steps.iter().find(|s| s.id == step_id)
+                            {
+                                skipped.extend(
+                                    untaken_branch(step, *condition_met)
+                                        .into_iter()
+                                        .map(String::from),
+                                );
+                            }
+
                             ctx.set_step_output(&step_id, output.to_value())
                                 .map_err(ExecutorError::Workflow)?;
 
@@ -413,9 +626,43 @@ impl<R: WorkflowRepository + 'static> DagExecutor<R> {
         };
 
         // Apply workflow-level timeout
-        tokio::time::timeout(workflow_timeout, execution)
-            .await
-            .map_err(|_| ExecutorError::WorkflowTimeout)?
+        let outcome = tokio::time::timeout(workflow_timeout, execution).await;
+        match outcome {
+            Ok(result) => result,
+            Err(_elapsed) => {
+                // Wait for in-flight steps to abort before recording them
+                join_set.shutdown().await;
+                let interrupted = self
+                    .checkpoint
+                    .interrupt_running_steps(run_id, STEP_INTERRUPTED_BY_TIMEOUT)
+                    .await
+                    .map_err(ExecutorError::Checkpoint)?;
+
+                for log in &interrupted {
+                    self.event_bus.publish(AgentEvent::WorkflowStepFailed {
+                        run_id,
+                        step_id: log.step_id.clone(),
+                        step_name: log.step_name.clone(),
+                        error: STEP_INTERRUPTED_BY_TIMEOUT.to_string(),
+                        will_retry: false,
+                    });
+                }
+
+                let interrupted_steps: Vec<String> =
+                    interrupted.into_iter().map(|log| log.step_id).collect();
+                tracing::warn!(
+                    run_id = %run_id,
+                    timeout_secs,
+                    interrupted = ?interrupted_steps,
+                    "workflow timed out"
+                );
+
+                Err(ExecutorError::WorkflowTimeout {
+                    timeout_secs,
+                    interrupted_steps,
+                })
+            }
+        }
     }
 }
 
@@ -426,142 +673,14 @@ impl<R: WorkflowRepository + 'static> WorkflowExecutor for DagExecutor<R> {
         trigger_type: &str,
         trigger_payload: Option<Value>,
     ) -> Result<ExecutionResult, ExecutorError> {
-        // Acquire concurrency permit (released on drop)
-        let _permit = self.acquire_concurrency_permit(definition).await?;
-
-        let run_id = Uuid::now_v7();
-        let cancel_token = tokio_util::sync::CancellationToken::new();
-        self.cancellation_tokens
-            .insert(run_id, cancel_token.clone());
-
-        let mut ctx = WorkflowContext::new(
-            definition.name.clone(),
-            run_id,
-            trigger_payload.clone(),
-        );
-
-        // Create the run record
-        let run = WorkflowRun {
-            id: run_id,
-            workflow_id: definition.id,
-            workflow_name: definition.name.clone(),
-            status: WorkflowRunStatus::Running,
-            trigger_type: trigger_type.to_string(),
+        self.execute_run(
+            definition,
+            Uuid::now_v7(),
+            trigger_type,
             trigger_payload,
-            context: ctx.to_json(),
-            started_at: Utc::now(),
-            completed_at: None,
-            error: None,
-            concurrency_key: Some(definition.name.clone()),
-        };
-
-        self.checkpoint
-            .repo()
-            .create_run(&run)
-            .await
-            .map_err(|e| {
-                ExecutorError::Workflow(WorkflowError::ExecutionError(e.to_string()))
-            })?;
-
-        // Publish run started event
-        self.event_bus.publish(AgentEvent::WorkflowRunStarted {
-            run_id,
-            workflow_name: definition.name.clone(),
-            trigger_type: trigger_type.to_string(),
-        });
-
-        tracing::info!(
-            run_id = %run_id,
-            workflow = definition.name.as_str(),
-            "starting workflow execution"
-        );
-
-        let run_start = std::time::Instant::now();
-        let completed_steps = HashSet::new();
-        let result = self
-            .execute_waves(definition, run_id, &mut ctx, &completed_steps, &cancel_token)
-            .await;
-
-        // Clean up cancellation token
-        self.cancellation_tokens.remove(&run_id);
-
-        match result {
-            Ok(status) => {
-                self.checkpoint
-                    .checkpoint_run_status(run_id, status, None, Some(&ctx.to_json()))
-                    .await
-                    .map_err(ExecutorError::Checkpoint)?;
-
-                let completed = self
-                    .checkpoint
-                    .get_completed_steps(run_id)
-                    .await
-                    .unwrap_or_default();
-
-                // Publish run completed event
-                self.event_bus.publish(AgentEvent::WorkflowRunCompleted {
-                    run_id,
-                    workflow_name: definition.name.clone(),
-                    duration_ms: run_start.elapsed().as_millis() as u64,
-                    steps_completed: completed.len() as u32,
-                });
-
-                Ok(ExecutionResult {
-                    run_id,
-                    status,
-                    context: ctx,
-                    completed_steps: completed,
-                    error: None,
-                })
-            }
-            Err(ExecutorError::ApprovalRequired { step_id, prompt }) => {
-                // Workflow is paused -- not a failure
-                let completed = self
-                    .checkpoint
-                    .get_completed_steps(run_id)
-                    .await
-                    .unwrap_or_default();
-
-                // Publish run paused event
-                self.event_bus.publish(AgentEvent::WorkflowRunPaused {
-                    run_id,
-                    step_id: step_id.clone(),
-                    reason: prompt.clone(),
-                });
-
-                Ok(ExecutionResult {
-                    run_id,
-                    status: WorkflowRunStatus::Paused,
-                    context: ctx,
-                    completed_steps: completed,
-                    error: Some(format!(
-                        "approval required at step '{}': {}",
-                        step_id, prompt
-                    )),
-                })
-            }
-            Err(e) => {
-                let err_msg = e.to_string();
-                let _ = self
-                    .checkpoint
-                    .checkpoint_run_status(
-                        run_id,
-                        WorkflowRunStatus::Failed,
-                        Some(&err_msg),
-                        Some(&ctx.to_json()),
-                    )
-                    .await;
-
-                // Publish run failed event
-                self.event_bus.publish(AgentEvent::WorkflowRunFailed {
-                    run_id,
-                    workflow_name: definition.name.clone(),
-                    error: err_msg,
-                });
-
-                Err(e)
-            }
-        }
+            None,
+        )
+        .await
     }
 
     async fn resume(
@@ -661,7 +780,7 @@ impl<R: WorkflowRepository + 'static> WorkflowExecutor for DagExecutor<R> {
                     .checkpoint
                     .checkpoint_run_status(
                         run_id,
-                        WorkflowRunStatus::Failed,
+                        e.run_status(),
                         Some(&err_msg),
                         Some(&ctx.to_json()),
                     )
@@ -696,6 +815,28 @@ impl<R: WorkflowRepository + 'static> WorkflowExecutor for DagExecutor<R> {
     }
 }
 
+/// Untaken branches of the conditional steps whose output is already in `ctx`.
+fn skipped_branches(definition: &WorkflowDefinition, ctx: &WorkflowContext) -> HashSet<String> {
+    let mut skipped = HashSet::new();
+    for step in &definition.steps {
+        if !matches!(step.config, StepConfig::Conditional { .. }) {
+            continue;
+        }
+        let condition_met = ctx
+            .get_step_output(&step.id)
+            .and_then(|output| output.get("condition_met"))
+            .and_then(Value::as_bool);
+        if let Some(condition_met) = condition_met {
+            skipped.extend(
+                untaken_branch(step, condition_met)
+                    .into_iter()
+                    .map(String::from),
+            );
+        }
+    }
+    skipped
+}
+
 // ---------------------------------------------------------------------------
 // ExecutorError
 // ---------------------------------------------------------------------------
@@ -720,8 +861,15 @@ pub enum ExecutorError {
     StepTimeout { step_id: String },
 
     /// Workflow exceeded its overall timeout.
-    #[error("workflow timed out")]
-    WorkflowTimeout,
+    #[error(
+        "workflow timed out after {timeout_secs}s{}",
+        interrupted_suffix(.interrupted_steps)
+    )]
+    WorkflowTimeout {
+        timeout_secs: u64,
+        /// Steps that were still running and got aborted.
+        interrupted_steps: Vec<String>,
+    },
 
     /// An approval gate requires human intervention.
     #[error("approval required at step '{step_id}': {prompt}")]
@@ -736,6 +884,29 @@ pub enum ExecutorError {
     RunNotFound(Uuid),
 }
 
+impl ExecutorError {
+    /// Run status to record when execution ends with this error.
+    ///
+    /// A timeout that aborted in-flight steps leaves the run `Crashed`; any
+    /// other error marks it `Failed`.
+    pub fn run_status(&self) -> WorkflowRunStatus {
+        match self {
+            ExecutorError::WorkflowTimeout {
+                interrupted_steps, ..
+            } if !interrupted_steps.is_empty() => WorkflowRunStatus::Crashed,
+            _ => WorkflowRunStatus::Failed,
+        }
+    }
+}
+
+fn interrupted_suffix(steps: &[String]) -> String {
+    if steps.is_empty() {
+        String::new()
+    } else {
+        format!(" (interrupted steps: {})", steps.join(", "))
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -753,7 +924,10 @@ mod tests {
         assert!(err.to_string().contains("gather"));
         assert!(err.to_string().contains("timeout"));
 
-        let err = ExecutorError::WorkflowTimeout;
+        let err = ExecutorError::WorkflowTimeout {
+            timeout_secs: 60,
+            interrupted_steps: vec![],
+        };
         assert!(err.to_string().contains("timed out"));
 
         let err = ExecutorError::ApprovalRequired {
@@ -764,6 +938,65 @@ mod tests {
         assert!(err.to_string().contains("check results"));
     }
 
+    #[test]
+    fn workflow_timeout_status_and_message() {
+        let idle = ExecutorError::WorkflowTimeout {
+            timeout_secs: 60,
+            interrupted_steps: vec![],
+        };
+        assert_eq!(idle.run_status(), WorkflowRunStatus::Failed);
+        assert_eq!(idle.to_string(), "workflow timed out after 60s");
+
+        let mid_step = ExecutorError::WorkflowTimeout {
+            timeout_secs: 60,
+            interrupted_steps: vec!["fetch".to_string(), "summarize".to_string()],
+        };
+        assert_eq!(mid_step.run_status(), WorkflowRunStatus::Crashed);
+        assert_eq!(
+            mid_step.to_string(),
+            "workflow timed out after 60s (interrupted steps: fetch, summarize)"
+        );
+
+        assert_eq!(
+            ExecutorError::Cancelled.run_status(),
+            WorkflowRunStatus::Failed
+        );
+    }
+
+    #[test]
+    fn skipped_branches_restored_from_context() {
+        let step = |id: &str, config: StepConfig| StepDefinition {
+            id: id.to_string(),
+            name: id.to_string(),
+            step_type: boternity_types::workflow::StepType::Conditional,
+            depends_on: vec![],
+            condition: None,
+            timeout_secs: None,
+            retry: None,
+            config,
+            ui: None,
+        };
+        let branch = |then_id: &str, else_id: &str| StepConfig::Conditional {
+            condition: "true".to_string(),
+            then_steps: vec![then_id.to_string()],
+            else_steps: vec![else_id.to_string()],
+        };
+        let definition = boternity_types::workflow::WorkflowDefinitionBuilder::new("test")
+            .step(step("decided", branch("a", "b")))
+            .step(step("pending", branch("c", "d")))
+            .build();
+
+        let mut ctx = WorkflowContext::new("test".to_string(), Uuid::nil(), None);
+        ctx.set_step_output(
+            "decided",
+            serde_json::json!({"type": "branch", "condition_met": false, "selected_steps": ["b"]}),
+        )
+        .unwrap();
+
+        let skipped = skipped_branches(&definition, &ctx);
+        assert_eq!(skipped, HashSet::from(["a".to_string()]));
+    }
+
     #[test]
     fn execution_result_default_fields() {
         let result = ExecutionResult {
@@ -1,15 +1,69 @@
 //! JEXL expression evaluator for workflow `when` clauses and conditional steps.
 //!
-//! Wraps `jexl_eval::Evaluator` with pre-registered standard transforms and
-//! provides convenience methods for boolean evaluation in workflow contexts.
+//! Wraps `jexl_eval::Evaluator` with a fixed function library and provides
+//! convenience methods for boolean evaluation in workflow contexts.
+//!
+//! JEXL calls functions as transforms (`subject|name(args)`). The library:
+//!
+//! | Group  | Functions |
+//! |--------|-----------|
+//! | String | `lower`, `upper`, `trim`, `split(sep)`, `join(sep)`, `replace(from, to)`, `startsWith(s)`, `endsWith(s)`, `match(s)` |
+//! | Any    | `length`, `contains(x)`, `not` |
+//! | Date   | `now` (Unix seconds; subject ignored, e.g. `0\|now`), `timestamp` (RFC 3339 -> Unix seconds), `isoDate` (Unix seconds -> RFC 3339) |
+//! | JSON   | `keys`, `values`, `toJson`, `fromJson` |
+//!
+//! Functions return `null` for input of the wrong type instead of failing.
+//!
+//! **Sandboxing:** expressions are checked before evaluation. Calls to
+//! functions outside the library are rejected, and expressions longer than
+//! [`MAX_EXPRESSION_LEN`], nested deeper than [`MAX_NESTING_DEPTH`] or
+//! applying more than [`MAX_FUNCTION_CALLS`] functions are rejected as too
+//! complex. String-producing functions return `null` rather than build
+//! strings over [`MAX_STRING_OUTPUT`] bytes, so chained calls cannot blow up.
 //!
 //! **Security note:** Payloads are always passed as context objects, NEVER
 //! interpolated into expression strings.
 
+use chrono::{DateTime, Utc};
 use serde_json::{json, Value};
 
 use super::context::WorkflowContext;
 
+/// Maximum expression length in bytes.
+pub const MAX_EXPRESSION_LEN: usize = 4096;
+
+/// Maximum nesting depth of parentheses, brackets and braces.
+pub const MAX_NESTING_DEPTH: usize = 32;
+
+/// Maximum number of function (transform) applications in one expression.
+pub const MAX_FUNCTION_CALLS: usize = 64;
+
+/// Maximum size in bytes of a string built by a library function.
+pub const MAX_STRING_OUTPUT: usize = 64 * 1024;
+
+/// Every function available to workflow expressions.
+pub const FUNCTIONS: &[&str] = &[
+    "lower",
+    "upper",
+    "trim",
+    "split",
+    "join",
+    "replace",
+    "startsWith",
+    "endsWith",
+    "match",
+    "length",
+    "contains",
+    "not",
+    "now",
+    "timestamp",
+    "isoDate",
+    "keys",
+    "values",
+    "toJson",
+    "fromJson",
+];
+
 // ---------------------------------------------------------------------------
 // Error type
 // ---------------------------------------------------------------------------
@@ -25,13 +79,19 @@ pub enum ExpressionError {
 
     #[error("Invalid context: {0}")]
     InvalidContext(String),
+
+    #[error("Unknown function '{0}'")]
+    UnknownFunction(String),
+
+    #[error("Expression too complex: {0}")]
+    TooComplex(String),
 }
 
 // ---------------------------------------------------------------------------
 // WorkflowEvaluator
 // ---------------------------------------------------------------------------
 
-/// JEXL expression evaluator with standard transforms pre-registered.
+/// JEXL expression evaluator with the workflow function library registered.
 ///
 /// Used for:
 /// - Trigger `when` clause filtering (e.g. `event.source == 'github'`)
@@ -42,7 +102,7 @@ pub struct WorkflowEvaluator {
 }
 
 impl WorkflowEvaluator {
-    /// Create a new evaluator with all standard transforms registered.
+    /// Create a new evaluator with the full function library registered.
     pub fn new() -> Self {
         let evaluator = jexl_eval::Evaluator::new()
             // String transforms
@@ -79,6 +139,36 @@ impl WorkflowEvaluator {
                 let parts: Vec<&str> = s.split(delimiter).collect();
                 Ok(json!(parts))
             })
+            .with_transform("join", |args: &[Value]| {
+                let Some(items) = args.first().and_then(|v| v.as_array()) else {
+                    return Ok(Value::Null);
+                };
+                let separator = args.get(1).and_then(|v| v.as_str()).unwrap_or(",");
+                let parts: Vec<String> = items
+                    .iter()
+                    .map(|v| match v {
+                        Value::String(s) => s.clone(),
+                        other => other.to_string(),
+                    })
+                    .collect();
+                Ok(bounded_string(parts.join(separator)))
+            })
+            .with_transform("replace", |args: &[Value]| {
+                let Some(s) = args.first().and_then(|v| v.as_str()) else {
+                    return Ok(Value::Null);
+                };
+                let from = args.get(1).and_then(|v| v.as_str()).unwrap_or("");
+                let to = args.get(2).and_then(|v| v.as_str()).unwrap_or("");
+                if from.is_empty() {
+                    return Ok(json!(s));
+                }
+                // Check the size before building the string
+                let grows_by = to.len().saturating_sub(from.len());
+                if s.len() + s.matches(from).count() * grows_by > MAX_STRING_OUTPUT {
+                    return Ok(Value::Null);
+                }
+                Ok(json!(s.replace(from, to)))
+            })
             // Boolean transforms
             .with_transform("not", |args: &[Value]| {
                 let val = args.first().cloned().unwrap_or(Value::Null);
@@ -91,11 +181,20 @@ impl WorkflowEvaluator {
                 };
                 Ok(json!(!truthy))
             })
-            // String search transforms
+            // Search transforms (substring, array element, or object key)
             .with_transform("contains", |args: &[Value]| {
-                let subject = args.first().and_then(|v| v.as_str()).unwrap_or("");
-                let search = args.get(1).and_then(|v| v.as_str()).unwrap_or("");
-                Ok(json!(subject.contains(search)))
+                let search = args.get(1).cloned().unwrap_or(Value::Null);
+                let found = match args.first() {
+                    Some(Value::String(subject)) => {
+                        search.as_str().is_some_and(|s| subject.contains(s))
+                    }
+                    Some(Value::Array(items)) => items.contains(&search),
+                    Some(Value::Object(map)) => {
+                        search.as_str().is_some_and(|key| map.contains_key(key))
+                    }
+                    _ => false,
+                };
+                Ok(json!(found))
             })
             .with_transform("startsWith", |args: &[Value]| {
                 let subject = args.first().and_then(|v| v.as_str()).unwrap_or("");
@@ -124,6 +223,51 @@ impl WorkflowEvaluator {
                     _ => 0,
                 };
                 Ok(json!(len as f64))
+            })
+            // Date transforms (Unix seconds as numbers, RFC 3339 as strings)
+            .with_transform("now", |_args: &[Value]| {
+                Ok(json!(Utc::now().timestamp() as f64))
+            })
+            .with_transform("timestamp", |args: &[Value]| {
+                let parsed = args
+                    .first()
+                    .and_then(|v| v.as_str())
+                    .and_then(|s| DateTime::parse_from_rfc3339(s).ok());
+                Ok(parsed.map_or(Value::Null, |dt| json!(dt.timestamp() as f64)))
+            })
+            .with_transform("isoDate", |args: &[Value]| {
+                let formatted = args
+                    .first()
+                    .and_then(|v| v.as_f64())
+                    .and_then(|secs| DateTime::<Utc>::from_timestamp(secs as i64, 0))
+                    .map(|dt| dt.to_rfc3339());
+                Ok(formatted.map_or(Value::Null, Value::String))
+            })
+            // JSON transforms
+            .with_transform("keys", |args: &[Value]| {
+                let keys = args
+                    .first()
+                    .and_then(|v| v.as_object())
+                    .map(|map| map.keys().cloned().collect::<Vec<_>>());
+                Ok(keys.map_or(Value::Null, |k| json!(k)))
+            })
+            .with_transform("values", |args: &[Value]| {
+                let values = args
+                    .first()
+                    .and_then(|v| v.as_object())
+                    .map(|map| map.values().cloned().collect::<Vec<_>>());
+                Ok(values.map_or(Value::Null, Value::Array))
+            })
+            .with_transform("toJson", |args: &[Value]| {
+                let val = args.first().cloned().unwrap_or(Value::Null);
+                Ok(bounded_string(val.to_string()))
+            })
+            .with_transform("fromJson", |args: &[Value]| {
+                let parsed = args
+                    .first()
+                    .and_then(|v| v.as_str())
+                    .and_then(|s| serde_json::from_str::<Value>(s).ok());
+                Ok(parsed.unwrap_or(Value::Null))
             });
 
         Self { evaluator }
@@ -138,17 +282,7 @@ impl WorkflowEvaluator {
         expression: &str,
         context: &Value,
     ) -> Result<bool, ExpressionError> {
-        if !context.is_object() {
-            return Err(ExpressionError::InvalidContext(
-                "context must be a JSON object".to_string(),
-            ));
-        }
-
-        let result = self
-            .evaluator
-            .eval_in_context(expression, context)
-            .map_err(|e| ExpressionError::EvalFailed(e.to_string()))?;
-
+        let result = self.evaluate_value(expression, context)?;
         Ok(Self::value_to_bool(&result))
     }
 
@@ -176,6 +310,7 @@ impl WorkflowEvaluator {
                 "context must be a JSON object".to_string(),
             ));
         }
+        check_expression(expression)?;
 
         self.evaluator
             .eval_in_context(expression, context)
@@ -200,6 +335,92 @@ impl Default for WorkflowEvaluator {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Sandbox checks
+// ---------------------------------------------------------------------------
+
+/// Check an expression against the sandbox limits without evaluating it.
+///
+/// Rejects calls to functions outside [`FUNCTIONS`] and expressions over the
+/// length, nesting or function-call limits. Syntax errors are left to the
+/// evaluator.
+pub fn check_expression(expression: &str) -> Result<(), ExpressionError> {
+    if expression.len() > MAX_EXPRESSION_LEN {
+        return Err(ExpressionError::TooComplex(format!(
+            "{} bytes exceeds the {MAX_EXPRESSION_LEN} byte limit",
+            expression.len()
+        )));
+    }
+
+    let chars: Vec<char> = expression.chars().collect();
+    let mut depth = 0usize;
+    let mut calls = 0usize;
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            quote @ ('\'' | '"') => {
+                // Skip the string literal, honouring backslash escapes
+                i += 1;
+                while i < chars.len() && chars[i] != quote {
+                    if chars[i] == '\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+            }
+            '(' | '[' | '{' => {
+                depth += 1;
+                if depth > MAX_NESTING_DEPTH {
+                    return Err(ExpressionError::TooComplex(format!(
+                        "nesting exceeds depth {MAX_NESTING_DEPTH}"
+                    )));
+                }
+            }
+            ')' | ']' | '}' => depth = depth.saturating_sub(1),
+            '|' if chars.get(i + 1) == Some(&'|') => {
+                // Logical OR, not a transform
+                i += 1;
+            }
+            '|' => {
+                let mut start = i + 1;
+                while start < chars.len() && chars[start].is_whitespace() {
+                    start += 1;
+                }
+                let mut end = start;
+                while end < chars.len()
+                    && (chars[end].is_alphanumeric() || matches!(chars[end], '_' | '$'))
+                {
+                    end += 1;
+                }
+                let name: String = chars[start..end].iter().collect();
+                if !name.is_empty() && !FUNCTIONS.contains(&name.as_str()) {
+                    return Err(ExpressionError::UnknownFunction(name));
+                }
+                calls += 1;
+                if calls > MAX_FUNCTION_CALLS {
+                    return Err(ExpressionError::TooComplex(format!(
+                        "more than {MAX_FUNCTION_CALLS} function calls"
+                    )));
+                }
+                i = end;
+                continue;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    Ok(())
+}
+
+/// Wrap a built string, or `null` if it exceeds [`MAX_STRING_OUTPUT`].
+fn bounded_string(s: String) -> Value {
+    if s.len() > MAX_STRING_OUTPUT {
+        Value::Null
+    } else {
+        Value::String(s)
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -628,4 +849,218 @@ mod tests {
         assert!(eval.evaluate_bool("items|length > 3", &ctx).unwrap());
         assert!(!eval.evaluate_bool("items|length > 10", &ctx).unwrap());
     }
+
+    // -------------------------------------------------------------------
+    // Function library
+    // -------------------------------------------------------------------
+
+    #[test]
+    fn test_every_listed_function_is_registered() {
+        let ctx = json!({ "x": "value" });
+        let eval = evaluator();
+        for name in FUNCTIONS {
+            let expr = format!("x|{name}");
+            assert!(
+                eval.evaluate_value(&expr, &ctx).is_ok(),
+                "function '{name}' should be registered"
+            );
+        }
+    }
+
+    #[test]
+    fn test_transform_join() {
+        let ctx = json!({ "tags": ["a", "b", 3] });
+        let eval = evaluator();
+        let result = eval.evaluate_value("tags|join('-')", &ctx).unwrap();
+        assert_eq!(result, json!("a-b-3"));
+        let result = eval.evaluate_value("tags|join", &ctx).unwrap();
+        assert_eq!(result, json!("a,b,3"));
+    }
+
+    #[test]
+    fn test_transform_replace() {
+        let ctx = json!({ "branch": "refs/heads/main" });
+        let eval = evaluator();
+        let result = eval
+            .evaluate_value("branch|replace('refs/heads/', '')", &ctx)
+            .unwrap();
+        assert_eq!(result, json!("main"));
+    }
+
+    #[test]
+    fn test_transform_replace_output_is_bounded() {
+        let ctx = json!({ "s": "a".repeat(1024), "big": "b".repeat(1024) });
+        let eval = evaluator();
+        // 1024 matches * 1023 extra bytes would exceed MAX_STRING_OUTPUT
+        let result = eval.evaluate_value("s|replace('a', big)", &ctx).unwrap();
+        assert_eq!(result, Value::Null);
+    }
+
+    #[test]
+    fn test_transform_contains_array_and_object() {
+        let ctx = json!({
+            "labels": ["bug", "urgent"],
+            "payload": { "action": "opened" }
+        });
+        let eval = evaluator();
+        assert!(
+            eval.evaluate_bool("labels|contains('urgent')", &ctx)
+                .unwrap()
+        );
+        assert!(
+            !eval
+                .evaluate_bool("labels|contains('wontfix')", &ctx)
+                .unwrap()
+        );
+        assert!(
+            eval.evaluate_bool("payload|contains('action')", &ctx)
+                .unwrap()
+        );
+        assert!(
+            !eval
+                .evaluate_bool("payload|contains('sender')", &ctx)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_transform_now() {
+        let ctx = json!({});
+        let eval = evaluator();
+        let before = Utc::now().timestamp() as f64;
+        let now = eval
+            .evaluate_value("0|now", &ctx)
+            .unwrap()
+            .as_f64()
+            .unwrap();
+        assert!(now >= before && now <= before + 5.0);
+    }
+
+    #[test]
+    fn test_transform_timestamp() {
+        let ctx = json!({ "at": "2026-01-01T00:00:00Z", "bad": "yesterday" });
+        let eval = evaluator();
+        let result = eval.evaluate_value("at|timestamp", &ctx).unwrap();
+        assert_eq!(result.as_f64(), Some(1_767_225_600.0));
+        assert_eq!(
+            eval.evaluate_value("bad|timestamp", &ctx).unwrap(),
+            Value::Null
+        );
+        // Dates compare as numbers
+        assert!(eval.evaluate_bool("at|timestamp < 0|now", &ctx).unwrap());
+    }
+
+    #[test]
+    fn test_transform_iso_date() {
+        let ctx = json!({ "secs": 1_767_225_600 });
+        let eval = evaluator();
+        let result = eval.evaluate_value("secs|isoDate", &ctx).unwrap();
+        assert_eq!(result, json!("2026-01-01T00:00:00+00:00"));
+    }
+
+    #[test]
+    fn test_transform_keys_and_values() {
+        let ctx = json!({ "headers": { "x-kind": "push" } });
+        let eval = evaluator();
+        assert_eq!(
+            eval.evaluate_value("headers|keys", &ctx).unwrap(),
+            json!(["x-kind"])
+        );
+        assert_eq!(
+            eval.evaluate_value("headers|values", &ctx).unwrap(),
+            json!(["push"])
+        );
+        assert_eq!(
+            eval.evaluate_value("'text'|keys", &ctx).unwrap(),
+            Value::Null
+        );
+    }
+
+    #[test]
+    fn test_transform_json_round_trip() {
+        let ctx = json!({ "obj": { "a": "b" }, "raw": "{\"n\": \"v\"}" });
+        let eval = evaluator();
+        assert_eq!(
+            eval.evaluate_value("obj|toJson", &ctx).unwrap(),
+            json!(r#"{"a":"b"}"#)
+        );
+        assert_eq!(
+            eval.evaluate_value("raw|fromJson", &ctx).unwrap(),
+            json!({ "n": "v" })
+        );
+        assert_eq!(
+            eval.evaluate_value("'not json'|fromJson", &ctx).unwrap(),
+            Value::Null
+        );
+    }
+
+    // -------------------------------------------------------------------
+    // Sandboxing
+    // -------------------------------------------------------------------
+
+    #[test]
+    fn test_unknown_function_rejected() {
+        let ctx = json!({ "name": "x" });
+        let eval = evaluator();
+        let err = eval
+            .evaluate_bool("name|exec('rm -rf /')", &ctx)
+            .unwrap_err();
+        assert!(matches!(err, ExpressionError::UnknownFunction(ref f) if f == "exec"));
+        let err = eval.evaluate_value("name|lower|  shell", &ctx).unwrap_err();
+        assert!(matches!(err, ExpressionError::UnknownFunction(ref f) if f == "shell"));
+    }
+
+    #[test]
+    fn test_pipe_in_string_or_logical_or_is_not_a_function() {
+        let ctx = json!({ "name": "a|bogus", "flag": false });
+        let eval = evaluator();
+        assert!(eval.evaluate_bool("name == 'a|bogus'", &ctx).unwrap());
+        assert!(
+            eval.evaluate_bool("flag || name == \"a|bogus\"", &ctx)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_overlong_expression_rejected() {
+        let ctx = json!({});
+        let eval = evaluator();
+        let expr = format!("'{}' == ''", "a".repeat(MAX_EXPRESSION_LEN));
+        let err = eval.evaluate_bool(&expr, &ctx).unwrap_err();
+        assert!(matches!(err, ExpressionError::TooComplex(_)));
+    }
+
+    #[test]
+    fn test_deep_nesting_rejected() {
+        let ctx = json!({});
+        let eval = evaluator();
+        let depth = MAX_NESTING_DEPTH + 1;
+        let expr = format!("{}1{}", "(".repeat(depth), ")".repeat(depth));
+        let err = eval.evaluate_value(&expr, &ctx).unwrap_err();
+        assert!(matches!(err, ExpressionError::TooComplex(_)));
+
+        // Within the limit still evaluates
+        let depth = MAX_NESTING_DEPTH;
+        let expr = format!("{}1{}", "(".repeat(depth), ")".repeat(depth));
+        assert!(eval.evaluate_bool(&expr, &ctx).unwrap());
+    }
+
+    #[test]
+    fn test_too_many_function_calls_rejected() {
+        let ctx = json!({ "s": "x" });
+        let eval = evaluator();
+        let expr = format!("s{}", "|upper".repeat(MAX_FUNCTION_CALLS + 1));
+        let err = eval.evaluate_value(&expr, &ctx).unwrap_err();
+        assert!(matches!(err, ExpressionError::TooComplex(_)));
+    }
+
+    #[test]
+    fn test_chained_growth_is_bounded() {
+        let ctx = json!({ "s": "ab" });
+        let eval = evaluator();
+        // Each step doubles the string; the bound stops it early
+        let expr = format!("s{}", "|toJson".repeat(MAX_FUNCTION_CALLS));
+        let result = eval.evaluate_value(&expr, &ctx).unwrap();
+        assert!(result.as_str().is_none_or(|s| s.len() <= MAX_STRING_OUTPUT));
+    }
 }
@@ -8,9 +8,10 @@
 //! - `retry` -- Retry handler with simple and LLM self-correction strategies
 //! - `checkpoint` -- Durable checkpoint manager for crash recovery
 //! - `executor` -- Wave-based parallel DAG executor
-//! - `step_runner` -- Step type dispatchers for all 8 step types
+//! - `step_runner` -- Step type dispatchers for all 9 step types
 //! - `scheduler` -- Cron scheduler with human-readable schedules and missed-run catch-up
 //! - `trigger` -- TriggerManager coordinating cron, webhook, event, and file triggers
+//! - `tail` -- Follows a run's step logs as ordered start/complete/fail events
 
 pub mod checkpoint;
 pub mod context;
@@ -21,4 +22,5 @@ pub mod expression;
 pub mod retry;
 pub mod scheduler;
 pub mod step_runner;
+pub mod tail;
 pub mod trigger;
@@ -145,6 +145,13 @@ impl RetryHandler {
             StepConfig::SubWorkflow { workflow_name, .. } => {
                 format!("SubWorkflow step (workflow={workflow_name})")
             }
+            StepConfig::Message {
+                recipient,
+                message_type,
+                ..
+            } => {
+                format!("Message step (type={message_type}, recipient={recipient:?})")
+            }
         }
     }
 }
@@ -1,18 +1,37 @@
-//! Step runner for all 8 workflow step types.
+//! Step runner for all 9 workflow step types.
 //!
 //! `StepRunner` dispatches execution to the appropriate handler based on
 //! `StepConfig` variant. Each handler resolves templates from the workflow
 //! context, executes the step logic, and returns a `StepOutput`.
 //!
-//! Step types: Agent, Skill, Code, Http, Conditional, Loop, Approval, SubWorkflow.
+//! Step types: Agent, Skill, Code, Http, Conditional, Loop, Approval, SubWorkflow,
+//! Message.
+//!
+//! HTTP and Code steps may reference secrets as `{{ secrets.NAME }}`. These
+//! are looked up through the runner's `SecretService` when the step runs and
+//! are replaced with [`REDACTED`] in the step's output and error, so they never
+//! reach step logs or the workflow context.
 
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
+use boternity_types::message::{MessagePriority, MessageRecipient};
+use boternity_types::secret::SecretScope;
 use boternity_types::workflow::{StepConfig, StepDefinition};
 use serde_json::{json, Value};
+use uuid::Uuid;
 
 use super::context::WorkflowContext;
+use crate::message::MessageBus;
+use crate::message::envelope;
+use crate::service::secret::SecretService;
+
+/// Written in place of secret values in step output and errors.
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Opening of a secret reference in a step template.
+const SECRET_MARKER: &str = "{{ secrets.";
 
 // ---------------------------------------------------------------------------
 // StepExecutionContext trait
@@ -217,6 +236,17 @@ impl StepError {
             _ => None,
         }
     }
+
+    /// Replace each of `secrets` in the error message with [`REDACTED`].
+    fn redacted(self, secrets: &[String]) -> Self {
+        match self {
+            StepError::ExecutionFailed(msg) => {
+                StepError::ExecutionFailed(redact_str(&msg, secrets))
+            }
+            StepError::TemplateError(msg) => StepError::TemplateError(redact_str(&msg, secrets)),
+            other => other,
+        }
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -226,10 +256,16 @@ impl StepError {
 /// Executes individual workflow steps by dispatching to type-specific handlers.
 ///
 /// Holds an optional `StepExecutionContext` for wiring to real services.
-/// When `exec_ctx` is `None`, uses placeholder implementations.
+/// When `exec_ctx` is `None`, uses placeholder implementations. Message steps
+/// need a `MessageBus`, attached with [`StepRunner::with_message_bus`], and
+/// `{{ secrets.NAME }}` references need a `SecretService`, attached with
+/// [`StepRunner::with_secrets`].
+#[derive(Clone)]
 pub struct StepRunner {
     data_dir: PathBuf,
     exec_ctx: Arc<dyn StepExecutionContext>,
+    message_bus: Option<Arc<MessageBus>>,
+    secrets: Option<Arc<SecretService>>,
 }
 
 impl StepRunner {
@@ -238,12 +274,31 @@ impl StepRunner {
         Self {
             data_dir,
             exec_ctx: Arc::new(PlaceholderExecutionContext),
+            message_bus: None,
+            secrets: None,
         }
     }
 
     /// Create a new step runner with a custom execution context for real service wiring.
     pub fn with_context(data_dir: PathBuf, exec_ctx: Arc<dyn StepExecutionContext>) -> Self {
-        Self { data_dir, exec_ctx }
+        Self {
+            data_dir,
+            exec_ctx,
+            message_bus: None,
+            secrets: None,
+        }
+    }
+
+    /// Send message steps on `bus`.
+    pub fn with_message_bus(mut self, bus: Arc<MessageBus>) -> Self {
+        self.message_bus = Some(bus);
+        self
+    }
+
+    /// Resolve `{{ secrets.NAME }}` references through `secrets` (global scope).
+    pub fn with_secrets(mut self, secrets: Arc<SecretService>) -> Self {
+        self.secrets = Some(secrets);
+        self
     }
 
     /// Run a step and return its output.
@@ -295,6 +350,25 @@ impl StepRunner {
                 self.run_sub_workflow(workflow_name, input.as_ref(), ctx, 0)
                     .await
             }
+            StepConfig::Message {
+                sender_bot_id,
+                recipient,
+                message_type,
+                body,
+                priority,
+                ttl_secs,
+            } => {
+                self.run_message(
+                    *sender_bot_id,
+                    recipient,
+                    message_type,
+                    body,
+                    *priority,
+                    *ttl_secs,
+                    ctx,
+                )
+                .await
+            }
         }
     }
 
@@ -343,15 +417,20 @@ impl StepRunner {
         &self,
         language: &boternity_types::workflow::CodeLanguage,
         source: &str,
-        _ctx: &WorkflowContext,
+        ctx: &WorkflowContext,
     ) -> Result<StepOutput, StepError> {
+        let mut secrets = Vec::new();
+        let resolved_source = self.resolve_with_secrets(source, ctx, &mut secrets).await?;
+
         tracing::debug!(language = ?language, "running code step (placeholder)");
-        Ok(StepOutput::Value(json!({
+        let mut value = json!({
             "type": "code",
             "language": format!("{:?}", language).to_lowercase(),
-            "source_length": source.len(),
+            "source_length": resolved_source.len(),
             "output": "[placeholder] code execution result",
-        })))
+        });
+        redact_value(&mut value, &secrets);
+        Ok(StepOutput::Value(value))
     }
 
     // -- HTTP step: resolves templates, delegates to StepExecutionContext --
@@ -364,22 +443,31 @@ impl StepRunner {
         body: Option<&str>,
         ctx: &WorkflowContext,
     ) -> Result<StepOutput, StepError> {
-        let resolved_url = ctx.resolve_template(url);
-        let resolved_body = body.map(|b| ctx.resolve_template(b));
-        let resolved_headers: Option<std::collections::HashMap<String, String>> =
-            headers.map(|h| {
-                h.iter()
-                    .map(|(k, v)| (k.clone(), ctx.resolve_template(v)))
-                    .collect()
-            });
+        let mut secrets = Vec::new();
+        let resolved_url = self.resolve_with_secrets(url, ctx, &mut secrets).await?;
+        let resolved_body = match body {
+            Some(b) => Some(self.resolve_with_secrets(b, ctx, &mut secrets).await?),
+            None => None,
+        };
+        let resolved_headers: Option<std::collections::HashMap<String, String>> = match headers {
+            Some(h) => {
+                let mut resolved = std::collections::HashMap::with_capacity(h.len());
+                for (k, v) in h {
+                    let value = self.resolve_with_secrets(v, ctx, &mut secrets).await?;
+                    resolved.insert(k.clone(), value);
+                }
+                Some(resolved)
+            }
+            None => None,
+        };
 
         tracing::debug!(
             method,
-            url = resolved_url.as_str(),
+            url = redact_str(&resolved_url, &secrets).as_str(),
             "running HTTP step (template resolved)"
         );
 
-        let value = self
+        let mut value = self
             .exec_ctx
             .execute_http(
                 method,
@@ -387,10 +475,56 @@ impl StepRunner {
                 resolved_headers.as_ref(),
                 resolved_body.as_deref(),
             )
-            .await?;
+            .await
+            .map_err(|e| e.redacted(&secrets))?;
+        redact_value(&mut value, &secrets);
         Ok(StepOutput::Value(value))
     }
 
+    // -- Secrets: resolved per step, redacted from everything the step returns --
+
+    /// Resolve context templates and `{{ secrets.NAME }}` references.
+    ///
+    /// Secret references are only honoured in the authored template, not in
+    /// values substituted from the context, so a trigger payload cannot ask
+    /// for a secret. Each resolved value is pushed onto `resolved`.
+    async fn resolve_with_secrets(
+        &self,
+        template: &str,
+        ctx: &WorkflowContext,
+        resolved: &mut Vec<String>,
+    ) -> Result<String, StepError> {
+        let mut out = String::with_capacity(template.len());
+        let mut rest = template;
+        while let Some(start) = rest.find(SECRET_MARKER) {
+            let Some(len) = rest[start..].find(" }}") else {
+                break;
+            };
+            let name = rest[start + SECRET_MARKER.len()..start + len].trim();
+            out.push_str(&ctx.resolve_template(&rest[..start]));
+            let value = self.lookup_secret(name).await?;
+            out.push_str(&value);
+            resolved.push(value);
+            rest = &rest[start + len + 3..];
+        }
+        out.push_str(&ctx.resolve_template(rest));
+        Ok(out)
+    }
+
+    /// Look up a secret referenced by a step.
+    async fn lookup_secret(&self, name: &str) -> Result<String, StepError> {
+        let secrets = self.secrets.as_ref().ok_or_else(|| {
+            StepError::ExecutionFailed(format!(
+                "step references secret '{name}' but no secret provider is configured"
+            ))
+        })?;
+        secrets
+            .get_secret(name, &SecretScope::Global)
+            .await
+            .map_err(|e| StepError::ExecutionFailed(format!("secret '{name}' lookup failed: {e}")))?
+            .ok_or_else(|| StepError::ExecutionFailed(format!("secret '{name}' not found")))
+    }
+
     // -- Conditional: evaluates JEXL condition, returns branch selection --
 
     async fn run_conditional(
@@ -522,6 +656,112 @@ impl StepRunner {
             "output": format!("[placeholder] sub-workflow '{}' at depth {}", workflow_name, depth),
         })))
     }
+
+    // -- Message: resolves templates, sends on the MessageBus --
+
+    #[allow(clippy::too_many_arguments)]
+    async fn run_message(
+        &self,
+        sender_bot_id: Option<Uuid>,
+        recipient: &MessageRecipient,
+        message_type: &str,
+        body: &Value,
+        priority: MessagePriority,
+        ttl_secs: Option<u64>,
+        ctx: &WorkflowContext,
+    ) -> Result<StepOutput, StepError> {
+        let bus = self.message_bus.as_ref().ok_or_else(|| {
+            StepError::ExecutionFailed("message step requires a message bus".to_string())
+        })?;
+
+        // Without an explicit sender the workflow itself is the sender.
+        let sender_bot_id = sender_bot_id.unwrap_or(Uuid::nil());
+        let sender_name = format!("workflow:{}", ctx.workflow_name);
+        let body = resolve_value_templates(body, ctx);
+        let mut msg = match recipient {
+            MessageRecipient::Direct { bot_id } => {
+                envelope::direct(sender_bot_id, sender_name, *bot_id, message_type, body)
+            }
+            MessageRecipient::Channel { name } => envelope::channel(
+                sender_bot_id,
+                sender_name,
+                ctx.resolve_template(name),
+                message_type,
+                body,
+            ),
+        };
+        msg = envelope::with_priority(msg, priority);
+        if let Some(secs) = ttl_secs {
+            msg = envelope::with_ttl(msg, Duration::from_secs(secs));
+        }
+
+        tracing::debug!(
+            message_type,
+            recipient = ?msg.recipient,
+            "running message step"
+        );
+
+        let message_id = msg.id;
+        let recipient = msg.recipient.clone();
+        let delivered = match &recipient {
+            MessageRecipient::Direct { .. } => bus.send(msg).await.map(|()| 1),
+            MessageRecipient::Channel { .. } => bus.publish(msg),
+        }
+        .map_err(|e| StepError::ExecutionFailed(format!("message send failed: {e}")))?;
+
+        Ok(StepOutput::Value(json!({
+            "type": "message",
+            "message_id": message_id,
+            "recipient": recipient,
+            "delivered": delivered,
+        })))
+    }
+}
+
+/// Resolve templates in every string inside a JSON value.
+fn resolve_value_templates(value: &Value, ctx: &WorkflowContext) -> Value {
+    match value {
+        Value::String(s) => Value::String(ctx.resolve_template(s)),
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| resolve_value_templates(item, ctx))
+                .collect(),
+        ),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| (k.clone(), resolve_value_templates(v, ctx)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+/// Replace each of `secrets` in `s` with [`REDACTED`].
+fn redact_str(s: &str, secrets: &[String]) -> String {
+    let mut sorted: Vec<&String> = secrets.iter().filter(|v| !v.is_empty()).collect();
+    // Longest first, so a secret containing another is redacted whole
+    sorted.sort_by_key(|v| std::cmp::Reverse(v.len()));
+    let mut out = s.to_string();
+    for secret in sorted {
+        if out.contains(secret.as_str()) {
+            out = out.replace(secret.as_str(), REDACTED);
+        }
+    }
+    out
+}
+
+/// Replace each of `secrets` in every string inside `value` with [`REDACTED`].
+fn redact_value(value: &mut Value, secrets: &[String]) {
+    if secrets.is_empty() {
+        return;
+    }
+    match value {
+        Value::String(s) => *s = redact_str(s, secrets),
+        Value::Array(items) => items.iter_mut().for_each(|v| redact_value(v, secrets)),
+        Value::Object(map) => map.values_mut().for_each(|v| redact_value(v, secrets)),
+        _ => {}
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -531,6 +771,7 @@ impl StepRunner {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::message::LoopGuard;
     use serde_json::json;
     use uuid::Uuid;
 
@@ -801,6 +1042,88 @@ mod tests {
     // StepOutput::to_value
     // -------------------------------------------------------------------
 
+    // -------------------------------------------------------------------
+    // Message step: bus delivery
+    // -------------------------------------------------------------------
+
+    fn message_bus() -> Arc<MessageBus> {
+        Arc::new(MessageBus::new(Arc::new(LoopGuard::default())))
+    }
+
+    #[tokio::test]
+    async fn test_message_step_delivers_direct_message() {
+        let bus = message_bus();
+        let runner = StepRunner::new(PathBuf::from("/tmp")).with_message_bus(Arc::clone(&bus));
+        let ctx = test_context();
+        let recipient = Uuid::now_v7();
+        let mut mailbox = bus.register_bot(recipient);
+
+        let step = make_step(StepConfig::Message {
+            sender_bot_id: None,
+            recipient: MessageRecipient::Direct { bot_id: recipient },
+            message_type: "digest".to_string(),
+            body: json!({"text": "{{ steps.gather.output }}", "count": 5}),
+            priority: MessagePriority::High,
+            ttl_secs: Some(60),
+        });
+
+        let output = runner.run(&step, &ctx).await.unwrap().to_value();
+        assert_eq!(output["delivered"], 1);
+
+        let msg = mailbox.try_recv().unwrap();
+        assert_eq!(output["message_id"], json!(msg.id));
+        assert_eq!(msg.sender_bot_name, "workflow:test-workflow");
+        assert_eq!(msg.message_type, "digest");
+        assert_eq!(msg.body, json!({"text": "gathered data", "count": 5}));
+        assert_eq!(msg.priority, MessagePriority::High);
+        assert!(msg.expires_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_message_step_publishes_to_channel() {
+        let bus = message_bus();
+        let runner = StepRunner::new(PathBuf::from("/tmp")).with_message_bus(Arc::clone(&bus));
+        let ctx = test_context();
+        let mut subscriber = bus.subscribe("alerts");
+        let sender = Uuid::now_v7();
+
+        let step = make_step(StepConfig::Message {
+            sender_bot_id: Some(sender),
+            recipient: MessageRecipient::Channel {
+                name: "alerts".to_string(),
+            },
+            message_type: "alert".to_string(),
+            body: json!("{{ steps.gather.output }}"),
+            priority: MessagePriority::Normal,
+            ttl_secs: None,
+        });
+
+        let output = runner.run(&step, &ctx).await.unwrap().to_value();
+        assert_eq!(output["delivered"], 1);
+
+        let msg = subscriber.recv().await.unwrap();
+        assert_eq!(msg.sender_bot_id, sender);
+        assert_eq!(msg.body, json!("gathered data"));
+    }
+
+    #[tokio::test]
+    async fn test_message_step_without_bus_fails() {
+        let runner = StepRunner::new(PathBuf::from("/tmp"));
+        let step = make_step(StepConfig::Message {
+            sender_bot_id: None,
+            recipient: MessageRecipient::Direct {
+                bot_id: Uuid::now_v7(),
+            },
+            message_type: "ping".to_string(),
+            body: Value::Null,
+            priority: MessagePriority::Normal,
+            ttl_secs: None,
+        });
+
+        let err = runner.run(&step, &test_context()).await.unwrap_err();
+        assert!(err.to_string().contains("message bus"));
+    }
+
     #[test]
     fn test_step_output_to_value_branch() {
         let output = StepOutput::Branch {
@@ -823,4 +1146,225 @@ mod tests {
         assert_eq!(v["iterations"], 5);
         assert_eq!(v["completed"], true);
     }
+
+    // -------------------------------------------------------------------
+    // Secret injection
+    // -------------------------------------------------------------------
+
+    struct StaticSecrets(std::collections::HashMap<String, String>);
+
+    impl crate::repository::secret::SecretProvider for StaticSecrets {
+        fn kind(&self) -> crate::repository::secret::SecretProviderKind {
+            crate::repository::secret::SecretProviderKind::Vault
+        }
+
+        async fn get(
+            &self,
+            key: &str,
+            _scope: &SecretScope,
+        ) -> Result<Option<String>, boternity_types::error::RepositoryError> {
+            Ok(self.0.get(key).cloned())
+        }
+
+        async fn set(
+            &self,
+            _key: &str,
+            _value: &str,
+            _scope: &SecretScope,
+        ) -> Result<(), boternity_types::error::RepositoryError> {
+            Ok(())
+        }
+
+        async fn delete(
+            &self,
+            _key: &str,
+            _scope: &SecretScope,
+        ) -> Result<(), boternity_types::error::RepositoryError> {
+            Ok(())
+        }
+
+        async fn list(
+            &self,
+            _scope: &SecretScope,
+        ) -> Result<
+            Vec<boternity_types::secret::SecretEntry>,
+            boternity_types::error::RepositoryError,
+        > {
+            Ok(vec![])
+        }
+    }
+
+    fn secret_service() -> Arc<SecretService> {
+        let values = [("SLACK_TOKEN", "xoxb-secret"), ("HOOK_ID", "h-123")]
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        Arc::new(SecretService::new(vec![Arc::new(StaticSecrets(values))]))
+    }
+
+    /// Records HTTP requests and echoes them back; URLs containing "fail"
+    /// return an error that quotes the URL.
+    #[derive(Default)]
+    struct RecordingContext {
+        requests: std::sync::Mutex<Vec<Value>>,
+    }
+
+    impl StepExecutionContext for RecordingContext {
+        fn execute_agent(
+            &self,
+            bot: &str,
+            prompt: &str,
+            model: Option<&str>,
+        ) -> std::pin::Pin<
+            Box<dyn std::future::Future<Output = Result<Value, StepError>> + Send + '_>,
+        > {
+            let (bot, prompt, model) =
+                (bot.to_string(), prompt.to_string(), model.map(String::from));
+            Box::pin(async move {
+                PlaceholderExecutionContext
+                    .execute_agent(&bot, &prompt, model.as_deref())
+                    .await
+            })
+        }
+
+        fn execute_skill(
+            &self,
+            skill: &str,
+            input: Option<&str>,
+        ) -> std::pin::Pin<
+            Box<dyn std::future::Future<Output = Result<Value, StepError>> + Send + '_>,
+        > {
+            let (skill, input) = (skill.to_string(), input.map(String::from));
+            Box::pin(async move {
+                PlaceholderExecutionContext
+                    .execute_skill(&skill, input.as_deref())
+                    .await
+            })
+        }
+
+        fn execute_http(
+            &self,
+            _method: &str,
+            url: &str,
+            headers: Option<&std::collections::HashMap<String, String>>,
+            body: Option<&str>,
+        ) -> std::pin::Pin<
+            Box<dyn std::future::Future<Output = Result<Value, StepError>> + Send + '_>,
+        > {
+            let request = json!({ "url": url, "headers": headers, "body": body });
+            self.requests.lock().unwrap().push(request.clone());
+            let failed = url.contains("fail");
+            let url = url.to_string();
+            Box::pin(async move {
+                if failed {
+                    Err(StepError::ExecutionFailed(format!(
+                        "request to {url} failed"
+                    )))
+                } else {
+                    Ok(request)
+                }
+            })
+        }
+    }
+
+    fn recording_runner() -> (StepRunner, Arc<RecordingContext>) {
+        let recorder = Arc::new(RecordingContext::default());
+        let runner = StepRunner::with_context(
+            PathBuf::from("/tmp"),
+            Arc::clone(&recorder) as Arc<dyn StepExecutionContext>,
+        )
+        .with_secrets(secret_service());
+        (runner, recorder)
+    }
+
+    fn http_step(
+        url: &str,
+        headers: Option<&[(&str, &str)]>,
+        body: Option<&str>,
+    ) -> StepDefinition {
+        make_step(StepConfig::Http {
+            method: "POST".to_string(),
+            url: url.to_string(),
+            headers: headers.map(|h| {
+                h.iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect()
+            }),
+            body: body.map(String::from),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_http_step_injects_secret_and_redacts_output() {
+        let (runner, recorder) = recording_runner();
+        let step = http_step(
+            "https://hooks.example.com/{{ secrets.HOOK_ID }}",
+            Some(&[("Authorization", "Bearer {{ secrets.SLACK_TOKEN }}")]),
+            Some("text={{ steps.gather.output }}"),
+        );
+
+        let output = runner.run(&step, &test_context()).await.unwrap().to_value();
+
+        // The request carries the real values
+        let sent = recorder.requests.lock().unwrap()[0].clone();
+        assert_eq!(sent["url"], "https://hooks.example.com/h-123");
+        assert_eq!(sent["headers"]["Authorization"], "Bearer xoxb-secret");
+        assert_eq!(sent["body"], "text=gathered data");
+
+        // The output does not
+        assert_eq!(output["url"], "https://hooks.example.com/[REDACTED]");
+        assert_eq!(output["headers"]["Authorization"], "Bearer [REDACTED]");
+        assert_eq!(output["body"], "text=gathered data");
+        let serialized = output.to_string();
+        assert!(!serialized.contains("xoxb-secret"));
+        assert!(!serialized.contains("h-123"));
+    }
+
+    #[tokio::test]
+    async fn test_secret_reference_from_context_is_not_resolved() {
+        let (runner, recorder) = recording_runner();
+        let ctx = WorkflowContext::new(
+            "test-workflow".to_string(),
+            Uuid::now_v7(),
+            Some(json!({ "source": "{{ secrets.SLACK_TOKEN }}" })),
+        );
+        let step = http_step("https://example.com/?q={{ trigger.source }}", None, None);
+
+        runner.run(&step, &ctx).await.unwrap();
+        let sent = recorder.requests.lock().unwrap()[0].clone();
+        assert_eq!(
+            sent["url"],
+            "https://example.com/?q={{ secrets.SLACK_TOKEN }}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_http_step_error_redacts_secret() {
+        let (runner, _recorder) = recording_runner();
+        let step = http_step(
+            "https://fail.example.com/{{ secrets.SLACK_TOKEN }}",
+            None,
+            None,
+        );
+
+        let err = runner.run(&step, &test_context()).await.unwrap_err();
+        let msg = err.to_string();
+        assert!(msg.contains("https://fail.example.com/[REDACTED]"), "{msg}");
+        assert!(!msg.contains("xoxb-secret"));
+    }
+
+    #[tokio::test]
+    async fn test_missing_secret_fails_step() {
+        let (runner, recorder) = recording_runner();
+        let step = http_step("https://example.com/{{ secrets.UNKNOWN }}", None, None);
+        let err = runner.run(&step, &test_context()).await.unwrap_err();
+        assert!(err.to_string().contains("secret 'UNKNOWN' not found"));
+        assert!(recorder.requests.lock().unwrap().is_empty());
+
+        // No secret service attached
+        let runner = StepRunner::new(PathBuf::from("/tmp"));
+        let step = http_step("https://example.com/{{ secrets.SLACK_TOKEN }}", None, None);
+        let err = runner.run(&step, &test_context()).await.unwrap_err();
+        assert!(err.to_string().contains("no secret provider"));
+    }
 }
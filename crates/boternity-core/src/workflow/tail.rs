@@ -0,0 +1,257 @@
+//! Live tail of a workflow run from its persisted step logs.
+//!
+//! The executor checkpoints every step transition, so another process (e.g.
+//! `bnity workflow tail`) can follow a run by polling the repository.
+//! `RunTail` diffs successive snapshots into ordered `TailEvent`s and stops
+//! once the run reaches a terminal status.
+
+use std::collections::HashMap;
+
+use boternity_types::error::RepositoryError;
+use boternity_types::workflow::{WorkflowRunStatus, WorkflowStepLog, WorkflowStepStatus};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::repository::workflow::WorkflowRepository;
+
+// ---------------------------------------------------------------------------
+// TailEvent
+// ---------------------------------------------------------------------------
+
+/// A change observed in a followed run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum TailEvent {
+    /// A step began executing.
+    StepStarted {
+        step_id: String,
+        step_name: String,
+        attempt: u32,
+    },
+    /// A step finished successfully.
+    StepCompleted {
+        step_id: String,
+        step_name: String,
+        /// Wall time from start to completion, when both are recorded.
+        duration_ms: Option<u64>,
+    },
+    /// A step failed (or was interrupted).
+    StepFailed {
+        step_id: String,
+        step_name: String,
+        error: Option<String>,
+    },
+    /// A step was skipped without running.
+    StepSkipped { step_id: String, step_name: String },
+    /// A step is paused waiting for human approval.
+    StepWaitingApproval { step_id: String, step_name: String },
+    /// The run moved to a new status.
+    RunStatus {
+        status: WorkflowRunStatus,
+        error: Option<String>,
+    },
+}
+
+// ---------------------------------------------------------------------------
+// RunTail
+// ---------------------------------------------------------------------------
+
+/// Follows one workflow run by polling its run record and step logs.
+pub struct RunTail<'a, R: WorkflowRepository> {
+    repo: &'a R,
+    run_id: Uuid,
+    /// Last status reported per step log entry.
+    seen_steps: HashMap<Uuid, WorkflowStepStatus>,
+    run_status: Option<WorkflowRunStatus>,
+    finished: bool,
+}
+
+impl<'a, R: WorkflowRepository> RunTail<'a, R> {
+    /// Start following `run_id`. Nothing is read until the first `poll`.
+    pub fn new(repo: &'a R, run_id: Uuid) -> Self {
+        Self {
+            repo,
+            run_id,
+            seen_steps: HashMap::new(),
+            run_status: None,
+            finished: false,
+        }
+    }
+
+    /// Whether the run has reached a terminal status.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Read the current state and return what changed since the last poll.
+    ///
+    /// Step events come in step-log order (by start time), followed by a
+    /// `RunStatus` event if the run status changed. Returns
+    /// `RepositoryError::NotFound` if the run does not exist.
+    pub async fn poll(&mut self) -> Result<Vec<TailEvent>, RepositoryError> {
+        // Read the run before its steps: once the run is terminal, every
+        // step log read afterwards is final too.
+        let run = self
+            .repo
+            .get_run(&self.run_id)
+            .await?
+            .ok_or(RepositoryError::NotFound)?;
+        let logs = self.repo.list_step_logs(&self.run_id).await?;
+
+        let mut events = Vec::new();
+        for log in &logs {
+            let previous = self.seen_steps.insert(log.id, log.status);
+            if previous != Some(log.status) {
+                events.extend(step_events(previous, log));
+            }
+        }
+
+        if self.run_status != Some(run.status) {
+            self.run_status = Some(run.status);
+            events.push(TailEvent::RunStatus {
+                status: run.status,
+                error: run.error,
+            });
+        }
+        self.finished = is_terminal(run.status);
+
+        Ok(events)
+    }
+}
+
+/// Events for a step log that moved from `previous` to its current status.
+///
+/// A step first seen already finished also gets its `StepStarted`, so every
+/// step that ran reads as start then outcome.
+fn step_events(previous: Option<WorkflowStepStatus>, log: &WorkflowStepLog) -> Vec<TailEvent> {
+    let started = || TailEvent::StepStarted {
+        step_id: log.step_id.clone(),
+        step_name: log.step_name.clone(),
+        attempt: log.attempt,
+    };
+
+    let mut events = Vec::new();
+    let first_seen = previous.is_none_or(|s| s == WorkflowStepStatus::Pending);
+    match log.status {
+        WorkflowStepStatus::Pending => {}
+        WorkflowStepStatus::Running => events.push(started()),
+        WorkflowStepStatus::Completed => {
+            if first_seen {
+                events.push(started());
+            }
+            let duration_ms = log
+                .started_at
+                .zip(log.completed_at)
+                .map(|(start, end)| (end - start).num_milliseconds().max(0) as u64);
+            events.push(TailEvent::StepCompleted {
+                step_id: log.step_id.clone(),
+                step_name: log.step_name.clone(),
+                duration_ms,
+            });
+        }
+        WorkflowStepStatus::Failed => {
+            if first_seen {
+                events.push(started());
+            }
+            events.push(TailEvent::StepFailed {
+                step_id: log.step_id.clone(),
+                step_name: log.step_name.clone(),
+                error: log.error.clone(),
+            });
+        }
+        WorkflowStepStatus::Skipped => events.push(TailEvent::StepSkipped {
+            step_id: log.step_id.clone(),
+            step_name: log.step_name.clone(),
+        }),
+        WorkflowStepStatus::WaitingApproval => {
+            if first_seen {
+                events.push(started());
+            }
+            events.push(TailEvent::StepWaitingApproval {
+                step_id: log.step_id.clone(),
+                step_name: log.step_name.clone(),
+            });
+        }
+    }
+    events
+}
+
+/// Whether a run in `status` will not change again on its own.
+fn is_terminal(status: WorkflowRunStatus) -> bool {
+    matches!(
+        status,
+        WorkflowRunStatus::Completed
+            | WorkflowRunStatus::Failed
+            | WorkflowRunStatus::Crashed
+            | WorkflowRunStatus::Cancelled
+    )
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+
+    fn log(status: WorkflowStepStatus) -> WorkflowStepLog {
+        let started_at = Utc::now();
+        WorkflowStepLog {
+            id: Uuid::now_v7(),
+            run_id: Uuid::nil(),
+            step_id: "fetch".to_string(),
+            step_name: "Fetch".to_string(),
+            status,
+            attempt: 1,
+            idempotency_key: None,
+            input: None,
+            output: None,
+            error: None,
+            started_at: Some(started_at),
+            completed_at: Some(started_at + Duration::milliseconds(250)),
+        }
+    }
+
+    #[test]
+    fn test_step_first_seen_completed_reports_start_and_completion() {
+        let events = step_events(None, &log(WorkflowStepStatus::Completed));
+        assert_eq!(
+            events,
+            vec![
+                TailEvent::StepStarted {
+                    step_id: "fetch".to_string(),
+                    step_name: "Fetch".to_string(),
+                    attempt: 1,
+                },
+                TailEvent::StepCompleted {
+                    step_id: "fetch".to_string(),
+                    step_name: "Fetch".to_string(),
+                    duration_ms: Some(250),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_step_transition_from_running_reports_outcome_only() {
+        let mut failed = log(WorkflowStepStatus::Failed);
+        failed.error = Some("boom".to_string());
+        let events = step_events(Some(WorkflowStepStatus::Running), &failed);
+        assert_eq!(
+            events,
+            vec![TailEvent::StepFailed {
+                step_id: "fetch".to_string(),
+                step_name: "Fetch".to_string(),
+                error: Some("boom".to_string()),
+            }]
+        );
+
+        let skipped = step_events(None, &log(WorkflowStepStatus::Skipped));
+        assert!(matches!(
+            skipped.as_slice(),
+            [TailEvent::StepSkipped { .. }]
+        ));
+    }
+}
@@ -334,7 +334,11 @@ impl TriggerManager {
                 })?;
                 Ok(())
             }
-            TriggerConfig::Webhook { path, .. } => {
+            TriggerConfig::Webhook {
+                path,
+                dedup_window_secs,
+                ..
+            } => {
                 if path.is_empty() {
                     return Err(TriggerError::RegistrationFailed(
                         "webhook path must not be empty".to_string(),
@@ -345,6 +349,11 @@ impl TriggerManager {
                         "webhook path must start with '/': '{path}'"
                     )));
                 }
+                if *dedup_window_secs == Some(0) {
+                    return Err(TriggerError::RegistrationFailed(
+                        "webhook dedup_window_secs must be > 0".to_string(),
+                    ));
+                }
                 Ok(())
             }
             TriggerConfig::Event {
@@ -482,6 +491,7 @@ mod tests {
             path: "".to_string(),
             auth: None,
             when: None,
+            dedup_window_secs: None,
         }];
         assert!(
             mgr.register_workflow(wf_id, "test-wf", &triggers)
@@ -494,6 +504,20 @@ mod tests {
             path: "hook".to_string(),
             auth: None,
             when: None,
+            dedup_window_secs: None,
+        }];
+        assert!(
+            mgr.register_workflow(wf_id, "test-wf", &triggers)
+                .await
+                .is_err()
+        );
+
+        // Zero dedup window
+        let triggers = vec![TriggerConfig::Webhook {
+            path: "/hook".to_string(),
+            auth: None,
+            when: None,
+            dedup_window_secs: Some(0),
         }];
         assert!(
             mgr.register_workflow(wf_id, "test-wf", &triggers)
@@ -588,6 +612,7 @@ mod tests {
                 secret_name: "TOKEN".to_string(),
             }),
             when: Some("event.type == 'push'".to_string()),
+            dedup_window_secs: None,
         }];
 
         mgr.register_workflow(wf_id, "test-wf", &triggers)
@@ -771,6 +796,7 @@ mod tests {
                 path: "/trigger/two".to_string(),
                 auth: None,
                 when: None,
+                dedup_window_secs: None,
             }],
         )
         .await
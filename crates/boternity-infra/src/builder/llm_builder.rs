@@ -12,7 +12,7 @@ use uuid::Uuid;
 
 use boternity_core::builder::agent::{BuilderAgent, BuilderError};
 use boternity_core::builder::defaults::classify_purpose;
-use boternity_core::builder::memory::{BuilderMemoryEntry, BuilderMemoryStore};
+use boternity_core::builder::memory::{BuilderMemoryEntry, BuilderMemoryStore, recall_relevant};
 use boternity_core::builder::prompt::{
     BuilderMode, RecalledBuilderMemory, build_forge_system_prompt,
 };
@@ -22,7 +22,7 @@ use boternity_types::builder::{
     BuilderAnswer, BuilderConfig, BuilderState, BuilderTurn, add_additional_properties_false,
 };
 use boternity_types::llm::{
-    CompletionRequest, Message, MessageRole, OutputConfig, OutputFormat, OutputJsonSchema,
+    CompletionRequest, Message, OutputConfig, OutputFormat, OutputJsonSchema,
 };
 
 // ---------------------------------------------------------------------------
@@ -89,19 +89,14 @@ impl<M: BuilderMemoryStore> LlmBuilderAgent<M> {
         system_prompt: String,
         user_message: String,
     ) -> Result<BuilderTurn, BuilderError> {
-        let request = CompletionRequest {
-            model: self.model.clone(),
-            messages: vec![Message {
-                role: MessageRole::User,
-                content: user_message,
-            }],
-            system: Some(system_prompt),
-            max_tokens: 2048,
-            temperature: Some(0.7),
-            stream: false,
-            stop_sequences: None,
-            output_config: Some(Self::output_config()),
-        };
+        let request = CompletionRequest::builder(self.model.clone())
+            .system(system_prompt)
+            .message(Message::from(user_message))
+            .max_tokens(2048)
+            .temperature(0.7)
+            .output_config(Self::output_config())
+            .build()
+            .map_err(|e| BuilderError::LlmError(e.to_string()))?;
 
         let response = self
             .provider
@@ -119,15 +114,15 @@ impl<M: BuilderMemoryStore> LlmBuilderAgent<M> {
 
     /// Query builder memory for past sessions matching the given purpose category.
     ///
-    /// Returns up to 3 recalled sessions converted to `RecalledBuilderMemory`.
+    /// Returns up to 5 recalled sessions (same category first, then recent
+    /// ones) converted to `RecalledBuilderMemory`.
     /// Returns an empty vec if memory_store is None or the query fails.
     async fn recall_memories(
         &self,
         category: &boternity_types::builder::PurposeCategory,
     ) -> Vec<RecalledBuilderMemory> {
         match &self.memory_store {
-            Some(store) => store
-                .recall_by_category(category, 3)
+            Some(store) => recall_relevant(store, category, 5)
                 .await
                 .unwrap_or_default()
                 .into_iter()
@@ -236,7 +231,12 @@ impl<M: BuilderMemoryStore + 'static> BuilderAgent for LlmBuilderAgent<M> {
         // Record the exchange (the question was from the previous turn)
         // We record the answer with a placeholder question since the actual
         // question text was in the previous BuilderTurn response
-        state.record_exchange("(previous question)".to_string(), answer_text.clone());
+        let question = match &state.pending_turn {
+            Some(BuilderTurn::AskQuestion { question, .. }) => question.clone(),
+            Some(BuilderTurn::Clarify { message }) => message.clone(),
+            _ => "(previous question)".to_string(),
+        };
+        state.record_exchange(question, answer_text.clone());
 
         // Rebuild Forge system prompt with updated state
         let recalled = self.recall_memories(
@@ -661,4 +661,105 @@ mod tests {
             "Go back to the previous step."
         );
     }
+
+    /// Records the system prompt of every request and returns a fixed turn.
+    struct CapturingProvider {
+        response_content: String,
+        system_prompts: std::sync::Arc<std::sync::Mutex<Vec<String>>>,
+    }
+
+    impl LlmProvider for CapturingProvider {
+        fn name(&self) -> &str {
+            "capturing"
+        }
+
+        fn capabilities(&self) -> &ProviderCapabilities {
+            &ProviderCapabilities {
+                streaming: false,
+                tool_calling: false,
+                vision: false,
+                extended_thinking: false,
+                max_context_tokens: 200_000,
+                max_output_tokens: 4096,
+            }
+        }
+
+        async fn complete(
+            &self,
+            request: &CompletionRequest,
+        ) -> Result<CompletionResponse, LlmError> {
+            self.system_prompts
+                .lock()
+                .unwrap()
+                .push(request.system.clone().unwrap_or_default());
+            Ok(CompletionResponse {
+                id: "msg_mock_456".to_string(),
+                content: self.response_content.clone(),
+                model: "mock-model".to_string(),
+                stop_reason: StopReason::EndTurn,
+                usage: Usage::default(),
+            })
+        }
+
+        fn stream(
+            &self,
+            _request: CompletionRequest,
+        ) -> Pin<Box<dyn Stream<Item = Result<StreamEvent, LlmError>> + Send + 'static>> {
+            Box::pin(futures_util::stream::empty())
+        }
+
+        async fn count_tokens(
+            &self,
+            _request: &CompletionRequest,
+        ) -> Result<TokenCount, LlmError> {
+            Ok(TokenCount { input_tokens: 100 })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_start_surfaces_past_decisions_in_forge_prompt() {
+        use crate::builder::sqlite_memory_store::SqliteBuilderMemoryStore;
+        use crate::sqlite::pool::DatabasePool;
+
+        let dir = tempfile::tempdir().unwrap();
+        let url = format!("sqlite://{}?mode=rwc", dir.path().join("test.db").display());
+        let store = SqliteBuilderMemoryStore::new(DatabasePool::new(&url).await.unwrap());
+
+        // A past build of a different kind of bot
+        store
+            .record_session(BuilderMemoryEntry {
+                id: Uuid::now_v7(),
+                purpose_category: serde_json::to_string(
+                    &boternity_types::builder::PurposeCategory::Creative,
+                )
+                .unwrap(),
+                initial_description: "A poetry companion".to_string(),
+                chosen_tone: Some("whimsical".to_string()),
+                chosen_model: Some("claude-haiku-4-5".to_string()),
+                chosen_skills: vec![],
+                bot_slug: Some("quill-poet".to_string()),
+                created_at: chrono::Utc::now(),
+            })
+            .await
+            .unwrap();
+
+        let system_prompts = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let provider = BoxLlmProvider::new(CapturingProvider {
+            response_content: mock_ask_question_json(),
+            system_prompts: system_prompts.clone(),
+        });
+        let agent = LlmBuilderAgent::new(provider, Some(store), "mock-model".to_string());
+
+        agent
+            .start(Uuid::now_v7(), "I want a coding assistant")
+            .await
+            .unwrap();
+
+        let prompts = system_prompts.lock().unwrap();
+        let prompt = &prompts[0];
+        assert!(prompt.contains("<past_sessions>"));
+        assert!(prompt.contains("quill-poet"));
+        assert!(prompt.contains("Preferred model: claude-haiku-4-5"));
+        assert!(prompt.contains("Existing bot names: quill-poet"));
+    }
 }
@@ -1,8 +1,9 @@
 //! SQLite implementation of `BuilderDraftStore`.
 //!
-//! Persists builder drafts in the `builder_drafts` table using INSERT OR REPLACE
-//! for upsert semantics. Extracts `initial_description` and `phase` from the
-//! serialized `state_json` for lightweight listing without full deserialization.
+//! Persists builder drafts in the `builder_drafts` table, upserting on
+//! `session_id` (the original `created_at` is kept). Extracts
+//! `initial_description`, `phase`, and the question count from the serialized
+//! `state_json` for lightweight listing without full deserialization.
 
 use boternity_core::builder::draft_store::{BuilderDraft, BuilderDraftSummary, BuilderDraftStore};
 use boternity_types::error::RepositoryError;
@@ -38,19 +39,17 @@ fn format_datetime(dt: &DateTime<Utc>) -> String {
     dt.to_rfc3339()
 }
 
-/// Extract a string field from a JSON string without full deserialization.
+/// Extract a string field from parsed state JSON.
 ///
 /// This is a lightweight extraction for listing purposes -- avoids
 /// deserializing the entire `BuilderState` just to get the description
 /// or phase.
-fn extract_json_field(json: &str, field: &str) -> String {
-    // Use serde_json::Value for correctness (handles escaping, nesting, etc.)
-    if let Ok(value) = serde_json::from_str::<serde_json::Value>(json) {
-        if let Some(s) = value.get(field).and_then(|v| v.as_str()) {
-            return s.to_string();
-        }
-    }
-    String::new()
+fn extract_json_field(value: &serde_json::Value, field: &str) -> String {
+    value
+        .get(field)
+        .and_then(|v| v.as_str())
+        .unwrap_or_default()
+        .to_string()
 }
 
 // ---------------------------------------------------------------------------
@@ -60,8 +59,12 @@ fn extract_json_field(json: &str, field: &str) -> String {
 impl BuilderDraftStore for SqliteBuilderDraftStore {
     async fn save_draft(&self, draft: BuilderDraft) -> Result<(), RepositoryError> {
         sqlx::query(
-            r#"INSERT OR REPLACE INTO builder_drafts (session_id, state_json, schema_version, created_at, updated_at)
-               VALUES (?, ?, ?, ?, ?)"#,
+            r#"INSERT INTO builder_drafts (session_id, state_json, schema_version, created_at, updated_at)
+               VALUES (?, ?, ?, ?, ?)
+               ON CONFLICT(session_id) DO UPDATE SET
+                   state_json = excluded.state_json,
+                   schema_version = excluded.schema_version,
+                   updated_at = excluded.updated_at"#,
         )
         .bind(draft.session_id.to_string())
         .bind(&draft.state_json)
@@ -120,7 +123,7 @@ impl BuilderDraftStore for SqliteBuilderDraftStore {
 
     async fn list_drafts(&self) -> Result<Vec<BuilderDraftSummary>, RepositoryError> {
         let rows = sqlx::query(
-            "SELECT session_id, state_json, updated_at FROM builder_drafts ORDER BY updated_at DESC",
+            "SELECT session_id, state_json, created_at, updated_at FROM builder_drafts ORDER BY updated_at DESC",
         )
         .fetch_all(&self.pool.reader)
         .await
@@ -134,15 +137,27 @@ impl BuilderDraftStore for SqliteBuilderDraftStore {
             let state_json: String = row
                 .try_get("state_json")
                 .map_err(|e| RepositoryError::Query(e.to_string()))?;
+            let created_at_str: String = row
+                .try_get("created_at")
+                .map_err(|e| RepositoryError::Query(e.to_string()))?;
             let updated_at_str: String = row
                 .try_get("updated_at")
                 .map_err(|e| RepositoryError::Query(e.to_string()))?;
 
+            // Use serde_json::Value for correctness (handles escaping, nesting, etc.)
+            let state: serde_json::Value =
+                serde_json::from_str(&state_json).unwrap_or_default();
+
             summaries.push(BuilderDraftSummary {
                 session_id: Uuid::parse_str(&sid)
                     .map_err(|e| RepositoryError::Query(format!("invalid session_id: {e}")))?,
-                initial_description: extract_json_field(&state_json, "initial_description"),
-                phase: extract_json_field(&state_json, "phase"),
+                initial_description: extract_json_field(&state, "initial_description"),
+                phase: extract_json_field(&state, "phase"),
+                question_count: state
+                    .get("conversation")
+                    .and_then(|c| c.as_array())
+                    .map_or(0, Vec::len),
+                created_at: parse_datetime(&created_at_str)?,
                 updated_at: parse_datetime(&updated_at_str)?,
             });
         }
@@ -307,4 +322,114 @@ mod tests {
         let loaded = store.load_draft(&id).await.unwrap().unwrap();
         assert_eq!(loaded.schema_version, 2);
     }
+
+    #[tokio::test]
+    async fn test_v1_draft_migrates_and_future_version_is_rejected() {
+        use boternity_core::builder::draft_store::DRAFT_SCHEMA_VERSION;
+        use boternity_types::builder::BuilderPhase;
+        use boternity_types::schema::SchemaError;
+
+        let pool = test_pool().await;
+        let store = SqliteBuilderDraftStore::new(pool);
+
+        // Written by a release that had neither `pending_turn` nor versioning
+        let id = Uuid::now_v7();
+        store
+            .save_draft(make_draft(id, "A gardening bot", "personality"))
+            .await
+            .unwrap();
+        let state = store.load_draft(&id).await.unwrap().unwrap().state().unwrap();
+        assert_eq!(state.initial_description, "A gardening bot");
+        assert_eq!(state.phase, BuilderPhase::Personality);
+        assert!(state.pending_turn.is_none());
+        assert_eq!(state.schema_version, DRAFT_SCHEMA_VERSION);
+
+        let mut future = make_draft(id, "A gardening bot", "personality");
+        future.schema_version = DRAFT_SCHEMA_VERSION + 1;
+        let err = future.state().unwrap_err();
+        assert!(matches!(
+            err,
+            SchemaError::UnsupportedVersion { found, .. } if found == DRAFT_SCHEMA_VERSION + 1
+        ));
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "builder state has schema version {}, but this build only understands up to {}; upgrade boternity to load it",
+                DRAFT_SCHEMA_VERSION + 1,
+                DRAFT_SCHEMA_VERSION
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resume_specific_draft_with_state_intact() {
+        use boternity_core::builder::state::{new_builder_state, BuilderStateExt};
+        use boternity_types::builder::{BuilderPhase, BuilderTurn, PurposeCategory};
+
+        let pool = test_pool().await;
+        let store = SqliteBuilderDraftStore::new(pool);
+
+        let mut states = Vec::new();
+        for (i, description) in ["A coding bot", "A cooking bot", "A travel bot"].iter().enumerate() {
+            let mut state = new_builder_state(Uuid::now_v7(), description.to_string());
+            state.purpose_category = Some(PurposeCategory::Custom(format!("custom-{i}")));
+            state.record_exchange("What should we call it?".to_string(), format!("Bot{i}"));
+            state.update_config_field("name", serde_json::json!(format!("Bot{i}")));
+            state.advance_phase(BuilderPhase::Personality);
+            state.pending_turn = Some(BuilderTurn::Clarify {
+                message: format!("Which tone suits bot {i}?"),
+            });
+            store
+                .save_draft(BuilderDraft::from_state(&state).unwrap())
+                .await
+                .unwrap();
+            states.push(state);
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+
+        let summaries = store.list_drafts().await.unwrap();
+        assert_eq!(summaries.len(), 3);
+        assert_eq!(summaries[0].initial_description, "A travel bot");
+        assert!(summaries.iter().all(|s| s.phase == "personality" && s.question_count == 1));
+
+        // Resume the middle (neither newest nor oldest) draft
+        let wanted = &states[1];
+        let draft = store.load_draft(&wanted.session_id).await.unwrap().unwrap();
+        let restored = draft.state().unwrap();
+        assert_eq!(restored.session_id, wanted.session_id);
+        assert_eq!(restored.initial_description, "A cooking bot");
+        assert_eq!(restored.phase, BuilderPhase::Personality);
+        assert_eq!(restored.phase_history, vec![BuilderPhase::Basics]);
+        assert_eq!(restored.conversation[0].answer, "Bot1");
+        assert_eq!(restored.config.name.as_deref(), Some("Bot1"));
+        assert!(matches!(
+            restored.purpose_category,
+            Some(PurposeCategory::Custom(ref c)) if c == "custom-1"
+        ));
+        assert!(matches!(
+            restored.pending_turn,
+            Some(BuilderTurn::Clarify { ref message }) if message == "Which tone suits bot 1?"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_save_keeps_created_at() {
+        let pool = test_pool().await;
+        let store = SqliteBuilderDraftStore::new(pool);
+
+        let id = Uuid::now_v7();
+        let first = make_draft(id, "A coding bot", "basics");
+        store.save_draft(first.clone()).await.unwrap();
+
+        let mut second = make_draft(id, "A coding bot", "model");
+        second.created_at = first.created_at + chrono::Duration::hours(1);
+        store.save_draft(second).await.unwrap();
+
+        let summaries = store.list_drafts().await.unwrap();
+        assert_eq!(summaries[0].phase, "model");
+        assert_eq!(
+            summaries[0].created_at.timestamp_millis(),
+            first.created_at.timestamp_millis()
+        );
+    }
 }
@@ -2,7 +2,8 @@
 //!
 //! Reads `config.toml` from the data directory (`~/.boternity/` in production)
 //! and deserializes it into [`GlobalConfig`]. Falls back to sensible defaults
-//! when the file is missing or malformed.
+//! when the file is missing or malformed. Entries from the updatable pricing
+//! table (`pricing.json`) are appended after the `config.toml` overrides.
 
 use std::path::Path;
 
@@ -16,7 +17,19 @@ const MIN_REQUEST_BUDGET: u32 = 10_000;
 /// - If the file does not exist, returns [`GlobalConfig::default()`] (500,000 token budget).
 /// - If the file exists but fails to parse, logs a warning and returns the default.
 /// - If the file exists and parses successfully, returns the parsed config.
+///
+/// In every case, entries from `{data_dir}/pricing.json` are appended to
+/// `provider_pricing`, so `config.toml` overrides still match first.
 pub async fn load_global_config(data_dir: &Path) -> GlobalConfig {
+    let mut config = load_config_toml(data_dir).await;
+    config
+        .provider_pricing
+        .extend(crate::llm::pricing::load_pricing_file(data_dir).await);
+    config
+}
+
+/// Read and parse `{data_dir}/config.toml`, falling back to the default.
+async fn load_config_toml(data_dir: &Path) -> GlobalConfig {
     let config_path = data_dir.join("config.toml");
 
     let content = match tokio::fs::read_to_string(&config_path).await {
@@ -55,6 +68,35 @@ pub fn resolve_request_budget(global_config: &GlobalConfig, identity_override: O
     budget.max(MIN_REQUEST_BUDGET)
 }
 
+/// A per-bot request budget that the primary provider cannot honor.
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+#[error(
+    "max_request_tokens {requested} exceeds the provider's context window of {max_context_tokens} tokens"
+)]
+pub struct RequestBudgetError {
+    pub requested: u32,
+    pub max_context_tokens: u32,
+}
+
+/// Validate a per-bot `max_request_tokens` override against the primary
+/// provider's context window.
+///
+/// A bot uses the override to cap its own orchestrator spend; a cap larger
+/// than the context window is almost certainly a typo. Callers fall back to
+/// the context window size when this fails.
+pub fn validate_request_budget_override(
+    requested: u32,
+    max_context_tokens: u32,
+) -> Result<u32, RequestBudgetError> {
+    if requested > max_context_tokens {
+        return Err(RequestBudgetError {
+            requested,
+            max_context_tokens,
+        });
+    }
+    Ok(requested)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -93,6 +135,38 @@ output_cost_per_million = 15.0
         assert_eq!(config.provider_pricing[0].provider_name, "anthropic");
     }
 
+    #[tokio::test]
+    async fn load_global_config_appends_pricing_file_after_overrides() {
+        let tmp = TempDir::new().unwrap();
+        tokio::fs::write(
+            tmp.path().join("config.toml"),
+            r#"
+[[provider_pricing]]
+provider_name = "anthropic"
+model_pattern = "claude-sonnet-4"
+input_cost_per_million = 1.0
+output_cost_per_million = 5.0
+"#,
+        )
+        .await
+        .unwrap();
+        tokio::fs::write(
+            tmp.path().join("pricing.json"),
+            r#"[
+                { "provider_name": "anthropic", "model_pattern": "claude-sonnet-4", "input_cost_per_million": 2.0, "output_cost_per_million": 10.0 },
+                { "provider_name": "openai", "model_pattern": "gpt-5", "input_cost_per_million": 1.25, "output_cost_per_million": 10.0 }
+            ]"#,
+        )
+        .await
+        .unwrap();
+
+        let config = load_global_config(tmp.path()).await;
+        assert_eq!(config.provider_pricing.len(), 3);
+        // The config.toml override still wins for the model both define.
+        assert_eq!(config.provider_pricing[0].input_cost_per_million, 1.0);
+        assert_eq!(config.provider_pricing[2].model_pattern, "gpt-5");
+    }
+
     #[tokio::test]
     async fn load_global_config_invalid_toml_returns_default() {
         let tmp = TempDir::new().unwrap();
@@ -111,6 +185,7 @@ output_cost_per_million = 15.0
         let global = GlobalConfig {
             default_request_budget: 500_000,
             provider_pricing: Vec::new(),
+            ..GlobalConfig::default()
         };
         let budget = resolve_request_budget(&global, Some(200_000));
         assert_eq!(budget, 200_000);
@@ -121,16 +196,43 @@ output_cost_per_million = 15.0
         let global = GlobalConfig {
             default_request_budget: 750_000,
             provider_pricing: Vec::new(),
+            ..GlobalConfig::default()
         };
         let budget = resolve_request_budget(&global, None);
         assert_eq!(budget, 750_000);
     }
 
+    #[test]
+    fn resolve_request_budget_from_identity_frontmatter() {
+        use crate::filesystem::identity::parse_identity_frontmatter;
+
+        let global = GlobalConfig::default();
+        let with_field = parse_identity_frontmatter(
+            "---\ndisplay_name: Frugal\nmax_request_tokens: 120000\n---\nBody",
+        )
+        .unwrap();
+        let without_field =
+            parse_identity_frontmatter("---\ndisplay_name: Default\n---\nBody").unwrap();
+
+        assert_eq!(resolve_request_budget(&global, with_field.max_request_tokens), 120_000);
+        assert_eq!(resolve_request_budget(&global, without_field.max_request_tokens), 500_000);
+    }
+
+    #[test]
+    fn validate_request_budget_override_checks_context_window() {
+        assert_eq!(validate_request_budget_override(120_000, 200_000), Ok(120_000));
+        assert_eq!(validate_request_budget_override(200_000, 200_000), Ok(200_000));
+        let err = validate_request_budget_override(300_000, 200_000).unwrap_err();
+        assert_eq!(err.max_context_tokens, 200_000);
+        assert!(err.to_string().contains("exceeds the provider's context window"));
+    }
+
     #[test]
     fn resolve_request_budget_enforces_minimum() {
         let global = GlobalConfig {
             default_request_budget: 500,
             provider_pricing: Vec::new(),
+            ..GlobalConfig::default()
         };
         // Global below minimum
         assert_eq!(resolve_request_budget(&global, None), MIN_REQUEST_BUDGET);
@@ -1,35 +1,113 @@
-//! SHA-256 content hashing for SOUL.md integrity verification.
+//! Content hashing for soul and shared-memory integrity verification.
 //!
-//! Implements the `ContentHasher` trait from `boternity-core` using the
-//! `sha2` crate (RustCrypto ecosystem).
+//! Supports SHA-256 (`sha2`, RustCrypto ecosystem) and BLAKE3. Every hash is
+//! recorded together with its algorithm: SHA-256 hashes as bare lowercase
+//! hex (the format used before BLAKE3 was added), other algorithms as
+//! `<algorithm>:<hex>`. Verification always uses the recorded algorithm, so
+//! data hashed under either algorithm verifies after the configured
+//! algorithm changes.
+//!
+//! Implements the `ContentHasher` trait from `boternity-core`.
 
 use sha2::{Digest, Sha256};
 
 use boternity_core::service::hash::ContentHasher;
+use boternity_types::config::HashAlgorithm;
+
+/// Incremental hash producing a recorded (algorithm-tagged) hash string.
+pub enum IntegrityDigest {
+    Sha256(Sha256),
+    Blake3(Box<blake3::Hasher>),
+}
+
+impl IntegrityDigest {
+    /// Start a new hash with `algorithm`.
+    pub fn new(algorithm: HashAlgorithm) -> Self {
+        match algorithm {
+            HashAlgorithm::Sha256 => Self::Sha256(Sha256::new()),
+            HashAlgorithm::Blake3 => Self::Blake3(Box::new(blake3::Hasher::new())),
+        }
+    }
+
+    /// Feed more data into the hash.
+    pub fn update(&mut self, data: &[u8]) {
+        match self {
+            Self::Sha256(hasher) => hasher.update(data),
+            Self::Blake3(hasher) => {
+                hasher.update(data);
+            }
+        }
+    }
+
+    /// Finish the hash and return it in recorded form.
+    pub fn finalize(self) -> String {
+        match self {
+            Self::Sha256(hasher) => format!("{:x}", hasher.finalize()),
+            Self::Blake3(hasher) => format!(
+                "{}:{}",
+                HashAlgorithm::Blake3.as_str(),
+                hasher.finalize().to_hex()
+            ),
+        }
+    }
+}
 
-/// SHA-256 implementation of `ContentHasher`.
+/// Hash `data` with `algorithm`, in recorded form.
+pub fn hash_bytes(algorithm: HashAlgorithm, data: &[u8]) -> String {
+    let mut digest = IntegrityDigest::new(algorithm);
+    digest.update(data);
+    digest.finalize()
+}
+
+/// The algorithm a recorded hash was computed with, or `None` if its
+/// algorithm prefix is unknown.
+pub fn recorded_algorithm(recorded: &str) -> Option<HashAlgorithm> {
+    match recorded.split_once(':') {
+        None => Some(HashAlgorithm::Sha256),
+        Some((prefix, _)) if prefix == HashAlgorithm::Blake3.as_str() => {
+            Some(HashAlgorithm::Blake3)
+        }
+        Some(_) => None,
+    }
+}
+
+/// Content hasher using a configurable algorithm for new hashes.
 ///
-/// Computes lowercase hex-encoded SHA-256 digests of content strings.
-/// Used to verify SOUL.md integrity at bot startup.
-pub struct Sha256ContentHasher;
+/// Computes recorded hashes of content strings with the configured
+/// algorithm, and verifies existing hashes with whichever algorithm they
+/// record. Used to verify SOUL.md integrity at bot startup.
+pub struct IntegrityHasher {
+    algorithm: HashAlgorithm,
+}
+
+impl IntegrityHasher {
+    /// Create a hasher producing new hashes with `algorithm`.
+    pub fn new(algorithm: HashAlgorithm) -> Self {
+        Self { algorithm }
+    }
 
-impl Sha256ContentHasher {
-    /// Create a new hasher.
-    pub fn new() -> Self {
-        Self
+    /// Algorithm used for new hashes.
+    pub fn algorithm(&self) -> HashAlgorithm {
+        self.algorithm
     }
 }
 
-impl Default for Sha256ContentHasher {
+impl Default for IntegrityHasher {
     fn default() -> Self {
-        Self::new()
+        Self::new(HashAlgorithm::default())
     }
 }
 
-impl ContentHasher for Sha256ContentHasher {
+impl ContentHasher for IntegrityHasher {
     fn compute_hash(&self, content: &str) -> String {
-        let digest = Sha256::digest(content.as_bytes());
-        format!("{:x}", digest)
+        hash_bytes(self.algorithm, content.as_bytes())
+    }
+
+    fn compute_hash_as(&self, content: &str, recorded: &str) -> String {
+        // An unknown algorithm cannot be reproduced; the configured one yields
+        // a hash that simply fails to match.
+        let algorithm = recorded_algorithm(recorded).unwrap_or(self.algorithm);
+        hash_bytes(algorithm, content.as_bytes())
     }
 }
 
@@ -39,7 +117,7 @@ mod tests {
 
     #[test]
     fn test_sha256_hash_known_value() {
-        let hasher = Sha256ContentHasher::new();
+        let hasher = IntegrityHasher::default();
         // SHA-256 of empty string
         let hash = hasher.compute_hash("");
         assert_eq!(
@@ -48,9 +126,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_blake3_hash_known_value() {
+        let hasher = IntegrityHasher::new(HashAlgorithm::Blake3);
+        // BLAKE3 of empty string
+        let hash = hasher.compute_hash("");
+        assert_eq!(
+            hash,
+            "blake3:af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262"
+        );
+    }
+
     #[test]
     fn test_sha256_hash_deterministic() {
-        let hasher = Sha256ContentHasher::new();
+        let hasher = IntegrityHasher::default();
         let content = "# Luna\nCurious and warm.";
         let hash1 = hasher.compute_hash(content);
         let hash2 = hasher.compute_hash(content);
@@ -59,7 +148,7 @@ mod tests {
 
     #[test]
     fn test_sha256_hash_different_content() {
-        let hasher = Sha256ContentHasher::new();
+        let hasher = IntegrityHasher::default();
         let hash1 = hasher.compute_hash("content A");
         let hash2 = hasher.compute_hash("content B");
         assert_ne!(hash1, hash2);
@@ -67,10 +156,52 @@ mod tests {
 
     #[test]
     fn test_sha256_hash_is_lowercase_hex() {
-        let hasher = Sha256ContentHasher::new();
+        let hasher = IntegrityHasher::default();
         let hash = hasher.compute_hash("test");
         assert_eq!(hash.len(), 64); // SHA-256 = 32 bytes = 64 hex chars
         assert!(hash.chars().all(|c| c.is_ascii_hexdigit()));
         assert!(hash.chars().all(|c| !c.is_ascii_uppercase()));
     }
+
+    #[test]
+    fn test_round_trip_with_each_algorithm() {
+        for algorithm in [HashAlgorithm::Sha256, HashAlgorithm::Blake3] {
+            let hasher = IntegrityHasher::new(algorithm);
+            let hash = hasher.compute_hash("# Luna\nCurious and warm.");
+            assert_eq!(recorded_algorithm(&hash), Some(algorithm));
+            assert!(hasher.verify_hash("# Luna\nCurious and warm.", &hash));
+            assert!(!hasher.verify_hash("# Luna\nCold and distant.", &hash));
+        }
+    }
+
+    #[test]
+    fn test_verification_uses_recorded_algorithm() {
+        let content = "Shared fact";
+        let sha256 = IntegrityHasher::new(HashAlgorithm::Sha256);
+        let blake3 = IntegrityHasher::new(HashAlgorithm::Blake3);
+
+        // Each hasher verifies hashes made under the other algorithm
+        let old_hash = sha256.compute_hash(content);
+        let new_hash = blake3.compute_hash(content);
+        assert_ne!(old_hash, new_hash);
+        assert!(blake3.verify_hash(content, &old_hash));
+        assert!(sha256.verify_hash(content, &new_hash));
+        assert_eq!(blake3.compute_hash_as(content, &old_hash), old_hash);
+
+        // Incremental and one-shot hashing agree
+        let mut digest = IntegrityDigest::new(HashAlgorithm::Blake3);
+        digest.update(b"Shared ");
+        digest.update(b"fact");
+        assert_eq!(digest.finalize(), new_hash);
+    }
+
+    #[test]
+    fn test_unknown_algorithm_never_verifies() {
+        let hasher = IntegrityHasher::default();
+        assert_eq!(
+            recorded_algorithm("md5:d41d8cd98f00b204e9800998ecf8427e"),
+            None
+        );
+        assert!(!hasher.verify_hash("", "md5:d41d8cd98f00b204e9800998ecf8427e"));
+    }
 }
@@ -1,7 +1,9 @@
 //! Cryptographic operations for Boternity.
 //!
-//! - `hash`: SHA-256 content hashing for SOUL.md integrity
-//! - `vault`: AES-256-GCM encryption for secrets at rest
+//! - `hash`: SHA-256 or BLAKE3 content hashing for soul and shared-memory integrity
+//! - `signing`: HMAC-SHA256 signatures for export bundles
+//! - `vault`: AES-256-GCM encryption for secrets and memory facts at rest
 
 pub mod hash;
+pub mod signing;
 pub mod vault;
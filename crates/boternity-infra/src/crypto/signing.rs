@@ -0,0 +1,243 @@
+//! HMAC-SHA256 signatures for JSON export bundles.
+//!
+//! A signed bundle carries a `signature` object (`algorithm`, `value`) next
+//! to its data. The MAC covers a canonical serialization of everything else
+//! (object keys sorted, no whitespace), so re-formatting the file keeps the
+//! signature valid while any change to the data breaks it.
+
+use aes_gcm::aead::OsRng;
+use aes_gcm::aead::rand_core::RngCore;
+use hmac::{Hmac, Mac};
+use serde_json::Value;
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Vault secret (global scope) holding the export signing key.
+pub const EXPORT_SIGNING_KEY_SECRET: &str = "BOTERNITY_EXPORT_SIGNING_KEY";
+
+/// Bundle field holding the signature.
+pub const SIGNATURE_FIELD: &str = "signature";
+
+/// The only supported signature algorithm.
+pub const SIGNATURE_ALGORITHM: &str = "hmac-sha256";
+
+/// Why a bundle could not be signed or verified.
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum BundleSignatureError {
+    #[error("export bundle must be a JSON object")]
+    NotAnObject,
+
+    #[error("export bundle is not signed")]
+    Unsigned,
+
+    #[error("unsupported export signature algorithm '{0}'")]
+    UnsupportedAlgorithm(String),
+
+    #[error("export bundle signature is malformed")]
+    Malformed,
+
+    #[error(
+        "export bundle signature does not match its contents (modified after export, or signed with another key)"
+    )]
+    Mismatch,
+}
+
+/// Generate a new random signing key (32 bytes, hex-encoded).
+pub fn generate_signing_key() -> String {
+    let mut key = [0u8; 32];
+    OsRng.fill_bytes(&mut key);
+    hex_encode(&key)
+}
+
+/// Whether `bundle` carries a signature field.
+pub fn is_signed(bundle: &Value) -> bool {
+    bundle.get(SIGNATURE_FIELD).is_some()
+}
+
+/// Sign `bundle` in place, replacing any existing signature.
+pub fn sign_bundle(bundle: &mut Value, key: &[u8]) -> Result<(), BundleSignatureError> {
+    let mac = compute_mac(bundle, key)?;
+    let signature = serde_json::json!({
+        "algorithm": SIGNATURE_ALGORITHM,
+        "value": hex_encode(&mac.finalize().into_bytes()),
+    });
+    bundle
+        .as_object_mut()
+        .ok_or(BundleSignatureError::NotAnObject)?
+        .insert(SIGNATURE_FIELD.to_owned(), signature);
+    Ok(())
+}
+
+/// Verify the signature on `bundle` in constant time.
+pub fn verify_bundle(bundle: &Value, key: &[u8]) -> Result<(), BundleSignatureError> {
+    let signature = bundle
+        .get(SIGNATURE_FIELD)
+        .ok_or(BundleSignatureError::Unsigned)?;
+    let algorithm = signature
+        .get("algorithm")
+        .and_then(Value::as_str)
+        .ok_or(BundleSignatureError::Malformed)?;
+    if algorithm != SIGNATURE_ALGORITHM {
+        return Err(BundleSignatureError::UnsupportedAlgorithm(
+            algorithm.to_owned(),
+        ));
+    }
+    let expected = signature
+        .get("value")
+        .and_then(Value::as_str)
+        .and_then(hex_decode)
+        .ok_or(BundleSignatureError::Malformed)?;
+
+    compute_mac(bundle, key)?
+        .verify_slice(&expected)
+        .map_err(|_| BundleSignatureError::Mismatch)
+}
+
+/// HMAC over the canonical form of `bundle` without its signature.
+fn compute_mac(bundle: &Value, key: &[u8]) -> Result<HmacSha256, BundleSignatureError> {
+    let object = bundle
+        .as_object()
+        .ok_or(BundleSignatureError::NotAnObject)?;
+    let mut canonical = String::new();
+    canonical.push('{');
+    let mut keys: Vec<&String> = object.keys().filter(|k| *k != SIGNATURE_FIELD).collect();
+    keys.sort();
+    for (i, key) in keys.into_iter().enumerate() {
+        if i > 0 {
+            canonical.push(',');
+        }
+        write_canonical(&Value::String(key.clone()), &mut canonical);
+        canonical.push(':');
+        write_canonical(&object[key], &mut canonical);
+    }
+    canonical.push('}');
+
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(canonical.as_bytes());
+    Ok(mac)
+}
+
+/// Append `value` to `out` with object keys sorted and no whitespace.
+fn write_canonical(value: &Value, out: &mut String) {
+    match value {
+        Value::Object(map) => {
+            out.push('{');
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            for (i, key) in keys.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(&Value::String(key.clone()), out);
+                out.push(':');
+                write_canonical(&map[key], out);
+            }
+            out.push('}');
+        }
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        scalar => out.push_str(&scalar.to_string()),
+    }
+}
+
+/// Hex-encode bytes to string.
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Hex-decode a string, or `None` if it is not valid hex.
+fn hex_decode(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &[u8] = b"test-signing-key";
+
+    fn bundle() -> Value {
+        serde_json::json!({
+            "session": { "id": "s1", "title": "Trip planning" },
+            "messages": [
+                { "role": "user", "content": "Find a hotel" },
+                { "role": "assistant", "content": "Here are three options" },
+            ],
+        })
+    }
+
+    #[test]
+    fn signed_bundle_verifies_after_reformatting() {
+        let mut bundle = bundle();
+        sign_bundle(&mut bundle, KEY).unwrap();
+        assert!(is_signed(&bundle));
+        assert_eq!(bundle["signature"]["algorithm"], SIGNATURE_ALGORITHM);
+
+        // Pretty-printing and re-parsing must not invalidate the signature
+        let reparsed: Value =
+            serde_json::from_str(&serde_json::to_string_pretty(&bundle).unwrap()).unwrap();
+        assert_eq!(verify_bundle(&reparsed, KEY), Ok(()));
+    }
+
+    #[test]
+    fn modified_bundle_fails_verification() {
+        let mut bundle = bundle();
+        sign_bundle(&mut bundle, KEY).unwrap();
+
+        let mut tampered = bundle.clone();
+        tampered["messages"][1]["content"] = Value::from("Send me your password");
+        assert_eq!(
+            verify_bundle(&tampered, KEY),
+            Err(BundleSignatureError::Mismatch)
+        );
+
+        assert_eq!(
+            verify_bundle(&bundle, b"another-key"),
+            Err(BundleSignatureError::Mismatch)
+        );
+    }
+
+    #[test]
+    fn unsigned_or_malformed_bundles_are_rejected() {
+        assert_eq!(
+            verify_bundle(&bundle(), KEY),
+            Err(BundleSignatureError::Unsigned)
+        );
+
+        let mut bad = bundle();
+        bad["signature"] = serde_json::json!({ "algorithm": "hmac-sha256", "value": "zz" });
+        assert_eq!(
+            verify_bundle(&bad, KEY),
+            Err(BundleSignatureError::Malformed)
+        );
+
+        bad["signature"]["algorithm"] = Value::from("md5");
+        assert_eq!(
+            verify_bundle(&bad, KEY),
+            Err(BundleSignatureError::UnsupportedAlgorithm("md5".to_owned()))
+        );
+    }
+
+    #[test]
+    fn generated_keys_are_random_hex() {
+        let key = generate_signing_key();
+        assert_eq!(key.len(), 64);
+        assert!(hex_decode(&key).is_some());
+        assert_ne!(key, generate_signing_key());
+    }
+}
@@ -8,12 +8,18 @@
 //!
 //! Encrypted format: `nonce (12 bytes) || ciphertext`
 //!
+//! Text fields stored alongside plaintext data (e.g. memory facts in LanceDB)
+//! use `encrypt_field`, which base64-encodes that format behind an
+//! [`ENCRYPTED_FIELD_PREFIX`] marker so encrypted and plaintext values can
+//! coexist in one column.
+//!
 //! SECURITY: Error types never contain plaintext or key material.
 
 use std::path::Path;
 
 use aes_gcm::aead::{Aead, KeyInit, OsRng};
 use aes_gcm::{AeadCore, Aes256Gcm, Nonce};
+use base64::Engine;
 use thiserror::Error;
 
 /// Nonce size for AES-256-GCM (96 bits / 12 bytes).
@@ -24,6 +30,9 @@ const KEYCHAIN_SERVICE: &str = "boternity";
 /// Keychain user/account for the vault master key.
 const KEYCHAIN_USER: &str = "vault-master-key";
 
+/// Marks a text field value produced by `VaultCrypto::encrypt_field`.
+pub const ENCRYPTED_FIELD_PREFIX: &str = "enc:v1:";
+
 /// Errors from vault encryption operations.
 ///
 /// IMPORTANT: These errors never include plaintext, key material, or ciphertext
@@ -39,6 +48,9 @@ pub enum VaultError {
     #[error("invalid ciphertext: too short")]
     CiphertextTooShort,
 
+    #[error("invalid encrypted field encoding")]
+    InvalidFieldEncoding,
+
     #[error("key derivation failed")]
     KeyDerivationFailed,
 
@@ -208,6 +220,36 @@ impl VaultCrypto {
             .decrypt(nonce, ciphertext)
             .map_err(|_| VaultError::DecryptionFailed)
     }
+
+    /// Encrypt a text field for storage in a text column.
+    ///
+    /// Returns `enc:v1:` followed by the base64 of `encrypt()`'s output.
+    pub fn encrypt_field(&self, plaintext: &str) -> Result<String, VaultError> {
+        let encrypted = self.encrypt(plaintext.as_bytes())?;
+        Ok(format!(
+            "{ENCRYPTED_FIELD_PREFIX}{}",
+            base64::engine::general_purpose::STANDARD.encode(encrypted)
+        ))
+    }
+
+    /// Decrypt a text field produced by `encrypt_field()`.
+    ///
+    /// Values without the encrypted-field prefix are returned unchanged, so
+    /// columns written before encryption was enabled stay readable.
+    pub fn decrypt_field(&self, value: &str) -> Result<String, VaultError> {
+        let Some(encoded) = value.strip_prefix(ENCRYPTED_FIELD_PREFIX) else {
+            return Ok(value.to_string());
+        };
+        let encrypted = base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .map_err(|_| VaultError::InvalidFieldEncoding)?;
+        String::from_utf8(self.decrypt(&encrypted)?).map_err(|_| VaultError::InvalidFieldEncoding)
+    }
+}
+
+/// Whether a text field value was produced by `VaultCrypto::encrypt_field`.
+pub fn is_encrypted_field(value: &str) -> bool {
+    value.starts_with(ENCRYPTED_FIELD_PREFIX)
 }
 
 /// Generate 32 random bytes using the OS CSPRNG.
@@ -308,6 +350,30 @@ mod tests {
         assert!(decrypted.is_empty());
     }
 
+    #[test]
+    fn test_field_roundtrip_and_plaintext_passthrough() {
+        let crypto = VaultCrypto::new(&test_key());
+
+        let field = crypto
+            .encrypt_field("User's sister is called Maya")
+            .unwrap();
+        assert!(is_encrypted_field(&field));
+        assert!(!field.contains("Maya"));
+        assert_eq!(
+            crypto.decrypt_field(&field).unwrap(),
+            "User's sister is called Maya"
+        );
+
+        // Values written before encryption was enabled read as-is
+        assert_eq!(crypto.decrypt_field("plain fact").unwrap(), "plain fact");
+
+        let corrupt = format!("{ENCRYPTED_FIELD_PREFIX}not base64!");
+        assert!(matches!(
+            crypto.decrypt_field(&corrupt),
+            Err(VaultError::InvalidFieldEncoding)
+        ));
+    }
+
     #[test]
     fn test_from_password() {
         let crypto1 = VaultCrypto::from_password("my-strong-password").unwrap();
@@ -351,6 +417,7 @@ mod tests {
             VaultError::EncryptionFailed,
             VaultError::DecryptionFailed,
             VaultError::CiphertextTooShort,
+            VaultError::InvalidFieldEncoding,
             VaultError::KeyDerivationFailed,
             VaultError::KeychainUnavailable("no keychain service".to_string()),
             VaultError::KeychainError("credential store locked".to_string()),
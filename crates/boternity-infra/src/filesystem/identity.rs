@@ -10,6 +10,11 @@
 //! provider: anthropic
 //! temperature: 0.7
 //! max_tokens: 4096
+//! preferred_providers: openrouter, gemini   # optional
+//! provider_exclusive: false                 # optional
+//! max_request_tokens: 100000                # optional
+//! guard_untrusted_content: true             # optional
+//! memory_extraction_interval: 5             # optional
 //! ---
 //! # Luna - Identity Configuration
 //! ...
@@ -17,6 +22,28 @@
 
 use boternity_types::bot::{BotCategory, BotId};
 use boternity_types::identity::Identity;
+use boternity_types::llm::ProviderPreference;
+use serde::Serialize;
+
+use crate::llm::pricing::known_model;
+
+/// Accepted `temperature` range.
+const TEMPERATURE_RANGE: std::ops::RangeInclusive<f64> = 0.0..=2.0;
+
+/// Frontmatter keys recognized by [`parse_identity_frontmatter`].
+const KNOWN_KEYS: &[&str] = &[
+    "display_name",
+    "category",
+    "model",
+    "provider",
+    "temperature",
+    "max_tokens",
+    "preferred_providers",
+    "provider_exclusive",
+    "max_request_tokens",
+    "guard_untrusted_content",
+    "memory_extraction_interval",
+];
 
 /// Parsed IDENTITY.md frontmatter fields.
 #[derive(Debug, Clone)]
@@ -27,6 +54,17 @@ pub struct IdentityFrontmatter {
     pub provider: String,
     pub temperature: f64,
     pub max_tokens: i32,
+    /// Per-bot provider chain override (`preferred_providers` /
+    /// `provider_exclusive`); `None` uses the global chain.
+    pub provider_preference: Option<ProviderPreference>,
+    /// Per-bot cap on the tokens one user request may spend across all
+    /// sub-agents; `None` uses the global `default_request_budget`.
+    pub max_request_tokens: Option<u32>,
+    /// Fence recalled memories off as untrusted data in the system prompt.
+    pub guard_untrusted_content: bool,
+    /// Turns between periodic memory extractions (0 disables them); `None`
+    /// uses the default cadence.
+    pub memory_extraction_interval: Option<u32>,
 }
 
 /// Parse the IDENTITY.md content into frontmatter fields.
@@ -54,6 +92,11 @@ pub fn parse_identity_frontmatter(content: &str) -> Option<IdentityFrontmatter>
     let mut provider = None;
     let mut temperature = None;
     let mut max_tokens = None;
+    let mut preferred_providers: Vec<String> = Vec::new();
+    let mut provider_exclusive = false;
+    let mut max_request_tokens = None;
+    let mut guard_untrusted_content = false;
+    let mut memory_extraction_interval = None;
 
     for line in yaml_str.lines() {
         let line = line.trim();
@@ -77,6 +120,40 @@ pub fn parse_identity_frontmatter(content: &str) -> Option<IdentityFrontmatter>
                 .trim()
                 .parse::<i32>()
                 .ok();
+        } else if line.starts_with("preferred_providers:") {
+            preferred_providers = line
+                .trim_start_matches("preferred_providers:")
+                .trim()
+                .trim_start_matches('[')
+                .trim_end_matches(']')
+                .split(',')
+                .map(|p| p.trim().trim_matches('"').to_string())
+                .filter(|p| !p.is_empty())
+                .collect();
+        } else if line.starts_with("provider_exclusive:") {
+            provider_exclusive = line
+                .trim_start_matches("provider_exclusive:")
+                .trim()
+                .parse::<bool>()
+                .unwrap_or(false);
+        } else if line.starts_with("max_request_tokens:") {
+            max_request_tokens = line
+                .trim_start_matches("max_request_tokens:")
+                .trim()
+                .parse::<u32>()
+                .ok();
+        } else if line.starts_with("guard_untrusted_content:") {
+            guard_untrusted_content = line
+                .trim_start_matches("guard_untrusted_content:")
+                .trim()
+                .parse::<bool>()
+                .unwrap_or(false);
+        } else if line.starts_with("memory_extraction_interval:") {
+            memory_extraction_interval = line
+                .trim_start_matches("memory_extraction_interval:")
+                .trim()
+                .parse::<u32>()
+                .ok();
         }
     }
 
@@ -87,9 +164,192 @@ pub fn parse_identity_frontmatter(content: &str) -> Option<IdentityFrontmatter>
         provider: provider.unwrap_or_else(|| Identity::DEFAULT_PROVIDER.to_string()),
         temperature: temperature.unwrap_or(Identity::DEFAULT_TEMPERATURE),
         max_tokens: max_tokens.unwrap_or(Identity::DEFAULT_MAX_TOKENS),
+        provider_preference: (!preferred_providers.is_empty()).then_some(ProviderPreference {
+            preferred: preferred_providers,
+            exclusive: provider_exclusive,
+        }),
+        max_request_tokens,
+        guard_untrusted_content,
+        memory_extraction_interval,
     })
 }
 
+/// How serious an [`IdentityIssue`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IssueSeverity {
+    /// The value is invalid and will be replaced by a default at load time.
+    Error,
+    /// The value is accepted but probably not what was intended.
+    Warning,
+}
+
+/// A problem found by [`validate_identity`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct IdentityIssue {
+    pub severity: IssueSeverity,
+    /// Frontmatter key the issue refers to (`frontmatter` for structural issues).
+    pub field: String,
+    pub message: String,
+}
+
+impl IdentityIssue {
+    fn error(field: &str, message: impl Into<String>) -> Self {
+        Self {
+            severity: IssueSeverity::Error,
+            field: field.to_string(),
+            message: message.into(),
+        }
+    }
+
+    fn warning(field: &str, message: impl Into<String>) -> Self {
+        Self {
+            severity: IssueSeverity::Warning,
+            field: field.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+/// Validate IDENTITY.md frontmatter and report every problem found.
+///
+/// [`parse_identity_frontmatter`] silently falls back to defaults for
+/// missing or unparseable values; this reports them instead. An empty
+/// result means the file is clean. Unknown models are warnings because the
+/// built-in model table lags new releases.
+pub fn validate_identity(content: &str) -> Vec<IdentityIssue> {
+    let Some((yaml_str, _body)) = parse_identity_content(content) else {
+        return vec![IdentityIssue::error(
+            "frontmatter",
+            "missing '---' delimited frontmatter block",
+        )];
+    };
+
+    let mut fields: Vec<(&str, &str)> = Vec::new();
+    let mut issues = Vec::new();
+    for line in yaml_str.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            issues.push(IdentityIssue::error(
+                "frontmatter",
+                format!("line '{line}' is not a 'key: value' pair"),
+            ));
+            continue;
+        };
+        let (key, value) = (key.trim(), value.trim());
+        if !KNOWN_KEYS.contains(&key) {
+            issues.push(IdentityIssue::warning(key, format!("unknown key '{key}' is ignored")));
+            continue;
+        }
+        if fields.iter().any(|(k, _)| *k == key) {
+            issues.push(IdentityIssue::warning(key, "duplicate key; the last value wins"));
+        }
+        fields.push((key, value));
+    }
+    let get = |key: &str| fields.iter().rev().find(|(k, _)| *k == key).map(|(_, v)| *v);
+
+    match get("display_name") {
+        Some(name) if !name.is_empty() => {}
+        _ => issues.push(IdentityIssue::error("display_name", "required field is missing")),
+    }
+
+    match get("category") {
+        None => {}
+        Some(category) if category.parse::<BotCategory>().is_ok() => {}
+        Some(category) => issues.push(IdentityIssue::error(
+            "category",
+            format!("unknown category '{category}'; defaults to assistant"),
+        )),
+    }
+
+    let provider = get("provider").filter(|p| !p.is_empty());
+    let model = get("model").filter(|m| !m.is_empty());
+    if provider.is_none() {
+        issues.push(IdentityIssue::warning(
+            "provider",
+            format!("not set; defaults to {}", Identity::DEFAULT_PROVIDER),
+        ));
+    }
+    match model {
+        None => issues.push(IdentityIssue::warning(
+            "model",
+            format!("not set; defaults to {}", Identity::DEFAULT_MODEL),
+        )),
+        Some(model) => {
+            let provider = provider.unwrap_or(Identity::DEFAULT_PROVIDER);
+            if known_model(provider, model) == Some(false) {
+                issues.push(IdentityIssue::warning(
+                    "model",
+                    format!("'{model}' is not a known {provider} model"),
+                ));
+            }
+        }
+    }
+
+    if let Some(raw) = get("temperature") {
+        match raw.parse::<f64>() {
+            Ok(t) if TEMPERATURE_RANGE.contains(&t) => {}
+            Ok(t) => issues.push(IdentityIssue::error(
+                "temperature",
+                format!(
+                    "{t} is outside {}..={}",
+                    TEMPERATURE_RANGE.start(),
+                    TEMPERATURE_RANGE.end()
+                ),
+            )),
+            Err(_) => issues.push(IdentityIssue::error(
+                "temperature",
+                format!("'{raw}' is not a number"),
+            )),
+        }
+    }
+
+    if let Some(raw) = get("max_tokens") {
+        match raw.parse::<i32>() {
+            Ok(n) if n > 0 => {}
+            Ok(n) => issues.push(IdentityIssue::error(
+                "max_tokens",
+                format!("{n} must be positive"),
+            )),
+            Err(_) => issues.push(IdentityIssue::error(
+                "max_tokens",
+                format!("'{raw}' is not an integer"),
+            )),
+        }
+    }
+
+    if let Some(raw) = get("max_request_tokens") {
+        if !matches!(raw.parse::<u32>(), Ok(n) if n > 0) {
+            issues.push(IdentityIssue::error(
+                "max_request_tokens",
+                format!("'{raw}' is not a positive integer"),
+            ));
+        }
+    }
+
+    if let Some(raw) = get("memory_extraction_interval") {
+        if raw.parse::<u32>().is_err() {
+            issues.push(IdentityIssue::error(
+                "memory_extraction_interval",
+                format!("'{raw}' is not a non-negative integer"),
+            ));
+        }
+    }
+
+    for key in ["provider_exclusive", "guard_untrusted_content"] {
+        if let Some(raw) = get(key) {
+            if raw.parse::<bool>().is_err() {
+                issues.push(IdentityIssue::error(key, format!("'{raw}' is not true or false")));
+            }
+        }
+    }
+
+    issues
+}
+
 /// Convert parsed identity frontmatter to a domain `Identity` struct.
 pub fn frontmatter_to_identity(
     bot_id: BotId,
@@ -104,6 +364,7 @@ pub fn frontmatter_to_identity(
         emoji: None,
         model: fm.model.clone(),
         provider: fm.provider.clone(),
+        provider_preference: fm.provider_preference.clone(),
         temperature: fm.temperature,
         max_tokens: fm.max_tokens,
         category,
@@ -154,6 +415,10 @@ max_tokens: 4096
             provider: "openai".to_string(),
             temperature: 0.5,
             max_tokens: 2048,
+            provider_preference: None,
+            max_request_tokens: None,
+            guard_untrusted_content: false,
+            memory_extraction_interval: None,
         };
         let identity = frontmatter_to_identity(BotId::new(), &fm);
         assert_eq!(identity.display_name, "Luna");
@@ -169,5 +434,104 @@ max_tokens: 4096
         assert_eq!(fm.display_name, "MinBot");
         assert_eq!(fm.category, "assistant"); // default
         assert_eq!(fm.model, Identity::DEFAULT_MODEL); // default
+        assert!(fm.provider_preference.is_none()); // global chain
+        assert!(fm.max_request_tokens.is_none()); // global budget
+        assert!(!fm.guard_untrusted_content);
+        assert!(fm.memory_extraction_interval.is_none());
+    }
+
+    #[test]
+    fn test_parse_identity_max_request_tokens() {
+        let content = "---\ndisplay_name: Frugal\nmax_request_tokens: 120000\n---\nBody";
+        let fm = parse_identity_frontmatter(content).unwrap();
+        assert_eq!(fm.max_request_tokens, Some(120_000));
+
+        let invalid = "---\ndisplay_name: Frugal\nmax_request_tokens: lots\n---\nBody";
+        assert!(parse_identity_frontmatter(invalid).unwrap().max_request_tokens.is_none());
+    }
+
+    fn issue_fields(issues: &[IdentityIssue], severity: IssueSeverity) -> Vec<&str> {
+        issues
+            .iter()
+            .filter(|i| i.severity == severity)
+            .map(|i| i.field.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_validate_identity_valid() {
+        assert!(validate_identity(SAMPLE_IDENTITY).is_empty());
+    }
+
+    #[test]
+    fn test_validate_identity_unknown_model() {
+        let content = SAMPLE_IDENTITY.replace("claude-sonnet-4-20250514", "claude-sonet-4");
+        let issues = validate_identity(&content);
+        assert_eq!(issue_fields(&issues, IssueSeverity::Warning), vec!["model"]);
+        assert!(issues[0].message.contains("claude-sonet-4"));
+
+        // Providers without a built-in model table accept any model.
+        let local = SAMPLE_IDENTITY
+            .replace("anthropic", "ollama")
+            .replace("claude-sonnet-4-20250514", "llama3");
+        assert!(validate_identity(&local).is_empty());
+    }
+
+    #[test]
+    fn test_validate_identity_out_of_range_values() {
+        let content = SAMPLE_IDENTITY
+            .replace("temperature: 0.7", "temperature: 3.5")
+            .replace("max_tokens: 4096", "max_tokens: 0");
+        let issues = validate_identity(&content);
+        assert_eq!(
+            issue_fields(&issues, IssueSeverity::Error),
+            vec!["temperature", "max_tokens"]
+        );
+
+        let content = SAMPLE_IDENTITY.replace("temperature: 0.7", "temperature: warm");
+        let issues = validate_identity(&content);
+        assert!(issues[0].message.contains("not a number"));
+    }
+
+    #[test]
+    fn test_validate_identity_missing_fields() {
+        let issues = validate_identity("---\ncategory: assistant\n---\nBody");
+        assert_eq!(issue_fields(&issues, IssueSeverity::Error), vec!["display_name"]);
+        assert_eq!(
+            issue_fields(&issues, IssueSeverity::Warning),
+            vec!["provider", "model"]
+        );
+
+        let issues = validate_identity("# No frontmatter");
+        assert_eq!(issue_fields(&issues, IssueSeverity::Error), vec!["frontmatter"]);
+    }
+
+    #[test]
+    fn test_validate_identity_unknown_key() {
+        let content = SAMPLE_IDENTITY.replace("max_tokens: 4096", "max_tokens: 4096\ntemprature: 0.2");
+        let issues = validate_identity(&content);
+        assert_eq!(issue_fields(&issues, IssueSeverity::Warning), vec!["temprature"]);
+    }
+
+    #[test]
+    fn test_parse_identity_memory_extraction_interval() {
+        let content = "---\ndisplay_name: Chatty\nmemory_extraction_interval: 4\n---\nBody";
+        let fm = parse_identity_frontmatter(content).unwrap();
+        assert_eq!(fm.memory_extraction_interval, Some(4));
+
+        let invalid = "---\ndisplay_name: Chatty\nmemory_extraction_interval: often\n---\nBody";
+        assert_eq!(validate_identity(invalid)[0].field, "memory_extraction_interval");
+    }
+
+    #[test]
+    fn test_parse_identity_provider_preference() {
+        let content = "---\ndisplay_name: Cheap\npreferred_providers: [openrouter, \"gemini\"]\nprovider_exclusive: true\n---\nBody";
+        let fm = parse_identity_frontmatter(content).unwrap();
+        let pref = fm.provider_preference.unwrap();
+        assert_eq!(pref.preferred, vec!["openrouter", "gemini"]);
+        assert!(pref.exclusive);
+
+        let identity = frontmatter_to_identity(BotId::new(), &parse_identity_frontmatter(content).unwrap());
+        assert_eq!(identity.provider_preference.unwrap().preferred[0], "openrouter");
     }
 }
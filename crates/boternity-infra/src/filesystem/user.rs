@@ -1,13 +1,25 @@
 //! USER.md file operations.
 //!
-//! USER.md is a user-curated briefing document. Unlike SOUL.md it has no
-//! frontmatter -- it's plain markdown authored by the user.
+//! USER.md is a user-curated briefing document: plain markdown authored by
+//! the user, optionally preceded by frontmatter with structured profile
+//! fields:
+//! ```text
+//! ---
+//! name: Sam
+//! pronouns: they/them
+//! timezone: Europe/Berlin
+//! preferences: [concise answers, metric units]
+//! ---
+//! # Luna - User Briefing
+//! ...
+//! ```
 //!
-//! This module provides read/write helpers and validation.
+//! This module provides read/write helpers, frontmatter parsing, and validation.
 
 use std::path::Path;
 
 use boternity_core::service::fs::FileSystem;
+use boternity_types::user::UserProfile;
 
 /// Read USER.md content from disk.
 ///
@@ -32,6 +44,59 @@ pub async fn write_user_file<F: FileSystem>(
     fs.write_file(user_path, content).await
 }
 
+/// Split USER.md into its structured profile and free-text body.
+///
+/// Without a frontmatter block the whole content is the body. Unknown keys
+/// are ignored; `preferences` accepts an inline `[a, b]` list or `- item`
+/// lines.
+pub fn parse_user_content(content: &str) -> (Option<UserProfile>, &str) {
+    let trimmed = content.trim_start();
+    let Some(after_opening) = trimmed.strip_prefix("---") else {
+        return (None, content);
+    };
+    let Some(closing_pos) = after_opening.find("\n---") else {
+        return (None, content);
+    };
+    let yaml_str = after_opening[..closing_pos].trim();
+    let body = after_opening[closing_pos + 4..].trim_start_matches(['\n', '\r']);
+
+    let mut profile = UserProfile::default();
+    let mut in_preferences = false;
+    let non_empty = |value: &str| {
+        let value = value.trim().trim_matches('"');
+        (!value.is_empty()).then(|| value.to_string())
+    };
+
+    for line in yaml_str.lines() {
+        let line = line.trim();
+        if in_preferences && line.starts_with("- ") {
+            profile
+                .preferences
+                .extend(non_empty(line.trim_start_matches("- ")));
+            continue;
+        }
+        in_preferences = false;
+
+        if let Some(value) = line.strip_prefix("name:") {
+            profile.name = non_empty(value);
+        } else if let Some(value) = line.strip_prefix("pronouns:") {
+            profile.pronouns = non_empty(value);
+        } else if let Some(value) = line.strip_prefix("timezone:") {
+            profile.timezone = non_empty(value);
+        } else if let Some(value) = line.strip_prefix("preferences:") {
+            let value = value.trim();
+            if let Some(inner) = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+                profile.preferences = inner.split(',').filter_map(non_empty).collect();
+            } else {
+                in_preferences = value.is_empty();
+                profile.preferences.extend(non_empty(value));
+            }
+        }
+    }
+
+    ((!profile.is_empty()).then_some(profile), body)
+}
+
 /// Check if USER.md has been customized (not just the default template).
 ///
 /// A "customized" USER.md has at least one non-comment, non-heading line
@@ -83,6 +148,52 @@ I prefer concise answers with bullet points.
         assert!(is_user_customized(customized));
     }
 
+    #[test]
+    fn test_parse_user_content_with_frontmatter() {
+        let content = r#"---
+name: Sam
+pronouns: they/them
+timezone: Europe/Berlin
+preferences:
+  - concise answers
+  - metric units
+---
+
+# Luna - User Briefing
+
+I'm working on a Rust project.
+"#;
+        let (profile, body) = parse_user_content(content);
+        let profile = profile.unwrap();
+        assert_eq!(profile.name.as_deref(), Some("Sam"));
+        assert_eq!(profile.pronouns.as_deref(), Some("they/them"));
+        assert_eq!(profile.timezone.as_deref(), Some("Europe/Berlin"));
+        assert_eq!(profile.preferences, vec!["concise answers", "metric units"]);
+        assert!(body.starts_with("# Luna - User Briefing"));
+        assert!(!body.contains("pronouns"));
+    }
+
+    #[test]
+    fn test_parse_user_content_inline_preferences() {
+        let (profile, _) = parse_user_content("---\npreferences: [tea, \"dark mode\"]\n---\nBody");
+        let profile = profile.unwrap();
+        assert!(profile.name.is_none());
+        assert_eq!(profile.preferences, vec!["tea", "dark mode"]);
+    }
+
+    #[test]
+    fn test_parse_user_content_without_frontmatter() {
+        let content = "# Briefing\n\nPlain notes.";
+        let (profile, body) = parse_user_content(content);
+        assert!(profile.is_none());
+        assert_eq!(body, content);
+
+        // Frontmatter with no recognized fields yields no profile.
+        let (profile, body) = parse_user_content("---\nfoo: bar\n---\nNotes");
+        assert!(profile.is_none());
+        assert_eq!(body, "Notes");
+    }
+
     #[test]
     fn test_empty_not_customized() {
         assert!(!is_user_customized(""));
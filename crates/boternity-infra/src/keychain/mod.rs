@@ -8,7 +8,7 @@
 //! Note: The keyring API does not support enumeration, so `list()` always
 //! returns an empty vec. The vault (SQLite) maintains the key index.
 
-use boternity_core::repository::secret::SecretProvider;
+use boternity_core::repository::secret::{SecretProvider, SecretProviderKind};
 use boternity_types::error::RepositoryError;
 use boternity_types::secret::{SecretEntry, SecretScope};
 
@@ -59,6 +59,10 @@ impl Default for KeychainProvider {
 }
 
 impl SecretProvider for KeychainProvider {
+    fn kind(&self) -> SecretProviderKind {
+        SecretProviderKind::Keychain
+    }
+
     async fn get(
         &self,
         key: &str,
@@ -10,6 +10,7 @@ pub mod crypto;
 pub mod filesystem;
 pub mod keychain;
 pub mod llm;
+pub mod search;
 pub mod secret;
 pub mod skill;
 pub mod sqlite;
@@ -12,7 +12,9 @@ use std::time::Duration;
 
 use futures_util::Stream;
 use secrecy::{ExposeSecret, SecretString};
+use tokio_util::sync::CancellationToken;
 
+use boternity_core::llm::errors::classify_http_status;
 use boternity_core::llm::provider::LlmProvider;
 use boternity_types::llm::{
     CompletionRequest, CompletionResponse, LlmError, ProviderCapabilities, StopReason, StreamEvent,
@@ -129,35 +131,33 @@ impl AnthropicProvider {
     /// Convert a generic [`CompletionRequest`] into an [`AnthropicRequest`].
     ///
     /// When `output_config` is present on the request, it is forwarded to the
-    /// Anthropic request body and `stream` is forced to `false` (structured
-    /// output with streaming is not supported for the builder use case).
-    fn to_anthropic_request(&self, request: &CompletionRequest, stream: bool) -> AnthropicRequest {
+    /// Anthropic request body. Streamed structured output is validated as it
+    /// arrives by the `BoxLlmProvider` wrapper.
+    ///
+    /// Fails when the request needs a feature the model lacks, such as vision.
+    fn to_anthropic_request(
+        &self,
+        request: &CompletionRequest,
+        stream: bool,
+    ) -> Result<AnthropicRequest, LlmError> {
+        request.check_capabilities(&self.capabilities)?;
+
         let messages = request
             .messages
             .iter()
-            .map(|m| AnthropicMessage {
-                role: m.role.to_string(),
-                content: m.content.clone(),
-            })
+            .map(AnthropicMessage::from)
             .collect();
 
-        // When output_config is present, force stream to false
-        let effective_stream = if request.output_config.is_some() {
-            false
-        } else {
-            stream
-        };
-
-        AnthropicRequest {
+        Ok(AnthropicRequest {
             model: request.model.clone(),
             max_tokens: request.max_tokens,
             messages,
             system: request.system.clone(),
-            stream: effective_stream,
+            stream,
             temperature: request.temperature,
             stop_sequences: request.stop_sequences.clone(),
             output_config: request.output_config.clone(),
-        }
+        })
     }
 }
 
@@ -175,7 +175,7 @@ impl LlmProvider for AnthropicProvider {
     }
 
     async fn complete(&self, request: &CompletionRequest) -> Result<CompletionResponse, LlmError> {
-        let body = self.to_anthropic_request(request, false);
+        let body = self.to_anthropic_request(request, false)?;
         let url = self.url("/v1/messages");
 
         let response = self
@@ -194,16 +194,7 @@ impl LlmProvider for AnthropicProvider {
         let status = response.status();
         if !status.is_success() {
             let error_body = response.text().await.unwrap_or_default();
-            return Err(match status.as_u16() {
-                401 => LlmError::AuthenticationFailed,
-                429 => LlmError::RateLimited {
-                    retry_after_ms: None,
-                },
-                529 => LlmError::Overloaded(error_body),
-                _ => LlmError::Provider {
-                    message: format!("HTTP {status}: {error_body}"),
-                },
-            });
+            return Err(classify_http_status(status.as_u16(), &error_body));
         }
 
         let anthropic_resp: AnthropicNonStreamResponse =
@@ -249,10 +240,21 @@ impl LlmProvider for AnthropicProvider {
         &self,
         request: CompletionRequest,
     ) -> Pin<Box<dyn Stream<Item = Result<StreamEvent, LlmError>> + Send + 'static>> {
-        let body = self.to_anthropic_request(&request, true);
+        self.stream_with_cancel(request, CancellationToken::new())
+    }
+
+    fn stream_with_cancel(
+        &self,
+        request: CompletionRequest,
+        cancel: CancellationToken,
+    ) -> Pin<Box<dyn Stream<Item = Result<StreamEvent, LlmError>> + Send + 'static>> {
+        let body = match self.to_anthropic_request(&request, true) {
+            Ok(body) => body,
+            Err(e) => return Box::pin(futures_util::stream::once(async move { Err(e) })),
+        };
         let url = self.url("/v1/messages");
 
-        create_anthropic_stream(&self.client, &url, body, &self.api_key)
+        create_anthropic_stream(&self.client, &url, body, &self.api_key, cancel)
     }
 
     async fn count_tokens(&self, request: &CompletionRequest) -> Result<TokenCount, LlmError> {
@@ -339,16 +341,21 @@ mod tests {
             messages: vec![boternity_types::llm::Message {
                 role: boternity_types::llm::MessageRole::User,
                 content: "Hello".to_string(),
+                tool: None,
+                images: Vec::new(),
             }],
             system: Some("Be helpful".to_string()),
             max_tokens: 1024,
             temperature: Some(0.7),
+            top_p: None,
+            frequency_penalty: None,
+            presence_penalty: None,
             stream: false,
             stop_sequences: None,
             output_config: None,
         };
 
-        let anthropic_req = provider.to_anthropic_request(&request, true);
+        let anthropic_req = provider.to_anthropic_request(&request, true).unwrap();
         assert_eq!(anthropic_req.model, "claude-sonnet-4-20250514");
         assert!(anthropic_req.stream);
         assert_eq!(anthropic_req.messages.len(), 1);
@@ -356,12 +363,144 @@ mod tests {
         assert_eq!(anthropic_req.system.as_deref(), Some("Be helpful"));
     }
 
+    #[test]
+    fn test_to_anthropic_request_maps_tool_turns() {
+        use boternity_types::llm::{Message, MessageRole, ToolCall};
+
+        let provider = make_provider();
+        let request = CompletionRequest {
+            model: "claude-sonnet-4-20250514".to_string(),
+            messages: vec![
+                Message {
+                    role: MessageRole::User,
+                    content: "What is 6 * 7?".to_string(),
+                    tool: None,
+                    images: Vec::new(),
+                },
+                Message::tool_calls(
+                    "Let me calculate.",
+                    vec![ToolCall {
+                        id: "toolu_1".to_string(),
+                        name: "calculator".to_string(),
+                        input: serde_json::json!({"expr": "6 * 7"}),
+                    }],
+                ),
+                Message::tool_result("toolu_1", "42", false),
+            ],
+            system: None,
+            max_tokens: 1024,
+            temperature: None,
+            top_p: None,
+            frequency_penalty: None,
+            presence_penalty: None,
+            stream: false,
+            stop_sequences: None,
+            output_config: None,
+        };
+
+        let json = serde_json::to_value(provider.to_anthropic_request(&request, false).unwrap()).unwrap();
+        let messages = json["messages"].as_array().unwrap();
+        assert_eq!(messages[0]["content"], "What is 6 * 7?");
+
+        assert_eq!(messages[1]["role"], "assistant");
+        assert_eq!(messages[1]["content"][0]["type"], "text");
+        assert_eq!(messages[1]["content"][1]["type"], "tool_use");
+        assert_eq!(messages[1]["content"][1]["id"], "toolu_1");
+        assert_eq!(messages[1]["content"][1]["input"]["expr"], "6 * 7");
+
+        // Tool results go back as a user message with a tool_result block
+        assert_eq!(messages[2]["role"], "user");
+        assert_eq!(
+            messages[2]["content"],
+            serde_json::json!([{"type": "tool_result", "tool_use_id": "toolu_1", "content": "42"}])
+        );
+    }
+
+    #[test]
+    fn test_to_anthropic_request_maps_images() {
+        use boternity_types::llm::{ImageSource, Message};
+
+        let mut request = CompletionRequest {
+            model: "claude-sonnet-4-20250514".to_string(),
+            messages: vec![
+                Message::from("What is in this picture?".to_string()).with_image(
+                    ImageSource::Base64 {
+                        media_type: "image/png".to_string(),
+                        data: "iVBORw0KGgo=".to_string(),
+                    },
+                ),
+            ],
+            system: None,
+            max_tokens: 1024,
+            temperature: None,
+            top_p: None,
+            frequency_penalty: None,
+            presence_penalty: None,
+            stream: false,
+            stop_sequences: None,
+            output_config: None,
+        };
+
+        let json =
+            serde_json::to_value(make_provider().to_anthropic_request(&request, false).unwrap())
+                .unwrap();
+        assert_eq!(
+            json["messages"][0]["content"],
+            serde_json::json!([
+                {"type": "text", "text": "What is in this picture?"},
+                {
+                    "type": "image",
+                    "source": {"type": "base64", "media_type": "image/png", "data": "iVBORw0KGgo="}
+                }
+            ])
+        );
+
+        // Unknown models are assumed to lack vision
+        let provider = AnthropicProvider::new(
+            SecretString::from("test-key-not-real"),
+            "claude-custom".to_string(),
+        );
+        request.model = "claude-custom".to_string();
+        assert!(matches!(
+            provider.to_anthropic_request(&request, false),
+            Err(LlmError::Unsupported { .. })
+        ));
+    }
+
     #[test]
     fn test_base_url_override() {
         let provider = make_provider().with_base_url("http://localhost:8080".to_string());
         assert_eq!(provider.url("/v1/messages"), "http://localhost:8080/v1/messages");
     }
 
+    #[tokio::test]
+    async fn test_stream_with_cancel_ends_without_events() {
+        use futures_util::StreamExt;
+        // Unroutable base URL: the event source must be closed before connecting.
+        let provider = make_provider().with_base_url("http://10.255.255.1:9".to_string());
+        let request = CompletionRequest {
+            model: "claude-sonnet-4-20250514".to_string(),
+            messages: vec![],
+            system: None,
+            max_tokens: 16,
+            temperature: None,
+            top_p: None,
+            frequency_penalty: None,
+            presence_penalty: None,
+            stream: true,
+            stop_sequences: None,
+            output_config: None,
+        };
+
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        let mut stream = provider.stream_with_cancel(request, cancel);
+        let next = tokio::time::timeout(Duration::from_millis(100), stream.next())
+            .await
+            .expect("cancelled stream should end promptly");
+        assert!(next.is_none());
+    }
+
     #[tokio::test]
     async fn test_count_tokens_estimation() {
         let provider = make_provider();
@@ -370,10 +509,15 @@ mod tests {
             messages: vec![boternity_types::llm::Message {
                 role: boternity_types::llm::MessageRole::User,
                 content: "Hello world, how are you doing today?".to_string(),
+                tool: None,
+                images: Vec::new(),
             }],
             system: Some("You are helpful.".to_string()),
             max_tokens: 1024,
             temperature: None,
+            top_p: None,
+            frequency_penalty: None,
+            presence_penalty: None,
             stream: false,
             stop_sequences: None,
             output_config: None,
@@ -17,7 +17,9 @@ use std::pin::Pin;
 
 use futures_util::{Stream, StreamExt};
 use secrecy::{ExposeSecret, SecretString};
+use tokio_util::sync::CancellationToken;
 
+use boternity_core::llm::errors::classify_error_type;
 use boternity_types::llm::{LlmError, StopReason, StreamEvent, Usage};
 
 use super::types::{
@@ -66,11 +68,14 @@ struct StreamState {
 /// * `url` - Full API URL (e.g., "https://api.anthropic.com/v1/messages")
 /// * `body` - Serialized Anthropic request with `stream: true`
 /// * `api_key` - API key wrapped in SecretString
+/// * `cancel` - When cancelled, the event source is closed and the stream
+///   ends without a `Done` event
 pub fn create_anthropic_stream(
     client: &reqwest::Client,
     url: &str,
     body: AnthropicRequest,
     api_key: &SecretString,
+    cancel: CancellationToken,
 ) -> Pin<Box<dyn Stream<Item = Result<StreamEvent, LlmError>> + Send + 'static>> {
     // Clone owned values for the 'static stream closure
     let client = client.clone();
@@ -94,7 +99,17 @@ pub fn create_anthropic_stream(
             model: None,
         };
 
-        while let Some(event) = es.next().await {
+        loop {
+            let event = tokio::select! {
+                biased;
+                _ = cancel.cancelled() => {
+                    tracing::debug!("Anthropic stream cancelled, closing event source");
+                    es.close();
+                    break;
+                }
+                event = es.next() => event,
+            };
+            let Some(event) = event else { break };
             match event {
                 Ok(reqwest_eventsource::Event::Open) => {
                     yield StreamEvent::Connected;
@@ -214,12 +229,8 @@ pub fn create_anthropic_stream(
                         "error" => {
                             let payload: ErrorPayload = serde_json::from_str(&msg.data)
                                 .map_err(|e| LlmError::Deserialization(format!("error event: {e}")))?;
-                            let err = match payload.error.error_type.as_str() {
-                                "overloaded_error" => LlmError::Overloaded(payload.error.message),
-                                "rate_limit_error" => LlmError::RateLimited { retry_after_ms: None },
-                                "authentication_error" => LlmError::AuthenticationFailed,
-                                _ => LlmError::Provider { message: payload.error.message },
-                            };
+                            let err = classify_error_type(&payload.error.error_type, &payload.error.message)
+                                .unwrap_or(LlmError::Provider { message: payload.error.message });
                             Err(err)?;
                         }
 
@@ -317,26 +328,20 @@ mod tests {
 
     #[test]
     fn test_error_type_mapping() {
-        let cases = vec![
-            ("overloaded_error", true),
-            ("rate_limit_error", true),
-            ("authentication_error", true),
-            ("unknown_error", true),
-        ];
-
-        for (error_type, should_produce_error) in cases {
-            let err = match error_type {
-                "overloaded_error" => LlmError::Overloaded("overloaded".to_string()),
-                "rate_limit_error" => LlmError::RateLimited { retry_after_ms: None },
-                "authentication_error" => LlmError::AuthenticationFailed,
-                _ => LlmError::Provider {
-                    message: "unknown".to_string(),
-                },
-            };
-            assert!(should_produce_error);
-            // Verify the error can be formatted
-            let _ = err.to_string();
-        }
+        assert!(matches!(
+            classify_error_type("overloaded_error", "overloaded"),
+            Some(LlmError::Overloaded(_))
+        ));
+        assert!(matches!(
+            classify_error_type("rate_limit_error", "slow down"),
+            Some(LlmError::RateLimited { retry_after_ms: None })
+        ));
+        assert!(matches!(
+            classify_error_type("authentication_error", "bad key"),
+            Some(LlmError::AuthenticationFailed)
+        ));
+        // Unknown types fall back to a generic provider error at the call site.
+        assert!(classify_error_type("unknown_error", "unknown").is_none());
     }
 
     #[test]
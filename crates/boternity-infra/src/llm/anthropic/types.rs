@@ -6,7 +6,7 @@
 
 use serde::{Deserialize, Serialize};
 
-use boternity_types::llm::OutputConfig;
+use boternity_types::llm::{ImageSource, Message, MessageRole, OutputConfig, ToolContent};
 
 /// Request body for the Anthropic Messages API.
 #[derive(Debug, Clone, Serialize)]
@@ -31,7 +31,120 @@ pub struct AnthropicRequest {
 #[derive(Debug, Clone, Serialize)]
 pub struct AnthropicMessage {
     pub role: String,
-    pub content: String,
+    pub content: AnthropicMessageContent,
+}
+
+/// Message content: a plain string, or content blocks for tool use and images.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum AnthropicMessageContent {
+    Text(String),
+    Blocks(Vec<AnthropicRequestBlock>),
+}
+
+impl From<String> for AnthropicMessageContent {
+    fn from(text: String) -> Self {
+        Self::Text(text)
+    }
+}
+
+/// A content block sent in a request message.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AnthropicRequestBlock {
+    Text {
+        text: String,
+    },
+    Image {
+        source: AnthropicImageSource,
+    },
+    ToolUse {
+        id: String,
+        name: String,
+        input: serde_json::Value,
+    },
+    ToolResult {
+        tool_use_id: String,
+        content: String,
+        #[serde(skip_serializing_if = "std::ops::Not::not")]
+        is_error: bool,
+    },
+}
+
+/// Where an image block's data comes from.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AnthropicImageSource {
+    Url { url: String },
+    Base64 { media_type: String, data: String },
+}
+
+impl From<&ImageSource> for AnthropicImageSource {
+    fn from(image: &ImageSource) -> Self {
+        match image {
+            ImageSource::Url { url } => Self::Url { url: url.clone() },
+            ImageSource::Base64 { media_type, data } => Self::Base64 {
+                media_type: media_type.clone(),
+                data: data.clone(),
+            },
+        }
+    }
+}
+
+impl From<&Message> for AnthropicMessage {
+    /// Map a generic message to the Messages API shape.
+    ///
+    /// Anthropic has no tool role: a tool result is a `tool_result` block in
+    /// a user message, and tool calls are `tool_use` blocks after the
+    /// assistant's text. Images become `image` blocks after the text.
+    fn from(message: &Message) -> Self {
+        match &message.tool {
+            Some(ToolContent::Result { call_id, is_error }) => Self {
+                role: MessageRole::User.to_string(),
+                content: AnthropicMessageContent::Blocks(vec![AnthropicRequestBlock::ToolResult {
+                    tool_use_id: call_id.clone(),
+                    content: message.content.clone(),
+                    is_error: *is_error,
+                }]),
+            },
+            Some(ToolContent::Calls { calls }) => {
+                let mut blocks = Vec::with_capacity(calls.len() + 1);
+                if !message.content.is_empty() {
+                    blocks.push(AnthropicRequestBlock::Text {
+                        text: message.content.clone(),
+                    });
+                }
+                blocks.extend(calls.iter().map(|call| AnthropicRequestBlock::ToolUse {
+                    id: call.id.clone(),
+                    name: call.name.clone(),
+                    input: call.input.clone(),
+                }));
+                Self {
+                    role: MessageRole::Assistant.to_string(),
+                    content: AnthropicMessageContent::Blocks(blocks),
+                }
+            }
+            None if message.images.is_empty() => Self {
+                role: message.role.to_string(),
+                content: message.content.clone().into(),
+            },
+            None => {
+                let mut blocks = Vec::with_capacity(message.images.len() + 1);
+                if !message.content.is_empty() {
+                    blocks.push(AnthropicRequestBlock::Text {
+                        text: message.content.clone(),
+                    });
+                }
+                blocks.extend(message.images.iter().map(|image| AnthropicRequestBlock::Image {
+                    source: image.into(),
+                }));
+                Self {
+                    role: message.role.to_string(),
+                    content: AnthropicMessageContent::Blocks(blocks),
+                }
+            }
+        }
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -174,7 +287,7 @@ mod tests {
             max_tokens: 1024,
             messages: vec![AnthropicMessage {
                 role: "user".to_string(),
-                content: "Hello".to_string(),
+                content: "Hello".to_string().into(),
             }],
             system: Some("You are helpful.".to_string()),
             stream: false,
@@ -201,7 +314,7 @@ mod tests {
             max_tokens: 2048,
             messages: vec![AnthropicMessage {
                 role: "user".to_string(),
-                content: "Hello".to_string(),
+                content: "Hello".to_string().into(),
             }],
             system: None,
             stream: false,
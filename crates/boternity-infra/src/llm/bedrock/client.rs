@@ -13,6 +13,7 @@ use std::time::Duration;
 use futures_util::Stream;
 use secrecy::{ExposeSecret, SecretString};
 
+use boternity_core::llm::errors::classify_http_status;
 use boternity_core::llm::provider::LlmProvider;
 use boternity_types::llm::{
     CompletionRequest, CompletionResponse, LlmError, ProviderCapabilities, StopReason, StreamEvent,
@@ -22,6 +23,7 @@ use boternity_types::llm::{
 use super::super::anthropic::types::{
     AnthropicContentBlock, AnthropicMessage, AnthropicNonStreamResponse,
 };
+use super::inventory;
 use super::streaming::create_bedrock_stream;
 use super::types::BedrockRequest;
 
@@ -89,6 +91,17 @@ impl BedrockProvider {
         }
     }
 
+    /// Create a new Bedrock provider, validating the model against the region.
+    ///
+    /// Same as [`new`](Self::new), but rejects malformed model ids and models
+    /// not offered in the effective region (after token region detection)
+    /// with [`LlmError::InvalidRequest`] instead of failing on the first call.
+    pub fn try_new(api_key: SecretString, model: String, region: String) -> Result<Self, LlmError> {
+        let provider = Self::new(api_key, model, region);
+        inventory::validate_model_for_region(&provider.model_id, &provider.region)?;
+        Ok(provider)
+    }
+
     /// Try to extract the AWS region from a base64-encoded presigned URL token.
     ///
     /// The token decodes to a URL like:
@@ -126,7 +139,7 @@ impl BedrockProvider {
     /// Bedrock cross-region inference profiles use a region shorthand prefix
     /// (e.g., `eu.`, `us.`) before the model ID. The `region` parameter is
     /// the full AWS region (e.g., `eu-west-1`); the shorthand is extracted
-    /// from the first segment before the dash.
+    /// from the first segment before the dash (`ap-*` regions map to `apac.`).
     ///
     /// If the model already contains a `.` (e.g., `eu.anthropic.claude-...`
     /// or `anthropic.claude-...`), it is returned as-is.
@@ -145,7 +158,7 @@ impl BedrockProvider {
             model.to_string()
         } else {
             // Extract region shorthand: "eu-west-1" → "eu", "us-east-1" → "us"
-            let region_prefix = region.split('-').next().unwrap_or("us");
+            let region_prefix = inventory::geography_prefix(region);
             format!("{region_prefix}.anthropic.{model}-v1:0")
         }
     }
@@ -202,17 +215,17 @@ impl BedrockProvider {
     /// Convert a generic [`CompletionRequest`] into a [`BedrockRequest`].
     ///
     /// Forwards `output_config` when present for structured output support.
-    fn to_bedrock_request(&self, request: &CompletionRequest) -> BedrockRequest {
+    /// Fails when the request needs a feature the model lacks, such as vision.
+    fn to_bedrock_request(&self, request: &CompletionRequest) -> Result<BedrockRequest, LlmError> {
+        request.check_capabilities(&self.capabilities)?;
+
         let messages = request
             .messages
             .iter()
-            .map(|m| AnthropicMessage {
-                role: m.role.to_string(),
-                content: m.content.clone(),
-            })
+            .map(AnthropicMessage::from)
             .collect();
 
-        BedrockRequest {
+        Ok(BedrockRequest {
             anthropic_version: Self::API_VERSION.to_string(),
             max_tokens: request.max_tokens,
             messages,
@@ -220,7 +233,7 @@ impl BedrockProvider {
             temperature: request.temperature,
             stop_sequences: request.stop_sequences.clone(),
             output_config: request.output_config.clone(),
-        }
+        })
     }
 }
 
@@ -237,7 +250,7 @@ impl LlmProvider for BedrockProvider {
     }
 
     async fn complete(&self, request: &CompletionRequest) -> Result<CompletionResponse, LlmError> {
-        let body = self.to_bedrock_request(request);
+        let body = self.to_bedrock_request(request)?;
         let url = self.url("invoke");
 
         tracing::debug!(url = %url, model_id = %self.model_id, region = %self.region, "Bedrock invoke request");
@@ -258,21 +271,7 @@ impl LlmProvider for BedrockProvider {
         if !status.is_success() {
             let error_body = response.text().await.unwrap_or_default();
             tracing::warn!(status = %status, body = %error_body, url = %url, "Bedrock API error response");
-            return Err(match status.as_u16() {
-                401 | 403 => LlmError::Provider {
-                    message: format!("Bedrock authentication failed (HTTP {status}): {error_body}"),
-                },
-                429 => LlmError::RateLimited {
-                    retry_after_ms: None,
-                },
-                529 => LlmError::Overloaded(error_body),
-                s if s >= 500 => LlmError::Provider {
-                    message: format!("Bedrock server error HTTP {status}: {error_body}"),
-                },
-                _ => LlmError::Provider {
-                    message: format!("HTTP {status}: {error_body}"),
-                },
-            });
+            return Err(classify_http_status(status.as_u16(), &error_body));
         }
 
         let bedrock_resp: AnthropicNonStreamResponse =
@@ -317,7 +316,10 @@ impl LlmProvider for BedrockProvider {
         &self,
         request: CompletionRequest,
     ) -> Pin<Box<dyn Stream<Item = Result<StreamEvent, LlmError>> + Send + 'static>> {
-        let body = self.to_bedrock_request(&request);
+        let body = match self.to_bedrock_request(&request) {
+            Ok(body) => body,
+            Err(e) => return Box::pin(futures_util::stream::once(async move { Err(e) })),
+        };
         let url = self.url("invoke-with-response-stream");
 
         create_bedrock_stream(&self.client, &url, body, &self.api_key)
@@ -392,6 +394,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_model_id_mapping_apac_region() {
+        assert_eq!(
+            BedrockProvider::to_bedrock_model_id("claude-sonnet-4-20250514", "ap-southeast-2"),
+            "apac.anthropic.claude-sonnet-4-20250514-v1:0"
+        );
+    }
+
+    #[test]
+    fn test_try_new_valid_model_region() {
+        let provider = BedrockProvider::try_new(
+            SecretString::from("bedrock-api-key-test"),
+            "claude-sonnet-4-20250514".to_string(),
+            "eu-west-1".to_string(),
+        )
+        .unwrap();
+        assert!(provider.url("invoke").contains("eu.anthropic."));
+    }
+
+    #[test]
+    fn test_try_new_model_unavailable_in_region() {
+        let result = BedrockProvider::try_new(
+            SecretString::from("bedrock-api-key-test"),
+            "claude-opus-4-20250514".to_string(),
+            "eu-west-1".to_string(),
+        );
+        assert!(matches!(result, Err(LlmError::InvalidRequest(_))));
+    }
+
+    #[test]
+    fn test_try_new_malformed_model_id() {
+        let result = BedrockProvider::try_new(
+            SecretString::from("bedrock-api-key-test"),
+            "anthropic.claude-sonnet-4".to_string(),
+            "us-east-1".to_string(),
+        );
+        assert!(matches!(result, Err(LlmError::InvalidRequest(_))));
+    }
+
     #[test]
     fn test_url_construction() {
         let provider = make_provider();
@@ -437,16 +478,21 @@ mod tests {
             messages: vec![boternity_types::llm::Message {
                 role: boternity_types::llm::MessageRole::User,
                 content: "Hello".to_string(),
+                tool: None,
+                images: Vec::new(),
             }],
             system: Some("Be helpful".to_string()),
             max_tokens: 1024,
             temperature: Some(0.7),
+            top_p: None,
+            frequency_penalty: None,
+            presence_penalty: None,
             stream: false,
             stop_sequences: None,
             output_config: None,
         };
 
-        let bedrock_req = provider.to_bedrock_request(&request);
+        let bedrock_req = provider.to_bedrock_request(&request).unwrap();
         assert_eq!(bedrock_req.anthropic_version, "bedrock-2023-05-31");
         assert_eq!(bedrock_req.max_tokens, 1024);
         assert_eq!(bedrock_req.messages.len(), 1);
@@ -463,12 +509,15 @@ mod tests {
             system: None,
             max_tokens: 1024,
             temperature: None,
+            top_p: None,
+            frequency_penalty: None,
+            presence_penalty: None,
             stream: false,
             stop_sequences: None,
             output_config: None,
         };
 
-        let bedrock_req = provider.to_bedrock_request(&request);
+        let bedrock_req = provider.to_bedrock_request(&request).unwrap();
         let json = serde_json::to_value(&bedrock_req).unwrap();
         // model must NOT be in the request body (it's in the URL path)
         assert!(json.get("model").is_none());
@@ -496,10 +545,15 @@ mod tests {
             messages: vec![boternity_types::llm::Message {
                 role: boternity_types::llm::MessageRole::User,
                 content: "Hello world, how are you doing today?".to_string(),
+                tool: None,
+                images: Vec::new(),
             }],
             system: Some("You are helpful.".to_string()),
             max_tokens: 1024,
             temperature: None,
+            top_p: None,
+            frequency_penalty: None,
+            presence_penalty: None,
             stream: false,
             stop_sequences: None,
             output_config: None,
@@ -0,0 +1,216 @@
+//! Static Bedrock model inventory and region validation.
+//!
+//! Bedrock model ids look like `{geo}.anthropic.{model}-v{n}:{m}` for
+//! cross-region inference profiles (e.g., `eu.anthropic.claude-sonnet-4-20250514-v1:0`)
+//! or `anthropic.{model}-v{n}:{m}` for in-region invocation. A malformed id or
+//! a model that is not offered in the configured region otherwise surfaces as
+//! an opaque 400/404 from the Runtime API on the first request.
+//!
+//! The availability table is approximate as of February 2026 and updated with
+//! version bumps. Models missing from the table are allowed (with a warning)
+//! so new releases work before the table catches up.
+
+use boternity_types::llm::LlmError;
+
+/// AWS regions where Bedrock Runtime serves Anthropic models.
+const BEDROCK_REGIONS: &[&str] = &[
+    "us-east-1",
+    "us-east-2",
+    "us-west-1",
+    "us-west-2",
+    "ca-central-1",
+    "eu-central-1",
+    "eu-central-2",
+    "eu-north-1",
+    "eu-south-1",
+    "eu-south-2",
+    "eu-west-1",
+    "eu-west-2",
+    "eu-west-3",
+    "ap-northeast-1",
+    "ap-northeast-2",
+    "ap-northeast-3",
+    "ap-south-1",
+    "ap-south-2",
+    "ap-southeast-1",
+    "ap-southeast-2",
+];
+
+/// Anthropic models and the inference-profile geographies that offer them.
+const MODEL_GEOGRAPHIES: &[(&str, &[&str])] = &[
+    ("claude-opus-4-20250514", &["us"]),
+    ("claude-opus-4-1-20250805", &["us"]),
+    ("claude-sonnet-4-20250514", &["us", "eu", "apac"]),
+    ("claude-sonnet-4-5-20250929", &["us", "eu", "apac", "global"]),
+    ("claude-haiku-4-5-20251001", &["us", "eu", "apac", "global"]),
+    ("claude-3-7-sonnet-20250219", &["us", "eu", "apac"]),
+    ("claude-3-5-haiku-20241022", &["us"]),
+];
+
+/// Cross-region inference profile prefix for an AWS region.
+///
+/// `us-east-1` -> `us`, `eu-west-1` -> `eu`, `ap-southeast-2` -> `apac`.
+/// Other regions map to their leading segment.
+pub fn geography_prefix(region: &str) -> &str {
+    match region.split('-').next().unwrap_or("us") {
+        "ap" => "apac",
+        other => other,
+    }
+}
+
+/// A parsed Bedrock model id.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BedrockModelId<'a> {
+    /// Inference profile geography (`us`, `eu`, `apac`, `global`), if any.
+    pub geography: Option<&'a str>,
+    /// Base model name (e.g., `claude-sonnet-4-20250514`).
+    pub model: &'a str,
+}
+
+/// Parse a Bedrock model id, rejecting malformed ids.
+pub fn parse_model_id(model_id: &str) -> Result<BedrockModelId<'_>, LlmError> {
+    let malformed = || {
+        LlmError::InvalidRequest(format!(
+            "malformed Bedrock model id '{model_id}': expected \
+             '[geo.]anthropic.<model>-v<N>:<M>' (e.g., 'us.anthropic.claude-sonnet-4-20250514-v1:0')"
+        ))
+    };
+
+    let (geography, rest) = match model_id.split_once(".anthropic.") {
+        Some((geo, rest)) => (Some(geo), rest),
+        None => (None, model_id.strip_prefix("anthropic.").ok_or_else(malformed)?),
+    };
+
+    if let Some(geo) = geography {
+        if geo.is_empty() || !geo.chars().all(|c| c.is_ascii_lowercase() || c == '-') {
+            return Err(malformed());
+        }
+    }
+
+    // Strip the `-v{n}:{m}` version suffix.
+    let (model, version) = rest.rsplit_once("-v").ok_or_else(malformed)?;
+    let (major, minor) = version.split_once(':').ok_or_else(malformed)?;
+    let is_number = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+    if !model.starts_with("claude-") || !is_number(major) || !is_number(minor) {
+        return Err(malformed());
+    }
+
+    Ok(BedrockModelId { geography, model })
+}
+
+/// Validate that `model_id` is well-formed and offered in `region`.
+///
+/// Checks, in order: the id parses, the region is a known Bedrock region,
+/// the inference-profile geography matches the region, and the model is
+/// offered in that geography. Unknown models pass with a warning.
+pub fn validate_model_for_region(model_id: &str, region: &str) -> Result<(), LlmError> {
+    let parsed = parse_model_id(model_id)?;
+
+    if !BEDROCK_REGIONS.contains(&region) {
+        return Err(LlmError::InvalidRequest(format!(
+            "unknown Bedrock region '{region}' (set AWS_REGION to a region such as us-east-1 or eu-west-1)"
+        )));
+    }
+
+    let region_geo = geography_prefix(region);
+    let geography = match parsed.geography {
+        Some("global") => "global",
+        Some(geo) if geo != region_geo => {
+            return Err(LlmError::InvalidRequest(format!(
+                "Bedrock model id '{model_id}' uses the '{geo}' inference profile, \
+                 which is not callable from region '{region}' (expected '{region_geo}.')"
+            )));
+        }
+        _ => region_geo,
+    };
+
+    match MODEL_GEOGRAPHIES.iter().find(|(m, _)| *m == parsed.model) {
+        Some((_, geos)) if geos.contains(&geography) => Ok(()),
+        Some((_, geos)) => Err(LlmError::InvalidRequest(format!(
+            "Bedrock model '{}' is not available in region '{region}' (available in: {})",
+            parsed.model,
+            geos.join(", ")
+        ))),
+        None => {
+            tracing::warn!(
+                model = %parsed.model,
+                region = %region,
+                "Bedrock model not in inventory; skipping availability check"
+            );
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_geography_prefix() {
+        assert_eq!(geography_prefix("us-east-1"), "us");
+        assert_eq!(geography_prefix("eu-west-1"), "eu");
+        assert_eq!(geography_prefix("ap-southeast-2"), "apac");
+    }
+
+    #[test]
+    fn test_parse_model_id() {
+        let parsed = parse_model_id("eu.anthropic.claude-sonnet-4-20250514-v1:0").unwrap();
+        assert_eq!(parsed.geography, Some("eu"));
+        assert_eq!(parsed.model, "claude-sonnet-4-20250514");
+
+        let parsed = parse_model_id("anthropic.claude-3-5-haiku-20241022-v1:0").unwrap();
+        assert_eq!(parsed.geography, None);
+        assert_eq!(parsed.model, "claude-3-5-haiku-20241022");
+    }
+
+    #[test]
+    fn test_parse_model_id_rejects_malformed() {
+        for id in [
+            "claude-sonnet-4-20250514",
+            "us.anthropic.claude-sonnet-4-20250514",
+            "us.anthropic.claude-sonnet-4-20250514-v1",
+            "us.anthropic.gpt-4o-v1:0",
+            "US.anthropic.claude-sonnet-4-20250514-v1:0",
+        ] {
+            assert!(
+                matches!(parse_model_id(id), Err(LlmError::InvalidRequest(_))),
+                "{id} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_valid_id_region_pair() {
+        assert!(validate_model_for_region("us.anthropic.claude-opus-4-20250514-v1:0", "us-west-2").is_ok());
+        assert!(validate_model_for_region("eu.anthropic.claude-sonnet-4-20250514-v1:0", "eu-west-1").is_ok());
+        assert!(validate_model_for_region("apac.anthropic.claude-sonnet-4-20250514-v1:0", "ap-northeast-1").is_ok());
+        assert!(validate_model_for_region("global.anthropic.claude-sonnet-4-5-20250929-v1:0", "eu-central-1").is_ok());
+    }
+
+    #[test]
+    fn test_validate_model_not_in_region() {
+        let err = validate_model_for_region("eu.anthropic.claude-opus-4-20250514-v1:0", "eu-west-1")
+            .unwrap_err();
+        assert!(err.to_string().contains("not available in region 'eu-west-1'"));
+    }
+
+    #[test]
+    fn test_validate_geography_mismatch() {
+        let err = validate_model_for_region("us.anthropic.claude-sonnet-4-20250514-v1:0", "eu-west-1")
+            .unwrap_err();
+        assert!(err.to_string().contains("not callable from region 'eu-west-1'"));
+    }
+
+    #[test]
+    fn test_validate_unknown_region() {
+        let err = validate_model_for_region("us.anthropic.claude-sonnet-4-20250514-v1:0", "mars-north-1")
+            .unwrap_err();
+        assert!(err.to_string().contains("unknown Bedrock region"));
+    }
+
+    #[test]
+    fn test_validate_unknown_model_allowed() {
+        assert!(validate_model_for_region("us.anthropic.claude-future-5-20270101-v1:0", "us-east-1").is_ok());
+    }
+}
@@ -4,6 +4,7 @@
 //! Bearer token authentication and the Bedrock event stream binary protocol.
 
 mod client;
+pub mod inventory;
 mod streaming;
 pub mod types;
 
@@ -20,6 +20,7 @@ use std::pin::Pin;
 use base64::Engine;
 use futures_util::{Stream, StreamExt};
 
+use boternity_core::llm::errors::{classify_error_type, classify_http_status};
 use boternity_types::llm::{LlmError, StopReason, StreamEvent, Usage};
 
 use super::super::anthropic::types::{
@@ -260,16 +261,10 @@ fn process_anthropic_event(
         "error" => {
             let payload: ErrorPayload = serde_json::from_str(json_data)
                 .map_err(|e| LlmError::Deserialization(format!("error event: {e}")))?;
-            let err = match payload.error.error_type.as_str() {
-                "overloaded_error" => LlmError::Overloaded(payload.error.message),
-                "rate_limit_error" => LlmError::RateLimited {
-                    retry_after_ms: None,
-                },
-                "authentication_error" => LlmError::AuthenticationFailed,
-                _ => LlmError::Provider {
+            let err = classify_error_type(&payload.error.error_type, &payload.error.message)
+                .unwrap_or(LlmError::Provider {
                     message: payload.error.message,
-                },
-            };
+                });
             return Err(err);
         }
 
@@ -323,20 +318,7 @@ pub fn create_bedrock_stream(
         let response = if !status.is_success() {
             let error_body = response.text().await.unwrap_or_default();
             tracing::warn!(status = %status, body = %error_body, "Bedrock stream API error response");
-            let err = match status.as_u16() {
-                401 | 403 => LlmError::Provider {
-                    message: format!("Bedrock authentication failed (HTTP {status}): {error_body}"),
-                },
-                429 => LlmError::RateLimited { retry_after_ms: None },
-                529 => LlmError::Overloaded(error_body),
-                s if s >= 500 => LlmError::Provider {
-                    message: format!("Bedrock server error HTTP {status}: {error_body}"),
-                },
-                _ => LlmError::Provider {
-                    message: format!("HTTP {status}: {error_body}"),
-                },
-            };
-            Err(err)?;
+            Err(classify_http_status(status.as_u16(), &error_body))?;
             unreachable!()
         } else {
             response
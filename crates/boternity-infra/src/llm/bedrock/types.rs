@@ -55,7 +55,7 @@ mod tests {
             max_tokens: 1024,
             messages: vec![AnthropicMessage {
                 role: "user".to_string(),
-                content: "Hello".to_string(),
+                content: "Hello".to_string().into(),
             }],
             system: Some("Be helpful.".to_string()),
             temperature: Some(0.7),
@@ -24,6 +24,7 @@
 use std::pin::Pin;
 
 use futures_util::Stream;
+use tokio_util::sync::CancellationToken;
 
 use boternity_core::llm::provider::LlmProvider;
 use boternity_types::llm::{
@@ -106,6 +107,14 @@ impl LlmProvider for ClaudeSubscriptionProvider {
         self.inner.stream(request)
     }
 
+    fn stream_with_cancel(
+        &self,
+        request: CompletionRequest,
+        cancel: CancellationToken,
+    ) -> Pin<Box<dyn Stream<Item = Result<StreamEvent, LlmError>> + Send + 'static>> {
+        self.inner.stream_with_cancel(request, cancel)
+    }
+
     async fn count_tokens(&self, request: &CompletionRequest) -> Result<TokenCount, LlmError> {
         self.inner.count_tokens(request).await
     }
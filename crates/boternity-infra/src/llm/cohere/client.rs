@@ -0,0 +1,390 @@
+//! CohereProvider -- concrete [`LlmProvider`] implementation for Cohere.
+//!
+//! Sends requests to the Cohere v2 Chat API (`/v2/chat`) with Bearer
+//! authentication. Cohere's native request/response format differs from
+//! OpenAI's, so requests and responses are mapped explicitly here.
+//!
+//! The API key is wrapped in [`secrecy::SecretString`] and is never logged
+//! or included in `Debug` output.
+
+use std::pin::Pin;
+use std::time::Duration;
+
+use futures_util::Stream;
+use secrecy::{ExposeSecret, SecretString};
+
+use boternity_core::llm::errors::classify_http_status;
+use boternity_core::llm::provider::LlmProvider;
+use boternity_types::llm::{
+    CompletionRequest, CompletionResponse, LlmError, MessageRole, ProviderCapabilities,
+    StreamEvent, TokenCount,
+};
+
+use super::streaming::create_cohere_stream;
+use super::types::{
+    map_finish_reason, CohereChatResponse, CohereContent, CohereErrorBody, CohereMessage,
+    CohereRequest,
+};
+
+/// Cohere LLM provider.
+///
+/// Implements [`LlmProvider`] for the Cohere v2 Chat API.
+pub struct CohereProvider {
+    client: reqwest::Client,
+    api_key: SecretString,
+    base_url: String,
+    model: String,
+    capabilities: ProviderCapabilities,
+}
+
+impl CohereProvider {
+    /// Create a new Cohere provider.
+    ///
+    /// # Arguments
+    ///
+    /// * `api_key` - Cohere API key wrapped in SecretString
+    /// * `model` - Model identifier (e.g., "command-r-plus", "command-a-03-2025")
+    pub fn new(api_key: SecretString, model: String) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(300)) // 5 min timeout for long generations
+            .build()
+            .expect("failed to create reqwest client");
+
+        let capabilities = Self::capabilities_for_model(&model);
+
+        Self {
+            client,
+            api_key,
+            base_url: "https://api.cohere.com".to_string(),
+            model,
+            capabilities,
+        }
+    }
+
+    /// The default model for this provider.
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    /// Override the base URL (useful for testing or proxies).
+    pub fn with_base_url(mut self, base_url: String) -> Self {
+        self.base_url = base_url;
+        self
+    }
+
+    /// Determine capabilities based on model name.
+    fn capabilities_for_model(model: &str) -> ProviderCapabilities {
+        if model.starts_with("command-a") {
+            ProviderCapabilities {
+                max_context_tokens: 256_000,
+                max_output_tokens: 8_000,
+                streaming: true,
+                tool_calling: true,
+                vision: model.contains("vision"),
+                extended_thinking: model.contains("reasoning"),
+            }
+        } else if model.starts_with("command-r") {
+            ProviderCapabilities {
+                max_context_tokens: 128_000,
+                max_output_tokens: 4_000,
+                streaming: true,
+                tool_calling: true,
+                vision: false,
+                extended_thinking: false,
+            }
+        } else {
+            // Conservative defaults for older or unknown models
+            ProviderCapabilities {
+                max_context_tokens: 4_096,
+                max_output_tokens: 4_000,
+                streaming: true,
+                tool_calling: false,
+                vision: false,
+                extended_thinking: false,
+            }
+        }
+    }
+
+    /// Build the full API URL for a given path.
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    /// Convert a generic [`CompletionRequest`] into a [`CohereRequest`].
+    ///
+    /// The system prompt becomes a leading `system` message. An empty
+    /// request model falls back to the provider's configured model.
+    fn to_cohere_request(&self, request: &CompletionRequest, stream: bool) -> CohereRequest {
+        let mut messages = Vec::with_capacity(request.messages.len() + 1);
+        if let Some(ref system) = request.system {
+            messages.push(CohereMessage {
+                role: MessageRole::System.to_string(),
+                content: system.clone(),
+            });
+        }
+        messages.extend(request.messages.iter().map(|m| CohereMessage {
+            role: m.role.to_string(),
+            content: m.content.clone(),
+        }));
+
+        let model = if request.model.is_empty() {
+            self.model.clone()
+        } else {
+            request.model.clone()
+        };
+
+        CohereRequest {
+            model,
+            messages,
+            stream,
+            max_tokens: Some(request.max_tokens),
+            temperature: request.temperature,
+            stop_sequences: request.stop_sequences.clone(),
+        }
+    }
+}
+
+/// Map a non-2xx Cohere HTTP response to an [`LlmError`].
+///
+/// Cohere error bodies are `{"message": "..."}`; the raw body is used when
+/// it does not parse. Classification is shared with the other providers
+/// (498 is Cohere's "invalid token" status).
+pub(crate) fn map_http_error(status: u16, body: &str) -> LlmError {
+    let message = serde_json::from_str::<CohereErrorBody>(body)
+        .map(|e| e.message)
+        .unwrap_or_else(|_| body.to_string());
+
+    classify_http_status(status, &message)
+}
+
+// CohereProvider intentionally does NOT derive Debug, matching AnthropicProvider.
+
+impl LlmProvider for CohereProvider {
+    fn name(&self) -> &str {
+        "cohere"
+    }
+
+    fn capabilities(&self) -> &ProviderCapabilities {
+        &self.capabilities
+    }
+
+    async fn complete(&self, request: &CompletionRequest) -> Result<CompletionResponse, LlmError> {
+        let body = self.to_cohere_request(request, false);
+        let url = self.url("/v2/chat");
+
+        let response = self
+            .client
+            .post(&url)
+            .bearer_auth(self.api_key.expose_secret())
+            .header("content-type", "application/json")
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| LlmError::Provider {
+                message: format!("HTTP request failed: {e}"),
+            })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_body = response.text().await.unwrap_or_default();
+            return Err(map_http_error(status.as_u16(), &error_body));
+        }
+
+        let cohere_resp: CohereChatResponse = response.json().await.map_err(|e| {
+            LlmError::Deserialization(format!("failed to parse response: {e}"))
+        })?;
+
+        Ok(parse_chat_response(cohere_resp, body.model))
+    }
+
+    fn stream(
+        &self,
+        request: CompletionRequest,
+    ) -> Pin<Box<dyn Stream<Item = Result<StreamEvent, LlmError>> + Send + 'static>> {
+        let body = self.to_cohere_request(&request, true);
+        let url = self.url("/v2/chat");
+
+        create_cohere_stream(&self.client, &url, body, &self.api_key)
+    }
+
+    async fn count_tokens(&self, request: &CompletionRequest) -> Result<TokenCount, LlmError> {
+        // Estimation: ~4 chars per token, same heuristic as the Anthropic provider.
+        let mut total_chars: usize = 0;
+
+        if let Some(system) = &request.system {
+            total_chars += system.len();
+        }
+
+        for msg in &request.messages {
+            total_chars += msg.content.len();
+            // Add overhead for role and message structure
+            total_chars += 10;
+        }
+
+        let estimated_tokens = (total_chars as f64 / 4.0).ceil() as u32;
+
+        Ok(TokenCount {
+            input_tokens: estimated_tokens,
+        })
+    }
+}
+
+/// Convert a Cohere chat response into a [`CompletionResponse`].
+///
+/// Cohere does not echo the model in its response, so the requested model
+/// is passed through.
+fn parse_chat_response(resp: CohereChatResponse, model: String) -> CompletionResponse {
+    let content = resp
+        .message
+        .content
+        .iter()
+        .filter_map(|c| match c {
+            CohereContent::Text { text } => Some(text.as_str()),
+            CohereContent::Other => None,
+        })
+        .collect::<Vec<_>>()
+        .join("");
+
+    CompletionResponse {
+        id: resp.id,
+        content,
+        model,
+        stop_reason: map_finish_reason(resp.finish_reason.as_deref()),
+        usage: resp.usage.map(|u| u.to_usage()).unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use boternity_types::llm::{Message, StopReason};
+
+    fn make_provider() -> CohereProvider {
+        CohereProvider::new(
+            SecretString::from("test-key-not-real"),
+            "command-r-plus".to_string(),
+        )
+    }
+
+    fn make_request(model: &str) -> CompletionRequest {
+        CompletionRequest {
+            model: model.to_string(),
+            messages: vec![
+                Message {
+                    role: MessageRole::User,
+                    content: "Hello".to_string(),
+                    tool: None,
+                    images: Vec::new(),
+                },
+                Message {
+                    role: MessageRole::Assistant,
+                    content: "Hi!".to_string(),
+                    tool: None,
+                    images: Vec::new(),
+                },
+            ],
+            system: Some("Be helpful".to_string()),
+            max_tokens: 1024,
+            temperature: Some(0.3),
+            top_p: None,
+            frequency_penalty: None,
+            presence_penalty: None,
+            stream: false,
+            stop_sequences: Some(vec!["END".to_string()]),
+            output_config: None,
+        }
+    }
+
+    #[test]
+    fn test_provider_name() {
+        assert_eq!(make_provider().name(), "cohere");
+    }
+
+    #[test]
+    fn test_command_r_capabilities() {
+        let caps = make_provider().capabilities().clone();
+        assert_eq!(caps.max_context_tokens, 128_000);
+        assert!(caps.streaming);
+        assert!(caps.tool_calling);
+        assert!(!caps.vision);
+    }
+
+    #[test]
+    fn test_command_a_capabilities() {
+        let provider = CohereProvider::new(
+            SecretString::from("test-key"),
+            "command-a-03-2025".to_string(),
+        );
+        assert_eq!(provider.capabilities().max_context_tokens, 256_000);
+    }
+
+    #[test]
+    fn test_to_cohere_request_puts_system_first() {
+        let provider = make_provider();
+        let req = provider.to_cohere_request(&make_request("command-r-plus"), true);
+
+        assert_eq!(req.model, "command-r-plus");
+        assert!(req.stream);
+        assert_eq!(req.max_tokens, Some(1024));
+        assert_eq!(req.temperature, Some(0.3));
+        assert_eq!(req.stop_sequences, Some(vec!["END".to_string()]));
+        assert_eq!(req.messages.len(), 3);
+        assert_eq!(req.messages[0].role, "system");
+        assert_eq!(req.messages[0].content, "Be helpful");
+        assert_eq!(req.messages[1].role, "user");
+        assert_eq!(req.messages[2].role, "assistant");
+    }
+
+    #[test]
+    fn test_to_cohere_request_falls_back_to_provider_model() {
+        let provider = make_provider();
+        let req = provider.to_cohere_request(&make_request(""), false);
+        assert_eq!(req.model, "command-r-plus");
+    }
+
+    #[test]
+    fn test_parse_chat_response() {
+        let json = r#"{
+            "id": "gen-1",
+            "finish_reason": "MAX_TOKENS",
+            "message": {
+                "role": "assistant",
+                "content": [{"type": "text", "text": "Hello "}, {"type": "text", "text": "world"}]
+            },
+            "usage": {"tokens": {"input_tokens": 40, "output_tokens": 2}}
+        }"#;
+        let resp = parse_chat_response(serde_json::from_str(json).unwrap(), "command-r".to_string());
+
+        assert_eq!(resp.id, "gen-1");
+        assert_eq!(resp.content, "Hello world");
+        assert_eq!(resp.model, "command-r");
+        assert_eq!(resp.stop_reason, StopReason::MaxTokens);
+        assert_eq!(resp.usage.input_tokens, 40);
+        assert_eq!(resp.usage.output_tokens, 2);
+    }
+
+    #[test]
+    fn test_error_mapping() {
+        assert!(matches!(map_http_error(401, ""), LlmError::AuthenticationFailed));
+        assert!(matches!(map_http_error(498, ""), LlmError::AuthenticationFailed));
+        assert!(matches!(map_http_error(429, ""), LlmError::RateLimited { .. }));
+        assert!(matches!(
+            map_http_error(400, r#"{"message": "invalid model"}"#),
+            LlmError::InvalidRequest(m) if m == "invalid model"
+        ));
+        assert!(matches!(
+            map_http_error(503, r#"{"message": "busy"}"#),
+            LlmError::Overloaded(m) if m == "busy"
+        ));
+        assert!(matches!(
+            map_http_error(500, "oops"),
+            LlmError::Provider { message } if message == "HTTP 500: oops"
+        ));
+    }
+
+    #[test]
+    fn test_base_url_override() {
+        let provider = make_provider().with_base_url("http://localhost:8080".to_string());
+        assert_eq!(provider.url("/v2/chat"), "http://localhost:8080/v2/chat");
+    }
+}
@@ -0,0 +1,12 @@
+//! Cohere LLM provider implementation.
+//!
+//! This module provides the [`CohereProvider`] which implements the
+//! [`LlmProvider`](boternity_core::llm::provider::LlmProvider) trait for
+//! the Cohere v2 Chat API (a native, non-OpenAI request format), including
+//! SSE streaming support.
+
+pub mod client;
+pub mod streaming;
+pub mod types;
+
+pub use client::CohereProvider;
@@ -0,0 +1,189 @@
+//! SSE stream creation and event mapping for the Cohere v2 Chat API.
+//!
+//! Cohere streams a sequence of typed JSON events:
+//! 1. `message-start` -- generation id
+//! 2. Per content item: `content-start` -> N x `content-delta` -> `content-end`
+//! 3. `message-end` -- finish_reason and usage (or an inline error)
+//!
+//! Tool plan, tool call, and citation events are not mapped yet and are
+//! skipped, as are unknown event types.
+
+use std::pin::Pin;
+
+use futures_util::{Stream, StreamExt};
+use secrecy::{ExposeSecret, SecretString};
+
+use boternity_types::llm::{LlmError, StreamEvent};
+
+use super::types::{map_finish_reason, CohereRequest, CohereStreamEvent};
+
+/// Create a streaming SSE connection to the Cohere v2 Chat API.
+///
+/// Returns a `Stream` of [`StreamEvent`]s mapped from Cohere-specific
+/// stream events by [`map_stream_event`].
+///
+/// # Arguments
+///
+/// * `client` - Shared reqwest HTTP client
+/// * `url` - Full API URL (e.g., "https://api.cohere.com/v2/chat")
+/// * `body` - Serialized Cohere request with `stream: true`
+/// * `api_key` - API key wrapped in SecretString
+pub fn create_cohere_stream(
+    client: &reqwest::Client,
+    url: &str,
+    body: CohereRequest,
+    api_key: &SecretString,
+) -> Pin<Box<dyn Stream<Item = Result<StreamEvent, LlmError>> + Send + 'static>> {
+    // Clone owned values for the 'static stream closure
+    let client = client.clone();
+    let url = url.to_string();
+    let api_key_str = api_key.expose_secret().to_string();
+
+    Box::pin(async_stream::try_stream! {
+        let request = client
+            .post(&url)
+            .bearer_auth(&api_key_str)
+            .header("content-type", "application/json")
+            .json(&body);
+
+        let mut es = reqwest_eventsource::EventSource::new(request)
+            .map_err(|e| LlmError::Stream(format!("failed to create event source: {e}")))?;
+
+        while let Some(event) = es.next().await {
+            match event {
+                Ok(reqwest_eventsource::Event::Open) => {
+                    yield StreamEvent::Connected;
+                }
+                Ok(reqwest_eventsource::Event::Message(msg)) => {
+                    let parsed: CohereStreamEvent = serde_json::from_str(&msg.data)
+                        .map_err(|e| LlmError::Deserialization(format!("stream event: {e}")))?;
+                    let finished = matches!(parsed, CohereStreamEvent::MessageEnd { .. });
+
+                    for mapped in map_stream_event(parsed)? {
+                        yield mapped;
+                    }
+
+                    // Cohere does not always close the connection promptly after message-end
+                    if finished {
+                        es.close();
+                        break;
+                    }
+                }
+                Err(reqwest_eventsource::Error::StreamEnded) => {
+                    break;
+                }
+                Err(reqwest_eventsource::Error::InvalidStatusCode(status, response)) => {
+                    let body = response.text().await.unwrap_or_default();
+                    Err(super::client::map_http_error(status.as_u16(), &body))?;
+                }
+                Err(e) => {
+                    Err(LlmError::Stream(e.to_string()))?;
+                }
+            }
+        }
+    })
+}
+
+/// Map one Cohere stream event to zero or more provider-agnostic events.
+///
+/// A `message-end` carrying an `error` (Cohere's mid-stream failure signal)
+/// is surfaced as [`LlmError::Provider`].
+pub fn map_stream_event(event: CohereStreamEvent) -> Result<Vec<StreamEvent>, LlmError> {
+    let events = match event {
+        CohereStreamEvent::ContentStart { index } => vec![StreamEvent::ContentBlockStart {
+            index,
+            content_type: "text".to_string(),
+        }],
+        CohereStreamEvent::ContentDelta { index, delta } => vec![StreamEvent::TextDelta {
+            index,
+            text: delta.message.content.text,
+        }],
+        CohereStreamEvent::ContentEnd { index } => vec![StreamEvent::ContentBlockStop { index }],
+        CohereStreamEvent::MessageEnd { delta } => {
+            if let Some(message) = delta.error {
+                return Err(LlmError::Provider { message });
+            }
+            let mut events = Vec::with_capacity(3);
+            if let Some(usage) = delta.usage {
+                events.push(StreamEvent::Usage(usage.to_usage()));
+            }
+            events.push(StreamEvent::MessageDelta {
+                stop_reason: map_finish_reason(delta.finish_reason.as_deref()),
+            });
+            events.push(StreamEvent::Done);
+            events
+        }
+        CohereStreamEvent::MessageStart { .. } | CohereStreamEvent::Other => Vec::new(),
+    };
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use boternity_types::llm::StopReason;
+
+    fn parse(json: &str) -> CohereStreamEvent {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_content_events_map_to_text_block() {
+        let start = map_stream_event(parse(
+            r#"{"type": "content-start", "index": 0, "delta": {"message": {"content": {"type": "text", "text": ""}}}}"#,
+        ))
+        .unwrap();
+        assert!(matches!(
+            &start[..],
+            [StreamEvent::ContentBlockStart { index: 0, content_type }] if content_type == "text"
+        ));
+
+        let delta = map_stream_event(parse(
+            r#"{"type": "content-delta", "index": 0, "delta": {"message": {"content": {"text": "Hello"}}}}"#,
+        ))
+        .unwrap();
+        assert!(matches!(
+            &delta[..],
+            [StreamEvent::TextDelta { index: 0, text }] if text == "Hello"
+        ));
+
+        let end = map_stream_event(parse(r#"{"type": "content-end", "index": 0}"#)).unwrap();
+        assert!(matches!(&end[..], [StreamEvent::ContentBlockStop { index: 0 }]));
+    }
+
+    #[test]
+    fn test_message_end_yields_usage_stop_reason_and_done() {
+        let events = map_stream_event(parse(
+            r#"{"type": "message-end", "delta": {"finish_reason": "COMPLETE", "usage": {"tokens": {"input_tokens": 12, "output_tokens": 34}}}}"#,
+        ))
+        .unwrap();
+
+        assert_eq!(events.len(), 3);
+        assert!(matches!(&events[0], StreamEvent::Usage(u) if u.input_tokens == 12 && u.output_tokens == 34));
+        assert!(matches!(
+            &events[1],
+            StreamEvent::MessageDelta { stop_reason: StopReason::EndTurn }
+        ));
+        assert!(matches!(&events[2], StreamEvent::Done));
+    }
+
+    #[test]
+    fn test_message_end_error_maps_to_provider_error() {
+        let result = map_stream_event(parse(
+            r#"{"type": "message-end", "delta": {"finish_reason": "ERROR", "error": "internal failure"}}"#,
+        ));
+        assert!(matches!(result, Err(LlmError::Provider { message }) if message == "internal failure"));
+    }
+
+    #[test]
+    fn test_start_and_unknown_events_are_skipped() {
+        let start = map_stream_event(parse(
+            r#"{"type": "message-start", "id": "abc", "delta": {"message": {"role": "assistant"}}}"#,
+        ))
+        .unwrap();
+        assert!(start.is_empty());
+
+        let citation = map_stream_event(parse(r#"{"type": "citation-start", "index": 0}"#)).unwrap();
+        assert!(citation.is_empty());
+    }
+}
@@ -0,0 +1,282 @@
+//! Cohere v2 Chat API types.
+//!
+//! These are Cohere-specific request/response structures used for HTTP
+//! communication with the Cohere `/v2/chat` endpoint. They are NOT the
+//! generic LLM types from boternity-types -- those are provider-agnostic.
+
+use serde::{Deserialize, Serialize};
+
+use boternity_types::llm::{StopReason, Usage};
+
+/// Request body for the Cohere v2 Chat API.
+#[derive(Debug, Clone, Serialize)]
+pub struct CohereRequest {
+    pub model: String,
+    pub messages: Vec<CohereMessage>,
+    pub stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_sequences: Option<Vec<String>>,
+}
+
+/// A single message in a Cohere conversation.
+///
+/// Unlike Anthropic, Cohere carries the system prompt as a `system` role
+/// message at the start of `messages`.
+#[derive(Debug, Clone, Serialize)]
+pub struct CohereMessage {
+    pub role: String,
+    pub content: String,
+}
+
+/// Non-streaming response from the Cohere v2 Chat API.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CohereChatResponse {
+    pub id: String,
+    pub finish_reason: Option<String>,
+    pub message: CohereResponseMessage,
+    #[serde(default)]
+    pub usage: Option<CohereUsage>,
+}
+
+/// The assistant message inside a chat response.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CohereResponseMessage {
+    #[serde(default)]
+    pub content: Vec<CohereContent>,
+}
+
+/// A content item in an assistant message.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type")]
+pub enum CohereContent {
+    #[serde(rename = "text")]
+    Text { text: String },
+    /// Forward-compatible catch-all for content types we don't consume.
+    #[serde(other)]
+    Other,
+}
+
+/// Token usage from Cohere.
+///
+/// `tokens` is the raw token count; `billed_units` is what the account is
+/// charged for. Cohere reports both as JSON numbers that may be floats.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CohereUsage {
+    pub billed_units: Option<CohereTokenCounts>,
+    pub tokens: Option<CohereTokenCounts>,
+}
+
+/// Input/output token counts.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CohereTokenCounts {
+    #[serde(default)]
+    pub input_tokens: f64,
+    #[serde(default)]
+    pub output_tokens: f64,
+}
+
+impl CohereUsage {
+    /// Convert to provider-agnostic [`Usage`], preferring raw token counts
+    /// over billed units when both are present.
+    pub fn to_usage(&self) -> Usage {
+        let counts = self
+            .tokens
+            .as_ref()
+            .or(self.billed_units.as_ref())
+            .cloned()
+            .unwrap_or_default();
+        Usage {
+            input_tokens: counts.input_tokens as u32,
+            output_tokens: counts.output_tokens as u32,
+            cache_creation_input_tokens: None,
+            cache_read_input_tokens: None,
+        }
+    }
+}
+
+/// Map a Cohere `finish_reason` to a [`StopReason`].
+pub fn map_finish_reason(reason: Option<&str>) -> StopReason {
+    match reason {
+        Some("MAX_TOKENS") => StopReason::MaxTokens,
+        Some("STOP_SEQUENCE") => StopReason::StopSequence,
+        Some("TOOL_CALL") => StopReason::ToolUse,
+        _ => StopReason::EndTurn,
+    }
+}
+
+/// Error body returned by the Cohere API on non-2xx responses.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CohereErrorBody {
+    pub message: String,
+}
+
+// ---------------------------------------------------------------------------
+// SSE event payloads
+//
+// Every Cohere v2 stream event carries a `type` field in its JSON data
+// (e.g., "content-delta", "message-end"), so a tagged enum is used here --
+// unlike the Anthropic stream, which dispatches on the SSE `event:` name.
+// ---------------------------------------------------------------------------
+
+/// A single event from the Cohere v2 chat stream.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type")]
+pub enum CohereStreamEvent {
+    #[serde(rename = "message-start")]
+    MessageStart {
+        #[serde(default)]
+        id: Option<String>,
+    },
+    #[serde(rename = "content-start")]
+    ContentStart { index: u32 },
+    #[serde(rename = "content-delta")]
+    ContentDelta { index: u32, delta: ContentDeltaObj },
+    #[serde(rename = "content-end")]
+    ContentEnd { index: u32 },
+    #[serde(rename = "message-end")]
+    MessageEnd { delta: MessageEndDelta },
+    /// Tool plans, tool calls, citations, and any future event types.
+    #[serde(other)]
+    Other,
+}
+
+/// The `delta` object of a `content-delta` event.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ContentDeltaObj {
+    pub message: ContentDeltaMessage,
+}
+
+/// The `delta.message` object of a `content-delta` event.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ContentDeltaMessage {
+    pub content: ContentDeltaText,
+}
+
+/// The `delta.message.content` object of a `content-delta` event.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ContentDeltaText {
+    #[serde(default)]
+    pub text: String,
+}
+
+/// The `delta` object of a `message-end` event.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MessageEndDelta {
+    pub finish_reason: Option<String>,
+    #[serde(default)]
+    pub usage: Option<CohereUsage>,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cohere_request_serialization() {
+        let req = CohereRequest {
+            model: "command-r-plus".to_string(),
+            messages: vec![CohereMessage {
+                role: "user".to_string(),
+                content: "Hello".to_string(),
+            }],
+            stream: false,
+            max_tokens: Some(512),
+            temperature: None,
+            stop_sequences: None,
+        };
+
+        let json = serde_json::to_value(&req).unwrap();
+        assert_eq!(json["model"], "command-r-plus");
+        assert_eq!(json["max_tokens"], 512);
+        assert_eq!(json["messages"][0]["role"], "user");
+        assert!(json.get("temperature").is_none());
+        assert!(json.get("stop_sequences").is_none());
+    }
+
+    #[test]
+    fn test_chat_response_deserialization() {
+        let json = r#"{
+            "id": "c14c80c3",
+            "finish_reason": "COMPLETE",
+            "message": {
+                "role": "assistant",
+                "content": [{"type": "text", "text": "Hi there!"}]
+            },
+            "usage": {
+                "billed_units": {"input_tokens": 5, "output_tokens": 3},
+                "tokens": {"input_tokens": 71.0, "output_tokens": 3.0}
+            }
+        }"#;
+        let resp: CohereChatResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(resp.id, "c14c80c3");
+        assert!(matches!(&resp.message.content[0], CohereContent::Text { text } if text == "Hi there!"));
+
+        let usage = resp.usage.unwrap().to_usage();
+        assert_eq!(usage.input_tokens, 71);
+        assert_eq!(usage.output_tokens, 3);
+    }
+
+    #[test]
+    fn test_usage_falls_back_to_billed_units() {
+        let usage: CohereUsage =
+            serde_json::from_str(r#"{"billed_units": {"input_tokens": 9, "output_tokens": 4}}"#)
+                .unwrap();
+        let usage = usage.to_usage();
+        assert_eq!(usage.input_tokens, 9);
+        assert_eq!(usage.output_tokens, 4);
+    }
+
+    #[test]
+    fn test_unknown_content_type_is_tolerated() {
+        let json = r#"{"type": "thinking", "thinking": "hmm"}"#;
+        let content: CohereContent = serde_json::from_str(json).unwrap();
+        assert!(matches!(content, CohereContent::Other));
+    }
+
+    #[test]
+    fn test_finish_reason_mapping() {
+        let cases = vec![
+            (Some("COMPLETE"), StopReason::EndTurn),
+            (Some("MAX_TOKENS"), StopReason::MaxTokens),
+            (Some("STOP_SEQUENCE"), StopReason::StopSequence),
+            (Some("TOOL_CALL"), StopReason::ToolUse),
+            (None, StopReason::EndTurn),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(map_finish_reason(input), expected);
+        }
+    }
+
+    #[test]
+    fn test_stream_event_deserialization() {
+        let delta = r#"{"type": "content-delta", "index": 0, "delta": {"message": {"content": {"text": "Hel"}}}}"#;
+        match serde_json::from_str::<CohereStreamEvent>(delta).unwrap() {
+            CohereStreamEvent::ContentDelta { index, delta } => {
+                assert_eq!(index, 0);
+                assert_eq!(delta.message.content.text, "Hel");
+            }
+            other => panic!("expected ContentDelta, got {other:?}"),
+        }
+
+        let end = r#"{"type": "message-end", "delta": {"finish_reason": "MAX_TOKENS", "usage": {"tokens": {"input_tokens": 10, "output_tokens": 20}}}}"#;
+        match serde_json::from_str::<CohereStreamEvent>(end).unwrap() {
+            CohereStreamEvent::MessageEnd { delta } => {
+                assert_eq!(delta.finish_reason.as_deref(), Some("MAX_TOKENS"));
+                assert_eq!(delta.usage.unwrap().to_usage().output_tokens, 20);
+            }
+            other => panic!("expected MessageEnd, got {other:?}"),
+        }
+
+        let plan = r#"{"type": "tool-plan-delta", "delta": {"message": {"tool_plan": "..."}}}"#;
+        assert!(matches!(
+            serde_json::from_str::<CohereStreamEvent>(plan).unwrap(),
+            CohereStreamEvent::Other
+        ));
+    }
+}
@@ -1,28 +1,36 @@
 //! LLM provider implementations.
 //!
 //! Contains concrete implementations of the [`LlmProvider`] trait
-//! defined in `boternity-core`, starting with Anthropic Claude.
+//! defined in `boternity-core`: Anthropic Claude, AWS Bedrock, Cohere,
+//! OpenAI-compatible APIs, and the Claude subscription proxy.
 //!
 //! Also provides a provider factory ([`create_provider`]) that constructs
 //! the right provider from a [`ProviderConfig`], and a connection test
-//! function ([`test_provider_connection`]) for verifying provider connectivity.
+//! function ([`test_provider_connection`]) for verifying provider connectivity
+//! and measuring latency.
 
 pub mod anthropic;
 pub mod bedrock;
 pub mod claude_sub;
+pub mod cohere;
 pub mod openai_compat;
 pub mod pricing;
 
+use std::time::Instant;
+
+use futures_util::StreamExt;
 use secrecy::SecretString;
 
 use boternity_core::llm::box_provider::BoxLlmProvider;
 use boternity_types::llm::{
-    CompletionRequest, LlmError, Message, MessageRole, ProviderConfig, ProviderType,
+    CompletionRequest, LlmError, Message, ProviderConfig, ProviderTestReport,
+    ProviderType, StreamEvent,
 };
 
 use self::anthropic::AnthropicProvider;
 use self::bedrock::BedrockProvider;
 use self::claude_sub::ClaudeSubscriptionProvider;
+use self::cohere::CohereProvider;
 use self::openai_compat::OpenAiCompatibleProvider;
 
 /// Create a [`BoxLlmProvider`] from a [`ProviderConfig`].
@@ -50,38 +58,84 @@ pub fn create_provider(config: &ProviderConfig, api_key: Option<&str>) -> Result
             let key = api_key.ok_or_else(|| LlmError::AuthenticationFailed)?;
             let region = std::env::var("AWS_REGION").unwrap_or_else(|_| "us-east-1".to_string());
             let secret = SecretString::from(key.to_string());
-            let provider = BedrockProvider::new(secret, config.model.clone(), region);
+            let provider = BedrockProvider::try_new(secret, config.model.clone(), region)?;
             Ok(BoxLlmProvider::new(provider))
         }
+        ProviderType::OpenAiCompatible if config.name == "azure" => {
+            let key = api_key.ok_or_else(|| LlmError::AuthenticationFailed)?;
+
+            // Resource comes from base_url (name or full endpoint) or the environment;
+            // the model field names the Azure deployment.
+            let resource = config
+                .base_url
+                .clone()
+                .or_else(|| std::env::var("AZURE_OPENAI_RESOURCE").ok())
+                .ok_or_else(|| {
+                    LlmError::InvalidRequest(
+                        "Azure OpenAI requires base_url or AZURE_OPENAI_RESOURCE".to_string(),
+                    )
+                })?;
+            let api_version = std::env::var("AZURE_OPENAI_API_VERSION")
+                .unwrap_or_else(|_| openai_compat::config::AZURE_DEFAULT_API_VERSION.to_string());
+
+            let mut oai_config = openai_compat::config::azure_defaults(
+                &resource,
+                &config.model,
+                &api_version,
+                key,
+            );
+            // User-configured headers/body extend (and override) the factory defaults
+            oai_config.extra_headers.extend(config.extra_headers.clone());
+            if !config.extra_body.is_null() {
+                oai_config.extra_body = config.extra_body.clone();
+            }
+            Ok(BoxLlmProvider::new(OpenAiCompatibleProvider::new(oai_config)))
+        }
         ProviderType::OpenAiCompatible => {
             let key = api_key.ok_or_else(|| LlmError::AuthenticationFailed)?;
 
             // Use base_url if specified, otherwise infer from provider name
-            let provider = match config.base_url.as_deref() {
-                Some(base_url) => {
-                    let oai_config = openai_compat::config::OpenAiCompatConfig {
-                        provider_name: config.name.clone(),
-                        base_url: base_url.to_string(),
-                        api_key: key.to_string(),
-                        model: config.model.clone(),
-                        capabilities: config.capabilities.clone(),
-                    };
-                    OpenAiCompatibleProvider::new(oai_config)
-                }
+            let mut oai_config = match config.base_url.as_deref() {
+                Some(base_url) => openai_compat::config::OpenAiCompatConfig {
+                    provider_name: config.name.clone(),
+                    base_url: base_url.to_string(),
+                    api_key: key.to_string(),
+                    model: config.model.clone(),
+                    capabilities: config.capabilities.clone(),
+                    azure: None,
+                    extra_headers: Default::default(),
+                    extra_body: Default::default(),
+                },
                 None => {
                     // Infer from provider name for well-known providers
+                    use openai_compat::config as defaults;
                     match config.name.as_str() {
-                        "openai" => OpenAiCompatibleProvider::openai(key, &config.model),
-                        "gemini" => OpenAiCompatibleProvider::gemini(key, &config.model),
-                        "mistral" => OpenAiCompatibleProvider::mistral(key, &config.model),
-                        "glm" => OpenAiCompatibleProvider::glm(key, &config.model),
+                        "openai" => defaults::openai_defaults(key, &config.model),
+                        "gemini" => defaults::gemini_defaults(key, &config.model),
+                        "mistral" => defaults::mistral_defaults(key, &config.model),
+                        "glm" => defaults::glm_defaults(key, &config.model),
+                        "openrouter" => defaults::openrouter_defaults(key, &config.model),
                         _ => {
                             // Default to OpenAI base URL for unknown providers
-                            OpenAiCompatibleProvider::openai(key, &config.model)
+                            defaults::openai_defaults(key, &config.model)
                         }
                     }
                 }
             };
+            // User-configured headers/body extend (and override) the factory defaults
+            oai_config.extra_headers.extend(config.extra_headers.clone());
+            if !config.extra_body.is_null() {
+                oai_config.extra_body = config.extra_body.clone();
+            }
+            Ok(BoxLlmProvider::new(OpenAiCompatibleProvider::new(oai_config)))
+        }
+        ProviderType::Cohere => {
+            let key = api_key.ok_or_else(|| LlmError::AuthenticationFailed)?;
+            let secret = SecretString::from(key.to_string());
+            let mut provider = CohereProvider::new(secret, config.model.clone());
+            if let Some(base_url) = config.base_url.as_deref() {
+                provider = provider.with_base_url(base_url.to_string());
+            }
             Ok(BoxLlmProvider::new(provider))
         }
         ProviderType::ClaudeSubscription => {
@@ -92,10 +146,13 @@ pub fn create_provider(config: &ProviderConfig, api_key: Option<&str>) -> Result
     }
 }
 
-/// Test provider connectivity by sending a minimal completion request.
+/// Test provider connectivity and measure latency with a minimal request.
 ///
-/// Used when a new provider is configured to verify the API key and endpoint
-/// are working. Sends a tiny "Hello" message with minimal token budget.
+/// Used when a new provider is configured (and by `bnity provider test`) to
+/// verify the API key and endpoint are working. Sends a tiny "Hello" message
+/// with minimal token budget, streaming when the provider advertises it so
+/// first-token latency can be measured. If streaming fails, falls back to a
+/// non-streaming request and records the streaming error in the report.
 ///
 /// # Arguments
 ///
@@ -103,23 +160,70 @@ pub fn create_provider(config: &ProviderConfig, api_key: Option<&str>) -> Result
 ///
 /// # Errors
 ///
-/// Returns the LLM error if the provider fails to respond.
-pub async fn test_provider_connection(provider: &BoxLlmProvider) -> Result<(), LlmError> {
-    let request = CompletionRequest {
-        model: String::new(), // Provider uses its configured default
-        messages: vec![Message {
-            role: MessageRole::User,
-            content: "Hello".to_string(),
-        }],
-        system: None,
-        max_tokens: 10,
-        temperature: Some(0.0),
-        stream: false,
-        stop_sequences: None,
-        output_config: None,
-    };
-    provider.complete(&request).await?;
-    Ok(())
+/// Returns the LLM error if the provider fails to respond at all.
+pub async fn test_provider_connection(
+    provider: &BoxLlmProvider,
+) -> Result<ProviderTestReport, LlmError> {
+    let capabilities = provider.capabilities().clone();
+    let mut streaming_error = None;
+
+    if capabilities.streaming {
+        match measure_stream(provider).await {
+            Ok((first_token_ms, total_ms)) => {
+                return Ok(ProviderTestReport {
+                    provider: provider.name().to_string(),
+                    first_token_ms,
+                    total_ms,
+                    streaming_ok: true,
+                    streaming_error: None,
+                    capabilities,
+                });
+            }
+            Err(e) => streaming_error = Some(e.to_string()),
+        }
+    }
+
+    let start = Instant::now();
+    provider.complete(&connection_test_request(false)).await?;
+
+    Ok(ProviderTestReport {
+        provider: provider.name().to_string(),
+        first_token_ms: None,
+        total_ms: start.elapsed().as_millis() as u64,
+        streaming_ok: false,
+        streaming_error,
+        capabilities,
+    })
+}
+
+/// Stream the connection test request, returning `(first_token_ms, total_ms)`.
+async fn measure_stream(provider: &BoxLlmProvider) -> Result<(Option<u64>, u64), LlmError> {
+    let start = Instant::now();
+    let mut first_token_ms = None;
+
+    let mut stream = provider.stream(connection_test_request(true));
+    while let Some(event) = stream.next().await {
+        match event? {
+            StreamEvent::TextDelta { .. } if first_token_ms.is_none() => {
+                first_token_ms = Some(start.elapsed().as_millis() as u64);
+            }
+            StreamEvent::Done => break,
+            _ => {}
+        }
+    }
+
+    Ok((first_token_ms, start.elapsed().as_millis() as u64))
+}
+
+/// Minimal request used for connection tests.
+fn connection_test_request(stream: bool) -> CompletionRequest {
+    CompletionRequest::builder(String::new()) // Provider uses its configured default
+        .message(Message::from("Hello".to_string()))
+        .max_tokens(10)
+        .temperature(0.0)
+        .stream(stream)
+        .build()
+        .expect("connection test request is valid")
 }
 
 #[cfg(test)]
@@ -149,6 +253,8 @@ mod tests {
             priority: 0,
             enabled: true,
             capabilities: default_caps(),
+            extra_headers: Default::default(),
+            extra_body: Default::default(),
         };
         let provider = create_provider(&config, Some("sk-test-key")).unwrap();
         assert_eq!(provider.name(), "anthropic");
@@ -165,6 +271,8 @@ mod tests {
             priority: 1,
             enabled: true,
             capabilities: default_caps(),
+            extra_headers: Default::default(),
+            extra_body: Default::default(),
         };
         let provider = create_provider(&config, Some("bedrock-api-key-test")).unwrap();
         assert_eq!(provider.name(), "bedrock");
@@ -181,11 +289,31 @@ mod tests {
             priority: 2,
             enabled: true,
             capabilities: default_caps(),
+            extra_headers: Default::default(),
+            extra_body: Default::default(),
         };
         let provider = create_provider(&config, Some("sk-openai-test")).unwrap();
         assert_eq!(provider.name(), "openai");
     }
 
+    #[test]
+    fn test_create_provider_openrouter_by_name() {
+        let config = ProviderConfig {
+            name: "openrouter".to_string(),
+            provider_type: ProviderType::OpenAiCompatible,
+            api_key_secret_name: Some("OPENROUTER_API_KEY".to_string()),
+            base_url: None,
+            model: "anthropic/claude-sonnet-4".to_string(),
+            priority: 2,
+            enabled: true,
+            capabilities: default_caps(),
+            extra_headers: Default::default(),
+            extra_body: Default::default(),
+        };
+        let provider = create_provider(&config, Some("sk-or-test")).unwrap();
+        assert_eq!(provider.name(), "openrouter");
+    }
+
     #[test]
     fn test_create_provider_openai_compatible_with_base_url() {
         let config = ProviderConfig {
@@ -197,6 +325,8 @@ mod tests {
             priority: 3,
             enabled: true,
             capabilities: default_caps(),
+            extra_headers: Default::default(),
+            extra_body: Default::default(),
         };
         let provider = create_provider(&config, Some("custom-key")).unwrap();
         assert_eq!(provider.name(), "custom-provider");
@@ -213,6 +343,8 @@ mod tests {
             priority: 10,
             enabled: true,
             capabilities: default_caps(),
+            extra_headers: Default::default(),
+            extra_body: Default::default(),
         };
         // ClaudeSubscription doesn't need an API key
         let provider = create_provider(&config, None).unwrap();
@@ -230,6 +362,8 @@ mod tests {
             priority: 0,
             enabled: true,
             capabilities: default_caps(),
+            extra_headers: Default::default(),
+            extra_body: Default::default(),
         };
         let result = create_provider(&config, None);
         assert!(result.is_err());
@@ -240,6 +374,63 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_create_provider_cohere() {
+        let config = ProviderConfig {
+            name: "cohere".to_string(),
+            provider_type: ProviderType::Cohere,
+            api_key_secret_name: Some("COHERE_API_KEY".to_string()),
+            base_url: None,
+            model: "command-r-plus".to_string(),
+            priority: 2,
+            enabled: true,
+            capabilities: default_caps(),
+            extra_headers: Default::default(),
+            extra_body: Default::default(),
+        };
+        let provider = create_provider(&config, Some("co-test-key")).unwrap();
+        assert_eq!(provider.name(), "cohere");
+        assert!(create_provider(&config, None).is_err());
+    }
+
+    #[test]
+    fn test_create_provider_azure() {
+        let config = ProviderConfig {
+            name: "azure".to_string(),
+            provider_type: ProviderType::OpenAiCompatible,
+            api_key_secret_name: Some("AZURE_OPENAI_API_KEY".to_string()),
+            base_url: Some("contoso".to_string()),
+            model: "gpt4o-prod".to_string(),
+            priority: 2,
+            enabled: true,
+            capabilities: default_caps(),
+            extra_headers: Default::default(),
+            extra_body: Default::default(),
+        };
+        let provider = create_provider(&config, Some("azure-key")).unwrap();
+        assert_eq!(provider.name(), "azure");
+    }
+
+    #[test]
+    fn test_create_provider_azure_missing_key() {
+        let config = ProviderConfig {
+            name: "azure".to_string(),
+            provider_type: ProviderType::OpenAiCompatible,
+            api_key_secret_name: Some("AZURE_OPENAI_API_KEY".to_string()),
+            base_url: Some("contoso".to_string()),
+            model: "gpt4o-prod".to_string(),
+            priority: 2,
+            enabled: true,
+            capabilities: default_caps(),
+            extra_headers: Default::default(),
+            extra_body: Default::default(),
+        };
+        assert!(matches!(
+            create_provider(&config, None),
+            Err(LlmError::AuthenticationFailed)
+        ));
+    }
+
     #[test]
     fn test_create_provider_gemini_by_name() {
         let config = ProviderConfig {
@@ -251,6 +442,8 @@ mod tests {
             priority: 2,
             enabled: true,
             capabilities: default_caps(),
+            extra_headers: Default::default(),
+            extra_body: Default::default(),
         };
         let provider = create_provider(&config, Some("gemini-key")).unwrap();
         assert_eq!(provider.name(), "gemini");
@@ -267,8 +460,117 @@ mod tests {
             priority: 2,
             enabled: true,
             capabilities: default_caps(),
+            extra_headers: Default::default(),
+            extra_body: Default::default(),
         };
         let provider = create_provider(&config, Some("mistral-key")).unwrap();
         assert_eq!(provider.name(), "mistral");
     }
+
+    // --- Connection test ---
+
+    /// Streams one token after a short delay, or fails streaming when `stream_fails`.
+    struct MockLatencyProvider {
+        caps: ProviderCapabilities,
+        stream_fails: bool,
+    }
+
+    impl boternity_core::llm::provider::LlmProvider for MockLatencyProvider {
+        fn name(&self) -> &str {
+            "mock-latency"
+        }
+
+        fn capabilities(&self) -> &ProviderCapabilities {
+            &self.caps
+        }
+
+        async fn complete(
+            &self,
+            _request: &CompletionRequest,
+        ) -> Result<boternity_types::llm::CompletionResponse, LlmError> {
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+            Ok(boternity_types::llm::CompletionResponse {
+                id: "resp".to_string(),
+                content: "Hi".to_string(),
+                model: "mock".to_string(),
+                stop_reason: boternity_types::llm::StopReason::EndTurn,
+                usage: Default::default(),
+            })
+        }
+
+        fn stream(
+            &self,
+            _request: CompletionRequest,
+        ) -> std::pin::Pin<
+            Box<dyn futures_util::Stream<Item = Result<StreamEvent, LlmError>> + Send + 'static>,
+        > {
+            let fails = self.stream_fails;
+            Box::pin(async_stream::stream! {
+                yield Ok(StreamEvent::Connected);
+                if fails {
+                    yield Err(LlmError::Stream("stream unsupported".to_string()));
+                    return;
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+                yield Ok(StreamEvent::TextDelta { index: 0, text: "Hi".to_string() });
+                tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+                yield Ok(StreamEvent::Done);
+            })
+        }
+
+        async fn count_tokens(
+            &self,
+            _request: &CompletionRequest,
+        ) -> Result<boternity_types::llm::TokenCount, LlmError> {
+            Ok(boternity_types::llm::TokenCount { input_tokens: 1 })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_provider_connection_reports_stream_latency() {
+        let provider = BoxLlmProvider::new(MockLatencyProvider {
+            caps: default_caps(),
+            stream_fails: false,
+        });
+
+        let report = test_provider_connection(&provider).await.unwrap();
+        assert_eq!(report.provider, "mock-latency");
+        assert!(report.streaming_ok);
+        assert!(report.streaming_error.is_none());
+        let first_token_ms = report.first_token_ms.expect("first token latency measured");
+        assert!(first_token_ms >= 5);
+        assert!(report.total_ms >= first_token_ms + 5);
+        assert!(report.capabilities.streaming);
+        assert!(report.capabilities.tool_calling);
+        assert_eq!(report.capabilities.max_context_tokens, 200_000);
+    }
+
+    #[tokio::test]
+    async fn test_provider_connection_falls_back_when_streaming_fails() {
+        let provider = BoxLlmProvider::new(MockLatencyProvider {
+            caps: default_caps(),
+            stream_fails: true,
+        });
+
+        let report = test_provider_connection(&provider).await.unwrap();
+        assert!(!report.streaming_ok);
+        assert!(report.first_token_ms.is_none());
+        assert!(report.streaming_error.unwrap().contains("stream unsupported"));
+        assert!(report.total_ms >= 5);
+    }
+
+    #[tokio::test]
+    async fn test_provider_connection_skips_stream_without_capability() {
+        let mut caps = default_caps();
+        caps.streaming = false;
+        let provider = BoxLlmProvider::new(MockLatencyProvider {
+            caps,
+            stream_fails: false,
+        });
+
+        let report = test_provider_connection(&provider).await.unwrap();
+        assert!(!report.streaming_ok);
+        assert!(report.streaming_error.is_none());
+        assert!(!report.capabilities.streaming);
+    }
 }
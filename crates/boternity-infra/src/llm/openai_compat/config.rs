@@ -22,8 +22,31 @@ pub struct OpenAiCompatConfig {
     pub model: String,
     /// What this provider supports.
     pub capabilities: ProviderCapabilities,
+    /// Azure OpenAI deployment settings. When set, requests use Azure's
+    /// deployment URL scheme, `api-key` header, and `api-version` query param.
+    pub azure: Option<AzureDeployment>,
+    /// Extra HTTP headers sent with every request (e.g., `HTTP-Referer`,
+    /// `X-Title` for OpenRouter).
+    pub extra_headers: HashMap<String, String>,
+    /// Provider-specific JSON object merged into the request body. `Null` disables merging.
+    pub extra_body: serde_json::Value,
 }
 
+/// Azure OpenAI deployment addressing.
+///
+/// Azure routes by deployment rather than model:
+/// `{base_url}/openai/deployments/{deployment}/chat/completions?api-version={api_version}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AzureDeployment {
+    /// Deployment name configured in the Azure portal.
+    pub deployment: String,
+    /// Azure OpenAI REST API version (e.g., "2024-10-21").
+    pub api_version: String,
+}
+
+/// Default Azure OpenAI API version (latest GA as of February 2026).
+pub const AZURE_DEFAULT_API_VERSION: &str = "2024-10-21";
+
 /// OpenAI default configuration.
 ///
 /// Base URL: `https://api.openai.com/v1`
@@ -42,6 +65,9 @@ pub fn openai_defaults(api_key: &str, model: &str) -> OpenAiCompatConfig {
             max_context_tokens: 128_000,
             max_output_tokens: 16_384,
         },
+        azure: None,
+        extra_headers: HashMap::new(),
+        extra_body: serde_json::Value::Null,
     }
 }
 
@@ -63,6 +89,9 @@ pub fn gemini_defaults(api_key: &str, model: &str) -> OpenAiCompatConfig {
             max_context_tokens: 1_000_000,
             max_output_tokens: 65_536,
         },
+        azure: None,
+        extra_headers: HashMap::new(),
+        extra_body: serde_json::Value::Null,
     }
 }
 
@@ -84,6 +113,9 @@ pub fn mistral_defaults(api_key: &str, model: &str) -> OpenAiCompatConfig {
             max_context_tokens: 128_000,
             max_output_tokens: 32_768,
         },
+        azure: None,
+        extra_headers: HashMap::new(),
+        extra_body: serde_json::Value::Null,
     }
 }
 
@@ -105,6 +137,110 @@ pub fn glm_defaults(api_key: &str, model: &str) -> OpenAiCompatConfig {
             max_context_tokens: 200_000,
             max_output_tokens: 128_000,
         },
+        azure: None,
+        extra_headers: HashMap::new(),
+        extra_body: serde_json::Value::Null,
+    }
+}
+
+/// Azure OpenAI configuration for a named deployment.
+///
+/// `resource` is either the Azure resource name (expanded to
+/// `https://{resource}.openai.azure.com`) or a full endpoint URL.
+/// Capabilities: same as OpenAI (streaming, tool calling, vision; 128K context, 16K output).
+pub fn azure_defaults(
+    resource: &str,
+    deployment: &str,
+    api_version: &str,
+    api_key: &str,
+) -> OpenAiCompatConfig {
+    OpenAiCompatConfig {
+        provider_name: "azure".into(),
+        base_url: azure_base_url(resource),
+        api_key: api_key.into(),
+        model: deployment.into(),
+        capabilities: ProviderCapabilities {
+            streaming: true,
+            tool_calling: true,
+            vision: true,
+            extended_thinking: false,
+            max_context_tokens: 128_000,
+            max_output_tokens: 16_384,
+        },
+        azure: Some(AzureDeployment {
+            deployment: deployment.into(),
+            api_version: api_version.into(),
+        }),
+        extra_headers: HashMap::new(),
+        extra_body: serde_json::Value::Null,
+    }
+}
+
+/// Expand an Azure resource name into its endpoint URL.
+///
+/// Full URLs are passed through with any trailing slash removed.
+pub fn azure_base_url(resource: &str) -> String {
+    if resource.starts_with("http://") || resource.starts_with("https://") {
+        resource.trim_end_matches('/').to_string()
+    } else {
+        format!("https://{resource}.openai.azure.com")
+    }
+}
+
+/// Default `HTTP-Referer` attribution header sent to OpenRouter.
+pub const OPENROUTER_REFERER: &str = "https://github.com/mondalsuman/boternity";
+
+/// Default `X-Title` attribution header sent to OpenRouter.
+pub const OPENROUTER_TITLE: &str = "Boternity";
+
+/// OpenRouter default configuration.
+///
+/// Base URL: `https://openrouter.ai/api/v1`
+/// Sends `HTTP-Referer`/`X-Title` attribution headers. Models are addressed as
+/// `{vendor}/{model}` (e.g., "anthropic/claude-sonnet-4"); capabilities are
+/// inferred from the vendor prefix via [`openrouter_capabilities`].
+pub fn openrouter_defaults(api_key: &str, model: &str) -> OpenAiCompatConfig {
+    OpenAiCompatConfig {
+        provider_name: "openrouter".into(),
+        base_url: "https://openrouter.ai/api/v1".into(),
+        api_key: api_key.into(),
+        model: model.into(),
+        capabilities: openrouter_capabilities(model),
+        azure: None,
+        extra_headers: HashMap::from([
+            ("HTTP-Referer".to_string(), OPENROUTER_REFERER.to_string()),
+            ("X-Title".to_string(), OPENROUTER_TITLE.to_string()),
+        ]),
+        extra_body: serde_json::Value::Null,
+    }
+}
+
+/// Infer capabilities for an OpenRouter model from its model family.
+///
+/// OpenRouter proxies hundreds of models; the vendor prefix (and model name
+/// for a few families) is a good-enough proxy for context size and vision
+/// support. Unknown families get conservative defaults.
+pub fn openrouter_capabilities(model: &str) -> ProviderCapabilities {
+    let (vendor, name) = model.split_once('/').unwrap_or(("", model));
+
+    let (vision, extended_thinking, max_context_tokens, max_output_tokens) = match vendor {
+        "anthropic" => (true, true, 200_000, 64_000),
+        "openai" => (true, name.starts_with('o'), 128_000, 16_384),
+        "google" => (true, false, 1_000_000, 65_536),
+        "mistralai" => (name.contains("pixtral"), false, 128_000, 32_768),
+        "meta-llama" => (name.contains("vision"), false, 128_000, 8_192),
+        "deepseek" => (false, name.contains('r'), 64_000, 8_192),
+        "z-ai" => (false, false, 200_000, 128_000),
+        _ => (false, false, 32_768, 4_096),
+    };
+
+    ProviderCapabilities {
+        streaming: true,
+        tool_calling: true,
+        vision,
+        extended_thinking,
+        max_context_tokens,
+        max_output_tokens,
     }
 }
 
@@ -131,6 +267,9 @@ pub fn claude_subscription_defaults(model: &str) -> OpenAiCompatConfig {
             max_context_tokens: 200_000,
             max_output_tokens: 128_000,
         },
+        azure: None,
+        extra_headers: HashMap::new(),
+        extra_body: serde_json::Value::Null,
     }
 }
 
@@ -247,6 +386,61 @@ mod tests {
         assert_eq!(config.capabilities.max_output_tokens, 128_000);
     }
 
+    #[test]
+    fn test_azure_defaults() {
+        let config = azure_defaults("contoso", "gpt4o-prod", "2024-10-21", "azure-key");
+        assert_eq!(config.provider_name, "azure");
+        assert_eq!(config.base_url, "https://contoso.openai.azure.com");
+        assert_eq!(config.model, "gpt4o-prod");
+        assert_eq!(
+            config.azure,
+            Some(AzureDeployment {
+                deployment: "gpt4o-prod".into(),
+                api_version: "2024-10-21".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_azure_base_url_accepts_full_endpoint() {
+        assert_eq!(
+            azure_base_url("https://custom.example.azure.com/"),
+            "https://custom.example.azure.com"
+        );
+        assert_eq!(azure_base_url("myres"), "https://myres.openai.azure.com");
+    }
+
+    #[test]
+    fn test_defaults_have_no_extra_headers_or_body() {
+        let config = openai_defaults("sk-test", "gpt-4o");
+        assert!(config.extra_headers.is_empty());
+        assert!(config.extra_body.is_null());
+    }
+
+    #[test]
+    fn test_openrouter_defaults() {
+        let config = openrouter_defaults("sk-or-test", "anthropic/claude-sonnet-4");
+        assert_eq!(config.provider_name, "openrouter");
+        assert_eq!(config.base_url, "https://openrouter.ai/api/v1");
+        assert_eq!(config.extra_headers["HTTP-Referer"], OPENROUTER_REFERER);
+        assert_eq!(config.extra_headers["X-Title"], OPENROUTER_TITLE);
+        assert!(config.capabilities.vision);
+        assert_eq!(config.capabilities.max_context_tokens, 200_000);
+    }
+
+    #[test]
+    fn test_openrouter_capabilities_by_family() {
+        assert_eq!(openrouter_capabilities("google/gemini-2.5-pro").max_context_tokens, 1_000_000);
+        assert!(openrouter_capabilities("openai/o3-mini").extended_thinking);
+        assert!(!openrouter_capabilities("openai/gpt-4o").extended_thinking);
+        assert!(!openrouter_capabilities("mistralai/mistral-large").vision);
+        assert!(openrouter_capabilities("mistralai/pixtral-large").vision);
+
+        let unknown = openrouter_capabilities("some-lab/new-model");
+        assert!(!unknown.vision);
+        assert_eq!(unknown.max_context_tokens, 32_768);
+    }
+
     #[test]
     fn test_claude_subscription_defaults() {
         let config = claude_subscription_defaults("claude-opus-4-20250514");
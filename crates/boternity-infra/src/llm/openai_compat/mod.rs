@@ -1,8 +1,8 @@
 //! OpenAI-compatible LLM provider implementation.
 //!
-//! A single [`OpenAiCompatibleProvider`] serves OpenAI, Google Gemini,
-//! Mistral, GLM 4.7, and Claude.ai subscription proxy -- four+ providers
-//! from one codebase via configurable base URLs and factory functions.
+//! A single [`OpenAiCompatibleProvider`] serves OpenAI, Azure OpenAI, Google
+//! Gemini, Mistral, GLM 4.7, OpenRouter, and Claude.ai subscription proxy -- five+
+//! providers from one codebase via configurable base URLs and factory functions.
 //!
 //! Uses [`async_openai`] for type-safe request/response handling and
 //! built-in SSE streaming.
@@ -11,22 +11,32 @@ pub mod config;
 pub mod streaming;
 
 use std::pin::Pin;
+use std::sync::Arc;
 
-use async_openai::config::OpenAIConfig;
+use async_openai::config::{AzureConfig, Config, OpenAIConfig};
 use async_openai::types::chat::{
+    ChatCompletionMessageToolCall, ChatCompletionMessageToolCalls,
     ChatCompletionRequestAssistantMessage, ChatCompletionRequestAssistantMessageContent,
-    ChatCompletionRequestMessage, ChatCompletionRequestSystemMessage,
-    ChatCompletionRequestSystemMessageContent, ChatCompletionRequestUserMessage,
-    ChatCompletionRequestUserMessageContent, ChatCompletionStreamOptions,
-    CreateChatCompletionRequest, FinishReason, StopConfiguration,
+    ChatCompletionRequestMessage, ChatCompletionRequestMessageContentPartImage,
+    ChatCompletionRequestMessageContentPartText, ChatCompletionRequestSystemMessage,
+    ChatCompletionRequestSystemMessageContent, ChatCompletionRequestToolMessage,
+    ChatCompletionRequestToolMessageContent, ChatCompletionRequestUserMessage,
+    ChatCompletionRequestUserMessageContent, ChatCompletionRequestUserMessageContentPart,
+    ChatCompletionResponseStream, ChatCompletionStreamOptions, CreateChatCompletionRequest,
+    CreateChatCompletionResponse, FinishReason, FunctionCall, ImageUrl, StopConfiguration,
 };
 use async_openai::Client;
 use futures_util::Stream;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use tokio_util::sync::CancellationToken;
 
+use boternity_core::llm::errors::{
+    classify_error_type, classify_http_status, is_context_length_message, parse_context_length,
+};
 use boternity_core::llm::provider::LlmProvider;
 use boternity_types::llm::{
     CompletionRequest, CompletionResponse, LlmError, MessageRole, ProviderCapabilities,
-    StopReason, StreamEvent, TokenCount, Usage,
+    SamplingPolicy, StopReason, StreamEvent, TokenCount, ToolContent, Usage,
 };
 
 use self::config::OpenAiCompatConfig;
@@ -34,7 +44,8 @@ use self::streaming::map_openai_stream;
 
 /// Unified provider for any OpenAI-compatible API.
 ///
-/// Supports: OpenAI, Google Gemini, Mistral, GLM 4.7, Claude.ai subscription proxy.
+/// Supports: OpenAI, Azure OpenAI, Google Gemini, Mistral, GLM 4.7,
+/// OpenRouter, Claude.ai subscription proxy.
 ///
 /// # API Key Security
 ///
@@ -42,27 +53,75 @@ use self::streaming::map_openai_stream;
 /// stored inside the `async_openai::Client`. Same defense-in-depth pattern
 /// as [`super::anthropic::client::AnthropicProvider`].
 pub struct OpenAiCompatibleProvider {
-    client: Client<OpenAIConfig>,
+    client: Client<Arc<dyn Config>>,
     provider_name: String,
     model: String,
     capabilities: ProviderCapabilities,
+    /// Extra headers installed as defaults on the underlying HTTP client.
+    extra_headers: HeaderMap,
+    /// Provider-specific fields merged into every request body.
+    extra_body: serde_json::Value,
+    /// Whether out-of-range sampling parameters are rejected or clamped.
+    sampling_policy: SamplingPolicy,
 }
 
 impl OpenAiCompatibleProvider {
     /// Create a new OpenAI-compatible provider from a configuration.
     pub fn new(config: OpenAiCompatConfig) -> Self {
-        let openai_config = OpenAIConfig::new()
-            .with_api_key(&config.api_key)
-            .with_api_base(&config.base_url);
+        // Azure uses deployment URLs with an `api-key` header and `api-version`
+        // query param; everything else uses Bearer auth against the base URL.
+        let client_config: Arc<dyn Config> = match &config.azure {
+            Some(azure) => Arc::new(
+                AzureConfig::new()
+                    .with_api_base(&config.base_url)
+                    .with_deployment_id(&azure.deployment)
+                    .with_api_version(&azure.api_version)
+                    .with_api_key(&config.api_key),
+            ),
+            None => Arc::new(
+                OpenAIConfig::new()
+                    .with_api_key(&config.api_key)
+                    .with_api_base(&config.base_url),
+            ),
+        };
+
+        // Gateway-specific headers ride on the HTTP client as defaults so they
+        // accompany both regular and streaming requests.
+        let extra_headers = build_header_map(&config.extra_headers);
+        let mut client = Client::with_config(client_config);
+        if !extra_headers.is_empty() {
+            match reqwest::Client::builder()
+                .default_headers(extra_headers.clone())
+                .build()
+            {
+                Ok(http_client) => client = client.with_http_client(http_client),
+                Err(e) => tracing::warn!(
+                    provider = %config.provider_name,
+                    error = %e,
+                    "failed to build HTTP client with extra headers; sending without them"
+                ),
+            }
+        }
 
         Self {
-            client: Client::with_config(openai_config),
+            client,
             provider_name: config.provider_name,
             model: config.model,
             capabilities: config.capabilities,
+            extra_headers,
+            extra_body: config.extra_body,
+            sampling_policy: SamplingPolicy::default(),
         }
     }
 
+    /// Set how out-of-range `temperature`/`top_p`/penalty values are handled.
+    ///
+    /// Defaults to [`SamplingPolicy::Reject`].
+    pub fn with_sampling_policy(mut self, policy: SamplingPolicy) -> Self {
+        self.sampling_policy = policy;
+        self
+    }
+
     /// Create an OpenAI provider.
     ///
     /// Uses `https://api.openai.com/v1` as the base URL.
@@ -70,6 +129,15 @@ impl OpenAiCompatibleProvider {
         Self::new(config::openai_defaults(api_key, model))
     }
 
+    /// Create an Azure OpenAI provider for a deployment.
+    ///
+    /// `resource` is the Azure resource name (or a full endpoint URL). Requests go to
+    /// `https://{resource}.openai.azure.com/openai/deployments/{deployment}` with the
+    /// `api-version` query param and `api-key` header.
+    pub fn azure(resource: &str, deployment: &str, api_version: &str, api_key: &str) -> Self {
+        Self::new(config::azure_defaults(resource, deployment, api_version, api_key))
+    }
+
     /// Create a Google Gemini provider (OpenAI-compatible beta endpoint).
     ///
     /// Uses `https://generativelanguage.googleapis.com/v1beta/openai` as the base URL.
@@ -91,6 +159,15 @@ impl OpenAiCompatibleProvider {
         Self::new(config::glm_defaults(api_key, model))
     }
 
+    /// Create an OpenRouter provider.
+    ///
+    /// Uses `https://openrouter.ai/api/v1` as the base URL and sends OpenRouter's
+    /// `HTTP-Referer`/`X-Title` attribution headers. `model` uses OpenRouter's
+    /// `{vendor}/{model}` routing form (e.g., "anthropic/claude-sonnet-4").
+    pub fn openrouter(api_key: &str, model: &str) -> Self {
+        Self::new(config::openrouter_defaults(api_key, model))
+    }
+
     /// Create a Claude.ai subscription provider via local proxy.
     ///
     /// **EXPERIMENTAL:** Requires `claude-max-api-proxy` running at `localhost:3456`.
@@ -108,6 +185,8 @@ impl OpenAiCompatibleProvider {
         request: &CompletionRequest,
         stream: bool,
     ) -> Result<CreateChatCompletionRequest, LlmError> {
+        request.check_capabilities(&self.capabilities)?;
+
         let mut messages: Vec<ChatCompletionRequestMessage> = Vec::new();
 
         // System message
@@ -131,29 +210,86 @@ impl OpenAiCompatibleProvider {
                         name: None,
                     },
                 ),
-                MessageRole::User => ChatCompletionRequestMessage::User(
-                    ChatCompletionRequestUserMessage {
-                        content: ChatCompletionRequestUserMessageContent::Text(
-                            msg.content.clone(),
-                        ),
+                MessageRole::User => {
+                    let content = if msg.images.is_empty() {
+                        ChatCompletionRequestUserMessageContent::Text(msg.content.clone())
+                    } else {
+                        // Text first, then each image as an `image_url` part
+                        let mut parts = Vec::with_capacity(msg.images.len() + 1);
+                        if !msg.content.is_empty() {
+                            parts.push(ChatCompletionRequestUserMessageContentPart::Text(
+                                ChatCompletionRequestMessageContentPartText {
+                                    text: msg.content.clone(),
+                                },
+                            ));
+                        }
+                        parts.extend(msg.images.iter().map(|image| {
+                            ChatCompletionRequestUserMessageContentPart::ImageUrl(
+                                ChatCompletionRequestMessageContentPartImage {
+                                    image_url: ImageUrl {
+                                        url: image.to_url(),
+                                        detail: None,
+                                    },
+                                },
+                            )
+                        }));
+                        ChatCompletionRequestUserMessageContent::Array(parts)
+                    };
+                    ChatCompletionRequestMessage::User(ChatCompletionRequestUserMessage {
+                        content,
                         name: None,
-                    },
-                ),
+                    })
+                }
                 MessageRole::Assistant => {
+                    let tool_calls = match &msg.tool {
+                        Some(ToolContent::Calls { calls }) => Some(
+                            calls
+                                .iter()
+                                .map(|call| {
+                                    ChatCompletionMessageToolCalls::Function(
+                                        ChatCompletionMessageToolCall {
+                                            id: call.id.clone(),
+                                            function: FunctionCall {
+                                                name: call.name.clone(),
+                                                arguments: call.input.to_string(),
+                                            },
+                                        },
+                                    )
+                                })
+                                .collect(),
+                        ),
+                        _ => None,
+                    };
+                    // A tool-calling turn may have no text at all
+                    let content = (!msg.content.is_empty() || tool_calls.is_none()).then(|| {
+                        ChatCompletionRequestAssistantMessageContent::Text(msg.content.clone())
+                    });
                     #[allow(deprecated)]
                     ChatCompletionRequestMessage::Assistant(
                         ChatCompletionRequestAssistantMessage {
-                            content: Some(ChatCompletionRequestAssistantMessageContent::Text(
-                                msg.content.clone(),
-                            )),
+                            content,
                             refusal: None,
                             name: None,
                             audio: None,
-                            tool_calls: None,
+                            tool_calls,
                             function_call: None,
                         },
                     )
                 }
+                MessageRole::Tool => {
+                    // OpenAI has no error flag on tool results; the output
+                    // text itself must say the call failed.
+                    let tool_call_id = match &msg.tool {
+                        Some(ToolContent::Result { call_id, .. }) => call_id.clone(),
+                        _ => String::new(),
+                    };
+                    ChatCompletionRequestMessage::Tool(ChatCompletionRequestToolMessage {
+                        content: ChatCompletionRequestToolMessageContent::Text(
+                            msg.content.clone(),
+                        ),
+                        tool_call_id,
+                    })
+                }
             };
             messages.push(oai_msg);
         }
@@ -165,11 +301,16 @@ impl OpenAiCompatibleProvider {
             request.model.clone()
         };
 
+        let sampling = request.sampling_params(self.sampling_policy)?;
+
         let mut req = CreateChatCompletionRequest {
             model,
             messages,
             max_completion_tokens: Some(request.max_tokens),
-            temperature: request.temperature.map(|t| t as f32),
+            temperature: sampling.temperature.map(|t| t as f32),
+            top_p: sampling.top_p.map(|p| p as f32),
+            frequency_penalty: sampling.frequency_penalty.map(|p| p as f32),
+            presence_penalty: sampling.presence_penalty.map(|p| p as f32),
             ..Default::default()
         };
 
@@ -191,6 +332,85 @@ impl OpenAiCompatibleProvider {
 
         Ok(req)
     }
+
+    /// Build the JSON request body, merging any configured `extra_body` fields
+    /// on top of the typed request from [`Self::build_request`].
+    ///
+    /// Top-level keys in `extra_body` override generated fields of the same name.
+    fn build_request_body(
+        &self,
+        request: &CompletionRequest,
+        stream: bool,
+    ) -> Result<serde_json::Value, LlmError> {
+        let req = self.build_request(request, stream)?;
+        let mut body = serde_json::to_value(&req)
+            .map_err(|e| LlmError::InvalidRequest(format!("failed to serialize request: {e}")))?;
+        merge_extra_body(&mut body, &self.extra_body)?;
+        Ok(body)
+    }
+
+    /// Send a non-streaming request, routing through the raw JSON body when
+    /// `extra_body` is configured.
+    async fn send(
+        &self,
+        request: &CompletionRequest,
+    ) -> Result<CreateChatCompletionResponse, LlmError> {
+        if self.extra_body.is_null() {
+            let oai_request = self.build_request(request, false)?;
+            self.client.chat().create(oai_request).await
+        } else {
+            let body = self.build_request_body(request, false)?;
+            self.client.chat().create_byot(body).await
+        }
+        .map_err(map_openai_error)
+    }
+}
+
+/// Outgoing chat request: the typed request, or a raw JSON body when
+/// `extra_body` fields have been merged in.
+enum RequestBody {
+    Typed(CreateChatCompletionRequest),
+    Raw(serde_json::Value),
+}
+
+/// Convert configured extra headers into a [`HeaderMap`].
+///
+/// Entries with invalid header names or values are skipped with a warning.
+fn build_header_map(headers: &std::collections::HashMap<String, String>) -> HeaderMap {
+    let mut map = HeaderMap::new();
+    for (name, value) in headers {
+        match (
+            HeaderName::from_bytes(name.as_bytes()),
+            HeaderValue::from_str(value),
+        ) {
+            (Ok(name), Ok(value)) => {
+                map.insert(name, value);
+            }
+            _ => tracing::warn!(header = %name, "skipping invalid extra header"),
+        }
+    }
+    map
+}
+
+/// Shallow-merge `extra` into the top level of `body`.
+///
+/// `Null` is a no-op; any other non-object value is rejected.
+fn merge_extra_body(body: &mut serde_json::Value, extra: &serde_json::Value) -> Result<(), LlmError> {
+    match extra {
+        serde_json::Value::Null => Ok(()),
+        serde_json::Value::Object(fields) => {
+            let target = body.as_object_mut().ok_or_else(|| {
+                LlmError::InvalidRequest("request body is not a JSON object".to_string())
+            })?;
+            for (key, value) in fields {
+                target.insert(key.clone(), value.clone());
+            }
+            Ok(())
+        }
+        _ => Err(LlmError::InvalidRequest(
+            "extra_body must be a JSON object".to_string(),
+        )),
+    }
 }
 
 // OpenAiCompatibleProvider intentionally does NOT derive Debug to prevent
@@ -207,14 +427,7 @@ impl LlmProvider for OpenAiCompatibleProvider {
     }
 
     async fn complete(&self, request: &CompletionRequest) -> Result<CompletionResponse, LlmError> {
-        let oai_request = self.build_request(request, false)?;
-
-        let response = self
-            .client
-            .chat()
-            .create(oai_request)
-            .await
-            .map_err(map_openai_error)?;
+        let response = self.send(request).await?;
 
         // Extract content from the first choice
         let content = response
@@ -260,10 +473,24 @@ impl LlmProvider for OpenAiCompatibleProvider {
     fn stream(
         &self,
         request: CompletionRequest,
+    ) -> Pin<Box<dyn Stream<Item = Result<StreamEvent, LlmError>> + Send + 'static>> {
+        self.stream_with_cancel(request, CancellationToken::new())
+    }
+
+    fn stream_with_cancel(
+        &self,
+        request: CompletionRequest,
+        cancel: CancellationToken,
     ) -> Pin<Box<dyn Stream<Item = Result<StreamEvent, LlmError>> + Send + 'static>> {
         // Build the request. If it fails, return a stream that immediately errors.
-        let oai_request = match self.build_request(&request, true) {
-            Ok(req) => req,
+        // With `extra_body` configured the merged JSON body is sent as-is.
+        let built = if self.extra_body.is_null() {
+            self.build_request(&request, true).map(RequestBody::Typed)
+        } else {
+            self.build_request_body(&request, true).map(RequestBody::Raw)
+        };
+        let body = match built {
+            Ok(body) => body,
             Err(e) => {
                 return Box::pin(futures_util::stream::once(async move { Err(e) }));
             }
@@ -273,19 +500,39 @@ impl LlmProvider for OpenAiCompatibleProvider {
         let client = self.client.clone();
 
         Box::pin(async_stream::try_stream! {
-            let oai_stream = client
-                .chat()
-                .create_stream(oai_request)
-                .await
-                .map_err(map_openai_error)?;
-
-            let mut inner = map_openai_stream(oai_stream);
-
-            use futures_util::StreamExt;
-            while let Some(event) = inner.next().await {
-                match event {
-                    Ok(ev) => yield ev,
-                    Err(e) => Err(e)?,
+            let connect = async {
+                match body {
+                    RequestBody::Typed(typed) => client.chat().create_stream(typed).await,
+                    RequestBody::Raw(raw) => client.chat().create_stream_byot(raw).await,
+                }
+            };
+            // Cancelling while connecting drops the pending request.
+            let connected = tokio::select! {
+                biased;
+                _ = cancel.cancelled() => None,
+                result = connect => Some(result),
+            };
+            let oai_stream: Option<ChatCompletionResponseStream> =
+                connected.transpose().map_err(map_openai_error)?;
+
+            if let Some(oai_stream) = oai_stream {
+                let mut inner = map_openai_stream(oai_stream);
+
+                use futures_util::StreamExt;
+                loop {
+                    let event = tokio::select! {
+                        biased;
+                        _ = cancel.cancelled() => {
+                            tracing::debug!("OpenAI-compatible stream cancelled");
+                            break;
+                        }
+                        event = inner.next() => event,
+                    };
+                    match event {
+                        Some(Ok(ev)) => yield ev,
+                        Some(Err(e)) => Err(e)?,
+                        None => break,
+                    }
                 }
             }
         })
@@ -319,53 +566,37 @@ fn map_openai_error(err: async_openai::error::OpenAIError) -> LlmError {
 
     match &err {
         OpenAIError::ApiError(api_err) => {
-            // Check for known error types by code or type field
-            let code = api_err.code.as_deref().unwrap_or("");
-            let error_type = api_err.r#type.as_deref().unwrap_or("");
-
-            if code == "authentication_error"
-                || error_type == "authentication_error"
-                || api_err.message.contains("Incorrect API key")
-                || api_err.message.contains("Invalid API key")
-            {
-                LlmError::AuthenticationFailed
-            } else if code == "rate_limit_exceeded" || error_type == "rate_limit_error" {
-                LlmError::RateLimited {
-                    retry_after_ms: None,
-                }
-            } else if code == "context_length_exceeded"
-                || api_err.message.contains("maximum context length")
-            {
-                LlmError::ContextLengthExceeded {
-                    max: 0,
-                    requested: 0,
-                }
-            } else if code == "server_error" || error_type == "overloaded_error" {
-                LlmError::Overloaded(api_err.message.clone())
-            } else {
-                LlmError::Provider {
-                    message: err.to_string(),
-                }
-            }
-        }
-        OpenAIError::Reqwest(reqwest_err) => {
-            if let Some(status) = reqwest_err.status() {
-                match status.as_u16() {
-                    401 => LlmError::AuthenticationFailed,
-                    429 => LlmError::RateLimited {
-                        retry_after_ms: None,
-                    },
-                    529 => LlmError::Overloaded(err.to_string()),
-                    _ => LlmError::Provider {
-                        message: err.to_string(),
-                    },
-                }
-            } else {
-                LlmError::Provider {
-                    message: err.to_string(),
-                }
-            }
+            // Classify by code first, then type, then well-known messages.
+            let message = &api_err.message;
+            api_err
+                .code
+                .as_deref()
+                .and_then(|code| classify_error_type(code, message))
+                .or_else(|| {
+                    api_err
+                        .r#type
+                        .as_deref()
+                        .and_then(|error_type| classify_error_type(error_type, message))
+                })
+                .unwrap_or_else(|| {
+                    if message.contains("Incorrect API key") || message.contains("Invalid API key") {
+                        LlmError::AuthenticationFailed
+                    } else if is_context_length_message(message) {
+                        let (max, requested) = parse_context_length(message);
+                        LlmError::ContextLengthExceeded { max, requested }
+                    } else {
+                        LlmError::Provider {
+                            message: err.to_string(),
+                        }
+                    }
+                })
         }
+        OpenAIError::Reqwest(reqwest_err) => match reqwest_err.status() {
+            Some(status) => classify_http_status(status.as_u16(), &err.to_string()),
+            None => LlmError::Provider {
+                message: err.to_string(),
+            },
+        },
         OpenAIError::JSONDeserialize(_, content) => {
             LlmError::Deserialization(format!("failed to parse response: {content}"))
         }
@@ -394,6 +625,56 @@ mod tests {
         assert_eq!(provider.capabilities().max_output_tokens, 16_384);
     }
 
+    #[test]
+    fn test_azure_factory_url_and_auth_shape() {
+        let provider =
+            OpenAiCompatibleProvider::azure("contoso", "gpt4o-prod", "2024-10-21", "azure-key");
+        assert_eq!(provider.name(), "azure");
+        assert_eq!(provider.model, "gpt4o-prod");
+
+        let cfg = provider.client.config();
+        assert_eq!(
+            cfg.url("/chat/completions"),
+            "https://contoso.openai.azure.com/openai/deployments/gpt4o-prod/chat/completions"
+        );
+        assert_eq!(cfg.query(), vec![("api-version", "2024-10-21")]);
+
+        let headers = cfg.headers();
+        assert_eq!(headers.get("api-key").unwrap(), "azure-key");
+        assert!(headers.get("authorization").is_none());
+    }
+
+    #[test]
+    fn test_openai_factory_uses_bearer_auth() {
+        let provider = OpenAiCompatibleProvider::openai("sk-test", "gpt-4o");
+        let cfg = provider.client.config();
+        assert_eq!(cfg.url("/chat/completions"), "https://api.openai.com/v1/chat/completions");
+        assert!(cfg.query().is_empty());
+        assert_eq!(cfg.headers().get("authorization").unwrap(), "Bearer sk-test");
+    }
+
+    #[test]
+    fn test_build_request_azure_uses_deployment_as_model() {
+        let provider =
+            OpenAiCompatibleProvider::azure("contoso", "gpt4o-prod", "2024-10-21", "azure-key");
+        let request = CompletionRequest {
+            model: String::new(),
+            messages: vec![],
+            system: None,
+            max_tokens: 256,
+            temperature: None,
+            top_p: None,
+            frequency_penalty: None,
+            presence_penalty: None,
+            stream: false,
+            stop_sequences: None,
+            output_config: None,
+        };
+
+        let oai_req = provider.build_request(&request, false).unwrap();
+        assert_eq!(oai_req.model, "gpt4o-prod");
+    }
+
     #[test]
     fn test_gemini_factory() {
         let provider = OpenAiCompatibleProvider::gemini("gemini-key", "gemini-2.5-pro");
@@ -422,6 +703,26 @@ mod tests {
         assert_eq!(provider.capabilities().max_output_tokens, 128_000);
     }
 
+    #[test]
+    fn test_openrouter_factory() {
+        let provider = OpenAiCompatibleProvider::openrouter("sk-or-test", "openai/gpt-4o");
+        assert_eq!(provider.name(), "openrouter");
+        assert_eq!(provider.model, "openai/gpt-4o");
+        assert_eq!(
+            provider.client.config().url("/chat/completions"),
+            "https://openrouter.ai/api/v1/chat/completions"
+        );
+        assert_eq!(
+            provider.extra_headers.get("http-referer").unwrap(),
+            config::OPENROUTER_REFERER
+        );
+        assert_eq!(
+            provider.extra_headers.get("x-title").unwrap(),
+            config::OPENROUTER_TITLE
+        );
+        assert!(provider.capabilities().vision);
+    }
+
     #[test]
     fn test_claude_subscription_factory() {
         let provider = OpenAiCompatibleProvider::claude_subscription("claude-opus-4-20250514");
@@ -441,15 +742,22 @@ mod tests {
                 boternity_types::llm::Message {
                     role: MessageRole::User,
                     content: "Hello".to_string(),
+                    tool: None,
+                    images: Vec::new(),
                 },
                 boternity_types::llm::Message {
                     role: MessageRole::Assistant,
                     content: "Hi there!".to_string(),
+                    tool: None,
+                    images: Vec::new(),
                 },
             ],
             system: Some("Be helpful".to_string()),
             max_tokens: 1024,
             temperature: Some(0.7),
+            top_p: None,
+            frequency_penalty: None,
+            presence_penalty: None,
             stream: false,
             stop_sequences: None,
             output_config: None,
@@ -464,6 +772,108 @@ mod tests {
         assert!(oai_req.stream_options.is_none());
     }
 
+    #[test]
+    fn test_build_request_maps_tool_turns() {
+        use boternity_types::llm::{Message, ToolCall};
+
+        let provider = OpenAiCompatibleProvider::openai("sk-test", "gpt-4o");
+        let request = CompletionRequest {
+            model: "gpt-4o".to_string(),
+            messages: vec![
+                Message {
+                    role: MessageRole::User,
+                    content: "What is 6 * 7?".to_string(),
+                    tool: None,
+                    images: Vec::new(),
+                },
+                Message::tool_calls(
+                    "",
+                    vec![ToolCall {
+                        id: "call_1".to_string(),
+                        name: "calculator".to_string(),
+                        input: serde_json::json!({"expr": "6 * 7"}),
+                    }],
+                ),
+                Message::tool_result("call_1", "42", false),
+            ],
+            system: None,
+            max_tokens: 1024,
+            temperature: None,
+            top_p: None,
+            frequency_penalty: None,
+            presence_penalty: None,
+            stream: false,
+            stop_sequences: None,
+            output_config: None,
+        };
+
+        let oai_req = provider.build_request(&request, false).unwrap();
+        let json = serde_json::to_value(&oai_req.messages).unwrap();
+
+        assert_eq!(json[1]["role"], "assistant");
+        assert!(json[1].get("content").is_none_or(|c| c.is_null()));
+        assert_eq!(json[1]["tool_calls"][0]["id"], "call_1");
+        assert_eq!(json[1]["tool_calls"][0]["type"], "function");
+        assert_eq!(json[1]["tool_calls"][0]["function"]["name"], "calculator");
+        assert_eq!(
+            json[1]["tool_calls"][0]["function"]["arguments"],
+            r#"{"expr":"6 * 7"}"#
+        );
+
+        assert_eq!(json[2]["role"], "tool");
+        assert_eq!(json[2]["tool_call_id"], "call_1");
+        assert_eq!(json[2]["content"], "42");
+    }
+
+    #[test]
+    fn test_build_request_maps_images() {
+        use boternity_types::llm::{ImageSource, Message};
+
+        let mut request = CompletionRequest {
+            model: "gpt-4o".to_string(),
+            messages: vec![
+                Message::from("Describe both.".to_string())
+                    .with_image(ImageSource::Url {
+                        url: "https://example.com/cat.jpg".to_string(),
+                    })
+                    .with_image(ImageSource::Base64 {
+                        media_type: "image/png".to_string(),
+                        data: "iVBORw0KGgo=".to_string(),
+                    }),
+            ],
+            system: None,
+            max_tokens: 1024,
+            temperature: None,
+            top_p: None,
+            frequency_penalty: None,
+            presence_penalty: None,
+            stream: false,
+            stop_sequences: None,
+            output_config: None,
+        };
+
+        let provider = OpenAiCompatibleProvider::openai("sk-test", "gpt-4o");
+        let oai_req = provider.build_request(&request, false).unwrap();
+        let json = serde_json::to_value(&oai_req.messages).unwrap();
+        assert_eq!(
+            json[0]["content"],
+            serde_json::json!([
+                {"type": "text", "text": "Describe both."},
+                {"type": "image_url", "image_url": {"url": "https://example.com/cat.jpg"}},
+                {"type": "image_url", "image_url": {"url": "data:image/png;base64,iVBORw0KGgo="}}
+            ])
+        );
+
+        let mut config = config::openai_defaults("sk-test", "text-only");
+        config.capabilities.vision = false;
+        let provider = OpenAiCompatibleProvider::new(config);
+        request.model = "text-only".to_string();
+        assert!(matches!(
+            provider.build_request(&request, false),
+            Err(LlmError::Unsupported { .. })
+        ));
+    }
+
     #[test]
     fn test_build_request_streaming() {
         let provider = OpenAiCompatibleProvider::openai("sk-test", "gpt-4o");
@@ -472,10 +882,15 @@ mod tests {
             messages: vec![boternity_types::llm::Message {
                 role: MessageRole::User,
                 content: "Hello".to_string(),
+                tool: None,
+                images: Vec::new(),
             }],
             system: None,
             max_tokens: 512,
             temperature: None,
+            top_p: None,
+            frequency_penalty: None,
+            presence_penalty: None,
             stream: true,
             stop_sequences: None,
             output_config: None,
@@ -497,6 +912,9 @@ mod tests {
             system: None,
             max_tokens: 1024,
             temperature: None,
+            top_p: None,
+            frequency_penalty: None,
+            presence_penalty: None,
             stream: false,
             stop_sequences: None,
             output_config: None,
@@ -506,6 +924,44 @@ mod tests {
         assert_eq!(oai_req.model, "gpt-4o");
     }
 
+    #[test]
+    fn test_build_request_passes_sampling_params_through() {
+        let provider = OpenAiCompatibleProvider::openai("sk-test", "gpt-4o");
+        let mut request = simple_request();
+        request.top_p = Some(0.9);
+        request.frequency_penalty = Some(0.5);
+        request.presence_penalty = Some(-0.5);
+
+        let req = provider.build_request(&request, false).unwrap();
+        assert_eq!(req.temperature, Some(0.7));
+        assert_eq!(req.top_p, Some(0.9));
+        assert_eq!(req.frequency_penalty, Some(0.5));
+        assert_eq!(req.presence_penalty, Some(-0.5));
+    }
+
+    #[test]
+    fn test_build_request_rejects_out_of_range_sampling() {
+        let provider = OpenAiCompatibleProvider::openai("sk-test", "gpt-4o");
+        let mut request = simple_request();
+        request.temperature = Some(5.0);
+
+        let err = provider.build_request(&request, false).unwrap_err();
+        assert!(matches!(err, LlmError::InvalidRequest(_)));
+    }
+
+    #[test]
+    fn test_build_request_clamps_sampling_when_configured() {
+        let provider = OpenAiCompatibleProvider::openai("sk-test", "gpt-4o")
+            .with_sampling_policy(SamplingPolicy::Clamp);
+        let mut request = simple_request();
+        request.temperature = Some(5.0);
+        request.presence_penalty = Some(-3.0);
+
+        let req = provider.build_request(&request, false).unwrap();
+        assert_eq!(req.temperature, Some(2.0));
+        assert_eq!(req.presence_penalty, Some(-2.0));
+    }
+
     #[test]
     fn test_build_request_stop_sequences() {
         let provider = OpenAiCompatibleProvider::openai("sk-test", "gpt-4o");
@@ -515,6 +971,9 @@ mod tests {
             system: None,
             max_tokens: 1024,
             temperature: None,
+            top_p: None,
+            frequency_penalty: None,
+            presence_penalty: None,
             stream: false,
             stop_sequences: Some(vec!["STOP".to_string(), "END".to_string()]),
             output_config: None,
@@ -524,6 +983,130 @@ mod tests {
         assert!(oai_req.stop.is_some());
     }
 
+    fn openrouter_config() -> OpenAiCompatConfig {
+        let mut config = config::openai_defaults("sk-or-test", "openai/gpt-4o");
+        config.provider_name = "openrouter".into();
+        config.base_url = "https://openrouter.ai/api/v1".into();
+        config.extra_headers = [
+            ("HTTP-Referer".to_string(), "https://boternity.dev".to_string()),
+            ("X-Title".to_string(), "Boternity".to_string()),
+        ]
+        .into_iter()
+        .collect();
+        config.extra_body = serde_json::json!({
+            "transforms": ["middle-out"],
+            "provider": { "order": ["openai", "azure"] },
+        });
+        config
+    }
+
+    fn simple_request() -> CompletionRequest {
+        CompletionRequest {
+            model: String::new(),
+            messages: vec![boternity_types::llm::Message {
+                role: MessageRole::User,
+                content: "Hello".to_string(),
+                tool: None,
+                images: Vec::new(),
+            }],
+            system: None,
+            max_tokens: 256,
+            temperature: Some(0.7),
+            top_p: None,
+            frequency_penalty: None,
+            presence_penalty: None,
+            stream: false,
+            stop_sequences: None,
+            output_config: None,
+        }
+    }
+
+    #[test]
+    fn test_extra_headers_installed_on_provider() {
+        let provider = OpenAiCompatibleProvider::new(openrouter_config());
+        assert_eq!(
+            provider.extra_headers.get("http-referer").unwrap(),
+            "https://boternity.dev"
+        );
+        assert_eq!(provider.extra_headers.get("x-title").unwrap(), "Boternity");
+        // Auth header still comes from the client config
+        assert_eq!(
+            provider.client.config().headers().get("authorization").unwrap(),
+            "Bearer sk-or-test"
+        );
+    }
+
+    #[test]
+    fn test_invalid_extra_header_is_skipped() {
+        let mut config = config::openai_defaults("sk-test", "gpt-4o");
+        config.extra_headers = [
+            ("bad header".to_string(), "x".to_string()),
+            ("X-Good".to_string(), "ok".to_string()),
+        ]
+        .into_iter()
+        .collect();
+        let provider = OpenAiCompatibleProvider::new(config);
+        assert_eq!(provider.extra_headers.len(), 1);
+        assert_eq!(provider.extra_headers.get("x-good").unwrap(), "ok");
+    }
+
+    #[test]
+    fn test_build_request_body_merges_extra_body() {
+        let provider = OpenAiCompatibleProvider::new(openrouter_config());
+        let body = provider.build_request_body(&simple_request(), true).unwrap();
+
+        assert_eq!(body["model"], "openai/gpt-4o");
+        assert_eq!(body["stream"], true);
+        assert_eq!(body["transforms"], serde_json::json!(["middle-out"]));
+        assert_eq!(body["provider"]["order"], serde_json::json!(["openai", "azure"]));
+        assert_eq!(body["messages"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_build_request_body_extra_body_overrides_fields() {
+        let mut config = config::openai_defaults("sk-test", "gpt-4o");
+        config.extra_body = serde_json::json!({ "max_completion_tokens": 42 });
+        let provider = OpenAiCompatibleProvider::new(config);
+
+        let body = provider.build_request_body(&simple_request(), false).unwrap();
+        assert_eq!(body["max_completion_tokens"], 42);
+    }
+
+    #[test]
+    fn test_build_request_body_without_extra_body_matches_typed_request() {
+        let provider = OpenAiCompatibleProvider::openai("sk-test", "gpt-4o");
+        let body = provider.build_request_body(&simple_request(), false).unwrap();
+        let typed = provider.build_request(&simple_request(), false).unwrap();
+        assert_eq!(body, serde_json::to_value(&typed).unwrap());
+    }
+
+    #[test]
+    fn test_build_request_body_rejects_non_object_extra_body() {
+        let mut config = config::openai_defaults("sk-test", "gpt-4o");
+        config.extra_body = serde_json::json!(["not", "an", "object"]);
+        let provider = OpenAiCompatibleProvider::new(config);
+
+        let err = provider.build_request_body(&simple_request(), false).unwrap_err();
+        assert!(matches!(err, LlmError::InvalidRequest(_)));
+    }
+
+    #[tokio::test]
+    async fn test_stream_with_cancel_before_connect_yields_nothing() {
+        use futures_util::StreamExt;
+        // Unroutable base URL: the request must never be awaited.
+        let mut config = config::openai_defaults("sk-test", "gpt-4o");
+        config.base_url = "http://10.255.255.1:9/v1".to_string();
+        let provider = OpenAiCompatibleProvider::new(config);
+
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        let mut stream = provider.stream_with_cancel(simple_request(), cancel);
+        let next = tokio::time::timeout(std::time::Duration::from_millis(100), stream.next())
+            .await
+            .expect("cancelled stream should end without connecting");
+        assert!(next.is_none());
+    }
+
     #[tokio::test]
     async fn test_count_tokens_estimation() {
         let provider = OpenAiCompatibleProvider::openai("sk-test", "gpt-4o");
@@ -532,10 +1115,15 @@ mod tests {
             messages: vec![boternity_types::llm::Message {
                 role: MessageRole::User,
                 content: "Hello world, how are you doing today?".to_string(),
+                tool: None,
+                images: Vec::new(),
             }],
             system: Some("You are helpful.".to_string()),
             max_tokens: 1024,
             temperature: None,
+            top_p: None,
+            frequency_penalty: None,
+            presence_penalty: None,
             stream: false,
             stop_sequences: None,
             output_config: None,
@@ -574,6 +1162,62 @@ mod tests {
         assert!(matches!(err, LlmError::RateLimited { .. }));
     }
 
+    #[test]
+    fn test_map_openai_error_context_length_populates_sizes() {
+        use async_openai::error::{ApiError, OpenAIError};
+        let api_err = ApiError {
+            message: "This model's maximum context length is 128000 tokens. However, your \
+                      messages resulted in 130512 tokens. Please reduce the length of the messages."
+                .to_string(),
+            r#type: Some("invalid_request_error".to_string()),
+            param: Some("messages".to_string()),
+            code: Some("context_length_exceeded".to_string()),
+        };
+        let err = map_openai_error(OpenAIError::ApiError(api_err));
+        assert!(matches!(
+            err,
+            LlmError::ContextLengthExceeded {
+                max: 128_000,
+                requested: 130_512
+            }
+        ));
+    }
+
+    #[test]
+    fn test_map_openai_error_matches_shared_taxonomy() {
+        use async_openai::error::{ApiError, OpenAIError};
+        use boternity_core::llm::errors::{is_auth, is_retryable};
+
+        // OpenAI error types must classify like the equivalent HTTP status
+        // does for Anthropic, Bedrock, and Cohere.
+        let cases = [
+            ("authentication_error", 401),
+            ("rate_limit_error", 429),
+            ("overloaded_error", 529),
+            ("invalid_request_error", 400),
+        ];
+        for (error_type, status) in cases {
+            let api_err = ApiError {
+                message: "msg".to_string(),
+                r#type: Some(error_type.to_string()),
+                param: None,
+                code: None,
+            };
+            let openai = map_openai_error(OpenAIError::ApiError(api_err));
+            let http = classify_http_status(status, "msg");
+            let cohere = crate::llm::cohere::client::map_http_error(status, "msg");
+            for other in [&http, &cohere] {
+                assert_eq!(
+                    std::mem::discriminant(&openai),
+                    std::mem::discriminant(other),
+                    "{error_type} vs HTTP {status}"
+                );
+            }
+            assert_eq!(is_retryable(&openai), is_retryable(&http));
+            assert_eq!(is_auth(&openai), is_auth(&http));
+        }
+    }
+
     #[test]
     fn test_map_openai_error_invalid_argument() {
         use async_openai::error::OpenAIError;
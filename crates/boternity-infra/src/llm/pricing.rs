@@ -1,8 +1,11 @@
 //! Cost estimation and pricing for LLM providers.
 //!
 //! Provides a hardcoded default pricing table for known models with
-//! user override capability from `config.toml`. Cost estimates are
-//! clearly labeled as approximate (`~$0.12`).
+//! user override capability from `config.toml` and an updatable pricing
+//! table in `pricing.json` (see [`parse_pricing_table`]). Cost estimates
+//! are clearly labeled as approximate (`~$0.12`).
+
+use std::path::{Path, PathBuf};
 
 use boternity_types::config::ProviderPricing;
 
@@ -99,6 +102,21 @@ fn matches_pattern(model: &str, pattern: &str) -> bool {
     model.starts_with(pattern)
 }
 
+/// Whether `model` is in the built-in table for `provider`.
+///
+/// Returns `None` when the provider has no built-in entries (local and
+/// aggregator providers accept arbitrary model names). Bedrock models are
+/// matched by substring so region-prefixed ids are recognized.
+pub fn known_model(provider: &str, model: &str) -> Option<bool> {
+    let defaults = default_pricing_table();
+    let mut entries = defaults.iter().filter(|e| e.provider == provider).peekable();
+    entries.peek()?;
+    Some(entries.any(|e| {
+        matches_pattern(model, e.model_pattern)
+            || (provider == "bedrock" && model.contains(e.model_pattern))
+    }))
+}
+
 /// Estimate the cost of a request in USD.
 ///
 /// Lookup order:
@@ -189,6 +207,180 @@ pub fn format_cost(cost: f64) -> String {
     }
 }
 
+/// Errors from loading or validating a pricing table.
+#[derive(Debug, thiserror::Error)]
+pub enum PricingTableError {
+    /// The source could not be read or fetched.
+    #[error("failed to load pricing from {source_name}: {message}")]
+    Load { source_name: String, message: String },
+
+    /// The content is not a JSON array of pricing entries.
+    #[error("invalid pricing JSON: {0}")]
+    Parse(#[from] serde_json::Error),
+
+    /// The table contains no entries.
+    #[error("pricing table is empty")]
+    Empty,
+
+    /// An entry failed validation.
+    #[error("entry {index} ({provider}/{model}): {reason}")]
+    InvalidEntry {
+        index: usize,
+        provider: String,
+        model: String,
+        reason: String,
+    },
+}
+
+/// Parse and validate a pricing table in JSON form.
+///
+/// The format is an array of entries using the same fields as
+/// `[[provider_pricing]]` in `config.toml`:
+///
+/// ```json
+/// [{ "provider_name": "anthropic", "model_pattern": "claude-sonnet-4",
+///    "input_cost_per_million": 3.0, "output_cost_per_million": 15.0 }]
+/// ```
+///
+/// Missing fields are a parse error. Entries with an empty provider or
+/// pattern, negative or non-finite costs, or a duplicate provider/pattern
+/// pair are rejected.
+pub fn parse_pricing_table(json: &str) -> Result<Vec<ProviderPricing>, PricingTableError> {
+    let entries: Vec<ProviderPricing> = serde_json::from_str(json)?;
+    if entries.is_empty() {
+        return Err(PricingTableError::Empty);
+    }
+
+    for (index, entry) in entries.iter().enumerate() {
+        let invalid = |reason: &str| PricingTableError::InvalidEntry {
+            index,
+            provider: entry.provider_name.clone(),
+            model: entry.model_pattern.clone(),
+            reason: reason.to_string(),
+        };
+
+        if entry.provider_name.trim().is_empty() {
+            return Err(invalid("provider_name is empty"));
+        }
+        if entry.model_pattern.trim().is_empty() {
+            return Err(invalid("model_pattern is empty"));
+        }
+        for (field, cost) in [
+            ("input_cost_per_million", entry.input_cost_per_million),
+            ("output_cost_per_million", entry.output_cost_per_million),
+        ] {
+            if !cost.is_finite() || cost < 0.0 {
+                return Err(invalid(&format!("{field} must be a non-negative number, got {cost}")));
+            }
+        }
+        if entries[..index].iter().any(|prev| {
+            prev.provider_name == entry.provider_name && prev.model_pattern == entry.model_pattern
+        }) {
+            return Err(invalid("duplicate provider/model_pattern"));
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Entries whose provider/model pattern does not overlap any model in the
+/// built-in table.
+///
+/// These are not rejected (the table exists to price models newer than the
+/// built-in list) but are surfaced so typos such as `claude-sonet-4` are
+/// caught before they silently fall through to the fallback price.
+pub fn unknown_models(entries: &[ProviderPricing]) -> Vec<&ProviderPricing> {
+    let defaults = default_pricing_table();
+    entries
+        .iter()
+        .filter(|entry| {
+            !defaults.iter().any(|known| {
+                known.provider == entry.provider_name
+                    && (matches_pattern(known.model_pattern, &entry.model_pattern)
+                        || matches_pattern(&entry.model_pattern, known.model_pattern))
+            })
+        })
+        .collect()
+}
+
+/// The built-in pricing table in config form, for display.
+pub fn default_pricing() -> Vec<ProviderPricing> {
+    default_pricing_table()
+        .into_iter()
+        .map(|entry| ProviderPricing {
+            provider_name: entry.provider.to_string(),
+            model_pattern: entry.model_pattern.to_string(),
+            input_cost_per_million: entry.input_cost_per_million,
+            output_cost_per_million: entry.output_cost_per_million,
+        })
+        .collect()
+}
+
+/// Read a pricing table from an `http(s)://` URL or a local file path.
+///
+/// Returns the raw content; pass it to [`parse_pricing_table`] to validate.
+pub async fn fetch_pricing_source(source: &str) -> Result<String, PricingTableError> {
+    let load_err = |message: String| PricingTableError::Load {
+        source_name: source.to_string(),
+        message,
+    };
+
+    if source.starts_with("http://") || source.starts_with("https://") {
+        let response = reqwest::get(source)
+            .await
+            .map_err(|e| load_err(e.to_string()))?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(load_err(format!("HTTP {status}")));
+        }
+        response.text().await.map_err(|e| load_err(e.to_string()))
+    } else {
+        tokio::fs::read_to_string(source)
+            .await
+            .map_err(|e| load_err(e.to_string()))
+    }
+}
+
+/// Path to the updatable pricing table (`{data_dir}/pricing.json`).
+pub fn pricing_json_path(data_dir: &Path) -> PathBuf {
+    data_dir.join("pricing.json")
+}
+
+/// Load the pricing table saved by `bnity provider pricing update`.
+///
+/// Returns an empty table if the file is missing; logs a warning and
+/// returns an empty table if it no longer validates.
+pub async fn load_pricing_file(data_dir: &Path) -> Vec<ProviderPricing> {
+    let path = pricing_json_path(data_dir);
+    let content = match tokio::fs::read_to_string(&path).await {
+        Ok(content) => content,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Vec::new(),
+        Err(err) => {
+            tracing::warn!("Failed to read {}: {err}, ignoring", path.display());
+            return Vec::new();
+        }
+    };
+
+    match parse_pricing_table(&content) {
+        Ok(entries) => entries,
+        Err(err) => {
+            tracing::warn!("Invalid pricing table {}: {err}, ignoring", path.display());
+            Vec::new()
+        }
+    }
+}
+
+/// Save a validated pricing table to `{data_dir}/pricing.json`.
+pub async fn save_pricing_file(
+    data_dir: &Path,
+    entries: &[ProviderPricing],
+) -> std::io::Result<PathBuf> {
+    let path = pricing_json_path(data_dir);
+    let content = serde_json::to_string_pretty(entries)?;
+    tokio::fs::write(&path, content).await?;
+    Ok(path)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -276,4 +468,99 @@ mod tests {
         let expected = (500_000.0 / 1_000_000.0) * 15.0 + (50_000.0 / 1_000_000.0) * 75.0;
         assert!((cost - expected).abs() < 0.001, "Expected ${expected}, got ${cost}");
     }
+
+    const PRICING_JSON: &str = r#"[
+        {
+            "provider_name": "anthropic",
+            "model_pattern": "claude-sonnet-4",
+            "input_cost_per_million": 2.0,
+            "output_cost_per_million": 10.0
+        },
+        {
+            "provider_name": "openai",
+            "model_pattern": "gpt-5",
+            "input_cost_per_million": 1.25,
+            "output_cost_per_million": 10.0
+        }
+    ]"#;
+
+    #[test]
+    fn parse_pricing_table_and_compute_cost() {
+        let table = parse_pricing_table(PRICING_JSON).unwrap();
+        assert_eq!(table.len(), 2);
+
+        let cost = estimate_cost(1_000_000, 100_000, "claude-sonnet-4-20250514", "anthropic", &table);
+        // $2.00 + $1.00 = $3.00
+        assert!((cost - 3.0).abs() < 0.001, "Expected ~$3.00, got ${cost}");
+    }
+
+    #[test]
+    fn parse_pricing_table_rejects_missing_field() {
+        let json = r#"[{ "provider_name": "anthropic", "model_pattern": "claude-sonnet-4", "input_cost_per_million": 3.0 }]"#;
+        let err = parse_pricing_table(json).unwrap_err();
+        assert!(matches!(err, PricingTableError::Parse(_)));
+        assert!(err.to_string().contains("output_cost_per_million"));
+    }
+
+    #[test]
+    fn parse_pricing_table_rejects_invalid_entries() {
+        let negative = r#"[{ "provider_name": "openai", "model_pattern": "gpt-4o", "input_cost_per_million": -1.0, "output_cost_per_million": 10.0 }]"#;
+        let err = parse_pricing_table(negative).unwrap_err();
+        assert!(matches!(err, PricingTableError::InvalidEntry { index: 0, .. }));
+        assert!(err.to_string().contains("input_cost_per_million"));
+
+        let empty_pattern = r#"[{ "provider_name": "openai", "model_pattern": " ", "input_cost_per_million": 1.0, "output_cost_per_million": 1.0 }]"#;
+        assert!(parse_pricing_table(empty_pattern).unwrap_err().to_string().contains("model_pattern is empty"));
+
+        let duplicate = r#"[
+            { "provider_name": "openai", "model_pattern": "gpt-4o", "input_cost_per_million": 1.0, "output_cost_per_million": 1.0 },
+            { "provider_name": "openai", "model_pattern": "gpt-4o", "input_cost_per_million": 2.0, "output_cost_per_million": 2.0 }
+        ]"#;
+        assert!(matches!(
+            parse_pricing_table(duplicate),
+            Err(PricingTableError::InvalidEntry { index: 1, .. })
+        ));
+
+        assert!(matches!(parse_pricing_table("[]"), Err(PricingTableError::Empty)));
+        assert!(matches!(parse_pricing_table("{}"), Err(PricingTableError::Parse(_))));
+    }
+
+    #[test]
+    fn known_model_matches_builtin_table() {
+        assert_eq!(known_model("anthropic", "claude-sonnet-4-20250514"), Some(true));
+        assert_eq!(known_model("anthropic", "claude-sonet-4"), Some(false));
+        assert_eq!(
+            known_model("bedrock", "eu.anthropic.claude-sonnet-4-20250514-v1:0"),
+            Some(true)
+        );
+        assert_eq!(known_model("ollama", "llama3"), None);
+    }
+
+    #[test]
+    fn unknown_models_flags_entries_outside_builtin_table() {
+        let table = parse_pricing_table(PRICING_JSON).unwrap();
+        let unknown = unknown_models(&table);
+        assert_eq!(unknown.len(), 1);
+        assert_eq!(unknown[0].model_pattern, "gpt-5");
+    }
+
+    #[tokio::test]
+    async fn pricing_file_round_trip() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        assert!(load_pricing_file(tmp.path()).await.is_empty());
+
+        let table = parse_pricing_table(PRICING_JSON).unwrap();
+        let path = save_pricing_file(tmp.path(), &table).await.unwrap();
+        assert_eq!(path, pricing_json_path(tmp.path()));
+
+        let source = fetch_pricing_source(path.to_str().unwrap()).await.unwrap();
+        assert_eq!(parse_pricing_table(&source).unwrap().len(), 2);
+        assert_eq!(load_pricing_file(tmp.path()).await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn fetch_pricing_source_missing_file_errors() {
+        let err = fetch_pricing_source("/nonexistent/pricing.json").await.unwrap_err();
+        assert!(matches!(err, PricingTableError::Load { .. }));
+    }
 }
@@ -0,0 +1,311 @@
+//! Global search across bots, chat sessions, and memories.
+//!
+//! Backs `bnity search "<query>"`. Three sources are queried and merged into
+//! one ranked list of [`SearchHit`]s:
+//!
+//! - Bot names and descriptions (word match, in memory -- the bot list is small)
+//! - Session titles and chat messages (SQLite FTS5, BM25 ranked)
+//! - Bot memories (vector similarity against the query embedding)
+//!
+//! Each source's score is normalized to `0.0..=1.0` before merging.
+
+use std::collections::HashMap;
+
+use boternity_core::chat::repository::ChatRepository;
+use boternity_core::memory::vector::VectorMemoryStore;
+use boternity_types::bot::Bot;
+use boternity_types::error::RepositoryError;
+use boternity_types::search::{SearchHit, SearchSource};
+use uuid::Uuid;
+
+/// Minimum cosine similarity for a memory to count as a hit.
+const MEMORY_MIN_SIMILARITY: f32 = 0.35;
+
+/// Search every source and return hits ordered by score (best first).
+///
+/// `bots` scopes the search (and supplies slugs for display); chat hits for
+/// bots not in the list are dropped. `query_embedding` is the embedded query
+/// used for the memory search. At most `limit` hits are taken per source.
+pub async fn global_search<C: ChatRepository, V: VectorMemoryStore>(
+    bots: &[Bot],
+    chat_repo: &C,
+    vector_memory: &V,
+    query: &str,
+    query_embedding: &[f32],
+    limit: usize,
+) -> Result<Vec<SearchHit>, RepositoryError> {
+    let slugs: HashMap<Uuid, &str> = bots.iter().map(|b| (b.id.0, b.slug.as_str())).collect();
+
+    let mut hits = search_bots(bots, query);
+    hits.truncate(limit);
+
+    for hit in chat_repo.search(query, limit as i64).await? {
+        let Some(slug) = slugs.get(&hit.bot_id) else {
+            continue;
+        };
+        hits.push(SearchHit {
+            source: if hit.message_id.is_some() {
+                SearchSource::Message
+            } else {
+                SearchSource::Session
+            },
+            bot_id: hit.bot_id,
+            bot_slug: slug.to_string(),
+            session_id: Some(hit.session_id),
+            text: hit.snippet,
+            score: bm25_to_score(hit.rank),
+        });
+    }
+
+    let mut memory_hits = Vec::new();
+    for bot in bots {
+        for ranked in vector_memory
+            .search(&bot.id.0, query_embedding, limit, MEMORY_MIN_SIMILARITY)
+            .await?
+        {
+            memory_hits.push(SearchHit {
+                source: SearchSource::Memory,
+                bot_id: bot.id.0,
+                bot_slug: bot.slug.clone(),
+                session_id: ranked.entry.session_id,
+                text: ranked.entry.fact,
+                score: (1.0 - ranked.distance).clamp(0.0, 1.0),
+            });
+        }
+    }
+    memory_hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+    memory_hits.truncate(limit);
+    hits.extend(memory_hits);
+
+    hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+    Ok(hits)
+}
+
+/// Group hits by source, preserving score order within each group.
+pub fn group_by_source(hits: &[SearchHit]) -> Vec<(SearchSource, Vec<&SearchHit>)> {
+    let mut groups: Vec<(SearchSource, Vec<&SearchHit>)> = Vec::new();
+    for hit in hits {
+        match groups.iter_mut().find(|(source, _)| *source == hit.source) {
+            Some((_, group)) => group.push(hit),
+            None => groups.push((hit.source, vec![hit])),
+        }
+    }
+    groups.sort_by_key(|(source, _)| *source);
+    groups
+}
+
+/// Match query words (3+ characters) against bot names and descriptions.
+///
+/// The score is the fraction of query words found.
+fn search_bots(bots: &[Bot], query: &str) -> Vec<SearchHit> {
+    let words: Vec<String> = query
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.chars().count() >= 3)
+        .map(str::to_lowercase)
+        .collect();
+    if words.is_empty() {
+        return Vec::new();
+    }
+
+    let mut hits: Vec<SearchHit> = bots
+        .iter()
+        .filter_map(|bot| {
+            let haystack = format!("{} {}", bot.name, bot.description).to_lowercase();
+            let matched = words.iter().filter(|w| haystack.contains(w.as_str())).count();
+            (matched > 0).then(|| SearchHit {
+                source: SearchSource::Bot,
+                bot_id: bot.id.0,
+                bot_slug: bot.slug.clone(),
+                session_id: None,
+                text: if bot.description.is_empty() {
+                    bot.name.clone()
+                } else {
+                    format!("{}: {}", bot.name, bot.description)
+                },
+                score: matched as f32 / words.len() as f32,
+            })
+        })
+        .collect();
+    hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+    hits
+}
+
+/// Map an FTS5 BM25 rank (negative, lower is better) to `0.0..1.0`.
+fn bm25_to_score(rank: f64) -> f32 {
+    let strength = (-rank).max(0.0);
+    (strength / (1.0 + strength)) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sqlite::bot::SqliteBotRepository;
+    use crate::sqlite::chat::SqliteChatRepository;
+    use crate::sqlite::pool::DatabasePool;
+    use crate::vector::lance::LanceVectorStore;
+    use crate::vector::memory::LanceVectorMemoryStore;
+    use crate::vector::schema::EMBEDDING_DIMENSION;
+    use boternity_core::repository::bot::BotRepository;
+    use boternity_types::bot::{BotCategory, BotId, BotStatus};
+    use boternity_types::chat::{ChatMessage, ChatSession, SessionStatus};
+    use boternity_types::llm::MessageRole;
+    use boternity_types::memory::{MemoryCategory, VectorMemoryEntry};
+    use chrono::Utc;
+
+    /// Bag-of-words embedding: texts sharing words have high cosine similarity.
+    fn keyword_embedding(text: &str) -> Vec<f32> {
+        let mut vec = vec![0.0f32; EMBEDDING_DIMENSION as usize];
+        for word in text.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()) {
+            let hash = word
+                .to_lowercase()
+                .bytes()
+                .fold(7usize, |h, b| h.wrapping_mul(31).wrapping_add(b as usize));
+            vec[hash % vec.len()] += 1.0;
+        }
+        vec
+    }
+
+    fn make_bot(name: &str, description: &str) -> Bot {
+        let now = Utc::now();
+        Bot {
+            id: BotId::new(),
+            slug: boternity_types::bot::slugify(name),
+            name: name.to_string(),
+            description: description.to_string(),
+            status: BotStatus::Active,
+            category: BotCategory::Assistant,
+            tags: vec![],
+            user_id: None,
+            conversation_count: 0,
+            total_tokens_used: 0,
+            version_count: 0,
+            created_at: now,
+            updated_at: now,
+            last_active_at: None,
+            deleted_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_global_search_surfaces_each_source() {
+        let dir = tempfile::tempdir().unwrap();
+        let url = format!("sqlite://{}?mode=rwc", dir.path().join("test.db").display());
+        let pool = DatabasePool::new(&url).await.unwrap();
+        let bot_repo = SqliteBotRepository::new(pool.clone());
+        let chat_repo = SqliteChatRepository::new(pool);
+        let vector_memory = LanceVectorMemoryStore::new(
+            LanceVectorStore::new(dir.path().join("vectors")).await.unwrap(),
+        );
+
+        // Bot description mentions the topic
+        let gardener = make_bot("Gardener", "Helps plan a tomato garden");
+        // Session title, message, and memory live on another bot
+        let helper = make_bot("Helper", "General assistant");
+        for bot in [&gardener, &helper] {
+            bot_repo.create(bot).await.unwrap();
+        }
+
+        let session = ChatSession {
+            id: Uuid::now_v7(),
+            bot_id: helper.id.0,
+            title: Some("Tomato blight questions".to_string()),
+            started_at: Utc::now(),
+            ended_at: None,
+            total_input_tokens: 0,
+            total_output_tokens: 0,
+            total_cost_usd: 0.0,
+            message_count: 0,
+            model: "claude-sonnet-4-20250514".to_string(),
+            status: SessionStatus::Active,
+            summary: None,
+            summarized_messages: 0,
+        };
+        chat_repo.create_session(&session).await.unwrap();
+        chat_repo
+            .save_message(&ChatMessage {
+                id: Uuid::now_v7(),
+                session_id: session.id,
+                role: MessageRole::User,
+                content: "My tomato leaves have brown spots".to_string(),
+                created_at: Utc::now(),
+                input_tokens: None,
+                output_tokens: None,
+                model: None,
+                stop_reason: None,
+                response_ms: None,
+            })
+            .await
+            .unwrap();
+
+        let fact = "User grows cherry tomato plants on the balcony";
+        vector_memory
+            .add(
+                &VectorMemoryEntry {
+                    id: Uuid::now_v7(),
+                    bot_id: helper.id.0,
+                    fact: fact.to_string(),
+                    category: MemoryCategory::Fact,
+                    importance: 3,
+                    session_id: None,
+                    source_memory_id: None,
+                    embedding_model: "keyword".to_string(),
+                    created_at: Utc::now(),
+                    last_accessed_at: None,
+                    access_count: 0,
+                },
+                &keyword_embedding(fact),
+            )
+            .await
+            .unwrap();
+
+        let bots = vec![gardener.clone(), helper.clone()];
+        let query = "tomato";
+        let hits = global_search(
+            &bots,
+            &chat_repo,
+            &vector_memory,
+            query,
+            &keyword_embedding("cherry tomato plants"),
+            10,
+        )
+        .await
+        .unwrap();
+
+        let sources: Vec<SearchSource> = group_by_source(&hits).into_iter().map(|(s, _)| s).collect();
+        assert_eq!(
+            sources,
+            vec![
+                SearchSource::Bot,
+                SearchSource::Session,
+                SearchSource::Message,
+                SearchSource::Memory
+            ]
+        );
+        let bot_hit = hits.iter().find(|h| h.source == SearchSource::Bot).unwrap();
+        assert_eq!(bot_hit.bot_slug, "gardener");
+        let memory_hit = hits.iter().find(|h| h.source == SearchSource::Memory).unwrap();
+        assert_eq!(memory_hit.text, fact);
+        assert!(hits.windows(2).all(|w| w[0].score >= w[1].score));
+        assert!(hits.iter().all(|h| (0.0..=1.0).contains(&h.score)));
+    }
+
+    #[test]
+    fn test_search_bots_scores_by_matched_words() {
+        let bots = vec![
+            make_bot("Chef", "Cooks pasta and soup"),
+            make_bot("Baker", "Bakes bread"),
+        ];
+        let hits = search_bots(&bots, "pasta soup");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].bot_slug, "chef");
+        assert!((hits[0].score - 1.0).abs() < f32::EPSILON);
+        assert!(search_bots(&bots, "a").is_empty());
+    }
+
+    #[test]
+    fn test_bm25_to_score_is_monotonic() {
+        assert_eq!(bm25_to_score(0.0), 0.0);
+        assert!(bm25_to_score(-5.0) > bm25_to_score(-1.0));
+        assert!(bm25_to_score(-100.0) < 1.0);
+    }
+}
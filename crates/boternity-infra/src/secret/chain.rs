@@ -4,11 +4,19 @@
 //! provider implementations. The resulting chain is passed to `SecretService`
 //! in `boternity-core` via the `DynSecretProvider` abstraction.
 //!
-//! Default chain order: `[EnvSecretProvider, KeychainProvider, VaultSecretProvider]`
+//! Default chain order: `[EnvSecretProvider, KeychainProvider, VaultSecretProvider]`.
+//! [`build_ordered_secret_chain`] takes any other precedence, e.g. from the
+//! `secret_providers` list in `config.toml`.
+//!
+//! Every provider in the chain looks up exactly the scope it is asked for.
+//! The scope hierarchy (bot scopes inheriting global keys) is applied once,
+//! across the whole chain, by `SecretService` according to its
+//! `ScopeResolution`, so a bot-scoped key in any provider shadows a global
+//! key in every provider.
 
 use std::sync::Arc;
 
-use boternity_core::repository::secret::DynSecretProvider;
+use boternity_core::repository::secret::{DynSecretProvider, SecretProviderKind};
 
 use crate::keychain::KeychainProvider;
 use crate::secret::env::EnvSecretProvider;
@@ -30,20 +38,127 @@ pub fn build_secret_chain(
     keychain: Option<KeychainProvider>,
     include_env: bool,
 ) -> Vec<DynSecretProvider> {
+    let order: Vec<SecretProviderKind> = SecretProviderKind::DEFAULT_ORDER
+        .into_iter()
+        .filter(|kind| include_env || *kind != SecretProviderKind::Environment)
+        .collect();
+    build_ordered_secret_chain(vault, keychain, &order)
+}
+
+/// Build a secret resolution chain in the given precedence order.
+///
+/// Providers are placed in `order` (first match wins). A provider missing
+/// from `order` is left out, except the vault: it is the only writable
+/// backend, so it is appended last when not listed. Repeated entries and
+/// `Keychain` without a keychain provider are ignored.
+pub fn build_ordered_secret_chain(
+    vault: VaultSecretProvider,
+    keychain: Option<KeychainProvider>,
+    order: &[SecretProviderKind],
+) -> Vec<DynSecretProvider> {
+    let mut vault = Some(vault);
+    let mut keychain = keychain;
+    let mut env = Some(EnvSecretProvider::new());
     let mut chain: Vec<DynSecretProvider> = Vec::new();
 
-    // 1. Environment variables (highest priority)
-    if include_env {
-        chain.push(Arc::new(EnvSecretProvider::new()));
+    for kind in order {
+        let provider: Option<DynSecretProvider> = match kind {
+            SecretProviderKind::Environment => env.take().map(|p| Arc::new(p) as DynSecretProvider),
+            SecretProviderKind::Keychain => {
+                keychain.take().map(|p| Arc::new(p) as DynSecretProvider)
+            }
+            SecretProviderKind::Vault => vault.take().map(|p| Arc::new(p) as DynSecretProvider),
+        };
+        chain.extend(provider);
     }
 
-    // 2. OS keychain (if available)
-    if let Some(kc) = keychain {
-        chain.push(Arc::new(kc));
+    if let Some(vault) = vault {
+        chain.push(Arc::new(vault));
     }
 
-    // 3. Encrypted vault (lowest priority, always available)
-    chain.push(Arc::new(vault));
-
     chain
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use boternity_core::repository::secret::SecretProvider;
+    use boternity_core::service::secret::SecretService;
+    use boternity_types::secret::SecretScope;
+
+    use crate::crypto::vault::VaultCrypto;
+    use crate::sqlite::pool::DatabasePool;
+    use crate::sqlite::secret::SqliteSecretRepository;
+
+    async fn vault_with(key: &str, value: &str) -> VaultSecretProvider {
+        let dir = tempfile::tempdir().unwrap();
+        let url = format!("sqlite://{}?mode=rwc", dir.path().join("test.db").display());
+        std::mem::forget(dir);
+        let pool = DatabasePool::new(&url).await.unwrap();
+        let vault = VaultSecretProvider::new(
+            SqliteSecretRepository::new(pool),
+            VaultCrypto::new(&[7u8; 32]),
+        );
+        vault.set(key, value, &SecretScope::Global).await.unwrap();
+        vault
+    }
+
+    fn kinds(chain: &[DynSecretProvider]) -> Vec<SecretProviderKind> {
+        chain.iter().map(|p| p.kind_boxed()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_env_overrides_vault_and_reordering_changes_winner() {
+        const KEY: &str = "BOTERNITY_TEST_CHAIN_ORDER_KEY";
+        // SAFETY: This var is unique to this test and removed below.
+        unsafe { std::env::set_var(KEY, "env-value") };
+
+        let default_chain = build_secret_chain(vault_with(KEY, "vault-value").await, None, true);
+        let service = SecretService::new(default_chain);
+        let (value, source) = service
+            .get_with_source(KEY, &SecretScope::Global)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(value, "env-value");
+        assert_eq!(source.provider, SecretProviderKind::Environment);
+
+        let vault_first = build_ordered_secret_chain(
+            vault_with(KEY, "vault-value").await,
+            None,
+            &[SecretProviderKind::Vault, SecretProviderKind::Environment],
+        );
+        let service = SecretService::new(vault_first);
+        let (value, source) = service
+            .get_with_source(KEY, &SecretScope::Global)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(value, "vault-value");
+        assert_eq!(source.provider, SecretProviderKind::Vault);
+
+        // SAFETY: As above.
+        unsafe { std::env::remove_var(KEY) };
+    }
+
+    #[tokio::test]
+    async fn test_ordered_chain_omits_unlisted_but_keeps_vault() {
+        let vault = vault_with("KEY", "value").await;
+        let chain = build_ordered_secret_chain(
+            vault,
+            None,
+            &[
+                SecretProviderKind::Keychain,
+                SecretProviderKind::Environment,
+                SecretProviderKind::Environment,
+            ],
+        );
+        assert_eq!(
+            kinds(&chain),
+            vec![SecretProviderKind::Environment, SecretProviderKind::Vault]
+        );
+
+        let chain = build_secret_chain(vault_with("KEY", "value").await, None, false);
+        assert_eq!(kinds(&chain), vec![SecretProviderKind::Vault]);
+    }
+}
@@ -0,0 +1,202 @@
+//! Dotenv-format file parser for bulk secret import.
+//!
+//! Supports the common subset of the dotenv format:
+//! - `KEY=value` pairs, one per line
+//! - Blank lines and `#` comment lines are skipped
+//! - An optional `export ` prefix (shell-compatible files)
+//! - Single-quoted values (literal, no escapes)
+//! - Double-quoted values (supports `\n`, `\t`, `\"`, `\\` escapes)
+//! - Trailing ` # comment` on unquoted values
+
+/// Errors that can occur while parsing a dotenv file.
+#[derive(Debug, thiserror::Error)]
+pub enum DotenvError {
+    /// A non-blank, non-comment line has no `=` separator.
+    #[error("line {line}: expected KEY=VALUE")]
+    MissingSeparator { line: usize },
+
+    /// The key is empty or contains characters other than `[A-Za-z0-9_.]`.
+    #[error("line {line}: invalid key '{key}'")]
+    InvalidKey { line: usize, key: String },
+
+    /// A quoted value has no closing quote.
+    #[error("line {line}: unterminated quoted value")]
+    UnterminatedQuote { line: usize },
+}
+
+/// Parse dotenv-format content into ordered `(key, value)` pairs.
+///
+/// Later duplicates of the same key are kept as-is; the caller decides
+/// whether the last write wins (as it does when storing into the vault).
+pub fn parse_dotenv(content: &str) -> Result<Vec<(String, String)>, DotenvError> {
+    let mut entries = Vec::new();
+
+    for (idx, raw_line) in content.lines().enumerate() {
+        let line_no = idx + 1;
+        let line = raw_line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let line = line
+            .strip_prefix("export ")
+            .map(str::trim_start)
+            .unwrap_or(line);
+
+        let (key, rest) = line
+            .split_once('=')
+            .ok_or(DotenvError::MissingSeparator { line: line_no })?;
+
+        let key = key.trim();
+        if key.is_empty()
+            || !key
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+        {
+            return Err(DotenvError::InvalidKey {
+                line: line_no,
+                key: key.to_string(),
+            });
+        }
+
+        let value = parse_value(rest.trim(), line_no)?;
+        entries.push((key.to_string(), value));
+    }
+
+    Ok(entries)
+}
+
+/// Parse the right-hand side of a `KEY=value` line.
+fn parse_value(raw: &str, line_no: usize) -> Result<String, DotenvError> {
+    if let Some(inner) = raw.strip_prefix('\'') {
+        let end = inner
+            .find('\'')
+            .ok_or(DotenvError::UnterminatedQuote { line: line_no })?;
+        return Ok(inner[..end].to_string());
+    }
+
+    if let Some(inner) = raw.strip_prefix('"') {
+        let mut value = String::new();
+        let mut chars = inner.chars();
+        loop {
+            match chars.next() {
+                None => return Err(DotenvError::UnterminatedQuote { line: line_no }),
+                Some('"') => return Ok(value),
+                Some('\\') => match chars.next() {
+                    Some('n') => value.push('\n'),
+                    Some('t') => value.push('\t'),
+                    Some('r') => value.push('\r'),
+                    Some(other) => value.push(other),
+                    None => return Err(DotenvError::UnterminatedQuote { line: line_no }),
+                },
+                Some(c) => value.push(c),
+            }
+        }
+    }
+
+    // Unquoted: strip a trailing inline comment (` #` preceded by whitespace)
+    let value = match raw.find(" #") {
+        Some(pos) => &raw[..pos],
+        None => raw,
+    };
+    Ok(value.trim_end().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use boternity_core::repository::secret::SecretProvider;
+    use boternity_types::secret::SecretScope;
+
+    use crate::crypto::vault::VaultCrypto;
+    use crate::secret::VaultSecretProvider;
+    use crate::sqlite::pool::DatabasePool;
+    use crate::sqlite::secret::SqliteSecretRepository;
+
+    const SAMPLE: &str = r#"
+# Provider keys
+ANTHROPIC_API_KEY=sk-ant-123
+
+export OPENAI_API_KEY="sk-openai-456"
+  # indented comment
+GEMINI_KEY='literal $value # not a comment'
+MULTI="line1\nline2"
+PLAIN=abc # trailing comment
+EMPTY=
+"#;
+
+    #[test]
+    fn test_parse_skips_comments_and_blanks() {
+        let entries = parse_dotenv(SAMPLE).unwrap();
+        let keys: Vec<&str> = entries.iter().map(|(k, _)| k.as_str()).collect();
+        assert_eq!(
+            keys,
+            vec![
+                "ANTHROPIC_API_KEY",
+                "OPENAI_API_KEY",
+                "GEMINI_KEY",
+                "MULTI",
+                "PLAIN",
+                "EMPTY"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_quotes_and_export_prefix() {
+        let entries = parse_dotenv(SAMPLE).unwrap();
+        let get = |k: &str| {
+            entries
+                .iter()
+                .find(|(key, _)| key == k)
+                .map(|(_, v)| v.as_str())
+                .unwrap()
+        };
+        assert_eq!(get("ANTHROPIC_API_KEY"), "sk-ant-123");
+        assert_eq!(get("OPENAI_API_KEY"), "sk-openai-456");
+        assert_eq!(get("GEMINI_KEY"), "literal $value # not a comment");
+        assert_eq!(get("MULTI"), "line1\nline2");
+        assert_eq!(get("PLAIN"), "abc");
+        assert_eq!(get("EMPTY"), "");
+    }
+
+    #[test]
+    fn test_parse_missing_separator_errors() {
+        let err = parse_dotenv("GOOD=1\nBAD_LINE\n").unwrap_err();
+        assert!(matches!(err, DotenvError::MissingSeparator { line: 2 }));
+    }
+
+    #[test]
+    fn test_parse_invalid_key_errors() {
+        let err = parse_dotenv("MY KEY=1").unwrap_err();
+        assert!(matches!(err, DotenvError::InvalidKey { line: 1, .. }));
+    }
+
+    #[test]
+    fn test_parse_unterminated_quote_errors() {
+        let err = parse_dotenv("KEY=\"unterminated").unwrap_err();
+        assert!(matches!(err, DotenvError::UnterminatedQuote { line: 1 }));
+    }
+
+    #[tokio::test]
+    async fn test_parsed_values_roundtrip_through_vault() {
+        let dir = tempfile::tempdir().unwrap();
+        let url = format!("sqlite://{}?mode=rwc", dir.path().join("test.db").display());
+        let pool = DatabasePool::new(&url).await.unwrap();
+        let provider = VaultSecretProvider::new(
+            SqliteSecretRepository::new(pool),
+            VaultCrypto::new(&[7u8; 32]),
+        );
+
+        let entries = parse_dotenv(SAMPLE).unwrap();
+        for (key, value) in &entries {
+            provider.set(key, value, &SecretScope::Global).await.unwrap();
+        }
+
+        for (key, value) in &entries {
+            let stored = provider.get(key, &SecretScope::Global).await.unwrap();
+            assert_eq!(stored.as_deref(), Some(value.as_str()), "key {key}");
+        }
+    }
+}
@@ -6,9 +6,14 @@
 //!
 //! Key resolution:
 //! - Global scope: checks `key` directly (e.g., "ANTHROPIC_API_KEY")
-//! - Bot scope: first checks `BOTERNITY_{SLUG}_{KEY}`, then falls back to `key` directly
+//! - Bot scope: checks `BOTERNITY_{SLUG}_{KEY}` only
+//!
+//! Like every provider, this one answers for exactly the requested scope.
+//! Falling back from a bot scope to the global `key` is decided by
+//! `SecretService` (see `ScopeResolution`), so `--strict` lookups are not
+//! answered by a global env var.
 
-use boternity_core::repository::secret::SecretProvider;
+use boternity_core::repository::secret::{SecretProvider, SecretProviderKind};
 use boternity_types::error::RepositoryError;
 use boternity_types::secret::{SecretEntry, SecretScope};
 
@@ -31,26 +36,31 @@ impl Default for EnvSecretProvider {
     }
 }
 
+/// Env var name holding `key` for one bot: `BOTERNITY_{BOT_ID}_{KEY}`.
+fn bot_env_var(bot_id: &str, key: &str) -> String {
+    format!(
+        "BOTERNITY_{}_{}",
+        bot_id.replace('-', "_").to_uppercase(),
+        key
+    )
+}
+
 impl SecretProvider for EnvSecretProvider {
+    fn kind(&self) -> SecretProviderKind {
+        SecretProviderKind::Environment
+    }
+
     async fn get(
         &self,
         key: &str,
         scope: &SecretScope,
     ) -> Result<Option<String>, RepositoryError> {
-        // For bot-scoped secrets, also check BOTERNITY_{SLUG}_{KEY} format
-        if let SecretScope::Bot(bot_id) = scope {
-            let slug_key = format!(
-                "BOTERNITY_{}_{}",
-                bot_id.to_string().replace('-', "_").to_uppercase(),
-                key
-            );
-            if let Ok(val) = std::env::var(&slug_key) {
-                return Ok(Some(val));
-            }
-        }
+        let name = match scope {
+            SecretScope::Global => key.to_string(),
+            SecretScope::Bot(bot_id) => bot_env_var(&bot_id.to_string(), key),
+        };
 
-        // Check the key directly as an env var name
-        match std::env::var(key) {
+        match std::env::var(&name) {
             Ok(val) => Ok(Some(val)),
             Err(std::env::VarError::NotPresent) => Ok(None),
             Err(std::env::VarError::NotUnicode(_)) => {
@@ -128,6 +138,40 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[tokio::test]
+    async fn test_env_provider_bot_scope_reads_only_bot_var() {
+        let bot_id = boternity_types::bot::BotId::new();
+        let scope = SecretScope::Bot(bot_id.clone());
+        let bot_var = bot_env_var(&bot_id.to_string(), "BOTERNITY_TEST_SECRET_2");
+
+        // SAFETY: These vars are unique to this test and removed below.
+        unsafe { std::env::set_var("BOTERNITY_TEST_SECRET_2", "global-value") };
+
+        let provider = EnvSecretProvider::new();
+        let result = provider
+            .get("BOTERNITY_TEST_SECRET_2", &scope)
+            .await
+            .unwrap();
+        assert!(
+            result.is_none(),
+            "global var must not answer a bot-scoped get"
+        );
+
+        // SAFETY: As above.
+        unsafe { std::env::set_var(&bot_var, "bot-value") };
+        let result = provider
+            .get("BOTERNITY_TEST_SECRET_2", &scope)
+            .await
+            .unwrap();
+        assert_eq!(result, Some("bot-value".to_string()));
+
+        // SAFETY: As above.
+        unsafe {
+            std::env::remove_var("BOTERNITY_TEST_SECRET_2");
+            std::env::remove_var(&bot_var);
+        }
+    }
+
     #[tokio::test]
     async fn test_env_provider_set_returns_error() {
         let provider = EnvSecretProvider::new();
@@ -2,12 +2,14 @@
 //!
 //! - `env`: Environment variable provider (read-only, highest priority)
 //! - `chain`: Secret chain builder wiring all providers together
+//! - `dotenv`: Dotenv-format parser for bulk secret import
 //! - `VaultSecretProvider`: Encrypts/decrypts secrets using AES-256-GCM vault + SQLite storage
 
 pub mod chain;
+pub mod dotenv;
 pub mod env;
 
-use boternity_core::repository::secret::SecretProvider;
+use boternity_core::repository::secret::{SecretProvider, SecretProviderKind};
 use boternity_types::error::RepositoryError;
 use boternity_types::secret::{SecretEntry, SecretScope};
 
@@ -54,6 +56,10 @@ fn hex_decode(s: &str) -> Result<Vec<u8>, String> {
 }
 
 impl SecretProvider for VaultSecretProvider {
+    fn kind(&self) -> SecretProviderKind {
+        SecretProviderKind::Vault
+    }
+
     async fn get(
         &self,
         key: &str,
@@ -4,29 +4,97 @@
 //! Local skills run with full trust on the host machine via process
 //! spawning. Tool-based local skills must have a `run.sh` or `run.py`
 //! script in their `scripts/` directory.
+//!
+//! Every process runs under [`LocalExecutionLimits`]: a wall-clock timeout,
+//! a cap on captured stdout/stderr, and a virtual memory cap. A breach kills
+//! the process and surfaces as a [`LocalSkillError`].
+//!
+//! [`run_attached_skill`] runs a skill on a bot's behalf (e.g. the chat
+//! `/skill` command) with the capabilities approved when it was attached.
 
-use std::time::Instant;
+use std::path::Path;
+use std::time::{Duration, Instant};
 
 use anyhow::{bail, Context};
 use boternity_core::skill::executor::{SkillExecutionResult, SkillExecutor};
 use boternity_core::skill::permission::CapabilityEnforcer;
-use boternity_types::skill::{Capability, InstalledSkill, SkillSource, SkillType};
-use tokio::io::AsyncWriteExt;
+use boternity_types::skill::{Capability, InstalledSkill, PermissionGrant, SkillSource, SkillType};
+use chrono::Utc;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
+
+use super::skill_store::SkillStore;
 
-/// Timeout for local skill execution (60 seconds).
+/// Default timeout for local skill execution (60 seconds).
 const LOCAL_EXECUTION_TIMEOUT_SECS: u64 = 60;
 
+/// Default cap on captured stdout, and separately on stderr (1 MiB).
+const DEFAULT_MAX_OUTPUT_BYTES: usize = 1024 * 1024;
+
+/// Default virtual memory cap for a local skill process (1 GiB).
+const DEFAULT_MAX_MEMORY_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// Resource limits applied to each local skill process.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalExecutionLimits {
+    /// Wall-clock limit; the process is killed when it expires.
+    pub timeout: Duration,
+    /// Maximum bytes captured from stdout, and separately from stderr.
+    /// Writing more kills the process.
+    pub max_output_bytes: usize,
+    /// Virtual memory cap, applied with `ulimit -v` before the script starts.
+    /// Allocations beyond it fail inside the skill. Best effort: ignored where
+    /// the OS does not support the limit. `None` disables it.
+    pub max_memory_bytes: Option<u64>,
+}
+
+impl Default for LocalExecutionLimits {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(LOCAL_EXECUTION_TIMEOUT_SECS),
+            max_output_bytes: DEFAULT_MAX_OUTPUT_BYTES,
+            max_memory_bytes: Some(DEFAULT_MAX_MEMORY_BYTES),
+        }
+    }
+}
+
+/// A local skill process breached one of its [`LocalExecutionLimits`].
+///
+/// Returned inside the `anyhow::Error` from [`LocalSkillExecutor::execute`];
+/// callers can `downcast_ref` to tell a breach from an ordinary failure.
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum LocalSkillError {
+    #[error("Skill '{skill}' timed out after {timeout_ms}ms and was terminated")]
+    TimedOut { skill: String, timeout_ms: u64 },
+
+    #[error("Skill '{skill}' wrote more than {limit_bytes} bytes to {stream} and was terminated")]
+    OutputLimitExceeded {
+        skill: String,
+        stream: &'static str,
+        limit_bytes: usize,
+    },
+}
+
 /// Local skill executor that runs skills via process spawning.
 ///
 /// Only accepts skills with [`SkillSource::Local`] and enforces the
 /// [`Capability::ExecCommand`] permission before spawning.
 #[derive(Debug, Clone)]
-pub struct LocalSkillExecutor;
+pub struct LocalSkillExecutor {
+    limits: LocalExecutionLimits,
+}
 
 impl LocalSkillExecutor {
-    /// Create a new local skill executor.
+    /// Create a new local skill executor with the default limits.
     pub fn new() -> Self {
-        Self
+        Self {
+            limits: LocalExecutionLimits::default(),
+        }
+    }
+
+    /// Override the resource limits applied to each skill process.
+    pub fn with_limits(mut self, limits: LocalExecutionLimits) -> Self {
+        self.limits = limits;
+        self
     }
 
     /// Find the executable script in the skill's `scripts/` directory.
@@ -111,16 +179,31 @@ impl SkillExecutor for LocalSkillExecutor {
             ("bash", vec![])
         };
 
-        // 6. Spawn the process with input on stdin
+        // 6. Spawn the process with input on stdin. With a memory cap, a
+        // bash wrapper sets the limit and then execs the interpreter.
         let start = Instant::now();
 
-        let mut child = tokio::process::Command::new(interpreter)
+        let mut command = match self.limits.max_memory_bytes {
+            Some(max_memory_bytes) => {
+                let mut command = tokio::process::Command::new("bash");
+                command
+                    .arg("-c")
+                    .arg(r#"ulimit -v "$1" 2>/dev/null; shift; exec "$@""#)
+                    .arg("bnity-skill")
+                    .arg((max_memory_bytes / 1024).to_string())
+                    .arg(interpreter);
+                command
+            }
+            None => tokio::process::Command::new(interpreter),
+        };
+        let mut child = command
             .args(&args)
             .arg(&script_path)
             .stdin(std::process::Stdio::piped())
             .stdout(std::process::Stdio::piped())
             .stderr(std::process::Stdio::piped())
             .current_dir(&skill.install_path)
+            .kill_on_drop(true)
             .spawn()
             .with_context(|| {
                 format!(
@@ -136,29 +219,60 @@ impl SkillExecutor for LocalSkillExecutor {
             // Drop stdin to close the pipe and signal EOF
         }
 
-        // 7. Wait with timeout
-        let output = tokio::time::timeout(
-            std::time::Duration::from_secs(LOCAL_EXECUTION_TIMEOUT_SECS),
-            child.wait_with_output(),
-        )
-        .await
-        .context("Local skill execution timed out (60s limit)")?
-        .context("Failed to wait for skill process")?;
+        // 7. Capture output under the size cap and wait under the timeout.
+        // Any breach kills the process before returning.
+        let name = &skill.manifest.name;
+        let limit = self.limits.max_output_bytes;
+        let stdout_pipe = child
+            .stdout
+            .take()
+            .context("Skill stdout was not captured")?;
+        let stderr_pipe = child
+            .stderr
+            .take()
+            .context("Skill stderr was not captured")?;
+        let run = async {
+            let (stdout, stderr) = tokio::try_join!(
+                read_capped(stdout_pipe, limit, name, "stdout"),
+                read_capped(stderr_pipe, limit, name, "stderr"),
+            )?;
+            let status = child
+                .wait()
+                .await
+                .context("Failed to wait for skill process")?;
+            anyhow::Ok((status, stdout, stderr))
+        };
+
+        let (status, stdout, stderr) = match tokio::time::timeout(self.limits.timeout, run).await {
+            Ok(Ok(captured)) => captured,
+            Ok(Err(e)) => {
+                let _ = child.kill().await;
+                return Err(e);
+            }
+            Err(_) => {
+                let _ = child.kill().await;
+                return Err(LocalSkillError::TimedOut {
+                    skill: name.clone(),
+                    timeout_ms: self.limits.timeout.as_millis() as u64,
+                }
+                .into());
+            }
+        };
 
         let duration = start.elapsed();
 
         // 8. Check exit status
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
+        if !status.success() {
+            let stderr = String::from_utf8_lossy(&stderr);
             bail!(
                 "Skill '{}' exited with status {}: {}",
                 skill.manifest.name,
-                output.status,
+                status,
                 stderr.trim()
             );
         }
 
-        let stdout = String::from_utf8(output.stdout)
+        let stdout = String::from_utf8(stdout)
             .context("Skill output is not valid UTF-8")?;
 
         Ok(SkillExecutionResult {
@@ -170,6 +284,78 @@ impl SkillExecutor for LocalSkillExecutor {
     }
 }
 
+/// Read `reader` to EOF, failing with [`LocalSkillError::OutputLimitExceeded`]
+/// as soon as more than `limit` bytes arrive.
+async fn read_capped(
+    reader: impl AsyncRead + Unpin,
+    limit: usize,
+    skill: &str,
+    stream: &'static str,
+) -> anyhow::Result<Vec<u8>> {
+    let mut buf = Vec::new();
+    reader
+        .take(limit as u64 + 1)
+        .read_to_end(&mut buf)
+        .await
+        .with_context(|| format!("Failed to read skill {stream}"))?;
+    if buf.len() > limit {
+        return Err(LocalSkillError::OutputLimitExceeded {
+            skill: skill.to_string(),
+            stream,
+            limit_bytes: limit,
+        }
+        .into());
+    }
+    Ok(buf)
+}
+
+// ---------------------------------------------------------------------------
+// Bot invocation
+// ---------------------------------------------------------------------------
+
+/// Run installed skill `name` for the bot whose directory is `bot_dir`.
+///
+/// The skill must be installed in `store` and attached (and enabled) in the
+/// bot's `skills.toml`; the capabilities recorded there at attach time are
+/// the grants. `input` is passed to the skill as-is.
+pub async fn run_attached_skill(
+    store: &SkillStore,
+    bot_dir: &Path,
+    name: &str,
+    input: &str,
+    executor: &impl SkillExecutor,
+) -> anyhow::Result<SkillExecutionResult> {
+    if !store.skill_exists(name) {
+        bail!("Skill '{}' is not installed", name);
+    }
+
+    let config = store.get_bot_skills_config(bot_dir)?;
+    let bot_skill = match config.skills.get(name) {
+        Some(bot_skill) if bot_skill.enabled => bot_skill,
+        Some(_) => bail!("Skill '{}' is disabled for this bot", name),
+        None => bail!("Skill '{}' is not attached to this bot", name),
+    };
+
+    let skill = store.get_skill(name)?;
+
+    let now = Utc::now();
+    let grants: Vec<PermissionGrant> = bot_skill
+        .capabilities
+        .iter()
+        .flatten()
+        .map(|capability| PermissionGrant {
+            skill_name: name.to_string(),
+            capability: capability.clone(),
+            granted: true,
+            granted_at: now,
+        })
+        .collect();
+    let enforcer = CapabilityEnforcer::new(name, &grants)
+        .with_context(|| format!("Skill '{}' has no approved capabilities", name))?;
+
+    executor.execute(&skill, input, &enforcer).await
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -210,6 +396,8 @@ mod tests {
                     parents: None,
                     secrets: None,
                     categories: None,
+                    cacheable: None,
+                    manifest_version: None,
                 }),
                 allowed_tools: None,
             },
@@ -329,6 +517,80 @@ mod tests {
         assert!(exec_result.memory_peak_bytes.is_none());
     }
 
+    /// A local tool skill in `dir` whose `run.sh` is `script`.
+    fn script_skill(dir: &Path, name: &str, script: &str) -> (InstalledSkill, CapabilityEnforcer) {
+        let scripts_dir = dir.join("scripts");
+        std::fs::create_dir_all(&scripts_dir).unwrap();
+        std::fs::write(scripts_dir.join("run.sh"), script).unwrap();
+
+        let skill = make_local_tool_skill(name, dir.to_path_buf());
+        let grants = vec![make_grant(name, Capability::ExecCommand, true)];
+        let enforcer = CapabilityEnforcer::new(name, &grants).unwrap();
+        (skill, enforcer)
+    }
+
+    #[tokio::test]
+    async fn output_over_limit_terminates_skill() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let (skill, enforcer) = script_skill(tmpdir.path(), "flood", "#!/bin/bash\nyes flood\n");
+        let executor = LocalSkillExecutor::new().with_limits(LocalExecutionLimits {
+            max_output_bytes: 4096,
+            ..LocalExecutionLimits::default()
+        });
+
+        let start = Instant::now();
+        let err = executor.execute(&skill, "", &enforcer).await.unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<LocalSkillError>(),
+            Some(&LocalSkillError::OutputLimitExceeded {
+                skill: "flood".to_owned(),
+                stream: "stdout",
+                limit_bytes: 4096,
+            })
+        );
+        // Terminated on the breach, not by the 60s timeout
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+
+    #[tokio::test]
+    async fn output_within_limit_is_returned() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let (skill, enforcer) =
+            script_skill(tmpdir.path(), "small", "#!/bin/bash\nprintf 'abcd'\n");
+        let executor = LocalSkillExecutor::new().with_limits(LocalExecutionLimits {
+            max_output_bytes: 4,
+            ..LocalExecutionLimits::default()
+        });
+
+        let result = executor.execute(&skill, "", &enforcer).await.unwrap();
+        assert_eq!(result.output, "abcd");
+    }
+
+    #[tokio::test]
+    async fn slow_skill_is_killed_at_timeout() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let (skill, enforcer) = script_skill(
+            tmpdir.path(),
+            "spin",
+            "#!/bin/bash\nwhile true; do :; done\n",
+        );
+        let executor = LocalSkillExecutor::new().with_limits(LocalExecutionLimits {
+            timeout: Duration::from_millis(200),
+            ..LocalExecutionLimits::default()
+        });
+
+        let start = Instant::now();
+        let err = executor.execute(&skill, "", &enforcer).await.unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<LocalSkillError>(),
+            Some(&LocalSkillError::TimedOut {
+                skill: "spin".to_owned(),
+                timeout_ms: 200,
+            })
+        );
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+
     #[tokio::test]
     async fn prompt_skill_returns_body_directly() {
         let tmpdir = tempfile::tempdir().unwrap();
@@ -351,6 +613,8 @@ mod tests {
                     parents: None,
                     secrets: None,
                     categories: None,
+                    cacheable: None,
+                    manifest_version: None,
                 }),
                 allowed_tools: None,
             },
@@ -369,4 +633,66 @@ mod tests {
             "You are a helpful assistant with special knowledge."
         );
     }
+
+    fn install_shout_skill(store: &SkillStore, bot_dir: &Path, attach: bool) {
+        let content = "---\nname: shout\ndescription: Upper-cases its input\nmetadata:\n  skill-type: tool\n  capabilities:\n    - exec_command\n---\n\nShout the input.\n";
+        let skill_dir = store.install_skill("shout", content, None, None).unwrap();
+        let scripts_dir = skill_dir.join("scripts");
+        std::fs::create_dir_all(&scripts_dir).unwrap();
+        std::fs::write(scripts_dir.join("run.sh"), "#!/bin/bash\ntr a-z A-Z\n").unwrap();
+
+        if attach {
+            let mut config = store.get_bot_skills_config(bot_dir).unwrap();
+            config.skills.insert(
+                "shout".to_owned(),
+                boternity_types::skill::BotSkillConfig {
+                    skill_name: "shout".to_owned(),
+                    enabled: true,
+                    trust_tier: None,
+                    version: None,
+                    overrides: std::collections::HashMap::new(),
+                    capabilities: Some(vec![Capability::ExecCommand]),
+                },
+            );
+            store.save_bot_skills_config(bot_dir, &config).unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn run_attached_skill_captures_output() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let store = SkillStore::new(tmpdir.path().to_path_buf());
+        let bot_dir = tmpdir.path().join("bots").join("test-bot");
+        install_shout_skill(&store, &bot_dir, true);
+
+        let result = run_attached_skill(
+            &store,
+            &bot_dir,
+            "shout",
+            "hello there",
+            &LocalSkillExecutor::new(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.output.trim(), "HELLO THERE");
+    }
+
+    #[tokio::test]
+    async fn run_attached_skill_rejects_unknown_and_unattached_skills() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let store = SkillStore::new(tmpdir.path().to_path_buf());
+        let bot_dir = tmpdir.path().join("bots").join("test-bot");
+        let executor = LocalSkillExecutor::new();
+
+        let err = run_attached_skill(&store, &bot_dir, "missing", "", &executor)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not installed"), "{err}");
+
+        install_shout_skill(&store, &bot_dir, false);
+        let err = run_attached_skill(&store, &bot_dir, "shout", "hi", &executor)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not attached"), "{err}");
+    }
 }
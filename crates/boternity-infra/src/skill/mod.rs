@@ -6,12 +6,14 @@
 pub mod audit;
 pub mod local_executor;
 pub mod registry_client;
+pub mod result_cache;
 pub mod sandbox;
 #[cfg(target_os = "macos")]
 pub mod sandbox_macos;
 #[cfg(target_os = "linux")]
 pub mod sandbox_linux;
 pub mod skill_store;
+pub mod suggest;
 pub mod wasm_compiler;
 pub mod wasm_executor;
 pub mod wasm_runtime;
@@ -0,0 +1,351 @@
+//! Result caching for deterministic skills.
+//!
+//! [`CachingSkillExecutor`] wraps another [`SkillExecutor`] and keeps
+//! successful outputs in the bot's KV store with a TTL, keyed by a SHA-256 of
+//! the skill name, version and input. Only skills whose manifest sets
+//! `cacheable: true` and that request no side-effecting capability are
+//! cached; everything else always runs.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use boternity_core::skill::executor::{SkillExecutionResult, SkillExecutor};
+use boternity_core::skill::permission::CapabilityEnforcer;
+use boternity_core::storage::kv_store::KvStore;
+use boternity_types::skill::{Capability, InstalledSkill, SkillManifest};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+/// Default lifetime of a cached skill result.
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// KV key prefix for cached skill results.
+pub const CACHE_KEY_PREFIX: &str = "skill-cache:";
+
+/// Whether results of the skill described by `manifest` may be cached.
+///
+/// The skill must opt in with `metadata.cacheable: true` and must not request
+/// a capability that changes state outside the skill (`WriteFile`,
+/// `HttpPost`, `KvWrite`). `ExecCommand` does not count: local skills need it
+/// to run at all, so for them `cacheable` is the author's promise.
+pub fn is_cacheable(manifest: &SkillManifest) -> bool {
+    let Some(metadata) = manifest.metadata.as_ref() else {
+        return false;
+    };
+    if metadata.cacheable != Some(true) {
+        return false;
+    }
+    !metadata.capabilities.iter().flatten().any(|cap| {
+        matches!(
+            cap,
+            Capability::WriteFile | Capability::HttpPost | Capability::KvWrite
+        )
+    })
+}
+
+/// KV key for the cached result of running `manifest`'s skill on `input`.
+///
+/// Includes the skill version so an upgraded skill does not serve results
+/// computed by the old one.
+pub fn cache_key(manifest: &SkillManifest, input: &str) -> String {
+    let version = manifest
+        .metadata
+        .as_ref()
+        .and_then(|m| m.version.as_deref())
+        .unwrap_or("");
+
+    let mut hasher = Sha256::new();
+    hasher.update(manifest.name.as_bytes());
+    hasher.update([0]);
+    hasher.update(version.as_bytes());
+    hasher.update([0]);
+    hasher.update(input.as_bytes());
+    format!("{CACHE_KEY_PREFIX}{}", hex_encode(&hasher.finalize()))
+}
+
+/// Encode bytes to a lowercase hex string.
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+// ---------------------------------------------------------------------------
+// CachingSkillExecutor
+// ---------------------------------------------------------------------------
+
+/// A [`SkillExecutor`] that serves repeat calls of cacheable skills from the
+/// bot's KV store.
+///
+/// Only successful results are cached. KV failures are logged and fall back
+/// to running the skill, so the cache never makes a call fail.
+pub struct CachingSkillExecutor<E, K> {
+    inner: E,
+    kv_store: Arc<K>,
+    bot_id: Uuid,
+    ttl: Duration,
+}
+
+impl<E, K> CachingSkillExecutor<E, K> {
+    /// Cache `inner`'s results in `bot_id`'s KV namespace for [`DEFAULT_CACHE_TTL`].
+    pub fn new(inner: E, kv_store: Arc<K>, bot_id: Uuid) -> Self {
+        Self {
+            inner,
+            kv_store,
+            bot_id,
+            ttl: DEFAULT_CACHE_TTL,
+        }
+    }
+
+    /// Override how long cached results live.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+}
+
+impl<E: SkillExecutor, K: KvStore> SkillExecutor for CachingSkillExecutor<E, K> {
+    async fn execute(
+        &self,
+        skill: &InstalledSkill,
+        input: &str,
+        enforcer: &CapabilityEnforcer,
+    ) -> Result<SkillExecutionResult> {
+        if !is_cacheable(&skill.manifest) {
+            return self.inner.execute(skill, input, enforcer).await;
+        }
+
+        let start = Instant::now();
+        let key = cache_key(&skill.manifest, input);
+
+        match self.kv_store.get(&self.bot_id, &key).await {
+            Ok(Some(serde_json::Value::String(output))) => {
+                tracing::debug!(skill = %skill.manifest.name, "skill result served from cache");
+                return Ok(SkillExecutionResult {
+                    output,
+                    fuel_consumed: None,
+                    memory_peak_bytes: None,
+                    duration: start.elapsed(),
+                });
+            }
+            Ok(_) => {}
+            Err(e) => {
+                tracing::warn!(
+                    skill = %skill.manifest.name,
+                    error = %e,
+                    "skill cache read failed, running skill"
+                );
+            }
+        }
+
+        let result = self.inner.execute(skill, input, enforcer).await?;
+
+        let value = serde_json::Value::String(result.output.clone());
+        if let Err(e) = self
+            .kv_store
+            .set_with_ttl(&self.bot_id, &key, &value, Some(self.ttl))
+            .await
+        {
+            tracing::warn!(
+                skill = %skill.manifest.name,
+                error = %e,
+                "skill cache write failed"
+            );
+        }
+
+        Ok(result)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use boternity_types::skill::{PermissionGrant, SkillMetadata, SkillSource, SkillType};
+    use chrono::Utc;
+
+    use crate::sqlite::kv::SqliteKvStore;
+    use crate::sqlite::pool::DatabasePool;
+
+    /// Executor that upper-cases its input and counts calls.
+    #[derive(Default)]
+    struct CountingExecutor {
+        calls: AtomicUsize,
+    }
+
+    impl SkillExecutor for &CountingExecutor {
+        async fn execute(
+            &self,
+            _skill: &InstalledSkill,
+            input: &str,
+            _enforcer: &CapabilityEnforcer,
+        ) -> Result<SkillExecutionResult> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(SkillExecutionResult {
+                output: input.to_uppercase(),
+                fuel_consumed: None,
+                memory_peak_bytes: None,
+                duration: Duration::ZERO,
+            })
+        }
+    }
+
+    async fn test_store() -> (Arc<SqliteKvStore>, Uuid) {
+        let dir = tempfile::tempdir().unwrap();
+        let url = format!("sqlite://{}?mode=rwc", dir.path().join("test.db").display());
+        std::mem::forget(dir);
+        let pool = DatabasePool::new(&url).await.unwrap();
+
+        let bot_id = Uuid::now_v7();
+        sqlx::query(
+            "INSERT INTO bots (id, slug, name, description, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(bot_id.to_string())
+        .bind(format!("bot-{bot_id}"))
+        .bind("Test Bot")
+        .bind("")
+        .bind(Utc::now().to_rfc3339())
+        .bind(Utc::now().to_rfc3339())
+        .execute(&pool.writer)
+        .await
+        .unwrap();
+
+        (Arc::new(SqliteKvStore::new(pool)), bot_id)
+    }
+
+    fn make_skill(name: &str, cacheable: Option<bool>, caps: Vec<Capability>) -> InstalledSkill {
+        InstalledSkill {
+            manifest: SkillManifest {
+                name: name.to_owned(),
+                description: "A test skill".to_owned(),
+                license: None,
+                compatibility: None,
+                metadata: Some(SkillMetadata {
+                    author: None,
+                    version: Some("1.0.0".to_owned()),
+                    skill_type: Some(SkillType::Tool),
+                    capabilities: Some(caps),
+                    dependencies: None,
+                    conflicts_with: None,
+                    trust_tier: None,
+                    parents: None,
+                    secrets: None,
+                    categories: None,
+                    cacheable,
+                    manifest_version: None,
+                }),
+                allowed_tools: None,
+            },
+            body: String::new(),
+            source: SkillSource::Local,
+            install_path: PathBuf::from("/tmp/nonexistent"),
+            wasm_path: None,
+        }
+    }
+
+    fn enforcer_for(skill: &InstalledSkill) -> CapabilityEnforcer {
+        let grants: Vec<PermissionGrant> = skill
+            .manifest
+            .metadata
+            .iter()
+            .flat_map(|m| m.capabilities.iter().flatten())
+            .map(|cap| PermissionGrant {
+                skill_name: skill.manifest.name.clone(),
+                capability: cap.clone(),
+                granted: true,
+                granted_at: Utc::now(),
+            })
+            .collect();
+        CapabilityEnforcer::new(&skill.manifest.name, &grants).unwrap()
+    }
+
+    #[test]
+    fn cacheable_requires_opt_in_and_no_side_effects() {
+        assert!(is_cacheable(
+            &make_skill("pure", Some(true), vec![Capability::HttpGet]).manifest
+        ));
+        assert!(!is_cacheable(
+            &make_skill("default", None, vec![Capability::HttpGet]).manifest
+        ));
+        assert!(!is_cacheable(
+            &make_skill("writer", Some(true), vec![Capability::WriteFile]).manifest
+        ));
+        assert!(!is_cacheable(
+            &make_skill("poster", Some(true), vec![Capability::HttpPost]).manifest
+        ));
+    }
+
+    #[test]
+    fn cache_key_changes_with_version_and_input() {
+        let skill = make_skill("pure", Some(true), vec![]);
+        let mut upgraded = skill.clone();
+        upgraded.manifest.metadata.as_mut().unwrap().version = Some("1.1.0".to_owned());
+
+        let key = cache_key(&skill.manifest, "hello");
+        assert!(key.starts_with(CACHE_KEY_PREFIX));
+        assert_eq!(key, cache_key(&skill.manifest, "hello"));
+        assert_ne!(key, cache_key(&skill.manifest, "hello!"));
+        assert_ne!(key, cache_key(&upgraded.manifest, "hello"));
+    }
+
+    #[tokio::test]
+    async fn cacheable_skill_returns_cached_output_on_identical_input() {
+        let (kv_store, bot_id) = test_store().await;
+        let inner = CountingExecutor::default();
+        let executor = CachingSkillExecutor::new(&inner, kv_store, bot_id);
+        let skill = make_skill("shout", Some(true), vec![Capability::ExecCommand]);
+        let enforcer = enforcer_for(&skill);
+
+        let first = executor.execute(&skill, "hello", &enforcer).await.unwrap();
+        let second = executor.execute(&skill, "hello", &enforcer).await.unwrap();
+        assert_eq!(first.output, "HELLO");
+        assert_eq!(second.output, "HELLO");
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+
+        // Different input misses the cache
+        let other = executor.execute(&skill, "bye", &enforcer).await.unwrap();
+        assert_eq!(other.output, "BYE");
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn side_effecting_skill_always_reruns() {
+        let (kv_store, bot_id) = test_store().await;
+        let inner = CountingExecutor::default();
+        let executor = CachingSkillExecutor::new(&inner, Arc::clone(&kv_store), bot_id);
+        let skill = make_skill(
+            "write-report",
+            Some(true),
+            vec![Capability::ExecCommand, Capability::WriteFile],
+        );
+        let enforcer = enforcer_for(&skill);
+
+        executor.execute(&skill, "hello", &enforcer).await.unwrap();
+        executor.execute(&skill, "hello", &enforcer).await.unwrap();
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+
+        // Nothing was written to the cache
+        let keys = kv_store.list_keys(&bot_id).await.unwrap();
+        assert!(keys.iter().all(|k| !k.starts_with(CACHE_KEY_PREFIX)));
+    }
+
+    #[tokio::test]
+    async fn expired_entry_reruns_skill() {
+        let (kv_store, bot_id) = test_store().await;
+        let inner = CountingExecutor::default();
+        let executor =
+            CachingSkillExecutor::new(&inner, kv_store, bot_id).with_ttl(Duration::from_millis(1));
+        let skill = make_skill("shout", Some(true), vec![Capability::ExecCommand]);
+        let enforcer = enforcer_for(&skill);
+
+        executor.execute(&skill, "hello", &enforcer).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        executor.execute(&skill, "hello", &enforcer).await.unwrap();
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+    }
+}
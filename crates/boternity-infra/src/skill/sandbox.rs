@@ -13,6 +13,10 @@
 //! The subprocess is spawned as `self --wasm-sandbox-exec`, which applies
 //! OS restrictions before running the WASM component. Communication happens
 //! via stdin/stdout JSON.
+//!
+//! [`self_test`] checks that the platform mechanism really restricts a probe
+//! (used by `bnity doctor`), so a sandbox that silently does nothing is
+//! reported instead of trusted.
 
 use std::path::{Path, PathBuf};
 
@@ -159,6 +163,89 @@ pub fn should_use_os_sandbox(tier: &TrustTier) -> bool {
     matches!(tier, TrustTier::Untrusted)
 }
 
+/// Outcome of the OS sandbox self-test.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum SandboxStatus {
+    /// The sandboxed probe was denied access to a file outside its policy.
+    Enforced,
+    /// The sandbox mechanism exists but does not restrict as much as expected.
+    Degraded { reason: String },
+    /// No usable sandbox mechanism: untrusted skills get WASM isolation only.
+    Unavailable { reason: String },
+}
+
+impl SandboxStatus {
+    pub fn is_enforced(&self) -> bool {
+        matches!(self, Self::Enforced)
+    }
+}
+
+/// Verify that the host's sandbox mechanism actually restricts a process.
+///
+/// Writes a file to a fresh temp directory, then has a probe try to read it
+/// under a policy that denies that directory (Landlock on a dedicated thread
+/// on Linux, `sandbox-exec` running `cat` on macOS). The sandbox counts as
+/// enforced only if the read is denied. Anything less is logged as a warning,
+/// since `run_sandboxed` would otherwise silently run without OS isolation.
+pub async fn self_test() -> SandboxStatus {
+    let probe_dir =
+        std::env::temp_dir().join(format!("boternity-sandbox-probe-{}", uuid::Uuid::now_v7()));
+    let status = match write_probe_file(&probe_dir).await {
+        Ok(secret) => probe(&secret).await,
+        Err(e) => SandboxStatus::Unavailable {
+            reason: format!("cannot create probe file: {e}"),
+        },
+    };
+    let _ = tokio::fs::remove_dir_all(&probe_dir).await;
+
+    match &status {
+        SandboxStatus::Enforced => tracing::debug!("OS sandbox self-test passed"),
+        SandboxStatus::Degraded { reason } => {
+            tracing::warn!(%reason, "OS sandbox is degraded; untrusted skills are not fully isolated")
+        }
+        SandboxStatus::Unavailable { reason } => {
+            tracing::warn!(%reason, "OS sandbox is unavailable; untrusted skills run with WASM isolation only")
+        }
+    }
+    status
+}
+
+/// Create `dir` with a file the probe must not be able to read.
+///
+/// Returns the canonical path: Seatbelt matches resolved paths, and the macOS
+/// temp directory sits behind a symlink.
+async fn write_probe_file(dir: &Path) -> std::io::Result<PathBuf> {
+    tokio::fs::create_dir_all(dir).await?;
+    let secret = dir.join("secret");
+    tokio::fs::write(&secret, b"sandbox probe").await?;
+    tokio::fs::canonicalize(&secret).await
+}
+
+/// Run the platform probe against `secret`.
+async fn probe(secret: &Path) -> SandboxStatus {
+    #[cfg(target_os = "macos")]
+    {
+        return super::sandbox_macos::probe_seatbelt(secret).await;
+    }
+    #[cfg(target_os = "linux")]
+    {
+        let secret = secret.to_path_buf();
+        return tokio::task::spawn_blocking(move || super::sandbox_linux::probe_landlock(&secret))
+            .await
+            .unwrap_or_else(|e| SandboxStatus::Unavailable {
+                reason: format!("landlock probe failed: {e}"),
+            });
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    {
+        let _ = secret;
+        SandboxStatus::Unavailable {
+            reason: "OS-level sandbox not supported on this platform".to_string(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -229,6 +316,43 @@ mod tests {
         );
     }
 
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn self_test_matches_kernel_landlock_support() {
+        let status = self_test().await;
+        let lsm = std::fs::read_to_string("/sys/kernel/security/lsm").ok();
+        match lsm.map(|lsm| lsm.split(',').any(|m| m.trim() == "landlock")) {
+            Some(true) => assert!(
+                !matches!(status, SandboxStatus::Unavailable { .. }),
+                "landlock is active but the self-test reported {status:?}"
+            ),
+            Some(false) => assert!(
+                matches!(status, SandboxStatus::Unavailable { .. }),
+                "landlock is not active but the self-test reported {status:?}"
+            ),
+            // securityfs not mounted (e.g. some containers): only the outcome's shape is known
+            None => {}
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    #[tokio::test]
+    async fn self_test_passes_on_macos() {
+        assert_eq!(self_test().await, SandboxStatus::Enforced);
+    }
+
+    #[test]
+    fn sandbox_status_serializes_with_reason() {
+        let status = SandboxStatus::Unavailable {
+            reason: "no landlock".to_string(),
+        };
+        let json = serde_json::to_value(&status).unwrap();
+        assert_eq!(json["status"], "unavailable");
+        assert_eq!(json["reason"], "no landlock");
+        assert!(!status.is_enforced());
+        assert!(SandboxStatus::Enforced.is_enforced());
+    }
+
     #[test]
     fn sandbox_request_serializes() {
         let req = SandboxRequest {
@@ -4,7 +4,9 @@
 //! the WASM executor subprocess. The host process is never restricted -- the
 //! Seatbelt profile applies only to the spawned child process.
 
-use super::sandbox::{SandboxConfig, SandboxRequest, SandboxResponse};
+use std::path::Path;
+
+use super::sandbox::{SandboxConfig, SandboxRequest, SandboxResponse, SandboxStatus};
 
 /// Generate a Seatbelt profile string from the sandbox configuration.
 ///
@@ -95,6 +97,47 @@ pub fn generate_seatbelt_profile(config: &SandboxConfig) -> String {
     profile
 }
 
+/// Check that Seatbelt denies a read of `secret` (see `sandbox::self_test`).
+///
+/// Runs `/bin/cat` under a profile that allows everything except reading the
+/// probe directory. `secret` must be a canonical path.
+pub async fn probe_seatbelt(secret: &Path) -> SandboxStatus {
+    let dir = secret.parent().unwrap_or(secret);
+    let profile = format!(
+        "(version 1)\n(allow default)\n(deny file-read* (subpath \"{}\"))\n",
+        dir.display()
+    );
+
+    let output = match tokio::process::Command::new("sandbox-exec")
+        .arg("-p")
+        .arg(&profile)
+        .arg("/bin/cat")
+        .arg(secret)
+        .output()
+        .await
+    {
+        Ok(output) => output,
+        Err(e) => {
+            return SandboxStatus::Unavailable {
+                reason: format!("failed to run sandbox-exec: {e}"),
+            };
+        }
+    };
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if output.status.success() {
+        SandboxStatus::Degraded {
+            reason: "sandbox-exec ran but the probe read a denied file".to_string(),
+        }
+    } else if stderr.contains("Operation not permitted") {
+        SandboxStatus::Enforced
+    } else {
+        SandboxStatus::Unavailable {
+            reason: format!("sandbox-exec failed: {}", stderr.trim()),
+        }
+    }
+}
+
 /// Run a WASM skill in a macOS Seatbelt sandbox.
 ///
 /// Spawns `sandbox-exec -p {profile} {exe} --wasm-sandbox-exec` as a subprocess.
@@ -46,6 +46,11 @@ impl SkillStore {
         self.skills_dir().join(name)
     }
 
+    /// Path of the skill description embedding index (see `skill::suggest`).
+    pub fn embedding_index_path(&self) -> PathBuf {
+        self.skills_dir().join(".embeddings.json")
+    }
+
     /// Check whether a skill exists on disk.
     pub fn skill_exists(&self, name: &str) -> bool {
         self.resolve_skill_path(name).join("SKILL.md").exists()
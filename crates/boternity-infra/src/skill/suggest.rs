@@ -0,0 +1,280 @@
+//! Semantic suggestion of installed skills.
+//!
+//! Each skill's name, description and categories are embedded when it is
+//! installed ([`index_skill`]) and kept in the store's embedding index file.
+//! [`suggest_skills`] ranks installed skills by cosine similarity to a query.
+//! Skills missing from the index (e.g. created locally), whose text changed,
+//! or that were embedded with another model are embedded on demand first.
+
+use std::collections::HashMap;
+
+use anyhow::Context;
+use boternity_core::memory::embedder::Embedder;
+use boternity_types::skill::{InstalledSkill, SkillManifest};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::skill_store::SkillStore;
+
+/// A skill relevant to a query.
+#[derive(Debug, Clone, Serialize)]
+pub struct SkillSuggestion {
+    pub name: String,
+    pub description: String,
+    /// Cosine similarity between the query and the skill text.
+    pub score: f32,
+}
+
+/// On-disk embedding index, keyed by skill name.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SkillEmbeddingIndex {
+    /// Embedding model every vector was produced with.
+    model: String,
+    entries: HashMap<String, IndexEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct IndexEntry {
+    /// SHA-256 of the embedded text, to detect edited skills.
+    text_hash: String,
+    vector: Vec<f32>,
+}
+
+/// Text embedded for a skill: name, description and categories.
+pub fn skill_text(manifest: &SkillManifest) -> String {
+    let mut text = format!("{}: {}", manifest.name, manifest.description);
+    let categories = manifest
+        .metadata
+        .as_ref()
+        .and_then(|m| m.categories.as_ref());
+    if let Some(categories) = categories.filter(|c| !c.is_empty()) {
+        text.push_str(&format!(" ({})", categories.join(", ")));
+    }
+    text
+}
+
+/// Embed `skill` and store it in the index.
+pub async fn index_skill(
+    store: &SkillStore,
+    embedder: &impl Embedder,
+    skill: &InstalledSkill,
+) -> anyhow::Result<()> {
+    let mut index = load_index(store, embedder.model_name())?;
+    let text = skill_text(&skill.manifest);
+    let vector = embed_one(embedder, &text).await?;
+    index.entries.insert(
+        skill.manifest.name.clone(),
+        IndexEntry {
+            text_hash: text_hash(&text),
+            vector,
+        },
+    );
+    save_index(store, &index)
+}
+
+/// The `k` installed skills most relevant to `query`, best first.
+///
+/// Brings the index up to date with the installed skills before ranking
+/// (see the module docs) and drops entries for removed skills.
+pub async fn suggest_skills(
+    store: &SkillStore,
+    embedder: &impl Embedder,
+    query: &str,
+    k: usize,
+) -> anyhow::Result<Vec<SkillSuggestion>> {
+    let skills = store.list_skills()?;
+    let mut index = load_index(store, embedder.model_name())?;
+
+    let texts: Vec<(String, String)> = skills
+        .iter()
+        .map(|skill| (skill.manifest.name.clone(), skill_text(&skill.manifest)))
+        .collect();
+    let stale: Vec<&(String, String)> = texts
+        .iter()
+        .filter(|(name, text)| {
+            index
+                .entries
+                .get(name)
+                .is_none_or(|entry| entry.text_hash != text_hash(text))
+        })
+        .collect();
+    let pruned = index
+        .entries
+        .keys()
+        .any(|name| !texts.iter().any(|(n, _)| n == name));
+    let changed = pruned || !stale.is_empty();
+
+    if !stale.is_empty() {
+        let inputs: Vec<String> = stale.iter().map(|(_, text)| text.clone()).collect();
+        let vectors = embedder
+            .embed(&inputs)
+            .await
+            .context("Failed to embed skill descriptions")?;
+        for ((name, text), vector) in stale.into_iter().zip(vectors) {
+            index.entries.insert(
+                name.clone(),
+                IndexEntry {
+                    text_hash: text_hash(text),
+                    vector,
+                },
+            );
+        }
+    }
+    if pruned {
+        index
+            .entries
+            .retain(|name, _| texts.iter().any(|(n, _)| n == name));
+    }
+    if changed {
+        save_index(store, &index)?;
+    }
+
+    let query_vector = embed_one(embedder, query).await?;
+    let mut suggestions: Vec<SkillSuggestion> = skills
+        .iter()
+        .filter_map(|skill| {
+            let entry = index.entries.get(&skill.manifest.name)?;
+            Some(SkillSuggestion {
+                name: skill.manifest.name.clone(),
+                description: skill.manifest.description.clone(),
+                score: cosine_similarity(&query_vector, &entry.vector),
+            })
+        })
+        .collect();
+    suggestions.sort_by(|a, b| b.score.total_cmp(&a.score));
+    suggestions.truncate(k);
+    Ok(suggestions)
+}
+
+/// Load the index, starting over if it was built with another model.
+fn load_index(store: &SkillStore, model: &str) -> anyhow::Result<SkillEmbeddingIndex> {
+    let path = store.embedding_index_path();
+    let index = match std::fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str::<SkillEmbeddingIndex>(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => SkillEmbeddingIndex::default(),
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to read {}", path.display()));
+        }
+    };
+    if index.model == model {
+        Ok(index)
+    } else {
+        Ok(SkillEmbeddingIndex {
+            model: model.to_owned(),
+            entries: HashMap::new(),
+        })
+    }
+}
+
+fn save_index(store: &SkillStore, index: &SkillEmbeddingIndex) -> anyhow::Result<()> {
+    let path = store.embedding_index_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let content = serde_json::to_string(index).context("Failed to serialize skill index")?;
+    std::fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+async fn embed_one(embedder: &impl Embedder, text: &str) -> anyhow::Result<Vec<f32>> {
+    embedder
+        .embed(&[text.to_owned()])
+        .await
+        .context("Failed to embed text")?
+        .pop()
+        .context("Embedder returned no vector")
+}
+
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+fn text_hash(text: &str) -> String {
+    hex_encode(&Sha256::digest(text.as_bytes()))
+}
+
+/// Encode bytes to a lowercase hex string.
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vector::embedder::DeterministicEmbedder;
+
+    fn install(store: &SkillStore, name: &str, description: &str) -> InstalledSkill {
+        let content = format!("---\nname: {name}\ndescription: {description}\n---\n\nBody.\n");
+        store.install_skill(name, &content, None, None).unwrap();
+        store.get_skill(name).unwrap()
+    }
+
+    #[tokio::test]
+    async fn query_returns_most_relevant_skill_first() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let store = SkillStore::new(tmpdir.path().to_path_buf());
+        let embedder = DeterministicEmbedder::new();
+
+        for (name, description) in [
+            ("web-search", "Search the web for pages and news articles"),
+            ("pdf-reader", "Extract text and tables from PDF documents"),
+            ("weather", "Get the weather forecast for a city"),
+            ("calendar", "Create and list events in the user's calendar"),
+        ] {
+            let skill = install(&store, name, description);
+            index_skill(&store, &embedder, &skill).await.unwrap();
+        }
+
+        let suggestions = suggest_skills(&store, &embedder, "weather forecast for Paris", 2)
+            .await
+            .unwrap();
+        assert_eq!(suggestions.len(), 2);
+        assert_eq!(suggestions[0].name, "weather");
+        assert!(suggestions[0].score > suggestions[1].score);
+
+        let suggestions = suggest_skills(&store, &embedder, "extract text from a PDF", 1)
+            .await
+            .unwrap();
+        assert_eq!(suggestions[0].name, "pdf-reader");
+    }
+
+    #[tokio::test]
+    async fn unindexed_and_removed_skills_are_reconciled() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let store = SkillStore::new(tmpdir.path().to_path_buf());
+        let embedder = DeterministicEmbedder::new();
+
+        let search = install(&store, "web-search", "Search the web for pages");
+        index_skill(&store, &embedder, &search).await.unwrap();
+        // Installed without indexing
+        install(&store, "translate", "Translate text between languages");
+
+        let suggestions = suggest_skills(&store, &embedder, "translate this text", 5)
+            .await
+            .unwrap();
+        assert_eq!(suggestions.len(), 2);
+        assert_eq!(suggestions[0].name, "translate");
+
+        store.remove_skill("web-search").unwrap();
+        let suggestions = suggest_skills(&store, &embedder, "search", 5)
+            .await
+            .unwrap();
+        assert_eq!(suggestions.len(), 1);
+
+        let index = load_index(&store, embedder.model_name()).unwrap();
+        assert_eq!(index.entries.len(), 1);
+        assert!(index.entries.contains_key("translate"));
+    }
+}
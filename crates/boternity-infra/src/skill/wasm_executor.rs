@@ -4,15 +4,28 @@
 //! [`Store`] to prevent state leaks, fuel limits track CPU usage, and a
 //! [`ResourceLimiter`] caps memory growth. Host imports are gated by
 //! [`Capability`] checks -- no bypass through WIT imports.
+//!
+//! The `kv-get`/`kv-set` and `get-secret` imports reach the invoking bot's KV
+//! store and secrets through [`SkillHostServices`]. They need `KvRead`,
+//! `KvWrite` or `GetSecret`, and secrets must also be declared in the skill's
+//! manifest.
 
 use std::collections::HashSet;
 use std::sync::Arc;
 use std::time::Instant;
 
 use anyhow::{bail, Context, Result};
+use boternity_core::service::secret::SecretService;
 use boternity_core::skill::executor::{SkillExecutionResult, SkillExecutor};
-use boternity_core::skill::permission::CapabilityEnforcer;
+use boternity_core::skill::permission::{
+    check_secret_declared, declared_secrets, CapabilityEnforcer,
+};
+use boternity_core::storage::kv_store::KvStore;
+use boternity_types::bot::BotId;
+use boternity_types::error::RepositoryError;
+use boternity_types::secret::SecretScope;
 use boternity_types::skill::{Capability, InstalledSkill, TrustTier};
+use futures_util::future::BoxFuture;
 use uuid::Uuid;
 use wasmtime::component::{HasSelf, Linker, ResourceTable};
 use wasmtime::{ResourceLimiter, Store};
@@ -20,6 +33,94 @@ use wasmtime_wasi::{WasiCtx, WasiCtxBuilder, WasiCtxView, WasiView};
 
 use super::wasm_runtime::{self, boternity::skill::host, WasmRuntime};
 
+// ---------------------------------------------------------------------------
+// SkillHostServices -- bot-scoped KV and secrets
+// ---------------------------------------------------------------------------
+
+/// Bot-scoped KV and secret access behind the `kv-*` and `get-secret` imports.
+///
+/// Object-safe so [`SkillState`] can hold it without being generic over the
+/// KV store. Capability and secret-declaration checks happen in the host
+/// imports; implementations only resolve data for the given bot.
+pub trait SkillHostServices: Send + Sync {
+    /// Read a KV value for `bot_id`.
+    fn kv_get<'a>(
+        &'a self,
+        bot_id: Uuid,
+        key: &'a str,
+    ) -> BoxFuture<'a, Result<Option<serde_json::Value>, RepositoryError>>;
+
+    /// Write a KV value for `bot_id`.
+    fn kv_set<'a>(
+        &'a self,
+        bot_id: Uuid,
+        key: &'a str,
+        value: serde_json::Value,
+    ) -> BoxFuture<'a, Result<(), RepositoryError>>;
+
+    /// Resolve a secret for `bot_id` (bot scope first, then global).
+    fn get_secret<'a>(
+        &'a self,
+        bot_id: Uuid,
+        name: &'a str,
+    ) -> BoxFuture<'a, Result<Option<String>, RepositoryError>>;
+}
+
+/// [`SkillHostServices`] backed by a [`KvStore`] and the [`SecretService`].
+pub struct StoreHostServices<K> {
+    kv_store: Arc<K>,
+    secret_service: Arc<SecretService>,
+}
+
+impl<K> StoreHostServices<K> {
+    /// Create host services over the given KV store and secret service.
+    pub fn new(kv_store: Arc<K>, secret_service: Arc<SecretService>) -> Self {
+        Self {
+            kv_store,
+            secret_service,
+        }
+    }
+}
+
+impl<K: KvStore + 'static> SkillHostServices for StoreHostServices<K> {
+    fn kv_get<'a>(
+        &'a self,
+        bot_id: Uuid,
+        key: &'a str,
+    ) -> BoxFuture<'a, Result<Option<serde_json::Value>, RepositoryError>> {
+        Box::pin(async move { self.kv_store.get(&bot_id, key).await })
+    }
+
+    fn kv_set<'a>(
+        &'a self,
+        bot_id: Uuid,
+        key: &'a str,
+        value: serde_json::Value,
+    ) -> BoxFuture<'a, Result<(), RepositoryError>> {
+        Box::pin(async move { self.kv_store.set(&bot_id, key, &value).await })
+    }
+
+    fn get_secret<'a>(
+        &'a self,
+        bot_id: Uuid,
+        name: &'a str,
+    ) -> BoxFuture<'a, Result<Option<String>, RepositoryError>> {
+        Box::pin(async move {
+            self.secret_service
+                .get_secret(name, &SecretScope::Bot(BotId(bot_id)))
+                .await
+        })
+    }
+}
+
+/// The bot a skill is invoked for. Scopes KV and secret access.
+#[derive(Debug, Clone)]
+pub struct InvokingBot {
+    pub id: Uuid,
+    pub slug: String,
+    pub name: String,
+}
+
 // ---------------------------------------------------------------------------
 // SkillState -- per-invocation Store data
 // ---------------------------------------------------------------------------
@@ -37,12 +138,30 @@ struct SkillState {
     skill_name: String,
     bot_slug: String,
     bot_name: String,
+    /// Bot the skill runs for; KV and secret imports are scoped to it.
+    bot_id: Option<Uuid>,
+    /// Secret names the skill declares in its manifest.
+    declared_secrets: HashSet<String>,
+    host_services: Option<Arc<dyn SkillHostServices>>,
     /// Tracked for audit logging; read when computing fuel_consumed.
     #[allow(dead_code)]
     fuel_initial: u64,
     max_memory_bytes: usize,
 }
 
+impl SkillState {
+    /// The invoking bot and host services for a bot-scoped import.
+    fn bot_scope(&self, import: &str) -> Result<(Uuid, Arc<dyn SkillHostServices>), String> {
+        match (self.bot_id, &self.host_services) {
+            (Some(bot_id), Some(services)) => Ok((bot_id, Arc::clone(services))),
+            _ => Err(format!(
+                "{import} unavailable: skill '{}' is not running for a bot",
+                self.skill_name
+            )),
+        }
+    }
+}
+
 impl WasiView for SkillState {
     fn ctx(&mut self) -> WasiCtxView<'_> {
         WasiCtxView {
@@ -174,16 +293,56 @@ impl host::Host for SkillState {
         }
     }
 
-    fn get_secret(&mut self, name: String) -> Result<String, String> {
+    async fn get_secret(&mut self, name: String) -> Result<String, String> {
         if !self.capabilities.contains(&Capability::GetSecret) {
             return Err(format!(
                 "capability denied: GetSecret not granted for skill '{}'",
                 self.skill_name
             ));
         }
-        tracing::debug!(skill = %self.skill_name, secret = %name, "get_secret stub");
-        // Stub: will be wired to secret provider in a future plan.
-        Err("get_secret not yet implemented".to_string())
+        check_secret_declared(&self.skill_name, &self.declared_secrets, &name)
+            .map_err(|e| e.to_string())?;
+        let (bot_id, services) = self.bot_scope("get_secret")?;
+        match services.get_secret(bot_id, &name).await {
+            Ok(Some(value)) => Ok(value),
+            Ok(None) => Err(format!("secret '{}' not found", name)),
+            Err(e) => Err(format!("failed to read secret '{}': {}", name, e)),
+        }
+    }
+
+    async fn kv_get(&mut self, key: String) -> Result<Option<String>, String> {
+        if !self.capabilities.contains(&Capability::KvRead) {
+            return Err(format!(
+                "capability denied: KvRead not granted for skill '{}'",
+                self.skill_name
+            ));
+        }
+        let (bot_id, services) = self.bot_scope("kv_get")?;
+        let value = services
+            .kv_get(bot_id, &key)
+            .await
+            .map_err(|e| format!("failed to read key '{}': {}", key, e))?;
+        // Values cross the WIT boundary as JSON text.
+        value
+            .map(|v| serde_json::to_string(&v))
+            .transpose()
+            .map_err(|e| format!("failed to encode key '{}': {}", key, e))
+    }
+
+    async fn kv_set(&mut self, key: String, value: String) -> Result<(), String> {
+        if !self.capabilities.contains(&Capability::KvWrite) {
+            return Err(format!(
+                "capability denied: KvWrite not granted for skill '{}'",
+                self.skill_name
+            ));
+        }
+        let value: serde_json::Value = serde_json::from_str(&value)
+            .map_err(|e| format!("value for key '{}' is not valid JSON: {}", key, e))?;
+        let (bot_id, services) = self.bot_scope("kv_set")?;
+        services
+            .kv_set(bot_id, &key, value)
+            .await
+            .map_err(|e| format!("failed to write key '{}': {}", key, e))
     }
 
     fn read_env(&mut self, name: String) -> Result<String, String> {
@@ -226,17 +385,27 @@ impl host::Host for SkillState {
 /// invocations without sharing mutable state.
 pub struct WasmSkillExecutor {
     runtime: Arc<WasmRuntime>,
+    host_services: Option<Arc<dyn SkillHostServices>>,
 }
 
 impl WasmSkillExecutor {
     /// Create a new executor backed by the given runtime.
     pub fn new(runtime: Arc<WasmRuntime>) -> Self {
-        Self { runtime }
+        Self {
+            runtime,
+            host_services: None,
+        }
     }
-}
 
-impl SkillExecutor for WasmSkillExecutor {
-    /// Execute a WASM skill component.
+    /// Back the KV and secret imports with `services`.
+    ///
+    /// Without host services those imports return an error.
+    pub fn with_host_services(mut self, services: Arc<dyn SkillHostServices>) -> Self {
+        self.host_services = Some(services);
+        self
+    }
+
+    /// Execute a WASM skill component on behalf of `bot`.
     ///
     /// # Security model
     ///
@@ -247,11 +416,15 @@ impl SkillExecutor for WasmSkillExecutor {
     /// 5. Fuel set per trust tier limits
     /// 6. ResourceLimiter caps memory growth
     /// 7. Component instantiated and `execute` called
-    async fn execute(
+    ///
+    /// KV and secret imports are scoped to `bot`; with `None` they are
+    /// unavailable to the skill.
+    pub async fn execute_for_bot(
         &self,
         skill: &InstalledSkill,
         input: &str,
         enforcer: &CapabilityEnforcer,
+        bot: Option<&InvokingBot>,
     ) -> Result<SkillExecutionResult> {
         let start = Instant::now();
         let invocation_id = Uuid::now_v7();
@@ -367,10 +540,9 @@ impl SkillExecutor for WasmSkillExecutor {
         // Collect granted capabilities for the SkillState.
         let capabilities: HashSet<Capability> = enforcer.granted_capabilities().clone();
 
-        // Extract bot context from skill name (bot_slug/bot_name are not
-        // available from InstalledSkill directly; use placeholder from skill).
-        let bot_slug = String::new();
-        let bot_name = String::new();
+        // Bot context is empty when the skill is not invoked for a bot.
+        let bot_slug = bot.map(|b| b.slug.clone()).unwrap_or_default();
+        let bot_name = bot.map(|b| b.name.clone()).unwrap_or_default();
 
         // 5. Create fresh Store with SkillState
         let skill_state = SkillState {
@@ -381,6 +553,9 @@ impl SkillExecutor for WasmSkillExecutor {
             skill_name: skill.manifest.name.clone(),
             bot_slug,
             bot_name,
+            bot_id: bot.map(|b| b.id),
+            declared_secrets: declared_secrets(&skill.manifest),
+            host_services: self.host_services.clone(),
             fuel_initial: resource_limits.max_fuel,
             max_memory_bytes: resource_limits.max_memory_bytes,
         };
@@ -428,6 +603,18 @@ impl SkillExecutor for WasmSkillExecutor {
     }
 }
 
+impl SkillExecutor for WasmSkillExecutor {
+    /// Execute a WASM skill component without an invoking bot.
+    async fn execute(
+        &self,
+        skill: &InstalledSkill,
+        input: &str,
+        enforcer: &CapabilityEnforcer,
+    ) -> Result<SkillExecutionResult> {
+        self.execute_for_bot(skill, input, enforcer, None).await
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -446,6 +633,9 @@ mod tests {
             skill_name: "test-skill".to_string(),
             bot_slug: "test-bot".to_string(),
             bot_name: "Test Bot".to_string(),
+            bot_id: None,
+            declared_secrets: HashSet::new(),
+            host_services: None,
             fuel_initial: 1_000_000,
             max_memory_bytes: 16 * 1024 * 1024, // 16 MB
         };
@@ -482,6 +672,9 @@ mod tests {
             skill_name: "test-skill".to_string(),
             bot_slug: "test-bot".to_string(),
             bot_name: "Test Bot".to_string(),
+            bot_id: None,
+            declared_secrets: HashSet::new(),
+            host_services: None,
             fuel_initial: 1_000_000,
             max_memory_bytes: 64 * 1024 * 1024,
         };
@@ -509,6 +702,9 @@ mod tests {
             skill_name: "ctx-skill".to_string(),
             bot_slug: "my-bot".to_string(),
             bot_name: "My Bot".to_string(),
+            bot_id: None,
+            declared_secrets: HashSet::new(),
+            host_services: None,
             fuel_initial: 1_000_000,
             max_memory_bytes: 64 * 1024 * 1024,
         };
@@ -529,6 +725,9 @@ mod tests {
             skill_name: "log-skill".to_string(),
             bot_slug: "my-bot".to_string(),
             bot_name: "My Bot".to_string(),
+            bot_id: None,
+            declared_secrets: HashSet::new(),
+            host_services: None,
             fuel_initial: 1_000_000,
             max_memory_bytes: 64 * 1024 * 1024,
         };
@@ -553,6 +752,9 @@ mod tests {
             skill_name: "denied-skill".to_string(),
             bot_slug: "test-bot".to_string(),
             bot_name: "Test Bot".to_string(),
+            bot_id: None,
+            declared_secrets: HashSet::new(),
+            host_services: None,
             fuel_initial: 1_000_000,
             max_memory_bytes: 64 * 1024 * 1024,
         };
@@ -572,6 +774,9 @@ mod tests {
             skill_name: "denied-skill".to_string(),
             bot_slug: "test-bot".to_string(),
             bot_name: "Test Bot".to_string(),
+            bot_id: None,
+            declared_secrets: HashSet::new(),
+            host_services: None,
             fuel_initial: 1_000_000,
             max_memory_bytes: 64 * 1024 * 1024,
         };
@@ -595,6 +800,9 @@ mod tests {
             skill_name: "denied-skill".to_string(),
             bot_slug: "test-bot".to_string(),
             bot_name: "Test Bot".to_string(),
+            bot_id: None,
+            declared_secrets: HashSet::new(),
+            host_services: None,
             fuel_initial: 1_000_000,
             max_memory_bytes: 64 * 1024 * 1024,
         };
@@ -614,6 +822,9 @@ mod tests {
             skill_name: "denied-skill".to_string(),
             bot_slug: "test-bot".to_string(),
             bot_name: "Test Bot".to_string(),
+            bot_id: None,
+            declared_secrets: HashSet::new(),
+            host_services: None,
             fuel_initial: 1_000_000,
             max_memory_bytes: 64 * 1024 * 1024,
         };
@@ -627,8 +838,8 @@ mod tests {
         assert!(result.unwrap_err().contains("capability denied"));
     }
 
-    #[test]
-    fn host_denies_get_secret_without_capability() {
+    #[tokio::test]
+    async fn host_denies_get_secret_without_capability() {
         let mut state = SkillState {
             ctx: WasiCtxBuilder::new().build(),
             table: ResourceTable::new(),
@@ -637,15 +848,203 @@ mod tests {
             skill_name: "denied-skill".to_string(),
             bot_slug: "test-bot".to_string(),
             bot_name: "Test Bot".to_string(),
+            bot_id: None,
+            declared_secrets: HashSet::new(),
+            host_services: None,
             fuel_initial: 1_000_000,
             max_memory_bytes: 64 * 1024 * 1024,
         };
 
-        let result = host::Host::get_secret(&mut state, "API_KEY".to_string());
+        let result = host::Host::get_secret(&mut state, "API_KEY".to_string()).await;
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("capability denied"));
     }
 
+    /// In-memory KV and secrets keyed by bot ID.
+    #[derive(Default)]
+    struct MemoryHostServices {
+        kv: std::sync::Mutex<std::collections::HashMap<(Uuid, String), serde_json::Value>>,
+        secrets: std::collections::HashMap<(Uuid, String), String>,
+    }
+
+    impl SkillHostServices for MemoryHostServices {
+        fn kv_get<'a>(
+            &'a self,
+            bot_id: Uuid,
+            key: &'a str,
+        ) -> BoxFuture<'a, Result<Option<serde_json::Value>, RepositoryError>> {
+            let value = self
+                .kv
+                .lock()
+                .unwrap()
+                .get(&(bot_id, key.to_string()))
+                .cloned();
+            Box::pin(async move { Ok(value) })
+        }
+
+        fn kv_set<'a>(
+            &'a self,
+            bot_id: Uuid,
+            key: &'a str,
+            value: serde_json::Value,
+        ) -> BoxFuture<'a, Result<(), RepositoryError>> {
+            self.kv
+                .lock()
+                .unwrap()
+                .insert((bot_id, key.to_string()), value);
+            Box::pin(async { Ok(()) })
+        }
+
+        fn get_secret<'a>(
+            &'a self,
+            bot_id: Uuid,
+            name: &'a str,
+        ) -> BoxFuture<'a, Result<Option<String>, RepositoryError>> {
+            let value = self.secrets.get(&(bot_id, name.to_string())).cloned();
+            Box::pin(async move { Ok(value) })
+        }
+    }
+
+    fn bot_state(
+        capabilities: &[Capability],
+        declared_secrets: &[&str],
+        bot_id: Uuid,
+        services: Arc<dyn SkillHostServices>,
+    ) -> SkillState {
+        SkillState {
+            ctx: WasiCtxBuilder::new().build(),
+            table: ResourceTable::new(),
+            capabilities: capabilities.iter().cloned().collect(),
+            invocation_id: Uuid::now_v7(),
+            skill_name: "kv-skill".to_string(),
+            bot_slug: "test-bot".to_string(),
+            bot_name: "Test Bot".to_string(),
+            bot_id: Some(bot_id),
+            declared_secrets: declared_secrets.iter().map(|s| s.to_string()).collect(),
+            host_services: Some(services),
+            fuel_initial: 1_000_000,
+            max_memory_bytes: 64 * 1024 * 1024,
+        }
+    }
+
+    #[tokio::test]
+    async fn host_kv_read_write_with_capability() {
+        let services: Arc<dyn SkillHostServices> = Arc::new(MemoryHostServices::default());
+        let bot_id = Uuid::now_v7();
+        let mut state = bot_state(
+            &[Capability::KvRead, Capability::KvWrite],
+            &[],
+            bot_id,
+            Arc::clone(&services),
+        );
+
+        host::Host::kv_set(
+            &mut state,
+            "last_run".to_string(),
+            r#"{"count":3}"#.to_string(),
+        )
+        .await
+        .expect("kv_set with KvWrite should succeed");
+        let value = host::Host::kv_get(&mut state, "last_run".to_string())
+            .await
+            .expect("kv_get with KvRead should succeed");
+        assert_eq!(value.as_deref(), Some(r#"{"count":3}"#));
+        assert_eq!(
+            host::Host::kv_get(&mut state, "missing".to_string()).await,
+            Ok(None)
+        );
+
+        // Values must be JSON text
+        let err = host::Host::kv_set(&mut state, "bad".to_string(), "not json".to_string())
+            .await
+            .unwrap_err();
+        assert!(err.contains("not valid JSON"), "{err}");
+
+        // Another bot's skill does not see this bot's keys
+        let mut other = bot_state(&[Capability::KvRead], &[], Uuid::now_v7(), services);
+        assert_eq!(
+            host::Host::kv_get(&mut other, "last_run".to_string()).await,
+            Ok(None)
+        );
+    }
+
+    #[tokio::test]
+    async fn host_denies_kv_without_capability() {
+        let services: Arc<dyn SkillHostServices> = Arc::new(MemoryHostServices::default());
+        let mut state = bot_state(&[Capability::KvRead], &[], Uuid::now_v7(), services);
+
+        let err = host::Host::kv_set(&mut state, "key".to_string(), "1".to_string())
+            .await
+            .unwrap_err();
+        assert!(err.contains("capability denied"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn host_get_secret_requires_capability_and_declaration() {
+        let bot_id = Uuid::now_v7();
+        let mut services = MemoryHostServices::default();
+        services
+            .secrets
+            .insert((bot_id, "GITHUB_TOKEN".to_string()), "ghp_test".to_string());
+        services
+            .secrets
+            .insert((bot_id, "OTHER_TOKEN".to_string()), "other".to_string());
+        let services: Arc<dyn SkillHostServices> = Arc::new(services);
+
+        // KV permission only: secret access denied even though declared
+        let mut kv_only = bot_state(
+            &[Capability::KvRead],
+            &["GITHUB_TOKEN"],
+            bot_id,
+            Arc::clone(&services),
+        );
+        let err = host::Host::get_secret(&mut kv_only, "GITHUB_TOKEN".to_string())
+            .await
+            .unwrap_err();
+        assert!(err.contains("capability denied"), "{err}");
+
+        let mut state = bot_state(
+            &[Capability::GetSecret],
+            &["GITHUB_TOKEN"],
+            bot_id,
+            services,
+        );
+        assert_eq!(
+            host::Host::get_secret(&mut state, "GITHUB_TOKEN".to_string()).await,
+            Ok("ghp_test".to_string())
+        );
+        let err = host::Host::get_secret(&mut state, "OTHER_TOKEN".to_string())
+            .await
+            .unwrap_err();
+        assert!(err.contains("not declared"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn host_kv_unavailable_without_invoking_bot() {
+        let mut caps = HashSet::new();
+        caps.insert(Capability::KvRead);
+
+        let mut state = SkillState {
+            ctx: WasiCtxBuilder::new().build(),
+            table: ResourceTable::new(),
+            capabilities: caps,
+            invocation_id: Uuid::now_v7(),
+            skill_name: "kv-skill".to_string(),
+            bot_slug: String::new(),
+            bot_name: String::new(),
+            bot_id: None,
+            declared_secrets: HashSet::new(),
+            host_services: Some(Arc::new(MemoryHostServices::default())),
+            fuel_initial: 1_000_000,
+            max_memory_bytes: 64 * 1024 * 1024,
+        };
+
+        let err = host::Host::kv_get(&mut state, "key".to_string())
+            .await
+            .unwrap_err();
+        assert!(err.contains("not running for a bot"), "{err}");
+    }
+
     #[test]
     fn host_denies_read_env_without_capability() {
         let mut state = SkillState {
@@ -656,6 +1055,9 @@ mod tests {
             skill_name: "denied-skill".to_string(),
             bot_slug: "test-bot".to_string(),
             bot_name: "Test Bot".to_string(),
+            bot_id: None,
+            declared_secrets: HashSet::new(),
+            host_services: None,
             fuel_initial: 1_000_000,
             max_memory_bytes: 64 * 1024 * 1024,
         };
@@ -678,6 +1080,9 @@ mod tests {
             skill_name: "env-skill".to_string(),
             bot_slug: "test-bot".to_string(),
             bot_name: "Test Bot".to_string(),
+            bot_id: None,
+            declared_secrets: HashSet::new(),
+            host_services: None,
             fuel_initial: 1_000_000,
             max_memory_bytes: 64 * 1024 * 1024,
         };
@@ -700,6 +1105,9 @@ mod tests {
             skill_name: "mem-skill".to_string(),
             bot_slug: "test-bot".to_string(),
             bot_name: "Test Bot".to_string(),
+            bot_id: None,
+            declared_secrets: HashSet::new(),
+            host_services: None,
             fuel_initial: 1_000_000,
             max_memory_bytes: 64 * 1024 * 1024,
         };
@@ -736,6 +1144,9 @@ mod tests {
             skill_name: "file-skill".to_string(),
             bot_slug: "test-bot".to_string(),
             bot_name: "Test Bot".to_string(),
+            bot_id: None,
+            declared_secrets: HashSet::new(),
+            host_services: None,
             fuel_initial: 1_000_000,
             max_memory_bytes: 64 * 1024 * 1024,
         };
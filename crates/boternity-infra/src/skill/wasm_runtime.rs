@@ -11,10 +11,16 @@ use wasmtime::{component::Component, Config, Engine};
 // Generate Rust bindings from the WIT skill-plugin world.
 // Exports use async so call_execute/call_get_name/call_get_description
 // are async fn (required when engine has async_support(true)).
-// Imports stay sync since host functions use blocking I/O.
+// Imports stay sync since host functions use blocking I/O, except the KV and
+// secret imports, which await the bot's KV store and the secret service.
 wasmtime::component::bindgen!({
     world: "skill-plugin",
     path: "../../wit/boternity-skill.wit",
+    imports: {
+        "boternity:skill/host/kv-get": async,
+        "boternity:skill/host/kv-set": async,
+        "boternity:skill/host/get-secret": async,
+    },
     exports: { default: async },
 });
 
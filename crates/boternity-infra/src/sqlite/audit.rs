@@ -1,7 +1,8 @@
 //! SQLite memory audit log implementation.
 //!
 //! Records memory operations (add, delete, share, revoke, merge) for auditing.
-//! Provides query methods for bot-scoped and memory-scoped audit trails.
+//! Provides query methods for bot-scoped and memory-scoped audit trails, and
+//! a filtered, paginated query across all bots for export.
 
 use boternity_types::error::RepositoryError;
 use boternity_types::memory::{AuditAction, MemoryAuditEntry};
@@ -11,6 +12,23 @@ use uuid::Uuid;
 
 use super::pool::DatabasePool;
 
+/// Filter criteria for querying the audit log across all bots.
+#[derive(Debug, Clone, Default)]
+pub struct AuditLogFilter {
+    /// Only entries recorded at or after this time.
+    pub since: Option<DateTime<Utc>>,
+    /// Filter by actor (exact match: `system`, `user`, or a bot slug).
+    pub actor: Option<String>,
+    /// Filter by action.
+    pub action: Option<AuditAction>,
+    /// Filter by bot.
+    pub bot_id: Option<Uuid>,
+    /// Maximum number of results.
+    pub limit: Option<i64>,
+    /// Number of results to skip (offset pagination).
+    pub offset: Option<i64>,
+}
+
 /// SQLite-backed memory audit log.
 pub struct SqliteAuditLog {
     pool: DatabasePool,
@@ -79,6 +97,62 @@ impl SqliteAuditLog {
 
         rows_to_entries(&rows)
     }
+
+    /// Query audit entries matching `filter`, most recent first.
+    pub async fn query(
+        &self,
+        filter: &AuditLogFilter,
+    ) -> Result<Vec<MemoryAuditEntry>, RepositoryError> {
+        let mut sql = String::from("SELECT * FROM memory_audit_log");
+        let mut conditions: Vec<&str> = Vec::new();
+        let mut binds: Vec<String> = Vec::new();
+
+        if let Some(ref since) = filter.since {
+            conditions.push("created_at >= ?");
+            binds.push(format_datetime(since));
+        }
+        if let Some(ref actor) = filter.actor {
+            conditions.push("actor = ?");
+            binds.push(actor.clone());
+        }
+        if let Some(ref action) = filter.action {
+            conditions.push("action = ?");
+            binds.push(action.to_string());
+        }
+        if let Some(ref bot_id) = filter.bot_id {
+            conditions.push("bot_id = ?");
+            binds.push(bot_id.to_string());
+        }
+
+        if !conditions.is_empty() {
+            sql.push_str(" WHERE ");
+            sql.push_str(&conditions.join(" AND "));
+        }
+        // Tie-break on the (time-ordered) UUIDv7 id for stable pages
+        sql.push_str(" ORDER BY created_at DESC, id DESC");
+
+        // Pagination (SQLite needs a LIMIT before OFFSET; -1 means no limit)
+        if filter.limit.is_some() || filter.offset.is_some() {
+            sql.push_str(" LIMIT ? OFFSET ?");
+        }
+
+        let mut query = sqlx::query(&sql);
+        for value in &binds {
+            query = query.bind(value);
+        }
+        if filter.limit.is_some() || filter.offset.is_some() {
+            query = query
+                .bind(filter.limit.unwrap_or(-1))
+                .bind(filter.offset.unwrap_or(0));
+        }
+
+        let rows = query
+            .fetch_all(&self.pool.reader)
+            .await
+            .map_err(|e| RepositoryError::Query(e.to_string()))?;
+
+        rows_to_entries(&rows)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -283,4 +357,53 @@ mod tests {
         let entries = audit.get_for_bot(&bot_id, Some(3)).await.unwrap();
         assert_eq!(entries.len(), 3);
     }
+
+    #[tokio::test]
+    async fn test_query_filters_and_paginates() {
+        let pool = test_pool().await;
+        let audit = SqliteAuditLog::new(pool.clone());
+        let bot_id = setup_bot(&pool).await;
+        let other_bot = setup_bot(&pool).await;
+        let start = Utc::now() - chrono::Duration::hours(3);
+
+        for (hours, bot, action, actor) in [
+            (0, bot_id, AuditAction::Share, "user"),
+            (1, bot_id, AuditAction::Revoke, "user"),
+            (2, other_bot, AuditAction::Share, "user"),
+            (3, bot_id, AuditAction::Share, "system"),
+        ] {
+            let mut entry = make_audit(bot, Uuid::now_v7(), action, actor);
+            entry.created_at = start + chrono::Duration::hours(hours);
+            audit.log(&entry).await.unwrap();
+        }
+
+        let all = audit.query(&AuditLogFilter::default()).await.unwrap();
+        assert_eq!(all.len(), 4);
+        assert!(all.windows(2).all(|w| w[0].created_at >= w[1].created_at));
+
+        let filter = AuditLogFilter {
+            actor: Some("user".to_string()),
+            action: Some(AuditAction::Share),
+            ..Default::default()
+        };
+        let shares = audit.query(&filter).await.unwrap();
+        assert_eq!(shares.len(), 2);
+
+        let filter = AuditLogFilter {
+            since: Some(start + chrono::Duration::minutes(90)),
+            ..Default::default()
+        };
+        assert_eq!(audit.query(&filter).await.unwrap().len(), 2);
+
+        let filter = AuditLogFilter {
+            bot_id: Some(bot_id),
+            limit: Some(2),
+            offset: Some(1),
+            ..Default::default()
+        };
+        let page = audit.query(&filter).await.unwrap();
+        assert_eq!(page.len(), 2);
+        assert_eq!(page[0].action, AuditAction::Revoke);
+        assert_eq!(page[1].actor, "user");
+    }
 }
@@ -4,7 +4,7 @@
 
 use boternity_core::repository::bot::{BotFilter, BotRepository};
 use boternity_core::repository::SortOrder;
-use boternity_types::bot::{Bot, BotCategory, BotId, BotStatus};
+use boternity_types::bot::{Bot, BotCategory, BotId, BotStatus, BotStatusTransition};
 use boternity_types::error::RepositoryError;
 use chrono::{DateTime, Utc};
 use sqlx::Row;
@@ -39,6 +39,7 @@ struct BotRow {
     created_at: String,
     updated_at: String,
     last_active_at: Option<String>,
+    deleted_at: Option<String>,
 }
 
 impl BotRow {
@@ -58,6 +59,7 @@ impl BotRow {
             created_at: row.try_get("created_at")?,
             updated_at: row.try_get("updated_at")?,
             last_active_at: row.try_get("last_active_at")?,
+            deleted_at: row.try_get("deleted_at")?,
         })
     }
 
@@ -87,6 +89,11 @@ impl BotRow {
             .as_deref()
             .map(parse_datetime)
             .transpose()?;
+        let deleted_at = self
+            .deleted_at
+            .as_deref()
+            .map(parse_datetime)
+            .transpose()?;
 
         Ok(Bot {
             id,
@@ -103,6 +110,7 @@ impl BotRow {
             created_at,
             updated_at,
             last_active_at,
+            deleted_at,
         })
     }
 }
@@ -123,8 +131,8 @@ impl BotRepository for SqliteBotRepository {
             serde_json::to_string(&bot.tags).map_err(|e| RepositoryError::Query(e.to_string()))?;
 
         let result = sqlx::query(
-            "INSERT INTO bots (id, slug, name, description, status, category, tags, user_id, conversation_count, total_tokens_used, version_count, created_at, updated_at, last_active_at)
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            "INSERT INTO bots (id, slug, name, description, status, category, tags, user_id, conversation_count, total_tokens_used, version_count, created_at, updated_at, last_active_at, deleted_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
         )
         .bind(bot.id.to_string())
         .bind(&bot.slug)
@@ -140,6 +148,7 @@ impl BotRepository for SqliteBotRepository {
         .bind(format_datetime(&bot.created_at))
         .bind(format_datetime(&bot.updated_at))
         .bind(bot.last_active_at.as_ref().map(format_datetime))
+        .bind(bot.deleted_at.as_ref().map(format_datetime))
         .execute(&self.pool.writer)
         .await;
 
@@ -201,6 +210,9 @@ impl BotRepository for SqliteBotRepository {
         if let Some(ref category) = filter.category {
             conditions.push(format!("category = '{}'", category));
         }
+        if !filter.include_deleted {
+            conditions.push("deleted_at IS NULL".to_string());
+        }
 
         if !conditions.is_empty() {
             sql.push_str(" WHERE ");
@@ -249,7 +261,7 @@ impl BotRepository for SqliteBotRepository {
             serde_json::to_string(&bot.tags).map_err(|e| RepositoryError::Query(e.to_string()))?;
 
         let result = sqlx::query(
-            "UPDATE bots SET slug = ?, name = ?, description = ?, status = ?, category = ?, tags = ?, user_id = ?, conversation_count = ?, total_tokens_used = ?, version_count = ?, updated_at = ?, last_active_at = ?
+            "UPDATE bots SET slug = ?, name = ?, description = ?, status = ?, category = ?, tags = ?, user_id = ?, conversation_count = ?, total_tokens_used = ?, version_count = ?, updated_at = ?, last_active_at = ?, deleted_at = ?
              WHERE id = ?",
         )
         .bind(&bot.slug)
@@ -264,6 +276,7 @@ impl BotRepository for SqliteBotRepository {
         .bind(bot.version_count)
         .bind(format_datetime(&bot.updated_at))
         .bind(bot.last_active_at.as_ref().map(format_datetime))
+        .bind(bot.deleted_at.as_ref().map(format_datetime))
         .bind(bot.id.to_string())
         .execute(&self.pool.writer)
         .await
@@ -276,6 +289,21 @@ impl BotRepository for SqliteBotRepository {
         Ok(bot.clone())
     }
 
+    async fn touch_last_active(&self, id: &BotId, at: DateTime<Utc>) -> Result<(), RepositoryError> {
+        let result = sqlx::query("UPDATE bots SET last_active_at = ? WHERE id = ?")
+            .bind(format_datetime(&at))
+            .bind(id.to_string())
+            .execute(&self.pool.writer)
+            .await
+            .map_err(|e| RepositoryError::Query(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        Ok(())
+    }
+
     async fn delete(&self, id: &BotId) -> Result<(), RepositoryError> {
         let result = sqlx::query("DELETE FROM bots WHERE id = ?")
             .bind(id.to_string())
@@ -289,6 +317,62 @@ impl BotRepository for SqliteBotRepository {
 
         Ok(())
     }
+
+    async fn record_status_transition(
+        &self,
+        transition: &BotStatusTransition,
+    ) -> Result<(), RepositoryError> {
+        sqlx::query(
+            "INSERT INTO bot_status_history (id, bot_id, from_status, to_status, reason, created_at)
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(transition.id.to_string())
+        .bind(transition.bot_id.to_string())
+        .bind(transition.from_status.to_string())
+        .bind(transition.to_status.to_string())
+        .bind(&transition.reason)
+        .bind(format_datetime(&transition.created_at))
+        .execute(&self.pool.writer)
+        .await
+        .map_err(|e| RepositoryError::Query(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn list_status_transitions(
+        &self,
+        bot_id: &BotId,
+    ) -> Result<Vec<BotStatusTransition>, RepositoryError> {
+        let rows = sqlx::query(
+            "SELECT id, from_status, to_status, reason, created_at FROM bot_status_history
+             WHERE bot_id = ? ORDER BY created_at ASC, id ASC",
+        )
+        .bind(bot_id.to_string())
+        .fetch_all(&self.pool.reader)
+        .await
+        .map_err(|e| RepositoryError::Query(e.to_string()))?;
+
+        rows.iter()
+            .map(|row| {
+                let get = |col: &str| -> Result<String, RepositoryError> {
+                    row.try_get(col)
+                        .map_err(|e| RepositoryError::Query(e.to_string()))
+                };
+                Ok(BotStatusTransition {
+                    id: get("id")?
+                        .parse()
+                        .map_err(|e| RepositoryError::Query(format!("invalid transition id: {e}")))?,
+                    bot_id: bot_id.clone(),
+                    from_status: get("from_status")?.parse().map_err(RepositoryError::Query)?,
+                    to_status: get("to_status")?.parse().map_err(RepositoryError::Query)?,
+                    reason: row
+                        .try_get("reason")
+                        .map_err(|e| RepositoryError::Query(e.to_string()))?,
+                    created_at: parse_datetime(&get("created_at")?)?,
+                })
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -323,6 +407,7 @@ mod tests {
             created_at: now,
             updated_at: now,
             last_active_at: None,
+            deleted_at: None,
         }
     }
 
@@ -442,6 +527,51 @@ mod tests {
         assert!(found.is_none());
     }
 
+    #[tokio::test]
+    async fn test_soft_deleted_bots_hidden_from_list() {
+        let pool = test_pool().await;
+        let repo = SqliteBotRepository::new(pool);
+
+        let mut bot = make_bot("Ghost");
+        repo.create(&bot).await.unwrap();
+        repo.create(&make_bot("Visible")).await.unwrap();
+
+        bot.deleted_at = Some(Utc::now());
+        repo.update(&bot).await.unwrap();
+
+        let listed = repo.list(None).await.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].name, "Visible");
+        let all = repo
+            .list(Some(BotFilter {
+                include_deleted: true,
+                ..Default::default()
+            }))
+            .await
+            .unwrap();
+        assert_eq!(all.len(), 2);
+
+        // Still reachable by slug so it can be restored
+        let found = repo.get_by_slug(&bot.slug).await.unwrap().unwrap();
+        assert!(found.deleted_at.is_some());
+
+        bot.deleted_at = None;
+        repo.update(&bot).await.unwrap();
+        assert_eq!(repo.list(None).await.unwrap().len(), 2);
+
+        // Purge is irrevocable: gone even from the include-deleted listing
+        repo.delete(&bot.id).await.unwrap();
+        assert!(repo.get_by_slug(&bot.slug).await.unwrap().is_none());
+        let all = repo
+            .list(Some(BotFilter {
+                include_deleted: true,
+                ..Default::default()
+            }))
+            .await
+            .unwrap();
+        assert_eq!(all.len(), 1);
+    }
+
     #[tokio::test]
     async fn test_slug_conflict() {
         let pool = test_pool().await;
@@ -463,4 +593,89 @@ mod tests {
         let err = repo.delete(&BotId::new()).await.unwrap_err();
         assert!(matches!(err, RepositoryError::NotFound));
     }
+
+    #[tokio::test]
+    async fn test_status_transition_history() {
+        let pool = test_pool().await;
+        let repo = SqliteBotRepository::new(pool);
+        let bot = make_bot("Historian");
+        repo.create(&bot).await.unwrap();
+
+        assert!(repo.list_status_transitions(&bot.id).await.unwrap().is_empty());
+
+        for (from, to, reason) in [
+            (BotStatus::Active, BotStatus::Disabled, Some("maintenance")),
+            (BotStatus::Disabled, BotStatus::Archived, None),
+        ] {
+            repo.record_status_transition(&BotStatusTransition {
+                id: uuid::Uuid::now_v7(),
+                bot_id: bot.id.clone(),
+                from_status: from,
+                to_status: to,
+                reason: reason.map(String::from),
+                created_at: Utc::now(),
+            })
+            .await
+            .unwrap();
+        }
+
+        let history = repo.list_status_transitions(&bot.id).await.unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].from_status, BotStatus::Active);
+        assert_eq!(history[0].to_status, BotStatus::Disabled);
+        assert_eq!(history[0].reason.as_deref(), Some("maintenance"));
+        assert_eq!(history[1].to_status, BotStatus::Archived);
+        assert!(history[1].reason.is_none());
+
+        // History is removed with the bot
+        repo.delete(&bot.id).await.unwrap();
+        assert!(repo.list_status_transitions(&bot.id).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_touch_last_active_updates_timestamp() {
+        let pool = test_pool().await;
+        let repo = SqliteBotRepository::new(pool);
+        let bot = make_bot("Chatty");
+        repo.create(&bot).await.unwrap();
+
+        let at = Utc::now();
+        repo.touch_last_active(&bot.id, at).await.unwrap();
+
+        let found = repo.get_by_id(&bot.id).await.unwrap().unwrap();
+        assert_eq!(found.last_active_at.map(|t| t.timestamp_micros()), Some(at.timestamp_micros()));
+        // Only last_active_at changes
+        assert_eq!(found.updated_at.timestamp_micros(), bot.updated_at.timestamp_micros());
+
+        let missing = repo.touch_last_active(&BotId::new(), at).await;
+        assert!(matches!(missing, Err(RepositoryError::NotFound)));
+    }
+
+    #[tokio::test]
+    async fn test_sort_by_last_active_orders_recent_first() {
+        let pool = test_pool().await;
+        let repo = SqliteBotRepository::new(pool);
+        let idle = make_bot("Idle");
+        let older = make_bot("Older");
+        let recent = make_bot("Recent");
+        for bot in [&idle, &older, &recent] {
+            repo.create(bot).await.unwrap();
+        }
+
+        let now = Utc::now();
+        repo.touch_last_active(&older.id, now - chrono::Duration::hours(2)).await.unwrap();
+        repo.touch_last_active(&recent.id, now).await.unwrap();
+
+        let bots = repo
+            .list(Some(BotFilter {
+                sort_by: Some("last_active_at".to_string()),
+                sort_order: Some(SortOrder::Desc),
+                ..Default::default()
+            }))
+            .await
+            .unwrap();
+        let names: Vec<&str> = bots.iter().map(|b| b.name.as_str()).collect();
+        // Never-used bots (NULL) sort last
+        assert_eq!(names, vec!["Recent", "Older", "Idle"]);
+    }
 }
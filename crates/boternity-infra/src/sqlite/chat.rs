@@ -5,9 +5,12 @@
 //! split reader/writer pool usage.
 
 use boternity_core::chat::repository::ChatRepository;
-use boternity_types::chat::{ChatMessage, ChatSession, ContextSummary, SessionStatus};
+use boternity_types::chat::{
+    ChatMessage, ChatSearchHit, ChatSession, ContextSummary, SessionStatus,
+};
 use boternity_types::error::RepositoryError;
 use boternity_types::llm::MessageRole;
+use boternity_types::quota::BotUsage;
 use chrono::{DateTime, Utc};
 use sqlx::Row;
 use uuid::Uuid;
@@ -39,9 +42,12 @@ struct ChatSessionRow {
     ended_at: Option<String>,
     total_input_tokens: i64,
     total_output_tokens: i64,
+    total_cost_usd: f64,
     message_count: i64,
     model: String,
     status: String,
+    summary: Option<String>,
+    summarized_messages: i64,
 }
 
 impl ChatSessionRow {
@@ -54,9 +60,12 @@ impl ChatSessionRow {
             ended_at: row.try_get("ended_at")?,
             total_input_tokens: row.try_get("total_input_tokens")?,
             total_output_tokens: row.try_get("total_output_tokens")?,
+            total_cost_usd: row.try_get("total_cost_usd")?,
             message_count: row.try_get("message_count")?,
             model: row.try_get("model")?,
             status: row.try_get("status")?,
+            summary: row.try_get("summary")?,
+            summarized_messages: row.try_get("summarized_messages")?,
         })
     }
 
@@ -84,9 +93,12 @@ impl ChatSessionRow {
             ended_at,
             total_input_tokens: self.total_input_tokens as u32,
             total_output_tokens: self.total_output_tokens as u32,
+            total_cost_usd: self.total_cost_usd,
             message_count: self.message_count as u32,
             model: self.model,
             status,
+            summary: self.summary,
+            summarized_messages: self.summarized_messages as u32,
         })
     }
 }
@@ -204,6 +216,25 @@ fn format_datetime(dt: &DateTime<Utc>) -> String {
     dt.to_rfc3339()
 }
 
+/// Build an FTS5 MATCH expression from free text.
+///
+/// Each word is quoted (so FTS5 operators and punctuation in user input are
+/// treated literally) and the words are OR-ed, letting BM25 rank documents
+/// that match more of them higher. Returns `None` if there are no words.
+fn fts_match_query(query: &str) -> Option<String> {
+    let terms: Vec<String> = query
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| format!("\"{}\"", word.to_lowercase()))
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(" OR "))
+}
+
+fn parse_uuid(s: &str) -> Result<Uuid, RepositoryError> {
+    s.parse()
+        .map_err(|e| RepositoryError::Query(format!("invalid uuid: {e}")))
+}
+
 // ---------------------------------------------------------------------------
 // ChatRepository implementation
 // ---------------------------------------------------------------------------
@@ -214,8 +245,8 @@ impl ChatRepository for SqliteChatRepository {
         session: &ChatSession,
     ) -> Result<ChatSession, RepositoryError> {
         sqlx::query(
-            r#"INSERT INTO chat_sessions (id, bot_id, title, started_at, ended_at, total_input_tokens, total_output_tokens, message_count, model, status)
-               VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
+            r#"INSERT INTO chat_sessions (id, bot_id, title, started_at, ended_at, total_input_tokens, total_output_tokens, total_cost_usd, message_count, model, status, summary, summarized_messages)
+               VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
         )
         .bind(session.id.to_string())
         .bind(session.bot_id.to_string())
@@ -224,9 +255,12 @@ impl ChatRepository for SqliteChatRepository {
         .bind(session.ended_at.as_ref().map(format_datetime))
         .bind(session.total_input_tokens as i64)
         .bind(session.total_output_tokens as i64)
+        .bind(session.total_cost_usd)
         .bind(session.message_count as i64)
         .bind(&session.model)
         .bind(session.status.to_string())
+        .bind(&session.summary)
+        .bind(session.summarized_messages as i64)
         .execute(&self.pool.writer)
         .await
         .map_err(|e| RepositoryError::Query(e.to_string()))?;
@@ -258,15 +292,19 @@ impl ChatRepository for SqliteChatRepository {
         let result = sqlx::query(
             r#"UPDATE chat_sessions
                SET title = ?, ended_at = ?, total_input_tokens = ?, total_output_tokens = ?,
-                   message_count = ?, status = ?
+                   total_cost_usd = ?, message_count = ?, status = ?, summary = ?,
+                   summarized_messages = ?
                WHERE id = ?"#,
         )
         .bind(&session.title)
         .bind(session.ended_at.as_ref().map(format_datetime))
         .bind(session.total_input_tokens as i64)
         .bind(session.total_output_tokens as i64)
+        .bind(session.total_cost_usd)
         .bind(session.message_count as i64)
         .bind(session.status.to_string())
+        .bind(&session.summary)
+        .bind(session.summarized_messages as i64)
         .bind(session.id.to_string())
         .execute(&self.pool.writer)
         .await
@@ -505,6 +543,113 @@ impl ChatRepository for SqliteChatRepository {
 
         Ok(count as u64)
     }
+
+    async fn search(&self, query: &str, limit: i64) -> Result<Vec<ChatSearchHit>, RepositoryError> {
+        let Some(match_query) = fts_match_query(query) else {
+            return Ok(Vec::new());
+        };
+
+        let rows = sqlx::query(
+            r#"SELECT * FROM (
+                   SELECT s.id AS session_id, s.bot_id AS bot_id, NULL AS message_id,
+                          s.title AS snippet, bm25(chat_sessions_fts) AS rank
+                   FROM chat_sessions_fts
+                   JOIN chat_sessions s ON s.rowid = chat_sessions_fts.rowid
+                   WHERE chat_sessions_fts MATCH ?1
+                   UNION ALL
+                   SELECT m.session_id, s.bot_id, m.id,
+                          snippet(chat_messages_fts, 0, '[', ']', '...', 12), bm25(chat_messages_fts)
+                   FROM chat_messages_fts
+                   JOIN chat_messages m ON m.rowid = chat_messages_fts.rowid
+                   JOIN chat_sessions s ON s.id = m.session_id
+                   WHERE chat_messages_fts MATCH ?1
+               )
+               ORDER BY rank ASC
+               LIMIT ?2"#,
+        )
+        .bind(&match_query)
+        .bind(limit)
+        .fetch_all(&self.pool.reader)
+        .await
+        .map_err(|e| RepositoryError::Query(e.to_string()))?;
+
+        rows.iter()
+            .map(|row| {
+                let get = |col: &str| -> Result<String, RepositoryError> {
+                    row.try_get(col)
+                        .map_err(|e| RepositoryError::Query(e.to_string()))
+                };
+                let message_id: Option<String> = row
+                    .try_get("message_id")
+                    .map_err(|e| RepositoryError::Query(e.to_string()))?;
+                Ok(ChatSearchHit {
+                    session_id: parse_uuid(&get("session_id")?)?,
+                    bot_id: parse_uuid(&get("bot_id")?)?,
+                    message_id: message_id.as_deref().map(parse_uuid).transpose()?,
+                    snippet: get("snippet")?,
+                    rank: row
+                        .try_get("rank")
+                        .map_err(|e| RepositoryError::Query(e.to_string()))?,
+                })
+            })
+            .collect()
+    }
+
+    async fn get_bot_usage(
+        &self,
+        bot_id: &Uuid,
+        window_start: DateTime<Utc>,
+    ) -> Result<Option<BotUsage>, RepositoryError> {
+        let row = sqlx::query(
+            "SELECT requests, tokens FROM bot_usage WHERE bot_id = ? AND window_start = ?",
+        )
+        .bind(bot_id.to_string())
+        .bind(format_datetime(&window_start))
+        .fetch_optional(&self.pool.reader)
+        .await
+        .map_err(|e| RepositoryError::Query(e.to_string()))?;
+
+        row.map(|row| {
+            let requests: i64 = row
+                .try_get("requests")
+                .map_err(|e| RepositoryError::Query(e.to_string()))?;
+            let tokens: i64 = row
+                .try_get("tokens")
+                .map_err(|e| RepositoryError::Query(e.to_string()))?;
+            Ok(BotUsage {
+                bot_id: *bot_id,
+                window_start,
+                requests: requests as u64,
+                tokens: tokens as u64,
+            })
+        })
+        .transpose()
+    }
+
+    async fn add_bot_usage(
+        &self,
+        bot_id: &Uuid,
+        window_start: DateTime<Utc>,
+        requests: u64,
+        tokens: u64,
+    ) -> Result<(), RepositoryError> {
+        sqlx::query(
+            r#"INSERT INTO bot_usage (bot_id, window_start, requests, tokens)
+               VALUES (?, ?, ?, ?)
+               ON CONFLICT (bot_id, window_start) DO UPDATE SET
+                   requests = requests + excluded.requests,
+                   tokens = tokens + excluded.tokens"#,
+        )
+        .bind(bot_id.to_string())
+        .bind(format_datetime(&window_start))
+        .bind(requests as i64)
+        .bind(tokens as i64)
+        .execute(&self.pool.writer)
+        .await
+        .map_err(|e| RepositoryError::Query(e.to_string()))?;
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -530,9 +675,12 @@ mod tests {
             ended_at: None,
             total_input_tokens: 0,
             total_output_tokens: 0,
+            total_cost_usd: 0.0,
             message_count: 0,
             model: "claude-sonnet-4-20250514".to_string(),
             status: SessionStatus::Active,
+            summary: None,
+            summarized_messages: 0,
         }
     }
 
@@ -609,6 +757,7 @@ mod tests {
         session.ended_at = Some(Utc::now());
         session.total_input_tokens = 500;
         session.total_output_tokens = 1000;
+        session.total_cost_usd = 0.0165;
         repo.update_session(&session).await.unwrap();
 
         let found = repo.get_session(&session.id).await.unwrap().unwrap();
@@ -617,6 +766,7 @@ mod tests {
         assert!(found.ended_at.is_some());
         assert_eq!(found.total_input_tokens, 500);
         assert_eq!(found.total_output_tokens, 1000);
+        assert!((found.total_cost_usd - 0.0165).abs() < 1e-9);
     }
 
     #[tokio::test]
@@ -815,4 +965,233 @@ mod tests {
         assert_eq!(latest.messages_end, 10);
         assert_eq!(latest.token_count, 150);
     }
+
+    #[tokio::test]
+    async fn test_rolling_summary_rehydrates_resumed_session() {
+        use boternity_core::agent::context::AgentContext;
+        use boternity_core::chat::service::ChatService;
+        use boternity_core::llm::token_budget::TokenBudget;
+        use boternity_types::agent::AgentConfig;
+
+        use crate::sqlite::memory::SqliteMemoryRepository;
+
+        let pool = test_pool().await;
+        let bot_id = Uuid::now_v7();
+        sqlx::query(
+            "INSERT INTO bots (id, slug, name, description, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(bot_id.to_string())
+        .bind("rolling-bot")
+        .bind("Rolling Bot")
+        .bind("")
+        .bind(Utc::now().to_rfc3339())
+        .bind(Utc::now().to_rfc3339())
+        .execute(&pool.writer)
+        .await
+        .unwrap();
+
+        let service = ChatService::new(
+            SqliteChatRepository::new(pool.clone()),
+            SqliteMemoryRepository::new(pool),
+        );
+        let session = service
+            .create_session(bot_id, "claude-sonnet-4-20250514".to_string())
+            .await
+            .unwrap();
+        for turn in ["one", "two", "three"] {
+            service
+                .save_user_message(session.id, format!("question {turn}"))
+                .await
+                .unwrap();
+            service
+                .save_assistant_message(
+                    session.id,
+                    format!("answer {turn}"),
+                    "claude-sonnet-4-20250514".to_string(),
+                    0,
+                    0,
+                    "end_turn".to_string(),
+                    0,
+                )
+                .await
+                .unwrap();
+        }
+
+        service
+            .update_rolling_summary(
+                &session.id,
+                Some("The user asked two questions about gardening.".to_string()),
+                4,
+            )
+            .await
+            .unwrap();
+        let loaded = service.get_session(&session.id).await.unwrap().unwrap();
+        assert_eq!(
+            loaded.summary.as_deref(),
+            Some("The user asked two questions about gardening.")
+        );
+        assert_eq!(loaded.summarized_messages, 4);
+
+        let mut context = AgentContext::new(
+            AgentConfig {
+                bot_id,
+                bot_name: "Rolling Bot".to_string(),
+                bot_slug: "rolling-bot".to_string(),
+                bot_emoji: None,
+                model: "claude-sonnet-4-20250514".to_string(),
+                temperature: 0.7,
+                max_tokens: 1024,
+                guard_untrusted_content: false,
+            },
+            String::new(),
+            String::new(),
+            String::new(),
+            vec![],
+            TokenBudget::new(200_000),
+        );
+        let replayed = service
+            .rehydrate_context(&session.id, &mut context)
+            .await
+            .unwrap();
+
+        // Only the turn after the summarized prefix is replayed
+        assert_eq!(replayed, 2);
+        let messages = context.build_messages();
+        assert_eq!(messages[0].content, "question three");
+        assert_eq!(messages[1].content, "answer three");
+        assert!(context.system_prompt.contains("<conversation_summary>"));
+        assert!(context
+            .system_prompt
+            .contains("The user asked two questions about gardening."));
+    }
+
+    #[test]
+    fn test_fts_match_query_quotes_terms() {
+        assert_eq!(
+            fts_match_query("Rust OR \"async\"*").as_deref(),
+            Some("\"rust\" OR \"or\" OR \"async\"")
+        );
+        assert!(fts_match_query("  ?! ").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_search_matches_titles_and_messages() {
+        let pool = test_pool().await;
+        let repo = SqliteChatRepository::new(pool.clone());
+
+        let bot_id = Uuid::now_v7();
+        sqlx::query(
+            "INSERT INTO bots (id, slug, name, description, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(bot_id.to_string())
+        .bind("test-bot")
+        .bind("Test Bot")
+        .bind("A test bot")
+        .bind(Utc::now().to_rfc3339())
+        .bind(Utc::now().to_rfc3339())
+        .execute(&pool.writer)
+        .await
+        .unwrap();
+
+        let mut session = make_session(bot_id);
+        repo.create_session(&session).await.unwrap();
+        session.title = Some("Kubernetes upgrade plan".to_string());
+        repo.update_session(&session).await.unwrap();
+
+        let message = make_message(session.id, MessageRole::User, "How do I drain a kubernetes node?");
+        repo.save_message(&message).await.unwrap();
+        repo.save_message(&make_message(session.id, MessageRole::Assistant, "Use kubectl drain."))
+            .await
+            .unwrap();
+
+        let hits = repo.search("kubernetes", 10).await.unwrap();
+        assert_eq!(hits.len(), 2);
+        assert!(hits.iter().all(|h| h.bot_id == bot_id && h.session_id == session.id));
+        assert!(hits.iter().any(|h| h.message_id.is_none() && h.snippet == "Kubernetes upgrade plan"));
+        let message_hit = hits.iter().find(|h| h.message_id == Some(message.id)).unwrap();
+        assert!(message_hit.snippet.contains("[kubernetes]"));
+
+        assert!(repo.search("terraform", 10).await.unwrap().is_empty());
+
+        // Deleting the session removes its messages from the index
+        repo.delete_session(&session.id).await.unwrap();
+        assert!(repo.search("kubernetes", 10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_bot_quota_blocks_until_window_resets() {
+        use boternity_core::chat::service::ChatService;
+        use boternity_types::config::{QuotaConfig, UsageLimits};
+        use boternity_types::error::QuotaError;
+        use boternity_types::quota::QuotaKind;
+        use chrono::Duration;
+
+        use crate::sqlite::memory::SqliteMemoryRepository;
+
+        let pool = test_pool().await;
+        let bot_id = Uuid::now_v7();
+        sqlx::query(
+            "INSERT INTO bots (id, slug, name, description, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(bot_id.to_string())
+        .bind("quota-bot")
+        .bind("Quota Bot")
+        .bind("")
+        .bind(Utc::now().to_rfc3339())
+        .bind(Utc::now().to_rfc3339())
+        .execute(&pool.writer)
+        .await
+        .unwrap();
+
+        let service = ChatService::new(
+            SqliteChatRepository::new(pool.clone()),
+            SqliteMemoryRepository::new(pool.clone()),
+        )
+        .with_quota(QuotaConfig {
+            default: UsageLimits {
+                max_requests: Some(3),
+                max_tokens: Some(1000),
+            },
+            ..Default::default()
+        });
+        let now = DateTime::parse_from_rfc3339("2026-03-01T10:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        // Accumulate usage up to the request cap
+        for _ in 0..3 {
+            service
+                .check_quota_at(&bot_id, "quota-bot", now)
+                .await
+                .unwrap();
+            service.record_usage_at(&bot_id, 1, 200, now).await.unwrap();
+        }
+
+        let usage = SqliteChatRepository::new(pool)
+            .get_bot_usage(&bot_id, now - Duration::hours(10))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!((usage.requests, usage.tokens), (3, 600));
+
+        let err = service
+            .check_quota_at(&bot_id, "quota-bot", now + Duration::hours(13))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            QuotaError::Exceeded {
+                kind: QuotaKind::Requests,
+                used: 3,
+                limit: 3,
+                ..
+            }
+        ));
+
+        // The next window starts with a clean slate
+        service
+            .check_quota_at(&bot_id, "quota-bot", now + Duration::hours(14))
+            .await
+            .unwrap();
+    }
 }
@@ -141,13 +141,14 @@ impl SqliteFileMetadataStore {
     /// Save a file version record.
     pub async fn save_version(&self, version: &FileVersion) -> Result<(), RepositoryError> {
         sqlx::query(
-            r#"INSERT INTO bot_file_versions (id, file_id, version, size_bytes, created_at)
-               VALUES (?, ?, ?, ?, ?)"#,
+            r#"INSERT INTO bot_file_versions (id, file_id, version, size_bytes, content_hash, created_at)
+               VALUES (?, ?, ?, ?, ?, ?)"#,
         )
         .bind(version.id.to_string())
         .bind(version.file_id.to_string())
         .bind(version.version as i64)
         .bind(version.size_bytes as i64)
+        .bind(&version.content_hash)
         .bind(format_datetime(&version.created_at))
         .execute(&self.pool.writer)
         .await
@@ -178,6 +179,54 @@ impl SqliteFileMetadataStore {
 
         Ok(versions)
     }
+
+    /// Get a single version record of a file.
+    pub async fn get_version(
+        &self,
+        file_id: &Uuid,
+        version: u32,
+    ) -> Result<Option<FileVersion>, RepositoryError> {
+        let row = sqlx::query("SELECT * FROM bot_file_versions WHERE file_id = ? AND version = ?")
+            .bind(file_id.to_string())
+            .bind(version as i64)
+            .fetch_optional(&self.pool.reader)
+            .await
+            .map_err(|e| RepositoryError::Query(e.to_string()))?;
+
+        match row {
+            Some(row) => {
+                let version_row = VersionRow::from_row(&row)
+                    .map_err(|e| RepositoryError::Query(e.to_string()))?;
+                Ok(Some(version_row.into_file_version()?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Count how many version records of a bot's files reference a content hash.
+    ///
+    /// Used to decide whether a content-addressed blob can be removed from disk.
+    pub async fn count_hash_references(
+        &self,
+        bot_id: &Uuid,
+        content_hash: &str,
+    ) -> Result<u64, RepositoryError> {
+        let row = sqlx::query(
+            r#"SELECT COUNT(*) AS refs FROM bot_file_versions v
+               JOIN bot_files f ON f.id = v.file_id
+               WHERE f.bot_id = ? AND v.content_hash = ?"#,
+        )
+        .bind(bot_id.to_string())
+        .bind(content_hash)
+        .fetch_one(&self.pool.reader)
+        .await
+        .map_err(|e| RepositoryError::Query(e.to_string()))?;
+
+        let refs: i64 = row
+            .try_get("refs")
+            .map_err(|e| RepositoryError::Query(e.to_string()))?;
+        Ok(refs as u64)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -238,6 +287,7 @@ struct VersionRow {
     file_id: String,
     version: i64,
     size_bytes: i64,
+    content_hash: Option<String>,
     created_at: String,
 }
 
@@ -248,6 +298,7 @@ impl VersionRow {
             file_id: row.try_get("file_id")?,
             version: row.try_get("version")?,
             size_bytes: row.try_get("size_bytes")?,
+            content_hash: row.try_get("content_hash")?,
             created_at: row.try_get("created_at")?,
         })
     }
@@ -264,6 +315,7 @@ impl VersionRow {
             file_id,
             version: self.version as u32,
             size_bytes: self.size_bytes as u64,
+            content_hash: self.content_hash,
             created_at,
         })
     }
@@ -465,6 +517,7 @@ mod tests {
             file_id: file.id,
             version: 1,
             size_bytes: 1024,
+            content_hash: None,
             created_at: Utc::now(),
         };
         let v2 = FileVersion {
@@ -472,6 +525,7 @@ mod tests {
             file_id: file.id,
             version: 2,
             size_bytes: 2048,
+            content_hash: None,
             created_at: Utc::now(),
         };
 
@@ -487,6 +541,39 @@ mod tests {
         assert_eq!(versions[1].size_bytes, 1024);
     }
 
+    #[tokio::test]
+    async fn test_get_version_and_count_hash_references() {
+        let pool = test_pool().await;
+        let store = SqliteFileMetadataStore::new(pool.clone());
+        let bot_id = setup_bot(&pool).await;
+
+        let a = make_file(bot_id, "a.txt");
+        let b = make_file(bot_id, "b.txt");
+        store.save_file(&a).await.unwrap();
+        store.save_file(&b).await.unwrap();
+
+        for (file_id, version) in [(a.id, 1), (a.id, 2), (b.id, 1)] {
+            store
+                .save_version(&FileVersion {
+                    id: Uuid::now_v7(),
+                    file_id,
+                    version,
+                    size_bytes: 3,
+                    content_hash: Some("abc123".to_string()),
+                    created_at: Utc::now(),
+                })
+                .await
+                .unwrap();
+        }
+
+        let v2 = store.get_version(&a.id, 2).await.unwrap().unwrap();
+        assert_eq!(v2.content_hash.as_deref(), Some("abc123"));
+        assert!(store.get_version(&a.id, 9).await.unwrap().is_none());
+
+        assert_eq!(store.count_hash_references(&bot_id, "abc123").await.unwrap(), 3);
+        assert_eq!(store.count_hash_references(&bot_id, "other").await.unwrap(), 0);
+    }
+
     #[tokio::test]
     async fn test_delete_file_cascades_versions() {
         let pool = test_pool().await;
@@ -501,6 +588,7 @@ mod tests {
             file_id: file.id,
             version: 1,
             size_bytes: 512,
+            content_hash: None,
             created_at: Utc::now(),
         };
         store.save_version(&v1).await.unwrap();
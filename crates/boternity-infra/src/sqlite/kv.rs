@@ -2,11 +2,14 @@
 //!
 //! Implements `KvStore` from `boternity-core` using sqlx with split read/write pools.
 //! Values are stored as JSON text and deserialized on read.
+//!
+//! Entries may carry an optional `expires_at`. Expired entries are treated as
+//! absent by every read and are purged lazily when encountered.
 
 use boternity_core::storage::kv_store::KvStore;
 use boternity_types::error::RepositoryError;
 use boternity_types::storage::KvEntry;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, SecondsFormat, Utc};
 use sqlx::Row;
 use uuid::Uuid;
 
@@ -22,6 +25,21 @@ impl SqliteKvStore {
     pub fn new(pool: DatabasePool) -> Self {
         Self { pool }
     }
+
+    /// Delete a single expired entry (no-op if it was refreshed meanwhile).
+    async fn purge_if_expired(&self, bot_id: &Uuid, key: &str) -> Result<(), RepositoryError> {
+        sqlx::query(
+            "DELETE FROM bot_kv_store WHERE bot_id = ? AND key = ? AND expires_at IS NOT NULL AND expires_at <= ?",
+        )
+        .bind(bot_id.to_string())
+        .bind(key)
+        .bind(format_expiry(&Utc::now()))
+        .execute(&self.pool.writer)
+        .await
+        .map_err(|e| RepositoryError::Query(e.to_string()))?;
+
+        Ok(())
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -34,6 +52,7 @@ struct KvRow {
     value: String,
     created_at: String,
     updated_at: String,
+    expires_at: Option<String>,
 }
 
 impl KvRow {
@@ -44,6 +63,7 @@ impl KvRow {
             value: row.try_get("value")?,
             created_at: row.try_get("created_at")?,
             updated_at: row.try_get("updated_at")?,
+            expires_at: row.try_get("expires_at")?,
         })
     }
 
@@ -54,6 +74,7 @@ impl KvRow {
             .map_err(|e| RepositoryError::Query(format!("invalid JSON value: {e}")))?;
         let created_at = parse_datetime(&self.created_at)?;
         let updated_at = parse_datetime(&self.updated_at)?;
+        let expires_at = self.expires_at.as_deref().map(parse_datetime).transpose()?;
 
         Ok(KvEntry {
             bot_id,
@@ -61,6 +82,7 @@ impl KvRow {
             value,
             created_at,
             updated_at,
+            expires_at,
         })
     }
 }
@@ -79,6 +101,18 @@ fn format_datetime(dt: &DateTime<Utc>) -> String {
     dt.to_rfc3339()
 }
 
+/// Fixed-width expiry format so `expires_at` compares correctly as text in SQL.
+fn format_expiry(dt: &DateTime<Utc>) -> String {
+    dt.to_rfc3339_opts(SecondsFormat::Micros, true)
+}
+
+fn is_expired(expires_at: Option<&str>) -> Result<bool, RepositoryError> {
+    match expires_at {
+        Some(s) => Ok(parse_datetime(s)? <= Utc::now()),
+        None => Ok(false),
+    }
+}
+
 // ---------------------------------------------------------------------------
 // KvStore implementation
 // ---------------------------------------------------------------------------
@@ -89,24 +123,7 @@ impl KvStore for SqliteKvStore {
         bot_id: &Uuid,
         key: &str,
     ) -> Result<Option<serde_json::Value>, RepositoryError> {
-        let row = sqlx::query("SELECT value FROM bot_kv_store WHERE bot_id = ? AND key = ?")
-            .bind(bot_id.to_string())
-            .bind(key)
-            .fetch_optional(&self.pool.reader)
-            .await
-            .map_err(|e| RepositoryError::Query(e.to_string()))?;
-
-        match row {
-            Some(row) => {
-                let value_str: String = row
-                    .try_get("value")
-                    .map_err(|e| RepositoryError::Query(e.to_string()))?;
-                let value: serde_json::Value = serde_json::from_str(&value_str)
-                    .map_err(|e| RepositoryError::Query(format!("invalid JSON value: {e}")))?;
-                Ok(Some(value))
-            }
-            None => Ok(None),
-        }
+        Ok(self.get_entry(bot_id, key).await?.map(|entry| entry.value))
     }
 
     async fn set(
@@ -115,20 +132,39 @@ impl KvStore for SqliteKvStore {
         key: &str,
         value: &serde_json::Value,
     ) -> Result<(), RepositoryError> {
-        let now = format_datetime(&Utc::now());
+        self.set_with_ttl(bot_id, key, value, None).await
+    }
+
+    async fn set_with_ttl(
+        &self,
+        bot_id: &Uuid,
+        key: &str,
+        value: &serde_json::Value,
+        ttl: Option<std::time::Duration>,
+    ) -> Result<(), RepositoryError> {
+        let now_dt = Utc::now();
+        let now = format_datetime(&now_dt);
         let value_str = serde_json::to_string(value)
             .map_err(|e| RepositoryError::Query(format!("failed to serialize value: {e}")))?;
+        let expires_at = ttl
+            .map(|ttl| {
+                chrono::Duration::from_std(ttl)
+                    .map(|d| format_expiry(&(now_dt + d)))
+                    .map_err(|e| RepositoryError::Query(format!("invalid TTL: {e}")))
+            })
+            .transpose()?;
 
         sqlx::query(
-            r#"INSERT INTO bot_kv_store (bot_id, key, value, created_at, updated_at)
-               VALUES (?, ?, ?, ?, ?)
-               ON CONFLICT (bot_id, key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at"#,
+            r#"INSERT INTO bot_kv_store (bot_id, key, value, created_at, updated_at, expires_at)
+               VALUES (?, ?, ?, ?, ?, ?)
+               ON CONFLICT (bot_id, key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at, expires_at = excluded.expires_at"#,
         )
         .bind(bot_id.to_string())
         .bind(key)
         .bind(&value_str)
         .bind(&now)
         .bind(&now)
+        .bind(&expires_at)
         .execute(&self.pool.writer)
         .await
         .map_err(|e| RepositoryError::Query(e.to_string()))?;
@@ -136,6 +172,82 @@ impl KvStore for SqliteKvStore {
         Ok(())
     }
 
+    async fn increment(
+        &self,
+        bot_id: &Uuid,
+        key: &str,
+        delta: i64,
+    ) -> Result<i64, RepositoryError> {
+        // Read-modify-write inside a transaction on the single-connection
+        // writer pool, so concurrent increments are serialized.
+        let mut tx = self
+            .pool
+            .writer
+            .begin()
+            .await
+            .map_err(|e| RepositoryError::Query(e.to_string()))?;
+
+        let row = sqlx::query(
+            "SELECT value, expires_at FROM bot_kv_store WHERE bot_id = ? AND key = ?",
+        )
+        .bind(bot_id.to_string())
+        .bind(key)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| RepositoryError::Query(e.to_string()))?;
+
+        let (current, expires_at) = match row {
+            Some(row) => {
+                let value_str: String = row
+                    .try_get("value")
+                    .map_err(|e| RepositoryError::Query(e.to_string()))?;
+                let expires_at: Option<String> = row
+                    .try_get("expires_at")
+                    .map_err(|e| RepositoryError::Query(e.to_string()))?;
+
+                if is_expired(expires_at.as_deref())? {
+                    (0, None)
+                } else {
+                    let value: serde_json::Value = serde_json::from_str(&value_str)
+                        .map_err(|e| RepositoryError::Query(format!("invalid JSON value: {e}")))?;
+                    let current = value.as_i64().ok_or_else(|| {
+                        RepositoryError::Conflict(format!(
+                            "cannot increment key '{key}': value {value} is not an integer"
+                        ))
+                    })?;
+                    (current, expires_at)
+                }
+            }
+            None => (0, None),
+        };
+
+        let next = current.checked_add(delta).ok_or_else(|| {
+            RepositoryError::Conflict(format!("cannot increment key '{key}': integer overflow"))
+        })?;
+        let now = format_datetime(&Utc::now());
+
+        sqlx::query(
+            r#"INSERT INTO bot_kv_store (bot_id, key, value, created_at, updated_at, expires_at)
+               VALUES (?, ?, ?, ?, ?, ?)
+               ON CONFLICT (bot_id, key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at, expires_at = excluded.expires_at"#,
+        )
+        .bind(bot_id.to_string())
+        .bind(key)
+        .bind(next.to_string())
+        .bind(&now)
+        .bind(&now)
+        .bind(&expires_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| RepositoryError::Query(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| RepositoryError::Query(e.to_string()))?;
+
+        Ok(next)
+    }
+
     async fn delete(&self, bot_id: &Uuid, key: &str) -> Result<(), RepositoryError> {
         sqlx::query("DELETE FROM bot_kv_store WHERE bot_id = ? AND key = ?")
             .bind(bot_id.to_string())
@@ -148,11 +260,14 @@ impl KvStore for SqliteKvStore {
     }
 
     async fn list_keys(&self, bot_id: &Uuid) -> Result<Vec<String>, RepositoryError> {
-        let rows = sqlx::query("SELECT key FROM bot_kv_store WHERE bot_id = ? ORDER BY key")
-            .bind(bot_id.to_string())
-            .fetch_all(&self.pool.reader)
-            .await
-            .map_err(|e| RepositoryError::Query(e.to_string()))?;
+        let rows = sqlx::query(
+            "SELECT key FROM bot_kv_store WHERE bot_id = ? AND (expires_at IS NULL OR expires_at > ?) ORDER BY key",
+        )
+        .bind(bot_id.to_string())
+        .bind(format_expiry(&Utc::now()))
+        .fetch_all(&self.pool.reader)
+        .await
+        .map_err(|e| RepositoryError::Query(e.to_string()))?;
 
         let mut keys = Vec::with_capacity(rows.len());
         for row in &rows {
@@ -177,14 +292,17 @@ impl KvStore for SqliteKvStore {
             .await
             .map_err(|e| RepositoryError::Query(e.to_string()))?;
 
-        match row {
-            Some(row) => {
-                let kv_row = KvRow::from_row(&row)
-                    .map_err(|e| RepositoryError::Query(e.to_string()))?;
-                Ok(Some(kv_row.into_entry()?))
-            }
-            None => Ok(None),
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        let kv_row = KvRow::from_row(&row).map_err(|e| RepositoryError::Query(e.to_string()))?;
+        if is_expired(kv_row.expires_at.as_deref())? {
+            self.purge_if_expired(bot_id, key).await?;
+            return Ok(None);
         }
+
+        Ok(Some(kv_row.into_entry()?))
     }
 }
 
@@ -442,4 +560,150 @@ mod tests {
             Some(serde_json::json!({"a": {"b": {"c": true}}}))
         );
     }
+
+    #[tokio::test]
+    async fn test_ttl_expiry_returns_none_and_purges() {
+        let pool = test_pool().await;
+        let store = SqliteKvStore::new(pool.clone());
+        let bot_id = setup_bot(&pool).await;
+
+        store
+            .set_with_ttl(
+                &bot_id,
+                "session",
+                &serde_json::json!("token"),
+                Some(std::time::Duration::from_millis(50)),
+            )
+            .await
+            .unwrap();
+        store
+            .set(&bot_id, "durable", &serde_json::json!(true))
+            .await
+            .unwrap();
+
+        let entry = store.get_entry(&bot_id, "session").await.unwrap().unwrap();
+        assert!(entry.expires_at.is_some());
+        assert_eq!(store.list_keys(&bot_id).await.unwrap(), vec!["durable", "session"]);
+
+        tokio::time::sleep(std::time::Duration::from_millis(80)).await;
+
+        assert!(store.get(&bot_id, "session").await.unwrap().is_none());
+        assert_eq!(store.list_keys(&bot_id).await.unwrap(), vec!["durable"]);
+
+        // The expired row was lazily purged from the table
+        let remaining: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM bot_kv_store WHERE bot_id = ? AND key = ?")
+                .bind(bot_id.to_string())
+                .bind("session")
+                .fetch_one(&pool.reader)
+                .await
+                .unwrap();
+        assert_eq!(remaining, 0);
+    }
+
+    #[tokio::test]
+    async fn test_set_without_ttl_clears_expiry() {
+        let pool = test_pool().await;
+        let store = SqliteKvStore::new(pool.clone());
+        let bot_id = setup_bot(&pool).await;
+
+        store
+            .set_with_ttl(
+                &bot_id,
+                "k",
+                &serde_json::json!(1),
+                Some(std::time::Duration::from_secs(60)),
+            )
+            .await
+            .unwrap();
+        store.set(&bot_id, "k", &serde_json::json!(2)).await.unwrap();
+
+        let entry = store.get_entry(&bot_id, "k").await.unwrap().unwrap();
+        assert!(entry.expires_at.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_increment_creates_and_adds() {
+        let pool = test_pool().await;
+        let store = SqliteKvStore::new(pool.clone());
+        let bot_id = setup_bot(&pool).await;
+
+        assert_eq!(store.increment(&bot_id, "hits", 1).await.unwrap(), 1);
+        assert_eq!(store.increment(&bot_id, "hits", 5).await.unwrap(), 6);
+        assert_eq!(store.increment(&bot_id, "hits", -2).await.unwrap(), 4);
+        assert_eq!(
+            store.get(&bot_id, "hits").await.unwrap(),
+            Some(serde_json::json!(4))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_increment_concurrent_calls_are_atomic() {
+        let pool = test_pool().await;
+        let store = std::sync::Arc::new(SqliteKvStore::new(pool.clone()));
+        let bot_id = setup_bot(&pool).await;
+
+        let mut handles = Vec::new();
+        for _ in 0..20 {
+            let store = store.clone();
+            handles.push(tokio::spawn(async move {
+                store.increment(&bot_id, "counter", 1).await.unwrap()
+            }));
+        }
+
+        let mut results = Vec::new();
+        for handle in handles {
+            results.push(handle.await.unwrap());
+        }
+        results.sort();
+
+        assert_eq!(results, (1..=20).collect::<Vec<i64>>());
+        assert_eq!(
+            store.get(&bot_id, "counter").await.unwrap(),
+            Some(serde_json::json!(20))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_increment_non_numeric_errors() {
+        let pool = test_pool().await;
+        let store = SqliteKvStore::new(pool.clone());
+        let bot_id = setup_bot(&pool).await;
+
+        store
+            .set(&bot_id, "name", &serde_json::json!("Alice"))
+            .await
+            .unwrap();
+
+        let err = store.increment(&bot_id, "name", 1).await.unwrap_err();
+        assert!(matches!(err, RepositoryError::Conflict(_)));
+
+        // Value is unchanged
+        assert_eq!(
+            store.get(&bot_id, "name").await.unwrap(),
+            Some(serde_json::json!("Alice"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_increment_after_expiry_restarts_at_zero() {
+        let pool = test_pool().await;
+        let store = SqliteKvStore::new(pool.clone());
+        let bot_id = setup_bot(&pool).await;
+
+        store
+            .set_with_ttl(
+                &bot_id,
+                "window",
+                &serde_json::json!(10),
+                Some(std::time::Duration::from_millis(30)),
+            )
+            .await
+            .unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(60)).await;
+
+        assert_eq!(store.increment(&bot_id, "window", 1).await.unwrap(), 1);
+        let entry = store.get_entry(&bot_id, "window").await.unwrap().unwrap();
+        assert!(entry.expires_at.is_none());
+    }
 }
@@ -195,13 +195,50 @@ impl MemoryRepository for SqliteMemoryRepository {
         Ok(())
     }
 
+    async fn get_memory(&self, memory_id: &Uuid) -> Result<Option<MemoryEntry>, RepositoryError> {
+        let row = sqlx::query("SELECT * FROM session_memories WHERE id = ?")
+            .bind(memory_id.to_string())
+            .fetch_optional(&self.pool.reader)
+            .await
+            .map_err(|e| RepositoryError::Query(e.to_string()))?;
+
+        match row {
+            Some(row) => {
+                let entry_row = MemoryEntryRow::from_row(&row)
+                    .map_err(|e| RepositoryError::Query(e.to_string()))?;
+                Ok(Some(entry_row.into_entry()?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn update_memory(&self, entry: &MemoryEntry) -> Result<(), RepositoryError> {
+        let result = sqlx::query(
+            "UPDATE session_memories SET fact = ?, category = ?, importance = ? WHERE id = ?",
+        )
+        .bind(&entry.fact)
+        .bind(entry.category.to_string())
+        .bind(entry.importance as i64)
+        .bind(entry.id.to_string())
+        .execute(&self.pool.writer)
+        .await
+        .map_err(|e| RepositoryError::Query(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        Ok(())
+    }
+
     async fn get_memories(
         &self,
         bot_id: &Uuid,
         limit: Option<i64>,
     ) -> Result<Vec<MemoryEntry>, RepositoryError> {
         let mut sql = String::from(
-            "SELECT * FROM session_memories WHERE bot_id = ? ORDER BY importance DESC, created_at DESC",
+            "SELECT * FROM session_memories WHERE bot_id = ? AND superseded_by IS NULL \
+             ORDER BY importance DESC, created_at DESC",
         );
 
         if let Some(limit) = limit {
@@ -238,6 +275,25 @@ impl MemoryRepository for SqliteMemoryRepository {
         Ok(())
     }
 
+    async fn mark_superseded(
+        &self,
+        memory_id: &Uuid,
+        superseded_by: &Uuid,
+    ) -> Result<(), RepositoryError> {
+        let result = sqlx::query("UPDATE session_memories SET superseded_by = ? WHERE id = ?")
+            .bind(superseded_by.to_string())
+            .bind(memory_id.to_string())
+            .execute(&self.pool.writer)
+            .await
+            .map_err(|e| RepositoryError::Query(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+
+        Ok(())
+    }
+
     async fn delete_all_memories(&self, bot_id: &Uuid) -> Result<u64, RepositoryError> {
         let result = sqlx::query("DELETE FROM session_memories WHERE bot_id = ?")
             .bind(bot_id.to_string())
@@ -313,6 +369,32 @@ impl MemoryRepository for SqliteMemoryRepository {
         Ok(pending)
     }
 
+    async fn get_due_pending_extractions(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<PendingExtraction>, RepositoryError> {
+        let rows = sqlx::query(
+            "SELECT * FROM pending_memory_extractions WHERE attempt_count < 3 ORDER BY created_at",
+        )
+        .fetch_all(&self.pool.reader)
+        .await
+        .map_err(|e| RepositoryError::Query(e.to_string()))?;
+
+        // RFC 3339 strings with varying precision don't compare reliably in
+        // SQL, so the due filter runs on parsed timestamps.
+        let mut pending = Vec::with_capacity(rows.len());
+        for row in &rows {
+            let pending_row = PendingExtractionRow::from_row(row)
+                .map_err(|e| RepositoryError::Query(e.to_string()))?;
+            let job = pending_row.into_pending()?;
+            if job.next_attempt_at <= now {
+                pending.push(job);
+            }
+        }
+
+        Ok(pending)
+    }
+
     async fn delete_pending_extraction(&self, id: &Uuid) -> Result<(), RepositoryError> {
         let result = sqlx::query("DELETE FROM pending_memory_extractions WHERE id = ?")
             .bind(id.to_string())
@@ -356,7 +438,15 @@ impl MemoryRepository for SqliteMemoryRepository {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::sqlite::chat::SqliteChatRepository;
     use crate::sqlite::pool::DatabasePool;
+    use crate::vector::lance::LanceVectorStore;
+    use crate::vector::memory::LanceVectorMemoryStore;
+    use crate::vector::schema::EMBEDDING_DIMENSION;
+    use boternity_core::chat::service::ChatService;
+    use boternity_core::memory::box_embedder::BoxEmbedder;
+    use boternity_core::memory::box_vector::BoxVectorMemoryStore;
+    use boternity_core::memory::embedder::Embedder;
     use boternity_types::chat::SessionStatus;
 
     async fn test_pool() -> DatabasePool {
@@ -478,6 +568,112 @@ mod tests {
         assert!(memories.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_superseded_memories_excluded_from_recall() {
+        let pool = test_pool().await;
+        let repo = SqliteMemoryRepository::new(pool.clone());
+        let (bot_id, session_id) = setup_bot_and_session(&pool).await;
+
+        let nyc = make_memory(bot_id, session_id, "User lives in NYC", 4);
+        let berlin = make_memory(bot_id, session_id, "User lives in Berlin", 4);
+        repo.save_memory(&nyc).await.unwrap();
+        repo.save_memory(&berlin).await.unwrap();
+        repo.mark_superseded(&nyc.id, &berlin.id).await.unwrap();
+
+        let memories = repo.get_memories(&bot_id, None).await.unwrap();
+        assert_eq!(memories.len(), 1);
+        assert_eq!(memories[0].fact, "User lives in Berlin");
+
+        // The superseded memory is kept for history
+        let session_memories = repo.get_memories_by_session(&session_id).await.unwrap();
+        let old = session_memories.iter().find(|m| m.id == nyc.id).unwrap();
+        assert_eq!(old.superseded_by, Some(berlin.id));
+
+        assert!(matches!(
+            repo.mark_superseded(&Uuid::now_v7(), &berlin.id).await,
+            Err(RepositoryError::NotFound)
+        ));
+    }
+
+    /// Bag-of-words embedder: texts sharing words have high cosine similarity.
+    struct KeywordEmbedder;
+
+    impl Embedder for KeywordEmbedder {
+        async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, RepositoryError> {
+            Ok(texts
+                .iter()
+                .map(|text| {
+                    let mut vec = vec![0.0f32; EMBEDDING_DIMENSION as usize];
+                    for word in text.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()) {
+                        let hash = word
+                            .to_lowercase()
+                            .bytes()
+                            .fold(7usize, |h, b| h.wrapping_mul(31).wrapping_add(b as usize));
+                        vec[hash % vec.len()] += 1.0;
+                    }
+                    vec
+                })
+                .collect())
+        }
+
+        fn model_name(&self) -> &str {
+            "keyword"
+        }
+
+        fn dimension(&self) -> usize {
+            EMBEDDING_DIMENSION as usize
+        }
+    }
+
+    #[tokio::test]
+    async fn test_edit_memory_reembeds_for_recall() {
+        let pool = test_pool().await;
+        let (bot_id, session_id) = setup_bot_and_session(&pool).await;
+        let service = ChatService::new(
+            SqliteChatRepository::new(pool.clone()),
+            SqliteMemoryRepository::new(pool),
+        );
+        let dir = tempfile::tempdir().unwrap();
+        let vector_store = BoxVectorMemoryStore::new(LanceVectorMemoryStore::new(
+            LanceVectorStore::new(dir.path().join("vectors")).await.unwrap(),
+        ));
+        let embedder = BoxEmbedder::new(KeywordEmbedder);
+
+        let mut entry = make_memory(bot_id, session_id, "User lives in NYC", 3);
+        service.memory_repo().save_memory(&entry).await.unwrap();
+        service
+            .refresh_memory_embedding(&entry, &embedder, &vector_store)
+            .await
+            .unwrap();
+
+        entry.fact = "User lives in Berlin".to_string();
+        entry.importance = 5;
+        service.memory_repo().update_memory(&entry).await.unwrap();
+        service
+            .refresh_memory_embedding(&entry, &embedder, &vector_store)
+            .await
+            .unwrap();
+
+        let stored = service.memory_repo().get_memory(&entry.id).await.unwrap().unwrap();
+        assert_eq!(stored.fact, "User lives in Berlin");
+        assert_eq!(stored.importance, 5);
+
+        // One vector, embedded from the new text
+        assert_eq!(vector_store.count(&bot_id).await.unwrap(), 1);
+        let query = embedder.embed(&["Berlin".to_string()]).await.unwrap().remove(0);
+        let hits = vector_store.search(&bot_id, &query, 5, 0.3).await.unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].entry.id, entry.id);
+        assert_eq!(hits[0].entry.fact, "User lives in Berlin");
+        let stale = embedder.embed(&["NYC".to_string()]).await.unwrap().remove(0);
+        assert!(vector_store.search(&bot_id, &stale, 5, 0.3).await.unwrap().is_empty());
+
+        assert!(matches!(
+            service.memory_repo().update_memory(&make_memory(bot_id, session_id, "x", 1)).await,
+            Err(RepositoryError::NotFound)
+        ));
+    }
+
     #[tokio::test]
     async fn test_get_memories_by_session() {
         let pool = test_pool().await;
@@ -562,6 +758,176 @@ mod tests {
         assert!(pending_list.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_due_pending_extractions_across_bots() {
+        let pool = test_pool().await;
+        let repo = SqliteMemoryRepository::new(pool.clone());
+        let (bot_a, session_a) = setup_bot_and_session(&pool).await;
+        let (bot_b, session_b) = setup_bot_and_session(&pool).await;
+
+        let due = PendingExtraction {
+            id: Uuid::now_v7(),
+            session_id: session_a,
+            bot_id: bot_a,
+            attempt_count: 0,
+            last_attempt_at: None,
+            next_attempt_at: Utc::now() - chrono::Duration::minutes(1),
+            error_message: None,
+            created_at: Utc::now(),
+        };
+        let later = PendingExtraction {
+            id: Uuid::now_v7(),
+            session_id: session_b,
+            bot_id: bot_b,
+            next_attempt_at: Utc::now() + chrono::Duration::hours(1),
+            ..due.clone()
+        };
+        repo.save_pending_extraction(&due).await.unwrap();
+        repo.save_pending_extraction(&later).await.unwrap();
+
+        let found = repo.get_due_pending_extractions(Utc::now()).await.unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, due.id);
+
+        let found = repo
+            .get_due_pending_extractions(Utc::now() + chrono::Duration::hours(2))
+            .await
+            .unwrap();
+        assert_eq!(found.len(), 2);
+    }
+
+    /// Replies to every completion with a fixed body, or fails.
+    struct ScriptedProvider {
+        reply: Option<String>,
+        capabilities: boternity_types::llm::ProviderCapabilities,
+    }
+
+    impl ScriptedProvider {
+        fn new(reply: Option<&str>) -> boternity_core::llm::box_provider::BoxLlmProvider {
+            boternity_core::llm::box_provider::BoxLlmProvider::new(Self {
+                reply: reply.map(String::from),
+                capabilities: boternity_types::llm::ProviderCapabilities {
+                    streaming: false,
+                    tool_calling: false,
+                    vision: false,
+                    extended_thinking: false,
+                    max_context_tokens: 100_000,
+                    max_output_tokens: 4_096,
+                },
+            })
+        }
+    }
+
+    impl boternity_core::llm::provider::LlmProvider for ScriptedProvider {
+        fn name(&self) -> &str {
+            "scripted"
+        }
+
+        fn capabilities(&self) -> &boternity_types::llm::ProviderCapabilities {
+            &self.capabilities
+        }
+
+        async fn complete(
+            &self,
+            _request: &boternity_types::llm::CompletionRequest,
+        ) -> Result<boternity_types::llm::CompletionResponse, boternity_types::llm::LlmError>
+        {
+            match &self.reply {
+                Some(content) => Ok(boternity_types::llm::CompletionResponse {
+                    id: "resp".to_string(),
+                    content: content.clone(),
+                    model: "scripted".to_string(),
+                    stop_reason: boternity_types::llm::StopReason::EndTurn,
+                    usage: Default::default(),
+                }),
+                None => Err(boternity_types::llm::LlmError::Provider {
+                    message: "overloaded".to_string(),
+                }),
+            }
+        }
+
+        fn stream(
+            &self,
+            _request: boternity_types::llm::CompletionRequest,
+        ) -> std::pin::Pin<
+            Box<
+                dyn futures_util::Stream<
+                        Item = Result<
+                            boternity_types::llm::StreamEvent,
+                            boternity_types::llm::LlmError,
+                        >,
+                    > + Send
+                    + 'static,
+            >,
+        > {
+            Box::pin(futures_util::stream::empty())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_interrupted_extraction_retried_after_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let url = format!("sqlite://{}?mode=rwc", dir.path().join("test.db").display());
+
+        // First run: the session is marked pending, then the process "crashes"
+        // before extraction completes.
+        let (bot_id, session_id) = {
+            let pool = DatabasePool::new(&url).await.unwrap();
+            let (bot_id, session_id) = setup_bot_and_session(&pool).await;
+            let service = ChatService::new(
+                SqliteChatRepository::new(pool.clone()),
+                SqliteMemoryRepository::new(pool.clone()),
+            );
+            service
+                .save_user_message(session_id, "I moved to Berlin last month.".to_string())
+                .await
+                .unwrap();
+            let first = service.begin_extraction(bot_id, session_id).await.unwrap();
+            // A second extraction for the same session shares the marker
+            let second = service.begin_extraction(bot_id, session_id).await.unwrap();
+            assert_eq!(first.id, second.id);
+            pool.writer.close().await;
+            pool.reader.close().await;
+            (bot_id, session_id)
+        };
+
+        // Restart: the marker is found and retried.
+        let pool = DatabasePool::new(&url).await.unwrap();
+        let service = ChatService::new(
+            SqliteChatRepository::new(pool.clone()),
+            SqliteMemoryRepository::new(pool.clone()),
+        );
+        let due = service.due_extractions().await.unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].session_id, session_id);
+
+        // A failed retry is rescheduled, not dropped
+        let failing = ScriptedProvider::new(None);
+        assert_eq!(service.retry_extraction(&due[0], &failing).await.unwrap(), None);
+        assert!(service.due_extractions().await.unwrap().is_empty());
+        let pending = service.memory_repo().get_pending_extractions(&bot_id).await.unwrap();
+        assert_eq!(pending[0].attempt_count, 1);
+        assert_eq!(pending[0].error_message.as_deref(), Some("provider error: overloaded"));
+
+        let provider = ScriptedProvider::new(Some(
+            r#"[{"fact": "User lives in Berlin", "category": "fact", "importance": 4}]"#,
+        ));
+        let saved = service.retry_extraction(&pending[0], &provider).await.unwrap();
+        assert_eq!(saved, Some(1));
+
+        let memories = service.load_memories(&bot_id).await.unwrap();
+        assert_eq!(memories.len(), 1);
+        assert_eq!(memories[0].fact, "User lives in Berlin");
+        assert!(
+            service
+                .memory_repo()
+                .get_pending_extractions(&bot_id)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
     #[tokio::test]
     async fn test_pending_extraction_max_retries() {
         let pool = test_pool().await;
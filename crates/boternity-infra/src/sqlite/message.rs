@@ -6,7 +6,9 @@
 
 use boternity_core::repository::message::MessageRepository;
 use boternity_types::error::RepositoryError;
-use boternity_types::message::{BotMessage, BotSubscription, Channel, MessageRecipient};
+use boternity_types::message::{
+    BotMessage, BotSubscription, Channel, MessagePriority, MessageRecipient,
+};
 use chrono::{DateTime, Utc};
 use sqlx::Row;
 use uuid::Uuid;
@@ -40,6 +42,8 @@ struct BotMessageRow {
     body: String,
     reply_to: Option<String>,
     timestamp: String,
+    priority: String,
+    expires_at: Option<String>,
 }
 
 impl BotMessageRow {
@@ -55,6 +59,8 @@ impl BotMessageRow {
             body: row.try_get("body")?,
             reply_to: row.try_get("reply_to")?,
             timestamp: row.try_get("timestamp")?,
+            priority: row.try_get("priority")?,
+            expires_at: row.try_get("expires_at")?,
         })
     }
 
@@ -96,6 +102,8 @@ impl BotMessageRow {
             .transpose()?;
 
         let timestamp = parse_datetime(&self.timestamp)?;
+        let priority: MessagePriority = self.priority.parse().map_err(RepositoryError::Query)?;
+        let expires_at = self.expires_at.as_deref().map(parse_datetime).transpose()?;
 
         Ok(BotMessage {
             id,
@@ -106,6 +114,8 @@ impl BotMessageRow {
             body,
             timestamp,
             reply_to,
+            priority,
+            expires_at,
         })
     }
 }
@@ -146,8 +156,9 @@ impl MessageRepository for SqliteMessageRepository {
         sqlx::query(
             r#"INSERT INTO bot_messages
                (id, sender_bot_id, sender_bot_name, recipient_type, recipient_bot_id,
-                recipient_channel, message_type, body, reply_to, timestamp)
-               VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
+                recipient_channel, message_type, body, reply_to, timestamp, priority,
+                expires_at)
+               VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
         )
         .bind(msg.id.to_string())
         .bind(msg.sender_bot_id.to_string())
@@ -159,6 +170,8 @@ impl MessageRepository for SqliteMessageRepository {
         .bind(&body_json)
         .bind(msg.reply_to.as_ref().map(|id| id.to_string()))
         .bind(format_datetime(&msg.timestamp))
+        .bind(msg.priority.to_string())
+        .bind(msg.expires_at.as_ref().map(format_datetime))
         .execute(&self.pool.writer)
         .await
         .map_err(|e| RepositoryError::Query(e.to_string()))?;
@@ -379,6 +392,8 @@ mod tests {
             body: json!({"text": "Hello!"}),
             timestamp: Utc::now(),
             reply_to: None,
+            priority: MessagePriority::default(),
+            expires_at: None,
         }
     }
 
@@ -394,6 +409,8 @@ mod tests {
             body: json!({"headline": "Breaking news"}),
             timestamp: Utc::now(),
             reply_to: None,
+            priority: MessagePriority::default(),
+            expires_at: None,
         }
     }
 
@@ -408,13 +425,18 @@ mod tests {
         let bot_b = Uuid::now_v7();
 
         let msg1 = make_direct_message(bot_a, bot_b);
-        let msg2 = make_direct_message(bot_b, bot_a);
+        let mut msg2 = make_direct_message(bot_b, bot_a);
+        msg2.priority = MessagePriority::Urgent;
+        msg2.expires_at = Some(msg2.timestamp + chrono::Duration::minutes(5));
 
         repo.save_message(&msg1).await.unwrap();
         repo.save_message(&msg2).await.unwrap();
 
         let messages = repo.get_messages_between(&bot_a, &bot_b, 10).await.unwrap();
         assert_eq!(messages.len(), 2);
+        let urgent = messages.iter().find(|m| m.id == msg2.id).unwrap();
+        assert_eq!(urgent.priority, MessagePriority::Urgent);
+        assert_eq!(urgent.expires_at, msg2.expires_at);
     }
 
     #[tokio::test]
@@ -0,0 +1,131 @@
+//! Embedded schema migrations.
+//!
+//! The ordered SQL files in `migrations/` are compiled into the binary and
+//! applied in version order. Applied versions are recorded in the
+//! `_sqlx_migrations` table, so re-running is a no-op and startup only applies
+//! what is new. `bnity db migrate --dry-run` lists pending migrations without
+//! touching the database.
+
+use serde::Serialize;
+use sqlx::migrate::{MigrateError, Migrator};
+use sqlx::SqlitePool;
+
+/// All migrations in `migrations/`, embedded at compile time.
+pub static MIGRATOR: Migrator = sqlx::migrate!("../../migrations");
+
+/// Name of the table recording applied migration versions.
+const VERSION_TABLE: &str = "_sqlx_migrations";
+
+/// A known migration and whether it has been applied to the database.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MigrationStatus {
+    /// Migration version (the numeric filename prefix).
+    pub version: i64,
+    /// Human-readable description from the filename.
+    pub description: String,
+    /// Whether the version is recorded in the version table.
+    pub applied: bool,
+}
+
+/// List every embedded migration with its applied state, in version order.
+pub async fn migration_status(pool: &SqlitePool) -> Result<Vec<MigrationStatus>, sqlx::Error> {
+    let has_version_table: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?)",
+    )
+    .bind(VERSION_TABLE)
+    .fetch_one(pool)
+    .await?;
+
+    let applied: Vec<i64> = if has_version_table {
+        sqlx::query_scalar(&format!(
+            "SELECT version FROM {VERSION_TABLE} WHERE success = 1"
+        ))
+        .fetch_all(pool)
+        .await?
+    } else {
+        Vec::new()
+    };
+
+    Ok(MIGRATOR
+        .iter()
+        .filter(|m| m.migration_type.is_up_migration())
+        .map(|m| MigrationStatus {
+            version: m.version,
+            description: m.description.to_string(),
+            applied: applied.contains(&m.version),
+        })
+        .collect())
+}
+
+/// List migrations that have not been applied yet, in the order they would run.
+pub async fn pending_migrations(pool: &SqlitePool) -> Result<Vec<MigrationStatus>, sqlx::Error> {
+    Ok(migration_status(pool)
+        .await?
+        .into_iter()
+        .filter(|m| !m.applied)
+        .collect())
+}
+
+/// Apply all pending migrations and return the ones that ran.
+///
+/// Each migration runs in its own transaction. Fails if an applied
+/// migration's checksum no longer matches its file.
+pub async fn run_migrations(pool: &SqlitePool) -> Result<Vec<MigrationStatus>, MigrateError> {
+    let pending = pending_migrations(pool).await?;
+    MIGRATOR.run(pool).await?;
+    Ok(pending
+        .into_iter()
+        .map(|m| MigrationStatus { applied: true, ..m })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sqlite::pool::DatabasePool;
+
+    async fn fresh_writer(dir: &tempfile::TempDir) -> SqlitePool {
+        let url = format!("sqlite://{}?mode=rwc", dir.path().join("test.db").display());
+        DatabasePool::connect_writer(&url).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_fresh_database_has_all_migrations_pending() {
+        let dir = tempfile::tempdir().unwrap();
+        let pool = fresh_writer(&dir).await;
+
+        let pending = pending_migrations(&pool).await.unwrap();
+        assert_eq!(pending.len(), MIGRATOR.iter().count());
+        assert!(pending.windows(2).all(|w| w[0].version < w[1].version));
+
+        // Listing pending migrations does not create the schema
+        let tables: Vec<String> =
+            sqlx::query_scalar("SELECT name FROM sqlite_master WHERE type = 'table'")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        assert!(tables.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_run_migrations_is_idempotent() {
+        let dir = tempfile::tempdir().unwrap();
+        let pool = fresh_writer(&dir).await;
+
+        let applied = run_migrations(&pool).await.unwrap();
+        assert_eq!(applied.len(), MIGRATOR.iter().count());
+        assert!(applied.iter().all(|m| m.applied));
+        assert!(pending_migrations(&pool).await.unwrap().is_empty());
+
+        let bots: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM bots")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(bots, 0);
+
+        // Re-running applies nothing and leaves every migration applied
+        assert!(run_migrations(&pool).await.unwrap().is_empty());
+        let status = migration_status(&pool).await.unwrap();
+        assert!(status.iter().all(|m| m.applied));
+    }
+}
@@ -10,6 +10,7 @@ pub mod file_metadata;
 pub mod kv;
 pub mod memory;
 pub mod message;
+pub mod migrations;
 pub mod pool;
 pub mod provider_health;
 pub mod secret;
@@ -3,13 +3,98 @@
 //! SQLite allows only one writer at a time. This module provides a `DatabasePool`
 //! with a multi-connection reader pool for concurrent reads and a single-connection
 //! writer pool for serialized writes. Both use WAL journal mode and enforce foreign keys.
+//!
+//! Reporting queries that span several statements (dashboard stats, search) can
+//! use [`DatabasePool::read_snapshot`] to read one consistent version of the
+//! database while the writer keeps committing.
+//!
+//! Maintenance operations (`bnity db backup|vacuum|stats`) live here too.
 
+use serde::Serialize;
 use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePool, SqlitePoolOptions};
+use sqlx::{Sqlite, Transaction};
+use std::path::Path;
 use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use boternity_types::config::DatabaseConfig;
+
+use super::migrations::run_migrations;
+
+/// A read-only transaction pinned to a single WAL snapshot.
+///
+/// Run queries against it with `&mut *snapshot`. The snapshot is released
+/// when the transaction is dropped (or explicitly rolled back).
+pub type ReadSnapshot = Transaction<'static, Sqlite>;
+
+/// Row count and on-disk size of one table.
+#[derive(Debug, Clone, Serialize)]
+pub struct TableStats {
+    pub name: String,
+    pub rows: u64,
+    /// Bytes used by the table's pages, when the `dbstat` table is available.
+    pub bytes: Option<u64>,
+}
+
+/// Database size before and after a `VACUUM`.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct VacuumReport {
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+}
+
+impl VacuumReport {
+    /// Bytes returned to the filesystem.
+    pub fn reclaimed(&self) -> u64 {
+        self.bytes_before.saturating_sub(self.bytes_after)
+    }
+}
+
+/// Connection usage of one pool.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct PoolMetrics {
+    /// Configured maximum connections.
+    pub max_connections: u32,
+    /// Open connections (idle + in use).
+    pub size: u32,
+    /// Open connections waiting in the pool.
+    pub idle: u32,
+    /// Connections currently checked out by queries.
+    pub in_use: u32,
+    /// Time taken to check out a connection, if one was obtained.
+    pub acquire_wait_ms: Option<u64>,
+}
+
+impl PoolMetrics {
+    async fn sample(pool: &SqlitePool) -> Self {
+        let size = pool.size();
+        let idle = pool.num_idle() as u32;
+        let started = Instant::now();
+        let acquire_wait_ms = pool
+            .acquire()
+            .await
+            .ok()
+            .map(|_conn| started.elapsed().as_millis() as u64);
+        Self {
+            max_connections: pool.options().get_max_connections(),
+            size,
+            idle,
+            in_use: size.saturating_sub(idle),
+            acquire_wait_ms,
+        }
+    }
+}
+
+/// Connection usage of the reader and writer pools.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct DatabasePoolMetrics {
+    pub reader: PoolMetrics,
+    pub writer: PoolMetrics,
+}
 
 /// Split read/write pool for SQLite with WAL mode.
 ///
-/// - `reader`: Multi-connection pool (up to 8) for concurrent SELECT queries.
+/// - `reader`: Multi-connection pool (8 by default) for concurrent SELECT queries.
 /// - `writer`: Single-connection pool for serialized INSERT/UPDATE/DELETE.
 #[derive(Clone)]
 pub struct DatabasePool {
@@ -20,35 +105,167 @@ pub struct DatabasePool {
 impl DatabasePool {
     /// Create a new DatabasePool with split reader/writer connections.
     ///
-    /// Runs migrations automatically on the writer pool.
-    /// Both pools use WAL journal mode, foreign key enforcement, and 5-second busy timeout.
+    /// Uses the default [`DatabaseConfig`]: 8 readers, 30-second acquire
+    /// timeout, 5-second busy timeout.
     pub async fn new(database_url: &str) -> Result<Self, sqlx::Error> {
-        let base_opts = SqliteConnectOptions::from_str(database_url)?
-            .journal_mode(SqliteJournalMode::Wal)
-            .foreign_keys(true)
-            .busy_timeout(std::time::Duration::from_secs(5))
-            .create_if_missing(true);
-
-        let read_opts = base_opts.clone().read_only(true);
-        let write_opts = base_opts;
-
-        let writer = SqlitePoolOptions::new()
-            .max_connections(1)
-            .connect_with(write_opts)
-            .await?;
+        Self::with_config(database_url, &DatabaseConfig::default()).await
+    }
+
+    /// Create a DatabasePool sized by the `[database]` section of `config.toml`.
+    ///
+    /// Runs pending migrations on the writer pool before opening the reader pool.
+    /// Both pools use WAL journal mode and foreign key enforcement.
+    pub async fn with_config(database_url: &str, config: &DatabaseConfig) -> Result<Self, sqlx::Error> {
+        let writer = writer_pool(database_url, config).await?;
 
         // Run migrations on writer before opening reader pool
-        sqlx::migrate!("../../migrations")
-            .run(&writer)
-            .await?;
+        let applied = run_migrations(&writer).await?;
+        if !applied.is_empty() {
+            tracing::info!(count = applied.len(), "Applied database migrations");
+        }
 
         let reader = SqlitePoolOptions::new()
-            .max_connections(8)
-            .connect_with(read_opts)
+            .max_connections(config.max_read_connections.max(1))
+            .acquire_timeout(Duration::from_millis(config.acquire_timeout_ms))
+            .connect_with(connect_options(database_url, config)?.read_only(true))
             .await?;
 
         Ok(Self { reader, writer })
     }
+
+    /// Open only the single-connection writer pool, without running migrations.
+    ///
+    /// Used by `bnity db migrate` to inspect and apply migrations explicitly.
+    pub async fn connect_writer(database_url: &str) -> Result<SqlitePool, sqlx::Error> {
+        writer_pool(database_url, &DatabaseConfig::default()).await
+    }
+
+    /// Snapshot connection usage for both pools.
+    ///
+    /// `acquire_wait_ms` is measured by briefly checking out a connection, so
+    /// it reflects current contention; it is `None` when the acquire timed out.
+    pub async fn metrics(&self) -> DatabasePoolMetrics {
+        let metrics = DatabasePoolMetrics {
+            reader: PoolMetrics::sample(&self.reader).await,
+            writer: PoolMetrics::sample(&self.writer).await,
+        };
+        tracing::debug!(
+            reader_in_use = metrics.reader.in_use,
+            reader_idle = metrics.reader.idle,
+            reader_wait_ms = ?metrics.reader.acquire_wait_ms,
+            writer_in_use = metrics.writer.in_use,
+            writer_idle = metrics.writer.idle,
+            writer_wait_ms = ?metrics.writer.acquire_wait_ms,
+            "Database pool metrics"
+        );
+        metrics
+    }
+
+    /// Begin a read-only transaction on the reader pool, pinned to the current
+    /// database snapshot.
+    ///
+    /// In WAL mode a read transaction sees the database as of its first read
+    /// until it ends; writes committed meanwhile are invisible to it and are
+    /// not blocked by it. Keep snapshots short -- an open reader prevents WAL
+    /// checkpoints from completing.
+    pub async fn read_snapshot(&self) -> Result<ReadSnapshot, sqlx::Error> {
+        let mut tx = self.reader.begin().await?;
+        // A deferred transaction takes its snapshot on the first read; pin it now
+        sqlx::query("SELECT COUNT(*) FROM sqlite_master")
+            .execute(&mut *tx)
+            .await?;
+        Ok(tx)
+    }
+
+    /// Write a consistent copy of the live database to `path`.
+    ///
+    /// Uses `VACUUM INTO`, which reads a single snapshot (safe with WAL and
+    /// concurrent writers) and produces a compacted, self-contained file.
+    /// Fails if `path` already exists.
+    pub async fn backup_to(&self, path: &Path) -> Result<(), sqlx::Error> {
+        sqlx::query("VACUUM INTO ?")
+            .bind(path.to_string_lossy().into_owned())
+            .execute(&self.writer)
+            .await?;
+        Ok(())
+    }
+
+    /// Rebuild the database file to reclaim space from deleted rows.
+    pub async fn vacuum(&self) -> Result<VacuumReport, sqlx::Error> {
+        let bytes_before = self.database_size().await?;
+        sqlx::query("VACUUM").execute(&self.writer).await?;
+        // Fold the rewritten pages from the WAL back into the main file
+        sqlx::query("PRAGMA wal_checkpoint(TRUNCATE)")
+            .execute(&self.writer)
+            .await?;
+        let bytes_after = self.database_size().await?;
+        Ok(VacuumReport {
+            bytes_before,
+            bytes_after,
+        })
+    }
+
+    /// Row counts and sizes for every table, read from one snapshot.
+    pub async fn table_stats(&self) -> Result<Vec<TableStats>, sqlx::Error> {
+        let mut snapshot = self.read_snapshot().await?;
+        let names: Vec<String> = sqlx::query_scalar(
+            "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name",
+        )
+        .fetch_all(&mut *snapshot)
+        .await?;
+
+        let mut stats = Vec::with_capacity(names.len());
+        for name in names {
+            let quoted = format!("\"{}\"", name.replace('"', "\"\""));
+            let rows: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {quoted}"))
+                .fetch_one(&mut *snapshot)
+                .await?;
+            // dbstat is a compile-time option; report no size if it is missing
+            let bytes: Option<i64> =
+                sqlx::query_scalar("SELECT SUM(pgsize) FROM dbstat WHERE name = ?")
+                    .bind(&name)
+                    .fetch_one(&mut *snapshot)
+                    .await
+                    .unwrap_or(None);
+            stats.push(TableStats {
+                name,
+                rows: rows as u64,
+                bytes: bytes.map(|b| b as u64),
+            });
+        }
+        Ok(stats)
+    }
+
+    /// Current database size in bytes (`page_count * page_size`).
+    async fn database_size(&self) -> Result<u64, sqlx::Error> {
+        let size: i64 = sqlx::query_scalar(
+            "SELECT page_count * page_size FROM pragma_page_count(), pragma_page_size()",
+        )
+        .fetch_one(&self.writer)
+        .await?;
+        Ok(size as u64)
+    }
+}
+
+/// Shared connection options: WAL, foreign keys, configured busy timeout.
+fn connect_options(
+    database_url: &str,
+    config: &DatabaseConfig,
+) -> Result<SqliteConnectOptions, sqlx::Error> {
+    Ok(SqliteConnectOptions::from_str(database_url)?
+        .journal_mode(SqliteJournalMode::Wal)
+        .foreign_keys(true)
+        .busy_timeout(Duration::from_millis(config.busy_timeout_ms))
+        .create_if_missing(true))
+}
+
+/// The single-connection writer pool (SQLite allows one writer at a time).
+async fn writer_pool(database_url: &str, config: &DatabaseConfig) -> Result<SqlitePool, sqlx::Error> {
+    SqlitePoolOptions::new()
+        .max_connections(1)
+        .acquire_timeout(Duration::from_millis(config.acquire_timeout_ms))
+        .connect_with(connect_options(database_url, config)?)
+        .await
 }
 
 /// Returns the default database URL based on `BOTERNITY_DATA_DIR` env var,
@@ -122,6 +339,155 @@ mod tests {
         assert_eq!(result.0, 1, "foreign keys should be enabled");
     }
 
+    async fn insert_bot(pool: &DatabasePool, slug: &str) {
+        sqlx::query(
+            "INSERT INTO bots (id, slug, name, description, created_at, updated_at) VALUES (?, ?, ?, '', ?, ?)",
+        )
+        .bind(uuid::Uuid::now_v7().to_string())
+        .bind(slug)
+        .bind(slug)
+        .bind(chrono::Utc::now().to_rfc3339())
+        .bind(chrono::Utc::now().to_rfc3339())
+        .execute(&pool.writer)
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_read_snapshot_is_consistent_during_writes() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("test_snapshot.db");
+        let url = format!("sqlite://{}?mode=rwc", db_path.display());
+        let pool = DatabasePool::new(&url).await.unwrap();
+        insert_bot(&pool, "first").await;
+
+        let mut snapshot = pool.read_snapshot().await.unwrap();
+        let before: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM bots")
+            .fetch_one(&mut *snapshot)
+            .await
+            .unwrap();
+        assert_eq!(before.0, 1);
+
+        // The writer is not blocked by the open snapshot
+        tokio::time::timeout(std::time::Duration::from_secs(2), insert_bot(&pool, "second"))
+            .await
+            .expect("write blocked by read snapshot");
+
+        // The snapshot still sees the old version; a fresh read sees the new row
+        let during: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM bots")
+            .fetch_one(&mut *snapshot)
+            .await
+            .unwrap();
+        assert_eq!(during.0, 1);
+        let fresh: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM bots")
+            .fetch_one(&pool.reader)
+            .await
+            .unwrap();
+        assert_eq!(fresh.0, 2);
+
+        drop(snapshot);
+        let mut next = pool.read_snapshot().await.unwrap();
+        let after: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM bots")
+            .fetch_one(&mut *next)
+            .await
+            .unwrap();
+        assert_eq!(after.0, 2);
+    }
+
+    #[tokio::test]
+    async fn test_read_snapshot_rejects_writes() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("test_snapshot_ro.db");
+        let url = format!("sqlite://{}?mode=rwc", db_path.display());
+        let pool = DatabasePool::new(&url).await.unwrap();
+
+        let mut snapshot = pool.read_snapshot().await.unwrap();
+        let result = sqlx::query("DELETE FROM bots").execute(&mut *snapshot).await;
+        assert!(result.is_err(), "snapshot connections are read-only");
+    }
+
+    #[tokio::test]
+    async fn test_backup_preserves_data() {
+        let dir = tempfile::tempdir().unwrap();
+        let url = format!("sqlite://{}?mode=rwc", dir.path().join("live.db").display());
+        let pool = DatabasePool::new(&url).await.unwrap();
+        insert_bot(&pool, "alpha").await;
+        insert_bot(&pool, "beta").await;
+
+        let backup_path = dir.path().join("backup.db");
+        pool.backup_to(&backup_path).await.unwrap();
+        // Refuses to overwrite an existing file
+        assert!(pool.backup_to(&backup_path).await.is_err());
+
+        let backup_url = format!("sqlite://{}", backup_path.display());
+        let backup = DatabasePool::new(&backup_url).await.unwrap();
+        let integrity: String = sqlx::query_scalar("PRAGMA integrity_check")
+            .fetch_one(&backup.reader)
+            .await
+            .unwrap();
+        assert_eq!(integrity, "ok");
+        let slugs: Vec<String> = sqlx::query_scalar("SELECT slug FROM bots ORDER BY slug")
+            .fetch_all(&backup.reader)
+            .await
+            .unwrap();
+        assert_eq!(slugs, vec!["alpha", "beta"]);
+    }
+
+    #[tokio::test]
+    async fn test_vacuum_and_table_stats() {
+        let dir = tempfile::tempdir().unwrap();
+        let url = format!("sqlite://{}?mode=rwc", dir.path().join("stats.db").display());
+        let pool = DatabasePool::new(&url).await.unwrap();
+        for i in 0..50 {
+            insert_bot(&pool, &format!("bot-{i}")).await;
+        }
+        sqlx::query("DELETE FROM bots WHERE slug != 'bot-0'")
+            .execute(&pool.writer)
+            .await
+            .unwrap();
+
+        let report = pool.vacuum().await.unwrap();
+        assert!(report.bytes_after <= report.bytes_before);
+
+        let stats = pool.table_stats().await.unwrap();
+        let bots = stats.iter().find(|t| t.name == "bots").unwrap();
+        assert_eq!(bots.rows, 1);
+        assert!(stats.windows(2).all(|w| w[0].name <= w[1].name));
+    }
+
+    #[tokio::test]
+    async fn test_configured_pool_limits_and_timeout() {
+        let dir = tempfile::tempdir().unwrap();
+        let url = format!("sqlite://{}?mode=rwc", dir.path().join("limits.db").display());
+        let config = DatabaseConfig {
+            max_read_connections: 2,
+            acquire_timeout_ms: 200,
+            busy_timeout_ms: 1_000,
+        };
+        let pool = DatabasePool::with_config(&url, &config).await.unwrap();
+
+        let first = pool.reader.acquire().await.unwrap();
+        let second = pool.reader.acquire().await.unwrap();
+
+        let metrics = pool.metrics().await;
+        assert_eq!(metrics.reader.max_connections, 2);
+        assert_eq!(metrics.reader.in_use, 2);
+        assert_eq!(metrics.reader.acquire_wait_ms, None);
+        assert_eq!(metrics.writer.max_connections, 1);
+        assert!(metrics.writer.acquire_wait_ms.is_some());
+
+        // A third reader waits for the configured timeout, then fails
+        let started = std::time::Instant::now();
+        let third = pool.reader.acquire().await;
+        assert!(matches!(third, Err(sqlx::Error::PoolTimedOut)));
+        assert!(started.elapsed() >= Duration::from_millis(150));
+
+        // Releasing a connection lets the next acquire through
+        drop(first);
+        let third = pool.reader.acquire().await.unwrap();
+        drop((second, third));
+    }
+
     #[tokio::test]
     async fn test_default_database_url() {
         let url = default_database_url();
@@ -4,7 +4,7 @@
 //! Secret values are stored as encrypted BLOB -- the encryption/decryption is handled by
 //! the caller (vault service in Plan 01-04). This repository stores and retrieves raw bytes.
 
-use boternity_core::repository::secret::SecretProvider;
+use boternity_core::repository::secret::{SecretProvider, SecretProviderKind};
 use boternity_types::error::RepositoryError;
 use boternity_types::secret::{SecretEntry, SecretKey, SecretScope};
 use chrono::{DateTime, Utc};
@@ -46,6 +46,10 @@ fn format_datetime(dt: &DateTime<Utc>) -> String {
 }
 
 impl SecretProvider for SqliteSecretRepository {
+    fn kind(&self) -> SecretProviderKind {
+        SecretProviderKind::Vault
+    }
+
     async fn get(
         &self,
         key: &str,
@@ -236,6 +240,7 @@ mod tests {
             created_at: now,
             updated_at: now,
             last_active_at: None,
+            deleted_at: None,
         }
     }
 
@@ -2,6 +2,8 @@
 //!
 //! Implements `SoulRepository` from `boternity-core` using sqlx with split read/write pools.
 //! Each soul version is immutable once saved. Version count is tracked on the parent bot.
+//! Each version stores the chain hash of its predecessor (`prev_hash`), computed by
+//! `SoulService`; this repository only persists it.
 
 use boternity_core::repository::soul::SoulRepository;
 use boternity_types::bot::BotId;
@@ -56,6 +58,9 @@ fn row_to_soul(row: &sqlx::sqlite::SqliteRow) -> Result<Soul, RepositoryError> {
     let created_at_str: String = row
         .try_get("created_at")
         .map_err(|e| RepositoryError::Query(e.to_string()))?;
+    let prev_hash: Option<String> = row
+        .try_get("prev_hash")
+        .map_err(|e| RepositoryError::Query(e.to_string()))?;
 
     Ok(Soul {
         id: id_str
@@ -69,6 +74,7 @@ fn row_to_soul(row: &sqlx::sqlite::SqliteRow) -> Result<Soul, RepositoryError> {
         version,
         message,
         created_at: parse_datetime(&created_at_str)?,
+        prev_hash,
     })
 }
 
@@ -88,6 +94,9 @@ fn row_to_soul_version(row: &sqlx::sqlite::SqliteRow) -> Result<SoulVersion, Rep
     let message: Option<String> = row
         .try_get("message")
         .map_err(|e| RepositoryError::Query(e.to_string()))?;
+    let prev_hash: Option<String> = row
+        .try_get("prev_hash")
+        .map_err(|e| RepositoryError::Query(e.to_string()))?;
 
     Ok(SoulVersion {
         version,
@@ -95,6 +104,7 @@ fn row_to_soul_version(row: &sqlx::sqlite::SqliteRow) -> Result<SoulVersion, Rep
         content,
         created_at: parse_datetime(&created_at_str)?,
         message,
+        prev_hash,
     })
 }
 
@@ -109,8 +119,8 @@ impl SoulRepository for SqliteSoulRepository {
             .map_err(|e| RepositoryError::Query(e.to_string()))?;
 
         sqlx::query(
-            "INSERT INTO soul_versions (id, bot_id, content, hash, version, message, created_at)
-             VALUES (?, ?, ?, ?, ?, ?, ?)",
+            "INSERT INTO soul_versions (id, bot_id, content, hash, version, message, created_at, prev_hash)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
         )
         .bind(soul.id.to_string())
         .bind(soul.bot_id.to_string())
@@ -119,6 +129,7 @@ impl SoulRepository for SqliteSoulRepository {
         .bind(soul.version)
         .bind(&soul.message)
         .bind(format_datetime(&soul.created_at))
+        .bind(&soul.prev_hash)
         .execute(&mut *tx)
         .await
         .map_err(|e| {
@@ -257,6 +268,7 @@ mod tests {
             created_at: now,
             updated_at: now,
             last_active_at: None,
+            deleted_at: None,
         }
     }
 
@@ -271,6 +283,7 @@ mod tests {
             version,
             message: None,
             created_at: Utc::now(),
+            prev_hash: None,
         }
     }
 
@@ -332,6 +345,29 @@ mod tests {
         assert_eq!(updated_bot.version_count, 3);
     }
 
+    #[tokio::test]
+    async fn test_prev_hash_round_trips() {
+        let pool = test_pool().await;
+        let bot_repo = SqliteBotRepository::new(pool.clone());
+        let soul_repo = SqliteSoulRepository::new(pool);
+
+        let bot = make_bot("Chained");
+        bot_repo.create(&bot).await.unwrap();
+
+        let v1 = make_soul(&bot.id, 1, "Version 1");
+        let mut v2 = make_soul(&bot.id, 2, "Version 2");
+        v2.prev_hash = Some("link-to-v1".to_string());
+        soul_repo.save_version(&v1).await.unwrap();
+        soul_repo.save_version(&v2).await.unwrap();
+
+        let current = soul_repo.get_current(&bot.id).await.unwrap().unwrap();
+        assert_eq!(current.prev_hash.as_deref(), Some("link-to-v1"));
+
+        let versions = soul_repo.list_versions(&bot.id).await.unwrap();
+        assert_eq!(versions[0].prev_hash, None);
+        assert_eq!(versions[1].prev_hash.as_deref(), Some("link-to-v1"));
+    }
+
     #[tokio::test]
     async fn test_get_stored_hash() {
         let pool = test_pool().await;
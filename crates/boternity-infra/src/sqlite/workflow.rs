@@ -4,8 +4,9 @@
 //! read/write pools. Workflow definitions are stored as JSON blobs. Runs and
 //! step logs track execution state for crash recovery and auditing.
 
-use boternity_core::repository::workflow::WorkflowRepository;
+use boternity_core::repository::workflow::{WorkflowRepository, WorkflowRunFilter};
 use boternity_types::error::RepositoryError;
+use boternity_types::schema::from_versioned_str;
 use boternity_types::workflow::{
     WorkflowDefinition, WorkflowOwner, WorkflowRun, WorkflowRunStatus, WorkflowStepLog,
     WorkflowStepStatus,
@@ -43,9 +44,9 @@ impl WorkflowDefRow {
         })
     }
 
+    /// Deserialize the stored definition, migrating older schema versions.
     fn into_definition(self) -> Result<WorkflowDefinition, RepositoryError> {
-        serde_json::from_str(&self.definition)
-            .map_err(|e| RepositoryError::Query(format!("invalid workflow definition JSON: {e}")))
+        from_versioned_str(&self.definition).map_err(|e| RepositoryError::Query(e.to_string()))
     }
 }
 
@@ -61,6 +62,7 @@ struct WorkflowRunRow {
     completed_at: Option<String>,
     error: Option<String>,
     concurrency_key: Option<String>,
+    idempotency_key: Option<String>,
 }
 
 impl WorkflowRunRow {
@@ -77,6 +79,7 @@ impl WorkflowRunRow {
             completed_at: row.try_get("completed_at")?,
             error: row.try_get("error")?,
             concurrency_key: row.try_get("concurrency_key")?,
+            idempotency_key: row.try_get("idempotency_key")?,
         })
     }
 
@@ -119,6 +122,7 @@ impl WorkflowRunRow {
             completed_at,
             error: self.error,
             concurrency_key: self.concurrency_key,
+            idempotency_key: self.idempotency_key,
         })
     }
 }
@@ -383,8 +387,8 @@ impl WorkflowRepository for SqliteWorkflowRepository {
         sqlx::query(
             r#"INSERT INTO workflow_runs
                (id, workflow_id, workflow_name, status, trigger_type, trigger_payload,
-                context, started_at, completed_at, error, concurrency_key)
-               VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
+                context, started_at, completed_at, error, concurrency_key, idempotency_key)
+               VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
         )
         .bind(run.id.to_string())
         .bind(run.workflow_id.to_string())
@@ -397,6 +401,7 @@ impl WorkflowRepository for SqliteWorkflowRepository {
         .bind(run.completed_at.as_ref().map(format_datetime))
         .bind(&run.error)
         .bind(&run.concurrency_key)
+        .bind(&run.idempotency_key)
         .execute(&self.pool.writer)
         .await
         .map_err(|e| RepositoryError::Query(e.to_string()))?;
@@ -507,6 +512,95 @@ impl WorkflowRepository for SqliteWorkflowRepository {
         Ok(runs)
     }
 
+    async fn list_runs_filtered(
+        &self,
+        filter: &WorkflowRunFilter,
+    ) -> Result<Vec<WorkflowRun>, RepositoryError> {
+        let mut sql = String::from("SELECT * FROM workflow_runs");
+        let mut conditions: Vec<&str> = Vec::new();
+        let mut binds: Vec<String> = Vec::new();
+
+        if let Some(ref status) = filter.status {
+            let status_str = serde_json::to_value(status)
+                .map_err(|e| RepositoryError::Query(e.to_string()))?
+                .as_str()
+                .unwrap_or("pending")
+                .to_string();
+            conditions.push("status = ?");
+            binds.push(status_str);
+        }
+        if let Some(ref name) = filter.workflow_name {
+            conditions.push("workflow_name = ?");
+            binds.push(name.clone());
+        }
+        if let Some(ref since) = filter.since {
+            conditions.push("started_at >= ?");
+            binds.push(format_datetime(since));
+        }
+
+        if !conditions.is_empty() {
+            sql.push_str(" WHERE ");
+            sql.push_str(&conditions.join(" AND "));
+        }
+        sql.push_str(" ORDER BY started_at DESC");
+
+        // Pagination (SQLite needs a LIMIT before OFFSET; -1 means no limit)
+        if filter.limit.is_some() || filter.offset.is_some() {
+            sql.push_str(" LIMIT ? OFFSET ?");
+        }
+
+        let mut query = sqlx::query(&sql);
+        for value in &binds {
+            query = query.bind(value);
+        }
+        if filter.limit.is_some() || filter.offset.is_some() {
+            query = query
+                .bind(filter.limit.unwrap_or(-1))
+                .bind(filter.offset.unwrap_or(0));
+        }
+
+        let rows = query
+            .fetch_all(&self.pool.reader)
+            .await
+            .map_err(|e| RepositoryError::Query(e.to_string()))?;
+
+        let mut runs = Vec::with_capacity(rows.len());
+        for row in &rows {
+            let r = WorkflowRunRow::from_row(row)
+                .map_err(|e| RepositoryError::Query(e.to_string()))?;
+            runs.push(r.into_run()?);
+        }
+        Ok(runs)
+    }
+
+    async fn find_run_by_idempotency_key(
+        &self,
+        workflow_id: &Uuid,
+        idempotency_key: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Option<WorkflowRun>, RepositoryError> {
+        let row = sqlx::query(
+            r#"SELECT * FROM workflow_runs
+               WHERE workflow_id = ? AND idempotency_key = ? AND started_at >= ?
+               ORDER BY started_at DESC LIMIT 1"#,
+        )
+        .bind(workflow_id.to_string())
+        .bind(idempotency_key)
+        .bind(format_datetime(&since))
+        .fetch_optional(&self.pool.reader)
+        .await
+        .map_err(|e| RepositoryError::Query(e.to_string()))?;
+
+        match row {
+            Some(row) => {
+                let r = WorkflowRunRow::from_row(&row)
+                    .map_err(|e| RepositoryError::Query(e.to_string()))?;
+                Ok(Some(r.into_run()?))
+            }
+            None => Ok(None),
+        }
+    }
+
     async fn list_crashed_runs(&self) -> Result<Vec<WorkflowRun>, RepositoryError> {
         let rows = sqlx::query(
             "SELECT * FROM workflow_runs WHERE status = 'running' ORDER BY started_at ASC",
@@ -670,6 +764,7 @@ impl WorkflowRepository for SqliteWorkflowRepository {
 mod tests {
     use super::*;
     use crate::sqlite::pool::DatabasePool;
+    use boternity_types::schema::WORKFLOW_DEFINITION_SCHEMA_VERSION;
     use boternity_types::workflow::*;
     use serde_json::json;
 
@@ -710,6 +805,7 @@ mod tests {
                 ui: None,
             }],
             metadata: Default::default(),
+            schema_version: WORKFLOW_DEFINITION_SCHEMA_VERSION,
         }
     }
 
@@ -726,6 +822,7 @@ mod tests {
             completed_at: None,
             error: None,
             concurrency_key: Some("daily-digest".to_string()),
+            idempotency_key: None,
         }
     }
 
@@ -762,6 +859,37 @@ mod tests {
         assert_eq!(loaded.steps.len(), 1);
     }
 
+    #[tokio::test]
+    async fn test_get_definition_migrates_stored_schema_versions() {
+        let pool = test_pool().await;
+        let repo = SqliteWorkflowRepository::new(pool.clone());
+        let def = sample_definition();
+        repo.save_definition(&def).await.unwrap();
+
+        let overwrite = |definition: serde_json::Value| {
+            sqlx::query("UPDATE workflows SET definition = ? WHERE id = ?")
+                .bind(definition.to_string())
+                .bind(def.id.to_string())
+                .execute(&pool.writer)
+        };
+
+        // Rows written before versioning have no schema_version
+        let mut v1 = serde_json::to_value(&def).unwrap();
+        v1.as_object_mut().unwrap().remove("schema_version");
+        overwrite(v1).await.unwrap();
+        let loaded = repo.get_definition(&def.id).await.unwrap().unwrap();
+        assert_eq!(loaded.schema_version, WORKFLOW_DEFINITION_SCHEMA_VERSION);
+        assert_eq!(loaded.steps.len(), 1);
+
+        let mut future = serde_json::to_value(&def).unwrap();
+        future["schema_version"] = json!(99);
+        overwrite(future).await.unwrap();
+        let err = repo.get_definition(&def.id).await.unwrap_err();
+        assert!(err.to_string().contains(
+            "workflow definition has schema version 99, but this build only understands up to 2"
+        ));
+    }
+
     #[tokio::test]
     async fn test_save_definition_upsert() {
         let pool = test_pool().await;
@@ -932,6 +1060,136 @@ mod tests {
         assert_eq!(runs.len(), 3);
     }
 
+    #[tokio::test]
+    async fn test_list_runs_filtered() {
+        let pool = test_pool().await;
+        let repo = SqliteWorkflowRepository::new(pool);
+        let digest = sample_definition();
+        repo.save_definition(&digest).await.unwrap();
+        let mut report = sample_definition();
+        report.id = Uuid::now_v7();
+        report.name = "weekly-report".to_string();
+        repo.save_definition(&report).await.unwrap();
+
+        let now = Utc::now();
+        let insert = |def: &WorkflowDefinition, status: WorkflowRunStatus, age_hours: i64| {
+            let mut run = sample_run(def.id);
+            run.workflow_name = def.name.clone();
+            run.status = status;
+            run.started_at = now - chrono::Duration::hours(age_hours);
+            run
+        };
+        let runs = [
+            insert(&digest, WorkflowRunStatus::Running, 1),
+            insert(&digest, WorkflowRunStatus::Failed, 30),
+            insert(&digest, WorkflowRunStatus::Completed, 2),
+            insert(&report, WorkflowRunStatus::Running, 3),
+            insert(&report, WorkflowRunStatus::Completed, 72),
+        ];
+        for run in &runs {
+            repo.create_run(run).await.unwrap();
+        }
+        let [
+            digest_running,
+            digest_failed,
+            digest_completed,
+            report_running,
+            report_completed,
+        ] = runs.map(|r| r.id);
+
+        let ids = |runs: Vec<WorkflowRun>| runs.into_iter().map(|r| r.id).collect::<Vec<_>>();
+
+        // No filter: everything, newest first
+        let all = repo
+            .list_runs_filtered(&WorkflowRunFilter::default())
+            .await
+            .unwrap();
+        assert_eq!(
+            ids(all),
+            vec![
+                digest_running,
+                digest_completed,
+                report_running,
+                digest_failed,
+                report_completed
+            ]
+        );
+
+        // By status
+        let running = repo
+            .list_runs_filtered(&WorkflowRunFilter {
+                status: Some(WorkflowRunStatus::Running),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(ids(running), vec![digest_running, report_running]);
+
+        // By name
+        let reports = repo
+            .list_runs_filtered(&WorkflowRunFilter {
+                workflow_name: Some("weekly-report".to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(ids(reports), vec![report_running, report_completed]);
+
+        // By start time
+        let last_day = repo
+            .list_runs_filtered(&WorkflowRunFilter {
+                since: Some(now - chrono::Duration::days(1)),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            ids(last_day),
+            vec![digest_running, digest_completed, report_running]
+        );
+
+        // Combined filters
+        let completed_digests = repo
+            .list_runs_filtered(&WorkflowRunFilter {
+                status: Some(WorkflowRunStatus::Completed),
+                workflow_name: Some("daily-digest".to_string()),
+                since: Some(now - chrono::Duration::days(1)),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(ids(completed_digests), vec![digest_completed]);
+
+        let none = repo
+            .list_runs_filtered(&WorkflowRunFilter {
+                status: Some(WorkflowRunStatus::Cancelled),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert!(none.is_empty());
+
+        // Pagination
+        let page = repo
+            .list_runs_filtered(&WorkflowRunFilter {
+                limit: Some(2),
+                offset: Some(2),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(ids(page), vec![report_running, digest_failed]);
+
+        let tail = repo
+            .list_runs_filtered(&WorkflowRunFilter {
+                offset: Some(4),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(ids(tail), vec![report_completed]);
+    }
+
     #[tokio::test]
     async fn test_list_crashed_runs() {
         let pool = test_pool().await;
@@ -1022,4 +1280,475 @@ mod tests {
         let completed = repo.get_completed_step_ids(&run.id).await.unwrap();
         assert_eq!(completed, vec!["gather"]);
     }
+
+    #[tokio::test]
+    async fn test_run_tail_observes_step_updates_in_order() {
+        use boternity_core::workflow::tail::{RunTail, TailEvent};
+
+        let pool = test_pool().await;
+        let repo = SqliteWorkflowRepository::new(pool);
+        let def = sample_definition();
+        repo.save_definition(&def).await.unwrap();
+        let run = sample_run(def.id);
+        repo.create_run(&run).await.unwrap();
+
+        // Render events as compact labels for order assertions
+        fn labels(events: &[TailEvent]) -> Vec<String> {
+            events
+                .iter()
+                .map(|e| match e {
+                    TailEvent::StepStarted { step_id, .. } => format!("start:{step_id}"),
+                    TailEvent::StepCompleted { step_id, .. } => format!("done:{step_id}"),
+                    TailEvent::StepFailed { step_id, .. } => format!("fail:{step_id}"),
+                    TailEvent::StepSkipped { step_id, .. } => format!("skip:{step_id}"),
+                    TailEvent::StepWaitingApproval { step_id, .. } => {
+                        format!("approval:{step_id}")
+                    }
+                    TailEvent::RunStatus { status, .. } => format!("run:{status:?}"),
+                })
+                .collect()
+        }
+
+        let mut tail = RunTail::new(&repo, run.id);
+        assert_eq!(labels(&tail.poll().await.unwrap()), vec!["run:Running"]);
+        assert!(!tail.is_finished());
+
+        // gather starts
+        let gather = sample_step_log(run.id);
+        repo.create_step_log(&gather).await.unwrap();
+        assert_eq!(labels(&tail.poll().await.unwrap()), vec!["start:gather"]);
+
+        // No changes -> no events
+        assert!(tail.poll().await.unwrap().is_empty());
+
+        // gather completes, summarize starts and fails before the next poll
+        repo.update_step_status(&gather.id, WorkflowStepStatus::Completed, None, None)
+            .await
+            .unwrap();
+        let mut summarize = sample_step_log(run.id);
+        summarize.id = Uuid::now_v7();
+        summarize.step_id = "summarize".to_string();
+        summarize.started_at = Some(Utc::now() + chrono::Duration::seconds(1));
+        repo.create_step_log(&summarize).await.unwrap();
+        repo.update_step_status(
+            &summarize.id,
+            WorkflowStepStatus::Failed,
+            None,
+            Some("provider unavailable"),
+        )
+        .await
+        .unwrap();
+        repo.update_run_status(
+            &run.id,
+            WorkflowRunStatus::Failed,
+            Some("step 'summarize' failed"),
+            None,
+        )
+        .await
+        .unwrap();
+
+        let events = tail.poll().await.unwrap();
+        assert_eq!(
+            labels(&events),
+            vec![
+                "done:gather",
+                "start:summarize",
+                "fail:summarize",
+                "run:Failed"
+            ]
+        );
+        assert!(matches!(
+            &events[2],
+            TailEvent::StepFailed { error: Some(e), .. } if e == "provider unavailable"
+        ));
+        assert!(tail.is_finished());
+
+        // Unknown runs surface as NotFound
+        let mut missing = RunTail::new(&repo, Uuid::now_v7());
+        assert!(matches!(
+            missing.poll().await,
+            Err(RepositoryError::NotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_workflow_message_step_delivers_to_bus() {
+        use boternity_core::event::bus::EventBus;
+        use boternity_core::message::{LoopGuard, MessageBus};
+        use boternity_core::workflow::executor::{DagExecutor, WorkflowExecutor};
+        use boternity_types::message::{MessagePriority, MessageRecipient};
+        use std::sync::Arc;
+
+        let pool = test_pool().await;
+        let recipient = Uuid::now_v7();
+        let bus = Arc::new(MessageBus::new(Arc::new(LoopGuard::default())));
+        let mut mailbox = bus.register_bot(recipient);
+
+        let mut def = sample_definition();
+        def.steps = vec![StepDefinition {
+            id: "notify".to_string(),
+            name: "Notify Researcher".to_string(),
+            step_type: StepType::Message,
+            depends_on: vec![],
+            condition: None,
+            timeout_secs: None,
+            retry: None,
+            config: StepConfig::Message {
+                sender_bot_id: None,
+                recipient: MessageRecipient::Direct { bot_id: recipient },
+                message_type: "digest_ready".to_string(),
+                body: json!({"source": "{{ trigger.source }}"}),
+                priority: MessagePriority::High,
+                ttl_secs: None,
+            },
+            ui: None,
+        }];
+        SqliteWorkflowRepository::new(pool.clone())
+            .save_definition(&def)
+            .await
+            .unwrap();
+
+        let executor = DagExecutor::new(
+            SqliteWorkflowRepository::new(pool),
+            EventBus::new(16),
+            std::env::temp_dir(),
+        )
+        .with_message_bus(Arc::clone(&bus));
+        let result = executor
+            .execute(&def, "manual", Some(json!({"source": "cron"})))
+            .await
+            .unwrap();
+        assert_eq!(result.status, WorkflowRunStatus::Completed);
+        assert_eq!(result.completed_steps, vec!["notify"]);
+
+        let msg = mailbox.try_recv().expect("message step should deliver");
+        assert_eq!(msg.message_type, "digest_ready");
+        assert_eq!(msg.sender_bot_name, "workflow:daily-digest");
+        assert_eq!(msg.body, json!({"source": "cron"}));
+        assert_eq!(msg.priority, MessagePriority::High);
+    }
+
+    #[tokio::test]
+    async fn test_conditional_skips_untaken_branch_and_dependents() {
+        use boternity_core::event::bus::EventBus;
+        use boternity_core::workflow::executor::{DagExecutor, WorkflowExecutor};
+        use std::collections::HashMap;
+
+        let agent = |id: &str, depends_on: &[&str]| StepDefinition {
+            id: id.to_string(),
+            name: id.to_string(),
+            step_type: StepType::Agent,
+            depends_on: depends_on.iter().map(|d| d.to_string()).collect(),
+            condition: None,
+            timeout_secs: None,
+            retry: None,
+            config: StepConfig::Agent {
+                bot: "researcher".to_string(),
+                prompt: format!("run {id}"),
+                model: None,
+            },
+            ui: None,
+        };
+
+        let pool = test_pool().await;
+        let repo = SqliteWorkflowRepository::new(pool.clone());
+        let mut def = sample_definition();
+        def.steps = vec![
+            StepDefinition {
+                id: "check".to_string(),
+                name: "check".to_string(),
+                step_type: StepType::Conditional,
+                depends_on: vec![],
+                condition: None,
+                timeout_secs: None,
+                retry: None,
+                config: StepConfig::Conditional {
+                    condition: "trigger.fast == true".to_string(),
+                    then_steps: vec!["fast".to_string()],
+                    else_steps: vec!["slow".to_string()],
+                },
+                ui: None,
+            },
+            agent("fast", &[]),
+            agent("slow", &[]),
+            agent("slow-summary", &["slow"]),
+            agent("report", &["fast", "slow-summary"]),
+        ];
+        repo.save_definition(&def).await.unwrap();
+
+        let executor = DagExecutor::new(
+            SqliteWorkflowRepository::new(pool),
+            EventBus::new(16),
+            std::env::temp_dir(),
+        );
+
+        for (fast, expected) in [
+            (
+                true,
+                [
+                    ("check", WorkflowStepStatus::Completed),
+                    ("fast", WorkflowStepStatus::Completed),
+                    ("slow", WorkflowStepStatus::Skipped),
+                    ("slow-summary", WorkflowStepStatus::Skipped),
+                    ("report", WorkflowStepStatus::Completed),
+                ],
+            ),
+            (
+                false,
+                [
+                    ("check", WorkflowStepStatus::Completed),
+                    ("fast", WorkflowStepStatus::Skipped),
+                    ("slow", WorkflowStepStatus::Completed),
+                    ("slow-summary", WorkflowStepStatus::Completed),
+                    ("report", WorkflowStepStatus::Completed),
+                ],
+            ),
+        ] {
+            let result = executor
+                .execute(&def, "manual", Some(json!({"fast": fast})))
+                .await
+                .unwrap();
+            assert_eq!(result.status, WorkflowRunStatus::Completed);
+
+            let run = repo.get_run(&result.run_id).await.unwrap().unwrap();
+            assert_eq!(run.status, WorkflowRunStatus::Completed);
+
+            let statuses: HashMap<String, WorkflowStepStatus> = repo
+                .list_step_logs(&result.run_id)
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|log| (log.step_id, log.status))
+                .collect();
+            assert_eq!(statuses.len(), expected.len(), "fast={fast}: {statuses:?}");
+            for (step_id, status) in expected {
+                assert_eq!(statuses[step_id], status, "fast={fast}, step {step_id}");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_workflow_timeout_interrupts_running_step() {
+        use boternity_core::event::bus::EventBus;
+        use boternity_core::workflow::executor::{
+            DagExecutor, ExecutorError, STEP_INTERRUPTED_BY_TIMEOUT, WorkflowExecutor,
+        };
+        use boternity_core::workflow::step_runner::{StepError, StepExecutionContext};
+        use std::collections::HashMap;
+        use std::future::Future;
+        use std::pin::Pin;
+        use std::sync::Arc;
+
+        type StepFuture<'a> =
+            Pin<Box<dyn Future<Output = Result<serde_json::Value, StepError>> + Send + 'a>>;
+
+        /// Agent prompts containing "slow" outlast the workflow timeout.
+        struct SlowAgent;
+
+        impl StepExecutionContext for SlowAgent {
+            fn execute_agent(&self, _: &str, prompt: &str, _: Option<&str>) -> StepFuture<'_> {
+                let slow = prompt.contains("slow");
+                Box::pin(async move {
+                    if slow {
+                        tokio::time::sleep(std::time::Duration::from_secs(30)).await;
+                    }
+                    Ok(json!({ "output": "done" }))
+                })
+            }
+
+            fn execute_skill(&self, _: &str, _: Option<&str>) -> StepFuture<'_> {
+                Box::pin(async { Ok(serde_json::Value::Null) })
+            }
+
+            fn execute_http(
+                &self,
+                _: &str,
+                _: &str,
+                _: Option<&HashMap<String, String>>,
+                _: Option<&str>,
+            ) -> StepFuture<'_> {
+                Box::pin(async { Ok(serde_json::Value::Null) })
+            }
+        }
+
+        let agent = |id: &str, depends_on: &[&str]| StepDefinition {
+            id: id.to_string(),
+            name: id.to_string(),
+            step_type: StepType::Agent,
+            depends_on: depends_on.iter().map(|d| d.to_string()).collect(),
+            condition: None,
+            timeout_secs: None,
+            retry: None,
+            config: StepConfig::Agent {
+                bot: "researcher".to_string(),
+                prompt: format!("{id} step"),
+                model: None,
+            },
+            ui: None,
+        };
+
+        let pool = test_pool().await;
+        let repo = SqliteWorkflowRepository::new(pool.clone());
+        let mut def = sample_definition();
+        def.timeout_secs = Some(1);
+        def.steps = vec![
+            agent("quick", &[]),
+            agent("slow", &["quick"]),
+            agent("publish", &["slow"]),
+        ];
+        repo.save_definition(&def).await.unwrap();
+
+        let executor = DagExecutor::with_execution_context(
+            SqliteWorkflowRepository::new(pool),
+            EventBus::new(16),
+            std::env::temp_dir(),
+            Arc::new(SlowAgent),
+        );
+        let started = std::time::Instant::now();
+        let err = executor.execute(&def, "manual", None).await.unwrap_err();
+        assert!(started.elapsed() < std::time::Duration::from_secs(10));
+        let ExecutorError::WorkflowTimeout {
+            timeout_secs,
+            interrupted_steps,
+        } = &err
+        else {
+            panic!("expected workflow timeout, got {err}");
+        };
+        assert_eq!(*timeout_secs, 1);
+        assert_eq!(interrupted_steps, &vec!["slow".to_string()]);
+
+        let runs = repo.list_runs(&def.id, 10).await.unwrap();
+        assert_eq!(runs.len(), 1);
+        let run = &runs[0];
+        assert_eq!(run.status, WorkflowRunStatus::Crashed);
+        assert!(run.completed_at.is_some());
+        let error = run.error.as_deref().unwrap();
+        assert!(error.contains("timed out"), "got: {error}");
+        assert!(error.contains("slow"), "got: {error}");
+
+        let logs = repo.list_step_logs(&run.id).await.unwrap();
+        let status_of = |step_id: &str| {
+            logs.iter()
+                .find(|log| log.step_id == step_id)
+                .map(|log| (log.status, log.error.clone()))
+        };
+        assert_eq!(
+            status_of("quick"),
+            Some((WorkflowStepStatus::Completed, None))
+        );
+        assert_eq!(
+            status_of("slow"),
+            Some((
+                WorkflowStepStatus::Failed,
+                Some(STEP_INTERRUPTED_BY_TIMEOUT.to_string())
+            ))
+        );
+        assert_eq!(status_of("publish"), None, "publish never started");
+    }
+
+    #[tokio::test]
+    async fn test_workflow_step_log_redacts_injected_secret() {
+        use boternity_core::event::bus::EventBus;
+        use boternity_core::repository::secret::SecretProvider;
+        use boternity_core::service::secret::SecretService;
+        use boternity_core::workflow::executor::{DagExecutor, WorkflowExecutor};
+        use boternity_core::workflow::step_runner::{StepError, StepExecutionContext};
+        use boternity_types::secret::SecretScope;
+        use std::collections::HashMap;
+        use std::future::Future;
+        use std::pin::Pin;
+        use std::sync::{Arc, Mutex};
+
+        type StepFuture<'a> =
+            Pin<Box<dyn Future<Output = Result<serde_json::Value, StepError>> + Send + 'a>>;
+
+        /// Records the Authorization header of each HTTP request.
+        #[derive(Default)]
+        struct AuthRecorder(Mutex<Vec<String>>);
+
+        impl StepExecutionContext for AuthRecorder {
+            fn execute_agent(&self, _: &str, _: &str, _: Option<&str>) -> StepFuture<'_> {
+                Box::pin(async { Ok(serde_json::Value::Null) })
+            }
+
+            fn execute_skill(&self, _: &str, _: Option<&str>) -> StepFuture<'_> {
+                Box::pin(async { Ok(serde_json::Value::Null) })
+            }
+
+            fn execute_http(
+                &self,
+                _method: &str,
+                url: &str,
+                headers: Option<&HashMap<String, String>>,
+                _body: Option<&str>,
+            ) -> StepFuture<'_> {
+                let auth = headers
+                    .and_then(|h| h.get("Authorization"))
+                    .cloned()
+                    .unwrap_or_default();
+                self.0.lock().unwrap().push(auth.clone());
+                let response = json!({ "url": url, "echo": auth, "status": 200 });
+                Box::pin(async move { Ok(response) })
+            }
+        }
+
+        let pool = test_pool().await;
+        let secret_repo = crate::sqlite::secret::SqliteSecretRepository::new(pool.clone());
+        // The SQLite store holds hex; this value round-trips unchanged
+        let token = "5ec7e7c0de";
+        secret_repo
+            .set("API_TOKEN", token, &SecretScope::Global)
+            .await
+            .unwrap();
+        let secrets = Arc::new(SecretService::new(vec![Arc::new(secret_repo)]));
+
+        let mut def = sample_definition();
+        def.steps = vec![StepDefinition {
+            id: "call".to_string(),
+            name: "Call API".to_string(),
+            step_type: StepType::Http,
+            depends_on: vec![],
+            condition: None,
+            timeout_secs: None,
+            retry: None,
+            config: StepConfig::Http {
+                method: "GET".to_string(),
+                url: "https://api.example.com/items".to_string(),
+                headers: Some(HashMap::from([(
+                    "Authorization".to_string(),
+                    "Bearer {{ secrets.API_TOKEN }}".to_string(),
+                )])),
+                body: None,
+            },
+            ui: None,
+        }];
+        let repo = SqliteWorkflowRepository::new(pool.clone());
+        repo.save_definition(&def).await.unwrap();
+
+        let recorder = Arc::new(AuthRecorder::default());
+        let executor = DagExecutor::with_execution_context(
+            SqliteWorkflowRepository::new(pool),
+            EventBus::new(16),
+            std::env::temp_dir(),
+            Arc::clone(&recorder) as Arc<dyn StepExecutionContext>,
+        )
+        .with_secrets(secrets);
+        let result = executor.execute(&def, "manual", None).await.unwrap();
+        assert_eq!(result.status, WorkflowRunStatus::Completed);
+
+        // The request carried the secret
+        assert_eq!(
+            recorder.0.lock().unwrap().as_slice(),
+            [format!("Bearer {token}")]
+        );
+
+        // The stored step log and run context did not
+        let logs = repo.list_step_logs(&result.run_id).await.unwrap();
+        assert_eq!(logs.len(), 1);
+        let output = logs[0].output.as_ref().expect("step output stored");
+        assert_eq!(output["echo"], "Bearer [REDACTED]");
+        assert!(!output.to_string().contains(token));
+        let run = repo.get_run(&result.run_id).await.unwrap().unwrap();
+        assert!(!serde_json::to_string(&run).unwrap().contains(token));
+    }
 }
@@ -5,8 +5,12 @@
 //! heading-aware splitting; all other text uses `TextSplitter`.
 //!
 //! Chunk target size: 512 characters with paragraph boundary awareness.
+//!
+//! `ChunkOptions` configures chunk size, overlap between adjacent chunks, and
+//! the `ChunkMode`: boundary-aware (never cuts inside a sentence that fits in
+//! a chunk) or fixed-size character windows.
 
-use text_splitter::{MarkdownSplitter, TextSplitter};
+use text_splitter::{ChunkConfig, MarkdownSplitter, TextSplitter};
 
 /// Default chunk size in characters.
 ///
@@ -14,6 +18,88 @@ use text_splitter::{MarkdownSplitter, TextSplitter};
 /// embedding model context window usage for BGESmallENV15.
 pub const DEFAULT_CHUNK_SIZE: usize = 512;
 
+/// How text is divided into chunks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChunkMode {
+    /// Split on paragraph, sentence, then word boundaries (Markdown-aware for
+    /// `.md` files). A sentence is only split if it alone exceeds the chunk size.
+    #[default]
+    Boundary,
+    /// Fixed-size character windows, ignoring text structure.
+    Fixed,
+}
+
+/// Errors from invalid chunking configuration.
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum ChunkOptionsError {
+    #[error("chunk size must be greater than zero")]
+    ZeroChunkSize,
+
+    #[error("chunk overlap ({overlap}) must be smaller than chunk size ({chunk_size})")]
+    OverlapTooLarge { overlap: usize, chunk_size: usize },
+}
+
+/// Chunking parameters: target size, overlap, and mode.
+///
+/// Construct via `ChunkOptions::new` so overlap is always smaller than size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkOptions {
+    chunk_size: usize,
+    overlap: usize,
+    mode: ChunkMode,
+}
+
+impl Default for ChunkOptions {
+    fn default() -> Self {
+        Self {
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            overlap: 0,
+            mode: ChunkMode::Boundary,
+        }
+    }
+}
+
+impl ChunkOptions {
+    /// Create options with the given size and overlap (in characters), boundary mode.
+    pub fn new(chunk_size: usize, overlap: usize) -> Result<Self, ChunkOptionsError> {
+        if chunk_size == 0 {
+            return Err(ChunkOptionsError::ZeroChunkSize);
+        }
+        if overlap >= chunk_size {
+            return Err(ChunkOptionsError::OverlapTooLarge {
+                overlap,
+                chunk_size,
+            });
+        }
+        Ok(Self {
+            chunk_size,
+            overlap,
+            mode: ChunkMode::Boundary,
+        })
+    }
+
+    /// Set the chunking mode.
+    pub fn with_mode(mut self, mode: ChunkMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Target chunk size in characters.
+    pub fn chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+
+    /// Characters shared between adjacent chunks.
+    pub fn overlap(&self) -> usize {
+        self.overlap
+    }
+
+    /// The chunking mode.
+    pub fn mode(&self) -> ChunkMode {
+        self.mode
+    }
+}
+
 /// Result of chunking a text file.
 #[derive(Debug)]
 pub struct ChunkResult {
@@ -38,8 +124,19 @@ pub struct ChunkResult {
 ///
 /// A `ChunkResult` with the ordered chunks and whether markdown splitting was used.
 pub fn chunk_text_file(text: &str, filename: &str, chunk_size: Option<usize>) -> ChunkResult {
-    let size = chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE);
+    let size = chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE).max(1);
+    let options = ChunkOptions {
+        chunk_size: size,
+        ..ChunkOptions::default()
+    };
+    chunk_text_with_options(text, filename, &options)
+}
 
+/// Chunk a text file using explicit size, overlap, and mode.
+///
+/// In `Boundary` mode adjacent chunks share up to `overlap` characters of
+/// whole sentences/words; in `Fixed` mode they share exactly `overlap` characters.
+pub fn chunk_text_with_options(text: &str, filename: &str, options: &ChunkOptions) -> ChunkResult {
     if text.is_empty() {
         return ChunkResult {
             chunks: vec![],
@@ -47,19 +144,80 @@ pub fn chunk_text_file(text: &str, filename: &str, chunk_size: Option<usize>) ->
         };
     }
 
+    if options.mode == ChunkMode::Fixed {
+        return ChunkResult {
+            chunks: fixed_windows(text, options.chunk_size, options.overlap),
+            is_markdown: false,
+        };
+    }
+
     let is_markdown = is_markdown_file(filename);
+    // Overlap < size is guaranteed by `ChunkOptions::new`.
+    let config = ChunkConfig::new(options.chunk_size)
+        .with_overlap(options.overlap)
+        .expect("chunk overlap validated against chunk size");
 
     let chunks: Vec<String> = if is_markdown {
-        let splitter = MarkdownSplitter::new(size);
+        let splitter = MarkdownSplitter::new(config);
         splitter.chunks(text).map(String::from).collect()
     } else {
-        let splitter = TextSplitter::new(size);
+        let splitter = TextSplitter::new(config);
         splitter.chunks(text).map(String::from).collect()
     };
 
     ChunkResult { chunks, is_markdown }
 }
 
+/// Split text into fixed-size character windows that overlap by `overlap` characters.
+fn fixed_windows(text: &str, size: usize, overlap: usize) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let step = size - overlap;
+    let mut chunks = Vec::new();
+    let mut start = 0;
+
+    loop {
+        let end = (start + size).min(chars.len());
+        chunks.push(chars[start..end].iter().collect());
+        if end == chars.len() {
+            break;
+        }
+        start += step;
+    }
+
+    chunks
+}
+
+/// Normalize text extracted from documents (PDF, DOCX) before chunking.
+///
+/// Extractors emit hard-wrapped lines and ragged whitespace. This joins
+/// wrapped lines within a paragraph and keeps blank-line paragraph breaks,
+/// so the splitter can find real paragraph and sentence boundaries.
+pub fn normalize_extracted_text(text: &str) -> String {
+    let mut paragraphs: Vec<String> = Vec::new();
+    let mut current: Vec<&str> = Vec::new();
+
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            if !current.is_empty() {
+                paragraphs.push(current.join(" "));
+                current.clear();
+            }
+        } else {
+            current.push(line);
+        }
+    }
+    if !current.is_empty() {
+        paragraphs.push(current.join(" "));
+    }
+
+    paragraphs
+        .iter()
+        .map(|p| p.split_whitespace().collect::<Vec<_>>().join(" "))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
 /// Check if a filename indicates Markdown content.
 fn is_markdown_file(filename: &str) -> bool {
     let lower = filename.to_lowercase();
@@ -111,6 +269,16 @@ mod tests {
         assert_eq!(DEFAULT_CHUNK_SIZE, 512);
     }
 
+    #[test]
+    fn test_normalize_extracted_text_joins_wrapped_lines() {
+        let raw = "  The quick brown\nfox jumps over\n\n\n  the   lazy dog.  \n";
+        assert_eq!(
+            normalize_extracted_text(raw),
+            "The quick brown fox jumps over\n\nthe lazy dog."
+        );
+        assert_eq!(normalize_extracted_text("\n \n"), "");
+    }
+
     #[test]
     fn test_is_markdown_file() {
         assert!(is_markdown_file("notes.md"));
@@ -134,6 +302,100 @@ mod tests {
         assert!(joined.contains("Section C"));
     }
 
+    fn sample_prose() -> String {
+        [
+            "The library opens at nine in the morning.",
+            "Visitors must sign in at the front desk.",
+            "Rare books are kept on the third floor.",
+            "Photography is not allowed in the reading room.",
+            "The cafe closes an hour before the library does.",
+            "Lockers are available for large bags and coats.",
+        ]
+        .join(" ")
+    }
+
+    #[test]
+    fn test_chunk_options_validation() {
+        assert_eq!(
+            ChunkOptions::new(0, 0).unwrap_err(),
+            ChunkOptionsError::ZeroChunkSize
+        );
+        assert_eq!(
+            ChunkOptions::new(100, 100).unwrap_err(),
+            ChunkOptionsError::OverlapTooLarge {
+                overlap: 100,
+                chunk_size: 100
+            }
+        );
+        let opts = ChunkOptions::new(100, 20).unwrap();
+        assert_eq!(opts.chunk_size(), 100);
+        assert_eq!(opts.overlap(), 20);
+        assert_eq!(opts.mode(), ChunkMode::Boundary);
+    }
+
+    #[test]
+    fn test_fixed_mode_shares_overlap_between_adjacent_chunks() {
+        let text = sample_prose();
+        let opts = ChunkOptions::new(50, 10).unwrap().with_mode(ChunkMode::Fixed);
+        let result = chunk_text_with_options(&text, "notes.txt", &opts);
+
+        assert!(result.chunks.len() > 2);
+        for pair in result.chunks.windows(2) {
+            let prev: Vec<char> = pair[0].chars().collect();
+            let tail: String = prev[prev.len() - 10..].iter().collect();
+            assert!(pair[1].starts_with(&tail), "{:?} / {:?}", pair[0], pair[1]);
+        }
+        // All but the last chunk are full-size
+        assert!(result.chunks[..result.chunks.len() - 1]
+            .iter()
+            .all(|c| c.chars().count() == 50));
+    }
+
+    #[test]
+    fn test_boundary_mode_overlap_repeats_text() {
+        let text = sample_prose();
+        let opts = ChunkOptions::new(100, 45).unwrap();
+        let result = chunk_text_with_options(&text, "notes.txt", &opts);
+
+        assert!(result.chunks.len() > 2);
+        for pair in result.chunks.windows(2) {
+            // Some suffix of the previous chunk reappears at the start of the next
+            let shared = pair[0]
+                .split(' ')
+                .rev()
+                .take(2)
+                .collect::<Vec<_>>()
+                .into_iter()
+                .rev()
+                .collect::<Vec<_>>()
+                .join(" ");
+            assert!(pair[1].contains(&shared), "{:?} / {:?}", pair[0], pair[1]);
+        }
+    }
+
+    #[test]
+    fn test_boundary_mode_does_not_cut_inside_sentences() {
+        let text = sample_prose();
+        let sentences: Vec<&str> = text.split_inclusive(". ").map(str::trim).collect();
+        let opts = ChunkOptions::new(120, 0).unwrap();
+        let result = chunk_text_with_options(&text, "notes.txt", &opts);
+
+        assert!(result.chunks.len() > 1);
+        for chunk in &result.chunks {
+            assert!(chunk.ends_with('.'), "chunk cut mid-sentence: {chunk:?}");
+            // Every chunk is made of whole sentences
+            assert!(sentences.iter().any(|s| chunk.starts_with(s)), "{chunk:?}");
+        }
+    }
+
+    #[test]
+    fn test_chunk_text_file_matches_default_options() {
+        let text = sample_prose();
+        let a = chunk_text_file(&text, "notes.txt", Some(80));
+        let b = chunk_text_with_options(&text, "notes.txt", &ChunkOptions::new(80, 0).unwrap());
+        assert_eq!(a.chunks, b.chunks);
+    }
+
     #[test]
     fn test_chunk_large_text() {
         // Create a large text with many paragraphs
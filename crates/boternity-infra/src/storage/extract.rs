@@ -0,0 +1,223 @@
+//! Text extraction from binary document formats (PDF, DOCX).
+//!
+//! Compiled only with the `document-extract` feature. Extracted text is fed
+//! through the same chunk-and-embed pipeline as plain text files, so PDFs
+//! and Word documents become searchable.
+//!
+//! Extraction is best-effort: encrypted or corrupt documents produce an
+//! `ExtractError` and the indexer skips them with a warning.
+
+use std::io::Read;
+
+/// Errors that can occur while extracting document text.
+#[derive(Debug, thiserror::Error)]
+pub enum ExtractError {
+    /// The PDF is password-protected.
+    #[error("document is encrypted")]
+    Encrypted,
+
+    /// The document could not be parsed.
+    #[error("document is corrupt or unreadable: {0}")]
+    Corrupt(String),
+
+    /// The MIME type (or legacy format) is not supported.
+    #[error("unsupported document format: {0}")]
+    Unsupported(String),
+}
+
+/// Check whether a MIME type has a text extractor.
+pub fn is_extractable_mime(mime: &str) -> bool {
+    matches!(mime, "application/pdf" | "application/msword")
+}
+
+/// Extract plain text from a document's bytes.
+///
+/// CPU-bound; call from `spawn_blocking` in async contexts.
+pub fn extract_text(mime: &str, content: &[u8]) -> Result<String, ExtractError> {
+    match mime {
+        "application/pdf" => extract_pdf(content),
+        "application/msword" => extract_docx(content),
+        other => Err(ExtractError::Unsupported(other.to_string())),
+    }
+}
+
+/// Extract text from a PDF.
+fn extract_pdf(content: &[u8]) -> Result<String, ExtractError> {
+    if !content.starts_with(b"%PDF") {
+        return Err(ExtractError::Corrupt("missing %PDF header".to_string()));
+    }
+    if content.windows(8).any(|w| w == b"/Encrypt") {
+        return Err(ExtractError::Encrypted);
+    }
+
+    // pdf-extract can panic on malformed streams; contain it.
+    let result = std::panic::catch_unwind(|| pdf_extract::extract_text_from_mem(content));
+    match result {
+        Ok(Ok(text)) => Ok(text),
+        Ok(Err(e)) => Err(ExtractError::Corrupt(e.to_string())),
+        Err(_) => Err(ExtractError::Corrupt("PDF parser panicked".to_string())),
+    }
+}
+
+/// Extract text from a DOCX (Office Open XML) document.
+///
+/// Reads `word/document.xml` and collects `<w:t>` runs, emitting a blank
+/// line between paragraphs. Legacy binary `.doc` files are not supported.
+fn extract_docx(content: &[u8]) -> Result<String, ExtractError> {
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(content)).map_err(|_| {
+        ExtractError::Unsupported("not a DOCX archive (legacy .doc is unsupported)".to_string())
+    })?;
+
+    let mut xml = String::new();
+    archive
+        .by_name("word/document.xml")
+        .map_err(|e| ExtractError::Corrupt(format!("missing word/document.xml: {e}")))?
+        .read_to_string(&mut xml)
+        .map_err(|e| ExtractError::Corrupt(e.to_string()))?;
+
+    Ok(docx_xml_to_text(&xml))
+}
+
+/// Convert WordprocessingML to plain text.
+fn docx_xml_to_text(xml: &str) -> String {
+    let mut out = String::new();
+    let mut rest = xml;
+
+    while let Some(start) = rest.find('<') {
+        let Some(end) = rest[start..].find('>') else {
+            break;
+        };
+        let tag = &rest[start + 1..start + end];
+        rest = &rest[start + end + 1..];
+
+        let name = tag.split_whitespace().next().unwrap_or("");
+        match name {
+            "w:t" => {
+                let close = rest.find("</w:t>").unwrap_or(rest.len());
+                out.push_str(&unescape_xml(&rest[..close]));
+                rest = &rest[close..];
+            }
+            "w:tab/" => out.push('\t'),
+            "w:br/" => out.push('\n'),
+            "/w:p" => out.push_str("\n\n"),
+            _ => {}
+        }
+    }
+
+    out.trim().to_string()
+}
+
+/// Decode the predefined XML entities.
+fn unescape_xml(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::io::Write;
+
+    /// Build a minimal single-page PDF with one line of Helvetica text.
+    pub(crate) fn sample_pdf(text: &str) -> Vec<u8> {
+        let stream = format!("BT /F1 12 Tf 72 720 Td ({text}) Tj ET");
+        let objects = [
+            "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+            "<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_string(),
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] \
+             /Resources << /Font << /F1 5 0 R >> >> /Contents 4 0 R >>"
+                .to_string(),
+            format!("<< /Length {} >>\nstream\n{stream}\nendstream", stream.len()),
+            "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica >>".to_string(),
+        ];
+
+        let mut pdf = b"%PDF-1.4\n".to_vec();
+        let mut offsets = Vec::new();
+        for (i, obj) in objects.iter().enumerate() {
+            offsets.push(pdf.len());
+            pdf.extend_from_slice(format!("{} 0 obj\n{obj}\nendobj\n", i + 1).as_bytes());
+        }
+
+        let xref_offset = pdf.len();
+        pdf.extend_from_slice(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).as_bytes());
+        for offset in offsets {
+            pdf.extend_from_slice(format!("{offset:010} 00000 n \n").as_bytes());
+        }
+        pdf.extend_from_slice(
+            format!(
+                "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref_offset}\n%%EOF\n",
+                objects.len() + 1
+            )
+            .as_bytes(),
+        );
+        pdf
+    }
+
+    /// Build a minimal DOCX archive whose body contains the given paragraphs.
+    fn sample_docx(paragraphs: &[&str]) -> Vec<u8> {
+        let body: String = paragraphs
+            .iter()
+            .map(|p| format!("<w:p><w:r><w:t xml:space=\"preserve\">{p}</w:t></w:r></w:p>"))
+            .collect();
+        let xml = format!(
+            "<?xml version=\"1.0\"?><w:document xmlns:w=\"http://schemas.openxmlformats.org/wordprocessingml/2006/main\"><w:body>{body}</w:body></w:document>"
+        );
+
+        let mut buf = std::io::Cursor::new(Vec::new());
+        let mut writer = zip::ZipWriter::new(&mut buf);
+        writer
+            .start_file("word/document.xml", zip::write::SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(xml.as_bytes()).unwrap();
+        writer.finish().unwrap();
+        buf.into_inner()
+    }
+
+    #[test]
+    fn test_extract_pdf_text() {
+        let pdf = sample_pdf("Quarterly revenue grew twelve percent");
+        let text = extract_text("application/pdf", &pdf).unwrap();
+        assert!(text.contains("Quarterly revenue grew twelve percent"), "got: {text:?}");
+    }
+
+    #[test]
+    fn test_encrypted_pdf_is_rejected() {
+        let mut pdf = sample_pdf("secret");
+        pdf.extend_from_slice(b"trailer\n<< /Encrypt 9 0 R >>\n");
+        assert!(matches!(
+            extract_text("application/pdf", &pdf),
+            Err(ExtractError::Encrypted)
+        ));
+    }
+
+    #[test]
+    fn test_corrupt_pdf_is_rejected() {
+        assert!(matches!(
+            extract_text("application/pdf", b"%PDF-1.4 garbage"),
+            Err(ExtractError::Corrupt(_))
+        ));
+        assert!(matches!(
+            extract_text("application/pdf", b"not a pdf"),
+            Err(ExtractError::Corrupt(_))
+        ));
+    }
+
+    #[test]
+    fn test_extract_docx_paragraphs() {
+        let docx = sample_docx(&["Meeting notes", "Ship &amp; celebrate"]);
+        let text = extract_text("application/msword", &docx).unwrap();
+        assert_eq!(text, "Meeting notes\n\nShip & celebrate");
+    }
+
+    #[test]
+    fn test_legacy_doc_is_unsupported() {
+        let ole_header = [0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1];
+        assert!(matches!(
+            extract_text("application/msword", &ole_header),
+            Err(ExtractError::Unsupported(_))
+        ));
+    }
+}
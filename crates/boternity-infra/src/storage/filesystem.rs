@@ -1,8 +1,9 @@
 //! Local filesystem file store implementation.
 //!
 //! Implements the `FileStore` trait from `boternity-core` with files stored at
-//! `{base_dir}/bots/{slug}/files/`. Version history is preserved in a
-//! `.versions/` subdirectory alongside each file.
+//! `{base_dir}/bots/{slug}/files/`. File content is content-addressed: each
+//! version's bytes live in a `.blobs/` subdirectory keyed by SHA-256, so
+//! re-uploading identical content reuses the existing blob.
 //!
 //! File metadata is tracked in SQLite via `SqliteFileMetadataStore` (03-05);
 //! this module handles the actual bytes on disk plus the `FileStore` trait glue.
@@ -13,28 +14,35 @@ use boternity_core::storage::file_store::FileStore;
 use boternity_types::error::RepositoryError;
 use boternity_types::storage::{FileVersion, StorageFile, MAX_FILE_SIZE_BYTES};
 use chrono::Utc;
+use sha2::{Digest, Sha256};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
 use uuid::Uuid;
 
 use crate::sqlite::file_metadata::SqliteFileMetadataStore;
 
+/// Buffer size used when streaming file content to and from disk.
+pub const STREAM_CHUNK_SIZE: usize = 64 * 1024;
+
 /// Local filesystem-backed file store with version history.
 ///
 /// Directory layout per bot:
 /// ```text
 /// {base_dir}/bots/{slug}/files/
-///   notes.txt
-///   report.md
-///   .versions/
+///   .blobs/
+///     3a7bd3e2...   (SHA-256 of content)
+///     9f86d081...
+///   .versions/      (legacy, pre content-addressing)
 ///     notes.txt.v1
-///     notes.txt.v2
-///     report.md.v1
 /// ```
 ///
 /// On each save:
-/// 1. Copy current file to `.versions/{filename}.v{version}` (if exists)
-/// 2. Write new content to `{filename}`
+/// 1. Hash the content (SHA-256)
+/// 2. Write `.blobs/{hash}` unless an identical blob already exists
 /// 3. Record metadata in SQLite
-/// 4. Record version in SQLite
+/// 4. Record version (with content hash) in SQLite
+///
+/// Files and versions written before content addressing have no hash and
+/// are still read from `{filename}` / `.versions/{filename}.v{N}`.
 pub struct LocalFileStore {
     base_dir: PathBuf,
     metadata_store: SqliteFileMetadataStore,
@@ -61,6 +69,11 @@ impl LocalFileStore {
         self.bot_files_dir(slug).join(".versions")
     }
 
+    /// Compute the content-addressed blob directory for a given bot slug.
+    fn bot_blobs_dir(&self, slug: &str) -> PathBuf {
+        self.bot_files_dir(slug).join(".blobs")
+    }
+
     /// Compute the file path on disk.
     fn file_path(&self, slug: &str, filename: &str) -> PathBuf {
         self.bot_files_dir(slug).join(filename)
@@ -72,91 +85,131 @@ impl LocalFileStore {
             .join(format!("{}.v{}", filename, version))
     }
 
-}
+    /// Compute the blob path for a content hash.
+    fn blob_path(&self, slug: &str, content_hash: &str) -> PathBuf {
+        self.bot_blobs_dir(slug).join(content_hash)
+    }
 
-impl FileStore for LocalFileStore {
-    async fn save_file(
+    /// Compute the lowercase hex SHA-256 digest used as the blob key.
+    fn content_hash(data: &[u8]) -> String {
+        format!("{:x}", Sha256::digest(data))
+    }
+
+    /// Resolve the on-disk path holding the bytes for a version record.
+    ///
+    /// Content-addressed versions are read from their blob; legacy versions
+    /// fall back to the pre-dedup layout.
+    fn version_content_path(
         &self,
-        bot_id: &Uuid,
-        filename: &str,
-        data: &[u8],
-    ) -> Result<StorageFile, RepositoryError> {
-        // Enforce 50MB size limit
-        if data.len() as u64 > MAX_FILE_SIZE_BYTES {
-            return Err(RepositoryError::Conflict(format!(
-                "File exceeds maximum size of {} bytes (got {} bytes)",
-                MAX_FILE_SIZE_BYTES,
-                data.len()
-            )));
+        slug: &str,
+        file: &StorageFile,
+        version: Option<&FileVersion>,
+        version_number: u32,
+    ) -> PathBuf {
+        match version.and_then(|v| v.content_hash.as_deref()) {
+            Some(hash) => self.blob_path(slug, hash),
+            None if version_number == file.version => self.file_path(slug, &file.filename),
+            None => self.version_path(slug, &file.filename, version_number),
         }
+    }
 
-        // Validate filename (no path traversal)
+    /// Read the bytes for a version record.
+    async fn read_version_bytes(
+        &self,
+        slug: &str,
+        file: &StorageFile,
+        version: Option<&FileVersion>,
+        version_number: u32,
+    ) -> Result<Vec<u8>, RepositoryError> {
+        let path = self.version_content_path(slug, file, version, version_number);
+
+        tokio::fs::read(&path)
+            .await
+            .map_err(|e| RepositoryError::Query(format!("Failed to read file: {e}")))
+    }
+
+    /// Reject filenames that could escape the bot's files directory.
+    fn validate_filename(filename: &str) -> Result<(), RepositoryError> {
         if filename.contains("..") || filename.contains('/') || filename.contains('\\') {
             return Err(RepositoryError::Conflict(
                 "Filename must not contain path separators or '..'".to_string(),
             ));
         }
+        Ok(())
+    }
+
+    /// Build the conflict error returned when content exceeds the size limit.
+    fn size_limit_error(size: u64) -> RepositoryError {
+        RepositoryError::Conflict(format!(
+            "File exceeds maximum size of {} bytes (got {} bytes)",
+            MAX_FILE_SIZE_BYTES, size
+        ))
+    }
+
+    /// Create the blob directory for a bot and return its path.
+    async fn ensure_blobs_dir(&self, slug: &str) -> Result<PathBuf, RepositoryError> {
+        let blobs_dir = self.bot_blobs_dir(slug);
+        tokio::fs::create_dir_all(&blobs_dir)
+            .await
+            .map_err(|e| RepositoryError::Query(format!("Failed to create blobs dir: {e}")))?;
+        Ok(blobs_dir)
+    }
 
+    /// Move a fully written temp file into place as the blob for `content_hash`.
+    ///
+    /// If an identical blob already exists the temp file is discarded instead.
+    async fn commit_blob(
+        &self,
+        slug: &str,
+        tmp_path: &std::path::Path,
+        content_hash: &str,
+    ) -> Result<(), RepositoryError> {
+        let blob_path = self.blob_path(slug, content_hash);
+        if blob_path.exists() {
+            let _ = tokio::fs::remove_file(tmp_path).await;
+            return Ok(());
+        }
+        tokio::fs::rename(tmp_path, &blob_path)
+            .await
+            .map_err(|e| RepositoryError::Query(format!("Failed to store blob: {e}")))
+    }
+
+    /// Upsert file metadata and append a version record for new content.
+    async fn record_version(
+        &self,
+        bot_id: &Uuid,
+        filename: &str,
+        size_bytes: u64,
+        content_hash: String,
+    ) -> Result<StorageFile, RepositoryError> {
         let mime_type = super::detect_mime(filename);
         let now = Utc::now();
 
         // Check if file already exists (update vs create)
         let existing = self.metadata_store.get_file(bot_id, filename).await?;
 
-        let (file, new_version) = if let Some(mut existing_file) = existing {
+        let file = if let Some(mut existing_file) = existing {
             // Existing file: bump version
-            let new_version = existing_file.version + 1;
-            existing_file.version = new_version;
-            existing_file.size_bytes = data.len() as u64;
+            existing_file.version += 1;
+            existing_file.size_bytes = size_bytes;
             existing_file.mime_type = mime_type;
             existing_file.updated_at = now;
-
-            (existing_file, new_version)
+            existing_file
         } else {
             // New file
-            let file = StorageFile {
+            StorageFile {
                 id: Uuid::now_v7(),
                 bot_id: *bot_id,
                 filename: filename.to_string(),
                 mime_type,
-                size_bytes: data.len() as u64,
+                size_bytes,
                 version: 1,
                 is_indexed: false,
                 created_at: now,
                 updated_at: now,
-            };
-            (file, 1)
+            }
         };
 
-        // Use bot_id as directory name (simple form) for filesystem storage
-        let slug = bot_id.simple().to_string();
-        let files_dir = self.bot_files_dir(&slug);
-        let versions_dir = self.bot_versions_dir(&slug);
-
-        // Ensure directories exist
-        tokio::fs::create_dir_all(&files_dir)
-            .await
-            .map_err(|e| RepositoryError::Query(format!("Failed to create files dir: {e}")))?;
-        tokio::fs::create_dir_all(&versions_dir)
-            .await
-            .map_err(|e| RepositoryError::Query(format!("Failed to create versions dir: {e}")))?;
-
-        let file_path = self.file_path(&slug, filename);
-
-        // If file exists on disk, copy current content to version archive
-        if file_path.exists() {
-            let prev_version = new_version - 1;
-            let version_path = self.version_path(&slug, filename, prev_version);
-            tokio::fs::copy(&file_path, &version_path).await.map_err(|e| {
-                RepositoryError::Query(format!("Failed to archive version {prev_version}: {e}"))
-            })?;
-        }
-
-        // Write new content
-        tokio::fs::write(&file_path, data)
-            .await
-            .map_err(|e| RepositoryError::Query(format!("Failed to write file: {e}")))?;
-
         // Save metadata to SQLite
         self.metadata_store.save_file(&file).await?;
 
@@ -164,8 +217,9 @@ impl FileStore for LocalFileStore {
         let version_record = FileVersion {
             id: Uuid::now_v7(),
             file_id: file.id,
-            version: new_version,
-            size_bytes: data.len() as u64,
+            version: file.version,
+            size_bytes,
+            content_hash: Some(content_hash),
             created_at: now,
         };
         self.metadata_store.save_version(&version_record).await?;
@@ -173,24 +227,173 @@ impl FileStore for LocalFileStore {
         Ok(file)
     }
 
+    /// Save a file from an async reader without buffering it in memory.
+    ///
+    /// Content is streamed to a temp file in `.blobs/` while being hashed,
+    /// then renamed into place once the hash is known. The size limit is
+    /// enforced as bytes arrive, so oversize uploads are aborted early.
+    pub async fn save_file_stream<R>(
+        &self,
+        bot_id: &Uuid,
+        filename: &str,
+        mut reader: R,
+    ) -> Result<StorageFile, RepositoryError>
+    where
+        R: AsyncRead + Unpin,
+    {
+        Self::validate_filename(filename)?;
+
+        let slug = bot_id.simple().to_string();
+        let blobs_dir = self.ensure_blobs_dir(&slug).await?;
+        let tmp_path = blobs_dir.join(format!("upload-{}.tmp", Uuid::now_v7()));
+
+        let written = async {
+            let mut out = tokio::fs::File::create(&tmp_path)
+                .await
+                .map_err(|e| RepositoryError::Query(format!("Failed to write file: {e}")))?;
+            let mut hasher = Sha256::new();
+            let mut total: u64 = 0;
+            let mut buf = vec![0u8; STREAM_CHUNK_SIZE];
+
+            loop {
+                let n = reader
+                    .read(&mut buf)
+                    .await
+                    .map_err(|e| RepositoryError::Query(format!("Failed to read upload: {e}")))?;
+                if n == 0 {
+                    break;
+                }
+                total += n as u64;
+                if total > MAX_FILE_SIZE_BYTES {
+                    return Err(Self::size_limit_error(total));
+                }
+                hasher.update(&buf[..n]);
+                out.write_all(&buf[..n])
+                    .await
+                    .map_err(|e| RepositoryError::Query(format!("Failed to write file: {e}")))?;
+            }
+
+            out.flush()
+                .await
+                .map_err(|e| RepositoryError::Query(format!("Failed to write file: {e}")))?;
+            Ok((format!("{:x}", hasher.finalize()), total))
+        }
+        .await;
+
+        let (content_hash, size_bytes) = match written {
+            Ok(v) => v,
+            Err(e) => {
+                let _ = tokio::fs::remove_file(&tmp_path).await;
+                return Err(e);
+            }
+        };
+
+        self.commit_blob(&slug, &tmp_path, &content_hash).await?;
+        self.record_version(bot_id, filename, size_bytes, content_hash)
+            .await
+    }
+
+    /// Open the current version of a file for streaming reads.
+    ///
+    /// Returns the metadata (for MIME type and content length) alongside an
+    /// open handle positioned at the start of the content.
+    pub async fn open_file(
+        &self,
+        bot_id: &Uuid,
+        filename: &str,
+    ) -> Result<(StorageFile, tokio::fs::File), RepositoryError> {
+        let file = self
+            .metadata_store
+            .get_file(bot_id, filename)
+            .await?
+            .ok_or(RepositoryError::NotFound)?;
+
+        let slug = bot_id.simple().to_string();
+        let latest = self.metadata_store.get_version(&file.id, file.version).await?;
+        let path = self.version_content_path(&slug, &file, latest.as_ref(), file.version);
+
+        let handle = tokio::fs::File::open(&path)
+            .await
+            .map_err(|e| RepositoryError::Query(format!("Failed to open file: {e}")))?;
+
+        Ok((file, handle))
+    }
+
+    /// Retrieve the content of a specific historical version of a file.
+    pub async fn get_file_version(
+        &self,
+        bot_id: &Uuid,
+        filename: &str,
+        version: u32,
+    ) -> Result<Vec<u8>, RepositoryError> {
+        let file = self
+            .metadata_store
+            .get_file(bot_id, filename)
+            .await?
+            .ok_or(RepositoryError::NotFound)?;
+
+        let record = self.metadata_store.get_version(&file.id, version).await?;
+        if record.is_none() && version != file.version {
+            return Err(RepositoryError::NotFound);
+        }
+
+        let slug = bot_id.simple().to_string();
+        self.read_version_bytes(&slug, &file, record.as_ref(), version)
+            .await
+    }
+}
+
+impl FileStore for LocalFileStore {
+    async fn save_file(
+        &self,
+        bot_id: &Uuid,
+        filename: &str,
+        data: &[u8],
+    ) -> Result<StorageFile, RepositoryError> {
+        // Enforce 50MB size limit
+        if data.len() as u64 > MAX_FILE_SIZE_BYTES {
+            return Err(Self::size_limit_error(data.len() as u64));
+        }
+
+        // Validate filename (no path traversal)
+        Self::validate_filename(filename)?;
+
+        // Use bot_id as directory name (simple form) for filesystem storage
+        let slug = bot_id.simple().to_string();
+        let blobs_dir = self.ensure_blobs_dir(&slug).await?;
+
+        // Write the blob only if identical content isn't already stored.
+        // Write-then-rename so a crash never leaves a truncated blob behind.
+        let content_hash = Self::content_hash(data);
+        if !self.blob_path(&slug, &content_hash).exists() {
+            let tmp_path = blobs_dir.join(format!("{content_hash}.{}.tmp", Uuid::now_v7()));
+            tokio::fs::write(&tmp_path, data)
+                .await
+                .map_err(|e| RepositoryError::Query(format!("Failed to write file: {e}")))?;
+            self.commit_blob(&slug, &tmp_path, &content_hash).await?;
+        }
+
+        self.record_version(bot_id, filename, data.len() as u64, content_hash)
+            .await
+    }
+
     async fn get_file(
         &self,
         bot_id: &Uuid,
         filename: &str,
     ) -> Result<Vec<u8>, RepositoryError> {
         // Verify file exists in metadata
-        let _file = self
+        let file = self
             .metadata_store
             .get_file(bot_id, filename)
             .await?
             .ok_or(RepositoryError::NotFound)?;
 
         let slug = bot_id.simple().to_string();
-        let file_path = self.file_path(&slug, filename);
+        let latest = self.metadata_store.get_version(&file.id, file.version).await?;
 
-        tokio::fs::read(&file_path)
+        self.read_version_bytes(&slug, &file, latest.as_ref(), file.version)
             .await
-            .map_err(|e| RepositoryError::Query(format!("Failed to read file: {e}")))
     }
 
     async fn delete_file(
@@ -205,17 +408,26 @@ impl FileStore for LocalFileStore {
             .await?
             .ok_or(RepositoryError::NotFound)?;
 
+        // Collect referenced blobs before the version rows are cascaded away
+        let mut hashes: Vec<String> = self
+            .metadata_store
+            .get_versions(&file.id)
+            .await?
+            .into_iter()
+            .filter_map(|v| v.content_hash)
+            .collect();
+        hashes.sort();
+        hashes.dedup();
+
         let slug = bot_id.simple().to_string();
-        let file_path = self.file_path(&slug, filename);
 
-        // Delete the main file from disk
+        // Delete legacy (pre content-addressing) files from disk
+        let file_path = self.file_path(&slug, filename);
         if file_path.exists() {
             tokio::fs::remove_file(&file_path).await.map_err(|e| {
                 RepositoryError::Query(format!("Failed to delete file: {e}"))
             })?;
         }
-
-        // Delete all version files from disk
         for v in 1..=file.version {
             let version_path = self.version_path(&slug, filename, v);
             if version_path.exists() {
@@ -226,6 +438,13 @@ impl FileStore for LocalFileStore {
         // Delete metadata (cascades to versions via FK)
         self.metadata_store.delete_file(bot_id, filename).await?;
 
+        // Remove blobs no longer referenced by any other file of this bot
+        for hash in &hashes {
+            if self.metadata_store.count_hash_references(bot_id, hash).await? == 0 {
+                let _ = tokio::fs::remove_file(self.blob_path(&slug, hash)).await;
+            }
+        }
+
         Ok(())
     }
 
@@ -342,13 +561,143 @@ mod tests {
         assert_eq!(versions[0].version, 2); // latest first
         assert_eq!(versions[1].version, 1);
 
-        // Version file should exist on disk
-        let slug = bot_id.simple().to_string();
-        let v1_path = store.version_path(&slug, "doc.md", 1);
-        let v1_content = tokio::fs::read(&v1_path).await.unwrap();
+        // Historical version content should still be retrievable
+        let v1_content = store.get_file_version(&bot_id, "doc.md", 1).await.unwrap();
         assert_eq!(v1_content, b"# Version 1");
     }
 
+    /// Deterministic byte generator that produces `remaining` bytes lazily.
+    struct PatternReader {
+        remaining: u64,
+        offset: u64,
+    }
+
+    impl AsyncRead for PatternReader {
+        fn poll_read(
+            mut self: std::pin::Pin<&mut Self>,
+            _cx: &mut std::task::Context<'_>,
+            buf: &mut tokio::io::ReadBuf<'_>,
+        ) -> std::task::Poll<std::io::Result<()>> {
+            let n = (buf.remaining() as u64).min(self.remaining) as usize;
+            let start = self.offset;
+            let chunk: Vec<u8> = (0..n as u64).map(|i| ((start + i) % 251) as u8).collect();
+            buf.put_slice(&chunk);
+            self.remaining -= n as u64;
+            self.offset += n as u64;
+            std::task::Poll::Ready(Ok(()))
+        }
+    }
+
+    /// Hash an async reader in fixed-size chunks.
+    async fn hash_reader<R: AsyncRead + Unpin>(mut reader: R) -> (String, u64) {
+        let mut hasher = Sha256::new();
+        let mut buf = vec![0u8; STREAM_CHUNK_SIZE];
+        let mut total = 0u64;
+        loop {
+            let n = reader.read(&mut buf).await.unwrap();
+            if n == 0 {
+                break;
+            }
+            hasher.update(&buf[..n]);
+            total += n as u64;
+        }
+        (format!("{:x}", hasher.finalize()), total)
+    }
+
+    #[tokio::test]
+    async fn test_stream_large_file_roundtrip() {
+        let pool = test_pool().await;
+        let bot_id = setup_bot(&pool).await;
+        let (store, _dir) = make_store(pool).await;
+
+        let size = 32 * 1024 * 1024 + 17;
+        let (expected_hash, _) = hash_reader(PatternReader { remaining: size, offset: 0 }).await;
+
+        let file = store
+            .save_file_stream(&bot_id, "large.bin", PatternReader { remaining: size, offset: 0 })
+            .await
+            .unwrap();
+        assert_eq!(file.size_bytes, size);
+        assert_eq!(file.mime_type, "application/octet-stream");
+
+        let (meta, handle) = store.open_file(&bot_id, "large.bin").await.unwrap();
+        assert_eq!(meta.size_bytes, size);
+
+        let (actual_hash, actual_size) = hash_reader(handle).await;
+        assert_eq!(actual_size, size);
+        assert_eq!(actual_hash, expected_hash);
+    }
+
+    #[tokio::test]
+    async fn test_stream_rejects_oversize_and_cleans_up() {
+        let pool = test_pool().await;
+        let bot_id = setup_bot(&pool).await;
+        let (store, _dir) = make_store(pool).await;
+
+        let reader = PatternReader {
+            remaining: MAX_FILE_SIZE_BYTES + 1,
+            offset: 0,
+        };
+        let result = store.save_file_stream(&bot_id, "huge.bin", reader).await;
+        assert!(matches!(result, Err(RepositoryError::Conflict(_))));
+
+        // No partial temp file or metadata is left behind
+        let slug = bot_id.simple().to_string();
+        let mut entries = tokio::fs::read_dir(store.bot_blobs_dir(&slug)).await.unwrap();
+        assert!(entries.next_entry().await.unwrap().is_none());
+        assert!(store.get_file(&bot_id, "huge.bin").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_identical_content_shares_single_blob() {
+        let pool = test_pool().await;
+        let bot_id = setup_bot(&pool).await;
+        let (store, _dir) = make_store(pool).await;
+
+        store.save_file(&bot_id, "same.txt", b"duplicate bytes").await.unwrap();
+        let file = store.save_file(&bot_id, "same.txt", b"duplicate bytes").await.unwrap();
+        assert_eq!(file.version, 2);
+
+        // Two logical versions referencing the same content hash
+        let versions = store.get_versions(&file.id).await.unwrap();
+        assert_eq!(versions.len(), 2);
+        assert!(versions[0].content_hash.is_some());
+        assert_eq!(versions[0].content_hash, versions[1].content_hash);
+
+        // Exactly one physical blob on disk
+        let slug = bot_id.simple().to_string();
+        let mut entries = tokio::fs::read_dir(store.bot_blobs_dir(&slug)).await.unwrap();
+        let mut blob_count = 0;
+        while entries.next_entry().await.unwrap().is_some() {
+            blob_count += 1;
+        }
+        assert_eq!(blob_count, 1);
+
+        let content = store.get_file(&bot_id, "same.txt").await.unwrap();
+        assert_eq!(content, b"duplicate bytes");
+    }
+
+    #[tokio::test]
+    async fn test_delete_keeps_blob_shared_with_other_file() {
+        let pool = test_pool().await;
+        let bot_id = setup_bot(&pool).await;
+        let (store, _dir) = make_store(pool).await;
+
+        store.save_file(&bot_id, "a.txt", b"shared").await.unwrap();
+        store.save_file(&bot_id, "b.txt", b"shared").await.unwrap();
+
+        store.delete_file(&bot_id, "a.txt").await.unwrap();
+
+        // b.txt still references the blob
+        let content = store.get_file(&bot_id, "b.txt").await.unwrap();
+        assert_eq!(content, b"shared");
+
+        store.delete_file(&bot_id, "b.txt").await.unwrap();
+        let slug = bot_id.simple().to_string();
+        let hash = LocalFileStore::content_hash(b"shared");
+        assert!(!store.blob_path(&slug, &hash).exists());
+    }
+
     #[tokio::test]
     async fn test_delete_removes_files_and_versions() {
         let pool = test_pool().await;
@@ -2,17 +2,18 @@
 //!
 //! Chunks text files, generates embeddings via `FastEmbedEmbedder`, and stores
 //! chunk vectors in LanceDB for semantic search. Non-text files are stored
-//! but not indexed.
+//! but not indexed, except PDF/DOCX documents when the `document-extract`
+//! feature is enabled (their text is extracted first).
 //!
 //! Each bot has its own `file_chunks_{bot_id}` table in LanceDB.
 
 use std::sync::Arc;
 
-use arrow_array::{Array, FixedSizeListArray, Int32Array, RecordBatch, RecordBatchIterator, StringArray};
+use arrow_array::{Array, FixedSizeListArray, Float32Array, Int32Array, RecordBatch, RecordBatchIterator, StringArray};
 use arrow_schema::{DataType, Field, Schema};
 use boternity_core::memory::embedder::Embedder;
 use boternity_types::error::RepositoryError;
-use boternity_types::storage::FileChunk;
+use boternity_types::storage::{FileChunk, FileChunkMatch};
 use futures_util::TryStreamExt;
 use lancedb::query::{ExecutableQuery, QueryBase};
 use uuid::Uuid;
@@ -20,29 +21,44 @@ use uuid::Uuid;
 use crate::vector::lance::LanceVectorStore;
 use crate::vector::schema::{file_chunks_schema, EMBEDDING_DIMENSION};
 
-use super::chunker::{chunk_text_file, ChunkResult};
+use super::chunker::{chunk_text_with_options, ChunkOptions, ChunkResult};
 
 /// File indexer that chunks text and stores embeddings in LanceDB.
 ///
-/// Works with any `Embedder` implementation (FastEmbedEmbedder in production).
+/// Works with any `Embedder` implementation (the configured `BoxEmbedder` in
+/// production).
 pub struct FileIndexer<E: Embedder> {
     vector_store: Arc<LanceVectorStore>,
     embedder: Arc<E>,
+    chunk_options: ChunkOptions,
 }
 
 impl<E: Embedder> FileIndexer<E> {
-    /// Create a new file indexer.
+    /// Create a new file indexer with default chunking (512 chars, no overlap).
     pub fn new(vector_store: Arc<LanceVectorStore>, embedder: Arc<E>) -> Self {
         Self {
             vector_store,
             embedder,
+            chunk_options: ChunkOptions::default(),
         }
     }
 
+    /// Use custom chunk size, overlap, and mode for subsequently indexed files.
+    pub fn with_chunk_options(mut self, chunk_options: ChunkOptions) -> Self {
+        self.chunk_options = chunk_options;
+        self
+    }
+
+    /// The chunking options used when indexing.
+    pub fn chunk_options(&self) -> &ChunkOptions {
+        &self.chunk_options
+    }
+
     /// Index a text file: chunk it, embed the chunks, and store in LanceDB.
     ///
     /// Returns the list of `FileChunk` records created (without embeddings).
-    /// Non-text files are silently skipped (returns empty vec).
+    /// Non-indexable files are silently skipped (returns empty vec). Documents
+    /// that fail text extraction (encrypted, corrupt) are skipped with a warning.
     ///
     /// # Arguments
     ///
@@ -58,20 +74,33 @@ impl<E: Embedder> FileIndexer<E> {
         content: &[u8],
     ) -> Result<Vec<FileChunk>, RepositoryError> {
         let mime = super::detect_mime(filename);
-        if !super::is_text_mime(&mime) {
+        if !super::is_indexable_mime(&mime) {
             return Ok(vec![]);
         }
 
-        // Decode content as UTF-8
-        let text = std::str::from_utf8(content)
-            .map_err(|e| RepositoryError::Query(format!("File is not valid UTF-8: {e}")))?;
+        let text: std::borrow::Cow<'_, str> = if super::is_text_mime(&mime) {
+            // Decode content as UTF-8
+            let text = std::str::from_utf8(content)
+                .map_err(|e| RepositoryError::Query(format!("File is not valid UTF-8: {e}")))?;
+            text.into()
+        } else {
+            match extract_document_text(&mime, content).await {
+                Some(text) => text.into(),
+                None => {
+                    tracing::warn!(%bot_id, %filename, "skipping document: text extraction failed");
+                    return Ok(vec![]);
+                }
+            }
+        };
+        let text = text.as_ref();
 
         if text.is_empty() {
             return Ok(vec![]);
         }
 
         // Chunk the text
-        let ChunkResult { chunks, .. } = chunk_text_file(text, filename, None);
+        let ChunkResult { chunks, .. } =
+            chunk_text_with_options(text, filename, &self.chunk_options);
 
         if chunks.is_empty() {
             return Ok(vec![]);
@@ -175,6 +204,25 @@ impl<E: Embedder> FileIndexer<E> {
         query: &str,
         limit: usize,
     ) -> Result<Vec<FileChunk>, RepositoryError> {
+        Ok(self
+            .search_file_chunks_scored(bot_id, query, limit)
+            .await?
+            .into_iter()
+            .map(|m| m.chunk)
+            .collect())
+    }
+
+    /// Search file chunks by semantic similarity, returning similarity scores.
+    ///
+    /// Uses cosine distance; `score` is `1.0 - distance`. Results are ordered
+    /// best-first. Chunks whose filename is not an indexable MIME type are
+    /// skipped, so only indexable files can surface.
+    pub async fn search_file_chunks_scored(
+        &self,
+        bot_id: &Uuid,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<FileChunkMatch>, RepositoryError> {
         let table_name = LanceVectorStore::file_chunks_table_name(bot_id);
 
         if !self.vector_store.table_exists(&table_name).await {
@@ -199,6 +247,7 @@ impl<E: Embedder> FileIndexer<E> {
         let results: Vec<RecordBatch> = table
             .vector_search(query_embedding)
             .map_err(|e| RepositoryError::Query(format!("Failed to search: {e}")))?
+            .distance_type(lancedb::DistanceType::Cosine)
             .limit(limit)
             .execute()
             .await
@@ -208,7 +257,7 @@ impl<E: Embedder> FileIndexer<E> {
             .map_err(|e| RepositoryError::Query(format!("Failed to collect results: {e}")))?;
 
         let model_name = self.embedder.model_name().to_string();
-        let mut chunks = Vec::new();
+        let mut matches = Vec::new();
 
         for batch in &results {
             let num_rows = batch.num_rows();
@@ -225,31 +274,73 @@ impl<E: Embedder> FileIndexer<E> {
                     RepositoryError::Query("chunk_index is not an int32 array".to_string())
                 })?;
             let chunk_text_col = get_string_col(batch, "chunk_text")?;
+            // The _distance column is added by LanceDB vector search
+            let distance_col = batch
+                .column_by_name("_distance")
+                .and_then(|c| c.as_any().downcast_ref::<Float32Array>());
 
             for i in 0..num_rows {
+                let filename = filename_col.value(i);
+                if !super::is_indexable_mime(&super::detect_mime(filename)) {
+                    continue;
+                }
+
                 let chunk_id = Uuid::parse_str(chunk_id_col.value(i))
                     .map_err(|e| RepositoryError::Query(format!("Invalid chunk_id: {e}")))?;
                 let file_id = Uuid::parse_str(file_id_col.value(i))
                     .map_err(|e| RepositoryError::Query(format!("Invalid file_id: {e}")))?;
                 let bot_id = Uuid::parse_str(bot_id_col.value(i))
                     .map_err(|e| RepositoryError::Query(format!("Invalid bot_id: {e}")))?;
-
-                chunks.push(FileChunk {
-                    chunk_id,
-                    file_id,
-                    bot_id,
-                    filename: filename_col.value(i).to_string(),
-                    chunk_index: chunk_index_col.value(i) as u32,
-                    chunk_text: chunk_text_col.value(i).to_string(),
-                    embedding_model: model_name.clone(),
+                let distance = distance_col.map_or(0.0, |d| d.value(i));
+
+                matches.push(FileChunkMatch {
+                    chunk: FileChunk {
+                        chunk_id,
+                        file_id,
+                        bot_id,
+                        filename: filename.to_string(),
+                        chunk_index: chunk_index_col.value(i) as u32,
+                        chunk_text: chunk_text_col.value(i).to_string(),
+                        embedding_model: model_name.clone(),
+                    },
+                    score: 1.0 - distance,
                 });
             }
         }
 
-        Ok(chunks)
+        matches.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+        Ok(matches)
+    }
+}
+
+/// Extract and normalize text from a document, off the async runtime.
+///
+/// Returns `None` if extraction fails (the reason is logged).
+#[cfg(feature = "document-extract")]
+async fn extract_document_text(mime: &str, content: &[u8]) -> Option<String> {
+    let mime = mime.to_string();
+    let content = content.to_vec();
+    match tokio::task::spawn_blocking(move || super::extract::extract_text(&mime, &content)).await
+    {
+        Ok(Ok(text)) => Some(super::chunker::normalize_extracted_text(&text)),
+        Ok(Err(e)) => {
+            tracing::warn!(error = %e, "document text extraction failed");
+            None
+        }
+        Err(e) => {
+            tracing::warn!(error = %e, "document text extraction task failed");
+            None
+        }
     }
 }
 
+/// Without `document-extract`, documents are never indexable.
+#[cfg(not(feature = "document-extract"))]
+async fn extract_document_text(_mime: &str, _content: &[u8]) -> Option<String> {
+    None
+}
+
 /// Extract a StringArray column from a RecordBatch.
 fn get_string_col<'a>(
     batch: &'a RecordBatch,
@@ -582,6 +673,157 @@ mod tests {
         assert!(results.iter().all(|c| c.bot_id == bot_id));
     }
 
+    /// Bag-of-words embedder: each lowercase word is hashed into a dimension,
+    /// so texts sharing vocabulary have high cosine similarity.
+    struct KeywordEmbedder;
+
+    impl Embedder for KeywordEmbedder {
+        async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, RepositoryError> {
+            Ok(texts
+                .iter()
+                .map(|text| {
+                    let mut vec = vec![0.0f32; EMBEDDING_DIMENSION as usize];
+                    for word in text.split(|c: char| !c.is_alphanumeric()) {
+                        if word.is_empty() {
+                            continue;
+                        }
+                        let hash = word
+                            .to_lowercase()
+                            .bytes()
+                            .fold(7usize, |h, b| h.wrapping_mul(31).wrapping_add(b as usize));
+                        vec[hash % vec.len()] += 1.0;
+                    }
+                    vec
+                })
+                .collect())
+        }
+
+        fn model_name(&self) -> &str {
+            "keyword-embedder"
+        }
+
+        fn dimension(&self) -> usize {
+            EMBEDDING_DIMENSION as usize
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scored_search_returns_relevant_file_chunk() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let vector_store = Arc::new(
+            LanceVectorStore::new(temp_dir.path().to_path_buf())
+                .await
+                .unwrap(),
+        );
+        let indexer = FileIndexer::new(vector_store, Arc::new(KeywordEmbedder));
+
+        let bot_id = Uuid::now_v7();
+        let recipes_id = Uuid::now_v7();
+        let travel_id = Uuid::now_v7();
+
+        indexer
+            .index_file(
+                &bot_id,
+                &recipes_id,
+                "recipes.md",
+                b"Sourdough bread needs flour, water, salt and a starter culture.",
+            )
+            .await
+            .unwrap();
+        indexer
+            .index_file(
+                &bot_id,
+                &travel_id,
+                "travel.txt",
+                b"Tokyo trains run on time; buy a rail pass before the trip.",
+            )
+            .await
+            .unwrap();
+
+        let results = indexer
+            .search_file_chunks_scored(&bot_id, "sourdough flour starter", 5)
+            .await
+            .unwrap();
+
+        assert!(!results.is_empty());
+        assert_eq!(results[0].chunk.file_id, recipes_id);
+        assert_eq!(results[0].chunk.filename, "recipes.md");
+        assert!(results[0].chunk.chunk_text.contains("Sourdough"));
+        // Best-first ordering
+        assert!(results.windows(2).all(|w| w[0].score >= w[1].score));
+        if let Some(other) = results.iter().find(|m| m.chunk.file_id == travel_id) {
+            assert!(results[0].score > other.score);
+        }
+    }
+
+    #[cfg(feature = "document-extract")]
+    #[tokio::test]
+    async fn test_index_pdf_produces_chunks() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let vector_store = Arc::new(
+            LanceVectorStore::new(temp_dir.path().to_path_buf())
+                .await
+                .unwrap(),
+        );
+        let indexer = FileIndexer::new(vector_store, Arc::new(MockEmbedder::new()));
+
+        let bot_id = Uuid::now_v7();
+        let file_id = Uuid::now_v7();
+        let pdf = crate::storage::extract::tests::sample_pdf("Invoices are due within thirty days");
+
+        let chunks = indexer
+            .index_file(&bot_id, &file_id, "terms.pdf", &pdf)
+            .await
+            .unwrap();
+
+        assert!(!chunks.is_empty());
+        assert!(chunks.iter().any(|c| c.chunk_text.contains("thirty days")));
+    }
+
+    #[cfg(feature = "document-extract")]
+    #[tokio::test]
+    async fn test_index_corrupt_pdf_is_skipped() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let vector_store = Arc::new(
+            LanceVectorStore::new(temp_dir.path().to_path_buf())
+                .await
+                .unwrap(),
+        );
+        let indexer = FileIndexer::new(vector_store, Arc::new(MockEmbedder::new()));
+
+        let chunks = indexer
+            .index_file(&Uuid::now_v7(), &Uuid::now_v7(), "broken.pdf", b"%PDF-1.4 junk")
+            .await
+            .unwrap();
+        assert!(chunks.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_index_uses_custom_chunk_options() {
+        use crate::storage::chunker::ChunkMode;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let vector_store = Arc::new(
+            LanceVectorStore::new(temp_dir.path().to_path_buf())
+                .await
+                .unwrap(),
+        );
+        let options = ChunkOptions::new(40, 8).unwrap().with_mode(ChunkMode::Fixed);
+        let indexer =
+            FileIndexer::new(vector_store, Arc::new(MockEmbedder::new())).with_chunk_options(options);
+
+        let content = "abcdefghij".repeat(10);
+        let chunks = indexer
+            .index_file(&Uuid::now_v7(), &Uuid::now_v7(), "letters.txt", content.as_bytes())
+            .await
+            .unwrap();
+
+        // 100 chars, window 40, step 32 -> starts at 0, 32, 64
+        assert_eq!(chunks.len(), 3);
+        assert!(chunks.iter().take(2).all(|c| c.chunk_text.len() == 40));
+        assert_eq!(&chunks[0].chunk_text[32..], &chunks[1].chunk_text[..8]);
+    }
+
     #[tokio::test]
     async fn test_search_empty_table() {
         let temp_dir = tempfile::tempdir().unwrap();
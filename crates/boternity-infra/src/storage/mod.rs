@@ -2,10 +2,13 @@
 //!
 //! Implements the `FileStore` trait from `boternity-core` for local filesystem
 //! storage with version history, plus text chunking and semantic indexing.
+//! With the `document-extract` feature, PDF and DOCX files are indexed too.
 
 use std::path::Path;
 
 pub mod chunker;
+#[cfg(feature = "document-extract")]
+pub mod extract;
 pub mod filesystem;
 pub mod indexer;
 
@@ -70,6 +73,21 @@ pub fn is_text_mime(mime: &str) -> bool {
     mime.starts_with("text/") || mime == "application/json"
 }
 
+/// Check whether a MIME type can be indexed for semantic search.
+///
+/// Text is always indexable; documents (PDF, DOCX) only when text extraction
+/// is compiled in via the `document-extract` feature.
+pub fn is_indexable_mime(mime: &str) -> bool {
+    if is_text_mime(mime) {
+        return true;
+    }
+    #[cfg(feature = "document-extract")]
+    if extract::is_extractable_mime(mime) {
+        return true;
+    }
+    false
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -95,4 +113,14 @@ mod tests {
         assert!(!is_text_mime("application/pdf"));
         assert!(!is_text_mime("application/octet-stream"));
     }
+
+    #[test]
+    fn test_is_indexable_mime() {
+        assert!(is_indexable_mime("text/plain"));
+        assert!(!is_indexable_mime("image/png"));
+        assert_eq!(
+            is_indexable_mime("application/pdf"),
+            cfg!(feature = "document-extract")
+        );
+    }
 }
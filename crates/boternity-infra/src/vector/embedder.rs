@@ -1,7 +1,8 @@
-//! FastEmbed-based local embedding generator.
+//! Local embedding generators.
 //!
 //! Implements the `Embedder` trait from `boternity-core` using fastembed's
-//! BGESmallENV15 model (384 dimensions) with ONNX runtime inference.
+//! BGESmallENV15 model (384 dimensions) with ONNX runtime inference, plus a
+//! hash-based [`DeterministicEmbedder`] for tests and offline use.
 //!
 //! CRITICAL: Embedding generation is CPU-intensive ONNX inference.
 //! All embed calls use `tokio::task::spawn_blocking` to avoid blocking
@@ -13,6 +14,7 @@ use std::sync::{Arc, Mutex};
 use boternity_core::memory::embedder::Embedder;
 use boternity_types::error::RepositoryError;
 use fastembed::{EmbeddingModel, TextEmbedding};
+use sha2::{Digest, Sha256};
 
 use super::schema::EMBEDDING_DIMENSION;
 
@@ -96,6 +98,75 @@ impl Embedder for FastEmbedEmbedder {
     }
 }
 
+/// Hash-based embedder that needs no model download.
+///
+/// Each lowercased word and character trigram is hashed (SHA-256) into one of
+/// [`EMBEDDING_DIMENSION`] buckets with a hashed sign, and the result is
+/// L2-normalized. The same text always yields the same vector, on every
+/// platform and release, and texts sharing words score closer than unrelated
+/// ones -- but there is no semantic understanding, so recall quality is far
+/// below the real model.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DeterministicEmbedder;
+
+impl DeterministicEmbedder {
+    /// Create the embedder.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Embed a single text.
+    pub fn embed_text(text: &str) -> Vec<f32> {
+        let dimension = EMBEDDING_DIMENSION as usize;
+        let mut vector = vec![0.0f32; dimension];
+        let lowered = text.to_lowercase();
+
+        let mut add_feature = |feature: &str| {
+            let digest = Sha256::digest(feature.as_bytes());
+            let bucket = u32::from_le_bytes([digest[0], digest[1], digest[2], digest[3]]) as usize;
+            let sign = if digest[4] & 1 == 0 { 1.0 } else { -1.0 };
+            vector[bucket % dimension] += sign;
+        };
+
+        let mut features = 0;
+        for word in lowered
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+        {
+            add_feature(&format!("w:{word}"));
+            features += 1;
+        }
+        let chars: Vec<char> = format!(" {lowered} ").chars().collect();
+        for trigram in chars.windows(3) {
+            add_feature(&format!("t:{}", trigram.iter().collect::<String>()));
+            features += 1;
+        }
+        if features == 0 {
+            add_feature("empty");
+        }
+
+        let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+        if norm > 0.0 {
+            vector.iter_mut().for_each(|v| *v /= norm);
+        }
+        vector
+    }
+}
+
+impl Embedder for DeterministicEmbedder {
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, RepositoryError> {
+        Ok(texts.iter().map(|text| Self::embed_text(text)).collect())
+    }
+
+    fn model_name(&self) -> &str {
+        "deterministic-hash"
+    }
+
+    fn dimension(&self) -> usize {
+        EMBEDDING_DIMENSION as usize
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -150,4 +221,45 @@ mod tests {
         let result = embedder.embed(&[]).await.expect("Empty embed failed");
         assert!(result.is_empty());
     }
+
+    fn cosine(a: &[f32], b: &[f32]) -> f32 {
+        a.iter().zip(b).map(|(x, y)| x * y).sum()
+    }
+
+    #[tokio::test]
+    async fn test_deterministic_embedder_is_stable_and_normalized() {
+        let embedder = DeterministicEmbedder::new();
+        assert_eq!(embedder.model_name(), "deterministic-hash");
+        assert_eq!(embedder.dimension(), 384);
+
+        let texts = vec![
+            "User prefers concise responses".to_string(),
+            "User prefers concise responses".to_string(),
+            "The weather is nice today".to_string(),
+            String::new(),
+        ];
+        let embeddings = embedder.embed(&texts).await.unwrap();
+        assert_eq!(embeddings.len(), 4);
+
+        for embedding in &embeddings {
+            assert_eq!(embedding.len(), 384);
+            let norm = cosine(embedding, embedding).sqrt();
+            assert!((norm - 1.0).abs() < 1e-5, "not unit length: {norm}");
+        }
+        assert_eq!(embeddings[0], embeddings[1]);
+        assert_ne!(embeddings[0], embeddings[2]);
+        // Separate calls agree too
+        assert_eq!(
+            DeterministicEmbedder::embed_text("User prefers concise responses"),
+            embeddings[0]
+        );
+    }
+
+    #[test]
+    fn test_deterministic_embedder_overlap_scores_higher() {
+        let base = DeterministicEmbedder::embed_text("user likes rust programming");
+        let related = DeterministicEmbedder::embed_text("user likes rust");
+        let unrelated = DeterministicEmbedder::embed_text("weather forecast tomorrow");
+        assert!(cosine(&base, &related) > cosine(&base, &unrelated));
+    }
 }
@@ -1,7 +1,9 @@
 //! LanceDB vector store wrapper for connection management and table operations.
 //!
 //! Provides `LanceVectorStore` which wraps a `lancedb::Connection` and offers
-//! helper methods for table lifecycle (create, open, drop) using Arrow schemas.
+//! helper methods for table lifecycle (create, open, drop) using Arrow schemas,
+//! for building the IVF_PQ index that keeps search fast on large tables, and
+//! for compacting tables fragmented by many small writes and deletes.
 //!
 //! This is the infrastructure layer only. Trait implementations for
 //! `VectorMemoryStore` and `SharedMemoryStore` live in Plans 03-07 and 03-09.
@@ -10,8 +12,31 @@ use std::path::PathBuf;
 use std::sync::Arc;
 
 use arrow_schema::Schema;
+use boternity_types::config::VectorIndexConfig;
+use lancedb::index::Index;
+use lancedb::index::vector::IvfPqIndexBuilder;
+use lancedb::table::OptimizeAction;
+use serde::Serialize;
 use uuid::Uuid;
 
+/// Name of the embedding column in every vector table.
+pub const VECTOR_COLUMN: &str = "vector";
+
+/// Fewest rows an IVF_PQ index can be trained on (one per PQ centroid).
+/// Smaller tables are always searched exhaustively.
+pub const MIN_INDEXABLE_ROWS: usize = 256;
+
+/// What one compaction pass did to a table.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CompactionReport {
+    /// Table name.
+    pub table: String,
+    /// Data fragments merged away (including ones emptied by deletes).
+    pub fragments_removed: usize,
+    /// Fragments written to replace them.
+    pub fragments_added: usize,
+}
+
 /// LanceDB vector store wrapper for connection and table management.
 ///
 /// Manages a single LanceDB connection at a filesystem path.
@@ -109,6 +134,98 @@ impl LanceVectorStore {
         self.db.table_names().execute().await
     }
 
+    /// Whether `table` has an ANN index on its vector column.
+    pub async fn has_vector_index(table: &lancedb::Table) -> Result<bool, lancedb::Error> {
+        Ok(table
+            .list_indices()
+            .await?
+            .iter()
+            .any(|index| index.columns.iter().any(|c| c == VECTOR_COLUMN)))
+    }
+
+    /// Build (or rebuild, replacing any existing one) the cosine IVF_PQ index
+    /// on `table`'s vector column.
+    pub async fn create_vector_index(
+        table: &lancedb::Table,
+        config: &VectorIndexConfig,
+    ) -> Result<(), lancedb::Error> {
+        let mut builder = IvfPqIndexBuilder::default()
+            .distance_type(lancedb::DistanceType::Cosine)
+            .num_sub_vectors(config.num_sub_vectors);
+        if let Some(partitions) = config.num_partitions {
+            builder = builder.num_partitions(partitions);
+        }
+        table
+            .create_index(&[VECTOR_COLUMN], Index::IvfPq(builder))
+            .replace(true)
+            .execute()
+            .await
+    }
+
+    /// Whether a table of `rows` rows should have a vector index.
+    pub fn should_index(rows: usize, config: &VectorIndexConfig) -> bool {
+        rows >= config.min_rows.max(MIN_INDEXABLE_ROWS)
+    }
+
+    /// Build the vector index once `table` reaches the configured size.
+    ///
+    /// Returns whether an index was created. Rows added after the index was
+    /// built are still found (LanceDB scans unindexed rows exhaustively);
+    /// `bnity memories reindex` folds them in.
+    pub async fn ensure_vector_index(
+        table: &lancedb::Table,
+        config: &VectorIndexConfig,
+    ) -> Result<bool, lancedb::Error> {
+        if Self::has_vector_index(table).await? {
+            return Ok(false);
+        }
+        let rows = table.count_rows(None).await?;
+        if !Self::should_index(rows, config) {
+            return Ok(false);
+        }
+        Self::create_vector_index(table, config).await?;
+        Ok(true)
+    }
+
+    /// Compact a table: merge small fragments, drop deleted rows, fold rows
+    /// added since the last index build into the index, and prune old
+    /// versions.
+    ///
+    /// Every add, delete, share, or revoke writes a new fragment or deletion
+    /// file, so tables with frequent small writes slow down until compacted.
+    /// Returns `None` if the table does not exist.
+    pub async fn compact_table(
+        &self,
+        table_name: &str,
+    ) -> Result<Option<CompactionReport>, lancedb::Error> {
+        let table = match self.db.open_table(table_name).execute().await {
+            Ok(table) => table,
+            Err(lancedb::Error::TableNotFound { .. }) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        let stats = table.optimize(OptimizeAction::All).await?;
+        let (fragments_removed, fragments_added) = stats
+            .compaction
+            .map_or((0, 0), |m| (m.fragments_removed, m.fragments_added));
+        Ok(Some(CompactionReport {
+            table: table_name.to_string(),
+            fragments_removed,
+            fragments_added,
+        }))
+    }
+
+    /// Compact every table in the store (per-bot memories, shared memories,
+    /// and file chunks).
+    pub async fn compact_all(&self) -> Result<Vec<CompactionReport>, lancedb::Error> {
+        let mut reports = Vec::new();
+        for table_name in self.table_names().await? {
+            if let Some(report) = self.compact_table(&table_name).await? {
+                reports.push(report);
+            }
+        }
+        Ok(reports)
+    }
+
     /// Get a reference to the underlying LanceDB connection.
     pub fn connection(&self) -> &lancedb::Connection {
         &self.db
@@ -245,6 +362,24 @@ mod tests {
             .expect("Second drop should be idempotent");
     }
 
+    #[test]
+    fn test_should_index_respects_threshold_and_floor() {
+        let config = VectorIndexConfig {
+            min_rows: 1_000,
+            ..VectorIndexConfig::default()
+        };
+        assert!(!LanceVectorStore::should_index(999, &config));
+        assert!(LanceVectorStore::should_index(1_000, &config));
+
+        // A threshold below what IVF_PQ can train on is raised to the floor
+        let tiny = VectorIndexConfig {
+            min_rows: 10,
+            ..VectorIndexConfig::default()
+        };
+        assert!(!LanceVectorStore::should_index(100, &tiny));
+        assert!(LanceVectorStore::should_index(MIN_INDEXABLE_ROWS, &tiny));
+    }
+
     #[test]
     fn test_table_name_generation() {
         let bot_id = Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap();
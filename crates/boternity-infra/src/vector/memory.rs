@@ -9,9 +9,14 @@
 //! - Semantic deduplication (configurable threshold, default 0.15)
 //! - Per-bot table isolation
 //! - Embedding model mismatch detection for re-embedding
-//! - Access count and recency tracking for memory reinforcement
+//! - Access count and recency tracking for memory reinforcement, written in
+//!   the background so recall latency is unaffected
+//! - Importance that drifts upward as a memory keeps being recalled
+//! - An IVF_PQ index built automatically once a bot's table is large enough
+//! - Optional encryption at rest of the `fact` column with `VaultCrypto`
+//!   (vectors stay in plaintext so search is unaffected)
 
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use arrow_array::{
     Array, FixedSizeListArray, Float32Array, Int32Array, RecordBatch, RecordBatchIterator,
@@ -21,14 +26,17 @@ use arrow_schema::{DataType, Field};
 use chrono::{DateTime, Utc};
 use futures_util::TryStreamExt;
 use lancedb::query::{ExecutableQuery, QueryBase};
+use tokio::task::JoinHandle;
 use uuid::Uuid;
 
 use boternity_core::memory::vector::VectorMemoryStore;
+use boternity_types::config::VectorIndexConfig;
 use boternity_types::error::RepositoryError;
 use boternity_types::memory::{MemoryCategory, RankedMemory, VectorMemoryEntry};
 
 use super::lance::LanceVectorStore;
-use super::schema::{bot_memory_schema, EMBEDDING_DIMENSION};
+use super::schema::{bot_memory_schema, check_embedding_dimension, EMBEDDING_DIMENSION};
+use crate::crypto::vault::{is_encrypted_field, VaultCrypto};
 
 /// LanceDB-backed vector memory store for per-bot long-term memory.
 ///
@@ -36,6 +44,12 @@ use super::schema::{bot_memory_schema, EMBEDDING_DIMENSION};
 /// cosine distance search, time-decay scoring, and semantic dedup.
 pub struct LanceVectorMemoryStore {
     store: LanceVectorStore,
+    /// Background access-stat updates started by `search`.
+    pending_access_updates: Mutex<Vec<JoinHandle<()>>>,
+    /// When to build the ANN index and how to search it.
+    index_config: VectorIndexConfig,
+    /// Encrypts facts before writing and decrypts them on read, if set.
+    crypto: Option<Arc<VaultCrypto>>,
 }
 
 /// Default cosine distance threshold for semantic dedup.
@@ -49,10 +63,169 @@ pub const DEFAULT_DEDUP_THRESHOLD: f32 = 0.15;
 /// After 30 days, a memory's time factor decays to 0.5 of its original value.
 const DECAY_HALF_LIFE_DAYS: f64 = 30.0;
 
+/// Number of recalls that raise a memory's importance by one level (max 5).
+const RECALLS_PER_IMPORTANCE_STEP: u32 = 5;
+
 impl LanceVectorMemoryStore {
     /// Create a new LanceVectorMemoryStore backed by the given LanceVectorStore.
     pub fn new(store: LanceVectorStore) -> Self {
-        Self { store }
+        Self {
+            store,
+            pending_access_updates: Mutex::new(Vec::new()),
+            index_config: VectorIndexConfig::default(),
+            crypto: None,
+        }
+    }
+
+    /// Use custom ANN index settings instead of the defaults.
+    pub fn with_index_config(mut self, index_config: VectorIndexConfig) -> Self {
+        self.index_config = index_config;
+        self
+    }
+
+    /// Encrypt facts at rest with `crypto`.
+    ///
+    /// New memories are written with an encrypted `fact` column; facts
+    /// stored before encryption was enabled keep reading as plaintext.
+    pub fn with_encryption(mut self, crypto: Arc<VaultCrypto>) -> Self {
+        self.crypto = Some(crypto);
+        self
+    }
+
+    /// The entry as it is written to disk (fact encrypted when enabled).
+    fn seal_entry(&self, entry: &VectorMemoryEntry) -> Result<VectorMemoryEntry, RepositoryError> {
+        let mut sealed = entry.clone();
+        if let Some(crypto) = &self.crypto
+            && !is_encrypted_field(&sealed.fact)
+        {
+            sealed.fact = crypto
+                .encrypt_field(&entry.fact)
+                .map_err(|e| RepositoryError::Query(format!("Failed to encrypt memory: {e}")))?;
+        }
+        Ok(sealed)
+    }
+
+    /// Decrypt the facts of entries read from disk.
+    ///
+    /// Fails on an encrypted fact when no key is configured (or the key is
+    /// wrong) rather than returning ciphertext as memory text.
+    fn open_entries(
+        &self,
+        entries: Vec<VectorMemoryEntry>,
+    ) -> Result<Vec<VectorMemoryEntry>, RepositoryError> {
+        entries
+            .into_iter()
+            .map(|mut entry| {
+                if is_encrypted_field(&entry.fact) {
+                    let crypto = self.crypto.as_ref().ok_or_else(|| {
+                        RepositoryError::Query(format!(
+                            "Memory {} is encrypted but no vault key is configured",
+                            entry.id
+                        ))
+                    })?;
+                    entry.fact = crypto.decrypt_field(&entry.fact).map_err(|e| {
+                        RepositoryError::Query(format!(
+                            "Failed to decrypt memory {}: {e}",
+                            entry.id
+                        ))
+                    })?;
+                }
+                Ok(entry)
+            })
+            .collect()
+    }
+
+    /// Rebuild the bot's vector index from all current rows.
+    ///
+    /// Returns the table's row count and whether it was indexed; tables
+    /// below the threshold are left unindexed and searched exhaustively.
+    pub async fn reindex(&self, bot_id: &Uuid) -> Result<(usize, bool), RepositoryError> {
+        let table_name = LanceVectorStore::bot_table_name(bot_id);
+        if !self.store.table_exists(&table_name).await {
+            return Ok((0, false));
+        }
+        let table = self.ensure_bot_table(bot_id).await?;
+        let rows = table
+            .count_rows(None)
+            .await
+            .map_err(|e| RepositoryError::Query(format!("Failed to count rows: {e}")))?;
+        if !LanceVectorStore::should_index(rows, &self.index_config) {
+            return Ok((rows, false));
+        }
+        LanceVectorStore::create_vector_index(&table, &self.index_config)
+            .await
+            .map_err(|e| RepositoryError::Query(format!("Failed to build vector index: {e}")))?;
+        Ok((rows, true))
+    }
+
+    /// Whether the bot's memory table currently has a vector index.
+    pub async fn has_index(&self, bot_id: &Uuid) -> Result<bool, RepositoryError> {
+        let table = self.ensure_bot_table(bot_id).await?;
+        LanceVectorStore::has_vector_index(&table)
+            .await
+            .map_err(|e| RepositoryError::Query(format!("Failed to list indices: {e}")))
+    }
+
+    /// Wait for all background access-stat updates to finish.
+    ///
+    /// `search` returns before recalled memories' stats are written; call
+    /// this when later reads must observe them (e.g., before shutdown).
+    pub async fn flush_access_updates(&self) {
+        let pending = std::mem::take(
+            &mut *self
+                .pending_access_updates
+                .lock()
+                .expect("access update lock poisoned"),
+        );
+        for handle in pending {
+            let _ = handle.await;
+        }
+    }
+
+    /// Record a recall of each memory in the background.
+    ///
+    /// Increments `access_count`, stamps `last_accessed_at`, and bumps
+    /// importance every [`RECALLS_PER_IMPORTANCE_STEP`] recalls. Concurrent
+    /// searches can race and drop an increment, which only slows the drift.
+    fn spawn_access_updates(&self, table: lancedb::Table, recalled: &[RankedMemory]) {
+        let updates: Vec<(Uuid, u32, u8)> = recalled
+            .iter()
+            .map(|m| {
+                let new_count = m.entry.access_count + 1;
+                (
+                    m.entry.id,
+                    new_count,
+                    reinforced_importance(m.entry.importance, new_count),
+                )
+            })
+            .collect();
+        if updates.is_empty() {
+            return;
+        }
+
+        let handle = tokio::spawn(async move {
+            let now = Utc::now().to_rfc3339();
+            for (id, access_count, importance) in updates {
+                if let Err(e) = table
+                    .update()
+                    .only_if(format!("id = '{id}'"))
+                    .column("access_count", access_count.to_string())
+                    .column("importance", importance.to_string())
+                    .column("last_accessed_at", format!("'{now}'"))
+                    .execute()
+                    .await
+                {
+                    tracing::warn!(memory_id = %id, error = %e, "Failed to update memory access stats");
+                }
+            }
+        });
+
+        let mut pending = self
+            .pending_access_updates
+            .lock()
+            .expect("access update lock poisoned");
+        pending.retain(|h| !h.is_finished());
+        pending.push(handle);
     }
 
     /// Ensure the bot's memory table exists, creating it if needed.
@@ -70,6 +243,8 @@ impl LanceVectorMemoryStore {
         entry: &VectorMemoryEntry,
         embedding: &[f32],
     ) -> Result<RecordBatch, RepositoryError> {
+        check_embedding_dimension(embedding)?;
+
         let schema = Arc::new(bot_memory_schema());
 
         let id_array = StringArray::from(vec![entry.id.to_string()]);
@@ -200,10 +375,13 @@ impl LanceVectorMemoryStore {
                     .unwrap_or(None)
             };
 
-            let category: MemoryCategory = category_col
+            let category = category_col
                 .value(i)
-                .parse()
-                .unwrap_or(MemoryCategory::Fact);
+                .parse::<MemoryCategory>()
+                .unwrap_or_else(|e| {
+                    tracing::warn!(memory_id = %id, "{e}; reading it as a fact");
+                    MemoryCategory::Fact
+                });
 
             entries.push(VectorMemoryEntry {
                 id,
@@ -259,6 +437,18 @@ fn compute_relevance_score(
     similarity * time_factor * reinforcement * importance_factor
 }
 
+/// Importance after a recall that brings the access count to `access_count`.
+///
+/// Rises one level every [`RECALLS_PER_IMPORTANCE_STEP`] recalls, capped at 5,
+/// so memories that keep proving useful rank higher in future recalls.
+fn reinforced_importance(importance: u8, access_count: u32) -> u8 {
+    if access_count > 0 && access_count % RECALLS_PER_IMPORTANCE_STEP == 0 {
+        importance.saturating_add(1).min(5)
+    } else {
+        importance
+    }
+}
+
 impl VectorMemoryStore for LanceVectorMemoryStore {
     async fn search(
         &self,
@@ -274,6 +464,8 @@ impl VectorMemoryStore for LanceVectorMemoryStore {
             .vector_search(query_embedding)
             .map_err(|e| RepositoryError::Query(format!("Vector search setup failed: {e}")))?
             .distance_type(lancedb::DistanceType::Cosine)
+            .nprobes(self.index_config.nprobes)
+            .refine_factor(self.index_config.refine_factor)
             .limit(limit * 2) // Fetch extra to account for min_similarity filtering
             .execute()
             .await
@@ -296,7 +488,7 @@ impl VectorMemoryStore for LanceVectorMemoryStore {
                 .column_by_name("_distance")
                 .and_then(|c| c.as_any().downcast_ref::<Float32Array>());
 
-            let entries = Self::record_batch_to_entries(batch);
+            let entries = self.open_entries(Self::record_batch_to_entries(batch))?;
 
             for (i, entry) in entries.into_iter().enumerate() {
                 let distance = distance_col.map_or(0.0, |d| d.value(i));
@@ -333,18 +525,8 @@ impl VectorMemoryStore for LanceVectorMemoryStore {
         // Trim to requested limit
         ranked.truncate(limit);
 
-        // Update access stats for returned memories
-        for memory in &ranked {
-            let new_count = memory.entry.access_count + 1;
-            let now = Utc::now().to_rfc3339();
-            let _ = table
-                .update()
-                .only_if(format!("id = '{}'", memory.entry.id))
-                .column("access_count", new_count.to_string())
-                .column("last_accessed_at", format!("'{now}'"))
-                .execute()
-                .await;
-        }
+        // Update access stats for returned memories without blocking recall
+        self.spawn_access_updates(table, &ranked);
 
         Ok(ranked)
     }
@@ -356,7 +538,7 @@ impl VectorMemoryStore for LanceVectorMemoryStore {
     ) -> Result<(), RepositoryError> {
         let table = self.ensure_bot_table(&entry.bot_id).await?;
 
-        let batch = Self::build_record_batch(entry, embedding)?;
+        let batch = Self::build_record_batch(&self.seal_entry(entry)?, embedding)?;
         let schema = batch.schema();
 
         let reader = RecordBatchIterator::new(vec![Ok(batch)], schema);
@@ -367,6 +549,14 @@ impl VectorMemoryStore for LanceVectorMemoryStore {
             .await
             .map_err(|e| RepositoryError::Query(format!("Failed to add memory: {e}")))?;
 
+        // The memory is stored either way; a failed index build only leaves
+        // search exhaustive.
+        match LanceVectorStore::ensure_vector_index(&table, &self.index_config).await {
+            Ok(true) => tracing::info!(bot_id = %entry.bot_id, "Built vector index for bot memories"),
+            Ok(false) => {}
+            Err(e) => tracing::warn!(bot_id = %entry.bot_id, error = %e, "Failed to build vector index"),
+        }
+
         Ok(())
     }
 
@@ -444,6 +634,8 @@ impl VectorMemoryStore for LanceVectorMemoryStore {
             .vector_search(embedding)
             .map_err(|e| RepositoryError::Query(format!("Dedup search setup failed: {e}")))?
             .distance_type(lancedb::DistanceType::Cosine)
+            .nprobes(self.index_config.nprobes)
+            .refine_factor(self.index_config.refine_factor)
             .limit(1)
             .execute()
             .await
@@ -468,7 +660,7 @@ impl VectorMemoryStore for LanceVectorMemoryStore {
             if let Some(distances) = distance_col {
                 let distance = distances.value(0);
                 if distance < threshold {
-                    let entries = Self::record_batch_to_entries(batch);
+                    let entries = self.open_entries(Self::record_batch_to_entries(batch))?;
                     if let Some(entry) = entries.into_iter().next() {
                         return Ok(Some(entry));
                     }
@@ -514,7 +706,7 @@ impl VectorMemoryStore for LanceVectorMemoryStore {
             entries.extend(Self::record_batch_to_entries(batch));
         }
 
-        Ok(entries)
+        self.open_entries(entries)
     }
 
     async fn update_embedding(
@@ -578,7 +770,8 @@ impl VectorMemoryStore for LanceVectorMemoryStore {
                         RepositoryError::Query(format!("Failed to delete old embedding: {e}"))
                     })?;
 
-                // Re-insert with new embedding and model name
+                // Re-insert with new embedding and model name (the fact is
+                // copied as stored, encrypted or not)
                 entry.embedding_model = model_name.to_string();
                 let batch = Self::build_record_batch(&entry, new_embedding)?;
                 let batch_schema = batch.schema();
@@ -965,6 +1158,190 @@ mod tests {
         }
     }
 
+    /// Read a single memory's stored row.
+    async fn get_entry(store: &LanceVectorMemoryStore, bot_id: &Uuid, id: Uuid) -> VectorMemoryEntry {
+        let table = store.ensure_bot_table(bot_id).await.unwrap();
+        let batches: Vec<RecordBatch> = table
+            .query()
+            .only_if(format!("id = '{id}'"))
+            .execute()
+            .await
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+        batches
+            .iter()
+            .flat_map(LanceVectorMemoryStore::record_batch_to_entries)
+            .next()
+            .expect("memory should exist")
+    }
+
+    #[tokio::test]
+    async fn test_search_records_access_in_background() {
+        let (store, _tmp) = setup_store().await;
+        let bot_id = Uuid::now_v7();
+        let entry = make_entry(bot_id, "User likes Rust", 3, "bge-small-en-v1.5");
+        store.add(&entry, &make_embedding(1.0)).await.unwrap();
+
+        for _ in 0..3 {
+            let results = store.search(&bot_id, &make_embedding(1.0), 5, 0.0).await.unwrap();
+            assert_eq!(results.len(), 1);
+            store.flush_access_updates().await;
+        }
+
+        let stored = get_entry(&store, &bot_id, entry.id).await;
+        assert_eq!(stored.access_count, 3);
+        assert!(stored.last_accessed_at.is_some());
+        assert_eq!(stored.importance, 3);
+    }
+
+    #[tokio::test]
+    async fn test_recalls_raise_importance_and_ranking() {
+        let (store, _tmp) = setup_store().await;
+        let bot_id = Uuid::now_v7();
+        // Two equally similar, equally important memories
+        let a = make_entry(bot_id, "User likes tea", 3, "bge-small-en-v1.5");
+        let b = make_entry(bot_id, "User likes coffee", 3, "bge-small-en-v1.5");
+        store.add(&a, &make_embedding(1.0)).await.unwrap();
+        store.add(&b, &make_embedding(1.0)).await.unwrap();
+
+        // Recall only the top hit, five times
+        let winner = store.search(&bot_id, &make_embedding(1.0), 1, 0.0).await.unwrap()[0]
+            .entry
+            .id;
+        store.flush_access_updates().await;
+        for _ in 0..4 {
+            let results = store.search(&bot_id, &make_embedding(1.0), 1, 0.0).await.unwrap();
+            assert_eq!(results[0].entry.id, winner);
+            store.flush_access_updates().await;
+        }
+        let loser = if winner == a.id { b.id } else { a.id };
+
+        let stored = get_entry(&store, &bot_id, winner).await;
+        assert_eq!(stored.access_count, 5);
+        assert_eq!(stored.importance, 4);
+        assert_eq!(get_entry(&store, &bot_id, loser).await.access_count, 0);
+
+        let results = store.search(&bot_id, &make_embedding(1.0), 2, 0.0).await.unwrap();
+        assert_eq!(results[0].entry.id, winner);
+        assert!(results[0].relevance_score > results[1].relevance_score);
+    }
+
+    fn test_crypto() -> Arc<VaultCrypto> {
+        Arc::new(VaultCrypto::new(&[7u8; 32]))
+    }
+
+    #[tokio::test]
+    async fn test_encrypted_facts_are_ciphertext_on_disk() {
+        let (store, _tmp) = setup_store().await;
+        let store = store.with_encryption(test_crypto());
+        let bot_id = Uuid::now_v7();
+
+        let entry = make_entry(
+            bot_id,
+            "User's sister is called Maya",
+            3,
+            "bge-small-en-v1.5",
+        );
+        store.add(&entry, &make_embedding(1.0)).await.unwrap();
+
+        let stored = get_entry(&store, &bot_id, entry.id).await;
+        assert!(is_encrypted_field(&stored.fact));
+        assert!(!stored.fact.contains("Maya"));
+
+        let results = store
+            .search(&bot_id, &make_embedding(1.0), 5, 0.0)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].entry.fact, "User's sister is called Maya");
+        store.flush_access_updates().await;
+    }
+
+    #[tokio::test]
+    async fn test_search_and_dedup_work_with_encrypted_facts() {
+        let (store, _tmp) = setup_store().await;
+        let store = store.with_encryption(test_crypto());
+        let bot_id = Uuid::now_v7();
+
+        let rust = make_entry(bot_id, "User likes Rust", 3, "bge-small-en-v1.5");
+        let hiking = make_entry(
+            bot_id,
+            "User goes hiking on weekends",
+            3,
+            "bge-small-en-v1.5",
+        );
+        store.add(&rust, &make_embedding(1.0)).await.unwrap();
+        store.add(&hiking, &make_embedding(100.0)).await.unwrap();
+
+        // Vectors are stored unencrypted, so nearest-neighbour search still ranks
+        let results = store
+            .search(&bot_id, &make_embedding(100.0), 1, 0.0)
+            .await
+            .unwrap();
+        assert_eq!(results[0].entry.id, hiking.id);
+        assert_eq!(results[0].entry.fact, "User goes hiking on weekends");
+        store.flush_access_updates().await;
+
+        let duplicate = store
+            .check_duplicate(&bot_id, &make_embedding(1.0), DEFAULT_DEDUP_THRESHOLD)
+            .await
+            .unwrap()
+            .expect("near-identical vector should be a duplicate");
+        assert_eq!(duplicate.fact, "User likes Rust");
+    }
+
+    #[tokio::test]
+    async fn test_plaintext_and_encrypted_facts_coexist() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let bot_id = Uuid::now_v7();
+
+        // Written before encryption was enabled
+        let plain_store = LanceVectorMemoryStore::new(
+            LanceVectorStore::new(temp_dir.path().to_path_buf())
+                .await
+                .unwrap(),
+        );
+        let old = make_entry(bot_id, "User likes Rust", 3, "bge-small-en-v1.5");
+        plain_store.add(&old, &make_embedding(1.0)).await.unwrap();
+
+        let store = LanceVectorMemoryStore::new(
+            LanceVectorStore::new(temp_dir.path().to_path_buf())
+                .await
+                .unwrap(),
+        )
+        .with_encryption(test_crypto());
+        let new = make_entry(bot_id, "User likes Go", 3, "bge-small-en-v1.5");
+        store.add(&new, &make_embedding(1.5)).await.unwrap();
+
+        let mut facts: Vec<String> = store
+            .search(&bot_id, &make_embedding(1.0), 5, 0.0)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|m| m.entry.fact)
+            .collect();
+        facts.sort();
+        assert_eq!(facts, vec!["User likes Go", "User likes Rust"]);
+        store.flush_access_updates().await;
+
+        // Without the key, encrypted memories are an error rather than ciphertext
+        assert!(
+            plain_store
+                .search(&bot_id, &make_embedding(1.0), 5, 0.0)
+                .await
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_reinforced_importance() {
+        assert_eq!(reinforced_importance(3, 1), 3);
+        assert_eq!(reinforced_importance(3, RECALLS_PER_IMPORTANCE_STEP), 4);
+        assert_eq!(reinforced_importance(5, RECALLS_PER_IMPORTANCE_STEP * 2), 5);
+    }
+
     #[test]
     fn test_compute_relevance_score_basic() {
         let now = Utc::now();
@@ -1073,4 +1450,77 @@ mod tests {
         assert_eq!(recovered.embedding_model, entry.embedding_model);
         assert_eq!(recovered.access_count, entry.access_count);
     }
+
+    #[test]
+    fn test_build_record_batch_rejects_wrong_dimension() {
+        let entry = make_entry(Uuid::now_v7(), "Short vector fact", 3, "bge-small-en-v1.5");
+
+        for len in [EMBEDDING_DIMENSION as usize - 1, EMBEDDING_DIMENSION as usize + 1] {
+            let err = LanceVectorMemoryStore::build_record_batch(&entry, &vec![0.1; len]).unwrap_err();
+            assert!(
+                err.to_string()
+                    .contains(&format!("Embedding has {len} dimensions, expected 384")),
+                "unexpected error: {err}"
+            );
+        }
+    }
+
+    fn indexed_fact(i: usize) -> String {
+        format!("Memory number {i} about topic {}", i * 7919 % 1000)
+    }
+
+    async fn add_indexed_fact(store: &LanceVectorMemoryStore, bot_id: Uuid, i: usize) {
+        use crate::vector::embedder::DeterministicEmbedder;
+
+        let entry = make_entry(bot_id, &indexed_fact(i), 3, "deterministic-hash");
+        let embedding = DeterministicEmbedder::embed_text(&entry.fact);
+        store.add(&entry, &embedding).await.unwrap();
+    }
+
+    async fn top_fact(store: &LanceVectorMemoryStore, bot_id: Uuid, i: usize) -> String {
+        use crate::vector::embedder::DeterministicEmbedder;
+
+        let query = DeterministicEmbedder::embed_text(&indexed_fact(i));
+        let results = store.search(&bot_id, &query, 1, 0.0).await.unwrap();
+        results[0].entry.fact.clone()
+    }
+
+    #[tokio::test]
+    async fn test_index_built_past_threshold_and_search_stays_correct() {
+        use crate::vector::lance::MIN_INDEXABLE_ROWS;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let lance_store = LanceVectorStore::new(temp_dir.path().to_path_buf())
+            .await
+            .unwrap();
+        let store = LanceVectorMemoryStore::new(lance_store).with_index_config(VectorIndexConfig {
+            min_rows: MIN_INDEXABLE_ROWS,
+            num_partitions: Some(2),
+            num_sub_vectors: 8,
+            ..VectorIndexConfig::default()
+        });
+        let bot_id = Uuid::now_v7();
+
+        // Below the threshold: no index, exhaustive search
+        for i in 0..MIN_INDEXABLE_ROWS - 1 {
+            add_indexed_fact(&store, bot_id, i).await;
+        }
+        assert!(!store.has_index(&bot_id).await.unwrap());
+        assert_eq!(top_fact(&store, bot_id, 42).await, indexed_fact(42));
+
+        // Crossing it builds the index; rows added afterwards remain searchable
+        let total = MIN_INDEXABLE_ROWS + 20;
+        for i in MIN_INDEXABLE_ROWS - 1..total {
+            add_indexed_fact(&store, bot_id, i).await;
+        }
+        assert!(store.has_index(&bot_id).await.unwrap());
+        for i in [0, 42, MIN_INDEXABLE_ROWS - 1, total - 1] {
+            assert_eq!(top_fact(&store, bot_id, i).await, indexed_fact(i));
+        }
+
+        // Reindexing folds the later rows into a fresh index
+        assert_eq!(store.reindex(&bot_id).await.unwrap(), (total, true));
+        assert_eq!(top_fact(&store, bot_id, total - 1).await, indexed_fact(total - 1));
+        store.flush_access_updates().await;
+    }
 }
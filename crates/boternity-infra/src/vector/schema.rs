@@ -8,10 +8,26 @@
 use std::sync::Arc;
 
 use arrow_schema::{DataType, Field, Schema};
+use boternity_types::error::RepositoryError;
 
 /// BGESmallENV15 embedding dimension.
 pub const EMBEDDING_DIMENSION: i32 = 384;
 
+/// Reject an embedding whose length does not match the vector column.
+///
+/// Arrow's `FixedSizeListArray::new` panics on a length mismatch, so every
+/// write path checks first.
+pub fn check_embedding_dimension(embedding: &[f32]) -> Result<(), RepositoryError> {
+    if embedding.len() != EMBEDDING_DIMENSION as usize {
+        return Err(RepositoryError::Query(format!(
+            "Embedding has {} dimensions, expected {EMBEDDING_DIMENSION} \
+             (was it produced by a different embedding model?)",
+            embedding.len()
+        )));
+    }
+    Ok(())
+}
+
 /// Schema for per-bot memory tables in LanceDB.
 ///
 /// Each bot has its own table named `bot_memory_{bot_id}`.
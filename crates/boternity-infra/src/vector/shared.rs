@@ -6,7 +6,7 @@
 //! Key features:
 //! - Trust-level filtering: Public (all bots), Trusted (explicit trust list), Private (author only)
 //! - Provenance tracking: "Written by BotX" annotation on all shared memories
-//! - SHA-256 tamper detection hash on every write
+//! - Tamper detection hash on every write (SHA-256 or BLAKE3, recorded with the hash)
 //! - Per-bot contribution cap (default 500)
 //! - Author-only deletion and revocation
 
@@ -20,17 +20,18 @@ use arrow_schema::{DataType, Field};
 use chrono::{DateTime, Utc};
 use futures_util::TryStreamExt;
 use lancedb::query::{ExecutableQuery, QueryBase};
-use sha2::{Digest, Sha256};
 use uuid::Uuid;
 
 use boternity_core::memory::shared::SharedMemoryStore;
+use boternity_types::config::{HashAlgorithm, VectorIndexConfig};
 use boternity_types::error::RepositoryError;
 use boternity_types::memory::{
     MemoryCategory, RankedMemory, SharedMemoryEntry, TrustLevel, VectorMemoryEntry,
 };
 
 use super::lance::LanceVectorStore;
-use super::schema::{shared_memory_schema, EMBEDDING_DIMENSION};
+use crate::crypto::hash::{IntegrityDigest, recorded_algorithm};
+use super::schema::{check_embedding_dimension, shared_memory_schema, EMBEDDING_DIMENSION};
 
 /// Default per-bot contribution cap for shared memories.
 pub const DEFAULT_CONTRIBUTION_CAP: u64 = 500;
@@ -38,10 +39,14 @@ pub const DEFAULT_CONTRIBUTION_CAP: u64 = 500;
 /// LanceDB-backed shared memory store for cross-bot knowledge sharing.
 ///
 /// Wraps a `LanceVectorStore` and implements `SharedMemoryStore` with
-/// trust-level filtering, provenance tracking, and SHA-256 integrity.
+/// trust-level filtering, provenance tracking, and write-hash integrity.
 pub struct LanceSharedMemoryStore {
     store: LanceVectorStore,
     contribution_cap: u64,
+    /// When to build the ANN index and how to search it.
+    index_config: VectorIndexConfig,
+    /// Algorithm for new write hashes.
+    hash_algorithm: HashAlgorithm,
 }
 
 impl LanceSharedMemoryStore {
@@ -50,6 +55,8 @@ impl LanceSharedMemoryStore {
         Self {
             store,
             contribution_cap: DEFAULT_CONTRIBUTION_CAP,
+            index_config: VectorIndexConfig::default(),
+            hash_algorithm: HashAlgorithm::default(),
         }
     }
 
@@ -58,9 +65,26 @@ impl LanceSharedMemoryStore {
         Self {
             store,
             contribution_cap: cap,
+            index_config: VectorIndexConfig::default(),
+            hash_algorithm: HashAlgorithm::default(),
         }
     }
 
+    /// Use custom ANN index settings instead of the defaults.
+    pub fn with_index_config(mut self, index_config: VectorIndexConfig) -> Self {
+        self.index_config = index_config;
+        self
+    }
+
+    /// Compute new write hashes with `algorithm` instead of SHA-256.
+    ///
+    /// Entries written under another algorithm still verify: each write hash
+    /// records the algorithm that produced it.
+    pub fn with_hash_algorithm(mut self, algorithm: HashAlgorithm) -> Self {
+        self.hash_algorithm = algorithm;
+        self
+    }
+
     /// Ensure the shared memory table exists, creating it if needed.
     async fn ensure_shared_table(&self) -> Result<lancedb::Table, RepositoryError> {
         let table_name = LanceVectorStore::shared_table_name();
@@ -73,11 +97,11 @@ impl LanceSharedMemoryStore {
             })
     }
 
-    /// Compute the SHA-256 write hash for tamper detection.
+    /// Compute the write hash for tamper detection with `algorithm`.
     ///
     /// Hash covers: id + fact + category + importance + author_bot_id + trust_level + created_at
-    fn compute_write_hash(entry: &SharedMemoryEntry) -> String {
-        let mut hasher = Sha256::new();
+    fn compute_write_hash(entry: &SharedMemoryEntry, algorithm: HashAlgorithm) -> String {
+        let mut hasher = IntegrityDigest::new(algorithm);
         hasher.update(entry.id.to_string().as_bytes());
         hasher.update(entry.fact.as_bytes());
         hasher.update(entry.category.to_string().as_bytes());
@@ -85,7 +109,7 @@ impl LanceSharedMemoryStore {
         hasher.update(entry.author_bot_id.to_string().as_bytes());
         hasher.update(entry.trust_level.to_string().as_bytes());
         hasher.update(entry.created_at.to_rfc3339().as_bytes());
-        format!("{:x}", hasher.finalize())
+        hasher.finalize()
     }
 
     /// Build an Arrow RecordBatch from a SharedMemoryEntry and its embedding.
@@ -93,6 +117,8 @@ impl LanceSharedMemoryStore {
         entry: &SharedMemoryEntry,
         embedding: &[f32],
     ) -> Result<RecordBatch, RepositoryError> {
+        check_embedding_dimension(embedding)?;
+
         let schema = Arc::new(shared_memory_schema());
 
         let id_array = StringArray::from(vec![entry.id.to_string()]);
@@ -198,14 +224,21 @@ impl LanceSharedMemoryStore {
             let created_at = DateTime::parse_from_rfc3339(created_at_col.value(i))
                 .map(|dt| dt.with_timezone(&Utc))
                 .unwrap_or_else(|_| Utc::now());
-            let category: MemoryCategory = category_col
+            let category = category_col
                 .value(i)
-                .parse()
-                .unwrap_or(MemoryCategory::Fact);
-            let trust_level: TrustLevel = trust_level_col
+                .parse::<MemoryCategory>()
+                .unwrap_or_else(|e| {
+                    tracing::warn!(memory_id = %id, "{e}; reading it as a fact");
+                    MemoryCategory::Fact
+                });
+            // An unreadable trust level must not widen visibility
+            let trust_level = trust_level_col
                 .value(i)
-                .parse()
-                .unwrap_or(TrustLevel::Private);
+                .parse::<TrustLevel>()
+                .unwrap_or_else(|e| {
+                    tracing::warn!(memory_id = %id, "{e}; treating it as private");
+                    TrustLevel::Private
+                });
 
             entries.push(SharedMemoryEntry {
                 id,
@@ -296,7 +329,8 @@ impl SharedMemoryStore for LanceSharedMemoryStore {
         // Compute write hash if not already set (empty or placeholder)
         let mut entry_with_hash = entry.clone();
         if entry_with_hash.write_hash.is_empty() {
-            entry_with_hash.write_hash = Self::compute_write_hash(&entry_with_hash);
+            entry_with_hash.write_hash =
+                Self::compute_write_hash(&entry_with_hash, self.hash_algorithm);
         }
 
         let batch = Self::build_record_batch(&entry_with_hash, embedding)?;
@@ -309,6 +343,14 @@ impl SharedMemoryStore for LanceSharedMemoryStore {
             .await
             .map_err(|e| RepositoryError::Query(format!("Failed to add shared memory: {e}")))?;
 
+        // The memory is stored either way; a failed index build only leaves
+        // search exhaustive.
+        match LanceVectorStore::ensure_vector_index(&table, &self.index_config).await {
+            Ok(true) => tracing::info!("Built vector index for shared memories"),
+            Ok(false) => {}
+            Err(e) => tracing::warn!(error = %e, "Failed to build shared memory vector index"),
+        }
+
         Ok(())
     }
 
@@ -330,6 +372,8 @@ impl SharedMemoryStore for LanceSharedMemoryStore {
                 RepositoryError::Query(format!("Shared vector search setup failed: {e}"))
             })?
             .distance_type(lancedb::DistanceType::Cosine)
+            .nprobes(self.index_config.nprobes)
+            .refine_factor(self.index_config.refine_factor)
             .only_if(trust_filter)
             .limit(limit * 2) // Over-fetch for filtering
             .execute()
@@ -454,7 +498,7 @@ impl SharedMemoryStore for LanceSharedMemoryStore {
         // Re-insert with updated trust level and recomputed hash
         let mut updated = entry;
         updated.trust_level = trust_level;
-        updated.write_hash = Self::compute_write_hash(&updated);
+        updated.write_hash = Self::compute_write_hash(&updated, self.hash_algorithm);
 
         let batch = Self::build_record_batch(&updated, &embedding)?;
         let schema = batch.schema();
@@ -525,7 +569,7 @@ impl SharedMemoryStore for LanceSharedMemoryStore {
         // Re-insert with Private trust level
         let mut updated = entry;
         updated.trust_level = TrustLevel::Private;
-        updated.write_hash = Self::compute_write_hash(&updated);
+        updated.write_hash = Self::compute_write_hash(&updated, self.hash_algorithm);
 
         let batch = Self::build_record_batch(&updated, &embedding)?;
         let schema = batch.schema();
@@ -600,7 +644,11 @@ impl SharedMemoryStore for LanceSharedMemoryStore {
             .next()
             .ok_or(RepositoryError::NotFound)?;
 
-        let expected_hash = Self::compute_write_hash(&entry);
+        // Verify with the algorithm the hash was written with
+        let Some(algorithm) = recorded_algorithm(&entry.write_hash) else {
+            return Ok(false);
+        };
+        let expected_hash = Self::compute_write_hash(&entry, algorithm);
         Ok(entry.write_hash == expected_hash)
     }
 }
@@ -658,7 +706,8 @@ mod tests {
             write_hash: String::new(),
             created_at: Utc::now(),
         };
-        entry.write_hash = LanceSharedMemoryStore::compute_write_hash(&entry);
+        entry.write_hash =
+            LanceSharedMemoryStore::compute_write_hash(&entry, HashAlgorithm::Sha256);
         entry
     }
 
@@ -862,6 +911,28 @@ mod tests {
         assert!(result, "Integrity check should pass for unmodified entry");
     }
 
+    #[tokio::test]
+    async fn test_integrity_check_uses_recorded_algorithm() {
+        let tmp = tempfile::tempdir().unwrap();
+        let lance_store = LanceVectorStore::new(tmp.path().to_path_buf())
+            .await
+            .unwrap();
+        let store =
+            LanceSharedMemoryStore::new(lance_store).with_hash_algorithm(HashAlgorithm::Blake3);
+        let bot_a = Uuid::now_v7();
+
+        // Written under SHA-256 before the switch to BLAKE3
+        let old = make_shared_entry(bot_a, "BotA", "Old fact", TrustLevel::Public, 3);
+        store.add(&old, &make_embedding(1.0)).await.unwrap();
+
+        let mut new = make_shared_entry(bot_a, "BotA", "New fact", TrustLevel::Public, 3);
+        new.write_hash = String::new();
+        store.add(&new, &make_embedding(2.0)).await.unwrap();
+
+        assert!(store.verify_integrity(&old.id).await.unwrap());
+        assert!(store.verify_integrity(&new.id).await.unwrap());
+    }
+
     #[tokio::test]
     async fn test_integrity_check_not_found() {
         let (store, _tmp) = setup_store().await;
@@ -1159,11 +1230,15 @@ mod tests {
                 .with_timezone(&Utc),
         };
 
-        let hash1 = LanceSharedMemoryStore::compute_write_hash(&entry);
-        let hash2 = LanceSharedMemoryStore::compute_write_hash(&entry);
+        let hash1 = LanceSharedMemoryStore::compute_write_hash(&entry, HashAlgorithm::Sha256);
+        let hash2 = LanceSharedMemoryStore::compute_write_hash(&entry, HashAlgorithm::Sha256);
 
         assert_eq!(hash1, hash2, "Hash should be deterministic");
         assert_eq!(hash1.len(), 64, "SHA-256 hex should be 64 characters");
+
+        let blake3 = LanceSharedMemoryStore::compute_write_hash(&entry, HashAlgorithm::Blake3);
+        assert_ne!(hash1, blake3);
+        assert!(blake3.starts_with("blake3:"));
     }
 
     #[test]
@@ -1187,8 +1262,8 @@ mod tests {
         let mut entry2 = entry1.clone();
         entry2.fact = "Fact B".to_string();
 
-        let hash1 = LanceSharedMemoryStore::compute_write_hash(&entry1);
-        let hash2 = LanceSharedMemoryStore::compute_write_hash(&entry2);
+        let hash1 = LanceSharedMemoryStore::compute_write_hash(&entry1, HashAlgorithm::Sha256);
+        let hash2 = LanceSharedMemoryStore::compute_write_hash(&entry2, HashAlgorithm::Sha256);
 
         assert_ne!(hash1, hash2, "Different content should produce different hashes");
     }
@@ -1210,7 +1285,8 @@ mod tests {
             write_hash: String::new(),
             created_at: Utc::now(),
         };
-        entry.write_hash = LanceSharedMemoryStore::compute_write_hash(&entry);
+        entry.write_hash =
+            LanceSharedMemoryStore::compute_write_hash(&entry, HashAlgorithm::Sha256);
 
         let embedding = make_embedding(42.0);
         let batch = LanceSharedMemoryStore::build_record_batch(&entry, &embedding).unwrap();
@@ -1306,4 +1382,74 @@ mod tests {
         let valid = store.verify_integrity(&entry_id).await.unwrap();
         assert!(valid, "Integrity should pass after revoke (hash recomputed)");
     }
+
+    #[test]
+    fn test_build_record_batch_rejects_wrong_dimension() {
+        let entry = make_shared_entry(
+            Uuid::now_v7(),
+            "TestBot",
+            "Short vector fact",
+            TrustLevel::Public,
+            3,
+        );
+
+        for len in [EMBEDDING_DIMENSION as usize - 1, EMBEDDING_DIMENSION as usize + 1] {
+            let err = LanceSharedMemoryStore::build_record_batch(&entry, &vec![0.1; len]).unwrap_err();
+            assert!(
+                err.to_string()
+                    .contains(&format!("Embedding has {len} dimensions, expected 384")),
+                "unexpected error: {err}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_compaction_after_share_revoke_churn_keeps_search_correct() {
+        let (store, _tmp) = setup_store().await;
+        let author = Uuid::now_v7();
+        let reader = Uuid::now_v7();
+
+        let mut ids = Vec::new();
+        for i in 0..10 {
+            let entry = make_shared_entry(
+                author,
+                "BotA",
+                &format!("Churned fact {i}"),
+                TrustLevel::Public,
+                3,
+            );
+            ids.push(entry.id);
+            store
+                .add(&entry, &make_embedding(i as f32 * 100.0))
+                .await
+                .unwrap();
+        }
+
+        // Each share/revoke rewrites the row, leaving fragments and deletions behind
+        for round in 0..5 {
+            for id in &ids {
+                store.revoke(id, &author).await.unwrap();
+                store.share(id, TrustLevel::Public).await.unwrap();
+            }
+            store.delete(&ids[round], &author).await.unwrap();
+        }
+
+        let report = store
+            .store
+            .compact_table(LanceVectorStore::shared_table_name())
+            .await
+            .unwrap()
+            .expect("shared table exists");
+        assert!(report.fragments_removed > 0);
+        assert!(store.store.compact_table("no_such_table").await.unwrap().is_none());
+
+        let results = store
+            .search(&reader, &[], &make_embedding(700.0), 10, 0.0)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 5);
+        assert_eq!(results[0].entry.fact, "Churned fact 7");
+        assert!(results.iter().all(|r| r.entry.trust_level == TrustLevel::Public));
+        assert!(store.verify_integrity(&ids[7]).await.unwrap());
+    }
 }
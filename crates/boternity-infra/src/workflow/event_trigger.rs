@@ -0,0 +1,286 @@
+//! Bridge from the internal `EventBus` to event-triggered workflows.
+//!
+//! Provides:
+//! - `EventTriggerBridge` -- RAII handle for the listener task that launches
+//!   workflows whose `TriggerConfig::Event` matches a published `AgentEvent`
+//! - `matching_workflows()` -- the matching rule on its own: source, event
+//!   type, then the optional JEXL `when` clause
+//!
+//! Events on the bus have source [`INTERNAL_EVENT_SOURCE`] and their serde
+//! `type` tag (e.g. `workflow_run_completed`) as event type. The `when` clause
+//! sees the event as `event` and the trigger metadata as `trigger`.
+
+use std::sync::Arc;
+
+use boternity_core::event::EventBus;
+use boternity_core::workflow::trigger::{TriggerContext, TriggerManager};
+use boternity_types::event::AgentEvent;
+use futures_util::future::BoxFuture;
+use serde_json::Value;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+/// Source name for events published on the internal event bus.
+pub const INTERNAL_EVENT_SOURCE: &str = "internal";
+
+/// Launches a workflow run for `(workflow_id, event_payload)`.
+pub type EventTriggerCallback = Arc<dyn Fn(Uuid, Value) -> BoxFuture<'static, ()> + Send + Sync>;
+
+// ---------------------------------------------------------------------------
+// Matching
+// ---------------------------------------------------------------------------
+
+/// Workflows whose event triggers match `event`.
+///
+/// Returns each matching workflow once, with the event as JSON for the run's
+/// trigger payload. A `when` clause that fails to evaluate is logged and
+/// treated as not matching.
+pub async fn matching_workflows(
+    manager: &TriggerManager,
+    event: &AgentEvent,
+) -> Vec<(Uuid, Value)> {
+    let Ok(payload) = serde_json::to_value(event) else {
+        return Vec::new();
+    };
+    let Some(event_type) = payload["type"].as_str() else {
+        return Vec::new();
+    };
+
+    let mut matched: Vec<(Uuid, Value)> = Vec::new();
+    for (workflow_id, source, trigger_event_type, when) in manager.get_event_triggers().await {
+        if source != INTERNAL_EVENT_SOURCE || trigger_event_type != event_type {
+            continue;
+        }
+        if matched.iter().any(|(id, _)| *id == workflow_id) {
+            continue;
+        }
+
+        let trigger_ctx = TriggerContext::new(
+            "event",
+            INTERNAL_EVENT_SOURCE,
+            workflow_id,
+            Some(payload.clone()),
+        );
+        match manager.evaluate_when_clause(when.as_deref(), &trigger_ctx) {
+            Ok(true) => matched.push((workflow_id, payload.clone())),
+            Ok(false) => {}
+            Err(e) => {
+                tracing::warn!(
+                    %workflow_id,
+                    error = %e,
+                    "event trigger when-clause evaluation failed"
+                );
+            }
+        }
+    }
+    matched
+}
+
+// ---------------------------------------------------------------------------
+// EventTriggerBridge
+// ---------------------------------------------------------------------------
+
+/// RAII handle for the event-trigger listener.
+///
+/// Subscribes to the event bus when started, so every event published after
+/// `start` returns is considered. Triggers are read from the
+/// `TriggerManager` per event, so workflows registered later are picked up.
+/// Dropping the handle stops the listener.
+pub struct EventTriggerBridge {
+    task: JoinHandle<()>,
+}
+
+impl EventTriggerBridge {
+    /// Start listening on `event_bus`, calling `launch` for each matching workflow.
+    ///
+    /// Each launch runs on its own task so a long workflow does not hold up
+    /// later events.
+    pub fn start(
+        event_bus: &EventBus,
+        manager: Arc<TriggerManager>,
+        launch: EventTriggerCallback,
+    ) -> Self {
+        let mut rx = event_bus.subscribe();
+        let task = tokio::spawn(async move {
+            loop {
+                match rx.recv().await {
+                    Ok(event) => {
+                        for (workflow_id, payload) in matching_workflows(&manager, &event).await {
+                            tracing::debug!(%workflow_id, "event trigger matched");
+                            tokio::spawn(launch(workflow_id, payload));
+                        }
+                    }
+                    Err(RecvError::Lagged(n)) => {
+                        tracing::warn!(skipped = n, "workflow event listener lagged");
+                    }
+                    Err(RecvError::Closed) => {
+                        tracing::info!("event bus closed, workflow event listener shutting down");
+                        break;
+                    }
+                }
+            }
+        });
+        Self { task }
+    }
+
+    /// Whether the listener task is still running.
+    pub fn is_running(&self) -> bool {
+        !self.task.is_finished()
+    }
+}
+
+impl Drop for EventTriggerBridge {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    use boternity_types::workflow::TriggerConfig;
+    use tokio::sync::mpsc;
+
+    fn run_completed(workflow_name: &str) -> AgentEvent {
+        AgentEvent::WorkflowRunCompleted {
+            run_id: Uuid::now_v7(),
+            workflow_name: workflow_name.to_string(),
+            duration_ms: 10,
+            steps_completed: 1,
+        }
+    }
+
+    fn event_trigger(event_type: &str, when: Option<&str>) -> TriggerConfig {
+        TriggerConfig::Event {
+            source: INTERNAL_EVENT_SOURCE.to_string(),
+            event_type: event_type.to_string(),
+            when: when.map(String::from),
+        }
+    }
+
+    fn recording_callback() -> (EventTriggerCallback, mpsc::UnboundedReceiver<(Uuid, Value)>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let callback: EventTriggerCallback = Arc::new(move |workflow_id, payload| {
+            let tx = tx.clone();
+            Box::pin(async move {
+                let _ = tx.send((workflow_id, payload));
+            })
+        });
+        (callback, rx)
+    }
+
+    #[tokio::test]
+    async fn test_matching_checks_source_type_and_when() {
+        let manager = TriggerManager::new();
+        let on_digest = Uuid::now_v7();
+        let on_any_run = Uuid::now_v7();
+        let external = Uuid::now_v7();
+        manager
+            .register_workflow(
+                on_digest,
+                "on-digest",
+                &[event_trigger(
+                    "workflow_run_completed",
+                    Some("event.workflow_name == 'daily-digest'"),
+                )],
+            )
+            .await
+            .unwrap();
+        manager
+            .register_workflow(
+                on_any_run,
+                "on-any-run",
+                &[
+                    event_trigger("workflow_run_completed", None),
+                    // A second matching trigger must not launch twice
+                    event_trigger(
+                        "workflow_run_completed",
+                        Some("trigger.source == 'internal'"),
+                    ),
+                ],
+            )
+            .await
+            .unwrap();
+        manager
+            .register_workflow(
+                external,
+                "external",
+                &[TriggerConfig::Event {
+                    source: "github".to_string(),
+                    event_type: "workflow_run_completed".to_string(),
+                    when: None,
+                }],
+            )
+            .await
+            .unwrap();
+
+        let mut matched: Vec<Uuid> = matching_workflows(&manager, &run_completed("daily-digest"))
+            .await
+            .into_iter()
+            .map(|(id, payload)| {
+                assert_eq!(payload["type"], "workflow_run_completed");
+                id
+            })
+            .collect();
+        matched.sort();
+        let mut expected = vec![on_digest, on_any_run];
+        expected.sort();
+        assert_eq!(matched, expected);
+
+        let matched = matching_workflows(&manager, &run_completed("other")).await;
+        assert_eq!(matched.len(), 1);
+        assert_eq!(matched[0].0, on_any_run);
+    }
+
+    #[tokio::test]
+    async fn test_bridge_launches_only_matching_events() {
+        let event_bus = EventBus::new(16);
+        let manager = Arc::new(TriggerManager::new());
+        let workflow_id = Uuid::now_v7();
+        manager
+            .register_workflow(
+                workflow_id,
+                "on-digest",
+                &[event_trigger(
+                    "workflow_run_completed",
+                    Some("event.workflow_name == 'daily-digest'"),
+                )],
+            )
+            .await
+            .unwrap();
+
+        let (callback, mut launched) = recording_callback();
+        let bridge = EventTriggerBridge::start(&event_bus, Arc::clone(&manager), callback);
+        assert!(bridge.is_running());
+
+        // Non-matching: wrong workflow name, then wrong event type
+        event_bus.publish(run_completed("weekly-report"));
+        event_bus.publish(AgentEvent::WorkflowRunStarted {
+            run_id: Uuid::now_v7(),
+            workflow_name: "daily-digest".to_string(),
+            trigger_type: "manual".to_string(),
+        });
+        // Matching
+        event_bus.publish(run_completed("daily-digest"));
+
+        let (id, payload) = tokio::time::timeout(Duration::from_secs(5), launched.recv())
+            .await
+            .expect("matching event should launch a run")
+            .unwrap();
+        assert_eq!(id, workflow_id);
+        assert_eq!(payload["workflow_name"], "daily-digest");
+
+        // Nothing else was launched
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(launched.try_recv().is_err());
+
+        drop(bridge);
+    }
+}
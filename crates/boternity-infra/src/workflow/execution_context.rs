@@ -19,7 +19,7 @@ use boternity_core::llm::box_provider::BoxLlmProvider;
 use boternity_core::service::secret::SecretService;
 use boternity_core::workflow::step_runner::{StepError, StepExecutionContext};
 use boternity_types::identity::Identity;
-use boternity_types::llm::{CompletionRequest, Message, MessageRole};
+use boternity_types::llm::{CompletionRequest, Message};
 use boternity_types::secret::SecretScope;
 use boternity_types::skill::SkillType;
 use secrecy::SecretString;
@@ -144,19 +144,12 @@ impl StepExecutionContext for LiveExecutionContext {
             let provider = self.create_provider(&model).await?;
 
             // Build completion request
-            let request = CompletionRequest {
-                model: model.clone(),
-                messages: vec![Message {
-                    role: MessageRole::User,
-                    content: prompt.clone(),
-                }],
-                system: None,
-                max_tokens: max_tokens as u32,
-                temperature: Some(temperature),
-                stream: false,
-                stop_sequences: None,
-                output_config: None,
-            };
+            let request = CompletionRequest::builder(model.clone())
+                .message(Message::from(prompt.clone()))
+                .max_tokens(max_tokens as u32)
+                .temperature(temperature)
+                .build()
+                .map_err(|e| StepError::ExecutionFailed(format!("invalid LLM request: {e}")))?;
 
             // Execute non-streaming completion
             let response = provider.complete(&request).await.map_err(|e| {
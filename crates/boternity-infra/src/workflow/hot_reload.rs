@@ -0,0 +1,515 @@
+//! Hot-reload of workflow definitions from YAML files on disk.
+//!
+//! Provides:
+//! - `WorkflowReloader` -- Re-validates a changed YAML file and updates the
+//!   repository and `TriggerManager`
+//! - `watch_definitions()` -- Starts a `file_trigger` watcher on a workflow
+//!   directory that feeds changed files to a `WorkflowReloader`
+//! - `DefinitionWatcher` -- RAII handle that keeps the watcher alive
+//!
+//! A file is matched to its stored definition by the `id` in the YAML. Files
+//! that fail to parse or validate are logged and the stored definition is
+//! kept. Deleting a file does not delete its definition.
+//!
+//! Runs execute against their own copy of the definition, so a reload never
+//! changes a run that is already in progress; only runs started afterwards
+//! see the new version.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use boternity_core::repository::workflow::WorkflowRepository;
+use boternity_core::workflow::dag::validate_dag;
+use boternity_core::workflow::definition::{WorkflowError, discover_workflows, load_workflow_file};
+use boternity_core::workflow::trigger::{TriggerError, TriggerManager};
+use boternity_types::error::RepositoryError;
+use boternity_types::workflow::WorkflowDefinition;
+use futures_util::future::BoxFuture;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+use super::file_trigger::{FileWatchError, WatcherHandle, start_file_watcher};
+
+/// Called with each added or updated definition, after it has been stored
+/// and its triggers registered (e.g. to reschedule cron triggers).
+pub type ReloadCallback = Arc<dyn Fn(WorkflowDefinition) -> BoxFuture<'static, ()> + Send + Sync>;
+
+// ---------------------------------------------------------------------------
+// Error type
+// ---------------------------------------------------------------------------
+
+/// Errors that can occur while reloading a workflow definition.
+#[derive(Debug, thiserror::Error)]
+pub enum ReloadError {
+    /// The file could not be read, parsed, or failed validation.
+    #[error("invalid workflow definition: {0}")]
+    Definition(#[from] WorkflowError),
+
+    /// One of the definition's triggers is invalid.
+    #[error("invalid workflow trigger: {0}")]
+    Trigger(#[from] TriggerError),
+
+    /// The definition could not be stored.
+    #[error("failed to store workflow definition: {0}")]
+    Repository(#[from] RepositoryError),
+}
+
+/// What a reload did with a definition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReloadOutcome {
+    /// No definition with this ID was stored before.
+    Added,
+    /// The stored definition was replaced.
+    Updated,
+    /// The file matches the stored definition; nothing changed.
+    Unchanged,
+}
+
+// ---------------------------------------------------------------------------
+// WorkflowReloader
+// ---------------------------------------------------------------------------
+
+/// Applies workflow YAML files to the repository and trigger registry.
+pub struct WorkflowReloader<R: WorkflowRepository> {
+    repo: Arc<R>,
+    trigger_manager: Arc<TriggerManager>,
+    on_reload: Option<ReloadCallback>,
+}
+
+impl<R: WorkflowRepository> WorkflowReloader<R> {
+    /// Create a reloader that updates `repo` and `trigger_manager`.
+    pub fn new(repo: Arc<R>, trigger_manager: Arc<TriggerManager>) -> Self {
+        Self {
+            repo,
+            trigger_manager,
+            on_reload: None,
+        }
+    }
+
+    /// Call `callback` with every added or updated definition.
+    pub fn with_on_reload(mut self, callback: ReloadCallback) -> Self {
+        self.on_reload = Some(callback);
+        self
+    }
+
+    /// Load, validate and apply a single workflow file.
+    pub async fn reload_file(&self, path: &Path) -> Result<ReloadOutcome, ReloadError> {
+        let def = load_workflow_file(path)?;
+        validate_dag(&def.steps)?;
+        self.apply(def).await
+    }
+
+    /// Apply every workflow file under `dir`.
+    ///
+    /// Files that fail to load or apply are logged and skipped. Returns the
+    /// outcome for each file that was applied.
+    pub async fn reload_dir(
+        &self,
+        dir: &Path,
+    ) -> Result<Vec<(PathBuf, ReloadOutcome)>, ReloadError> {
+        let mut outcomes = Vec::new();
+        for (path, def) in discover_workflows(dir)? {
+            let result = match validate_dag(&def.steps) {
+                Ok(()) => self.apply(def).await,
+                Err(e) => Err(e.into()),
+            };
+            match result {
+                Ok(outcome) => outcomes.push((path, outcome)),
+                Err(e) => {
+                    tracing::warn!(?path, error = %e, "skipping workflow file");
+                }
+            }
+        }
+        Ok(outcomes)
+    }
+
+    /// Store `def` and register its triggers, unless it is unchanged.
+    async fn apply(&self, def: WorkflowDefinition) -> Result<ReloadOutcome, ReloadError> {
+        let existing = self.repo.get_definition(&def.id).await?;
+        if let Some(prev) = &existing
+            && same_definition(prev, &def)
+        {
+            return Ok(ReloadOutcome::Unchanged);
+        }
+
+        // Registering validates the triggers before anything is stored
+        self.trigger_manager
+            .register_workflow(def.id, &def.name, &def.triggers)
+            .await?;
+        if let Err(e) = self.repo.save_definition(&def).await {
+            // Put the triggers back in line with what is stored
+            match &existing {
+                Some(prev) => {
+                    let _ = self
+                        .trigger_manager
+                        .register_workflow(prev.id, &prev.name, &prev.triggers)
+                        .await;
+                }
+                None => {
+                    let _ = self.trigger_manager.unregister_workflow(def.id).await;
+                }
+            }
+            return Err(e.into());
+        }
+
+        let outcome = if existing.is_some() {
+            ReloadOutcome::Updated
+        } else {
+            ReloadOutcome::Added
+        };
+        tracing::info!(
+            workflow_id = %def.id,
+            workflow_name = %def.name,
+            version = %def.version,
+            ?outcome,
+            "workflow definition reloaded"
+        );
+        if let Some(callback) = &self.on_reload {
+            callback(def).await;
+        }
+        Ok(outcome)
+    }
+}
+
+/// Compare definitions by their serialized form.
+fn same_definition(a: &WorkflowDefinition, b: &WorkflowDefinition) -> bool {
+    match (serde_json::to_value(a), serde_json::to_value(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// DefinitionWatcher
+// ---------------------------------------------------------------------------
+
+/// RAII handle for a workflow directory watcher.
+///
+/// Dropping the handle stops both the filesystem watcher and the reload task.
+pub struct DefinitionWatcher {
+    _watcher: WatcherHandle,
+    task: JoinHandle<()>,
+    dir: PathBuf,
+}
+
+impl DefinitionWatcher {
+    /// The directory being watched.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+}
+
+impl Drop for DefinitionWatcher {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Watch `dir` for changed `.yaml`/`.yml` files and reload them.
+///
+/// Only changes made after this call are picked up; call
+/// [`WorkflowReloader::reload_dir`] first to load what is already there.
+pub fn watch_definitions<R>(
+    dir: &Path,
+    reloader: Arc<WorkflowReloader<R>>,
+    debounce_ms: Option<u64>,
+) -> Result<DefinitionWatcher, FileWatchError>
+where
+    R: WorkflowRepository + 'static,
+{
+    let (watcher, mut rx) = start_file_watcher(
+        Uuid::nil(),
+        &[dir.display().to_string()],
+        Some(vec!["*.yaml".to_string(), "*.yml".to_string()]),
+        debounce_ms,
+    )?;
+
+    let task = tokio::spawn(async move {
+        while let Some(batch) = rx.recv().await {
+            let paths: BTreeSet<PathBuf> = batch.into_iter().map(|e| e.path).collect();
+            for path in paths {
+                if !path.is_file() {
+                    tracing::debug!(?path, "workflow file removed, keeping stored definition");
+                    continue;
+                }
+                if let Err(e) = reloader.reload_file(&path).await {
+                    tracing::warn!(
+                        ?path,
+                        error = %e,
+                        "workflow reload failed, keeping previous definition"
+                    );
+                }
+            }
+        }
+    });
+
+    Ok(DefinitionWatcher {
+        _watcher: watcher,
+        task,
+        dir: dir.to_path_buf(),
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::pin::Pin;
+    use std::time::Duration;
+
+    use boternity_core::event::bus::EventBus;
+    use boternity_core::workflow::definition::save_workflow_file;
+    use boternity_core::workflow::executor::{DagExecutor, WorkflowExecutor};
+    use boternity_core::workflow::step_runner::{
+        PlaceholderExecutionContext, StepError, StepExecutionContext,
+    };
+    use boternity_types::workflow::{
+        CodeLanguage, StepConfig, StepDefinition, StepType, TriggerConfig,
+        WorkflowDefinitionBuilder, WorkflowRunStatus,
+    };
+    use serde_json::{Value, json};
+    use tokio::sync::Notify;
+
+    use crate::sqlite::pool::DatabasePool;
+    use crate::sqlite::workflow::SqliteWorkflowRepository;
+
+    async fn test_pool() -> DatabasePool {
+        let dir = tempfile::tempdir().unwrap();
+        let url = format!("sqlite://{}?mode=rwc", dir.path().join("test.db").display());
+        std::mem::forget(dir);
+        DatabasePool::new(&url).await.unwrap()
+    }
+
+    fn http_step(id: &str) -> StepDefinition {
+        StepDefinition {
+            id: id.to_string(),
+            name: id.to_string(),
+            step_type: StepType::Http,
+            depends_on: vec![],
+            condition: None,
+            timeout_secs: None,
+            retry: None,
+            config: StepConfig::Http {
+                method: "GET".to_string(),
+                url: "https://example.com/feed".to_string(),
+                headers: None,
+                body: None,
+            },
+            ui: None,
+        }
+    }
+
+    fn code_step(id: &str, after: &str) -> StepDefinition {
+        StepDefinition {
+            id: id.to_string(),
+            name: id.to_string(),
+            step_type: StepType::Code,
+            depends_on: vec![after.to_string()],
+            condition: None,
+            timeout_secs: None,
+            retry: None,
+            config: StepConfig::Code {
+                language: CodeLanguage::TypeScript,
+                source: "return 1".to_string(),
+            },
+            ui: None,
+        }
+    }
+
+    fn definition(id: Uuid, version: &str, last_step: &str) -> WorkflowDefinition {
+        WorkflowDefinitionBuilder::new("news-digest")
+            .id(id)
+            .version(version)
+            .step(http_step("fetch"))
+            .step(code_step(last_step, "fetch"))
+            .build()
+    }
+
+    /// HTTP steps block until `release` is notified.
+    #[derive(Default)]
+    struct GatedContext {
+        started: Notify,
+        release: Notify,
+    }
+
+    impl StepExecutionContext for GatedContext {
+        fn execute_agent(
+            &self,
+            bot: &str,
+            prompt: &str,
+            model: Option<&str>,
+        ) -> Pin<Box<dyn Future<Output = Result<Value, StepError>> + Send + '_>> {
+            let (bot, prompt, model) =
+                (bot.to_string(), prompt.to_string(), model.map(String::from));
+            Box::pin(async move {
+                PlaceholderExecutionContext
+                    .execute_agent(&bot, &prompt, model.as_deref())
+                    .await
+            })
+        }
+
+        fn execute_skill(
+            &self,
+            skill: &str,
+            input: Option<&str>,
+        ) -> Pin<Box<dyn Future<Output = Result<Value, StepError>> + Send + '_>> {
+            let (skill, input) = (skill.to_string(), input.map(String::from));
+            Box::pin(async move {
+                PlaceholderExecutionContext
+                    .execute_skill(&skill, input.as_deref())
+                    .await
+            })
+        }
+
+        fn execute_http(
+            &self,
+            _method: &str,
+            _url: &str,
+            _headers: Option<&std::collections::HashMap<String, String>>,
+            _body: Option<&str>,
+        ) -> Pin<Box<dyn Future<Output = Result<Value, StepError>> + Send + '_>> {
+            Box::pin(async move {
+                self.started.notify_one();
+                self.release.notified().await;
+                Ok(json!({"status": 200}))
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reload_file_adds_updates_and_skips_unchanged() {
+        let pool = test_pool().await;
+        let repo = Arc::new(SqliteWorkflowRepository::new(pool.clone()));
+        let triggers = Arc::new(TriggerManager::new());
+        let reloader = WorkflowReloader::new(Arc::clone(&repo), Arc::clone(&triggers));
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("news-digest.yaml");
+        let id = Uuid::now_v7();
+
+        save_workflow_file(&path, &definition(id, "1.0.0", "summarize")).unwrap();
+        assert_eq!(
+            reloader.reload_file(&path).await.unwrap(),
+            ReloadOutcome::Added
+        );
+        assert_eq!(
+            reloader.reload_file(&path).await.unwrap(),
+            ReloadOutcome::Unchanged
+        );
+
+        let mut updated = definition(id, "1.1.0", "summarize");
+        updated.triggers.push(TriggerConfig::Event {
+            source: "internal".to_string(),
+            event_type: "bot_created".to_string(),
+            when: None,
+        });
+        save_workflow_file(&path, &updated).unwrap();
+        assert_eq!(
+            reloader.reload_file(&path).await.unwrap(),
+            ReloadOutcome::Updated
+        );
+
+        let stored = repo.get_definition(&id).await.unwrap().unwrap();
+        assert_eq!(stored.version, "1.1.0");
+        assert_eq!(triggers.get_event_triggers().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_edit_keeps_previous_definition() {
+        let pool = test_pool().await;
+        let repo = Arc::new(SqliteWorkflowRepository::new(pool.clone()));
+        let triggers = Arc::new(TriggerManager::new());
+        let reloader = WorkflowReloader::new(Arc::clone(&repo), Arc::clone(&triggers));
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("news-digest.yaml");
+        let id = Uuid::now_v7();
+
+        save_workflow_file(&path, &definition(id, "1.0.0", "summarize")).unwrap();
+        reloader.reload_file(&path).await.unwrap();
+
+        // Dependency on a step that does not exist
+        let mut broken = definition(id, "2.0.0", "summarize");
+        broken.steps[1].depends_on = vec!["missing".to_string()];
+        save_workflow_file(&path, &broken).unwrap();
+        assert!(matches!(
+            reloader.reload_file(&path).await,
+            Err(ReloadError::Definition(_))
+        ));
+
+        // Invalid cron schedule
+        let mut bad_trigger = definition(id, "2.0.0", "summarize");
+        bad_trigger.triggers.push(TriggerConfig::Cron {
+            schedule: "every blue moon".to_string(),
+            timezone: None,
+        });
+        save_workflow_file(&path, &bad_trigger).unwrap();
+        assert!(matches!(
+            reloader.reload_file(&path).await,
+            Err(ReloadError::Trigger(_))
+        ));
+
+        let stored = repo.get_definition(&id).await.unwrap().unwrap();
+        assert_eq!(stored.version, "1.0.0");
+    }
+
+    #[tokio::test]
+    async fn test_watcher_reloads_edit_while_run_finishes_on_old_definition() {
+        let pool = test_pool().await;
+        let repo = Arc::new(SqliteWorkflowRepository::new(pool.clone()));
+        let triggers = Arc::new(TriggerManager::new());
+        let reloaded = Arc::new(Notify::new());
+        let reloaded_tx = Arc::clone(&reloaded);
+        let on_reload: ReloadCallback = Arc::new(move |_def| {
+            let reloaded = Arc::clone(&reloaded_tx);
+            Box::pin(async move { reloaded.notify_one() })
+        });
+        let reloader = Arc::new(
+            WorkflowReloader::new(Arc::clone(&repo), Arc::clone(&triggers))
+                .with_on_reload(on_reload),
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("news-digest.yaml");
+        let id = Uuid::now_v7();
+        save_workflow_file(&path, &definition(id, "1.0.0", "summarize")).unwrap();
+        let outcomes = reloader.reload_dir(dir.path()).await.unwrap();
+        assert_eq!(outcomes, vec![(path.clone(), ReloadOutcome::Added)]);
+        reloaded.notified().await;
+
+        let watcher = watch_definitions(dir.path(), Arc::clone(&reloader), Some(50)).unwrap();
+        assert_eq!(watcher.dir(), dir.path());
+
+        // Start a run on v1 and hold it inside its first step
+        let gate = Arc::new(GatedContext::default());
+        let executor = Arc::new(DagExecutor::with_execution_context(
+            SqliteWorkflowRepository::new(pool),
+            EventBus::new(16),
+            dir.path().to_path_buf(),
+            Arc::clone(&gate) as Arc<dyn StepExecutionContext>,
+        ));
+        let v1 = repo.get_definition(&id).await.unwrap().unwrap();
+        let run = tokio::spawn({
+            let executor = Arc::clone(&executor);
+            async move { executor.execute(&v1, "manual", None).await }
+        });
+        gate.started.notified().await;
+
+        // Edit the file on disk; the watcher applies it
+        save_workflow_file(&path, &definition(id, "2.0.0", "publish")).unwrap();
+        tokio::time::timeout(Duration::from_secs(10), reloaded.notified())
+            .await
+            .expect("watcher should reload the edited file");
+        let stored = repo.get_definition(&id).await.unwrap().unwrap();
+        assert_eq!(stored.version, "2.0.0");
+        assert_eq!(stored.steps[1].id, "publish");
+
+        // The in-flight run finishes with the steps it started with
+        gate.release.notify_one();
+        let result = run.await.unwrap().unwrap();
+        assert_eq!(result.status, WorkflowRunStatus::Completed);
+        assert_eq!(result.completed_steps, vec!["fetch", "summarize"]);
+    }
+}
@@ -1,11 +1,16 @@
-//! Workflow infrastructure: webhook handlers, filesystem watchers, and execution context.
+//! Workflow infrastructure: webhook handlers, filesystem watchers, event
+//! triggers, definition hot-reload, and execution context.
 //!
 //! This module provides concrete implementations for workflow trigger
 //! subsystems and step execution:
 //! - `webhook_handler` -- HMAC-SHA256/bearer token auth, webhook registry
 //! - `file_trigger` -- Debounced filesystem watcher with glob filtering
+//! - `event_trigger` -- EventBus listener that launches event-triggered workflows
+//! - `hot_reload` -- Reloads edited workflow YAML files without a restart
 //! - `execution_context` -- Live step execution wiring (Agent/Skill/HTTP)
 
+pub mod event_trigger;
 pub mod execution_context;
 pub mod file_trigger;
+pub mod hot_reload;
 pub mod webhook_handler;
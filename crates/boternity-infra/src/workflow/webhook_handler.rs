@@ -4,12 +4,20 @@
 //! - `verify_hmac_sha256()` -- constant-time HMAC-SHA256 signature verification
 //! - `verify_bearer_token()` -- constant-time bearer token comparison
 //! - `WebhookRegistry` -- DashMap-backed registry for path -> webhook config lookup
+//! - `DeliveryDeduplicator` -- suppresses repeat deliveries by idempotency key
 
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
+use boternity_core::repository::workflow::WorkflowRepository;
+use boternity_types::error::RepositoryError;
+use boternity_types::workflow::{TriggerConfig, WorkflowDefinition};
+use chrono::{DateTime, Utc};
 use dashmap::DashMap;
 use hmac::{Hmac, Mac};
 use sha2::Sha256;
+use tokio::sync::Mutex;
 use uuid::Uuid;
 
 // Type alias for HMAC-SHA256
@@ -256,6 +264,113 @@ impl Default for WebhookRegistry {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Delivery deduplication
+// ---------------------------------------------------------------------------
+
+/// Request header carrying a sender-provided idempotency key.
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Idempotency key for a delivery.
+///
+/// Uses the sender's key when one is provided, otherwise a SHA-256 hash of
+/// the body, so retries of the same payload share a key.
+pub fn delivery_idempotency_key(header: Option<&str>, body: &[u8]) -> String {
+    match header.map(str::trim).filter(|key| !key.is_empty()) {
+        Some(key) => format!("key:{key}"),
+        None => format!(
+            "sha256:{}",
+            hex_encode(&<Sha256 as sha2::Digest>::digest(body))
+        ),
+    }
+}
+
+/// Dedup window configured on `definition`'s webhook trigger for `path`.
+pub fn dedup_window(definition: &WorkflowDefinition, path: &str) -> Option<Duration> {
+    let path = normalize_path(path);
+    definition
+        .triggers
+        .iter()
+        .find_map(|trigger| match trigger {
+            TriggerConfig::Webhook {
+                path: trigger_path,
+                dedup_window_secs: Some(secs),
+                ..
+            } if normalize_path(trigger_path) == path => Some(Duration::from_secs(*secs)),
+            _ => None,
+        })
+}
+
+/// Result of claiming a delivery with `DeliveryDeduplicator::claim`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeliveryClaim {
+    /// First delivery with this key in the window: start a run with this ID.
+    New(Uuid),
+    /// Repeat delivery: the run with this ID was already started for the key.
+    Duplicate(Uuid),
+}
+
+/// Deduplicates trigger deliveries by idempotency key within a time window.
+///
+/// Claims are serialized, so concurrent deliveries of the same key cannot
+/// both start a run. A claim is kept in memory for its window because the
+/// run record may not exist yet; after a restart the run repository is
+/// consulted instead.
+pub struct DeliveryDeduplicator {
+    /// (workflow_id, key) -> (run_id, claim expiry).
+    claims: Mutex<HashMap<(Uuid, String), (Uuid, DateTime<Utc>)>>,
+}
+
+impl DeliveryDeduplicator {
+    /// Create an empty deduplicator.
+    pub fn new() -> Self {
+        Self {
+            claims: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Claim a delivery of `key` for `workflow_id`.
+    ///
+    /// Returns `Duplicate` with the earlier run's ID if the key was claimed
+    /// or recorded on a run within `window`, otherwise `New` with the ID the
+    /// caller must use for the run.
+    pub async fn claim<R: WorkflowRepository>(
+        &self,
+        repo: &R,
+        workflow_id: Uuid,
+        key: &str,
+        window: Duration,
+    ) -> Result<DeliveryClaim, RepositoryError> {
+        let window = chrono::Duration::from_std(window)
+            .map_err(|e| RepositoryError::Query(format!("invalid dedup window: {e}")))?;
+        let now = Utc::now();
+
+        let mut claims = self.claims.lock().await;
+        claims.retain(|_, (_, expires_at)| *expires_at > now);
+
+        let claim_key = (workflow_id, key.to_string());
+        if let Some((run_id, _)) = claims.get(&claim_key) {
+            return Ok(DeliveryClaim::Duplicate(*run_id));
+        }
+        if let Some(run) = repo
+            .find_run_by_idempotency_key(&workflow_id, key, now - window)
+            .await?
+        {
+            return Ok(DeliveryClaim::Duplicate(run.id));
+        }
+
+        let run_id = Uuid::now_v7();
+        claims.insert(claim_key, (run_id, now + window));
+        Ok(DeliveryClaim::New(run_id))
+    }
+}
+
+impl Default for DeliveryDeduplicator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Helpers
 // ---------------------------------------------------------------------------
@@ -697,6 +812,187 @@ mod tests {
         assert!(hex_decode("zz").is_err()); // Invalid chars
     }
 
+    // -------------------------------------------------------------------
+    // Delivery deduplication
+    // -------------------------------------------------------------------
+
+    async fn dedup_fixture() -> (
+        crate::sqlite::pool::DatabasePool,
+        WorkflowDefinition,
+        boternity_core::workflow::executor::DagExecutor<
+            crate::sqlite::workflow::SqliteWorkflowRepository,
+        >,
+    ) {
+        use crate::sqlite::workflow::SqliteWorkflowRepository;
+        use boternity_core::event::bus::EventBus;
+        use boternity_core::workflow::executor::DagExecutor;
+        use boternity_types::workflow::{
+            StepConfig, StepDefinition, StepType, WorkflowDefinitionBuilder,
+        };
+
+        let dir = tempfile::tempdir().unwrap();
+        let url = format!("sqlite://{}?mode=rwc", dir.path().join("test.db").display());
+        std::mem::forget(dir);
+        let pool = crate::sqlite::pool::DatabasePool::new(&url).await.unwrap();
+
+        let def = WorkflowDefinitionBuilder::new("on-push")
+            .trigger(TriggerConfig::Webhook {
+                path: "/hooks/push".to_string(),
+                auth: None,
+                when: None,
+                dedup_window_secs: Some(300),
+            })
+            .step(StepDefinition {
+                id: "ack".to_string(),
+                name: "ack".to_string(),
+                step_type: StepType::Agent,
+                depends_on: vec![],
+                condition: None,
+                timeout_secs: None,
+                retry: None,
+                config: StepConfig::Agent {
+                    bot: "ci".to_string(),
+                    prompt: "{{ trigger.ref }}".to_string(),
+                    model: None,
+                },
+                ui: None,
+            })
+            .build();
+        SqliteWorkflowRepository::new(pool.clone())
+            .save_definition(&def)
+            .await
+            .unwrap();
+
+        let executor = DagExecutor::new(
+            SqliteWorkflowRepository::new(pool.clone()),
+            EventBus::new(16),
+            std::env::temp_dir(),
+        );
+        (pool, def, executor)
+    }
+
+    /// Deliver `body` the way the webhook endpoint does: claim, then run.
+    async fn deliver(
+        dedup: &DeliveryDeduplicator,
+        repo: &crate::sqlite::workflow::SqliteWorkflowRepository,
+        executor: &boternity_core::workflow::executor::DagExecutor<
+            crate::sqlite::workflow::SqliteWorkflowRepository,
+        >,
+        def: &WorkflowDefinition,
+        body: &[u8],
+        window: Duration,
+    ) -> DeliveryClaim {
+        let key = delivery_idempotency_key(None, body);
+        let claim = dedup.claim(repo, def.id, &key, window).await.unwrap();
+        if let DeliveryClaim::New(run_id) = claim {
+            let payload = serde_json::from_slice(body).ok();
+            executor
+                .execute_run(def, run_id, "webhook", payload, Some(key))
+                .await
+                .unwrap();
+        }
+        claim
+    }
+
+    #[test]
+    fn test_delivery_idempotency_key() {
+        let body = br#"{"ref":"main"}"#;
+        let hashed = delivery_idempotency_key(None, body);
+        assert!(hashed.starts_with("sha256:"));
+        assert_eq!(hashed, delivery_idempotency_key(Some("  "), body));
+        assert_ne!(hashed, delivery_idempotency_key(None, br#"{"ref":"dev"}"#));
+
+        // A sender key wins over the body hash
+        assert_eq!(
+            delivery_idempotency_key(Some("delivery-1"), body),
+            "key:delivery-1"
+        );
+        assert_eq!(
+            delivery_idempotency_key(Some("delivery-1"), b"other body"),
+            "key:delivery-1"
+        );
+    }
+
+    #[test]
+    fn test_dedup_window_from_webhook_trigger() {
+        let def = boternity_types::workflow::WorkflowDefinitionBuilder::new("wf")
+            .trigger(TriggerConfig::Webhook {
+                path: "/hooks/push".to_string(),
+                auth: None,
+                when: None,
+                dedup_window_secs: Some(60),
+            })
+            .trigger(TriggerConfig::Webhook {
+                path: "/hooks/plain".to_string(),
+                auth: None,
+                when: None,
+                dedup_window_secs: None,
+            })
+            .build();
+        assert_eq!(
+            dedup_window(&def, "/hooks/push/"),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(dedup_window(&def, "/hooks/plain"), None);
+        assert_eq!(dedup_window(&def, "/hooks/other"), None);
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_delivery_within_window_starts_one_run() {
+        use crate::sqlite::workflow::SqliteWorkflowRepository;
+
+        let (pool, def, executor) = dedup_fixture().await;
+        let repo = SqliteWorkflowRepository::new(pool);
+        let dedup = DeliveryDeduplicator::new();
+        let window = Duration::from_secs(300);
+        let body = br#"{"ref":"main"}"#;
+
+        let first = deliver(&dedup, &repo, &executor, &def, body, window).await;
+        let DeliveryClaim::New(run_id) = first else {
+            panic!("first delivery should start a run, got {first:?}");
+        };
+        let retry = deliver(&dedup, &repo, &executor, &def, body, window).await;
+        assert_eq!(retry, DeliveryClaim::Duplicate(run_id));
+
+        // A fresh deduplicator (e.g. after a restart) finds the run record
+        let restarted = DeliveryDeduplicator::new();
+        let after_restart = deliver(&restarted, &repo, &executor, &def, body, window).await;
+        assert_eq!(after_restart, DeliveryClaim::Duplicate(run_id));
+
+        // A different payload is not a duplicate
+        let other = deliver(&dedup, &repo, &executor, &def, br#"{"ref":"dev"}"#, window).await;
+        assert!(matches!(other, DeliveryClaim::New(id) if id != run_id));
+
+        let runs = repo.list_runs(&def.id, 10).await.unwrap();
+        assert_eq!(runs.len(), 2);
+        let run = runs.iter().find(|r| r.id == run_id).unwrap();
+        assert_eq!(
+            run.idempotency_key.as_deref(),
+            Some(delivery_idempotency_key(None, body).as_str())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_delivery_outside_window_starts_new_run() {
+        use crate::sqlite::workflow::SqliteWorkflowRepository;
+
+        let (pool, def, executor) = dedup_fixture().await;
+        let repo = SqliteWorkflowRepository::new(pool);
+        let dedup = DeliveryDeduplicator::new();
+        let window = Duration::from_millis(200);
+        let body = br#"{"ref":"main"}"#;
+
+        let first = deliver(&dedup, &repo, &executor, &def, body, window).await;
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        let second = deliver(&dedup, &repo, &executor, &def, body, window).await;
+
+        let (DeliveryClaim::New(first_id), DeliveryClaim::New(second_id)) = (first, second) else {
+            panic!("both deliveries should start runs, got {first:?} and {second:?}");
+        };
+        assert_ne!(first_id, second_id);
+        assert_eq!(repo.list_runs(&def.id, 10).await.unwrap().len(), 2);
+    }
+
     // -------------------------------------------------------------------
     // normalize_path
     // -------------------------------------------------------------------
@@ -20,6 +20,10 @@ pub struct AgentConfig {
     pub model: String,
     pub temperature: f64,
     pub max_tokens: u32,
+    /// Wrap recalled memories in "data, not instructions" blocks and strip
+    /// known prompt-injection phrases (`guard_untrusted_content` in IDENTITY.md).
+    #[serde(default)]
+    pub guard_untrusted_content: bool,
 }
 
 /// Mode for spawning sub-agents.
@@ -95,6 +99,7 @@ mod tests {
             model: "claude-sonnet-4-20250514".to_string(),
             temperature: 0.7,
             max_tokens: 4096,
+            guard_untrusted_content: false,
         };
         let json = serde_json::to_string(&config).unwrap();
         assert!(json.contains("\"bot_name\":\"Luna\""));
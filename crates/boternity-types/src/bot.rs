@@ -2,6 +2,8 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::error::BotError;
+
 use std::fmt;
 use std::str::FromStr;
 
@@ -72,6 +74,9 @@ pub struct Bot {
     pub updated_at: DateTime<Utc>,
     /// Last time this bot was used in a conversation.
     pub last_active_at: Option<DateTime<Utc>>,
+    /// When the bot was soft-deleted; `None` for live bots.
+    #[serde(default)]
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 /// Bot lifecycle states.
@@ -100,22 +105,71 @@ impl fmt::Display for BotStatus {
 impl FromStr for BotStatus {
     type Err = String;
 
+    /// Parse the exact [`Display`](fmt::Display) form; anything else is an error.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_lowercase().as_str() {
+        match s {
             "active" => Ok(BotStatus::Active),
             "disabled" => Ok(BotStatus::Disabled),
             "archived" => Ok(BotStatus::Archived),
-            other => Err(format!("invalid bot status: '{other}'")),
+            other => Err(format!(
+                "invalid bot status: '{other}' (expected active, disabled, or archived)"
+            )),
         }
     }
 }
 
+impl BotStatus {
+    /// Every status, in declaration order.
+    pub const ALL: [BotStatus; 3] = [BotStatus::Active, BotStatus::Disabled, BotStatus::Archived];
+
+    /// Parse user input, ignoring case and surrounding whitespace.
+    pub fn parse_lenient(s: &str) -> Result<Self, String> {
+        s.trim().to_lowercase().parse()
+    }
+}
+
 impl Default for BotStatus {
     fn default() -> Self {
         BotStatus::Active
     }
 }
 
+impl BotStatus {
+    /// Validate a lifecycle transition from this status to `to`.
+    ///
+    /// - Active <-> Disabled and Active/Disabled -> Archived are always allowed.
+    /// - Archived -> Active/Disabled requires `restore`, so archived bots are
+    ///   only brought back deliberately.
+    /// - Transitioning to the current status is rejected.
+    pub fn check_transition(&self, to: &BotStatus, restore: bool) -> Result<(), BotError> {
+        let reject = |reason: &str| BotError::InvalidStatusTransition {
+            from: self.to_string(),
+            to: to.to_string(),
+            reason: reason.to_string(),
+        };
+
+        match (self, to) {
+            (from, to) if from == to => Err(reject("bot already has this status")),
+            (BotStatus::Archived, _) if !restore => {
+                Err(reject("archived bots must be restored explicitly (--restore)"))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A recorded bot status change, kept as an append-only history.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BotStatusTransition {
+    pub id: Uuid,
+    pub bot_id: BotId,
+    pub from_status: BotStatus,
+    pub to_status: BotStatus,
+    /// Optional free-text reason given by whoever made the change.
+    pub reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
 /// System categories for bot organization.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -265,11 +319,39 @@ mod tests {
 
     #[test]
     fn test_bot_status_roundtrip() {
-        for status in [BotStatus::Active, BotStatus::Disabled, BotStatus::Archived] {
+        for status in BotStatus::ALL {
             let s = status.to_string();
             let parsed: BotStatus = s.parse().unwrap();
             assert_eq!(status, parsed);
+            assert_eq!(serde_json::to_value(&status).unwrap(), s.as_str());
+        }
+        for bad in ["deleted", "", "Active"] {
+            assert!(bad.parse::<BotStatus>().is_err(), "{bad:?} should not parse");
         }
+        assert_eq!(BotStatus::parse_lenient(" Active"), Ok(BotStatus::Active));
+    }
+
+    #[test]
+    fn test_bot_status_valid_transitions() {
+        assert!(BotStatus::Active.check_transition(&BotStatus::Disabled, false).is_ok());
+        assert!(BotStatus::Disabled.check_transition(&BotStatus::Active, false).is_ok());
+        assert!(BotStatus::Disabled.check_transition(&BotStatus::Archived, false).is_ok());
+        assert!(BotStatus::Archived.check_transition(&BotStatus::Active, true).is_ok());
+    }
+
+    #[test]
+    fn test_bot_status_guarded_transition_requires_restore() {
+        let err = BotStatus::Archived
+            .check_transition(&BotStatus::Active, false)
+            .unwrap_err();
+        assert!(matches!(err, BotError::InvalidStatusTransition { .. }));
+        assert!(err.to_string().contains("from archived to active"));
+        assert!(err.to_string().contains("--restore"));
+    }
+
+    #[test]
+    fn test_bot_status_same_status_rejected() {
+        assert!(BotStatus::Active.check_transition(&BotStatus::Active, true).is_err());
     }
 
     #[test]
@@ -12,6 +12,8 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::schema::{BUILDER_STATE_SCHEMA_VERSION, Versioned};
+
 // ---------------------------------------------------------------------------
 // Builder phases
 // ---------------------------------------------------------------------------
@@ -165,6 +167,40 @@ pub struct BuilderState {
     pub conversation: Vec<BuilderExchange>,
     pub config: PartialBuilderConfig,
     pub phase_history: Vec<BuilderPhase>,
+    /// The turn currently shown to the user, awaiting an answer.
+    ///
+    /// Saved with drafts so a resumed session re-presents the same question
+    /// instead of asking the LLM to reconstruct it.
+    #[serde(default)]
+    pub pending_turn: Option<BuilderTurn>,
+    /// Shape version of the saved JSON; see [`crate::schema`].
+    #[serde(default = "current_state_schema")]
+    pub schema_version: u32,
+}
+
+fn current_state_schema() -> u32 {
+    BUILDER_STATE_SCHEMA_VERSION
+}
+
+impl Versioned for BuilderState {
+    const KIND: &'static str = "builder state";
+    const SCHEMA_VERSION: u32 = BUILDER_STATE_SCHEMA_VERSION;
+
+    fn migrate(from: u32, value: &mut serde_json::Value) -> Result<(), String> {
+        match from {
+            // Version 1 drafts may predate `pending_turn`; version 2 also
+            // introduced the `schema_version` tag itself.
+            1 => {
+                if let Some(state) = value.as_object_mut() {
+                    state
+                        .entry("pending_turn")
+                        .or_insert(serde_json::Value::Null);
+                }
+                Ok(())
+            }
+            _ => Err(format!("no migration defined from version {from}")),
+        }
+    }
 }
 
 /// A single question-answer exchange in the builder conversation.
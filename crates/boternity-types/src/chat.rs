@@ -67,9 +67,18 @@ pub struct ChatSession {
     pub ended_at: Option<DateTime<Utc>>,
     pub total_input_tokens: u32,
     pub total_output_tokens: u32,
+    /// Estimated spend for the session in USD (approximate).
+    #[serde(default)]
+    pub total_cost_usd: f64,
     pub message_count: u32,
     pub model: String,
     pub status: SessionStatus,
+    /// Rolling summary of the turns condensed out of the context window.
+    #[serde(default)]
+    pub summary: Option<String>,
+    /// Number of leading messages `summary` stands in for when resuming.
+    #[serde(default)]
+    pub summarized_messages: u32,
 }
 
 /// A single message within a chat session.
@@ -115,6 +124,19 @@ pub struct ContextSummary {
     pub created_at: DateTime<Utc>,
 }
 
+/// A full-text match in a session title or chat message.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatSearchHit {
+    pub session_id: Uuid,
+    pub bot_id: Uuid,
+    /// The matching message, or `None` when the session title matched.
+    pub message_id: Option<Uuid>,
+    /// Snippet around the match (or the full title).
+    pub snippet: String,
+    /// BM25 rank from FTS5; more negative is a better match.
+    pub rank: f64,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -163,9 +185,12 @@ mod tests {
             ended_at: None,
             total_input_tokens: 100,
             total_output_tokens: 200,
+            total_cost_usd: 0.0,
             message_count: 5,
             model: "claude-sonnet-4-20250514".to_string(),
             status: SessionStatus::Active,
+            summary: None,
+            summarized_messages: 0,
         };
         let json = serde_json::to_string(&session).unwrap();
         assert!(json.contains("\"status\":\"active\""));
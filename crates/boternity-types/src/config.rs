@@ -1,10 +1,16 @@
 //! Global configuration types for Boternity.
 //!
 //! `GlobalConfig` represents the top-level `config.toml` that controls
-//! request budgets, provider pricing, and other global settings.
+//! request budgets, provider pricing, database pool sizing, the embedding
+//! model, vector index tuning, the integrity hash algorithm, secret provider
+//! precedence, per-bot LLM quotas, and other global settings.
+
+use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
+use crate::secret::SecretProvider;
+
 /// Top-level configuration for the Boternity platform.
 ///
 /// Loaded from `~/.boternity/config.toml`. All fields have sensible defaults.
@@ -17,21 +23,260 @@ pub struct GlobalConfig {
     /// Pricing information for cost estimation per provider/model.
     #[serde(default)]
     pub provider_pricing: Vec<ProviderPricing>,
+
+    /// SQLite connection pool sizing (`[database]` table).
+    #[serde(default)]
+    pub database: DatabaseConfig,
+
+    /// Which embedding model backs semantic memory and file search.
+    #[serde(default)]
+    pub embedder: EmbedderKind,
+
+    /// Pick the memory recall cutoff from each search's similarity
+    /// distribution instead of a fixed threshold.
+    #[serde(default)]
+    pub adaptive_recall: bool,
+
+    /// ANN index tuning for vector tables (`[vector_index]` table).
+    #[serde(default)]
+    pub vector_index: VectorIndexConfig,
+
+    /// Encrypt memory facts in the vector store with the vault key.
+    /// Embeddings stay unencrypted so semantic search keeps working.
+    #[serde(default)]
+    pub encrypt_memories: bool,
+
+    /// Hash algorithm for new soul and shared-memory integrity hashes.
+    /// Existing hashes keep verifying with the algorithm they were made with.
+    #[serde(default)]
+    pub integrity_hash: HashAlgorithm,
+
+    /// Secret provider precedence, highest first (e.g.
+    /// `["vault", "environment"]` lets stored secrets override env vars).
+    /// Unlisted providers are not consulted, except the vault.
+    #[serde(default = "default_secret_providers")]
+    pub secret_providers: Vec<SecretProvider>,
+
+    /// Per-bot LLM request and token quotas (`[quota]` table).
+    #[serde(default)]
+    pub quota: QuotaConfig,
 }
 
 fn default_request_budget() -> u32 {
     500_000
 }
 
+fn default_secret_providers() -> Vec<SecretProvider> {
+    SecretProvider::DEFAULT_ORDER.to_vec()
+}
+
 impl Default for GlobalConfig {
     fn default() -> Self {
         Self {
             default_request_budget: default_request_budget(),
             provider_pricing: Vec::new(),
+            database: DatabaseConfig::default(),
+            embedder: EmbedderKind::default(),
+            adaptive_recall: false,
+            vector_index: VectorIndexConfig::default(),
+            encrypt_memories: false,
+            integrity_hash: HashAlgorithm::default(),
+            secret_providers: default_secret_providers(),
+            quota: QuotaConfig::default(),
+        }
+    }
+}
+
+/// Embedding model selection (`embedder = "..."` in `config.toml`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmbedderKind {
+    /// Local BGE-small model via fastembed (downloaded on first use).
+    #[default]
+    Fastembed,
+    /// Hash-based pseudo-embeddings: no model download, stable across runs,
+    /// but with no semantic similarity. For tests and offline use.
+    Deterministic,
+}
+
+/// Integrity hash algorithm (`integrity_hash = "..."` in `config.toml`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HashAlgorithm {
+    /// SHA-256 (the original algorithm; hashes are stored as bare hex).
+    #[default]
+    Sha256,
+    /// BLAKE3: much faster on large content.
+    Blake3,
+}
+
+impl HashAlgorithm {
+    /// Name used in config and as the prefix of recorded hashes.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Sha256 => "sha256",
+            Self::Blake3 => "blake3",
+        }
+    }
+}
+
+/// SQLite connection pool settings.
+///
+/// The writer pool always has a single connection (SQLite allows one writer
+/// at a time); only the reader pool is sized.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DatabaseConfig {
+    /// Maximum concurrent reader connections.
+    #[serde(default = "default_max_read_connections")]
+    pub max_read_connections: u32,
+
+    /// How long a query waits for a free pool connection before failing.
+    #[serde(default = "default_acquire_timeout_ms")]
+    pub acquire_timeout_ms: u64,
+
+    /// How long a connection waits on a locked database before failing.
+    #[serde(default = "default_busy_timeout_ms")]
+    pub busy_timeout_ms: u64,
+}
+
+fn default_max_read_connections() -> u32 {
+    8
+}
+
+fn default_acquire_timeout_ms() -> u64 {
+    30_000
+}
+
+fn default_busy_timeout_ms() -> u64 {
+    5_000
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        Self {
+            max_read_connections: default_max_read_connections(),
+            acquire_timeout_ms: default_acquire_timeout_ms(),
+            busy_timeout_ms: default_busy_timeout_ms(),
+        }
+    }
+}
+
+/// ANN (IVF_PQ) index settings for LanceDB vector tables.
+///
+/// Tables below `min_rows` have no index and are searched exhaustively,
+/// which is exact and fast enough at that size.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VectorIndexConfig {
+    /// Rows a table must hold before an index is built.
+    #[serde(default = "default_index_min_rows")]
+    pub min_rows: usize,
+
+    /// IVF partitions. Unset lets LanceDB choose (about the square root of
+    /// the row count).
+    #[serde(default)]
+    pub num_partitions: Option<u32>,
+
+    /// PQ sub-vectors; must divide the embedding dimension (384).
+    #[serde(default = "default_num_sub_vectors")]
+    pub num_sub_vectors: u32,
+
+    /// Partitions probed per search. Higher is more accurate and slower.
+    #[serde(default = "default_nprobes")]
+    pub nprobes: usize,
+
+    /// Re-rank `limit * refine_factor` candidates by exact distance.
+    #[serde(default = "default_refine_factor")]
+    pub refine_factor: u32,
+
+    /// Compact all vector tables this often while `bnity serve` runs.
+    /// Unset disables scheduled compaction (`bnity memories compact` still
+    /// works).
+    #[serde(default)]
+    pub compact_interval_hours: Option<u64>,
+}
+
+fn default_index_min_rows() -> usize {
+    10_000
+}
+
+fn default_num_sub_vectors() -> u32 {
+    48
+}
+
+fn default_nprobes() -> usize {
+    20
+}
+
+fn default_refine_factor() -> u32 {
+    10
+}
+
+impl Default for VectorIndexConfig {
+    fn default() -> Self {
+        Self {
+            min_rows: default_index_min_rows(),
+            num_partitions: None,
+            num_sub_vectors: default_num_sub_vectors(),
+            nprobes: default_nprobes(),
+            refine_factor: default_refine_factor(),
+            compact_interval_hours: None,
         }
     }
 }
 
+/// Per-bot LLM usage quotas.
+///
+/// Usage is counted in fixed windows of `window_hours` aligned to the Unix
+/// epoch (the default 24 is a UTC day). The top-level limits apply to every
+/// bot; an entry under `[quota.bots.<slug>]` replaces them for that bot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuotaConfig {
+    /// Length of a quota window.
+    #[serde(default = "default_quota_window_hours")]
+    pub window_hours: u32,
+
+    /// Limits for bots without their own entry.
+    #[serde(flatten)]
+    pub default: UsageLimits,
+
+    /// Per-bot limits, keyed by bot slug.
+    #[serde(default)]
+    pub bots: HashMap<String, UsageLimits>,
+}
+
+fn default_quota_window_hours() -> u32 {
+    24
+}
+
+impl Default for QuotaConfig {
+    fn default() -> Self {
+        Self {
+            window_hours: default_quota_window_hours(),
+            default: UsageLimits::default(),
+            bots: HashMap::new(),
+        }
+    }
+}
+
+impl QuotaConfig {
+    /// The limits that apply to the bot with `slug`.
+    pub fn limits_for(&self, slug: &str) -> &UsageLimits {
+        self.bots.get(slug).unwrap_or(&self.default)
+    }
+}
+
+/// Caps on a bot's LLM usage per quota window. Unset means unlimited.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UsageLimits {
+    /// Completion requests per window.
+    #[serde(default)]
+    pub max_requests: Option<u64>,
+
+    /// Input plus output tokens per window.
+    #[serde(default)]
+    pub max_tokens: Option<u64>,
+}
+
 /// Cost information for a specific provider/model pattern.
 ///
 /// Used by the budget tracker to estimate spend and warn about cost.
@@ -91,9 +336,88 @@ output_cost_per_million = 10.0
         assert_eq!(config.provider_pricing[1].provider_name, "openai");
     }
 
+    #[test]
+    fn test_database_config_partial_table() {
+        let config: GlobalConfig = toml::from_str("[database]\nmax_read_connections = 16\n").unwrap();
+        assert_eq!(config.database.max_read_connections, 16);
+        assert_eq!(config.database.acquire_timeout_ms, 30_000);
+        assert_eq!(config.database.busy_timeout_ms, 5_000);
+        assert_eq!(GlobalConfig::default().database, DatabaseConfig::default());
+    }
+
+    #[test]
+    fn test_secret_provider_order() {
+        assert_eq!(
+            GlobalConfig::default().secret_providers,
+            SecretProvider::DEFAULT_ORDER.to_vec()
+        );
+        let config: GlobalConfig =
+            toml::from_str("secret_providers = [\"vault\", \"environment\"]\n").unwrap();
+        assert_eq!(
+            config.secret_providers,
+            vec![SecretProvider::Vault, SecretProvider::Environment]
+        );
+    }
+
+    #[test]
+    fn test_quota_config() {
+        let config = GlobalConfig::default();
+        assert_eq!(config.quota.window_hours, 24);
+        assert_eq!(config.quota.limits_for("any"), &UsageLimits::default());
+
+        let toml_str = r#"
+[quota]
+max_tokens = 100000
+
+[quota.bots.heavy]
+max_tokens = 500000
+max_requests = 200
+"#;
+        let config: GlobalConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.quota.limits_for("light").max_tokens, Some(100_000));
+        assert_eq!(config.quota.limits_for("light").max_requests, None);
+        assert_eq!(config.quota.limits_for("heavy").max_tokens, Some(500_000));
+        assert_eq!(config.quota.limits_for("heavy").max_requests, Some(200));
+    }
+
+    #[test]
+    fn test_embedder_kind() {
+        assert_eq!(GlobalConfig::default().embedder, EmbedderKind::Fastembed);
+        let config: GlobalConfig = toml::from_str("embedder = \"deterministic\"\n").unwrap();
+        assert_eq!(config.embedder, EmbedderKind::Deterministic);
+        assert!(toml::from_str::<GlobalConfig>("embedder = \"openai\"\n").is_err());
+    }
+
+    #[test]
+    fn test_integrity_hash_algorithm() {
+        assert_eq!(GlobalConfig::default().integrity_hash, HashAlgorithm::Sha256);
+        let config: GlobalConfig = toml::from_str("integrity_hash = \"blake3\"\n").unwrap();
+        assert_eq!(config.integrity_hash, HashAlgorithm::Blake3);
+        assert!(toml::from_str::<GlobalConfig>("integrity_hash = \"md5\"\n").is_err());
+    }
+
+    #[test]
+    fn test_vector_index_config_partial_table() {
+        let config: GlobalConfig =
+            toml::from_str("[vector_index]\nmin_rows = 500\nnum_partitions = 4\n").unwrap();
+        assert_eq!(config.vector_index.min_rows, 500);
+        assert_eq!(config.vector_index.num_partitions, Some(4));
+        assert_eq!(config.vector_index.num_sub_vectors, 48);
+        assert_eq!(config.vector_index.nprobes, 20);
+        assert_eq!(GlobalConfig::default().vector_index.min_rows, 10_000);
+    }
+
     #[test]
     fn test_global_config_serde_roundtrip() {
         let config = GlobalConfig {
+            database: DatabaseConfig::default(),
+            embedder: EmbedderKind::Deterministic,
+            adaptive_recall: true,
+            vector_index: VectorIndexConfig::default(),
+            encrypt_memories: true,
+            integrity_hash: HashAlgorithm::Blake3,
+            secret_providers: vec![SecretProvider::Vault, SecretProvider::Environment],
+            quota: QuotaConfig::default(),
             default_request_budget: 750_000,
             provider_pricing: vec![ProviderPricing {
                 provider_name: "anthropic".to_string(),
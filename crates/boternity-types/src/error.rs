@@ -1,5 +1,8 @@
+use chrono::{DateTime, Utc};
 use thiserror::Error;
 
+use crate::quota::QuotaKind;
+
 /// Errors related to bot operations.
 #[derive(Debug, Error)]
 pub enum BotError {
@@ -12,6 +15,13 @@ pub enum BotError {
     #[error("invalid bot status: '{0}'")]
     InvalidStatus(String),
 
+    #[error("cannot change bot status from {from} to {to}: {reason}")]
+    InvalidStatusTransition {
+        from: String,
+        to: String,
+        reason: String,
+    },
+
     #[error("invalid bot name: {0}")]
     InvalidName(String),
 
@@ -79,6 +89,21 @@ pub enum RepositoryError {
     Conflict(String),
 }
 
+/// Errors from per-bot LLM usage quotas.
+#[derive(Debug, Error)]
+pub enum QuotaError {
+    #[error("{kind} quota exceeded: {used} of {limit} used this window (resets at {resets_at})")]
+    Exceeded {
+        kind: QuotaKind,
+        used: u64,
+        limit: u64,
+        resets_at: DateTime<Utc>,
+    },
+
+    #[error("storage error: {0}")]
+    Storage(#[from] RepositoryError),
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -181,6 +181,17 @@ impl AgentEvent {
             | AgentEvent::WorkflowRunPaused { .. } => None,
         }
     }
+
+    /// Returns the request_id from request-scoped variants, or None.
+    pub fn request_id(&self) -> Option<Uuid> {
+        match self {
+            AgentEvent::BudgetUpdate { request_id, .. }
+            | AgentEvent::BudgetWarning { request_id, .. }
+            | AgentEvent::BudgetExhausted { request_id, .. }
+            | AgentEvent::SynthesisStarted { request_id } => Some(*request_id),
+            _ => None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 use crate::bot::{BotCategory, BotId};
+use crate::llm::ProviderPreference;
 
 /// Bot identity configuration (stored in IDENTITY.md).
 ///
@@ -22,6 +23,9 @@ pub struct Identity {
     pub model: String,
     /// LLM provider name.
     pub provider: String,
+    /// Optional override of the global provider fallback chain for this bot.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider_preference: Option<ProviderPreference>,
     /// Sampling temperature for LLM responses.
     pub temperature: f64,
     /// Maximum tokens per LLM response.
@@ -52,6 +56,7 @@ impl Identity {
             emoji: None,
             model: Self::DEFAULT_MODEL.to_string(),
             provider: Self::DEFAULT_PROVIDER.to_string(),
+            provider_preference: None,
             temperature: Self::DEFAULT_TEMPERATURE,
             max_tokens: Self::DEFAULT_MAX_TOKENS,
             category: BotCategory::default(),
@@ -16,8 +16,12 @@ pub mod identity;
 pub mod llm;
 pub mod memory;
 pub mod message;
+pub mod quota;
+pub mod schema;
+pub mod search;
 pub mod secret;
 pub mod skill;
 pub mod soul;
 pub mod storage;
+pub mod user;
 pub mod workflow;
@@ -4,6 +4,7 @@
 //! completion requests, streaming events, usage tracking, and error handling.
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fmt;
 use std::str::FromStr;
 
@@ -14,6 +15,8 @@ pub enum MessageRole {
     System,
     User,
     Assistant,
+    /// Output of a tool the assistant called, sent back to the model.
+    Tool,
 }
 
 impl fmt::Display for MessageRole {
@@ -22,6 +25,7 @@ impl fmt::Display for MessageRole {
             MessageRole::System => write!(f, "system"),
             MessageRole::User => write!(f, "user"),
             MessageRole::Assistant => write!(f, "assistant"),
+            MessageRole::Tool => write!(f, "tool"),
         }
     }
 }
@@ -34,16 +38,121 @@ impl FromStr for MessageRole {
             "system" => Ok(MessageRole::System),
             "user" => Ok(MessageRole::User),
             "assistant" => Ok(MessageRole::Assistant),
+            "tool" => Ok(MessageRole::Tool),
             other => Err(format!("invalid message role: '{other}'")),
         }
     }
 }
 
 /// A single message in an LLM conversation.
+///
+/// Plain turns carry only text. Tool-use turns also carry [`ToolContent`]:
+/// an `Assistant` message lists the calls it made, and each `Tool` message
+/// answers one call with its output in `content`. `User` messages may attach
+/// images, sent after the text to providers with vision.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Message {
     pub role: MessageRole,
     pub content: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool: Option<ToolContent>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub images: Vec<ImageSource>,
+}
+
+impl From<String> for Message {
+    /// A plain-text user message.
+    fn from(content: String) -> Self {
+        Self {
+            role: MessageRole::User,
+            content,
+            tool: None,
+            images: Vec::new(),
+        }
+    }
+}
+
+impl Message {
+    /// An assistant turn that calls tools, with any text it produced first.
+    pub fn tool_calls(content: impl Into<String>, calls: Vec<ToolCall>) -> Self {
+        Self {
+            role: MessageRole::Assistant,
+            content: content.into(),
+            tool: Some(ToolContent::Calls { calls }),
+            images: Vec::new(),
+        }
+    }
+
+    /// The output of the tool call `call_id`.
+    pub fn tool_result(
+        call_id: impl Into<String>,
+        output: impl Into<String>,
+        is_error: bool,
+    ) -> Self {
+        Self {
+            role: MessageRole::Tool,
+            content: output.into(),
+            tool: Some(ToolContent::Result {
+                call_id: call_id.into(),
+                is_error,
+            }),
+            images: Vec::new(),
+        }
+    }
+
+    /// Attach an image to this message.
+    pub fn with_image(mut self, image: ImageSource) -> Self {
+        self.images.push(image);
+        self
+    }
+}
+
+/// An image attached to a [`Message`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ImageSource {
+    /// An image the provider fetches itself.
+    Url { url: String },
+    /// Inline image bytes, base64-encoded.
+    Base64 {
+        /// MIME type, e.g. `image/png`.
+        media_type: String,
+        data: String,
+    },
+}
+
+impl ImageSource {
+    /// The image as a URL, with inline data as a `data:` URL.
+    pub fn to_url(&self) -> String {
+        match self {
+            Self::Url { url } => url.clone(),
+            Self::Base64 { media_type, data } => format!("data:{media_type};base64,{data}"),
+        }
+    }
+}
+
+/// Tool-use data attached to a [`Message`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ToolContent {
+    /// Calls requested by an assistant message.
+    Calls { calls: Vec<ToolCall> },
+    /// Identifies the call a tool message answers.
+    Result {
+        call_id: String,
+        /// The tool failed; `content` holds the error.
+        #[serde(default)]
+        is_error: bool,
+    },
+}
+
+/// One tool invocation requested by the model.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolCall {
+    /// Provider-assigned id, echoed back in the matching tool result.
+    pub id: String,
+    pub name: String,
+    pub input: serde_json::Value,
 }
 
 /// Request to an LLM provider for a completion.
@@ -56,6 +165,15 @@ pub struct CompletionRequest {
     pub max_tokens: u32,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub temperature: Option<f64>,
+    /// Nucleus sampling cutoff (0.0..=1.0).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f64>,
+    /// Penalty for repeated tokens, proportional to frequency (-2.0..=2.0).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frequency_penalty: Option<f64>,
+    /// Penalty for tokens already present in the output (-2.0..=2.0).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub presence_penalty: Option<f64>,
     #[serde(default)]
     pub stream: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -66,6 +184,264 @@ pub struct CompletionRequest {
     pub output_config: Option<OutputConfig>,
 }
 
+/// Builds a [`CompletionRequest`], validating it on [`build`](Self::build).
+///
+/// Unset fields take their defaults: no system prompt, no sampling overrides,
+/// [`DEFAULT_MAX_TOKENS`](CompletionRequestBuilder::DEFAULT_MAX_TOKENS), and
+/// non-streaming.
+///
+/// ```
+/// use boternity_types::llm::{CompletionRequest, Message};
+///
+/// let request = CompletionRequest::builder("claude-sonnet-4-20250514")
+///     .system("Be concise.")
+///     .message(Message::from("Hello".to_string()))
+///     .max_tokens(256)
+///     .build()
+///     .unwrap();
+/// assert_eq!(request.messages.len(), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CompletionRequestBuilder {
+    request: CompletionRequest,
+}
+
+impl CompletionRequestBuilder {
+    /// `max_tokens` when none is set.
+    pub const DEFAULT_MAX_TOKENS: u32 = 4096;
+
+    /// Start a request for `model`.
+    pub fn new(model: impl Into<String>) -> Self {
+        Self {
+            request: CompletionRequest {
+                model: model.into(),
+                messages: Vec::new(),
+                system: None,
+                max_tokens: Self::DEFAULT_MAX_TOKENS,
+                temperature: None,
+                top_p: None,
+                frequency_penalty: None,
+                presence_penalty: None,
+                stream: false,
+                stop_sequences: None,
+                output_config: None,
+            },
+        }
+    }
+
+    /// Replace the conversation messages.
+    pub fn messages(mut self, messages: Vec<Message>) -> Self {
+        self.request.messages = messages;
+        self
+    }
+
+    /// Append one message.
+    pub fn message(mut self, message: Message) -> Self {
+        self.request.messages.push(message);
+        self
+    }
+
+    /// Set the system prompt.
+    pub fn system(mut self, system: impl Into<String>) -> Self {
+        self.request.system = Some(system.into());
+        self
+    }
+
+    /// Set the output token limit (must be greater than zero).
+    pub fn max_tokens(mut self, max_tokens: u32) -> Self {
+        self.request.max_tokens = max_tokens;
+        self
+    }
+
+    /// Set the sampling temperature (0.0..=2.0).
+    pub fn temperature(mut self, temperature: f64) -> Self {
+        self.request.temperature = Some(temperature);
+        self
+    }
+
+    /// Set the nucleus sampling cutoff.
+    pub fn top_p(mut self, top_p: f64) -> Self {
+        self.request.top_p = Some(top_p);
+        self
+    }
+
+    /// Set the frequency penalty.
+    pub fn frequency_penalty(mut self, penalty: f64) -> Self {
+        self.request.frequency_penalty = Some(penalty);
+        self
+    }
+
+    /// Set the presence penalty.
+    pub fn presence_penalty(mut self, penalty: f64) -> Self {
+        self.request.presence_penalty = Some(penalty);
+        self
+    }
+
+    /// Request a streamed response.
+    pub fn stream(mut self, stream: bool) -> Self {
+        self.request.stream = stream;
+        self
+    }
+
+    /// Set sequences that stop generation.
+    pub fn stop_sequences(mut self, stops: Vec<String>) -> Self {
+        self.request.stop_sequences = Some(stops);
+        self
+    }
+
+    /// Constrain the response to a JSON schema.
+    pub fn output_config(mut self, config: OutputConfig) -> Self {
+        self.request.output_config = Some(config);
+        self
+    }
+
+    /// Validate and return the request.
+    ///
+    /// Fails with [`LlmError::InvalidRequest`] when `max_tokens` is zero,
+    /// there are neither messages nor a system prompt, or the temperature is
+    /// outside [`CompletionRequest::TEMPERATURE_RANGE`].
+    pub fn build(self) -> Result<CompletionRequest, LlmError> {
+        let request = self.request;
+        if request.max_tokens == 0 {
+            return Err(LlmError::InvalidRequest(
+                "max_tokens must be greater than 0".to_string(),
+            ));
+        }
+        if request.messages.is_empty() && request.system.is_none() {
+            return Err(LlmError::InvalidRequest(
+                "a request needs at least one message or a system prompt".to_string(),
+            ));
+        }
+        if let Some(temperature) = request.temperature {
+            let (min, max) = CompletionRequest::TEMPERATURE_RANGE;
+            if !(min..=max).contains(&temperature) {
+                return Err(LlmError::InvalidRequest(format!(
+                    "temperature must be between {min} and {max}, got {temperature}"
+                )));
+            }
+        }
+        Ok(request)
+    }
+}
+
+/// How out-of-range sampling parameters are handled before a request is sent.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SamplingPolicy {
+    /// Reject the request with [`LlmError::InvalidRequest`].
+    #[default]
+    Reject,
+    /// Clamp each value into its valid range.
+    Clamp,
+}
+
+/// Sampling parameters from a [`CompletionRequest`], checked against their valid ranges.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SamplingParams {
+    pub temperature: Option<f64>,
+    pub top_p: Option<f64>,
+    pub frequency_penalty: Option<f64>,
+    pub presence_penalty: Option<f64>,
+}
+
+impl CompletionRequest {
+    /// Start building a request for `model`.
+    pub fn builder(model: impl Into<String>) -> CompletionRequestBuilder {
+        CompletionRequestBuilder::new(model)
+    }
+
+    /// Valid range for `temperature`.
+    pub const TEMPERATURE_RANGE: (f64, f64) = (0.0, 2.0);
+    /// Valid range for `top_p`.
+    pub const TOP_P_RANGE: (f64, f64) = (0.0, 1.0);
+    /// Valid range for `frequency_penalty` and `presence_penalty`.
+    pub const PENALTY_RANGE: (f64, f64) = (-2.0, 2.0);
+
+    /// Validate the sampling parameters, clamping or rejecting out-of-range values.
+    ///
+    /// Non-finite values (NaN, infinity) are always rejected.
+    pub fn sampling_params(&self, policy: SamplingPolicy) -> Result<SamplingParams, LlmError> {
+        fn check(
+            name: &str,
+            value: Option<f64>,
+            (min, max): (f64, f64),
+            policy: SamplingPolicy,
+        ) -> Result<Option<f64>, LlmError> {
+            let Some(v) = value else { return Ok(None) };
+            if !v.is_finite() {
+                return Err(LlmError::InvalidRequest(format!("{name} must be a finite number, got {v}")));
+            }
+            if (min..=max).contains(&v) {
+                return Ok(Some(v));
+            }
+            match policy {
+                SamplingPolicy::Reject => Err(LlmError::InvalidRequest(format!(
+                    "{name} must be between {min} and {max}, got {v}"
+                ))),
+                SamplingPolicy::Clamp => Ok(Some(v.clamp(min, max))),
+            }
+        }
+
+        Ok(SamplingParams {
+            temperature: check("temperature", self.temperature, Self::TEMPERATURE_RANGE, policy)?,
+            top_p: check("top_p", self.top_p, Self::TOP_P_RANGE, policy)?,
+            frequency_penalty: check(
+                "frequency_penalty",
+                self.frequency_penalty,
+                Self::PENALTY_RANGE,
+                policy,
+            )?,
+            presence_penalty: check(
+                "presence_penalty",
+                self.presence_penalty,
+                Self::PENALTY_RANGE,
+                policy,
+            )?,
+        })
+    }
+
+    /// Features the provider must support to serve this request.
+    ///
+    /// Streaming is not included: it depends on how the request is sent, not
+    /// on its contents.
+    pub fn required_features(&self) -> Vec<LlmFeature> {
+        let mut features = Vec::new();
+        if self.messages.iter().any(|m| !m.images.is_empty()) {
+            features.push(LlmFeature::Vision);
+        }
+        if self
+            .messages
+            .iter()
+            .any(|m| m.tool.is_some() || m.role == MessageRole::Tool)
+        {
+            features.push(LlmFeature::ToolCalling);
+        }
+        features
+    }
+
+    /// Check the request against what the provider supports.
+    ///
+    /// Fails with [`LlmError::Unsupported`] for the first required feature
+    /// the provider lacks, and with [`LlmError::InvalidRequest`] for images
+    /// attached to anything but a user message.
+    pub fn check_capabilities(&self, capabilities: &ProviderCapabilities) -> Result<(), LlmError> {
+        for feature in self.required_features() {
+            capabilities.require(feature)?;
+        }
+        if let Some(msg) = self
+            .messages
+            .iter()
+            .find(|m| !m.images.is_empty() && m.role != MessageRole::User)
+        {
+            return Err(LlmError::InvalidRequest(format!(
+                "images can only be attached to user messages, not {}",
+                msg.role
+            )));
+        }
+        Ok(())
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Structured output types
 // ---------------------------------------------------------------------------
@@ -243,6 +619,30 @@ pub enum LlmError {
 
     #[error("invalid request: {0}")]
     InvalidRequest(String),
+
+    #[error("provider does not support {feature}")]
+    Unsupported { feature: LlmFeature },
+}
+
+/// An optional provider feature a request can depend on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LlmFeature {
+    Streaming,
+    ToolCalling,
+    Vision,
+    ExtendedThinking,
+}
+
+impl fmt::Display for LlmFeature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LlmFeature::Streaming => write!(f, "streaming"),
+            LlmFeature::ToolCalling => write!(f, "tool calling"),
+            LlmFeature::Vision => write!(f, "vision"),
+            LlmFeature::ExtendedThinking => write!(f, "extended thinking"),
+        }
+    }
 }
 
 /// Capabilities of an LLM provider.
@@ -256,6 +656,27 @@ pub struct ProviderCapabilities {
     pub max_output_tokens: u32,
 }
 
+impl ProviderCapabilities {
+    /// Whether the provider supports `feature`.
+    pub fn supports(&self, feature: LlmFeature) -> bool {
+        match feature {
+            LlmFeature::Streaming => self.streaming,
+            LlmFeature::ToolCalling => self.tool_calling,
+            LlmFeature::Vision => self.vision,
+            LlmFeature::ExtendedThinking => self.extended_thinking,
+        }
+    }
+
+    /// Fail with [`LlmError::Unsupported`] unless the provider supports `feature`.
+    pub fn require(&self, feature: LlmFeature) -> Result<(), LlmError> {
+        if self.supports(feature) {
+            Ok(())
+        } else {
+            Err(LlmError::Unsupported { feature })
+        }
+    }
+}
+
 /// Type of LLM provider backend.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -265,6 +686,7 @@ pub enum ProviderType {
     #[serde(rename = "openai_compatible")]
     OpenAiCompatible,
     ClaudeSubscription,
+    Cohere,
 }
 
 impl fmt::Display for ProviderType {
@@ -274,6 +696,7 @@ impl fmt::Display for ProviderType {
             ProviderType::Bedrock => write!(f, "bedrock"),
             ProviderType::OpenAiCompatible => write!(f, "openai_compatible"),
             ProviderType::ClaudeSubscription => write!(f, "claude_subscription"),
+            ProviderType::Cohere => write!(f, "cohere"),
         }
     }
 }
@@ -287,6 +710,7 @@ impl FromStr for ProviderType {
             "bedrock" => Ok(ProviderType::Bedrock),
             "openai_compatible" => Ok(ProviderType::OpenAiCompatible),
             "claude_subscription" => Ok(ProviderType::ClaudeSubscription),
+            "cohere" => Ok(ProviderType::Cohere),
             other => Err(format!("invalid provider type: '{other}'")),
         }
     }
@@ -311,6 +735,26 @@ pub struct ProviderConfig {
     pub enabled: bool,
     /// What this provider supports.
     pub capabilities: ProviderCapabilities,
+    /// Extra HTTP headers sent with every request (e.g., `HTTP-Referer` and
+    /// `X-Title` for OpenRouter).
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub extra_headers: HashMap<String, String>,
+    /// Provider-specific JSON object merged into the outgoing request body.
+    #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
+    pub extra_body: serde_json::Value,
+}
+
+/// Per-bot override of the global provider fallback chain.
+///
+/// Stored in the bot's IDENTITY.md. Named providers lead the chain in the
+/// given order; with `exclusive`, all other providers are dropped.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProviderPreference {
+    /// Provider names (matching `ProviderConfig.name`) to put first, in order.
+    pub preferred: Vec<String>,
+    /// Restrict the chain to the preferred providers only.
+    #[serde(default)]
+    pub exclusive: bool,
 }
 
 /// Configuration for the multi-provider fallback chain.
@@ -358,13 +802,36 @@ pub struct ProviderStatusInfo {
     pub uptime_since: Option<String>,
 }
 
+/// Result of a provider connection test (`bnity provider test`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderTestReport {
+    /// Provider name as reported by the provider.
+    pub provider: String,
+    /// Milliseconds until the first streamed text token, if streaming worked.
+    pub first_token_ms: Option<u64>,
+    /// Milliseconds until the full response completed.
+    pub total_ms: u64,
+    /// Whether a streaming request completed successfully.
+    pub streaming_ok: bool,
+    /// Error from the streaming attempt when it failed and the test fell
+    /// back to a non-streaming request.
+    pub streaming_error: Option<String>,
+    /// The provider's advertised capabilities.
+    pub capabilities: ProviderCapabilities,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_message_role_roundtrip() {
-        for role in [MessageRole::System, MessageRole::User, MessageRole::Assistant] {
+        for role in [
+            MessageRole::System,
+            MessageRole::User,
+            MessageRole::Assistant,
+            MessageRole::Tool,
+        ] {
             let s = role.to_string();
             let parsed: MessageRole = s.parse().unwrap();
             assert_eq!(role, parsed);
@@ -404,6 +871,121 @@ mod tests {
         assert_eq!(parsed, MessageRole::Assistant);
     }
 
+    #[test]
+    fn test_tool_messages_serde() {
+        let plain = Message {
+            role: MessageRole::User,
+            content: "hi".to_string(),
+            tool: None,
+            images: Vec::new(),
+        };
+        let json = serde_json::to_value(&plain).unwrap();
+        assert!(json.get("tool").is_none());
+
+        let result = Message::tool_result("call_1", "42", false);
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["role"], "tool");
+        assert_eq!(json["tool"]["type"], "result");
+        assert_eq!(json["tool"]["call_id"], "call_1");
+        let parsed: Message = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.tool, result.tool);
+
+        let call = ToolCall {
+            id: "call_1".to_string(),
+            name: "calculator".to_string(),
+            input: serde_json::json!({"x": 1}),
+        };
+        let calls = Message::tool_calls("", vec![call.clone()]);
+        let json = serde_json::to_value(&calls).unwrap();
+        assert_eq!(json["tool"]["type"], "calls");
+        assert_eq!(json["tool"]["calls"][0]["name"], "calculator");
+        let parsed: Message = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.tool, Some(ToolContent::Calls { calls: vec![call] }));
+    }
+
+    #[test]
+    fn test_tool_turns_require_tool_calling() {
+        let request = CompletionRequest::builder("m")
+            .message(Message::from("What is 6 * 7?".to_string()))
+            .message(Message::tool_result("call_1", "42", false))
+            .build()
+            .unwrap();
+        assert_eq!(request.required_features(), vec![LlmFeature::ToolCalling]);
+
+        let mut capabilities = ProviderCapabilities {
+            streaming: true,
+            tool_calling: true,
+            vision: false,
+            extended_thinking: false,
+            max_context_tokens: 8_192,
+            max_output_tokens: 1_024,
+        };
+        assert!(request.check_capabilities(&capabilities).is_ok());
+        capabilities.tool_calling = false;
+        assert!(matches!(
+            request.check_capabilities(&capabilities),
+            Err(LlmError::Unsupported {
+                feature: LlmFeature::ToolCalling
+            })
+        ));
+        assert!(capabilities.require(LlmFeature::Streaming).is_ok());
+    }
+
+    #[test]
+    fn test_image_attachments() {
+        let msg = Message::from("What is this?".to_string()).with_image(ImageSource::Base64 {
+            media_type: "image/png".to_string(),
+            data: "iVBORw0KGgo=".to_string(),
+        });
+        assert_eq!(msg.role, MessageRole::User);
+        assert_eq!(msg.images[0].to_url(), "data:image/png;base64,iVBORw0KGgo=");
+        let json = serde_json::to_value(&msg).unwrap();
+        assert_eq!(json["images"][0]["type"], "base64");
+        let parsed: Message = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.images, msg.images);
+
+        let mut capabilities = ProviderCapabilities {
+            streaming: true,
+            tool_calling: false,
+            vision: true,
+            extended_thinking: false,
+            max_context_tokens: 8_192,
+            max_output_tokens: 1_024,
+        };
+        let mut request = CompletionRequest {
+            model: "text-only".to_string(),
+            messages: vec![msg],
+            system: None,
+            max_tokens: 100,
+            temperature: None,
+            top_p: None,
+            frequency_penalty: None,
+            presence_penalty: None,
+            stream: false,
+            stop_sequences: None,
+            output_config: None,
+        };
+        assert_eq!(request.required_features(), vec![LlmFeature::Vision]);
+        assert!(request.check_capabilities(&capabilities).is_ok());
+
+        capabilities.vision = false;
+        let err = request.check_capabilities(&capabilities).unwrap_err();
+        assert!(matches!(
+            err,
+            LlmError::Unsupported {
+                feature: LlmFeature::Vision
+            }
+        ));
+        assert_eq!(err.to_string(), "provider does not support vision");
+
+        capabilities.vision = true;
+        request.messages[0].role = MessageRole::Assistant;
+        assert!(matches!(
+            request.check_capabilities(&capabilities),
+            Err(LlmError::InvalidRequest(_))
+        ));
+    }
+
     #[test]
     fn test_stop_reason_serde() {
         let reason = StopReason::EndTurn;
@@ -430,6 +1012,7 @@ mod tests {
             ProviderType::Bedrock,
             ProviderType::OpenAiCompatible,
             ProviderType::ClaudeSubscription,
+            ProviderType::Cohere,
         ] {
             let s = pt.to_string();
             let parsed: ProviderType = s.parse().unwrap();
@@ -454,6 +1037,32 @@ mod tests {
         assert!((config.cost_warning_multiplier - 3.0).abs() < f64::EPSILON);
     }
 
+    #[test]
+    fn test_provider_config_extra_fields_default() {
+        let json = r#"{
+            "name": "openrouter",
+            "provider_type": "openai_compatible",
+            "api_key_secret_name": null,
+            "base_url": "https://openrouter.ai/api/v1",
+            "model": "openai/gpt-4o",
+            "priority": 0,
+            "enabled": true,
+            "capabilities": {
+                "streaming": true, "tool_calling": true, "vision": true,
+                "extended_thinking": false, "max_context_tokens": 128000,
+                "max_output_tokens": 16384
+            }
+        }"#;
+        let config: ProviderConfig = serde_json::from_str(json).unwrap();
+        assert!(config.extra_headers.is_empty());
+        assert!(config.extra_body.is_null());
+
+        // Empty extras are omitted when persisted
+        let value = serde_json::to_value(&config).unwrap();
+        assert!(value.get("extra_headers").is_none());
+        assert!(value.get("extra_body").is_none());
+    }
+
     #[test]
     fn test_output_config_serialization() {
         let config = OutputConfig {
@@ -480,6 +1089,9 @@ mod tests {
             system: None,
             max_tokens: 100,
             temperature: None,
+            top_p: None,
+            frequency_penalty: None,
+            presence_penalty: None,
             stream: false,
             stop_sequences: None,
             output_config: None,
@@ -488,4 +1100,131 @@ mod tests {
         // output_config should not appear when None (skip_serializing_if)
         assert!(json.get("output_config").is_none());
     }
+
+    fn sampling_request(
+        temperature: Option<f64>,
+        top_p: Option<f64>,
+        frequency_penalty: Option<f64>,
+    ) -> CompletionRequest {
+        CompletionRequest {
+            model: "test".to_string(),
+            messages: vec![],
+            system: None,
+            max_tokens: 100,
+            temperature,
+            top_p,
+            frequency_penalty,
+            presence_penalty: None,
+            stream: false,
+            stop_sequences: None,
+            output_config: None,
+        }
+    }
+
+    #[test]
+    fn test_sampling_params_valid() {
+        let request = sampling_request(Some(0.7), Some(0.9), Some(-1.5));
+        let params = request.sampling_params(SamplingPolicy::Reject).unwrap();
+        assert_eq!(params.temperature, Some(0.7));
+        assert_eq!(params.top_p, Some(0.9));
+        assert_eq!(params.frequency_penalty, Some(-1.5));
+        assert_eq!(params.presence_penalty, None);
+    }
+
+    #[test]
+    fn test_sampling_params_reject_out_of_range() {
+        let err = sampling_request(Some(5.0), None, None)
+            .sampling_params(SamplingPolicy::Reject)
+            .unwrap_err();
+        assert!(err.to_string().contains("temperature must be between 0 and 2, got 5"));
+
+        let err = sampling_request(None, Some(1.5), None)
+            .sampling_params(SamplingPolicy::Reject)
+            .unwrap_err();
+        assert!(err.to_string().contains("top_p"));
+    }
+
+    #[test]
+    fn test_sampling_params_clamp_out_of_range() {
+        let params = sampling_request(Some(5.0), Some(-0.1), Some(3.0))
+            .sampling_params(SamplingPolicy::Clamp)
+            .unwrap();
+        assert_eq!(params.temperature, Some(2.0));
+        assert_eq!(params.top_p, Some(0.0));
+        assert_eq!(params.frequency_penalty, Some(2.0));
+    }
+
+    #[test]
+    fn test_sampling_params_rejects_nan_even_when_clamping() {
+        let result = sampling_request(Some(f64::NAN), None, None).sampling_params(SamplingPolicy::Clamp);
+        assert!(matches!(result, Err(LlmError::InvalidRequest(_))));
+    }
+
+    #[test]
+    fn test_builder_defaults() {
+        let request = CompletionRequest::builder("test-model")
+            .message(Message::from("Hello".to_string()))
+            .build()
+            .unwrap();
+        assert_eq!(request.model, "test-model");
+        assert_eq!(request.max_tokens, CompletionRequestBuilder::DEFAULT_MAX_TOKENS);
+        assert_eq!(request.messages.len(), 1);
+        assert_eq!(request.messages[0].role, MessageRole::User);
+        assert!(request.system.is_none());
+        assert!(request.temperature.is_none());
+        assert!(!request.stream);
+        assert!(request.output_config.is_none());
+    }
+
+    #[test]
+    fn test_builder_sets_every_field() {
+        let request = CompletionRequest::builder("test-model")
+            .system("Be brief.")
+            .messages(vec![Message::from("Hi".to_string())])
+            .message(Message::from("Again".to_string()))
+            .max_tokens(128)
+            .temperature(0.3)
+            .top_p(0.9)
+            .frequency_penalty(0.5)
+            .presence_penalty(-0.5)
+            .stream(true)
+            .stop_sequences(vec!["END".to_string()])
+            .build()
+            .unwrap();
+        assert_eq!(request.system.as_deref(), Some("Be brief."));
+        assert_eq!(request.messages.len(), 2);
+        assert_eq!(request.max_tokens, 128);
+        assert_eq!(request.temperature, Some(0.3));
+        assert_eq!(request.top_p, Some(0.9));
+        assert_eq!(request.frequency_penalty, Some(0.5));
+        assert_eq!(request.presence_penalty, Some(-0.5));
+        assert!(request.stream);
+        assert_eq!(request.stop_sequences, Some(vec!["END".to_string()]));
+
+        // A system prompt alone is a valid request
+        assert!(CompletionRequest::builder("m").system("s").build().is_ok());
+    }
+
+    #[test]
+    fn test_builder_rejects_invalid_requests() {
+        let hello = || Message::from("Hello".to_string());
+
+        let err = CompletionRequest::builder("m").build().unwrap_err();
+        assert!(err.to_string().contains("at least one message"));
+
+        let err = CompletionRequest::builder("m")
+            .message(hello())
+            .max_tokens(0)
+            .build()
+            .unwrap_err();
+        assert!(err.to_string().contains("max_tokens"));
+
+        for temperature in [-0.1, 2.5, f64::NAN] {
+            let result = CompletionRequest::builder("m")
+                .message(hello())
+                .temperature(temperature)
+                .build();
+            assert!(matches!(result, Err(LlmError::InvalidRequest(_))));
+        }
+    }
 }
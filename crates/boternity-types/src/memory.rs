@@ -38,18 +38,37 @@ impl fmt::Display for MemoryCategory {
 impl FromStr for MemoryCategory {
     type Err = String;
 
+    /// Parse the exact [`Display`](fmt::Display) form; anything else is an error.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_lowercase().as_str() {
+        match s {
             "preference" => Ok(MemoryCategory::Preference),
             "fact" => Ok(MemoryCategory::Fact),
             "decision" => Ok(MemoryCategory::Decision),
             "context" => Ok(MemoryCategory::Context),
             "correction" => Ok(MemoryCategory::Correction),
-            other => Err(format!("invalid memory category: '{other}'")),
+            other => Err(format!(
+                "invalid memory category: '{other}' (expected preference, fact, decision, context, or correction)"
+            )),
         }
     }
 }
 
+impl MemoryCategory {
+    /// Every category, in declaration order.
+    pub const ALL: [MemoryCategory; 5] = [
+        MemoryCategory::Preference,
+        MemoryCategory::Fact,
+        MemoryCategory::Decision,
+        MemoryCategory::Context,
+        MemoryCategory::Correction,
+    ];
+
+    /// Parse user input, ignoring case and surrounding whitespace.
+    pub fn parse_lenient(s: &str) -> Result<Self, String> {
+        s.trim().to_lowercase().parse()
+    }
+}
+
 /// A single memory entry extracted from a conversation.
 ///
 /// Memories are bot-scoped and session-linked. They can be superseded
@@ -124,16 +143,29 @@ impl fmt::Display for TrustLevel {
 impl FromStr for TrustLevel {
     type Err = String;
 
+    /// Parse the exact [`Display`](fmt::Display) form; anything else is an error.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_lowercase().as_str() {
+        match s {
             "public" => Ok(TrustLevel::Public),
             "trusted" => Ok(TrustLevel::Trusted),
             "private" => Ok(TrustLevel::Private),
-            other => Err(format!("invalid trust level: '{other}'")),
+            other => Err(format!(
+                "invalid trust level: '{other}' (expected public, trusted, or private)"
+            )),
         }
     }
 }
 
+impl TrustLevel {
+    /// Every trust level, in declaration order.
+    pub const ALL: [TrustLevel; 3] = [TrustLevel::Public, TrustLevel::Trusted, TrustLevel::Private];
+
+    /// Parse user input, ignoring case and surrounding whitespace.
+    pub fn parse_lenient(s: &str) -> Result<Self, String> {
+        s.trim().to_lowercase().parse()
+    }
+}
+
 /// A memory entry stored in the vector database for semantic search.
 ///
 /// Linked back to the SQLite `MemoryEntry` via `source_memory_id`.
@@ -250,17 +282,25 @@ mod tests {
 
     #[test]
     fn test_memory_category_roundtrip() {
-        for cat in [
-            MemoryCategory::Preference,
-            MemoryCategory::Fact,
-            MemoryCategory::Decision,
-            MemoryCategory::Context,
-            MemoryCategory::Correction,
-        ] {
+        for cat in MemoryCategory::ALL {
             let s = cat.to_string();
             let parsed: MemoryCategory = s.parse().unwrap();
             assert_eq!(cat, parsed);
+            // Display agrees with the serde spelling
+            assert_eq!(serde_json::to_value(&cat).unwrap(), s.as_str());
+        }
+    }
+
+    #[test]
+    fn test_memory_category_parse_is_strict_unless_lenient() {
+        for bad in ["urgent", "", "facts", "Fact", " fact"] {
+            assert!(bad.parse::<MemoryCategory>().is_err(), "{bad:?} should not parse");
         }
+        assert_eq!(
+            MemoryCategory::parse_lenient(" Fact "),
+            Ok(MemoryCategory::Fact)
+        );
+        assert!(MemoryCategory::parse_lenient("urgent").is_err());
     }
 
     #[test]
@@ -294,11 +334,16 @@ mod tests {
 
     #[test]
     fn test_trust_level_roundtrip() {
-        for tl in [TrustLevel::Public, TrustLevel::Trusted, TrustLevel::Private] {
+        for tl in TrustLevel::ALL {
             let s = tl.to_string();
             let parsed: TrustLevel = s.parse().unwrap();
             assert_eq!(tl, parsed);
+            assert_eq!(serde_json::to_value(&tl).unwrap(), s.as_str());
+        }
+        for bad in ["secret", "", "PUBLIC"] {
+            assert!(bad.parse::<TrustLevel>().is_err(), "{bad:?} should not parse");
         }
+        assert_eq!(TrustLevel::parse_lenient("PUBLIC"), Ok(TrustLevel::Public));
     }
 
     #[test]
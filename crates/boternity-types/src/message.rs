@@ -7,6 +7,9 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use std::fmt;
+use std::str::FromStr;
+
 /// A message sent between bots.
 ///
 /// The `BotMessage` envelope supports both direct messaging (1:1) and pub/sub
@@ -31,6 +34,66 @@ pub struct BotMessage {
     /// Optional reference to a previous message for conversation threading.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub reply_to: Option<Uuid>,
+    /// Delivery priority; higher-priority messages jump ahead in mailboxes.
+    #[serde(default)]
+    pub priority: MessagePriority,
+    /// When the message goes stale; expired messages are dead-lettered
+    /// instead of delivered. `None` never expires.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl BotMessage {
+    /// Whether the message has expired as of `now`.
+    pub fn is_expired_at(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| now >= expires_at)
+    }
+}
+
+/// Delivery priority of a bot-to-bot message.
+///
+/// Mailboxes deliver higher priorities first and keep FIFO order within a
+/// priority. Variants are ordered from lowest to highest.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MessagePriority {
+    /// Bulk traffic that can wait.
+    Low,
+    /// Ordinary messages.
+    #[default]
+    Normal,
+    /// Time-sensitive messages.
+    High,
+    /// Control messages that must be handled before anything else.
+    Urgent,
+}
+
+impl fmt::Display for MessagePriority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MessagePriority::Low => write!(f, "low"),
+            MessagePriority::Normal => write!(f, "normal"),
+            MessagePriority::High => write!(f, "high"),
+            MessagePriority::Urgent => write!(f, "urgent"),
+        }
+    }
+}
+
+impl FromStr for MessagePriority {
+    type Err = String;
+
+    /// Parse the exact [`Display`](fmt::Display) form; anything else is an error.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "low" => Ok(MessagePriority::Low),
+            "normal" => Ok(MessagePriority::Normal),
+            "high" => Ok(MessagePriority::High),
+            "urgent" => Ok(MessagePriority::Urgent),
+            other => Err(format!(
+                "invalid message priority: '{other}' (expected low, normal, high, or urgent)"
+            )),
+        }
+    }
 }
 
 /// The recipient of a bot-to-bot message.
@@ -87,6 +150,8 @@ mod tests {
             body: json!({"text": "What is the latest on AI?", "urgency": "high"}),
             timestamp: Utc::now(),
             reply_to: None,
+            priority: MessagePriority::default(),
+            expires_at: None,
         };
         let json_str = serde_json::to_string(&msg).unwrap();
 
@@ -117,6 +182,8 @@ mod tests {
             body: json!({"headline": "New discovery"}),
             timestamp: Utc::now(),
             reply_to: Some(Uuid::now_v7()),
+            priority: MessagePriority::default(),
+            expires_at: None,
         };
         let json_str = serde_json::to_string(&msg).unwrap();
 
@@ -179,6 +246,61 @@ mod tests {
         assert_eq!(parsed.channel_name, "news-feed");
     }
 
+    #[test]
+    fn test_message_priority_order_and_default() {
+        assert!(MessagePriority::Urgent > MessagePriority::High);
+        assert!(MessagePriority::High > MessagePriority::Normal);
+        assert!(MessagePriority::Normal > MessagePriority::Low);
+
+        for priority in [
+            MessagePriority::Low,
+            MessagePriority::Normal,
+            MessagePriority::High,
+            MessagePriority::Urgent,
+        ] {
+            assert_eq!(priority.to_string().parse(), Ok(priority));
+        }
+        assert!("High".parse::<MessagePriority>().is_err());
+
+        // Messages stored before priorities existed deserialize as normal
+        let json = json!({
+            "id": Uuid::now_v7(),
+            "sender_bot_id": Uuid::now_v7(),
+            "sender_bot_name": "legacy",
+            "recipient": {"type": "channel", "name": "alerts"},
+            "message_type": "status_update",
+            "body": {},
+            "timestamp": Utc::now(),
+        });
+        let parsed: BotMessage = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.priority, MessagePriority::Normal);
+    }
+
+    #[test]
+    fn test_bot_message_expiry() {
+        let now = Utc::now();
+        let mut msg: BotMessage = serde_json::from_value(json!({
+            "id": Uuid::now_v7(),
+            "sender_bot_id": Uuid::now_v7(),
+            "sender_bot_name": "reminder",
+            "recipient": {"type": "direct", "bot_id": Uuid::now_v7()},
+            "message_type": "reminder",
+            "body": {},
+            "timestamp": now,
+        }))
+        .unwrap();
+        assert!(msg.expires_at.is_none());
+        assert!(!msg.is_expired_at(now + chrono::Duration::days(365)));
+
+        msg.expires_at = Some(now + chrono::Duration::minutes(5));
+        assert!(!msg.is_expired_at(now));
+        assert!(msg.is_expired_at(now + chrono::Duration::minutes(5)));
+
+        let json_str = serde_json::to_string(&msg).unwrap();
+        let parsed: BotMessage = serde_json::from_str(&json_str).unwrap();
+        assert_eq!(parsed.expires_at, msg.expires_at);
+    }
+
     #[test]
     fn test_bot_message_delegation_pattern() {
         // Verify the delegation use case from CONTEXT.md
@@ -197,6 +319,8 @@ mod tests {
             }),
             timestamp: Utc::now(),
             reply_to: None,
+            priority: MessagePriority::default(),
+            expires_at: None,
         };
         let json_str = serde_json::to_string(&msg).unwrap();
         let parsed: BotMessage = serde_json::from_str(&json_str).unwrap();
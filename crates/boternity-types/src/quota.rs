@@ -0,0 +1,40 @@
+//! Per-bot LLM usage accounting types.
+//!
+//! Usage is counted per bot in fixed quota windows (see `QuotaConfig` in
+//! `config`) and checked against the bot's `UsageLimits` before each
+//! completion request.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use std::fmt;
+
+/// LLM usage recorded for one bot in one quota window.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BotUsage {
+    pub bot_id: Uuid,
+    /// Start of the window this usage belongs to.
+    pub window_start: DateTime<Utc>,
+    /// Completion requests dispatched.
+    pub requests: u64,
+    /// Input plus output tokens consumed.
+    pub tokens: u64,
+}
+
+/// The resource a quota caps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QuotaKind {
+    Requests,
+    Tokens,
+}
+
+impl fmt::Display for QuotaKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QuotaKind::Requests => write!(f, "request"),
+            QuotaKind::Tokens => write!(f, "token"),
+        }
+    }
+}
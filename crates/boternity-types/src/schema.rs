@@ -0,0 +1,134 @@
+//! Schema versioning for persisted JSON.
+//!
+//! Types stored as JSON blobs carry a `schema_version` field. Loading goes
+//! through [`from_versioned_str`], which reads the version, runs the type's
+//! [`Versioned::migrate`] steps one version at a time up to the current one,
+//! and only then deserializes. Payloads written before versioning existed
+//! have no `schema_version` and are treated as version 1.
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use thiserror::Error;
+
+/// Name of the version field in persisted payloads.
+pub const SCHEMA_VERSION_FIELD: &str = "schema_version";
+
+/// Version assumed for payloads without a `schema_version` field.
+pub const UNVERSIONED_SCHEMA_VERSION: u32 = 1;
+
+/// Current schema version of [`crate::workflow::WorkflowDefinition`].
+pub const WORKFLOW_DEFINITION_SCHEMA_VERSION: u32 = 2;
+
+/// Current schema version of [`crate::builder::BuilderState`].
+pub const BUILDER_STATE_SCHEMA_VERSION: u32 = 2;
+
+/// Errors loading a versioned payload.
+#[derive(Debug, Error)]
+pub enum SchemaError {
+    #[error(
+        "{kind} has schema version {found}, but this build only understands up to {supported}; upgrade boternity to load it"
+    )]
+    UnsupportedVersion {
+        kind: &'static str,
+        found: u32,
+        supported: u32,
+    },
+
+    #[error("invalid {kind} schema version: {value}")]
+    InvalidVersion { kind: &'static str, value: String },
+
+    #[error("failed to migrate {kind} from schema version {from}: {reason}")]
+    Migration {
+        kind: &'static str,
+        from: u32,
+        reason: String,
+    },
+
+    #[error("invalid {kind} JSON: {source}")]
+    Json {
+        kind: &'static str,
+        #[source]
+        source: serde_json::Error,
+    },
+}
+
+/// A persisted type whose JSON shape is versioned.
+pub trait Versioned: DeserializeOwned {
+    /// Name used in error messages, e.g. "workflow definition".
+    const KIND: &'static str;
+
+    /// The version this build writes.
+    const SCHEMA_VERSION: u32;
+
+    /// Rewrite `value` from schema version `from` to `from + 1`.
+    ///
+    /// Called once per version step, so each migration only needs to know
+    /// about its neighbour.
+    fn migrate(from: u32, value: &mut Value) -> Result<(), String>;
+}
+
+/// Deserialize a versioned JSON string, migrating older payloads forward.
+pub fn from_versioned_str<T: Versioned>(json: &str) -> Result<T, SchemaError> {
+    let value = serde_json::from_str(json).map_err(|source| SchemaError::Json {
+        kind: T::KIND,
+        source,
+    })?;
+    from_versioned_value(value)
+}
+
+/// Deserialize a versioned JSON value, migrating older payloads forward.
+pub fn from_versioned_value<T: Versioned>(value: Value) -> Result<T, SchemaError> {
+    let version = payload_version::<T>(&value)?;
+    migrate_and_parse(value, version)
+}
+
+/// Like [`from_versioned_str`], for payloads whose version is stored
+/// alongside them rather than inside.
+pub fn from_str_at_version<T: Versioned>(json: &str, version: u32) -> Result<T, SchemaError> {
+    let value = serde_json::from_str(json).map_err(|source| SchemaError::Json {
+        kind: T::KIND,
+        source,
+    })?;
+    migrate_and_parse(value, version)
+}
+
+/// The `schema_version` recorded in `value`, or version 1 when absent.
+fn payload_version<T: Versioned>(value: &Value) -> Result<u32, SchemaError> {
+    match value.get(SCHEMA_VERSION_FIELD) {
+        None | Some(Value::Null) => Ok(UNVERSIONED_SCHEMA_VERSION),
+        Some(v) => v
+            .as_u64()
+            .and_then(|n| u32::try_from(n).ok())
+            .filter(|n| *n >= UNVERSIONED_SCHEMA_VERSION)
+            .ok_or_else(|| SchemaError::InvalidVersion {
+                kind: T::KIND,
+                value: v.to_string(),
+            }),
+    }
+}
+
+fn migrate_and_parse<T: Versioned>(mut value: Value, version: u32) -> Result<T, SchemaError> {
+    if version > T::SCHEMA_VERSION {
+        return Err(SchemaError::UnsupportedVersion {
+            kind: T::KIND,
+            found: version,
+            supported: T::SCHEMA_VERSION,
+        });
+    }
+
+    for from in version..T::SCHEMA_VERSION {
+        T::migrate(from, &mut value).map_err(|reason| SchemaError::Migration {
+            kind: T::KIND,
+            from,
+            reason,
+        })?;
+    }
+    if let Some(object) = value.as_object_mut() {
+        object.insert(SCHEMA_VERSION_FIELD.to_string(), T::SCHEMA_VERSION.into());
+    }
+
+    serde_json::from_value(value).map_err(|source| SchemaError::Json {
+        kind: T::KIND,
+        source,
+    })
+}
@@ -0,0 +1,57 @@
+//! Global search result types.
+//!
+//! `bnity search` queries several sources (bot descriptions, session titles,
+//! chat messages, and vector memories) and merges the hits into one ranked
+//! list. Scores are normalized to `0.0..=1.0` so hits from different sources
+//! can be compared.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use std::fmt;
+
+/// Where a search hit came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchSource {
+    Bot,
+    Session,
+    Message,
+    Memory,
+}
+
+impl fmt::Display for SearchSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SearchSource::Bot => write!(f, "bot"),
+            SearchSource::Session => write!(f, "session"),
+            SearchSource::Message => write!(f, "message"),
+            SearchSource::Memory => write!(f, "memory"),
+        }
+    }
+}
+
+/// A single result from a global search.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchHit {
+    pub source: SearchSource,
+    pub bot_id: Uuid,
+    pub bot_slug: String,
+    /// Session the hit belongs to (session titles and messages only).
+    pub session_id: Option<Uuid>,
+    /// Matching text or a snippet around the match.
+    pub text: String,
+    /// Normalized relevance, higher is better (0.0 - 1.0).
+    pub score: f32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_source_serializes_lowercase() {
+        assert_eq!(serde_json::to_string(&SearchSource::Memory).unwrap(), "\"memory\"");
+        assert_eq!(SearchSource::Session.to_string(), "session");
+    }
+}
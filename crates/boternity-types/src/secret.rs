@@ -52,6 +52,16 @@ pub enum SecretProvider {
     Environment,
 }
 
+impl SecretProvider {
+    /// Default resolution precedence: env vars override the keychain, which
+    /// overrides the vault.
+    pub const DEFAULT_ORDER: [SecretProvider; 3] = [
+        SecretProvider::Environment,
+        SecretProvider::Keychain,
+        SecretProvider::Vault,
+    ];
+}
+
 impl fmt::Display for SecretProvider {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -81,6 +91,30 @@ impl fmt::Display for SecretScope {
     }
 }
 
+/// Where a resolved secret value came from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SecretSource {
+    /// The provider that answered the lookup.
+    pub provider: SecretProvider,
+    /// The scope the value was found in (global when a bot scope inherited it).
+    pub scope: SecretScope,
+}
+
+/// How a bot-scoped lookup treats keys missing from the bot's own scope.
+///
+/// Scopes form a two-level hierarchy: a bot inherits every global secret it
+/// does not override. `Strict` limits a lookup to the requested scope, e.g.
+/// to check whether a bot really has its own key.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScopeResolution {
+    /// Fall back to the global scope when the bot scope has no value.
+    #[default]
+    Inherit,
+    /// Only consult the requested scope.
+    Strict,
+}
+
 /// A wrapper that redacts secret values in Debug and Display output.
 ///
 /// Use this to wrap any `String` that might contain sensitive data.
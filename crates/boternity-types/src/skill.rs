@@ -67,6 +67,8 @@ pub enum Capability {
     ReadEnv,
     RecallMemory,
     GetSecret,
+    KvRead,
+    KvWrite,
 }
 
 // ---------------------------------------------------------------------------
@@ -118,6 +120,13 @@ pub struct SkillMetadata {
     pub secrets: Option<Vec<String>>,
     #[serde(default)]
     pub categories: Option<Vec<String>>,
+    /// Output depends only on input, so results may be cached.
+    #[serde(default)]
+    pub cacheable: Option<bool>,
+    /// Version of the boternity manifest schema (`major.minor`) the skill
+    /// was written against. Absent means 1.0.
+    #[serde(default, rename = "manifest-version")]
+    pub manifest_version: Option<String>,
 }
 
 // ---------------------------------------------------------------------------
@@ -43,8 +43,10 @@ impl FromStr for SoulId {
 
 /// A versioned soul (SOUL.md content) for a bot.
 ///
-/// Each soul version is immutable once created. The hash is SHA-256 of the
-/// content, used for integrity verification at bot startup.
+/// Each soul version is immutable once created. The hash (SHA-256 or BLAKE3)
+/// of the content is used for integrity verification at bot startup. `prev_hash`
+/// links each version to the one before it, chaining the whole history so a
+/// removed or altered intermediate version is detectable.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Soul {
     pub id: SoulId,
@@ -52,20 +54,25 @@ pub struct Soul {
     pub bot_id: BotId,
     /// Full SOUL.md content (YAML frontmatter + markdown body).
     pub content: String,
-    /// SHA-256 hex digest of the content.
+    /// Hex digest of the content (SHA-256, or `<algorithm>:<hex>` for others).
     pub hash: String,
     /// Monotonically increasing version number per bot.
     pub version: i32,
     /// Optional commit message describing what changed in this version.
     pub message: Option<String>,
     pub created_at: DateTime<Utc>,
+    /// Chain hash of the previous version (`None` for version 1 and for
+    /// versions saved before history chaining existed).
+    #[serde(default)]
+    pub prev_hash: Option<String>,
 }
 
 /// Result of a soul integrity verification check.
 ///
-/// Compares the SHA-256 hash of the SOUL.md file on disk against the stored
+/// Compares the hash of the SOUL.md file on disk against the stored
 /// hash in the database. A mismatch indicates the file was modified outside
-/// of the Boternity update flow (potential tampering).
+/// of the Boternity update flow (potential tampering). The stored version
+/// history is checked too: `valid` is false if either check fails.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SoulIntegrityResult {
     /// Whether the file hash matches the stored hash.
@@ -76,6 +83,36 @@ pub struct SoulIntegrityResult {
     pub actual_hash: String,
     /// The current version number.
     pub version: i32,
+    /// First break found in the version history chain, if any.
+    #[serde(default)]
+    pub history_break: Option<SoulChainBreak>,
+}
+
+/// Where and how a soul's version history chain is broken.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SoulChainBreak {
+    /// A version's content no longer matches its stored hash.
+    ContentMismatch { version: i32 },
+    /// A version does not link to the version before it (an intermediate
+    /// version was altered or replaced).
+    BrokenLink { version: i32 },
+    /// A version number is missing from the history (deleted version).
+    MissingVersion { version: i32 },
+}
+
+impl fmt::Display for SoulChainBreak {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ContentMismatch { version } => {
+                write!(f, "version {version} content does not match its hash")
+            }
+            Self::BrokenLink { version } => {
+                write!(f, "version {version} does not link to the previous version")
+            }
+            Self::MissingVersion { version } => write!(f, "version {version} is missing"),
+        }
+    }
 }
 
 /// Structured data parsed from the YAML frontmatter of SOUL.md.
@@ -103,13 +140,16 @@ pub struct SoulFrontmatter {
 pub struct SoulVersion {
     /// Version number (1-based, monotonically increasing per bot).
     pub version: i32,
-    /// SHA-256 hex digest of the content.
+    /// Hex digest of the content (see [`Soul::hash`]).
     pub hash: String,
     /// Full content snapshot for this version.
     pub content: String,
     pub created_at: DateTime<Utc>,
     /// Optional commit message describing what changed.
     pub message: Option<String>,
+    /// Chain hash of the previous version (see [`Soul::prev_hash`]).
+    #[serde(default)]
+    pub prev_hash: Option<String>,
 }
 
 #[cfg(test)]
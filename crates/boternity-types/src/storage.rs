@@ -32,6 +32,11 @@ pub struct FileVersion {
     pub file_id: Uuid,
     pub version: u32,
     pub size_bytes: u64,
+    /// SHA-256 hex digest of the version's content (content-addressed blob key).
+    ///
+    /// `None` for versions stored before content addressing was introduced.
+    #[serde(default)]
+    pub content_hash: Option<String>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -47,6 +52,14 @@ pub struct FileChunk {
     pub embedding_model: String,
 }
 
+/// A file chunk returned from semantic search, with its similarity score.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileChunkMatch {
+    pub chunk: FileChunk,
+    /// Cosine similarity to the query (1.0 = identical direction).
+    pub score: f32,
+}
+
 /// A key-value entry in a bot's persistent KV store.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KvEntry {
@@ -55,6 +68,9 @@ pub struct KvEntry {
     pub value: serde_json::Value,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// When the entry expires. `None` means it never expires.
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
 }
 
 #[cfg(test)]
@@ -92,6 +108,7 @@ mod tests {
             value: serde_json::json!("dark"),
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            expires_at: None,
         };
         let json = serde_json::to_string(&entry).unwrap();
         assert!(json.contains("\"key\":\"theme\""));
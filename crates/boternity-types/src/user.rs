@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+
+/// Structured user profile from the USER.md frontmatter.
+///
+/// All fields are optional; the free-text briefing below the frontmatter
+/// is kept separately.
+/// Format:
+/// ```yaml
+/// ---
+/// name: Sam
+/// pronouns: they/them
+/// timezone: Europe/Berlin
+/// preferences:
+///   - concise answers
+///   - metric units
+/// ---
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UserProfile {
+    /// How the user wants to be addressed.
+    pub name: Option<String>,
+    /// The user's pronouns.
+    pub pronouns: Option<String>,
+    /// IANA timezone name (e.g., `America/New_York`).
+    pub timezone: Option<String>,
+    /// Short preference statements.
+    #[serde(default)]
+    pub preferences: Vec<String>,
+}
+
+impl UserProfile {
+    /// Whether no field is set.
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none()
+            && self.pronouns.is_none()
+            && self.timezone.is_none()
+            && self.preferences.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_user_profile_is_empty() {
+        assert!(UserProfile::default().is_empty());
+        let profile = UserProfile {
+            timezone: Some("UTC".to_string()),
+            ..Default::default()
+        };
+        assert!(!profile.is_empty());
+    }
+}
@@ -6,11 +6,16 @@
 //! tracking types (`WorkflowRun`, `WorkflowStepLog`) and trigger configuration.
 
 use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::message::{MessagePriority, MessageRecipient};
+use crate::schema::{Versioned, WORKFLOW_DEFINITION_SCHEMA_VERSION};
+
 // ---------------------------------------------------------------------------
 // Workflow Definition (canonical IR)
 // ---------------------------------------------------------------------------
@@ -46,6 +51,28 @@ pub struct WorkflowDefinition {
     /// Extensible metadata (for future use / custom integrations).
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub metadata: HashMap<String, serde_json::Value>,
+    /// Shape version of the stored JSON; see [`crate::schema`].
+    ///
+    /// Hand-written YAML may omit it and gets the current version.
+    #[serde(default = "current_definition_schema")]
+    pub schema_version: u32,
+}
+
+fn current_definition_schema() -> u32 {
+    WORKFLOW_DEFINITION_SCHEMA_VERSION
+}
+
+impl Versioned for WorkflowDefinition {
+    const KIND: &'static str = "workflow definition";
+    const SCHEMA_VERSION: u32 = WORKFLOW_DEFINITION_SCHEMA_VERSION;
+
+    fn migrate(from: u32, _value: &mut serde_json::Value) -> Result<(), String> {
+        match from {
+            // Version 2 only introduced the `schema_version` tag itself.
+            1 => Ok(()),
+            _ => Err(format!("no migration defined from version {from}")),
+        }
+    }
 }
 
 /// Who owns a workflow.
@@ -103,6 +130,62 @@ pub enum StepType {
     Loop,
     Approval,
     SubWorkflow,
+    Message,
+}
+
+impl fmt::Display for StepType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            StepType::Agent => "agent",
+            StepType::Skill => "skill",
+            StepType::Code => "code",
+            StepType::Http => "http",
+            StepType::Conditional => "conditional",
+            StepType::Loop => "loop",
+            StepType::Approval => "approval",
+            StepType::SubWorkflow => "sub_workflow",
+            StepType::Message => "message",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for StepType {
+    type Err = String;
+
+    /// Parse the exact [`Display`](fmt::Display) form (the YAML spelling);
+    /// anything else is an error.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        StepType::ALL
+            .into_iter()
+            .find(|step_type| step_type.to_string() == s)
+            .ok_or_else(|| {
+                format!(
+                    "invalid step type: '{s}' (expected agent, skill, code, http, conditional, loop, approval, sub_workflow, or message)"
+                )
+            })
+    }
+}
+
+impl StepType {
+    /// Every step type, in declaration order.
+    pub const ALL: [StepType; 9] = [
+        StepType::Agent,
+        StepType::Skill,
+        StepType::Code,
+        StepType::Http,
+        StepType::Conditional,
+        StepType::Loop,
+        StepType::Approval,
+        StepType::SubWorkflow,
+        StepType::Message,
+    ];
+
+    /// Parse user input, ignoring case and surrounding whitespace and
+    /// accepting `-` for `_` (e.g. `Sub-Workflow`).
+    pub fn parse_lenient(s: &str) -> Result<Self, String> {
+        s.trim().to_lowercase().replace('-', "_").parse()
+    }
 }
 
 /// Step-specific configuration payload.
@@ -169,6 +252,22 @@ pub enum StepConfig {
         #[serde(default, skip_serializing_if = "Option::is_none")]
         input: Option<serde_json::Value>,
     },
+    /// Send a bot-to-bot message on the message bus.
+    ///
+    /// String values in `body` (and a channel name) are template-resolved.
+    /// Without `sender_bot_id` the message is sent on the workflow's behalf.
+    Message {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        sender_bot_id: Option<Uuid>,
+        recipient: MessageRecipient,
+        message_type: String,
+        #[serde(default)]
+        body: serde_json::Value,
+        #[serde(default)]
+        priority: MessagePriority,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        ttl_secs: Option<u64>,
+    },
 }
 
 /// Language for inline Code steps.
@@ -235,6 +334,11 @@ pub enum TriggerConfig {
         /// Optional JEXL expression to filter trigger payloads.
         #[serde(default, skip_serializing_if = "Option::is_none")]
         when: Option<String>,
+        /// Treat repeat deliveries with the same idempotency key (the
+        /// `Idempotency-Key` header, or a hash of the body) within this many
+        /// seconds as duplicates of the first run.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        dedup_window_secs: Option<u64>,
     },
     /// Internal event bus trigger.
     Event {
@@ -362,6 +466,9 @@ pub struct WorkflowRun {
     /// Key for concurrency limiting (matches `WorkflowDefinition.name` by default).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub concurrency_key: Option<String>,
+    /// Trigger-provided key used to deduplicate repeat deliveries.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idempotency_key: Option<String>,
 }
 
 /// Execution log for a single step within a workflow run.
@@ -534,6 +641,7 @@ impl WorkflowDefinitionBuilder {
             triggers: self.triggers,
             steps: self.steps,
             metadata: self.metadata,
+            schema_version: WORKFLOW_DEFINITION_SCHEMA_VERSION,
         }
     }
 }
@@ -678,6 +786,7 @@ mod tests {
                         secret_name: "DIGEST_WEBHOOK_SECRET".to_string(),
                     }),
                     when: Some("event.source == 'github'".to_string()),
+                    dedup_window_secs: Some(300),
                 },
                 TriggerConfig::Event {
                     source: "internal".to_string(),
@@ -823,6 +932,7 @@ mod tests {
                 },
             ],
             metadata: HashMap::from([("created_by".to_string(), json!("builder"))]),
+            schema_version: WORKFLOW_DEFINITION_SCHEMA_VERSION,
         }
     }
 
@@ -830,6 +940,66 @@ mod tests {
     // YAML roundtrip
     // -----------------------------------------------------------------------
 
+    #[test]
+    fn test_step_type_display_roundtrip() {
+        for step_type in StepType::ALL {
+            let s = step_type.to_string();
+            assert_eq!(s.parse::<StepType>(), Ok(step_type));
+            // Display matches the YAML/serde spelling
+            assert_eq!(serde_json::to_value(step_type).unwrap(), s.as_str());
+        }
+        for bad in ["subworkflow", "sub-workflow", "Agent", ""] {
+            assert!(bad.parse::<StepType>().is_err(), "{bad:?} should not parse");
+        }
+        assert_eq!(
+            StepType::parse_lenient("Sub-Workflow"),
+            Ok(StepType::SubWorkflow)
+        );
+    }
+
+    #[test]
+    fn test_message_step_yaml() {
+        let yaml = r#"
+id: announce
+name: Announce Digest
+type: message
+config:
+  type: message
+  recipient:
+    type: channel
+    name: digests
+  message_type: digest_ready
+  body:
+    summary: "{{ steps.transform.output }}"
+  priority: high
+"#;
+        let step: StepDefinition = serde_yaml_ng::from_str(yaml).unwrap();
+        assert_eq!(step.step_type, StepType::Message);
+        match step.config {
+            StepConfig::Message {
+                sender_bot_id,
+                recipient,
+                message_type,
+                body,
+                priority,
+                ttl_secs,
+            } => {
+                assert!(sender_bot_id.is_none());
+                assert_eq!(
+                    recipient,
+                    MessageRecipient::Channel {
+                        name: "digests".to_string()
+                    }
+                );
+                assert_eq!(message_type, "digest_ready");
+                assert_eq!(body["summary"], "{{ steps.transform.output }}");
+                assert_eq!(priority, MessagePriority::High);
+                assert!(ttl_secs.is_none());
+            }
+            other => panic!("expected message config, got {other:?}"),
+        }
+    }
+
     #[test]
     fn test_workflow_definition_yaml_roundtrip() {
         let original = sample_workflow();
@@ -1005,12 +1175,20 @@ mod tests {
                 secret_name: "MY_TOKEN".to_string(),
             }),
             when: Some("event.type == 'push'".to_string()),
+            dedup_window_secs: Some(60),
         };
         let json = serde_json::to_string(&trigger).unwrap();
         assert!(json.contains("\"type\":\"webhook\""));
         assert!(json.contains("\"type\":\"bearer_token\""));
+        assert!(json.contains("\"dedup_window_secs\":60"));
         let parsed: TriggerConfig = serde_json::from_str(&json).unwrap();
-        assert!(matches!(parsed, TriggerConfig::Webhook { .. }));
+        assert!(matches!(
+            parsed,
+            TriggerConfig::Webhook {
+                dedup_window_secs: Some(60),
+                ..
+            }
+        ));
     }
 
     #[test]
@@ -1120,6 +1298,7 @@ mod tests {
             completed_at: None,
             error: None,
             concurrency_key: Some("daily-digest".to_string()),
+            idempotency_key: None,
         };
         let json_str = serde_json::to_string(&run).unwrap();
         let parsed: WorkflowRun = serde_json::from_str(&json_str).unwrap();
@@ -0,0 +1,8 @@
+-- Boternity: content-addressed file storage
+-- Each file version references a SHA-256 content hash. Identical content
+-- uploaded more than once shares a single blob on disk.
+
+-- NULL for versions written before content addressing (legacy on-disk layout)
+ALTER TABLE bot_file_versions ADD COLUMN content_hash TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_file_versions_content_hash ON bot_file_versions(content_hash);
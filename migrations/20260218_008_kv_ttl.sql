@@ -0,0 +1,6 @@
+-- Boternity: optional TTL expiry for bot KV entries
+-- NULL expires_at means the entry never expires. Expired rows are purged lazily on read.
+
+ALTER TABLE bot_kv_store ADD COLUMN expires_at TEXT;  -- ISO 8601 (UTC, microsecond precision)
+
+CREATE INDEX IF NOT EXISTS idx_kv_store_expires_at ON bot_kv_store(expires_at);
@@ -0,0 +1,4 @@
+-- Boternity: persisted per-session spend for the chat cost budget guard
+-- Estimated USD cost accumulated across all turns; resumed sessions keep their spend.
+
+ALTER TABLE chat_sessions ADD COLUMN total_cost_usd REAL NOT NULL DEFAULT 0;
@@ -0,0 +1,12 @@
+-- Boternity: append-only history of bot lifecycle status changes
+
+CREATE TABLE IF NOT EXISTS bot_status_history (
+    id TEXT PRIMARY KEY NOT NULL,
+    bot_id TEXT NOT NULL REFERENCES bots(id) ON DELETE CASCADE,
+    from_status TEXT NOT NULL,
+    to_status TEXT NOT NULL,
+    reason TEXT,
+    created_at TEXT NOT NULL  -- ISO 8601 (UTC)
+);
+
+CREATE INDEX IF NOT EXISTS idx_bot_status_history_bot_id ON bot_status_history(bot_id, created_at);
@@ -0,0 +1,44 @@
+-- Boternity: FTS5 full-text indexes over chat messages and session titles
+-- External-content tables kept in sync by triggers; used by `bnity search`.
+
+CREATE VIRTUAL TABLE IF NOT EXISTS chat_messages_fts USING fts5(
+    content,
+    content='chat_messages',
+    content_rowid='rowid'
+);
+
+CREATE TRIGGER IF NOT EXISTS chat_messages_fts_insert AFTER INSERT ON chat_messages BEGIN
+    INSERT INTO chat_messages_fts(rowid, content) VALUES (new.rowid, new.content);
+END;
+
+CREATE TRIGGER IF NOT EXISTS chat_messages_fts_delete AFTER DELETE ON chat_messages BEGIN
+    INSERT INTO chat_messages_fts(chat_messages_fts, rowid, content) VALUES ('delete', old.rowid, old.content);
+END;
+
+CREATE TRIGGER IF NOT EXISTS chat_messages_fts_update AFTER UPDATE OF content ON chat_messages BEGIN
+    INSERT INTO chat_messages_fts(chat_messages_fts, rowid, content) VALUES ('delete', old.rowid, old.content);
+    INSERT INTO chat_messages_fts(rowid, content) VALUES (new.rowid, new.content);
+END;
+
+CREATE VIRTUAL TABLE IF NOT EXISTS chat_sessions_fts USING fts5(
+    title,
+    content='chat_sessions',
+    content_rowid='rowid'
+);
+
+CREATE TRIGGER IF NOT EXISTS chat_sessions_fts_insert AFTER INSERT ON chat_sessions BEGIN
+    INSERT INTO chat_sessions_fts(rowid, title) VALUES (new.rowid, new.title);
+END;
+
+CREATE TRIGGER IF NOT EXISTS chat_sessions_fts_delete AFTER DELETE ON chat_sessions BEGIN
+    INSERT INTO chat_sessions_fts(chat_sessions_fts, rowid, title) VALUES ('delete', old.rowid, old.title);
+END;
+
+CREATE TRIGGER IF NOT EXISTS chat_sessions_fts_update AFTER UPDATE OF title ON chat_sessions BEGIN
+    INSERT INTO chat_sessions_fts(chat_sessions_fts, rowid, title) VALUES ('delete', old.rowid, old.title);
+    INSERT INTO chat_sessions_fts(rowid, title) VALUES (new.rowid, new.title);
+END;
+
+-- Index rows written before this migration
+INSERT INTO chat_messages_fts(chat_messages_fts) VALUES ('rebuild');
+INSERT INTO chat_sessions_fts(chat_sessions_fts) VALUES ('rebuild');
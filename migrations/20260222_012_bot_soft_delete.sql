@@ -0,0 +1,7 @@
+-- Boternity: recoverable bot deletion
+-- Soft-deleted bots keep all their data but are hidden from default listings
+-- until restored or purged.
+
+ALTER TABLE bots ADD COLUMN deleted_at TEXT;  -- ISO 8601 (UTC), NULL = not deleted
+
+CREATE INDEX IF NOT EXISTS idx_bots_deleted_at ON bots(deleted_at);
@@ -0,0 +1,6 @@
+-- Boternity: rolling conversation summary on the session record
+-- The summarizer condenses older turns into `summary`; it stands in for the
+-- first `summarized_messages` stored messages when a session is resumed.
+
+ALTER TABLE chat_sessions ADD COLUMN summary TEXT;
+ALTER TABLE chat_sessions ADD COLUMN summarized_messages INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,5 @@
+-- Boternity: delivery priority for bot-to-bot messages
+-- Mailboxes deliver higher priorities first; existing rows are 'normal'.
+
+ALTER TABLE bot_messages ADD COLUMN priority TEXT NOT NULL DEFAULT 'normal'
+    CHECK (priority IN ('low', 'normal', 'high', 'urgent'));
@@ -0,0 +1,4 @@
+-- Boternity: optional expiry for bot-to-bot messages
+-- Messages past `expires_at` are dead-lettered instead of delivered.
+
+ALTER TABLE bot_messages ADD COLUMN expires_at TEXT;
@@ -0,0 +1,8 @@
+-- Boternity: idempotency keys for workflow runs
+-- Duplicate trigger deliveries (e.g. webhook retries) resolve to the run
+-- already started with the same key instead of launching a new one.
+
+ALTER TABLE workflow_runs ADD COLUMN idempotency_key TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_workflow_runs_idempotency
+    ON workflow_runs(workflow_id, idempotency_key);
@@ -0,0 +1,6 @@
+-- Boternity: chained soul history
+-- Each soul version records the chain hash of the version before it, so a
+-- deleted or altered intermediate version breaks the chain. Versions saved
+-- before this migration have no link.
+
+ALTER TABLE soul_versions ADD COLUMN prev_hash TEXT;
@@ -0,0 +1,11 @@
+-- Boternity: per-bot LLM usage accounting for quotas
+-- One row per bot per quota window; requests and tokens are incremented
+-- after every chat turn and checked before the next completion request.
+
+CREATE TABLE IF NOT EXISTS bot_usage (
+    bot_id        TEXT NOT NULL REFERENCES bots(id) ON DELETE CASCADE,
+    window_start  TEXT NOT NULL,              -- ISO 8601, start of the quota window
+    requests      INTEGER NOT NULL DEFAULT 0,
+    tokens        INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (bot_id, window_start)
+);